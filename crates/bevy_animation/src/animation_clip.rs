@@ -0,0 +1,171 @@
+use bevy_core::Name;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::TypeUuid;
+use bevy_transform::prelude::Transform;
+use bevy_utils::HashMap;
+
+/// The names of the entities from the entity with the [AnimationPlayer] down to the entity a curve
+/// animates. The first name is looked for among all descendants of the player, and each next
+/// name among the children of the entity before it. An empty path animates the player's entity.
+///
+/// [AnimationPlayer]: crate::AnimationPlayer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EntityPath {
+    pub parts: Vec<Name>,
+}
+
+impl<T: Into<Name>> From<Vec<T>> for EntityPath {
+    fn from(parts: Vec<T>) -> Self {
+        EntityPath {
+            parts: parts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The values a [VariableCurve] sets at each of its keyframes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Keyframes {
+    Translation(Vec<Vec3>),
+    Rotation(Vec<Quat>),
+    Scale(Vec<Vec3>),
+}
+
+impl Keyframes {
+    pub fn len(&self) -> usize {
+        match self {
+            Keyframes::Translation(values) => values.len(),
+            Keyframes::Rotation(values) => values.len(),
+            Keyframes::Scale(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// How the values of a [VariableCurve] change between two keyframes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// The value of a keyframe is kept until the next keyframe
+    Step,
+    /// The values are blended linearly, and rotations along the shortest arc
+    Linear,
+}
+
+impl Default for Interpolation {
+    fn default() -> Self {
+        Interpolation::Linear
+    }
+}
+
+/// Keyframes of a part of a [Transform], at increasing timestamps in seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableCurve {
+    pub keyframe_timestamps: Vec<f32>,
+    pub keyframes: Keyframes,
+    pub interpolation: Interpolation,
+}
+
+impl VariableCurve {
+    /// Sets the part of `transform` the curve animates to its value at `time`. Before the first
+    /// keyframe and after the last one, the value of that keyframe is kept.
+    pub fn sample(&self, time: f32, transform: &mut Transform) {
+        let count = self.keyframe_timestamps.len().min(self.keyframes.len());
+        if count == 0 {
+            return;
+        }
+        let timestamps = &self.keyframe_timestamps[..count];
+        let (from, to, blend) = match timestamps.iter().position(|timestamp| *timestamp > time) {
+            Some(0) => (0, 0, 0.0),
+            Some(next) => {
+                let previous = next - 1;
+                let step = timestamps[next] - timestamps[previous];
+                let blend = match self.interpolation {
+                    Interpolation::Step => 0.0,
+                    Interpolation::Linear if step > 0.0 => (time - timestamps[previous]) / step,
+                    Interpolation::Linear => 0.0,
+                };
+                (previous, next, blend)
+            }
+            None => (count - 1, count - 1, 0.0),
+        };
+
+        match &self.keyframes {
+            Keyframes::Translation(values) => {
+                transform.translation = values[from].lerp(values[to], blend);
+            }
+            Keyframes::Rotation(values) => {
+                // quaternions on opposite sides of the sphere are the same rotation
+                let end = if values[from].dot(values[to]) < 0.0 {
+                    -values[to]
+                } else {
+                    values[to]
+                };
+                transform.rotation = values[from].slerp(end, blend).normalize();
+            }
+            Keyframes::Scale(values) => {
+                transform.scale = values[from].lerp(values[to], blend);
+            }
+        }
+    }
+
+    /// The timestamp of the last keyframe
+    pub fn duration(&self) -> f32 {
+        self.keyframe_timestamps.last().cloned().unwrap_or(0.0)
+    }
+}
+
+/// Curves animating the transforms of a hierarchy of entities, played by an [AnimationPlayer]
+///
+/// [AnimationPlayer]: crate::AnimationPlayer
+#[derive(Debug, Clone, Default, TypeUuid)]
+#[uuid = "9db86859-1f21-4486-8ecd-a9393f3b3cd7"]
+pub struct AnimationClip {
+    curves: HashMap<EntityPath, Vec<VariableCurve>>,
+    duration: f32,
+}
+
+impl AnimationClip {
+    /// Adds a curve animating the entity at `path`
+    pub fn add_curve_to_path(&mut self, path: EntityPath, curve: VariableCurve) {
+        self.duration = self.duration.max(curve.duration());
+        self.curves.entry(path).or_insert_with(Vec::new).push(curve);
+    }
+
+    pub fn curves(&self) -> &HashMap<EntityPath, Vec<VariableCurve>> {
+        &self.curves
+    }
+
+    /// The time in seconds the clip lasts, until the last keyframe of its curves
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interpolation, Keyframes, VariableCurve};
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn curves_blend_between_keyframes() {
+        let mut curve = VariableCurve {
+            keyframe_timestamps: vec![1.0, 3.0],
+            keyframes: Keyframes::Translation(vec![Vec3::zero(), Vec3::new(4.0, 0.0, 0.0)]),
+            interpolation: Interpolation::Linear,
+        };
+        let mut transform = Transform::default();
+        curve.sample(2.0, &mut transform);
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+        curve.sample(0.0, &mut transform);
+        assert_eq!(transform.translation, Vec3::zero());
+        curve.sample(5.0, &mut transform);
+        assert_eq!(transform.translation, Vec3::new(4.0, 0.0, 0.0));
+
+        curve.interpolation = Interpolation::Step;
+        curve.sample(2.5, &mut transform);
+        assert_eq!(transform.translation, Vec3::zero());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{AnimationClip, EntityPath};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{Name, Time};
+use bevy_ecs::{Entity, Query, Res};
+use bevy_transform::prelude::{Children, Transform};
+
+/// Plays an [AnimationClip] on the entity and its descendants
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    animation_clip: Handle<AnimationClip>,
+    elapsed: f32,
+    speed: f32,
+    paused: bool,
+    looping: bool,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        AnimationPlayer {
+            animation_clip: Default::default(),
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+            looping: false,
+        }
+    }
+}
+
+impl AnimationPlayer {
+    /// Starts playing `animation_clip` from its beginning
+    pub fn play(&mut self, animation_clip: Handle<AnimationClip>) -> &mut Self {
+        self.animation_clip = animation_clip;
+        self.elapsed = 0.0;
+        self.paused = false;
+        self
+    }
+
+    pub fn animation_clip(&self) -> &Handle<AnimationClip> {
+        &self.animation_clip
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Makes the clip start over once it ended, instead of stopping at its last keyframes
+    pub fn set_looping(&mut self, looping: bool) -> &mut Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets how fast the clip is played, with negative speeds playing it backwards
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Moves to `elapsed` seconds into the clip, which is also shown while the player is paused
+    pub fn seek_to(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// The time in seconds the clip was played for, since it started or was last seeked
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+}
+
+/// Advances [AnimationPlayer]s and sets the transforms of the entities their clips animate
+pub fn animation_player_system(
+    time: Res<Time>,
+    animation_clips: Res<Assets<AnimationClip>>,
+    mut player_query: Query<(Entity, &mut AnimationPlayer)>,
+    name_query: Query<&Name>,
+    children_query: Query<&Children>,
+    mut transform_query: Query<&mut Transform>,
+) {
+    for (entity, mut player) in player_query.iter_mut() {
+        let animation_clip = match animation_clips.get(&player.animation_clip) {
+            Some(animation_clip) => animation_clip,
+            None => continue,
+        };
+        if !player.paused {
+            player.elapsed += time.delta_seconds() * player.speed;
+        }
+        let duration = animation_clip.duration();
+        let mut elapsed = player.elapsed;
+        if player.looping && duration > 0.0 {
+            elapsed = elapsed.rem_euclid(duration);
+        }
+
+        for (path, curves) in animation_clip.curves().iter() {
+            let target = match find_entity(entity, path, &name_query, &children_query) {
+                Some(target) => target,
+                None => continue,
+            };
+            if let Ok(mut transform) = transform_query.get_mut(target) {
+                let mut animated = *transform;
+                for curve in curves.iter() {
+                    curve.sample(elapsed, &mut animated);
+                }
+                // transforms that didn't move aren't marked as changed
+                if *transform != animated {
+                    *transform = animated;
+                }
+            }
+        }
+    }
+}
+
+fn find_entity(
+    root: Entity,
+    path: &EntityPath,
+    name_query: &Query<&Name>,
+    children_query: &Query<&Children>,
+) -> Option<Entity> {
+    let (first, rest) = match path.parts.split_first() {
+        Some(parts) => parts,
+        None => return Some(root),
+    };
+    let has_name = |entity: Entity, name: &Name| {
+        name_query
+            .get(entity)
+            .map_or(false, |entity_name| entity_name == name)
+    };
+
+    // the first entity is searched breadth first, to find the closest one with its name
+    let mut current = None;
+    let mut queue = vec![root];
+    let mut index = 0;
+    while let Some(entity) = queue.get(index).cloned() {
+        index += 1;
+        if let Ok(children) = children_query.get(entity) {
+            if let Some(child) = children.iter().find(|child| has_name(**child, first)) {
+                current = Some(*child);
+                break;
+            }
+            queue.extend(children.iter().cloned());
+        }
+    }
+
+    let mut current = current?;
+    for part in rest.iter() {
+        let children = children_query.get(current).ok()?;
+        current = *children.iter().find(|child| has_name(**child, part))?;
+    }
+    Some(current)
+}
@@ -0,0 +1,24 @@
+mod animation_clip;
+mod animation_player;
+
+pub use animation_clip::*;
+pub use animation_player::*;
+
+pub mod prelude {
+    pub use crate::{AnimationClip, AnimationPlayer, EntityPath};
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AddAsset;
+
+/// Adds support for playing keyframe animations to an App
+#[derive(Default)]
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        // transforms are animated before they are propagated to the global transforms
+        app.add_asset::<AnimationClip>()
+            .add_system_to_stage(stage::UPDATE, animation_player_system);
+    }
+}
@@ -1,16 +1,25 @@
 use crate::{
-    path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    path::{get_hasher, AssetPath, AssetPathId, SourcePathId},
+    Asset, AssetEvent, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, Assets, FolderFilter, Handle, HandleId, HandleUntyped,
+    LabelId, LoadContext, LoadState, LoaderSettings, RefChange, RefChangeChannel, SourceInfo,
+    SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::Res;
 use bevy_tasks::TaskPool;
-use bevy_utils::{HashMap, Uuid};
+use bevy_utils::tracing::warn;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::{info_span, Instrument};
+use bevy_utils::{HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::RwLock;
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use std::{
+    collections::hash_map::Entry,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an AssetServer
@@ -26,6 +35,12 @@ pub enum AssetServerError {
     AssetLoaderError(anyhow::Error),
     #[error("PathLoader encountered an error")]
     PathLoaderError(#[from] AssetIoError),
+    #[error("The loaded source does not contain the requested asset {0:?}.")]
+    MissingAsset(AssetPath<'static>),
+    #[error("Failed to load the dependency {0:?}.")]
+    DependencyFailed(AssetPath<'static>),
+    #[error("The dependency {0:?} depends on the source that loads it.")]
+    DependencyCycle(AssetPath<'static>),
 }
 
 #[derive(Default)]
@@ -42,6 +57,12 @@ pub struct AssetServerInternal {
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    /// The asset types requested through [AssetServer::load], used to route load failures to the
+    /// right [Assets] collection. Entries are removed once their handle is freed.
+    handle_to_type: RwLock<HashMap<HandleId, Uuid>>,
+    /// Settings passed to [AssetServer::load_with_settings] whose load hasn't begun yet. Beginning
+    /// the load moves them into the source's [SourceInfo].
+    pub(crate) loader_settings: RwLock<HashMap<SourcePathId, Arc<LoaderSettings>>>,
     task_pool: TaskPool,
 }
 
@@ -67,6 +88,8 @@ impl AssetServer {
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
+                handle_to_type: Default::default(),
+                loader_settings: Default::default(),
                 asset_lifecycles: Default::default(),
                 task_pool,
                 asset_io: Box::new(source_io),
@@ -97,6 +120,12 @@ impl AssetServer {
         loaders.push(Arc::new(Box::new(loader)));
     }
 
+    /// Returns the [AssetIo] assets are read from. Downcast it to access backend specific state,
+    /// such as the download progress reported by `WasmAssetIo`.
+    pub fn asset_io(&self) -> &dyn AssetIo {
+        &*self.server.asset_io
+    }
+
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.server.asset_io.watch_for_changes()?;
         Ok(())
@@ -124,7 +153,7 @@ impl AssetServer {
             .ok_or_else(|| AssetServerError::MissingAssetLoader(Some(extension.to_string())))
     }
 
-    fn get_path_asset_loader<P: AsRef<Path>>(
+    pub(crate) fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
@@ -155,6 +184,29 @@ impl AssetServer {
         }
     }
 
+    /// Returns the type of the asset at `id` once its source finished loading, which is `None` if
+    /// the source doesn't contain the asset
+    pub(crate) fn get_asset_type(&self, id: AssetPathId) -> Option<Option<Uuid>> {
+        let asset_sources = self.server.asset_sources.read();
+        let source_info = asset_sources.get(&id.source_path_id())?;
+        source_info.meta.as_ref()?;
+        Some(source_info.asset_types.get(&id.label_id()).copied())
+    }
+
+    /// Returns the load state of `handle`, treating it as still loading until every asset it
+    /// depends on (recursively) has loaded
+    pub fn get_load_state_with_dependencies<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        match handle.into() {
+            HandleId::AssetPathId(id) => get_dependency_load_state(
+                &self.server.asset_sources.read(),
+                id.source_path_id(),
+                &mut HashSet::default(),
+                &mut None,
+            ),
+            HandleId::Id(_, _) => LoadState::NotLoaded,
+        }
+    }
+
     pub fn get_group_load_state(&self, handles: impl IntoIterator<Item = HandleId>) -> LoadState {
         let mut load_state = LoadState::Loaded;
         for handle_id in handles {
@@ -175,7 +227,40 @@ impl AssetServer {
     }
 
     pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
-        self.load_untyped(path).typed()
+        let asset_path: AssetPath = path.into();
+        self.server
+            .handle_to_type
+            .write()
+            .insert(asset_path.get_id().into(), T::TYPE_UUID);
+        self.load_untyped(asset_path).typed()
+    }
+
+    /// Loads `path` with loader specific `settings`, which the [AssetLoader] can read with
+    /// [LoadContext::settings]. The settings are part of the handle's identity, so loading the same
+    /// path with different settings produces separate assets.
+    pub fn load_with_settings<'a, T, P, S>(&self, path: P, settings: S) -> Handle<T>
+    where
+        T: Asset,
+        P: Into<AssetPath<'a>>,
+        S: Hash + Send + Sync + 'static,
+    {
+        let mut hasher = get_hasher();
+        std::any::type_name::<S>().hash(&mut hasher);
+        settings.hash(&mut hasher);
+        let settings_hash = hasher.finish();
+
+        let asset_path = path.into().with_settings_hash(Some(settings_hash));
+        self.server
+            .loader_settings
+            .write()
+            .entry(asset_path.get_id().source_path_id())
+            .or_insert_with(|| {
+                Arc::new(LoaderSettings {
+                    hash: settings_hash,
+                    value: Box::new(settings),
+                })
+            });
+        self.load(asset_path)
     }
 
     // TODO: properly set failed LoadState in all failure cases
@@ -188,59 +273,97 @@ impl AssetServer {
         let asset_loader = self.get_path_asset_loader(asset_path.path())?;
         let asset_path_id: AssetPathId = asset_path.get_id();
 
-        // load metadata and update source info. this is done in a scope to ensure we release the locks before loading
-        let version = {
-            let mut asset_sources = self.server.asset_sources.write();
-            let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
-                Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => entry.insert(SourceInfo {
-                    asset_types: Default::default(),
-                    committed_assets: Default::default(),
-                    load_state: LoadState::NotLoaded,
-                    meta: None,
-                    path: asset_path.path().to_owned(),
-                    version: 0,
-                }),
-            };
-
-            // if asset is already loaded (or is loading), don't load again
-            if !force
-                && source_info
-                    .committed_assets
-                    .contains(&asset_path_id.label_id())
-            {
-                return Ok(asset_path_id);
-            }
-
-            source_info.load_state = LoadState::Loading;
-            source_info.committed_assets.clear();
-            source_info.version += 1;
-            source_info.meta = None;
-            source_info.version
+        let version = match self.begin_load(&asset_path, force) {
+            Some(version) => version,
+            None => return Ok(asset_path_id),
         };
 
         // load the asset bytes
         let bytes = self.server.asset_io.load_path(asset_path.path()).await?;
 
         // load the asset source using the corresponding AssetLoader
+        let settings = self
+            .server
+            .asset_sources
+            .read()
+            .get(&asset_path_id.source_path_id())
+            .and_then(|source_info| source_info.settings.clone());
+        #[cfg(feature = "trace")]
+        let load_span = info_span!("asset_load", path = %asset_path.path().display());
         let mut load_context = LoadContext::new(
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
             &*self.server.asset_io,
+            self,
             version,
+            settings.as_deref(),
         );
-        asset_loader
-            .load(&bytes, &mut load_context)
-            .await
-            .map_err(AssetServerError::AssetLoaderError)?;
+        let load = asset_loader.load(&bytes, &mut load_context);
+        // the span follows the loader across the threads it is polled on
+        #[cfg(feature = "trace")]
+        let load = load.instrument(load_span);
+        load.await.map_err(AssetServerError::AssetLoaderError)?;
+
+        self.finish_load(&asset_path, &mut load_context);
+        Ok(asset_path_id)
+    }
+
+    /// Marks the source of `asset_path` as loading and returns the version of the new load, or
+    /// `None` if the asset is already loaded and the load isn't forced
+    pub(crate) fn begin_load(&self, asset_path: &AssetPath, force: bool) -> Option<usize> {
+        let asset_path_id = asset_path.get_id();
+        let mut asset_sources = self.server.asset_sources.write();
+        let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(SourceInfo {
+                asset_types: Default::default(),
+                committed_assets: Default::default(),
+                load_state: LoadState::NotLoaded,
+                meta: None,
+                path: asset_path.path().to_owned(),
+                version: 0,
+                settings: None,
+            }),
+        };
+
+        // the source keeps its settings, so reloads use them as well
+        if let Some(settings) = self
+            .server
+            .loader_settings
+            .write()
+            .remove(&asset_path_id.source_path_id())
+        {
+            source_info.settings = Some(settings);
+        }
+
+        // if asset is already loaded (or is loading), don't load again
+        if !force
+            && source_info
+                .committed_assets
+                .contains(&asset_path_id.label_id())
+        {
+            return None;
+        }
+
+        source_info.load_state = LoadState::Loading;
+        source_info.committed_assets.clear();
+        source_info.version += 1;
+        source_info.meta = None;
+        Some(source_info.version)
+    }
+
+    /// Records the assets loaded into `load_context`, loads their dependencies and sends them to
+    /// their [Assets]. Does nothing if a newer version of the source started loading since.
+    pub(crate) fn finish_load(&self, asset_path: &AssetPath, load_context: &mut LoadContext) {
+        let asset_path_id = asset_path.get_id();
 
         // if version has changed since we loaded and grabbed a lock, return. theres is a newer version being loaded
         let mut asset_sources = self.server.asset_sources.write();
         let source_info = asset_sources
             .get_mut(&asset_path_id.source_path_id())
             .expect("AssetSource should exist at this point");
-        if version != source_info.version {
-            return Ok(asset_path_id);
+        if load_context.version != source_info.version {
+            return;
         }
 
         // if all assets have been committed already (aka there were 0), set state to "Loaded"
@@ -257,7 +380,8 @@ impl AssetServer {
             assets: load_context.get_asset_metas(),
         });
 
-        // load asset dependencies and prepare asset type hashmap
+        // load asset dependencies and prepare asset type hashmap. sources loaded with
+        // LoadContext::load_dependency were already loaded
         for (label, loaded_asset) in load_context.labeled_assets.iter_mut() {
             let label_id = LabelId::from(label.as_ref().map(|label| label.as_str()));
             let type_uuid = loaded_asset.value.as_ref().unwrap().type_uuid();
@@ -271,8 +395,7 @@ impl AssetServer {
             .asset_io
             .watch_path_for_changes(asset_path.path())
             .unwrap();
-        self.create_assets_in_load_context(&mut load_context);
-        Ok(asset_path_id)
+        self.create_assets_in_load_context(load_context);
     }
 
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
@@ -291,38 +414,180 @@ impl AssetServer {
         self.server
             .task_pool
             .spawn(async move {
-                server.load_async(owned_path, force).await.unwrap();
+                if let Err(error) = server.load_async(owned_path.clone(), force).await {
+                    server.set_load_failed(&owned_path, error);
+                }
             })
             .detach();
         asset_path.into()
     }
 
+    /// Reloads the source at `path`, including every copy of it that was loaded with settings
+    pub(crate) fn reload_path(&self, path: &Path) {
+        let settings_hashes = self
+            .server
+            .asset_sources
+            .read()
+            .values()
+            .filter(|source_info| source_info.path == path)
+            .filter_map(|source_info| source_info.settings.as_ref())
+            .map(|settings| settings.hash)
+            .collect::<Vec<_>>();
+
+        self.load_untracked(path, true);
+        for settings_hash in settings_hashes {
+            let asset_path = AssetPath::from(path).with_settings_hash(Some(settings_hash));
+            self.load_untracked(asset_path, true);
+        }
+    }
+
+    pub(crate) fn set_load_failed(&self, asset_path: &AssetPath, error: AssetServerError) {
+        warn!("Failed to load {:?}: {:?}", asset_path, error);
+        let asset_path_id = asset_path.get_id();
+        {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .entry(asset_path_id.source_path_id())
+                .or_insert_with(|| SourceInfo {
+                    asset_types: Default::default(),
+                    committed_assets: Default::default(),
+                    load_state: LoadState::NotLoaded,
+                    meta: None,
+                    path: asset_path.path().to_owned(),
+                    version: 0,
+                    settings: None,
+                });
+            source_info.load_state = LoadState::Failed;
+            // loads can fail before they begin, which leaves their settings queued
+            self.server
+                .loader_settings
+                .write()
+                .remove(&asset_path_id.source_path_id());
+        }
+
+        // every typed handle into the source fails, and untyped loads of a default asset fail with
+        // the type of asset their loader produces
+        let source_path_id = asset_path_id.source_path_id();
+        let mut failed_handles = self
+            .server
+            .handle_to_type
+            .read()
+            .iter()
+            .filter(|(handle_id, _)| match handle_id {
+                HandleId::AssetPathId(id) => id.source_path_id() == source_path_id,
+                HandleId::Id(_, _) => false,
+            })
+            .map(|(handle_id, type_uuid)| (*handle_id, *type_uuid))
+            .collect::<Vec<_>>();
+        let handle_id = HandleId::AssetPathId(asset_path_id);
+        if asset_path.label().is_none() && !failed_handles.iter().any(|(id, _)| *id == handle_id) {
+            if let Some(type_uuid) = self
+                .get_path_asset_loader(asset_path.path())
+                .ok()
+                .and_then(|loader| loader.asset_type_uuid())
+            {
+                failed_handles.push((handle_id, type_uuid));
+            }
+        }
+
+        let error = Arc::new(error);
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        for (handle_id, type_uuid) in failed_handles {
+            if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
+                asset_lifecycle.fail_asset(handle_id, error.clone());
+            }
+        }
+    }
+
     pub fn load_folder<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
+        self.load_folder_filtered_untyped(path, &FolderFilter::default())
+    }
+
+    /// Loads every file in the folder at `path` that passes `filter` as an asset of type `T`. Files
+    /// whose [AssetLoader] doesn't report `T` as its [AssetLoader::asset_type_uuid] are skipped.
+    pub fn load_folder_filtered<T: Asset, P: AsRef<Path>>(
+        &self,
+        path: P,
+        filter: &FolderFilter,
+    ) -> Result<Vec<Handle<T>>, AssetServerError> {
+        Ok(self
+            .get_folder_paths(path.as_ref(), filter, Some(T::TYPE_UUID))?
+            .iter()
+            .map(|path| self.load(path.as_path()))
+            .collect())
+    }
+
+    /// Loads every file in the folder at `path` that passes `filter`
+    pub fn load_folder_filtered_untyped<P: AsRef<Path>>(
+        &self,
+        path: P,
+        filter: &FolderFilter,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        Ok(self
+            .get_folder_paths(path.as_ref(), filter, None)?
+            .iter()
+            .map(|path| self.load_untyped(path.as_path()))
+            .collect())
+    }
+
+    /// Returns the paths of the files in the folder at `path` that pass `filter` and have a loader,
+    /// which produces assets with the given type uuid if there is one
+    fn get_folder_paths(
+        &self,
+        path: &Path,
+        filter: &FolderFilter,
+        type_uuid: Option<Uuid>,
+    ) -> Result<Vec<PathBuf>, AssetServerError> {
         if !self.server.asset_io.is_directory(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let mut handles = Vec::new();
-        for child_path in self.server.asset_io.read_directory(path.as_ref())? {
+        let mut paths = Vec::new();
+        self.get_folder_paths_recursive(path, path, filter, type_uuid, &mut paths)?;
+        Ok(paths)
+    }
+
+    fn get_folder_paths_recursive(
+        &self,
+        root_path: &Path,
+        path: &Path,
+        filter: &FolderFilter,
+        type_uuid: Option<Uuid>,
+        paths: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetServerError> {
+        for child_path in self.server.asset_io.read_directory(path)? {
             if self.server.asset_io.is_directory(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+                if filter.recursive {
+                    self.get_folder_paths_recursive(
+                        root_path,
+                        &child_path,
+                        filter,
+                        type_uuid,
+                        paths,
+                    )?;
+                }
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                let asset_loader = match self.get_path_asset_loader(&child_path) {
+                    Ok(asset_loader) => asset_loader,
+                    Err(_) => continue,
+                };
+                if type_uuid.is_some() && asset_loader.asset_type_uuid() != type_uuid {
+                    continue;
+                }
+                let relative_path = child_path.strip_prefix(root_path).unwrap_or(&child_path);
+                if !filter.matches(relative_path) {
                     continue;
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string"));
-                handles.push(handle);
+                paths.push(child_path);
             }
         }
 
-        Ok(handles)
+        Ok(())
     }
 
     pub fn free_unused_assets(&self) {
@@ -350,9 +615,11 @@ impl AssetServer {
 
         if !potential_frees.is_empty() {
             let asset_lifecycles = self.server.asset_lifecycles.read();
+            let mut handle_to_type = self.server.handle_to_type.write();
             for potential_free in potential_frees {
                 if let Some(i) = ref_counts.get(&potential_free).cloned() {
                     if i == 0 {
+                        handle_to_type.remove(&potential_free);
                         let type_uuid = match potential_free {
                             HandleId::Id(type_uuid, _) => Some(type_uuid),
                             HandleId::AssetPathId(id) => asset_sources
@@ -380,7 +647,8 @@ impl AssetServer {
                 .expect("Asset should exist at this point");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
                 let asset_path =
-                    AssetPath::new_ref(&load_context.path, label.as_ref().map(|l| l.as_str()));
+                    AssetPath::new_ref(&load_context.path, label.as_ref().map(|l| l.as_str()))
+                        .with_settings_hash(load_context.settings.map(|settings| settings.hash));
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!("Failed to find AssetLifecycle for label {:?}, which has an asset type {:?}. Are you sure that is a registered asset type?", label, asset_value.type_uuid());
@@ -413,6 +681,9 @@ impl AssetServer {
                         }
                     }
 
+                    if let HandleId::AssetPathId(_) = result.id {
+                        assets.awaiting_dependencies.insert(result.id);
+                    }
                     assets.set(result.id, result.asset);
                 }
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
@@ -426,15 +697,105 @@ impl AssetServer {
                             }
                         }
                     }
+                    assets.awaiting_dependencies.remove(&handle_id);
                     assets.remove(handle_id);
                 }
+                Ok(AssetLifecycleEvent::Fail(handle_id, error)) => {
+                    assets.events.send(AssetEvent::Failed {
+                        handle: Handle::weak(handle_id),
+                        error,
+                    });
+                }
                 Err(TryRecvError::Empty) => {
                     break;
                 }
                 Err(TryRecvError::Disconnected) => panic!("AssetChannel disconnected"),
             }
         }
+        drop(asset_sources_guard);
+
+        if !assets.awaiting_dependencies.is_empty() {
+            let asset_sources = self.server.asset_sources.read();
+            let mut finished = Vec::new();
+            for handle_id in assets.awaiting_dependencies.iter() {
+                if let HandleId::AssetPathId(id) = handle_id {
+                    let mut failed_dependency = None;
+                    match get_dependency_load_state(
+                        &asset_sources,
+                        id.source_path_id(),
+                        &mut HashSet::default(),
+                        &mut failed_dependency,
+                    ) {
+                        LoadState::Loaded => finished.push((*handle_id, None)),
+                        LoadState::Failed => finished.push((*handle_id, Some(failed_dependency))),
+                        LoadState::Loading | LoadState::NotLoaded => {}
+                    }
+                }
+            }
+
+            // assets whose own source failed already got a failed event
+            for (handle_id, failed_dependency) in finished {
+                assets.awaiting_dependencies.remove(&handle_id);
+                match failed_dependency {
+                    None => assets.events.send(AssetEvent::LoadedWithDependencies {
+                        handle: Handle::weak(handle_id),
+                    }),
+                    Some(Some(dependency)) => assets.events.send(AssetEvent::Failed {
+                        handle: Handle::weak(handle_id),
+                        error: Arc::new(AssetServerError::DependencyFailed(dependency)),
+                    }),
+                    Some(None) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Walks the dependencies recorded in each source's [SourceMeta]. Sources that were already visited
+/// are treated as loaded, which keeps dependency cycles from recursing forever. When a dependency
+/// failed, its path is written to `failed_dependency`.
+fn get_dependency_load_state(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    source_path_id: SourcePathId,
+    visited: &mut HashSet<SourcePathId>,
+    failed_dependency: &mut Option<AssetPath<'static>>,
+) -> LoadState {
+    if !visited.insert(source_path_id) {
+        return LoadState::Loaded;
+    }
+
+    let source_info = match asset_sources.get(&source_path_id) {
+        Some(source_info) => source_info,
+        None => return LoadState::NotLoaded,
+    };
+    if source_info.load_state != LoadState::Loaded {
+        return source_info.load_state;
+    }
+
+    let mut load_state = LoadState::Loaded;
+    let dependencies = source_info
+        .meta
+        .iter()
+        .flat_map(|meta| meta.assets.iter())
+        .flat_map(|asset_meta| asset_meta.dependencies.iter());
+    for dependency in dependencies {
+        let dependency_source_path_id = dependency.get_id().source_path_id();
+        match get_dependency_load_state(
+            asset_sources,
+            dependency_source_path_id,
+            visited,
+            failed_dependency,
+        ) {
+            LoadState::Loaded => {}
+            LoadState::Failed => {
+                failed_dependency.get_or_insert_with(|| dependency.clone());
+                return LoadState::Failed;
+            }
+            state => load_state = state,
+        }
     }
+
+    load_state
 }
 
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
@@ -1,17 +1,36 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, Handle, HandleId, RefChange,
+    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetServerError, Handle,
+    HandleId, RefChange,
 };
 use bevy_app::{prelude::Events, AppBuilder};
 use bevy_ecs::{FromResources, ResMut};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 /// Events that happen on assets of type `T`
 pub enum AssetEvent<T: Asset> {
-    Created { handle: Handle<T> },
-    Modified { handle: Handle<T> },
-    Removed { handle: Handle<T> },
+    Created {
+        handle: Handle<T>,
+    },
+    Modified {
+        handle: Handle<T>,
+    },
+    Removed {
+        handle: Handle<T>,
+    },
+    /// Loading the asset, or an asset it depends on (recursively), failed. Sent for handles returned
+    /// by [AssetServer::load], and for untyped handles whose [AssetLoader] reports the asset type it
+    /// produces
+    Failed {
+        handle: Handle<T>,
+        error: Arc<AssetServerError>,
+    },
+    /// The asset was loaded by the [AssetServer] and every asset it depends on (recursively) has
+    /// finished loading too
+    LoadedWithDependencies {
+        handle: Handle<T>,
+    },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -38,6 +57,21 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id)
                 .finish(),
+            AssetEvent::Failed { handle, error } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::Failed",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id)
+                .field("error", error)
+                .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id)
+                .finish(),
         }
     }
 }
@@ -46,8 +80,10 @@ impl<T: Asset> Debug for AssetEvent<T> {
 #[derive(Debug)]
 pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
-    events: Events<AssetEvent<T>>,
+    pub(crate) events: Events<AssetEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    /// Loaded assets whose dependencies are still loading
+    pub(crate) awaiting_dependencies: HashSet<HandleId>,
 }
 
 impl<T: Asset> Assets<T> {
@@ -56,6 +92,7 @@ impl<T: Asset> Assets<T> {
             assets: HashMap::default(),
             events: Events::default(),
             ref_change_sender,
+            awaiting_dependencies: HashSet::default(),
         }
     }
 
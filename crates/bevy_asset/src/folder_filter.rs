@@ -0,0 +1,126 @@
+use std::path::Path;
+
+/// Selects which files [AssetServer::load_folder_filtered](crate::AssetServer::load_folder_filtered)
+/// loads from a folder. The default filter loads every file with a registered loader, including
+/// files in subfolders.
+#[derive(Debug, Clone)]
+pub struct FolderFilter {
+    pub recursive: bool,
+    /// File extensions (without the leading `.`) to load. Empty means any extension.
+    pub extensions: Vec<String>,
+    /// A glob pattern matched against file paths relative to the loaded folder, using `/` as the
+    /// separator. `*` matches within a single folder, `**` matches across folders and `?` matches
+    /// a single character.
+    pub pattern: Option<String>,
+}
+
+impl Default for FolderFilter {
+    fn default() -> Self {
+        FolderFilter {
+            recursive: true,
+            extensions: Vec::new(),
+            pattern: None,
+        }
+    }
+}
+
+impl FolderFilter {
+    /// Only load files directly inside the folder
+    pub fn non_recursive(mut self) -> Self {
+        self.recursive = false;
+        self
+    }
+
+    /// Only load files with the given extension. Can be called multiple times to allow several
+    /// extensions.
+    pub fn with_extension(mut self, extension: &str) -> Self {
+        self.extensions
+            .push(extension.trim_start_matches('.').to_string());
+        self
+    }
+
+    /// Only load files whose path relative to the folder matches the glob `pattern`
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Returns true if the file at `relative_path` (relative to the loaded folder) passes the filter
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        if !self.extensions.is_empty() {
+            let extension = relative_path.extension().and_then(|e| e.to_str());
+            if !self
+                .extensions
+                .iter()
+                .any(|allowed| Some(allowed.as_str()) == extension)
+            {
+                return false;
+            }
+        }
+
+        if let Some(pattern) = &self.pattern {
+            let path = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            return glob_match(pattern.as_bytes(), path.as_bytes());
+        }
+
+        true
+    }
+}
+
+fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((b'*', rest)) if rest.first() == Some(&b'*') => {
+            // "**/" also matches zero folders
+            let rest = &rest[1..];
+            if rest.first() == Some(&b'/') && glob_match(&rest[1..], path) {
+                return true;
+            }
+            (0..=path.len()).any(|skip| glob_match(rest, &path[skip..]))
+        }
+        Some((b'*', rest)) => (0..=path.len())
+            .take_while(|skip| *skip == 0 || path[skip - 1] != b'/')
+            .any(|skip| glob_match(rest, &path[skip..])),
+        Some((b'?', rest)) => match path.split_first() {
+            Some((character, path)) if *character != b'/' => glob_match(rest, path),
+            _ => false,
+        },
+        Some((character, rest)) => match path.split_first() {
+            Some((path_character, path)) if path_character == character => glob_match(rest, path),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_filter() {
+        let filter = FolderFilter::default()
+            .with_extension("png")
+            .with_extension(".jpg");
+        assert!(filter.matches(Path::new("ui/button.png")));
+        assert!(filter.matches(Path::new("photo.jpg")));
+        assert!(!filter.matches(Path::new("music.ogg")));
+        assert!(!filter.matches(Path::new("no_extension")));
+    }
+
+    #[test]
+    fn glob_filter() {
+        let filter = FolderFilter::default().with_pattern("ui/*.png");
+        assert!(filter.matches(Path::new("ui/button.png")));
+        assert!(!filter.matches(Path::new("ui/icons/close.png")));
+        assert!(!filter.matches(Path::new("button.png")));
+
+        let filter = FolderFilter::default().with_pattern("**/icon_?.png");
+        assert!(filter.matches(Path::new("icon_a.png")));
+        assert!(filter.matches(Path::new("ui/icons/icon_b.png")));
+        assert!(!filter.matches(Path::new("ui/icons/icon_ab.png")));
+    }
+}
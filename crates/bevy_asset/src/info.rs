@@ -1,7 +1,7 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{path::AssetPath, LabelId, LoaderSettings};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceMeta {
@@ -24,6 +24,8 @@ pub struct SourceInfo {
     pub load_state: LoadState,
     pub committed_assets: HashSet<LabelId>,
     pub version: usize,
+    /// The settings the source was loaded with, kept to reload it with the same settings
+    pub settings: Option<Arc<LoaderSettings>>,
 }
 
 impl SourceInfo {
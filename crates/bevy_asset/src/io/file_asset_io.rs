@@ -1,10 +1,13 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetRead, AssetServer,
+    AssetStream, MemoryAssetIo,
+};
 use anyhow::Result;
 use bevy_ecs::{bevy_utils::BoxedFuture, Res};
 use bevy_utils::HashSet;
 use crossbeam_channel::TryRecvError;
 use fs::File;
-use io::Read;
+use io::{BufReader, Read};
 use parking_lot::RwLock;
 use std::{
     env, fs, io,
@@ -41,27 +44,45 @@ impl FileAssetIo {
     }
 }
 
+fn open_file(full_path: &Path) -> Result<File, AssetIoError> {
+    File::open(full_path).map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            AssetIoError::NotFound(full_path.to_owned())
+        } else {
+            e.into()
+        }
+    })
+}
+
+/// Reopens the file for every reader, so that readers don't share a position
+struct FileStream(PathBuf);
+
+impl AssetStream for FileStream {
+    fn reader(&self) -> Result<Box<dyn AssetRead>, AssetIoError> {
+        Ok(Box::new(BufReader::new(open_file(&self.0)?)))
+    }
+}
+
 impl AssetIo for FileAssetIo {
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
         Box::pin(async move {
             let mut bytes = Vec::new();
-            let full_path = self.root_path.join(path);
-            match File::open(&full_path) {
-                Ok(mut file) => {
-                    file.read_to_end(&mut bytes)?;
-                }
-                Err(e) => {
-                    return if e.kind() == std::io::ErrorKind::NotFound {
-                        Err(AssetIoError::NotFound(full_path))
-                    } else {
-                        Err(e.into())
-                    }
-                }
-            }
+            open_file(&self.root_path.join(path))?.read_to_end(&mut bytes)?;
             Ok(bytes)
         })
     }
 
+    fn stream_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Arc<dyn AssetStream>, AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            open_file(&full_path)?;
+            Ok(Arc::new(FileStream(full_path)) as Arc<dyn AssetStream>)
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -102,6 +123,15 @@ impl AssetIo for FileAssetIo {
     fn is_directory(&self, path: &Path) -> bool {
         self.root_path.join(path).is_dir()
     }
+
+    fn save_path(&self, path: &Path, bytes: &[u8]) -> Result<(), AssetIoError> {
+        let full_path = self.root_path.join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(full_path, bytes)?;
+        Ok(())
+    }
 }
 
 #[cfg(all(
@@ -110,12 +140,18 @@ impl AssetIo for FileAssetIo {
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
+    let asset_io = &asset_server.server.asset_io;
+    let asset_io = if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        asset_io
+    } else if let Some(asset_io) = asset_io
+        .downcast_ref::<MemoryAssetIo>()
+        .and_then(|memory_asset_io| memory_asset_io.fallback())
+        .and_then(|fallback| fallback.downcast_ref::<FileAssetIo>())
+    {
+        asset_io
+    } else {
+        return;
+    };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -133,7 +169,7 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 for path in paths.iter() {
                     if !changed.contains(path) {
                         let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path, true);
+                        asset_server.reload_path(relative_path);
                     }
                 }
                 changed.extend(paths);
@@ -0,0 +1,216 @@
+use crate::{AssetIo, AssetIoError, AssetServer, AssetStream};
+use anyhow::Result;
+use bevy_ecs::{bevy_utils::BoxedFuture, Res};
+use bevy_utils::{HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+#[derive(Default)]
+struct MemoryAssetIoState {
+    files: HashMap<PathBuf, Vec<u8>>,
+    watch_for_changes: bool,
+    watched_paths: HashSet<PathBuf>,
+    changed_paths: Vec<PathBuf>,
+}
+
+/// An [AssetIo] backed by files held in memory, for assets created at runtime such as generated
+/// levels or downloaded content.
+///
+/// Clones share the same files, so a clone can be kept around to add files after the
+/// [AssetServer] was created. Replacing a file that was already loaded reloads it when
+/// [AssetServer::watch_for_changes] is enabled. Paths that aren't in memory are read from the
+/// optional fallback [AssetIo].
+///
+/// When a `MemoryAssetIo` resource is added before the `AssetPlugin`, it is layered on top of the
+/// platform's default [AssetIo].
+#[derive(Clone, Default)]
+pub struct MemoryAssetIo {
+    state: Arc<RwLock<MemoryAssetIoState>>,
+    fallback: Option<Arc<dyn AssetIo>>,
+}
+
+impl MemoryAssetIo {
+    /// Reads paths that aren't in memory from `fallback`
+    pub fn with_fallback<T: AssetIo>(mut self, fallback: T) -> Self {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    pub fn fallback(&self) -> Option<&dyn AssetIo> {
+        self.fallback.as_deref()
+    }
+
+    /// Adds or replaces the file at `path`
+    pub fn insert<P: AsRef<Path>>(&self, path: P, bytes: Vec<u8>) {
+        let path = normalize_path(path.as_ref());
+        let mut state = self.state.write();
+        if state.watch_for_changes && state.watched_paths.contains(&path) {
+            state.changed_paths.push(path.clone());
+        }
+        state.files.insert(path, bytes);
+    }
+
+    /// Removes the file at `path`, returning its contents
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Vec<u8>> {
+        self.state
+            .write()
+            .files
+            .remove(&normalize_path(path.as_ref()))
+    }
+
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.state
+            .read()
+            .files
+            .contains_key(&normalize_path(path.as_ref()))
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.state.read().files.get(&normalize_path(path)).cloned();
+            match (bytes, &self.fallback) {
+                (Some(bytes), _) => Ok(bytes),
+                (None, Some(fallback)) => fallback.load_path(path).await,
+                (None, None) => Err(AssetIoError::NotFound(path.to_owned())),
+            }
+        })
+    }
+
+    fn stream_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Arc<dyn AssetStream>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.state.read().files.get(&normalize_path(path)).cloned();
+            match (bytes, &self.fallback) {
+                (Some(bytes), _) => Ok(Arc::new(Arc::<[u8]>::from(bytes)) as Arc<dyn AssetStream>),
+                (None, Some(fallback)) => fallback.stream_path(path).await,
+                (None, None) => Err(AssetIoError::NotFound(path.to_owned())),
+            }
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let directory = normalize_path(path);
+        let mut children = HashSet::default();
+        for file_path in self.state.read().files.keys() {
+            if let Ok(relative_path) = file_path.strip_prefix(&directory) {
+                if let Some(child) = relative_path.components().next() {
+                    children.insert(directory.join(child));
+                }
+            }
+        }
+
+        if let Some(fallback) = &self.fallback {
+            if fallback.is_directory(path) {
+                children.extend(fallback.read_directory(path)?);
+            }
+        }
+
+        if children.is_empty() && !self.is_directory(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        let directory = normalize_path(path);
+        let in_memory = self
+            .state
+            .read()
+            .files
+            .keys()
+            .any(|file_path| file_path != &directory && file_path.starts_with(&directory));
+        in_memory
+            || self
+                .fallback
+                .as_ref()
+                .map_or(false, |fallback| fallback.is_directory(path))
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        let path = normalize_path(path);
+        let mut state = self.state.write();
+        if state.files.contains_key(&path) {
+            state.watched_paths.insert(path);
+            Ok(())
+        } else if let Some(fallback) = &self.fallback {
+            fallback.watch_path_for_changes(&path)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.state.write().watch_for_changes = true;
+        if let Some(fallback) = &self.fallback {
+            fallback.watch_for_changes()?;
+        }
+        Ok(())
+    }
+
+    fn save_path(&self, path: &Path, bytes: &[u8]) -> Result<(), AssetIoError> {
+        self.insert(path, bytes.to_vec());
+        Ok(())
+    }
+}
+
+/// Drops `.` components so `a/./b.png` and `a/b.png` refer to the same file
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+/// Reloads in-memory files that were replaced after being loaded
+pub fn memory_asset_io_system(asset_server: Res<AssetServer>) {
+    let asset_io =
+        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<MemoryAssetIo>() {
+            asset_io
+        } else {
+            return;
+        };
+    let changed_paths = std::mem::take(&mut asset_io.state.write().changed_paths);
+    for path in changed_paths {
+        asset_server.reload_path(&path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directories() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("levels/generated/0.ron", Vec::new());
+        asset_io.insert("levels/generated/1.ron", Vec::new());
+        asset_io.insert("levels/start.ron", Vec::new());
+
+        assert!(asset_io.is_directory(Path::new("levels")));
+        assert!(asset_io.is_directory(Path::new("levels/generated")));
+        assert!(!asset_io.is_directory(Path::new("levels/start.ron")));
+
+        let mut children = asset_io
+            .read_directory(Path::new("levels"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("levels/generated"),
+                PathBuf::from("levels/start.ron")
+            ]
+        );
+    }
+}
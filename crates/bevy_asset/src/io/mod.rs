@@ -2,6 +2,7 @@
 mod android_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+mod memory_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
@@ -9,6 +10,7 @@ mod wasm_asset_io;
 pub use android_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+pub use memory_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
@@ -16,8 +18,9 @@ use anyhow::Result;
 use bevy_ecs::bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
 use std::{
-    io,
+    io::{self, Cursor, Read, Seek},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use thiserror::Error;
 
@@ -30,6 +33,8 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("Failed to watch path")]
     PathWatchError(PathBuf),
+    #[error("The server answered with HTTP status {status}")]
+    HttpStatus { path: PathBuf, status: u16 },
 }
 
 /// Handles load requests from an AssetServer
@@ -42,6 +47,45 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn is_directory(&self, path: &Path) -> bool;
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+
+    /// Writes `bytes` to `path`, for example to cache processed assets. Read-only backends return
+    /// an error.
+    fn save_path(&self, _path: &Path, _bytes: &[u8]) -> Result<(), AssetIoError> {
+        Err(AssetIoError::Io(io::Error::new(
+            io::ErrorKind::Other,
+            "this AssetIo does not support writing assets",
+        )))
+    }
+
+    /// Prepares `path` to be read incrementally, for large assets like music that are decoded
+    /// while they are used instead of being loaded up front. Backends that can't read files
+    /// incrementally load the whole file.
+    fn stream_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Arc<dyn AssetStream>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes: Arc<[u8]> = self.load_path(path).await?.into();
+            Ok(Arc::new(bytes) as Arc<dyn AssetStream>)
+        })
+    }
+}
+
+/// A reader over the contents of an asset
+pub trait AssetRead: Read + Seek + Send + Sync {}
+
+impl<T: Read + Seek + Send + Sync> AssetRead for T {}
+
+/// Opens readers over the contents of an asset, see [AssetIo::stream_path]. Each reader starts at
+/// the beginning of the asset.
+pub trait AssetStream: Send + Sync {
+    fn reader(&self) -> Result<Box<dyn AssetRead>, AssetIoError>;
+}
+
+impl AssetStream for Arc<[u8]> {
+    fn reader(&self) -> Result<Box<dyn AssetRead>, AssetIoError> {
+        Ok(Box::new(Cursor::new(self.clone())))
+    }
 }
 
 impl_downcast!(AssetIo);
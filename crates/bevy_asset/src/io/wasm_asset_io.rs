@@ -1,35 +1,195 @@
 use crate::{AssetIo, AssetIoError};
 use anyhow::Result;
 use bevy_ecs::bevy_utils::BoxedFuture;
-use js_sys::Uint8Array;
-use std::path::{Path, PathBuf};
-use wasm_bindgen::JsCast;
-use wasm_bindgen_futures::JsFuture;
-use web_sys::Response;
+use bevy_utils::{tracing::warn, HashMap};
+use js_sys::{Function, Promise, Reflect, Uint8Array};
+use parking_lot::RwLock;
+use std::{
+    cell::RefCell,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode, Response};
 
+const CACHE_STORE: &str = "assets";
+
+thread_local! {
+    // IndexedDB databases can't be sent between threads, so they are kept by the thread that
+    // opened them, which is the only thread in the browser
+    static CACHE_DATABASES: RefCell<HashMap<String, Promise>> = RefCell::new(HashMap::default());
+}
+
+/// The download progress of an asset loaded by [WasmAssetIo]
+#[derive(Debug, Clone, Default)]
+pub struct LoadProgress {
+    pub loaded_bytes: u64,
+    /// The size reported by the server, if it sent a `Content-Length` header
+    pub total_bytes: Option<u64>,
+    pub content_type: Option<String>,
+}
+
+impl LoadProgress {
+    /// Returns the downloaded fraction between 0.0 and 1.0, if the total size is known
+    pub fn fraction(&self) -> Option<f32> {
+        self.total_bytes.map(|total_bytes| {
+            if total_bytes == 0 {
+                1.0
+            } else {
+                (self.loaded_bytes as f64 / total_bytes as f64).min(1.0) as f32
+            }
+        })
+    }
+}
+
+/// Loads assets with the browser's `fetch` API, streaming the response to track download progress.
+/// Assets can optionally be cached in IndexedDB so later visits don't download them again.
 pub struct WasmAssetIo {
     root_path: PathBuf,
+    cache_database: Option<String>,
+    progress: Arc<RwLock<HashMap<PathBuf, LoadProgress>>>,
 }
 
 impl WasmAssetIo {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         WasmAssetIo {
             root_path: path.as_ref().to_owned(),
+            cache_database: None,
+            progress: Default::default(),
         }
     }
+
+    /// Caches downloaded assets in the IndexedDB database called `database_name`, which is opened
+    /// by the first load. Use a new name (for example one that includes your app's version) to
+    /// invalidate the cache.
+    pub fn with_cache(mut self, database_name: &str) -> Self {
+        self.cache_database = Some(database_name.to_string());
+        self
+    }
+
+    /// Returns the download progress of the asset at `path` while it is being downloaded
+    pub fn get_progress<P: AsRef<Path>>(&self, path: P) -> Option<LoadProgress> {
+        self.progress.read().get(path.as_ref()).cloned()
+    }
+
+    async fn fetch(&self, path: &Path, url: &str) -> Result<Vec<u8>, AssetIoError> {
+        let window = web_sys::window().expect("fetch requires a window");
+        let response: Response = JsFuture::from(window.fetch_with_str(url))
+            .await
+            .and_then(|response| response.dyn_into())
+            .map_err(js_error)?;
+        match response.status() {
+            200..=299 => {}
+            404 => return Err(AssetIoError::NotFound(PathBuf::from(url))),
+            status => {
+                return Err(AssetIoError::HttpStatus {
+                    path: PathBuf::from(url),
+                    status,
+                })
+            }
+        }
+
+        let headers = response.headers();
+        let content_type = headers.get("content-type").ok().flatten();
+        let total_bytes = headers
+            .get("content-length")
+            .ok()
+            .flatten()
+            .and_then(|length| length.parse().ok());
+        self.set_progress(
+            path,
+            LoadProgress {
+                loaded_bytes: 0,
+                total_bytes,
+                content_type: content_type.clone(),
+            },
+        );
+
+        let bytes = match response.body() {
+            Some(body) => self.read_stream(path, &body).await?,
+            None => {
+                let buffer = response.array_buffer().map_err(js_error)?;
+                let data = JsFuture::from(buffer).await.map_err(js_error)?;
+                Uint8Array::new(&data).to_vec()
+            }
+        };
+
+        if is_html_fallback(path, content_type.as_deref(), &bytes) {
+            return Err(AssetIoError::NotFound(PathBuf::from(url)));
+        }
+
+        Ok(bytes)
+    }
+
+    async fn read_stream(
+        &self,
+        path: &Path,
+        body: &web_sys::ReadableStream,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let reader = body.get_reader();
+        let read: Function = Reflect::get(&reader, &JsValue::from_str("read"))
+            .and_then(|read| read.dyn_into())
+            .map_err(js_error)?;
+        let mut bytes = Vec::new();
+        loop {
+            let promise: Promise = read
+                .call0(&reader)
+                .and_then(|promise| promise.dyn_into())
+                .map_err(js_error)?;
+            let chunk = JsFuture::from(promise).await.map_err(js_error)?;
+            let done = Reflect::get(&chunk, &JsValue::from_str("done"))
+                .map_err(js_error)?
+                .as_bool()
+                .unwrap_or(true);
+            if done {
+                break;
+            }
+
+            let value = Reflect::get(&chunk, &JsValue::from_str("value")).map_err(js_error)?;
+            bytes.extend(Uint8Array::new(&value).to_vec());
+            if let Some(progress) = self.progress.write().get_mut(path) {
+                progress.loaded_bytes = bytes.len() as u64;
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    fn set_progress(&self, path: &Path, progress: LoadProgress) {
+        self.progress.write().insert(path.to_owned(), progress);
+    }
 }
 
 impl AssetIo for WasmAssetIo {
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
         Box::pin(async move {
-            let path = self.root_path.join(path);
-            let window = web_sys::window().unwrap();
-            let resp_value = JsFuture::from(window.fetch_with_str(path.to_str().unwrap()))
-                .await
-                .unwrap();
-            let resp: Response = resp_value.dyn_into().unwrap();
-            let data = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
-            let bytes = Uint8Array::new(&data).to_vec();
+            let url = self.root_path.join(path);
+            let url = url.to_str().unwrap();
+
+            let database = match &self.cache_database {
+                Some(name) => get_cache(name).await.ok(),
+                None => None,
+            };
+
+            if let Some(database) = &database {
+                if let Ok(Some(bytes)) = read_cache(database, url).await {
+                    return Ok(bytes);
+                }
+            }
+
+            // the progress is only tracked while the download runs
+            let result = self.fetch(path, url).await;
+            self.progress.write().remove(path);
+            let bytes = result?;
+
+            if let Some(database) = &database {
+                if let Err(error) = write_cache(database, url, &bytes).await {
+                    warn!("Failed to cache asset {}: {:?}", url, error);
+                }
+            }
+
             Ok(bytes)
         })
     }
@@ -53,3 +213,107 @@ impl AssetIo for WasmAssetIo {
         self.root_path.join(path).is_dir()
     }
 }
+
+/// Development servers often answer unknown paths with the app's `index.html` instead of a 404
+fn is_html_fallback(path: &Path, content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let expects_html = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            extension.eq_ignore_ascii_case("html") || extension.eq_ignore_ascii_case("htm")
+        });
+    if expects_html {
+        return false;
+    }
+
+    match content_type {
+        Some(content_type) => content_type.starts_with("text/html"),
+        None => {
+            let start = String::from_utf8_lossy(&bytes[..bytes.len().min(64)]).to_lowercase();
+            let start = start.trim_start();
+            start.starts_with("<!doctype html") || start.starts_with("<html")
+        }
+    }
+}
+
+fn js_error(value: JsValue) -> AssetIoError {
+    AssetIoError::Io(io::Error::new(io::ErrorKind::Other, format!("{:?}", value)))
+}
+
+/// Resolves once the IndexedDB `request` succeeds or fails
+async fn request_result(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::once_into_js(move |event: web_sys::Event| {
+            let request: IdbRequest = event.target().unwrap().unchecked_into();
+            let result = request.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+        });
+        let on_error = Closure::once_into_js(move |event: web_sys::Event| {
+            let _ = reject.call1(&JsValue::UNDEFINED, &event);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+/// Returns the cache database called `name`. It is opened by the first call, later calls wait for
+/// the same database.
+async fn get_cache(name: &str) -> Result<IdbDatabase, JsValue> {
+    let promise = CACHE_DATABASES.with(|databases| {
+        databases
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                let name = name.to_string();
+                future_to_promise(async move {
+                    match open_cache(&name).await {
+                        Ok(database) => Ok(database.into()),
+                        Err(error) => {
+                            warn!("Failed to open asset cache {}: {:?}", name, error);
+                            Err(error)
+                        }
+                    }
+                })
+            })
+            .clone()
+    });
+    Ok(JsFuture::from(promise).await?.unchecked_into())
+}
+
+async fn open_cache(name: &str) -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .expect("IndexedDB requires a window")
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB is not available"))?;
+    let request: IdbOpenDbRequest = factory.open_with_u32(name, 1)?;
+    let on_upgrade_needed = Closure::once_into_js(move |event: web_sys::Event| {
+        let request: IdbOpenDbRequest = event.target().unwrap().unchecked_into();
+        if let Ok(database) = request.result() {
+            let database: IdbDatabase = database.unchecked_into();
+            let _ = database.create_object_store(CACHE_STORE);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+    Ok(request_result(&request).await?.unchecked_into())
+}
+
+async fn read_cache(database: &IdbDatabase, key: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let transaction = database.transaction_with_str(CACHE_STORE)?;
+    let store = transaction.object_store(CACHE_STORE)?;
+    let value = request_result(&store.get(&JsValue::from_str(key))?).await?;
+    if value.is_undefined() {
+        Ok(None)
+    } else {
+        Ok(Some(Uint8Array::new(&value).to_vec()))
+    }
+}
+
+async fn write_cache(database: &IdbDatabase, key: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    let transaction =
+        database.transaction_with_str_and_mode(CACHE_STORE, IdbTransactionMode::Readwrite)?;
+    let store = transaction.object_store(CACHE_STORE)?;
+    let value = Uint8Array::from(bytes);
+    request_result(&store.put_with_key(&value, &JsValue::from_str(key))?).await?;
+    Ok(())
+}
@@ -5,6 +5,7 @@ mod assets;
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 mod filesystem_watcher;
+mod folder_filter;
 mod handle;
 mod info;
 mod io;
@@ -15,6 +16,7 @@ pub use asset_server::*;
 pub use assets::*;
 use bevy_reflect::RegisterTypeBuilder;
 use bevy_tasks::IoTaskPool;
+pub use folder_filter::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
@@ -28,7 +30,9 @@ pub mod stage {
 }
 
 pub mod prelude {
-    pub use crate::{AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleUntyped};
+    pub use crate::{
+        AddAsset, AssetEvent, AssetServer, Assets, FolderFilter, Handle, HandleUntyped,
+    };
 }
 
 use bevy_app::{prelude::Plugin, AppBuilder};
@@ -40,12 +44,16 @@ pub struct AssetPlugin;
 
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// The IndexedDB database used to cache downloaded assets in wasm builds. Caching is disabled
+    /// when this is `None`.
+    pub cache_database: Option<String>,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            cache_database: None,
         }
     }
 }
@@ -60,6 +68,7 @@ impl Plugin for AssetPlugin {
             .clone();
 
         let asset_server = {
+            let memory_asset_io = app.resources().get::<MemoryAssetIo>().map(|io| io.clone());
             let settings = app
                 .resources_mut()
                 .get_or_insert_with(AssetServerSettings::default);
@@ -67,10 +76,20 @@ impl Plugin for AssetPlugin {
             #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
             let source = FileAssetIo::new(&settings.asset_folder);
             #[cfg(target_arch = "wasm32")]
-            let source = WasmAssetIo::new(&settings.asset_folder);
+            let source = match &settings.cache_database {
+                Some(cache_database) => {
+                    WasmAssetIo::new(&settings.asset_folder).with_cache(cache_database)
+                }
+                None => WasmAssetIo::new(&settings.asset_folder),
+            };
             #[cfg(target_os = "android")]
             let source = AndroidAssetIo::new(&settings.asset_folder);
-            AssetServer::new(source, task_pool)
+
+            if let Some(memory_asset_io) = memory_asset_io {
+                AssetServer::new(memory_asset_io.with_fallback(source), task_pool)
+            } else {
+                AssetServer::new(source, task_pool)
+            }
         };
 
         app.add_stage_before(bevy_app::stage::PRE_UPDATE, stage::LOAD_ASSETS)
@@ -80,7 +99,8 @@ impl Plugin for AssetPlugin {
             .add_system_to_stage(
                 bevy_app::stage::PRE_UPDATE,
                 asset_server::free_unused_assets_system,
-            );
+            )
+            .add_system_to_stage(stage::LOAD_ASSETS, io::memory_asset_io_system);
 
         #[cfg(all(
             feature = "filesystem_watcher",
@@ -1,14 +1,18 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, AssetServerError, Assets,
+    Handle, HandleId, LoadState, RefChangeChannel,
 };
 use anyhow::Result;
 use bevy_ecs::{Res, ResMut, Resource};
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
-use bevy_utils::{BoxedFuture, HashMap};
+use bevy_utils::{BoxedFuture, HashMap, Uuid};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
@@ -18,6 +22,12 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+
+    /// The [TypeUuid] of the default asset this loader produces, if it is always the same type.
+    /// Failures of untyped loads are reported to the [Assets] of this type.
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        None
+    }
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -56,9 +66,15 @@ impl LoadedAsset {
 pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) asset_server: &'a AssetServer,
     pub(crate) labeled_assets: HashMap<Option<String>, LoadedAsset>,
+    /// Sources loaded with [LoadContext::load_dependency], which every asset in the source depends on
+    pub(crate) dependencies: Vec<AssetPath<'static>>,
+    /// Sources whose loaders are waiting for this source, to detect dependency cycles
+    pub(crate) loading_sources: Vec<PathBuf>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<&'a LoaderSettings>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -66,14 +82,20 @@ impl<'a> LoadContext<'a> {
         path: &'a Path,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
+        asset_server: &'a AssetServer,
         version: usize,
+        settings: Option<&'a LoaderSettings>,
     ) -> Self {
         Self {
             ref_change_channel,
             asset_io,
+            asset_server,
             labeled_assets: Default::default(),
+            dependencies: Vec::new(),
+            loading_sources: Vec::new(),
             version,
             path,
+            settings,
         }
     }
 
@@ -81,6 +103,26 @@ impl<'a> LoadContext<'a> {
         &self.path
     }
 
+    /// Returns the settings passed to [AssetServer::load_with_settings], if they are of type `S`
+    pub fn settings<S: Any>(&self) -> Option<&S> {
+        self.settings
+            .and_then(|settings| settings.value.downcast_ref::<S>())
+    }
+
+    /// Returns the path of the asset with the given label in the source being loaded. Use this
+    /// instead of building the path from [LoadContext::path] so sources loaded with settings refer
+    /// to their own labeled assets.
+    pub fn get_label_path<'b>(&'b self, label: &'b str) -> AssetPath<'b> {
+        AssetPath::new_ref(self.path, Some(label))
+            .with_settings_hash(self.settings.map(|settings| settings.hash))
+    }
+
+    /// Returns the path of the default asset of the source being loaded
+    pub fn get_default_path(&self) -> AssetPath<'_> {
+        AssetPath::new_ref(self.path, None)
+            .with_settings_hash(self.settings.map(|settings| settings.hash))
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -102,11 +144,114 @@ impl<'a> LoadContext<'a> {
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    /// Writes `bytes` to `path`, for loaders that cache processed data next to their sources
+    pub fn write_asset_bytes<P: AsRef<Path>>(
+        &self,
+        path: P,
+        bytes: &[u8],
+    ) -> Result<(), AssetIoError> {
+        self.asset_io.save_path(path.as_ref(), bytes)
+    }
+
+    /// Resolves `path` relative to the folder of the asset being loaded
+    pub fn resolve_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        match self.path.parent() {
+            Some(parent) => parent.join(path),
+            None => path.as_ref().to_owned(),
+        }
+    }
+
+    /// Loads the source of the asset at `path`, relative to the asset being loaded, with its
+    /// registered [AssetLoader] and waits for it. Every asset in the loaded source is sent to the
+    /// [AssetServer], and the source is registered as a dependency of every asset in this source.
+    /// Sources the [AssetServer] already loaded, or is loading, are reused instead of read again.
+    pub async fn load_dependency<'b, T: Asset, P: Into<AssetPath<'b>>>(
+        &mut self,
+        path: P,
+    ) -> Result<Handle<T>, AssetServerError> {
+        let relative_path: AssetPath = path.into();
+        let asset_path = AssetPath::new(
+            self.resolve_path(relative_path.path()),
+            relative_path.label().map(|label| label.to_string()),
+        );
+        if asset_path.path() == self.path
+            || self
+                .loading_sources
+                .iter()
+                .any(|path| path == asset_path.path())
+        {
+            return Err(AssetServerError::DependencyCycle(asset_path.to_owned()));
+        }
+
+        let asset_path_id = asset_path.get_id();
+        if let LoadState::NotLoaded | LoadState::Failed =
+            self.asset_server.get_load_state(asset_path_id)
+        {
+            self.load_dependency_source(&asset_path).await?;
+        }
+        // sources that are still loading elsewhere are checked when they finish
+        match self.asset_server.get_asset_type(asset_path_id) {
+            Some(None) => return Err(AssetServerError::MissingAsset(asset_path.to_owned())),
+            Some(Some(type_uuid)) if type_uuid != T::TYPE_UUID => {
+                return Err(AssetServerError::IncorrectHandleType)
+            }
+            _ => {}
+        }
+
+        self.dependencies.push(asset_path.to_owned());
+        Ok(self.get_handle(asset_path_id))
+    }
+
+    async fn load_dependency_source(
+        &mut self,
+        asset_path: &AssetPath<'_>,
+    ) -> Result<(), AssetServerError> {
+        let version = match self.asset_server.begin_load(asset_path, false) {
+            Some(version) => version,
+            None => return Ok(()),
+        };
+
+        let mut load_context = LoadContext::new(
+            asset_path.path(),
+            self.ref_change_channel,
+            self.asset_io,
+            self.asset_server,
+            version,
+            None,
+        );
+        load_context.loading_sources = self.loading_sources.clone();
+        load_context.loading_sources.push(self.path.to_owned());
+        let loaded = async {
+            let asset_loader = self.asset_server.get_path_asset_loader(asset_path.path())?;
+            let bytes = self.asset_io.load_path(asset_path.path()).await?;
+            asset_loader
+                .load(&bytes, &mut load_context)
+                .await
+                .map_err(AssetServerError::AssetLoaderError)
+        }
+        .await;
+        match loaded {
+            Ok(()) => {
+                self.asset_server.finish_load(asset_path, &mut load_context);
+                Ok(())
+            }
+            Err(error) => {
+                self.asset_server.set_load_failed(asset_path, error);
+                Err(AssetServerError::DependencyFailed(asset_path.to_owned()))
+            }
+        }
+    }
+
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
         for (label, asset) in self.labeled_assets.iter() {
             asset_metas.push(AssetMeta {
-                dependencies: asset.dependencies.clone(),
+                dependencies: asset
+                    .dependencies
+                    .iter()
+                    .chain(self.dependencies.iter())
+                    .cloned()
+                    .collect(),
                 label: label.clone(),
                 type_uuid: asset.value.as_ref().unwrap().type_uuid(),
             });
@@ -115,6 +260,20 @@ impl<'a> LoadContext<'a> {
     }
 }
 
+/// Loader specific settings passed to [AssetServer::load_with_settings]
+pub struct LoaderSettings {
+    pub(crate) hash: u64,
+    pub(crate) value: Box<dyn Any + Send + Sync>,
+}
+
+impl std::fmt::Debug for LoaderSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoaderSettings")
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
 /// The result of loading an asset of type `T`
 #[derive(Debug)]
 pub struct AssetResult<T: Resource> {
@@ -133,11 +292,13 @@ pub struct AssetLifecycleChannel<T: Resource> {
 pub enum AssetLifecycleEvent<T: Resource> {
     Create(AssetResult<T>),
     Free(HandleId),
+    Fail(HandleId, Arc<AssetServerError>),
 }
 
 pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
     fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize);
     fn free_asset(&self, id: HandleId);
+    fn fail_asset(&self, id: HandleId, error: Arc<AssetServerError>);
 }
 impl_downcast!(AssetLifecycle);
 
@@ -159,6 +320,12 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
     fn free_asset(&self, id: HandleId) {
         self.sender.send(AssetLifecycleEvent::Free(id)).unwrap();
     }
+
+    fn fail_asset(&self, id: HandleId, error: Arc<AssetServerError>) {
+        self.sender
+            .send(AssetLifecycleEvent::Fail(id, error))
+            .unwrap();
+    }
 }
 
 impl<T: Resource> Default for AssetLifecycleChannel<T> {
@@ -175,3 +342,185 @@ pub fn update_asset_storage_system<T: Asset + AssetDynamic>(
 ) {
     asset_server.update_asset_storage(&mut assets);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryAssetIo;
+    use bevy_tasks::TaskPool;
+    use futures_lite::future;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq, TypeUuid)]
+    #[uuid = "4d7b5e3a-2c1f-4b8e-9a6d-0f3e2b1c5a97"]
+    struct Line(String);
+
+    /// Settings that make [LineLoader] prefix the default asset
+    #[derive(Hash)]
+    struct Prefix(&'static str);
+
+    /// Loads every line of a file as a labeled asset, and the whole file as the default asset
+    struct LineLoader;
+
+    impl AssetLoader for LineLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                for line in text.lines() {
+                    load_context.set_labeled_asset(line, LoadedAsset::new(Line(line.to_string())));
+                }
+                let prefix = load_context
+                    .settings::<Prefix>()
+                    .map_or("", |prefix| prefix.0);
+                load_context
+                    .set_default_asset(LoadedAsset::new(Line(format!("{}{}", prefix, text))));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["lines"]
+        }
+    }
+
+    /// Loads the sources named on every line as dependencies
+    struct DependencyLoader;
+
+    impl AssetLoader for DependencyLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<()>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                for line in text.lines() {
+                    load_context.load_dependency::<Line, _>(line).await?;
+                }
+                load_context.set_default_asset(LoadedAsset::new(Line(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["deps"]
+        }
+    }
+
+    fn test_server(asset_io: &MemoryAssetIo) -> AssetServer {
+        let asset_server = AssetServer::new(asset_io.clone(), TaskPool::new());
+        asset_server.add_loader(LineLoader);
+        asset_server.add_loader(DependencyLoader);
+        asset_server.register_asset_type::<Line>();
+        asset_server
+    }
+
+    #[test]
+    fn load_dependency() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("levels/names.lines", b"first\nsecond".to_vec());
+        let asset_server = test_server(&asset_io);
+        let ref_change_channel = RefChangeChannel::default();
+        let mut load_context = LoadContext::new(
+            Path::new("levels/start.lines"),
+            &ref_change_channel,
+            &asset_io,
+            &asset_server,
+            1,
+            None,
+        );
+
+        let handle =
+            future::block_on(load_context.load_dependency::<Line, _>("names.lines#second"))
+                .unwrap();
+        assert_eq!(
+            handle.id,
+            HandleId::from(AssetPath::from("levels/names.lines#second"))
+        );
+        assert_eq!(load_context.dependencies.len(), 1);
+
+        // every asset of the source was sent to the server
+        let asset_lifecycles = asset_server.server.asset_lifecycles.read();
+        let lifecycle_channel = asset_lifecycles
+            .get(&Line::TYPE_UUID)
+            .and_then(|lifecycle| lifecycle.downcast_ref::<AssetLifecycleChannel<Line>>())
+            .unwrap();
+        assert_eq!(lifecycle_channel.receiver.try_iter().count(), 3);
+
+        // the source is only loaded once
+        future::block_on(load_context.load_dependency::<Line, _>("names.lines#first")).unwrap();
+        assert_eq!(lifecycle_channel.receiver.try_iter().count(), 0);
+        assert_eq!(load_context.dependencies.len(), 2);
+
+        match future::block_on(load_context.load_dependency::<Line, _>("names.lines#third")) {
+            Err(AssetServerError::MissingAsset(asset_path)) => {
+                assert_eq!(asset_path.label(), Some("third"))
+            }
+            _ => panic!("expected a missing asset error"),
+        }
+        assert_eq!(load_context.dependencies.len(), 2);
+    }
+
+    #[test]
+    fn dependency_cycles_fail() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("a.deps", b"b.deps".to_vec());
+        asset_io.insert("b.deps", b"a.deps".to_vec());
+        let asset_server = test_server(&asset_io);
+        let ref_change_channel = RefChangeChannel::default();
+        let mut load_context = LoadContext::new(
+            Path::new("a.deps"),
+            &ref_change_channel,
+            &asset_io,
+            &asset_server,
+            1,
+            None,
+        );
+
+        // b.deps fails because it depends on a.deps, which is waiting for it
+        match future::block_on(load_context.load_dependency::<Line, _>("b.deps")) {
+            Err(AssetServerError::DependencyFailed(asset_path)) => {
+                assert_eq!(asset_path.path(), Path::new("b.deps"))
+            }
+            _ => panic!("expected a failed dependency"),
+        }
+        assert_eq!(asset_server.get_load_state("b.deps"), LoadState::Failed);
+        assert!(matches!(
+            future::block_on(load_context.load_dependency::<Line, _>("a.deps")),
+            Err(AssetServerError::DependencyCycle(_))
+        ));
+    }
+
+    #[test]
+    fn load_with_settings() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("names.lines", b"first".to_vec());
+        let asset_server = test_server(&asset_io);
+        let handle: Handle<Line> = asset_server.load_with_settings("names.lines", Prefix("> "));
+
+        let asset_lifecycles = asset_server.server.asset_lifecycles.read();
+        let lifecycle_channel = asset_lifecycles
+            .get(&Line::TYPE_UUID)
+            .and_then(|lifecycle| lifecycle.downcast_ref::<AssetLifecycleChannel<Line>>())
+            .unwrap();
+        let default_asset = (0..2)
+            .map(|_| {
+                lifecycle_channel
+                    .receiver
+                    .recv_timeout(Duration::from_secs(10))
+                    .unwrap()
+            })
+            .find_map(|event| match event {
+                AssetLifecycleEvent::Create(result) if result.id == handle.id => Some(result.asset),
+                _ => None,
+            });
+        assert_eq!(default_asset, Some(Line("> first".to_string())));
+
+        // the settings moved to the source once its load began
+        assert!(asset_server.server.loader_settings.read().is_empty());
+    }
+}
@@ -11,6 +11,9 @@ use std::{
 pub struct AssetPath<'a> {
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
+    /// Hash of the loader settings passed to [AssetServer::load_with_settings](crate::AssetServer::load_with_settings)
+    #[serde(default)]
+    settings_hash: Option<u64>,
 }
 
 impl<'a> AssetPath<'a> {
@@ -19,6 +22,7 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(|val| Cow::Borrowed(val)),
+            settings_hash: None,
         }
     }
 
@@ -27,6 +31,7 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
+            settings_hash: None,
         }
     }
 
@@ -53,8 +58,22 @@ impl<'a> AssetPath<'a> {
                 .label
                 .as_ref()
                 .map(|value| Cow::Owned(value.to_string())),
+            settings_hash: self.settings_hash,
         }
     }
+
+    /// Returns the hash of the loader settings this path is loaded with, if any. Paths with
+    /// different settings refer to different sources.
+    #[inline]
+    pub fn settings_hash(&self) -> Option<u64> {
+        self.settings_hash
+    }
+
+    #[inline]
+    pub fn with_settings_hash(mut self, settings_hash: Option<u64>) -> Self {
+        self.settings_hash = settings_hash;
+        self
+    }
 }
 
 #[derive(
@@ -83,6 +102,19 @@ impl<'a> From<&'a Path> for SourcePathId {
     }
 }
 
+impl SourcePathId {
+    /// Sources loaded with settings get their own id, so the same file can be loaded with several
+    /// import configurations at once
+    pub fn new(path: &Path, settings_hash: Option<u64>) -> Self {
+        let mut hasher = get_hasher();
+        path.hash(&mut hasher);
+        if let Some(settings_hash) = settings_hash {
+            settings_hash.hash(&mut hasher);
+        }
+        SourcePathId(hasher.finish())
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -125,7 +157,7 @@ where
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.path(), asset_path.settings_hash()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -134,7 +166,7 @@ where
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.path(), asset_path.settings_hash()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -148,6 +180,7 @@ impl<'a> From<&'a str> for AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(|label| Cow::Borrowed(label)),
+            settings_hash: None,
         }
     }
 }
@@ -157,6 +190,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: None,
+            settings_hash: None,
         }
     }
 }
@@ -166,6 +200,7 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: None,
+            settings_hash: None,
         }
     }
 }
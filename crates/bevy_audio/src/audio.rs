@@ -1,14 +1,51 @@
-use crate::{AudioSource, Decodable};
+use crate::{AudioChannel, AudioSink, AudioSource, Decodable, Fade, PlaybackSettings};
 use bevy_asset::Handle;
 use parking_lot::RwLock;
-use std::{collections::VecDeque, fmt};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+/// The volume of every sound, for example to implement a master volume setting. 1.0 is the volume
+/// of the sources.
+#[derive(Debug, Clone)]
+pub struct GlobalVolume {
+    volume: Arc<AtomicU32>,
+}
+
+impl Default for GlobalVolume {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl GlobalVolume {
+    pub fn new(volume: f32) -> Self {
+        Self {
+            volume: Arc::new(AtomicU32::new(volume.max(0.0).to_bits())),
+        }
+    }
+
+    pub fn set(&self, volume: f32) {
+        self.volume
+            .store(volume.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.volume.load(Ordering::Relaxed))
+    }
+}
 
 /// The external struct used to play audio
 pub struct Audio<P = AudioSource>
 where
     P: Decodable,
 {
-    pub queue: RwLock<VecDeque<Handle<P>>>,
+    pub queue: RwLock<VecDeque<(Handle<P>, AudioSink)>>,
 }
 
 impl<P> fmt::Debug for Audio<P>
@@ -37,7 +74,52 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    pub fn play(&self, audio_source: Handle<P>) {
-        self.queue.write().push_front(audio_source);
+    /// Plays `audio_source` once it is loaded. The returned [AudioSink] controls the sound, it can
+    /// be dropped to let the sound play to the end.
+    pub fn play(&self, audio_source: Handle<P>) -> AudioSink {
+        self.play_with_settings(audio_source, PlaybackSettings::ONCE)
+    }
+
+    /// Plays `audio_source` in a loop, until it is stopped through the returned [AudioSink]
+    pub fn play_looped(&self, audio_source: Handle<P>) -> AudioSink {
+        self.play_with_settings(audio_source, PlaybackSettings::LOOP)
+    }
+
+    pub fn play_with_settings(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+    ) -> AudioSink {
+        self.play_internal(audio_source, settings, None)
+    }
+
+    /// Plays `audio_source` in `channel`, which can pause, stop and change the volume of all of its
+    /// sounds at once
+    pub fn play_in_channel(&self, audio_source: Handle<P>, channel: &AudioChannel) -> AudioSink {
+        self.play_internal(audio_source, PlaybackSettings::ONCE, Some(channel))
+    }
+
+    /// Fades out the sounds playing in `channel` while fading in `audio_source`, for example to
+    /// switch between music tracks
+    pub fn crossfade(
+        &self,
+        audio_source: Handle<P>,
+        channel: &AudioChannel,
+        settings: PlaybackSettings,
+        fade: Fade,
+    ) -> AudioSink {
+        channel.stop_with_fade(fade);
+        self.play_internal(audio_source, settings.with_fade_in(fade), Some(channel))
+    }
+
+    fn play_internal(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+        channel: Option<&AudioChannel>,
+    ) -> AudioSink {
+        let sink = AudioSink::new(settings, channel);
+        self.queue.write().push_front((audio_source, sink.clone()));
+        sink
     }
 }
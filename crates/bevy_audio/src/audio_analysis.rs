@@ -0,0 +1,210 @@
+use bevy_ecs::{Res, ResMut};
+use parking_lot::Mutex;
+use rodio::Source;
+use std::{collections::VecDeque, f32::consts::PI, sync::Arc, time::Duration};
+
+/// Frames of output audio that are analyzed, a power of two for the FFT
+const ANALYSIS_FRAMES: usize = 1024;
+/// Samples a tap collects before handing them to the analysis, to avoid locking for every sample
+const TAP_BATCH_SIZE: usize = 512;
+
+/// Enables the optional parts of [AudioAnalysis]
+#[derive(Debug, Clone, Default)]
+pub struct AudioAnalysisSettings {
+    /// Compute [AudioAnalysis::spectrum]
+    pub spectrum: bool,
+}
+
+/// Analysis of the audio that was played most recently, updated every frame
+#[derive(Debug, Clone, Default)]
+pub struct AudioAnalysis {
+    /// The RMS amplitude of each output channel, between 0.0 and 1.0
+    pub amplitude: Vec<f32>,
+    /// The magnitude of each frequency band of the output, from 0 Hz to half the sample rate. Only
+    /// computed if enabled in [AudioAnalysisSettings].
+    pub spectrum: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl AudioAnalysis {
+    /// Returns the frequency in Hz at the center of the spectrum band `index`
+    pub fn band_frequency(&self, index: usize) -> f32 {
+        index as f32 * self.sample_rate as f32 / ANALYSIS_FRAMES as f32
+    }
+}
+
+type TapBuffer = Arc<Mutex<VecDeque<f32>>>;
+
+/// The recent output of every [AudioOutput](crate::AudioOutput)
+#[derive(Clone, Default)]
+pub(crate) struct AnalysisTaps {
+    buffers: Arc<Mutex<Vec<(TapBuffer, u16, u32)>>>,
+}
+
+impl AnalysisTaps {
+    /// Wraps an output source to record what it plays
+    pub(crate) fn tap<S>(&self, source: S) -> AnalysisTap<S>
+    where
+        S: Source<Item = f32>,
+    {
+        let buffer = TapBuffer::default();
+        let mut buffers = self.buffers.lock();
+        buffers.retain(|(buffer, _, _)| Arc::strong_count(buffer) > 1);
+        buffers.push((buffer.clone(), source.channels(), source.sample_rate()));
+        AnalysisTap {
+            source,
+            buffer,
+            pending: Vec::with_capacity(TAP_BATCH_SIZE),
+        }
+    }
+}
+
+/// Copies the samples of a source to a buffer analyzed by [audio_analysis_system]
+pub(crate) struct AnalysisTap<S> {
+    source: S,
+    buffer: TapBuffer,
+    pending: Vec<f32>,
+}
+
+impl<S> Iterator for AnalysisTap<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.source.next()?;
+        self.pending.push(sample);
+        if self.pending.len() >= TAP_BATCH_SIZE {
+            let max_samples = ANALYSIS_FRAMES * self.source.channels() as usize;
+            let mut buffer = self.buffer.lock();
+            buffer.extend(self.pending.drain(..));
+            let overflow = buffer.len().saturating_sub(max_samples);
+            buffer.drain(..overflow);
+        }
+        Some(sample)
+    }
+}
+
+impl<S> Source for AnalysisTap<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.source.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.source.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.source.total_duration()
+    }
+}
+
+/// Updates [AudioAnalysis] from the audio played by every [AudioOutput](crate::AudioOutput).
+/// Outputs are combined assuming their sounds are unrelated, by adding their power.
+pub(crate) fn audio_analysis_system(
+    taps: Res<AnalysisTaps>,
+    settings: Res<AudioAnalysisSettings>,
+    mut analysis: ResMut<AudioAnalysis>,
+) {
+    let mut power = Vec::new();
+    let mut spectrum_power = Vec::new();
+    let mut sample_rate = analysis.sample_rate;
+    for (buffer, channels, tap_sample_rate) in taps.buffers.lock().iter() {
+        let channels = *channels as usize;
+        let samples = buffer.lock().iter().copied().collect::<Vec<f32>>();
+        if samples.is_empty() {
+            continue;
+        }
+        sample_rate = *tap_sample_rate;
+
+        power.resize(power.len().max(channels), 0.0);
+        let frames = samples.len() / channels;
+        for (channel, channel_power) in power.iter_mut().enumerate().take(channels) {
+            let sum: f32 = samples
+                .iter()
+                .skip(channel)
+                .step_by(channels)
+                .map(|sample| sample * sample)
+                .sum();
+            *channel_power += sum / frames.max(1) as f32;
+        }
+
+        if settings.spectrum {
+            let mono = samples
+                .chunks_exact(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+                .collect::<Vec<f32>>();
+            let magnitudes = spectrum(&mono);
+            spectrum_power.resize(magnitudes.len(), 0.0);
+            for (band_power, magnitude) in spectrum_power.iter_mut().zip(magnitudes) {
+                *band_power += magnitude * magnitude;
+            }
+        }
+    }
+
+    analysis.amplitude = power.into_iter().map(f32::sqrt).collect();
+    analysis.spectrum = spectrum_power.into_iter().map(f32::sqrt).collect();
+    analysis.sample_rate = sample_rate;
+}
+
+/// Returns the magnitudes of the frequencies of the last [ANALYSIS_FRAMES] `samples`, from 0 Hz to
+/// half the sample rate
+fn spectrum(samples: &[f32]) -> Vec<f32> {
+    let start = samples.len().saturating_sub(ANALYSIS_FRAMES);
+    let mut real = vec![0.0; ANALYSIS_FRAMES];
+    let mut imaginary = vec![0.0; ANALYSIS_FRAMES];
+    for (index, sample) in samples[start..].iter().enumerate() {
+        // a Hann window reduces the leakage of frequencies into neighboring bands
+        let window = 0.5 - 0.5 * (2.0 * PI * index as f32 / (ANALYSIS_FRAMES - 1) as f32).cos();
+        real[index] = sample * window;
+    }
+
+    fft(&mut real, &mut imaginary);
+    real.iter()
+        .zip(imaginary.iter())
+        .take(ANALYSIS_FRAMES / 2)
+        .map(|(real, imaginary)| {
+            (real * real + imaginary * imaginary).sqrt() * 2.0 / ANALYSIS_FRAMES as f32
+        })
+        .collect()
+}
+
+/// An in-place radix-2 FFT. The length of the input must be a power of two.
+fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let len = real.len();
+    let bits = len.trailing_zeros();
+    for index in 0..len {
+        let reversed = index.reverse_bits() >> (std::mem::size_of::<usize>() as u32 * 8 - bits);
+        if reversed > index {
+            real.swap(index, reversed);
+            imaginary.swap(index, reversed);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let angle = -2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (angle * offset as f32).sin_cos();
+                let even = start + offset;
+                let odd = even + size / 2;
+                let odd_real = real[odd] * cos - imaginary[odd] * sin;
+                let odd_imaginary = real[odd] * sin + imaginary[odd] * cos;
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+            }
+        }
+        size *= 2;
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    audio_effect::EffectList, audio_fade::FadeCommand, audio_sink::SinkState, AudioEffect, Fade,
+};
+use bevy_utils::HashMap;
+use parking_lot::{Mutex, RwLock};
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    Arc, Weak,
+};
+
+/// A named group of sounds, such as "music" or "sfx", that can be paused, stopped and have its
+/// volume changed together. Sounds are routed to a channel with
+/// [Audio::play_in_channel](crate::Audio::play_in_channel).
+///
+/// Clones control the same channel.
+#[derive(Debug, Clone)]
+pub struct AudioChannel {
+    name: Arc<str>,
+    pub(crate) state: Arc<ChannelState>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ChannelState {
+    volume: AtomicU32,
+    paused: AtomicBool,
+    /// Incremented by [AudioChannel::stop]. Sounds started before the latest stop are stopped.
+    pub(crate) stop_generation: AtomicU64,
+    pub(crate) effects: EffectList,
+    /// The sounds played in the channel, to fade them out
+    sinks: Mutex<Vec<Weak<SinkState>>>,
+}
+
+impl AudioChannel {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            state: Arc::new(ChannelState {
+                volume: AtomicU32::new(1.0f32.to_bits()),
+                paused: AtomicBool::new(false),
+                stop_generation: AtomicU64::new(0),
+                effects: Default::default(),
+                sinks: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the volume of every sound in the channel. 1.0 is the volume of the source.
+    pub fn set_volume(&self, volume: f32) {
+        self.state
+            .volume
+            .store(volume.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn volume(&self) -> f32 {
+        f32::from_bits(self.state.volume.load(Ordering::Relaxed))
+    }
+
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Relaxed)
+    }
+
+    /// Replaces the effects applied to every sound in the channel, after the effects of the sound
+    pub fn set_effects(&self, effects: Vec<AudioEffect>) {
+        self.state.effects.set(effects);
+    }
+
+    pub fn effects(&self) -> Vec<AudioEffect> {
+        self.state.effects.get()
+    }
+
+    /// Stops every sound that is currently playing or waiting to play in the channel. Sounds
+    /// played afterwards are not affected.
+    pub fn stop(&self) {
+        self.state.stop_generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Fades out and stops every sound that is currently playing or waiting to play in the channel
+    pub fn stop_with_fade(&self, fade: Fade) {
+        for sink in self.state.sinks.lock().iter().filter_map(Weak::upgrade) {
+            sink.send_fade(FadeCommand {
+                from: None,
+                to: 0.0,
+                fade,
+                stop: true,
+            });
+        }
+    }
+
+    pub(crate) fn add_sink(&self, sink: &Arc<SinkState>) {
+        let mut sinks = self.state.sinks.lock();
+        sinks.retain(|sink| sink.strong_count() > 0);
+        sinks.push(Arc::downgrade(sink));
+    }
+}
+
+/// The [AudioChannel]s of an App, created when they are first requested
+#[derive(Debug, Default)]
+pub struct AudioChannels {
+    channels: RwLock<HashMap<String, AudioChannel>>,
+}
+
+impl AudioChannels {
+    /// Returns the channel named `name`, creating it if it doesn't exist yet
+    pub fn get(&self, name: &str) -> AudioChannel {
+        if let Some(channel) = self.channels.read().get(name) {
+            return channel.clone();
+        }
+        self.channels
+            .write()
+            .entry(name.to_string())
+            .or_insert_with(|| AudioChannel::new(name))
+            .clone()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = AudioChannel> {
+        self.channels
+            .read()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
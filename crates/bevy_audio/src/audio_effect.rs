@@ -0,0 +1,207 @@
+use parking_lot::RwLock;
+use std::{
+    f32::consts::PI,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// An effect applied to the samples of a sound or of every sound in an
+/// [AudioChannel](crate::AudioChannel)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioEffect {
+    /// Removes frequencies above `cutoff` Hz, for example to muffle sounds heard underwater or
+    /// through a wall
+    LowPass { cutoff: f32 },
+    /// Removes frequencies below `cutoff` Hz
+    HighPass { cutoff: f32 },
+    /// Scales the bass (below 300 Hz), mid and treble (above 3000 Hz) frequencies
+    Equalizer { low: f32, mid: f32, high: f32 },
+    /// Adds echoes of the sound, like a room would. `room_size` is the delay of the echoes in
+    /// seconds, `decay` how much of an echo remains for the next one, and `mix` how loud the echoes
+    /// are relative to the sound. The echoes stop when the sound ends.
+    Reverb {
+        room_size: f32,
+        decay: f32,
+        mix: f32,
+    },
+}
+
+/// Effects shared between the main thread and the audio thread
+#[derive(Debug, Default)]
+pub(crate) struct EffectList {
+    effects: RwLock<Vec<AudioEffect>>,
+    version: AtomicU64,
+}
+
+impl EffectList {
+    pub(crate) fn set(&self, effects: Vec<AudioEffect>) {
+        *self.effects.write() = effects;
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    pub(crate) fn get(&self) -> Vec<AudioEffect> {
+        self.effects.read().clone()
+    }
+}
+
+/// Applies the effects of an [EffectList] to a stream of samples, keeping the state of each effect
+/// between samples
+#[derive(Debug, Default)]
+pub(crate) struct EffectChain {
+    version: u64,
+    channels: u16,
+    sample_rate: u32,
+    processors: Vec<EffectProcessor>,
+}
+
+impl EffectChain {
+    /// Rebuilds the chain if the effects or the format of the stream changed
+    pub(crate) fn update(&mut self, effects: &EffectList, channels: u16, sample_rate: u32) {
+        let version = effects.version.load(Ordering::Acquire);
+        if version == self.version && channels == self.channels && sample_rate == self.sample_rate {
+            return;
+        }
+
+        self.version = version;
+        self.channels = channels;
+        self.sample_rate = sample_rate;
+        self.processors = effects
+            .get()
+            .into_iter()
+            .map(|effect| EffectProcessor::new(effect, channels as usize, sample_rate as f32))
+            .collect();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// Processes one sample of the interleaved `channel`
+    pub(crate) fn process(&mut self, channel: usize, sample: f32) -> f32 {
+        self.processors
+            .iter_mut()
+            .fold(sample, |sample, processor| {
+                processor.process(channel, sample)
+            })
+    }
+}
+
+#[derive(Debug)]
+enum EffectProcessor {
+    LowPass(OnePole),
+    HighPass(OnePole),
+    Equalizer {
+        gains: [f32; 3],
+        low: OnePole,
+        high: OnePole,
+    },
+    Reverb {
+        decay: f32,
+        mix: f32,
+        combs: Vec<Comb>,
+    },
+}
+
+/// Delays of the reverb's comb filters relative to the room size. Mutually prime ratios keep the
+/// echoes from lining up.
+const REVERB_DELAYS: [f32; 4] = [1.0, 1.13, 1.27, 1.41];
+
+impl EffectProcessor {
+    fn new(effect: AudioEffect, channels: usize, sample_rate: f32) -> Self {
+        match effect {
+            AudioEffect::LowPass { cutoff } => {
+                EffectProcessor::LowPass(OnePole::new(cutoff, sample_rate, channels))
+            }
+            AudioEffect::HighPass { cutoff } => {
+                EffectProcessor::HighPass(OnePole::new(cutoff, sample_rate, channels))
+            }
+            AudioEffect::Equalizer { low, mid, high } => EffectProcessor::Equalizer {
+                gains: [low, mid, high],
+                low: OnePole::new(300.0, sample_rate, channels),
+                high: OnePole::new(3000.0, sample_rate, channels),
+            },
+            AudioEffect::Reverb {
+                room_size,
+                decay,
+                mix,
+            } => EffectProcessor::Reverb {
+                decay: decay.max(0.0).min(0.99),
+                mix,
+                combs: REVERB_DELAYS
+                    .iter()
+                    .map(|ratio| {
+                        let delay = (room_size.max(0.0) * ratio * sample_rate) as usize;
+                        Comb::new(delay.max(1), channels)
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn process(&mut self, channel: usize, sample: f32) -> f32 {
+        match self {
+            EffectProcessor::LowPass(filter) => filter.process(channel, sample),
+            EffectProcessor::HighPass(filter) => sample - filter.process(channel, sample),
+            EffectProcessor::Equalizer { gains, low, high } => {
+                let bass = low.process(channel, sample);
+                let treble = sample - high.process(channel, sample);
+                let mid = sample - bass - treble;
+                bass * gains[0] + mid * gains[1] + treble * gains[2]
+            }
+            EffectProcessor::Reverb { decay, mix, combs } => {
+                let echoes = combs
+                    .iter_mut()
+                    .map(|comb| comb.process(channel, sample, *decay))
+                    .sum::<f32>()
+                    / combs.len() as f32;
+                sample + echoes * *mix
+            }
+        }
+    }
+}
+
+/// A one pole low-pass filter for each channel
+#[derive(Debug)]
+struct OnePole {
+    coefficient: f32,
+    previous: Vec<f32>,
+}
+
+impl OnePole {
+    fn new(cutoff: f32, sample_rate: f32, channels: usize) -> Self {
+        Self {
+            coefficient: 1.0 - (-2.0 * PI * cutoff.max(0.0) / sample_rate).exp(),
+            previous: vec![0.0; channels],
+        }
+    }
+
+    fn process(&mut self, channel: usize, sample: f32) -> f32 {
+        let previous = &mut self.previous[channel];
+        *previous += self.coefficient * (sample - *previous);
+        *previous
+    }
+}
+
+/// A feedback delay line for each channel
+#[derive(Debug)]
+struct Comb {
+    buffers: Vec<Vec<f32>>,
+    position: Vec<usize>,
+}
+
+impl Comb {
+    fn new(delay: usize, channels: usize) -> Self {
+        Self {
+            buffers: vec![vec![0.0; delay]; channels],
+            position: vec![0; channels],
+        }
+    }
+
+    fn process(&mut self, channel: usize, sample: f32, decay: f32) -> f32 {
+        let buffer = &mut self.buffers[channel];
+        let position = &mut self.position[channel];
+        let delayed = buffer[*position];
+        buffer[*position] = sample + delayed * decay;
+        *position = (*position + 1) % buffer.len();
+        delayed
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+/// How the volume changes over the duration of a [Fade]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FadeCurve {
+    Linear,
+    /// Changes slowly at first, then faster
+    EaseIn,
+    /// Changes quickly at first, then slower
+    EaseOut,
+    /// Changes slowly at the start and the end
+    SmoothStep,
+}
+
+impl FadeCurve {
+    /// Maps the progress of a fade, from 0.0 to 1.0, to how far the volume has changed
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            FadeCurve::Linear => t,
+            FadeCurve::EaseIn => t * t,
+            FadeCurve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            FadeCurve::SmoothStep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A gradual volume change, see [AudioSink::fade_in](crate::AudioSink::fade_in)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fade {
+    pub duration: Duration,
+    pub curve: FadeCurve,
+}
+
+impl Fade {
+    pub fn new(duration: Duration, curve: FadeCurve) -> Self {
+        Self { duration, curve }
+    }
+
+    pub fn linear(duration: Duration) -> Self {
+        Self::new(duration, FadeCurve::Linear)
+    }
+}
+
+/// A fade requested through an [AudioSink](crate::AudioSink), waiting to be picked up by the audio
+/// thread
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FadeCommand {
+    /// The volume to start from, or `None` to start from the current fade volume
+    pub from: Option<f32>,
+    pub to: f32,
+    pub fade: Fade,
+    /// Stop the sound when the fade completes
+    pub stop: bool,
+}
+
+/// A fade in progress on the audio thread
+#[derive(Debug)]
+pub(crate) struct FadeProgress {
+    from: f32,
+    to: f32,
+    curve: FadeCurve,
+    frames: u64,
+    frames_done: u64,
+    pub stop: bool,
+}
+
+impl FadeProgress {
+    pub(crate) fn new(command: FadeCommand, current_volume: f32, sample_rate: u32) -> Self {
+        Self {
+            from: command.from.unwrap_or(current_volume),
+            to: command.to,
+            curve: command.fade.curve,
+            frames: (command.fade.duration.as_secs_f64() * sample_rate as f64) as u64,
+            frames_done: 0,
+            stop: command.stop,
+        }
+    }
+
+    /// Advances the fade by a frame, returning the new fade volume
+    pub(crate) fn advance(&mut self) -> f32 {
+        self.frames_done = (self.frames_done + 1).min(self.frames);
+        let t = if self.frames == 0 {
+            1.0
+        } else {
+            self.frames_done as f32 / self.frames as f32
+        };
+        self.from + (self.to - self.from) * self.curve.apply(t)
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.frames_done >= self.frames
+    }
+}
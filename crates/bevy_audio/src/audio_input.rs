@@ -0,0 +1,151 @@
+use bevy_app::Events;
+use bevy_ecs::{Resources, World};
+use parking_lot::Mutex;
+use rodio::cpal::{
+    self,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Sample, SampleFormat, Stream, StreamConfig,
+};
+use std::{collections::VecDeque, sync::Arc};
+
+/// Controls audio capture from a microphone or other input device
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioInputSettings {
+    /// Record audio and send it as [AudioInputBuffer] events
+    pub capture: bool,
+    /// The name of the input device, as returned by [input_device_names]. `None` selects the
+    /// default device of the system.
+    pub device: Option<String>,
+}
+
+/// Audio recorded since the last frame. Samples are interleaved and between -1.0 and 1.0.
+#[derive(Debug, Clone)]
+pub struct AudioInputBuffer {
+    pub samples: Vec<f32>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+/// Returns the names of the audio input devices, which can be selected with
+/// [AudioInputSettings]
+pub fn input_device_names() -> Vec<String> {
+    cpal::default_host()
+        .input_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Samples kept while the app doesn't read them, so that a slow frame doesn't lose audio
+const MAX_QUEUED_SECONDS: usize = 2;
+
+struct CaptureStream {
+    _stream: Stream,
+    settings: AudioInputSettings,
+    channels: u16,
+    sample_rate: u32,
+    samples: Arc<Mutex<VecDeque<f32>>>,
+}
+
+/// Used internally to record audio from the current input device
+#[derive(Default)]
+pub struct AudioInput {
+    stream: Option<CaptureStream>,
+    /// Settings that failed to open a stream, to avoid retrying every frame
+    failed_settings: Option<AudioInputSettings>,
+}
+
+impl AudioInput {
+    fn open(settings: &AudioInputSettings) -> Option<CaptureStream> {
+        let host = cpal::default_host();
+        let device = match &settings.device {
+            Some(name) => host.input_devices().ok()?.find(|device| {
+                device
+                    .name()
+                    .map_or(false, |device_name| &device_name == name)
+            }),
+            None => host.default_input_device(),
+        }?;
+        let config = device.default_input_config().ok()?;
+        let sample_format = config.sample_format();
+        let config: StreamConfig = config.into();
+        let samples = Arc::new(Mutex::new(VecDeque::new()));
+        let max_samples =
+            config.sample_rate.0 as usize * config.channels as usize * MAX_QUEUED_SECONDS;
+
+        let stream = match sample_format {
+            SampleFormat::F32 => {
+                build_stream::<f32>(&device, &config, samples.clone(), max_samples)
+            }
+            SampleFormat::I16 => {
+                build_stream::<i16>(&device, &config, samples.clone(), max_samples)
+            }
+            SampleFormat::U16 => {
+                build_stream::<u16>(&device, &config, samples.clone(), max_samples)
+            }
+        }?;
+        stream.play().ok()?;
+
+        Some(CaptureStream {
+            _stream: stream,
+            settings: settings.clone(),
+            channels: config.channels,
+            sample_rate: config.sample_rate.0,
+            samples,
+        })
+    }
+}
+
+fn build_stream<T: Sample>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    samples: Arc<Mutex<VecDeque<f32>>>,
+    max_samples: usize,
+) -> Option<Stream> {
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                let mut samples = samples.lock();
+                samples.extend(data.iter().map(|sample| sample.to_f32()));
+                let overflow = samples.len().saturating_sub(max_samples);
+                samples.drain(..overflow);
+            },
+            |_error| {},
+        )
+        .ok()
+}
+
+/// Opens or closes the input stream to match [AudioInputSettings], and sends the audio recorded
+/// since the last frame as an [AudioInputBuffer] event
+pub fn audio_input_system(_world: &mut World, resources: &mut Resources) {
+    let mut audio_input = resources.get_thread_local_mut::<AudioInput>().unwrap();
+    let settings = resources.get::<AudioInputSettings>().unwrap();
+
+    if !settings.capture {
+        audio_input.stream = None;
+        audio_input.failed_settings = None;
+        return;
+    }
+    let is_open = audio_input
+        .stream
+        .as_ref()
+        .map_or(false, |stream| stream.settings == *settings);
+    if !is_open && audio_input.failed_settings.as_ref() != Some(&*settings) {
+        audio_input.stream = AudioInput::open(&settings);
+        if audio_input.stream.is_none() {
+            audio_input.failed_settings = Some(settings.clone());
+        }
+    }
+
+    if let Some(stream) = &audio_input.stream {
+        let samples: Vec<f32> = stream.samples.lock().drain(..).collect();
+        if !samples.is_empty() {
+            let mut events = resources.get_mut::<Events<AudioInputBuffer>>().unwrap();
+            events.send(AudioInputBuffer {
+                samples,
+                channels: stream.channels,
+                sample_rate: stream.sample_rate,
+            });
+        }
+    }
+}
@@ -1,16 +1,170 @@
-use crate::{Audio, AudioSource, Decodable};
+use crate::{
+    audio_analysis::AnalysisTaps, Audio, AudioSink, AudioSource, ControlledSource, Decodable,
+    GlobalVolume,
+};
 use bevy_asset::{Asset, Assets};
-use bevy_ecs::{Resources, World};
-use rodio::{OutputStream, OutputStreamHandle, Sink};
-use std::marker::PhantomData;
+use bevy_ecs::{FromResources, Resources, World};
+use rodio::{
+    cpal::{
+        self,
+        traits::{DeviceTrait, HostTrait},
+    },
+    dynamic_mixer::{self, DynamicMixer, DynamicMixerController},
+    OutputStream, OutputStreamHandle, Source,
+};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-/// Used internally to play audio on the current "audio device"
+/// Selects the device sounds are played on. Sounds that are playing when the device changes
+/// continue on the new device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioDeviceSettings {
+    /// The name of the output device, as returned by [output_device_names]. `None` selects the
+    /// default device of the system.
+    pub output_device: Option<String>,
+}
+
+/// Returns the names of the audio output devices, which can be selected with
+/// [AudioDeviceSettings]
+pub fn output_device_names() -> Vec<String> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the name of the device sounds play on when no device is selected
+pub fn default_output_device_name() -> Option<String> {
+    cpal::default_host()
+        .default_output_device()
+        .and_then(|device| device.name().ok())
+}
+
+fn open_stream(device_name: Option<&str>) -> (OutputStream, OutputStreamHandle) {
+    let device = device_name.and_then(|device_name| {
+        cpal::default_host().output_devices().ok()?.find(|device| {
+            device
+                .name()
+                .map_or(false, |name| name.as_str() == device_name)
+        })
+    });
+    // fall back to the default device if the selected one was unplugged
+    device
+        .and_then(|device| OutputStream::try_from_device(&device).ok())
+        .unwrap_or_else(|| OutputStream::try_default().unwrap())
+}
+
+const MIXER_CHANNELS: u16 = 2;
+const MIXER_SAMPLE_RATE: u32 = 44_100;
+
+/// Mixes the sounds of an [AudioOutput]. Plays silence while no sound is playing, so that the
+/// output stays open for the sounds played later.
+struct OutputMixer {
+    mixer: DynamicMixer<f32>,
+}
+
+impl Iterator for OutputMixer {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        Some(self.mixer.next().unwrap_or(0.0))
+    }
+}
+
+impl Source for OutputMixer {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        MIXER_CHANNELS
+    }
+
+    fn sample_rate(&self) -> u32 {
+        MIXER_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Opens an output stream on the given device, and plays a mixer on it whose output is recorded by
+/// `analysis_taps`
+fn open_mixer(
+    device_name: Option<&str>,
+    analysis_taps: &AnalysisTaps,
+) -> (OutputStream, Arc<DynamicMixerController<f32>>) {
+    let (stream, stream_handle) = open_stream(device_name);
+    let (mixer_controller, mixer) = dynamic_mixer::mixer(MIXER_CHANNELS, MIXER_SAMPLE_RATE);
+    stream_handle
+        .play_raw(analysis_taps.tap(OutputMixer { mixer }))
+        .unwrap();
+    (stream, mixer_controller)
+}
+
+struct ActiveSound<P> {
+    source: P,
+    sink: AudioSink,
+    /// Set to stop the sound on the current device without finishing its [AudioSink]
+    retired: Arc<AtomicBool>,
+}
+
+/// The output stream the sounds of every [AudioOutput] are mixed into, on the device selected by
+/// [AudioDeviceSettings]
+pub struct AudioDevice {
+    _stream: OutputStream,
+    mixer: Arc<DynamicMixerController<f32>>,
+    analysis_taps: AnalysisTaps,
+    device_name: Option<String>,
+    /// Incremented when the stream is opened on another device, for the outputs to move their
+    /// sounds to it
+    generation: u64,
+}
+
+impl FromResources for AudioDevice {
+    fn from_resources(resources: &Resources) -> Self {
+        let analysis_taps = resources
+            .get::<AnalysisTaps>()
+            .map(|taps| taps.clone())
+            .unwrap_or_default();
+        let (stream, mixer) = open_mixer(None, &analysis_taps);
+
+        Self {
+            _stream: stream,
+            mixer,
+            analysis_taps,
+            device_name: None,
+            generation: 0,
+        }
+    }
+}
+
+impl AudioDevice {
+    /// Opens the stream on the device named `device_name`, or the default device if it is `None`
+    fn set_device(&mut self, device_name: Option<String>) {
+        let (stream, mixer) = open_mixer(device_name.as_deref(), &self.analysis_taps);
+        self._stream = stream;
+        self.mixer = mixer;
+        self.device_name = device_name;
+        self.generation += 1;
+    }
+}
+
+/// Used internally to play audio of one source type on the [AudioDevice]
 pub struct AudioOutput<P = AudioSource>
 where
     P: Decodable,
 {
-    _stream: OutputStream,
-    stream_handle: OutputStreamHandle,
+    /// The generation of the [AudioDevice] stream the active sounds are playing on
+    generation: u64,
+    active_sounds: Vec<ActiveSound<P>>,
     phantom: PhantomData<P>,
 }
 
@@ -19,11 +173,9 @@ where
     P: Decodable,
 {
     fn default() -> Self {
-        let (stream, stream_handle) = OutputStream::try_default().unwrap();
-
         Self {
-            _stream: stream,
-            stream_handle,
+            generation: 0,
+            active_sounds: Vec::new(),
             phantom: PhantomData,
         }
     }
@@ -31,44 +183,99 @@ where
 
 impl<P> AudioOutput<P>
 where
-    P: Asset + Decodable,
+    P: Asset + Decodable + Clone,
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    fn play_source(&self, audio_source: &P) {
-        let sink = Sink::try_new(&self.stream_handle).unwrap();
-        sink.append(audio_source.decoder());
-        sink.detach();
+    fn play_source(
+        &mut self,
+        device: &AudioDevice,
+        audio_source: &P,
+        sink: AudioSink,
+        global_volume: &GlobalVolume,
+    ) {
+        let retired = Arc::new(AtomicBool::new(false));
+        let decodable = audio_source.clone();
+        let source = ControlledSource::new(
+            Box::new(move || decodable.decoder()),
+            sink.clone(),
+            global_volume.clone(),
+            retired.clone(),
+        );
+        device.mixer.add(source);
+
+        self.active_sounds.push(ActiveSound {
+            source: audio_source.clone(),
+            sink,
+            retired,
+        });
     }
 
-    fn try_play_queued(&self, audio_sources: &Assets<P>, audio: &mut Audio<P>) {
+    /// Moves playing sounds to the current stream of `device`
+    fn move_sounds(&mut self, device: &AudioDevice, global_volume: &GlobalVolume) {
+        self.generation = device.generation;
+        for sound in std::mem::take(&mut self.active_sounds) {
+            if sound.sink.is_finished() {
+                continue;
+            }
+            sound.retired.store(true, Ordering::Relaxed);
+            // continue where the sound was
+            sound.sink.seek(sound.sink.position());
+            self.play_source(device, &sound.source, sound.sink, global_volume);
+        }
+    }
+
+    fn try_play_queued(
+        &mut self,
+        device: &AudioDevice,
+        audio_sources: &Assets<P>,
+        audio: &mut Audio<P>,
+        global_volume: &GlobalVolume,
+    ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
         let mut i = 0;
         while i < len {
-            let audio_source_handle = queue.pop_back().unwrap();
-            if let Some(audio_source) = audio_sources.get(&audio_source_handle) {
-                self.play_source(audio_source);
+            let (audio_source_handle, sink) = queue.pop_back().unwrap();
+            if sink.is_finished() {
+                // stopped before it started playing
+            } else if let Some(audio_source) = audio_sources.get(&audio_source_handle) {
+                self.play_source(device, audio_source, sink, global_volume);
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
-                queue.push_front(audio_source_handle);
+                queue.push_front((audio_source_handle, sink));
             }
             i += 1;
         }
     }
 }
 
-/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource
+/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource, on the
+/// [AudioDevice] shared by every source type
 pub fn play_queued_audio_system<P: Asset>(_world: &mut World, resources: &mut Resources)
 where
-    P: Decodable,
+    P: Decodable + Clone,
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    let audio_output = resources.get_thread_local::<AudioOutput<P>>().unwrap();
+    let mut device = resources.get_thread_local_mut::<AudioDevice>().unwrap();
+    let mut audio_output = resources.get_thread_local_mut::<AudioOutput<P>>().unwrap();
     let mut audio = resources.get_mut::<Audio<P>>().unwrap();
+    let global_volume = resources.get::<GlobalVolume>().unwrap();
+    let device_settings = resources.get::<AudioDeviceSettings>().unwrap();
+
+    audio_output
+        .active_sounds
+        .retain(|sound| !sound.sink.is_finished());
+    // the output of the first source type to notice the settings changed reopens the stream
+    if device_settings.output_device != device.device_name {
+        device.set_device(device_settings.output_device.clone());
+    }
+    if audio_output.generation != device.generation {
+        audio_output.move_sounds(&device, &global_volume);
+    }
 
     if let Some(audio_sources) = resources.get::<Assets<P>>() {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio);
+        audio_output.try_play_queued(&device, &*audio_sources, &mut *audio, &global_volume);
     }
 }
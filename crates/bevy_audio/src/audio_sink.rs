@@ -0,0 +1,563 @@
+use crate::{
+    audio_effect::{EffectChain, EffectList},
+    audio_fade::{FadeCommand, FadeProgress},
+    AudioChannel, AudioEffect, Fade, GlobalVolume,
+};
+use parking_lot::Mutex;
+use rodio::{Sample, Source};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+const NO_SEEK: u64 = u64::MAX;
+
+/// Settings for a sound started with [Audio::play_with_settings](crate::Audio::play_with_settings)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackSettings {
+    /// Start again from the beginning when the end is reached
+    pub looped: bool,
+    /// Volume of the sound, 1.0 is the volume of the source
+    pub volume: f32,
+    /// Stereo position of the sound, from -1.0 (left) to 1.0 (right)
+    pub pan: f32,
+    /// Playback speed of the sound. Like a record player, this changes the pitch as well.
+    pub speed: f32,
+    /// Fade the sound in when it starts
+    pub fade_in: Option<Fade>,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self::ONCE
+    }
+}
+
+impl PlaybackSettings {
+    pub const ONCE: PlaybackSettings = PlaybackSettings {
+        looped: false,
+        volume: 1.0,
+        pan: 0.0,
+        speed: 1.0,
+        fade_in: None,
+    };
+    pub const LOOP: PlaybackSettings = PlaybackSettings {
+        looped: true,
+        ..PlaybackSettings::ONCE
+    };
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn with_pan(mut self, pan: f32) -> Self {
+        self.pan = pan;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn with_fade_in(mut self, fade: Fade) -> Self {
+        self.fade_in = Some(fade);
+        self
+    }
+}
+
+/// Controls a sound started with [Audio::play](crate::Audio::play).
+///
+/// Clones control the same sound. Commands sent before the sound starts playing (for example
+/// while its asset is still loading) are applied when it starts.
+#[derive(Debug, Clone, Default)]
+pub struct AudioSink {
+    state: Arc<SinkState>,
+    /// The channel of the sound, and its stop generation when the sound was started
+    channel: Option<(AudioChannel, u64)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct SinkState {
+    paused: AtomicBool,
+    looped: AtomicBool,
+    volume: AtomicU32,
+    pan: AtomicU32,
+    speed: AtomicU32,
+    stopped: AtomicBool,
+    finished: AtomicBool,
+    position_nanos: AtomicU64,
+    seek_nanos: AtomicU64,
+    effects: EffectList,
+    fade: Mutex<Option<FadeCommand>>,
+    fade_pending: AtomicBool,
+}
+
+impl SinkState {
+    pub(crate) fn send_fade(&self, command: FadeCommand) {
+        *self.fade.lock() = Some(command);
+        self.fade_pending.store(true, Ordering::Release);
+    }
+
+    fn take_fade(&self) -> Option<FadeCommand> {
+        if self.fade_pending.swap(false, Ordering::Acquire) {
+            self.fade.lock().take()
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for SinkState {
+    fn default() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            looped: AtomicBool::new(false),
+            volume: AtomicU32::new(1.0f32.to_bits()),
+            pan: AtomicU32::new(0.0f32.to_bits()),
+            speed: AtomicU32::new(1.0f32.to_bits()),
+            stopped: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            position_nanos: AtomicU64::new(0),
+            seek_nanos: AtomicU64::new(NO_SEEK),
+            effects: Default::default(),
+            fade: Mutex::new(None),
+            fade_pending: AtomicBool::new(false),
+        }
+    }
+}
+
+impl AudioSink {
+    pub(crate) fn new(settings: PlaybackSettings, channel: Option<&AudioChannel>) -> Self {
+        let sink = AudioSink {
+            state: Default::default(),
+            channel: channel.map(|channel| {
+                let generation = channel.state.stop_generation.load(Ordering::Relaxed);
+                (channel.clone(), generation)
+            }),
+        };
+        sink.set_looped(settings.looped);
+        sink.set_volume(settings.volume);
+        sink.set_pan(settings.pan);
+        sink.set_speed(settings.speed);
+        if let Some(fade) = settings.fade_in {
+            sink.fade_in(fade);
+        }
+        if let Some(channel) = channel {
+            channel.add_sink(&sink.state);
+        }
+        sink
+    }
+
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Relaxed)
+    }
+
+    /// Turns looping on or off. Turning it off lets the sound play to the end of the current loop.
+    pub fn set_looped(&self, looped: bool) {
+        self.state.looped.store(looped, Ordering::Relaxed);
+    }
+
+    pub fn is_looped(&self) -> bool {
+        self.state.looped.load(Ordering::Relaxed)
+    }
+
+    /// Sets the volume of the sound. 1.0 is the volume of the source.
+    pub fn set_volume(&self, volume: f32) {
+        self.state
+            .volume
+            .store(volume.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn volume(&self) -> f32 {
+        f32::from_bits(self.state.volume.load(Ordering::Relaxed))
+    }
+
+    /// Moves the sound between the left (-1.0) and right (1.0) speakers. Mono sounds are played in
+    /// stereo so that they can be panned.
+    pub fn set_pan(&self, pan: f32) {
+        self.state
+            .pan
+            .store(pan.max(-1.0).min(1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn pan(&self) -> f32 {
+        f32::from_bits(self.state.pan.load(Ordering::Relaxed))
+    }
+
+    /// Sets the playback speed of the sound, for example 0.5 to play it an octave lower at half
+    /// speed. Like a record player, this changes the pitch as well.
+    pub fn set_speed(&self, speed: f32) {
+        self.state
+            .speed
+            .store(speed.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn speed(&self) -> f32 {
+        f32::from_bits(self.state.speed.load(Ordering::Relaxed))
+    }
+
+    /// Fades the sound in from silence
+    pub fn fade_in(&self, fade: Fade) {
+        self.state.send_fade(FadeCommand {
+            from: Some(0.0),
+            to: 1.0,
+            fade,
+            stop: false,
+        });
+    }
+
+    /// Fades the sound out to silence, without stopping it
+    pub fn fade_out(&self, fade: Fade) {
+        self.state.send_fade(FadeCommand {
+            from: None,
+            to: 0.0,
+            fade,
+            stop: false,
+        });
+    }
+
+    /// Fades the sound out, then stops it
+    pub fn stop_with_fade(&self, fade: Fade) {
+        self.state.send_fade(FadeCommand {
+            from: None,
+            to: 0.0,
+            fade,
+            stop: true,
+        });
+    }
+
+    /// Stops the sound for good. Use [AudioSink::pause] to stop it temporarily.
+    pub fn stop(&self) {
+        self.state.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true once the sound or its channel was stopped, or it has played to the end
+    pub fn is_finished(&self) -> bool {
+        self.is_stopped() || self.state.finished.load(Ordering::Relaxed)
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.state.stopped.load(Ordering::Relaxed)
+            || self
+                .channel
+                .as_ref()
+                .map_or(false, |(channel, generation)| {
+                    channel.state.stop_generation.load(Ordering::Relaxed) != *generation
+                })
+    }
+
+    /// Replaces the effects applied to the sound
+    pub fn set_effects(&self, effects: Vec<AudioEffect>) {
+        self.state.effects.set(effects);
+    }
+
+    pub fn effects(&self) -> Vec<AudioEffect> {
+        self.state.effects.get()
+    }
+
+    /// The channel the sound was played in
+    pub fn channel(&self) -> Option<&AudioChannel> {
+        self.channel.as_ref().map(|(channel, _)| channel)
+    }
+
+    /// The volume of `output_channel`, including the volume of the sound's channel and its pan
+    fn output_volume(&self, output_channel: usize) -> f32 {
+        let pan = self.pan();
+        let pan_volume = match output_channel {
+            0 => (1.0 - pan).min(1.0),
+            1 => (1.0 + pan).min(1.0),
+            _ => 1.0,
+        };
+        self.volume() * pan_volume * self.channel().map_or(1.0, |channel| channel.volume())
+    }
+
+    /// Returns true if the sound or its channel is paused
+    fn is_silenced(&self) -> bool {
+        self.is_paused() || self.channel().map_or(false, |channel| channel.is_paused())
+    }
+
+    /// Moves playback to `position`. Seeking backwards decodes the sound again from the start.
+    pub fn seek(&self, position: Duration) {
+        self.state
+            .seek_nanos
+            .store(position.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// How far into the sound playback is
+    pub fn position(&self) -> Duration {
+        Duration::from_nanos(self.state.position_nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// Wraps a decoder to apply the commands sent through an [AudioSink]. Mono sources are played in
+/// stereo so that they can be panned.
+pub(crate) struct ControlledSource<S> {
+    source: S,
+    restart: Box<dyn FnMut() -> S + Send>,
+    sink: AudioSink,
+    global_volume: GlobalVolume,
+    /// Set when the sound moves to another output device
+    retired: Arc<AtomicBool>,
+    frames_read: u64,
+    /// The source frames the output is interpolated between when the speed isn't 1.0
+    previous_frame: Vec<f32>,
+    next_frame: Vec<f32>,
+    /// How far the output is between `previous_frame` and `next_frame`
+    fraction: f64,
+    interpolating: bool,
+    /// The source frame the output is mixed from, reused so the audio thread doesn't allocate
+    frame: Vec<f32>,
+    /// The output frame being played, and the index of the next sample in it
+    output: Vec<f32>,
+    output_index: usize,
+    fade_volume: f32,
+    fade: Option<FadeProgress>,
+    effects: EffectChain,
+    channel_effects: EffectChain,
+}
+
+impl<S> ControlledSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    pub(crate) fn new(
+        mut restart: Box<dyn FnMut() -> S + Send>,
+        sink: AudioSink,
+        global_volume: GlobalVolume,
+        retired: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            source: restart(),
+            restart,
+            sink,
+            global_volume,
+            retired,
+            frames_read: 0,
+            previous_frame: Vec::new(),
+            next_frame: Vec::new(),
+            fraction: 0.0,
+            interpolating: false,
+            frame: Vec::new(),
+            output: Vec::new(),
+            output_index: 0,
+            fade_volume: 1.0,
+            fade: None,
+            effects: Default::default(),
+            channel_effects: Default::default(),
+        }
+    }
+
+    fn source_channels(&self) -> u16 {
+        self.source.channels().max(1)
+    }
+
+    fn position(&self) -> Duration {
+        let frames = self.frames_read.saturating_sub(1);
+        Duration::from_secs_f64(frames as f64 / self.source.sample_rate().max(1) as f64)
+    }
+
+    fn restart(&mut self) {
+        self.source = (self.restart)();
+        self.frames_read = 0;
+    }
+
+    /// Reads the next frame of the source into `frame`, starting over at the end of looped sounds.
+    /// Returns false at the end of the sound.
+    fn read_frame(&mut self, frame: &mut Vec<f32>) -> bool {
+        frame.clear();
+        let channels = self.source_channels() as usize;
+        while frame.len() < channels {
+            match self.source.next() {
+                Some(sample) => frame.push(sample.to_f32()),
+                None => break,
+            }
+        }
+
+        if frame.is_empty() {
+            if self.sink.is_looped() && self.frames_read > 0 {
+                self.restart();
+                return self.read_frame(frame);
+            }
+            return false;
+        }
+        frame.resize(channels, 0.0);
+        self.frames_read += 1;
+        true
+    }
+
+    fn seek(&mut self, target: Duration) {
+        if target < self.position() {
+            self.restart();
+        }
+        let mut frame = Vec::new();
+        while self.position() < target && self.read_frame(&mut frame) {}
+        self.interpolating = false;
+    }
+
+    /// Resamples the source to play it at the speed of the sink, writing the next frame to
+    /// `frame`. Returns false at the end of the sound.
+    fn next_frame(&mut self, frame: &mut Vec<f32>) -> bool {
+        let mut previous_frame = std::mem::take(&mut self.previous_frame);
+        let mut next_frame = std::mem::take(&mut self.next_frame);
+        let has_frame = if self.interpolating {
+            self.fraction += self.sink.speed() as f64;
+            let mut has_frame = true;
+            while self.fraction >= 1.0 && has_frame {
+                self.fraction -= 1.0;
+                std::mem::swap(&mut previous_frame, &mut next_frame);
+                has_frame = self.read_frame(&mut next_frame);
+            }
+            has_frame
+        } else {
+            self.fraction = 0.0;
+            self.interpolating = self.read_frame(&mut previous_frame);
+            if self.interpolating && !self.read_frame(&mut next_frame) {
+                next_frame = previous_frame.clone();
+            }
+            self.interpolating
+        };
+
+        if has_frame {
+            let fraction = self.fraction as f32;
+            frame.clear();
+            frame.extend(
+                previous_frame
+                    .iter()
+                    .zip(next_frame.iter())
+                    .map(|(previous, next)| previous + (next - previous) * fraction),
+            );
+        }
+        self.previous_frame = previous_frame;
+        self.next_frame = next_frame;
+        has_frame
+    }
+
+    /// Prepares the next output frame. Returns false at the end of the sound.
+    fn next_output(&mut self) -> bool {
+        let seek = self.sink.state.seek_nanos.swap(NO_SEEK, Ordering::Relaxed);
+        if seek != NO_SEEK {
+            self.seek(Duration::from_nanos(seek));
+        }
+        let position = self.position().as_nanos() as u64;
+        self.sink
+            .state
+            .position_nanos
+            .store(position, Ordering::Relaxed);
+
+        let (channels, sample_rate) = (self.source_channels(), self.source.sample_rate());
+        self.effects
+            .update(&self.sink.state.effects, channels, sample_rate);
+        if let Some(channel) = self.sink.channel() {
+            self.channel_effects
+                .update(&channel.state.effects, channels, sample_rate);
+        }
+
+        let mut output = std::mem::take(&mut self.output);
+        output.clear();
+        self.output_index = 0;
+        if self.sink.is_silenced() {
+            output.resize(self.channels() as usize, 0.0);
+            self.output = output;
+            return true;
+        }
+
+        let mut frame = std::mem::take(&mut self.frame);
+        if !self.next_frame(&mut frame) {
+            self.frame = frame;
+            self.output = output;
+            return false;
+        }
+        if let Some(command) = self.sink.state.take_fade() {
+            self.fade = Some(FadeProgress::new(command, self.fade_volume, sample_rate));
+        }
+        if let Some(fade) = &mut self.fade {
+            self.fade_volume = fade.advance();
+            if fade.is_done() {
+                if fade.stop {
+                    self.sink.stop();
+                }
+                self.fade = None;
+            }
+        }
+
+        let global_volume = self.global_volume.get() * self.fade_volume;
+        let has_effects = !self.effects.is_empty() || !self.channel_effects.is_empty();
+        for (channel, &sample) in frame.iter().enumerate() {
+            let sample = if has_effects {
+                self.channel_effects
+                    .process(channel, self.effects.process(channel, sample))
+            } else {
+                sample
+            };
+            output.push(sample * global_volume * self.sink.output_volume(channel));
+            if frame.len() == 1 {
+                output.push(sample * global_volume * self.sink.output_volume(1));
+            }
+        }
+        self.frame = frame;
+        self.output = output;
+        true
+    }
+}
+
+impl<S> Iterator for ControlledSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sink.is_stopped() || self.retired.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        // commands are only applied between frames so that channels stay interleaved correctly
+        if self.output_index >= self.output.len() && !self.next_output() {
+            self.sink.state.finished.store(true, Ordering::Relaxed);
+            return None;
+        }
+        let sample = self.output[self.output_index];
+        self.output_index += 1;
+        Some(sample)
+    }
+}
+
+impl<S> Source for ControlledSource<S>
+where
+    S: Source,
+    S::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        match self.source_channels() {
+            1 => 2,
+            channels => channels,
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -1,8 +1,18 @@
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{
+    AssetIoError, AssetLoader, AssetRead, AssetServer, AssetStream, LoadContext, LoadedAsset,
+};
 use bevy_reflect::TypeUuid;
-use bevy_utils::BoxedFuture;
-use std::{io::Cursor, sync::Arc};
+use bevy_utils::{tracing::warn, BoxedFuture, Uuid};
+use rodio::{decoder::DecoderError, Source};
+use std::{
+    fmt,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use thiserror::Error;
 
 /// A source of audio data
 #[derive(Debug, Clone, TypeUuid)]
@@ -17,6 +27,60 @@ impl AsRef<[u8]> for AudioSource {
     }
 }
 
+/// A source of audio data that is decoded incrementally as it plays, instead of being read into
+/// memory up front. Prefer this over [AudioSource] for long tracks like music.
+#[derive(Clone, TypeUuid)]
+#[uuid = "0e3bd5b1-5a4b-4d0c-9c9b-b8a2f2f5c1d6"]
+pub struct StreamingAudioSource {
+    path: PathBuf,
+    stream: Arc<dyn AssetStream>,
+}
+
+impl fmt::Debug for StreamingAudioSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingAudioSource")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// An error that occurs when opening a [StreamingAudioSource]
+#[derive(Error, Debug)]
+pub enum StreamingAudioError {
+    #[error("Failed to open the audio file.")]
+    Io(#[from] AssetIoError),
+    #[error("Failed to decode the audio file.")]
+    Decoder(#[from] DecoderError),
+}
+
+impl StreamingAudioSource {
+    /// Opens the audio file at the asset `path` through the [AssetIo](bevy_asset::AssetIo) of the
+    /// `asset_server`, after checking that the file can be decoded. Backends that can't read files
+    /// incrementally, like the web, load the encoded file into memory and decode it as it plays.
+    pub async fn open<P: AsRef<Path>>(
+        asset_server: &AssetServer,
+        path: P,
+    ) -> Result<Self, StreamingAudioError> {
+        let path = path.as_ref();
+        let stream = asset_server.asset_io().stream_path(path).await?;
+        open_decoder(&*stream)?;
+        Ok(Self {
+            path: path.to_owned(),
+            stream,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn open_decoder(
+    stream: &dyn AssetStream,
+) -> Result<rodio::Decoder<Box<dyn AssetRead>>, StreamingAudioError> {
+    Ok(rodio::Decoder::new(stream.reader()?)?)
+}
+
 /// Loads mp3 files as [AudioSource] [Assets](bevy_asset::Assets)
 #[derive(Default)]
 pub struct Mp3Loader;
@@ -32,6 +96,10 @@ impl AssetLoader for Mp3Loader {
     fn extensions(&self) -> &[&str] {
         &["mp3", "flac", "wav", "ogg"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(AudioSource::TYPE_UUID)
+    }
 }
 
 pub trait Decodable: Send + Sync + 'static {
@@ -47,3 +115,61 @@ impl Decodable for AudioSource {
         rodio::Decoder::new(Cursor::new(self.clone())).unwrap()
     }
 }
+
+/// Decodes a [StreamingAudioSource], or plays nothing if its file can no longer be decoded
+pub struct StreamingDecoder {
+    decoder: Option<rodio::Decoder<Box<dyn AssetRead>>>,
+}
+
+impl Iterator for StreamingDecoder {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        self.decoder.as_mut()?.next()
+    }
+}
+
+impl Source for StreamingDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.decoder
+            .as_ref()
+            .map_or(Some(0), |decoder| decoder.current_frame_len())
+    }
+
+    fn channels(&self) -> u16 {
+        self.decoder
+            .as_ref()
+            .map_or(1, |decoder| decoder.channels())
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.decoder
+            .as_ref()
+            .map_or(44_100, |decoder| decoder.sample_rate())
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.decoder
+            .as_ref()
+            .map_or(Some(Duration::from_secs(0)), |decoder| {
+                decoder.total_duration()
+            })
+    }
+}
+
+impl Decodable for StreamingAudioSource {
+    type Decoder = StreamingDecoder;
+
+    /// Opens a new reader, which is done on the audio thread when the sound starts over. Files
+    /// that were moved or can no longer be decoded since the source was opened end the sound.
+    fn decoder(&self) -> Self::Decoder {
+        let decoder = match open_decoder(&*self.stream) {
+            Ok(decoder) => Some(decoder),
+            Err(error) => {
+                warn!("Failed to stream {}: {}", self.path.display(), error);
+                None
+            }
+        };
+        StreamingDecoder { decoder }
+    }
+}
@@ -1,15 +1,38 @@
 mod audio;
+mod audio_analysis;
+mod audio_channel;
+mod audio_effect;
+mod audio_fade;
+#[cfg(not(target_arch = "wasm32"))]
+mod audio_input;
 mod audio_output;
+mod audio_sink;
 mod audio_source;
+mod procedural_audio;
 
 pub use audio::*;
+pub use audio_analysis::{AudioAnalysis, AudioAnalysisSettings};
+pub use audio_channel::*;
+pub use audio_effect::AudioEffect;
+pub use audio_fade::{Fade, FadeCurve};
+#[cfg(not(target_arch = "wasm32"))]
+pub use audio_input::*;
 pub use audio_output::*;
+pub use audio_sink::*;
 pub use audio_source::*;
+pub use procedural_audio::*;
 
 pub mod prelude {
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable};
+    pub use crate::{
+        Audio, AudioAnalysis, AudioAnalysisSettings, AudioChannel, AudioChannels,
+        AudioDeviceSettings, AudioEffect, AudioOutput, AudioSink, AudioSource, Decodable, Fade,
+        FadeCurve, GlobalVolume, PlaybackSettings, ProceduralAudio, StreamingAudioSource,
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use crate::{AudioInputBuffer, AudioInputSettings};
 }
 
+use audio_analysis::{audio_analysis_system, AnalysisTaps};
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
 
@@ -19,10 +42,39 @@ pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.init_thread_local_resource::<AudioOutput<AudioSource>>()
+        // the outputs register with the analysis taps when they are created
+        app.init_resource::<AnalysisTaps>()
+            .init_resource::<AudioAnalysisSettings>()
+            .init_resource::<AudioAnalysis>()
+            .add_system_to_stage(stage::PRE_UPDATE, audio_analysis_system)
+            .init_thread_local_resource::<AudioDevice>()
+            .init_thread_local_resource::<AudioOutput<AudioSource>>()
             .add_asset::<AudioSource>()
             .init_asset_loader::<Mp3Loader>()
+            .init_resource::<AudioChannels>()
+            .init_resource::<GlobalVolume>()
+            .init_resource::<AudioDeviceSettings>()
             .init_resource::<Audio<AudioSource>>()
-            .add_system_to_stage(stage::POST_UPDATE, play_queued_audio_system::<AudioSource>);
+            .add_system_to_stage(stage::POST_UPDATE, play_queued_audio_system::<AudioSource>)
+            .init_thread_local_resource::<AudioOutput<ProceduralAudio>>()
+            .add_asset::<ProceduralAudio>()
+            .init_resource::<Audio<ProceduralAudio>>()
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                play_queued_audio_system::<ProceduralAudio>,
+            )
+            .init_thread_local_resource::<AudioOutput<StreamingAudioSource>>()
+            .add_asset::<StreamingAudioSource>()
+            .init_resource::<Audio<StreamingAudioSource>>()
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                play_queued_audio_system::<StreamingAudioSource>,
+            );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_thread_local_resource::<AudioInput>()
+            .init_resource::<AudioInputSettings>()
+            .add_event::<AudioInputBuffer>()
+            .add_system_to_stage(stage::PRE_UPDATE, audio_input_system);
     }
 }
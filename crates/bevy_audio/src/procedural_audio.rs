@@ -0,0 +1,182 @@
+use crate::Decodable;
+use bevy_reflect::TypeUuid;
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+type MakeGenerator = dyn Fn() -> Box<dyn FnMut() -> Option<f32> + Send + Sync> + Send + Sync;
+
+/// Audio that is generated while it plays instead of being decoded from a file, for example by a
+/// synthesizer or a voice chat connection.
+///
+/// Add it to [Assets](bevy_asset::Assets) and play the handle with an
+/// `Audio<ProceduralAudio>` resource.
+#[derive(Clone, TypeUuid)]
+#[uuid = "5b0c1c43-4d5e-4b5f-9e0c-4bd5b2b9b7e1"]
+pub struct ProceduralAudio {
+    channels: u16,
+    sample_rate: u32,
+    kind: ProceduralAudioKind,
+}
+
+#[derive(Clone)]
+enum ProceduralAudioKind {
+    Generator(Arc<MakeGenerator>),
+    Stream(Arc<StreamState>),
+}
+
+struct StreamState {
+    samples: Mutex<VecDeque<f32>>,
+    closed: AtomicBool,
+}
+
+impl ProceduralAudio {
+    /// Creates audio from a generator returning interleaved samples between -1.0 and 1.0. The
+    /// sound ends when the generator returns `None`.
+    ///
+    /// `make_generator` is called every time the sound starts, and again when it loops or seeks
+    /// backwards.
+    pub fn from_generator<F, G>(channels: u16, sample_rate: u32, make_generator: F) -> Self
+    where
+        F: Fn() -> G + Send + Sync + 'static,
+        G: FnMut() -> Option<f32> + Send + Sync + 'static,
+    {
+        Self {
+            channels,
+            sample_rate,
+            kind: ProceduralAudioKind::Generator(Arc::new(move || Box::new(make_generator()))),
+        }
+    }
+
+    /// Creates audio that plays the samples pushed to the returned [AudioStream]. Silence is played
+    /// while no samples are queued, and the sound ends once the stream is dropped and its queued
+    /// samples were played.
+    pub fn stream(channels: u16, sample_rate: u32) -> (Self, AudioStream) {
+        let state = Arc::new(StreamState {
+            samples: Mutex::new(VecDeque::new()),
+            closed: AtomicBool::new(false),
+        });
+        let audio = Self {
+            channels,
+            sample_rate,
+            kind: ProceduralAudioKind::Stream(state.clone()),
+        };
+        (audio, AudioStream { state })
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+/// Feeds samples to audio created with [ProceduralAudio::stream]
+pub struct AudioStream {
+    state: Arc<StreamState>,
+}
+
+impl AudioStream {
+    /// Queues interleaved samples between -1.0 and 1.0. Push whole frames (a sample for each
+    /// channel) to keep the channels in order.
+    pub fn push(&self, samples: &[f32]) {
+        self.state.samples.lock().extend(samples.iter().copied());
+    }
+
+    /// The number of samples that haven't been played yet
+    pub fn queued_len(&self) -> usize {
+        self.state.samples.lock().len()
+    }
+}
+
+impl Drop for AudioStream {
+    fn drop(&mut self) {
+        self.state.closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Plays a [ProceduralAudio]
+pub struct ProceduralAudioDecoder {
+    channels: u16,
+    sample_rate: u32,
+    kind: DecoderKind,
+}
+
+enum DecoderKind {
+    Generator(Box<dyn FnMut() -> Option<f32> + Send + Sync>),
+    Stream {
+        state: Arc<StreamState>,
+        buffer: VecDeque<f32>,
+    },
+}
+
+/// Samples taken from an [AudioStream] at once, to avoid locking it for every sample
+const STREAM_BATCH_SIZE: usize = 1024;
+
+impl Iterator for ProceduralAudioDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        match &mut self.kind {
+            DecoderKind::Generator(generator) => generator(),
+            DecoderKind::Stream { state, buffer } => {
+                if buffer.is_empty() {
+                    let mut samples = state.samples.lock();
+                    let count = samples.len().min(STREAM_BATCH_SIZE);
+                    buffer.extend(samples.drain(..count));
+                }
+                match buffer.pop_front() {
+                    Some(sample) => Some(sample),
+                    None if state.closed.load(Ordering::Relaxed) => None,
+                    None => Some(0.0),
+                }
+            }
+        }
+    }
+}
+
+impl rodio::Source for ProceduralAudioDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Decodable for ProceduralAudio {
+    type Decoder = ProceduralAudioDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        ProceduralAudioDecoder {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            kind: match &self.kind {
+                ProceduralAudioKind::Generator(make_generator) => {
+                    DecoderKind::Generator(make_generator())
+                }
+                ProceduralAudioKind::Stream(state) => DecoderKind::Stream {
+                    state: state.clone(),
+                    buffer: VecDeque::new(),
+                },
+            },
+        }
+    }
+}
@@ -1,6 +1,7 @@
 mod bytes;
 mod float_ord;
 mod label;
+mod name;
 mod task_pool_options;
 mod time;
 
@@ -10,11 +11,15 @@ use bevy_reflect::RegisterTypeBuilder;
 pub use bytes::*;
 pub use float_ord::*;
 pub use label::*;
+pub use name::*;
 pub use task_pool_options::DefaultTaskPoolOptions;
 pub use time::*;
 
 pub mod prelude {
-    pub use crate::{DefaultTaskPoolOptions, EntityLabels, Labels, Time, Timer};
+    pub use crate::{
+        DefaultTaskPoolOptions, EntityLabels, FixedTime, FixedUpdate, FrameLimiter, Labels, Name,
+        Time, Timer,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -32,11 +37,18 @@ impl Plugin for CorePlugin {
             .create_default_pools(app.resources_mut());
 
         app.init_resource::<Time>()
+            .init_resource::<FixedTime>()
+            .init_resource::<FrameLimiter>()
             .init_resource::<EntityLabels>()
             .register_type::<Option<String>>()
             .register_type::<Range<f32>>()
             .register_type::<Timer>()
+            .register_type::<Name>()
             .add_system_to_stage(stage::FIRST, time_system)
             .add_system_to_stage(stage::PRE_UPDATE, entity_labels_system);
+
+        // threads can't sleep on the web, where the browser paces frames
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_system_to_stage(stage::LAST, frame_limiter_system);
     }
 }
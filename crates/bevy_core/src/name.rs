@@ -0,0 +1,42 @@
+use bevy_reflect::{Reflect, ReflectComponent};
+use std::fmt;
+
+/// The name of an entity, used to find it in hierarchies loaded from files, like the nodes of a
+/// glTF scene, and to show it when debugging
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct Name {
+    name: String,
+}
+
+impl Name {
+    pub fn new(name: impl Into<String>) -> Self {
+        Name { name: name.into() }
+    }
+
+    pub fn set(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Name::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Name::new(name)
+    }
+}
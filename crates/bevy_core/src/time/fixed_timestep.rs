@@ -0,0 +1,194 @@
+use crate::Time;
+use bevy_ecs::{
+    ArchetypeComponent, IntoSystem, Resources, System, SystemId, ThreadLocalExecution, TypeAccess,
+    World,
+};
+use bevy_utils::Duration;
+use std::{any::TypeId, borrow::Cow};
+
+/// The fixed timestep of [FixedUpdate] systems, and the time accumulated towards the next step
+#[derive(Debug, Clone)]
+pub struct FixedTime {
+    step: Duration,
+    accumulator: Duration,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        FixedTime::from_seconds(1.0 / 60.0)
+    }
+}
+
+impl FixedTime {
+    pub fn new(step: Duration) -> Self {
+        FixedTime {
+            step,
+            accumulator: Duration::from_secs(0),
+        }
+    }
+
+    pub fn from_seconds(seconds: f64) -> Self {
+        FixedTime::new(Duration::from_secs_f64(seconds))
+    }
+
+    /// The time between fixed steps
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    #[inline]
+    pub fn step_seconds(&self) -> f32 {
+        self.step.as_secs_f32()
+    }
+
+    pub fn set_step(&mut self, step: Duration) {
+        self.step = step;
+    }
+
+    /// The time accumulated since the last fixed step, which is less than a step once the fixed
+    /// steps of the frame ran
+    #[inline]
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// How far the frame is between the last fixed step and the next one, from 0 to 1. State
+    /// updated in fixed steps is drawn interpolated by this fraction between its last two steps.
+    pub fn overstep_fraction(&self) -> f32 {
+        if self.step == Duration::from_secs(0) {
+            return 0.0;
+        }
+        (self.accumulator.as_secs_f64() / self.step.as_secs_f64()) as f32
+    }
+
+    /// Adds the time of a frame towards the next steps
+    pub fn accumulate(&mut self, delta: Duration) {
+        self.accumulator += delta;
+    }
+
+    /// Takes a step from the accumulated time, and returns whether there was enough for it
+    pub fn expend(&mut self) -> bool {
+        if self.step > Duration::from_secs(0) && self.accumulator >= self.step {
+            self.accumulator -= self.step;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A system that runs its systems once per [FixedTime] step, zero or more times per frame,
+/// whatever the frame rate. The steps follow the [Time] delta, so they slow down and stop with
+/// it. The systems run one after the other, like thread local systems.
+///
+/// ```ignore
+/// app.add_system(FixedUpdate::new().with_system(physics_system.system()));
+/// ```
+pub struct FixedUpdate {
+    systems: Vec<Box<dyn System<Input = (), Output = ()>>>,
+    resource_access: TypeAccess<TypeId>,
+    archetype_component_access: TypeAccess<ArchetypeComponent>,
+    id: SystemId,
+}
+
+impl Default for FixedUpdate {
+    fn default() -> Self {
+        FixedUpdate {
+            systems: Vec::new(),
+            resource_access: TypeAccess::default(),
+            archetype_component_access: TypeAccess::default(),
+            id: SystemId::new(),
+        }
+    }
+}
+
+impl FixedUpdate {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_system<S, Params, IntoS>(mut self, system: IntoS) -> Self
+    where
+        S: System<Input = (), Output = ()>,
+        IntoS: IntoSystem<Params, S>,
+    {
+        self.systems.push(Box::new(system.system()));
+        self
+    }
+}
+
+impl System for FixedUpdate {
+    type Input = ();
+    type Output = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        "FixedUpdate".into()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.systems.iter().all(|system| system.is_initialized())
+    }
+
+    fn update(&mut self, _world: &World) {}
+
+    fn archetype_component_access(&self) -> &TypeAccess<ArchetypeComponent> {
+        &self.archetype_component_access
+    }
+
+    fn resource_access(&self) -> &TypeAccess<TypeId> {
+        &self.resource_access
+    }
+
+    fn thread_local_execution(&self) -> ThreadLocalExecution {
+        ThreadLocalExecution::Immediate
+    }
+
+    unsafe fn run_unsafe(
+        &mut self,
+        _input: (),
+        _world: &World,
+        _resources: &Resources,
+    ) -> Option<()> {
+        Some(())
+    }
+
+    fn run_thread_local(&mut self, world: &mut World, resources: &mut Resources) {
+        let delta = resources.get::<Time>().unwrap().delta();
+        resources.get_mut::<FixedTime>().unwrap().accumulate(delta);
+        while resources.get_mut::<FixedTime>().unwrap().expend() {
+            for system in self.systems.iter_mut() {
+                system.update(world);
+                system.run((), world, resources);
+                system.run_thread_local(world, resources);
+            }
+        }
+    }
+
+    fn initialize(&mut self, world: &mut World, resources: &mut Resources) {
+        for system in self.systems.iter_mut() {
+            system.initialize(world, resources);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedTime;
+    use bevy_utils::Duration;
+
+    #[test]
+    fn steps_are_expended_from_the_accumulated_time() {
+        let mut fixed_time = FixedTime::new(Duration::from_millis(20));
+        fixed_time.accumulate(Duration::from_millis(50));
+        assert!(fixed_time.expend());
+        assert!(fixed_time.expend());
+        assert!(!fixed_time.expend());
+        assert_eq!(fixed_time.accumulator(), Duration::from_millis(10));
+        assert_eq!(fixed_time.overstep_fraction(), 0.5);
+    }
+}
@@ -0,0 +1,78 @@
+use bevy_ecs::ResMut;
+use bevy_utils::{Duration, Instant};
+
+/// Caps the frame rate of the app by waiting at the end of every frame, independently of the
+/// present mode of its windows. Apps running on battery or in the background use it to not update
+/// more often than they need to.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    /// The maximum number of frames per second, or `None` to not limit the frame rate
+    pub max_fps: Option<f64>,
+    /// The time before the end of a frame when the limiter stops sleeping and spins instead.
+    /// Sleeping overshoots by up to a few milliseconds on most platforms, so spinning keeps frames
+    /// evenly paced at the cost of some CPU time.
+    pub spin_duration: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        FrameLimiter {
+            max_fps: None,
+            spin_duration: Duration::from_millis(2),
+            last_frame: None,
+        }
+    }
+}
+
+impl FrameLimiter {
+    pub fn from_fps(max_fps: f64) -> Self {
+        FrameLimiter {
+            max_fps: Some(max_fps),
+            ..Default::default()
+        }
+    }
+
+    /// The shortest duration of a frame
+    pub fn frame_duration(&self) -> Option<Duration> {
+        self.max_fps
+            .filter(|max_fps| *max_fps > 0.0)
+            .map(|max_fps| Duration::from_secs_f64(1.0 / max_fps))
+    }
+}
+
+/// Waits until the frame took at least the [FrameLimiter::frame_duration]
+pub fn frame_limiter_system(mut frame_limiter: ResMut<FrameLimiter>) {
+    if let (Some(frame_duration), Some(last_frame)) =
+        (frame_limiter.frame_duration(), frame_limiter.last_frame)
+    {
+        let frame_end = last_frame + frame_duration;
+        let now = Instant::now();
+        if frame_end > now {
+            let remaining = frame_end - now;
+            if remaining > frame_limiter.spin_duration {
+                std::thread::sleep(remaining - frame_limiter.spin_duration);
+            }
+            while Instant::now() < frame_end {
+                std::hint::spin_loop();
+            }
+        }
+    }
+    frame_limiter.last_frame = Some(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameLimiter;
+    use bevy_utils::Duration;
+
+    #[test]
+    fn frame_duration_from_fps() {
+        assert_eq!(
+            FrameLimiter::from_fps(50.0).frame_duration(),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(FrameLimiter::from_fps(0.0).frame_duration(), None);
+        assert_eq!(FrameLimiter::default().frame_duration(), None);
+    }
+}
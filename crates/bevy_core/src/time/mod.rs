@@ -1,6 +1,10 @@
+mod fixed_timestep;
+mod frame_limiter;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
+pub use fixed_timestep::*;
+pub use frame_limiter::*;
 pub use time::*;
 pub use timer::*;
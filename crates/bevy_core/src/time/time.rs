@@ -2,12 +2,20 @@ use bevy_ecs::ResMut;
 use bevy_utils::{Duration, Instant};
 
 /// Tracks elapsed time since the last update and since the App has started
+///
+/// The delta is scaled by the relative speed of time, and is zero while time is paused, so that
+/// systems using it slow down or stop with the game. The raw delta is the real time between
+/// updates, for the systems that keep running at the same pace, like the ui of a pause menu.
 #[derive(Debug)]
 pub struct Time {
     delta: Duration,
     last_update: Option<Instant>,
     delta_seconds_f64: f64,
     delta_seconds: f32,
+    raw_delta: Duration,
+    elapsed: Duration,
+    relative_speed: f64,
+    paused: bool,
     seconds_since_startup: f64,
     startup: Instant,
 }
@@ -21,6 +29,10 @@ impl Default for Time {
             delta_seconds_f64: 0.0,
             seconds_since_startup: 0.0,
             delta_seconds: 0.0,
+            raw_delta: Duration::from_secs(0),
+            elapsed: Duration::from_secs(0),
+            relative_speed: 1.0,
+            paused: false,
         }
     }
 }
@@ -33,9 +45,17 @@ impl Time {
 
     pub(crate) fn update_with_instant(&mut self, instant: Instant) {
         if let Some(last_update) = self.last_update {
-            self.delta = instant - last_update;
+            self.raw_delta = instant - last_update;
+            self.delta = if self.paused {
+                Duration::from_secs(0)
+            } else if self.relative_speed == 1.0 {
+                self.raw_delta
+            } else {
+                self.raw_delta.mul_f64(self.relative_speed)
+            };
             self.delta_seconds_f64 = self.delta.as_secs_f64();
             self.delta_seconds = self.delta.as_secs_f32();
+            self.elapsed += self.delta;
         }
 
         let duration_since_startup = instant - self.startup;
@@ -61,7 +81,73 @@ impl Time {
         self.delta_seconds_f64
     }
 
-    /// The time since startup in seconds
+    /// The real time between the current and last tick, which isn't scaled or paused
+    #[inline]
+    pub fn raw_delta(&self) -> Duration {
+        self.raw_delta
+    }
+
+    /// The real time between the current and last tick as [`f32`] seconds
+    #[inline]
+    pub fn raw_delta_seconds(&self) -> f32 {
+        self.raw_delta.as_secs_f32()
+    }
+
+    /// The real time between the current and last tick as [`f64`] seconds
+    #[inline]
+    pub fn raw_delta_seconds_f64(&self) -> f64 {
+        self.raw_delta.as_secs_f64()
+    }
+
+    /// The sum of the scaled deltas since startup, which doesn't advance while time is paused
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The sum of the scaled deltas since startup in seconds
+    #[inline]
+    pub fn elapsed_seconds_f64(&self) -> f64 {
+        self.elapsed.as_secs_f64()
+    }
+
+    /// How fast time passes compared to real time: 0.5 is slow motion and 2.0 is twice as fast
+    #[inline]
+    pub fn relative_speed(&self) -> f64 {
+        self.relative_speed
+    }
+
+    /// Sets how fast time passes compared to real time, from the next tick
+    ///
+    /// # Panics
+    ///
+    /// Panics if the speed is negative or not finite
+    pub fn set_relative_speed(&mut self, relative_speed: f64) {
+        assert!(
+            relative_speed.is_finite() && relative_speed >= 0.0,
+            "tried to set the relative speed of time to {}",
+            relative_speed
+        );
+        self.relative_speed = relative_speed;
+    }
+
+    /// Stops time from the next tick: the delta is zero until time is unpaused
+    #[inline]
+    pub fn pause(&mut self) {
+        self.paused = true
+    }
+
+    #[inline]
+    pub fn unpause(&mut self) {
+        self.paused = false
+    }
+
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The real time since startup in seconds
     #[inline]
     pub fn seconds_since_startup(&self) -> f64 {
         self.seconds_since_startup
@@ -143,4 +229,24 @@ mod tests {
         );
         assert_eq!(time.delta_seconds(), time.delta().as_secs_f32());
     }
+
+    #[test]
+    fn scaled_and_paused_delta() {
+        let mut time = Time::default();
+        let start_instant = Instant::now();
+        time.update_with_instant(start_instant);
+
+        time.set_relative_speed(0.5);
+        time.update_with_instant(start_instant + Duration::from_millis(100));
+        assert_eq!(time.raw_delta(), Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(50));
+        assert_eq!(time.elapsed(), Duration::from_millis(50));
+
+        time.pause();
+        time.update_with_instant(start_instant + Duration::from_millis(200));
+        assert_eq!(time.raw_delta(), Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_secs(0));
+        assert_eq!(time.delta_seconds(), 0.0);
+        assert_eq!(time.elapsed(), Duration::from_millis(50));
+    }
 }
@@ -75,6 +75,11 @@ impl Diagnostic {
         }
     }
 
+    /// The measurements in the history, from the oldest to the newest
+    pub fn measurements(&self) -> impl Iterator<Item = &DiagnosticMeasurement> {
+        self.history.iter().rev()
+    }
+
     pub fn history_len(&self) -> usize {
         self.history.len()
     }
@@ -6,8 +6,10 @@ use gilrs::{EventType, Gilrs};
 
 pub fn gilrs_event_startup_system(_world: &mut World, resources: &mut Resources) {
     let gilrs = resources.get_thread_local::<Gilrs>().unwrap();
+    let mut gamepads = resources.get_mut::<Gamepads>().unwrap();
     let mut event = resources.get_mut::<Events<GamepadEventRaw>>().unwrap();
-    for (id, _) in gilrs.gamepads() {
+    for (id, gamepad) in gilrs.gamepads() {
+        gamepads.set_name(convert_gamepad_id(id), gamepad.name());
         event.send(GamepadEventRaw(
             convert_gamepad_id(id),
             GamepadEventType::Connected,
@@ -17,11 +19,14 @@ pub fn gilrs_event_startup_system(_world: &mut World, resources: &mut Resources)
 
 pub fn gilrs_event_system(_world: &mut World, resources: &mut Resources) {
     let mut gilrs = resources.get_thread_local_mut::<Gilrs>().unwrap();
+    let mut gamepads = resources.get_mut::<Gamepads>().unwrap();
     let mut event = resources.get_mut::<Events<GamepadEventRaw>>().unwrap();
     event.update();
     while let Some(gilrs_event) = gilrs.next_event() {
         match gilrs_event.event {
             EventType::Connected => {
+                let name = gilrs.gamepad(gilrs_event.id).name().to_string();
+                gamepads.set_name(convert_gamepad_id(gilrs_event.id), name);
                 event.send(GamepadEventRaw(
                     convert_gamepad_id(gilrs_event.id),
                     GamepadEventType::Connected,
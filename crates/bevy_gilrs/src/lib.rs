@@ -1,10 +1,12 @@
 mod converter;
 mod gilrs_system;
+mod rumble;
 
 use bevy_app::{prelude::*, startup_stage::PRE_STARTUP};
 use bevy_utils::tracing::error;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
+use rumble::{gilrs_rumble_system, RunningRumbles};
 
 #[derive(Default)]
 pub struct GilrsPlugin;
@@ -18,8 +20,10 @@ impl Plugin for GilrsPlugin {
         {
             Ok(gilrs) => {
                 app.add_thread_local_resource(gilrs)
+                    .add_thread_local_resource(RunningRumbles::default())
                     .add_startup_system_to_stage(PRE_STARTUP, gilrs_event_startup_system)
-                    .add_system_to_stage(stage::PRE_EVENT, gilrs_event_system);
+                    .add_system_to_stage(stage::PRE_EVENT, gilrs_event_system)
+                    .add_system_to_stage(stage::POST_UPDATE, gilrs_rumble_system);
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
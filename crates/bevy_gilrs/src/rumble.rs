@@ -0,0 +1,112 @@
+use crate::converter::convert_gamepad_id;
+use bevy_app::{EventReader, Events};
+use bevy_ecs::{Resources, World};
+use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_utils::{
+    tracing::{debug, warn},
+    Duration, HashMap, Instant,
+};
+use gilrs::{
+    ff::{self, BaseEffect, BaseEffectType, EffectBuilder, Repeat, Replay, Ticks},
+    GamepadId, Gilrs,
+};
+
+/// A rumble that plays until its deadline. Effects stop when they are dropped.
+struct RunningRumble {
+    deadline: Instant,
+    #[allow(dead_code)]
+    effect: ff::Effect,
+}
+
+/// The rumbles playing on gamepads, kept with the gilrs context on the main thread
+#[derive(Default)]
+pub(crate) struct RunningRumbles {
+    rumbles: HashMap<Gamepad, Vec<RunningRumble>>,
+    request_reader: EventReader<GamepadRumbleRequest>,
+}
+
+fn magnitude(intensity: f32) -> u16 {
+    (intensity.max(0.0).min(1.0) * u16::MAX as f32) as u16
+}
+
+fn add_rumble(
+    gilrs: &mut Gilrs,
+    gamepad_id: GamepadId,
+    intensity: GamepadRumbleIntensity,
+    duration: Duration,
+) -> Result<ff::Effect, ff::Error> {
+    let play_for = Ticks::from_ms(duration.as_millis().min(u32::MAX as u128) as u32);
+    let scheduling = Replay {
+        play_for,
+        ..Default::default()
+    };
+    let effect = EffectBuilder::new()
+        .add_effect(BaseEffect {
+            kind: BaseEffectType::Strong {
+                magnitude: magnitude(intensity.strong_motor),
+            },
+            scheduling,
+            envelope: Default::default(),
+        })
+        .add_effect(BaseEffect {
+            kind: BaseEffectType::Weak {
+                magnitude: magnitude(intensity.weak_motor),
+            },
+            scheduling,
+            envelope: Default::default(),
+        })
+        .repeat(Repeat::For(play_for))
+        .gamepads(&[gamepad_id])
+        .finish(gilrs)?;
+    effect.play()?;
+    Ok(effect)
+}
+
+/// Plays the rumbles requested with [GamepadRumbleRequest] events, and stops them once they are
+/// done
+pub(crate) fn gilrs_rumble_system(_world: &mut World, resources: &mut Resources) {
+    let mut gilrs = resources.get_thread_local_mut::<Gilrs>().unwrap();
+    let mut running_rumbles = resources.get_thread_local_mut::<RunningRumbles>().unwrap();
+    let requests = resources.get::<Events<GamepadRumbleRequest>>().unwrap();
+    let running_rumbles = &mut *running_rumbles;
+
+    let now = Instant::now();
+    for rumbles in running_rumbles.rumbles.values_mut() {
+        rumbles.retain(|rumble| rumble.deadline > now);
+    }
+
+    for request in running_rumbles.request_reader.iter(&requests) {
+        let gamepad = request.gamepad();
+        let gamepad_id = match gilrs
+            .gamepads()
+            .find(|(id, _gamepad)| convert_gamepad_id(*id) == gamepad)
+        {
+            Some((id, gilrs_gamepad)) if gilrs_gamepad.is_ff_supported() => id,
+            Some(_) => {
+                debug!("{:?} doesn't support rumble", gamepad);
+                continue;
+            }
+            None => continue,
+        };
+        match request {
+            GamepadRumbleRequest::Add {
+                intensity,
+                duration,
+                ..
+            } => match add_rumble(&mut gilrs, gamepad_id, *intensity, *duration) {
+                Ok(effect) => running_rumbles
+                    .rumbles
+                    .entry(gamepad)
+                    .or_insert_with(Vec::new)
+                    .push(RunningRumble {
+                        deadline: now + *duration,
+                        effect,
+                    }),
+                Err(err) => warn!("Failed to rumble {:?}: {}", gamepad, err),
+            },
+            GamepadRumbleRequest::Stop { .. } => {
+                running_rumbles.rumbles.remove(&gamepad);
+            }
+        }
+    }
+}
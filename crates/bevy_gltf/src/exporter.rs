@@ -0,0 +1,436 @@
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Entity, World};
+use bevy_pbr::prelude::StandardMaterial;
+use bevy_render::{
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    pipeline::PrimitiveTopology,
+};
+use bevy_transform::prelude::{Children, Parent, Transform};
+use serde_json::{json, Value};
+use std::{fs, io, path::Path};
+use thiserror::Error;
+
+/// An error that occurs when exporting a GLTF file
+#[derive(Error, Debug)]
+pub enum GltfExportError {
+    #[error("Mesh has no positions.")]
+    MissingPositions,
+    #[error("Unsupported vertex format for the {0} attribute.")]
+    UnsupportedVertexFormat(&'static str),
+    #[error("Failed to write the GLTF file.")]
+    Io(#[from] io::Error),
+}
+
+// GLTF constants for buffer views and accessors
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const FLOAT: u32 = 5126;
+
+/// Builds GLTF 2.0 documents out of [Mesh] assets, [StandardMaterial]s and [Transform] hierarchies.
+///
+/// Only positions, normals, the first UV channel, indices and material colors are exported.
+/// Textures are not exported.
+#[derive(Default)]
+pub struct GltfExporter {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    meshes: Vec<Value>,
+    materials: Vec<Value>,
+    nodes: Vec<Value>,
+    root_nodes: Vec<usize>,
+    extensions_used: Vec<&'static str>,
+}
+
+impl GltfExporter {
+    /// Adds a mesh (optionally with a material) and returns its GLTF mesh index
+    pub fn add_mesh(
+        &mut self,
+        mesh: &Mesh,
+        material: Option<&StandardMaterial>,
+    ) -> Result<usize, GltfExportError> {
+        let mut attributes = serde_json::Map::new();
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float3(positions)) => positions,
+            Some(_) => return Err(GltfExportError::UnsupportedVertexFormat("POSITION")),
+            None => return Err(GltfExportError::MissingPositions),
+        };
+        let accessor = self.add_float_accessor(positions, "VEC3", true);
+        attributes.insert("POSITION".to_string(), json!(accessor));
+
+        match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float3(normals)) => {
+                let accessor = self.add_float_accessor(normals, "VEC3", false);
+                attributes.insert("NORMAL".to_string(), json!(accessor));
+            }
+            Some(_) => return Err(GltfExportError::UnsupportedVertexFormat("NORMAL")),
+            None => {}
+        }
+
+        match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float2(uvs)) => {
+                let accessor = self.add_float_accessor(uvs, "VEC2", false);
+                attributes.insert("TEXCOORD_0".to_string(), json!(accessor));
+            }
+            Some(_) => return Err(GltfExportError::UnsupportedVertexFormat("TEXCOORD_0")),
+            None => {}
+        }
+
+        let mut primitive = json!({
+            "attributes": attributes,
+            "mode": primitive_mode(mesh.primitive_topology()),
+        });
+        if let Some(indices) = mesh.indices() {
+            primitive["indices"] = json!(self.add_index_accessor(indices));
+        }
+        if let Some(material) = material {
+            primitive["material"] = json!(self.add_material(material));
+        }
+
+        self.meshes.push(json!({ "primitives": [primitive] }));
+        Ok(self.meshes.len() - 1)
+    }
+
+    /// Adds a node and returns its GLTF node index. `children` are indices returned by earlier
+    /// calls to `add_node`. Nodes that are never passed as a child become scene roots.
+    pub fn add_node(
+        &mut self,
+        transform: &Transform,
+        mesh: Option<usize>,
+        children: &[usize],
+    ) -> usize {
+        let mut node = json!({
+            "translation": <[f32; 3]>::from(transform.translation),
+            "rotation": <[f32; 4]>::from(transform.rotation),
+            "scale": <[f32; 3]>::from(transform.scale),
+        });
+        if let Some(mesh) = mesh {
+            node["mesh"] = json!(mesh);
+        }
+        if !children.is_empty() {
+            node["children"] = json!(children);
+            self.root_nodes.retain(|root| !children.contains(root));
+        }
+
+        self.nodes.push(node);
+        let index = self.nodes.len() - 1;
+        self.root_nodes.push(index);
+        index
+    }
+
+    /// Adds every entity in `world` that has a [Transform] (and no [Parent]) to the exporter,
+    /// along with their descendants. Entities with a `Handle<Mesh>` export their mesh and, if
+    /// present, their `Handle<StandardMaterial>`.
+    pub fn add_world(
+        &mut self,
+        world: &World,
+        meshes: &Assets<Mesh>,
+        materials: &Assets<StandardMaterial>,
+    ) -> Result<(), GltfExportError> {
+        let roots = world
+            .query::<(Entity, &Transform)>()
+            .map(|(entity, _)| entity)
+            .filter(|entity| world.get::<Parent>(*entity).is_err())
+            .collect::<Vec<_>>();
+        for root in roots {
+            self.add_entity(world, root, meshes, materials)?;
+        }
+
+        Ok(())
+    }
+
+    fn add_entity(
+        &mut self,
+        world: &World,
+        entity: Entity,
+        meshes: &Assets<Mesh>,
+        materials: &Assets<StandardMaterial>,
+    ) -> Result<usize, GltfExportError> {
+        let mut children = Vec::new();
+        if let Ok(entity_children) = world.get::<Children>(entity) {
+            for child in entity_children.iter() {
+                if world.get::<Transform>(*child).is_ok() {
+                    children.push(self.add_entity(world, *child, meshes, materials)?);
+                }
+            }
+        }
+
+        let material = world
+            .get::<Handle<StandardMaterial>>(entity)
+            .ok()
+            .and_then(|handle| materials.get(handle));
+        let mesh = match world
+            .get::<Handle<Mesh>>(entity)
+            .ok()
+            .and_then(|handle| meshes.get(handle))
+        {
+            Some(mesh) => Some(self.add_mesh(mesh, material)?),
+            None => None,
+        };
+
+        let transform = world
+            .get::<Transform>(entity)
+            .map(|transform| *transform)
+            .unwrap_or_default();
+        Ok(self.add_node(&transform, mesh, &children))
+    }
+
+    /// Serializes the document as a `.gltf` file, with the binary data embedded as base64
+    pub fn to_gltf(&self) -> String {
+        let uri = format!(
+            "data:application/octet-stream;base64,{}",
+            base64::encode(&self.buffer)
+        );
+        self.document(Some(uri)).to_string()
+    }
+
+    /// Serializes the document as a binary `.glb` file
+    pub fn to_glb(&self) -> Vec<u8> {
+        const MAGIC: &[u8] = b"glTF";
+        const VERSION: u32 = 2;
+        const JSON_CHUNK: &[u8] = b"JSON";
+        const BIN_CHUNK: &[u8] = b"BIN\0";
+
+        // chunks must be 4 byte aligned, json is padded with spaces and binary data with zeros
+        let mut json = self.document(None).to_string().into_bytes();
+        json.resize(padded_len(json.len()), b' ');
+        let mut bin = self.buffer.clone();
+        bin.resize(padded_len(bin.len()), 0);
+
+        let bin_chunk_len = if bin.is_empty() { 0 } else { 8 + bin.len() };
+        let total_len = 12 + 8 + json.len() + bin_chunk_len;
+        let mut glb = Vec::with_capacity(total_len);
+        glb.extend_from_slice(MAGIC);
+        glb.extend_from_slice(&VERSION.to_le_bytes());
+        glb.extend_from_slice(&(total_len as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(JSON_CHUNK);
+        glb.extend_from_slice(&json);
+        if !bin.is_empty() {
+            glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(BIN_CHUNK);
+            glb.extend_from_slice(&bin);
+        }
+        glb
+    }
+
+    /// Writes the document to `path`, as a `.glb` file if the path has that extension and as a
+    /// `.gltf` file otherwise
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), GltfExportError> {
+        let path = path.as_ref();
+        if path
+            .extension()
+            .map_or(false, |extension| extension == "glb")
+        {
+            fs::write(path, self.to_glb())?;
+        } else {
+            fs::write(path, self.to_gltf())?;
+        }
+
+        Ok(())
+    }
+
+    fn document(&self, buffer_uri: Option<String>) -> Value {
+        let mut buffer = json!({ "byteLength": self.buffer.len() });
+        if let Some(uri) = buffer_uri {
+            buffer["uri"] = json!(uri);
+        }
+
+        let mut document = json!({
+            "asset": { "version": "2.0", "generator": "bevy_gltf" },
+            "scene": 0,
+            "scenes": [{ "nodes": self.root_nodes }],
+            "nodes": self.nodes,
+            "meshes": self.meshes,
+            "accessors": self.accessors,
+            "bufferViews": self.buffer_views,
+        });
+        if !self.materials.is_empty() {
+            document["materials"] = json!(self.materials);
+        }
+        if !self.extensions_used.is_empty() {
+            document["extensionsUsed"] = json!(self.extensions_used);
+        }
+        if !self.buffer.is_empty() {
+            document["buffers"] = json!([buffer]);
+        }
+        document
+    }
+
+    fn add_material(&mut self, material: &StandardMaterial) -> usize {
+        let albedo = material.albedo;
+        let mut gltf_material = json!({
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [albedo.r(), albedo.g(), albedo.b(), albedo.a()],
+            },
+        });
+        if albedo.a() < 1.0 {
+            gltf_material["alphaMode"] = json!("BLEND");
+        }
+        if !material.shaded {
+            gltf_material["extensions"] = json!({ "KHR_materials_unlit": {} });
+            self.use_extension("KHR_materials_unlit");
+        }
+
+        self.materials.push(gltf_material);
+        self.materials.len() - 1
+    }
+
+    fn use_extension(&mut self, extension: &'static str) {
+        if !self.extensions_used.contains(&extension) {
+            self.extensions_used.push(extension);
+        }
+    }
+
+    fn add_float_accessor<T: AsRef<[f32]>>(
+        &mut self,
+        values: &[T],
+        accessor_type: &str,
+        with_bounds: bool,
+    ) -> usize {
+        let mut bytes = Vec::new();
+        for value in values.iter() {
+            for component in value.as_ref().iter() {
+                bytes.extend_from_slice(&component.to_le_bytes());
+            }
+        }
+        let buffer_view = self.add_buffer_view(&bytes, ARRAY_BUFFER);
+
+        let mut accessor = json!({
+            "bufferView": buffer_view,
+            "componentType": FLOAT,
+            "count": values.len(),
+            "type": accessor_type,
+        });
+        // POSITION accessors are required to have bounds
+        if with_bounds && !values.is_empty() {
+            let components = values[0].as_ref().len();
+            let mut min = vec![f32::MAX; components];
+            let mut max = vec![f32::MIN; components];
+            for value in values.iter() {
+                for (i, component) in value.as_ref().iter().enumerate() {
+                    min[i] = min[i].min(*component);
+                    max[i] = max[i].max(*component);
+                }
+            }
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn add_index_accessor(&mut self, indices: &Indices) -> usize {
+        let mut bytes = Vec::new();
+        let (component_type, count) = match indices {
+            Indices::U16(indices) => {
+                for index in indices.iter() {
+                    bytes.extend_from_slice(&index.to_le_bytes());
+                }
+                (UNSIGNED_SHORT, indices.len())
+            }
+            Indices::U32(indices) => {
+                for index in indices.iter() {
+                    bytes.extend_from_slice(&index.to_le_bytes());
+                }
+                (UNSIGNED_INT, indices.len())
+            }
+        };
+        let buffer_view = self.add_buffer_view(&bytes, ELEMENT_ARRAY_BUFFER);
+
+        self.accessors.push(json!({
+            "bufferView": buffer_view,
+            "componentType": component_type,
+            "count": count,
+            "type": "SCALAR",
+        }));
+        self.accessors.len() - 1
+    }
+
+    fn add_buffer_view(&mut self, bytes: &[u8], target: u32) -> usize {
+        // keep every view 4 byte aligned so any component type can be read from it
+        self.buffer.resize(padded_len(self.buffer.len()), 0);
+        self.buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": bytes.len(),
+            "target": target,
+        }));
+        self.buffer.extend_from_slice(bytes);
+        self.buffer_views.len() - 1
+    }
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn primitive_mode(primitive_topology: PrimitiveTopology) -> u32 {
+    match primitive_topology {
+        PrimitiveTopology::PointList => 0,
+        PrimitiveTopology::LineList => 1,
+        PrimitiveTopology::LineStrip => 3,
+        PrimitiveTopology::TriangleList => 4,
+        PrimitiveTopology::TriangleStrip => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GltfExporter;
+    use bevy_pbr::prelude::StandardMaterial;
+    use bevy_render::{
+        mesh::{Indices, Mesh, VertexAttributeValues},
+        pipeline::PrimitiveTopology,
+    };
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn exported_documents_are_imported() {
+        let positions = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            VertexAttributeValues::Float3(positions.clone()),
+        );
+        mesh.set_indices(Some(Indices::U32(vec![0, 1, 2])));
+        let material = StandardMaterial {
+            shaded: false,
+            ..Default::default()
+        };
+
+        let mut exporter = GltfExporter::default();
+        let mesh_index = exporter.add_mesh(&mesh, Some(&material)).unwrap();
+        exporter.add_node(&Transform::default(), Some(mesh_index), &[]);
+
+        let gltf = gltf::Gltf::from_slice(&exporter.to_glb()).unwrap();
+        assert_eq!(
+            gltf.extensions_used().collect::<Vec<_>>(),
+            vec!["KHR_materials_unlit"]
+        );
+        assert_eq!(gltf.nodes().count(), 1);
+        let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+        assert!(primitive.material().unlit());
+
+        let reader = primitive.reader(|_| gltf.blob.as_deref());
+        assert_eq!(
+            reader.read_positions().unwrap().collect::<Vec<_>>(),
+            positions
+        );
+        let indices = reader
+            .read_indices()
+            .unwrap()
+            .into_u32()
+            .collect::<Vec<_>>();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        // documents without unlit materials don't use the extension
+        let mut exporter = GltfExporter::default();
+        exporter.add_mesh(&mesh, None).unwrap();
+        let gltf = gltf::Gltf::from_slice(exporter.to_gltf().as_bytes()).unwrap();
+        assert_eq!(gltf.extensions_used().count(), 0);
+    }
+}
@@ -1,8 +1,17 @@
+mod exporter;
 mod loader;
+pub use exporter::*;
 pub use loader::*;
 
+use bevy_animation::AnimationClip;
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Handle};
+use bevy_ecs::bevy_utils::HashMap;
+use bevy_pbr::prelude::StandardMaterial;
+use bevy_reflect::TypeUuid;
+use bevy_render::mesh::Mesh;
+use bevy_scene::Scene;
+use bevy_transform::prelude::Transform;
 
 /// Adds support for GLTF file loading to Apps
 #[derive(Default)]
@@ -10,6 +19,55 @@ pub struct GltfPlugin;
 
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.init_asset_loader::<GltfLoader>();
+        app.init_asset_loader::<GltfLoader>()
+            .add_asset::<Gltf>()
+            .add_asset::<GltfNode>()
+            .add_asset::<GltfMesh>();
     }
 }
+
+/// The contents of a GLTF file, labeled `Gltf`. Each of its parts is also a labeled asset, like
+/// `Scene0`, `Node0`, `Mesh0`, `Mesh0/Primitive0`, `Material0` and `Animation0`, and the parts
+/// with a name in the file can be found by it. The default scene isn't labeled like the other
+/// scenes, it is the default asset of the file.
+#[derive(Debug, Clone, Default, TypeUuid)]
+#[uuid = "c8e2c8c7-9844-4e69-a66d-d0ad2e865b25"]
+pub struct Gltf {
+    pub scenes: Vec<Handle<Scene>>,
+    pub named_scenes: HashMap<String, Handle<Scene>>,
+    /// The scene shown by default, which is also the default asset of the file
+    pub default_scene: Option<Handle<Scene>>,
+    pub nodes: Vec<Handle<GltfNode>>,
+    pub named_nodes: HashMap<String, Handle<GltfNode>>,
+    pub meshes: Vec<Handle<GltfMesh>>,
+    pub named_meshes: HashMap<String, Handle<GltfMesh>>,
+    pub materials: Vec<Handle<StandardMaterial>>,
+    pub named_materials: HashMap<String, Handle<StandardMaterial>>,
+    pub animations: Vec<Handle<AnimationClip>>,
+    pub named_animations: HashMap<String, Handle<AnimationClip>>,
+}
+
+/// A node of a GLTF file, with its transform relative to its parent
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "1e051a46-a81a-4079-abfd-5c06e54f2cef"]
+pub struct GltfNode {
+    pub name: Option<String>,
+    pub children: Vec<Handle<GltfNode>>,
+    pub mesh: Option<Handle<GltfMesh>>,
+    pub transform: Transform,
+}
+
+/// A mesh of a GLTF file, drawn in parts with a material each
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "089db55c-3c76-4e84-a41d-20c5037c9af2"]
+pub struct GltfMesh {
+    pub name: Option<String>,
+    pub primitives: Vec<GltfPrimitive>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GltfPrimitive {
+    pub mesh: Handle<Mesh>,
+    /// The material of the primitive, or nothing for the default material of GLTF
+    pub material: Option<Handle<StandardMaterial>>,
+}
@@ -1,13 +1,20 @@
+use crate::{Gltf, GltfMesh, GltfNode, GltfPrimitive};
 use anyhow::Result;
-use bevy_asset::{AssetIoError, AssetLoader, AssetPath, LoadContext, LoadedAsset};
-use bevy_ecs::{bevy_utils::BoxedFuture, World, WorldBuilderSource};
-use bevy_math::Mat4;
-use bevy_pbr::prelude::{PbrBundle, StandardMaterial};
+use bevy_animation::{AnimationClip, EntityPath, Interpolation, Keyframes, VariableCurve};
+use bevy_asset::{Asset, AssetIoError, AssetLoader, AssetPath, Handle, LoadContext, LoadedAsset};
+use bevy_core::Name;
+use bevy_ecs::{
+    bevy_utils::{BoxedFuture, HashMap, Uuid},
+    Entity, World, WorldBuilderSource,
+};
+use bevy_math::{Mat4, Quat, Vec2, Vec3};
+use bevy_pbr::prelude::{MorphWeights, PbrBundle, Skin, StandardMaterial};
+use bevy_reflect::TypeUuid;
 use bevy_render::{
     camera::{
         Camera, CameraProjection, OrthographicProjection, PerspectiveProjection, VisibleEntities,
     },
-    mesh::{Indices, Mesh, VertexAttributeValues},
+    mesh::{Indices, Mesh, MorphTarget, VertexAttributeValues},
     pipeline::PrimitiveTopology,
     prelude::{Color, Texture},
     render_graph::base,
@@ -21,6 +28,7 @@ use bevy_transform::{
     prelude::{GlobalTransform, Transform},
 };
 use gltf::{
+    animation::util::ReadOutputs,
     mesh::Mode,
     texture::{MagFilter, MinFilter, WrappingMode},
     Primitive,
@@ -50,9 +58,17 @@ pub enum GltfError {
     ImageError(#[from] image::ImageError),
     #[error("Failed to load an asset path.")]
     AssetIoError(#[from] AssetIoError),
+    #[error("Unsupported required extension: {0}.")]
+    UnsupportedExtension(String),
 }
 
-/// Loads meshes from GLTF files into Mesh assets
+/// Required extensions the loader can't decode. Draco compressed primitives are not decoded, files
+/// that only use the extension are loaded from the uncompressed fallback data of their primitives.
+const UNSUPPORTED_REQUIRED_EXTENSIONS: &[&str] = &["KHR_draco_mesh_compression"];
+
+/// Loads GLTF files into a [Scene] of their default scene, with a [Gltf] asset and labeled assets
+/// for each of their parts. The entities of the nodes keep their hierarchy, transforms and
+/// cameras, and have a [Name], which is the index of the node for nodes without a name.
 #[derive(Default)]
 pub struct GltfLoader;
 
@@ -68,6 +84,20 @@ impl AssetLoader for GltfLoader {
     fn extensions(&self) -> &[&str] {
         &["gltf", "glb"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Scene::TYPE_UUID)
+    }
+}
+
+fn check_required_extensions(gltf: &gltf::Gltf) -> Result<(), GltfError> {
+    match gltf
+        .extensions_required()
+        .find(|extension| UNSUPPORTED_REQUIRED_EXTENSIONS.contains(extension))
+    {
+        Some(extension) => Err(GltfError::UnsupportedExtension(extension.to_string())),
+        None => Ok(()),
+    }
 }
 
 async fn load_gltf<'a, 'b>(
@@ -75,10 +105,9 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
-    let mut world = World::default();
+    check_required_extensions(&gltf)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
-
-    let world_builder = &mut world.build();
+    let mut gltf_asset = Gltf::default();
 
     for mesh in gltf.meshes() {
         for primitive in mesh.primitives() {
@@ -110,10 +139,43 @@ async fn load_gltf<'a, 'b>(
                     mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vertex_attribute);
                 }
 
+                if let Some(vertex_attribute) = reader.read_joints(0).map(|v| {
+                    VertexAttributeValues::Uint4(
+                        v.into_u16()
+                            .map(|joints| {
+                                [
+                                    joints[0] as u32,
+                                    joints[1] as u32,
+                                    joints[2] as u32,
+                                    joints[3] as u32,
+                                ]
+                            })
+                            .collect(),
+                    )
+                }) {
+                    mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vertex_attribute);
+                }
+
+                if let Some(vertex_attribute) = reader
+                    .read_weights(0)
+                    .map(|v| VertexAttributeValues::Float4(v.into_f32().collect()))
+                {
+                    mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vertex_attribute);
+                }
+
                 if let Some(indices) = reader.read_indices() {
                     mesh.set_indices(Some(Indices::U32(indices.into_u32().collect())));
                 };
 
+                let morph_targets = reader
+                    .read_morph_targets()
+                    .map(|(positions, normals, _tangents)| MorphTarget {
+                        positions: positions.map(|p| p.collect()).unwrap_or_default(),
+                        normals: normals.map(|n| n.collect()),
+                    })
+                    .collect();
+                mesh.set_morph_targets(morph_targets);
+
                 load_context.set_labeled_asset(&primitive_label, LoadedAsset::new(mesh));
             };
         }
@@ -151,12 +213,16 @@ async fn load_gltf<'a, 'b>(
         let material_label = material_label(&material);
         let pbr = material.pbr_metallic_roughness();
         let mut dependencies = Vec::new();
+        let uv_transform = pbr
+            .base_color_texture()
+            .and_then(|info| info.texture_transform())
+            .map(|texture_transform| uv_transform(&texture_transform))
+            .unwrap_or_else(Mat4::identity);
         let texture_handle = if let Some(info) = pbr.base_color_texture() {
             match info.texture().source().source() {
                 gltf::image::Source::View { .. } => {
                     let label = texture_label(&info.texture());
-                    let path = AssetPath::new_ref(load_context.path(), Some(&label));
-                    Some(load_context.get_handle(path))
+                    Some(load_context.get_handle(load_context.get_label_path(&label)))
                 }
                 gltf::image::Source::Uri { uri, .. } => {
                     let parent = load_context.path().parent().unwrap();
@@ -176,47 +242,257 @@ async fn load_gltf<'a, 'b>(
             LoadedAsset::new(StandardMaterial {
                 albedo: Color::rgba(color[0], color[1], color[2], color[3]),
                 albedo_texture: texture_handle,
-                ..Default::default()
+                uv_transform,
+                shaded: !material.unlit(),
             })
             .with_dependencies(dependencies),
-        )
+        );
+        add_gltf_part(
+            &mut gltf_asset.materials,
+            &mut gltf_asset.named_materials,
+            label_handle(load_context, &material_label),
+            material.name(),
+        );
     }
 
-    for scene in gltf.scenes() {
-        let mut err = None;
-        world_builder
-            .spawn((Transform::default(), GlobalTransform::default()))
-            .with_children(|parent| {
-                for node in scene.nodes() {
-                    let result = load_node(&node, parent, load_context, &buffer_data);
-                    if result.is_err() {
-                        err = Some(result);
-                        return;
-                    }
+    // curves find the nodes they animate by the names of the nodes from the root of the scene
+    let node_names = gltf
+        .nodes()
+        .map(|node| node_name(&node))
+        .collect::<Vec<_>>();
+    let mut node_parents = HashMap::default();
+    for node in gltf.nodes() {
+        for child in node.children() {
+            node_parents.insert(child.index(), node.index());
+        }
+    }
+    for animation in gltf.animations() {
+        let mut animation_clip = AnimationClip::default();
+        for channel in animation.channels() {
+            let reader = channel.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            let keyframe_timestamps = match reader.read_inputs() {
+                Some(inputs) => inputs.collect(),
+                None => continue,
+            };
+            let (interpolation, cubic_spline) = match channel.sampler().interpolation() {
+                gltf::animation::Interpolation::Step => (Interpolation::Step, false),
+                gltf::animation::Interpolation::Linear => (Interpolation::Linear, false),
+                // the values of cubic splines are blended linearly, without their tangents
+                gltf::animation::Interpolation::CubicSpline => (Interpolation::Linear, true),
+            };
+            let keyframes = match reader.read_outputs() {
+                Some(ReadOutputs::Translations(translations)) => Keyframes::Translation(
+                    keyframe_values(translations.map(Vec3::from), cubic_spline),
+                ),
+                Some(ReadOutputs::Rotations(rotations)) => Keyframes::Rotation(keyframe_values(
+                    rotations
+                        .into_f32()
+                        .map(|[x, y, z, w]| Quat::from_xyzw(x, y, z, w)),
+                    cubic_spline,
+                )),
+                Some(ReadOutputs::Scales(scales)) => {
+                    Keyframes::Scale(keyframe_values(scales.map(Vec3::from), cubic_spline))
                 }
-            });
-        if let Some(Err(err)) = err {
-            return Err(err);
+                // morph target weights aren't animated
+                _ => continue,
+            };
+
+            let mut parts = Vec::new();
+            let mut node = Some(channel.target().node().index());
+            while let Some(index) = node {
+                parts.push(Name::new(node_names[index].clone()));
+                node = node_parents.get(&index).cloned();
+            }
+            parts.reverse();
+            animation_clip.add_curve_to_path(
+                EntityPath { parts },
+                VariableCurve {
+                    keyframe_timestamps,
+                    keyframes,
+                    interpolation,
+                },
+            );
         }
+        let animation_label = animation_label(&animation);
+        load_context.set_labeled_asset(&animation_label, LoadedAsset::new(animation_clip));
+        add_gltf_part(
+            &mut gltf_asset.animations,
+            &mut gltf_asset.named_animations,
+            label_handle(load_context, &animation_label),
+            animation.name(),
+        );
     }
 
-    load_context.set_default_asset(LoadedAsset::new(Scene::new(world)));
+    for mesh in gltf.meshes() {
+        let primitives = mesh
+            .primitives()
+            .map(|primitive| {
+                let material = primitive.material();
+                GltfPrimitive {
+                    mesh: label_handle(load_context, &primitive_label(&mesh, &primitive)),
+                    material: material
+                        .index()
+                        .map(|_| label_handle(load_context, &material_label(&material))),
+                }
+            })
+            .collect();
+        let mesh_label = mesh_label(&mesh);
+        load_context.set_labeled_asset(
+            &mesh_label,
+            LoadedAsset::new(GltfMesh {
+                name: mesh.name().map(|name| name.to_string()),
+                primitives,
+            }),
+        );
+        add_gltf_part(
+            &mut gltf_asset.meshes,
+            &mut gltf_asset.named_meshes,
+            label_handle(load_context, &mesh_label),
+            mesh.name(),
+        );
+    }
+
+    for node in gltf.nodes() {
+        let transform = node.transform().matrix();
+        let gltf_node = GltfNode {
+            name: node.name().map(|name| name.to_string()),
+            children: node
+                .children()
+                .map(|child| label_handle(load_context, &node_label(&child)))
+                .collect(),
+            mesh: node
+                .mesh()
+                .map(|mesh| label_handle(load_context, &mesh_label(&mesh))),
+            transform: Transform::from_matrix(Mat4::from_cols_array_2d(&transform)),
+        };
+        let node_label = node_label(&node);
+        load_context.set_labeled_asset(&node_label, LoadedAsset::new(gltf_node));
+        add_gltf_part(
+            &mut gltf_asset.nodes,
+            &mut gltf_asset.named_nodes,
+            label_handle(load_context, &node_label),
+            node.name(),
+        );
+    }
+
+    // the default asset is the scene the file shows first, which is its first scene unless it
+    // names another one. That scene isn't labeled, so it is only built once.
+    let default_scene_index = gltf
+        .default_scene()
+        .or_else(|| gltf.scenes().next())
+        .map(|scene| scene.index());
+    let mut default_scene = None;
+    for scene in gltf.scenes() {
+        let scene_asset = load_scene(&scene, load_context, &buffer_data)?;
+        let scene_handle = if Some(scene.index()) == default_scene_index {
+            default_scene = Some(scene_asset);
+            let scene_handle: Handle<Scene> =
+                load_context.get_handle(load_context.get_default_path());
+            gltf_asset.default_scene = Some(scene_handle.clone());
+            scene_handle
+        } else {
+            let scene_label = scene_label(&scene);
+            load_context.set_labeled_asset(&scene_label, LoadedAsset::new(scene_asset));
+            label_handle(load_context, &scene_label)
+        };
+        add_gltf_part(
+            &mut gltf_asset.scenes,
+            &mut gltf_asset.named_scenes,
+            scene_handle,
+            scene.name(),
+        );
+    }
+
+    load_context.set_labeled_asset("Gltf", LoadedAsset::new(gltf_asset));
+    load_context.set_default_asset(LoadedAsset::new(
+        default_scene.unwrap_or_else(|| Scene::new(World::default())),
+    ));
 
     Ok(())
 }
 
-fn load_node(
-    gltf_node: &gltf::Node,
+/// Spawns the nodes of a scene with their names, meshes, cameras and skins
+fn load_scene(
+    scene: &gltf::Scene,
+    load_context: &mut LoadContext,
+    buffer_data: &[Vec<u8>],
+) -> Result<Scene, GltfError> {
+    let mut world = World::default();
+    let world_builder = &mut world.build();
+    let mut node_entities = HashMap::default();
+    let mut skinned_entities = Vec::new();
+    let mut err = None;
+    world_builder
+        .spawn((
+            Transform::default(),
+            GlobalTransform::default(),
+            Name::new(scene_name(scene)),
+        ))
+        .with_children(|parent| {
+            for node in scene.nodes() {
+                let result = load_node(
+                    &node,
+                    parent,
+                    load_context,
+                    buffer_data,
+                    &mut node_entities,
+                    &mut skinned_entities,
+                );
+                if result.is_err() {
+                    err = Some(result);
+                    return;
+                }
+            }
+        });
+    if let Some(Err(err)) = err {
+        return Err(err);
+    }
+
+    // skins are added once the entities of all of their joints were spawned
+    for (entity, skin) in skinned_entities {
+        let joints = skin
+            .joints()
+            .filter_map(|joint| node_entities.get(&joint.index()).cloned())
+            .collect();
+        let inverse_bind_matrices = skin
+            .reader(|buffer| Some(&buffer_data[buffer.index()]))
+            .read_inverse_bind_matrices()
+            .map(|matrices| {
+                matrices
+                    .map(|matrix| Mat4::from_cols_array_2d(&matrix))
+                    .collect()
+            })
+            .unwrap_or_default();
+        world
+            .insert_one(
+                entity,
+                Skin {
+                    joints,
+                    inverse_bind_matrices,
+                },
+            )
+            .unwrap();
+    }
+
+    Ok(Scene::new(world))
+}
+
+fn load_node<'a>(
+    gltf_node: &gltf::Node<'a>,
     world_builder: &mut WorldChildBuilder,
     load_context: &mut LoadContext,
     buffer_data: &[Vec<u8>],
+    node_entities: &mut HashMap<usize, Entity>,
+    skinned_entities: &mut Vec<(Entity, gltf::Skin<'a>)>,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
     let node = world_builder.spawn((
         Transform::from_matrix(Mat4::from_cols_array_2d(&transform.matrix())),
         GlobalTransform::default(),
+        Name::new(node_name(gltf_node)),
     ));
+    node_entities.insert(gltf_node.index(), node.current_entity().unwrap());
 
     // create camera node
     if let Some(camera) = gltf_node.camera() {
@@ -272,23 +548,41 @@ fn load_node(
             // append primitives
             for primitive in mesh.primitives() {
                 let primitive_label = primitive_label(&mesh, &primitive);
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
+                let mesh_asset_path = load_context.get_label_path(&primitive_label);
                 let material = primitive.material();
                 let material_label = material_label(&material);
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
+                let material_asset_path = load_context.get_label_path(&material_label);
                 parent.spawn(PbrBundle {
                     mesh: load_context.get_handle(mesh_asset_path),
                     material: load_context.get_handle(material_asset_path),
                     ..Default::default()
                 });
+                let morph_target_count = primitive.morph_targets().len();
+                if morph_target_count > 0 {
+                    // the weights of the node override the default weights of the mesh
+                    let weights = gltf_node
+                        .weights()
+                        .or_else(|| mesh.weights())
+                        .map(|weights| weights.to_vec())
+                        .unwrap_or_else(|| vec![0.0; morph_target_count]);
+                    parent.with(MorphWeights::new(weights));
+                }
+                if let Some(skin) = gltf_node.skin() {
+                    skinned_entities.push((parent.current_entity().unwrap(), skin));
+                }
             }
         }
 
         // append other nodes
         for child in gltf_node.children() {
-            if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
+            if let Err(err) = load_node(
+                &child,
+                parent,
+                load_context,
+                buffer_data,
+                node_entities,
+                skinned_entities,
+            ) {
                 gltf_error = Some(err);
                 return;
             }
@@ -301,6 +595,64 @@ fn load_node(
     }
 }
 
+/// Adds a part of the file to the [Gltf] asset, and by its name if it has one
+fn add_gltf_part<T: Asset>(
+    parts: &mut Vec<Handle<T>>,
+    named_parts: &mut HashMap<String, Handle<T>>,
+    handle: Handle<T>,
+    name: Option<&str>,
+) {
+    if let Some(name) = name {
+        named_parts.insert(name.to_string(), handle.clone());
+    }
+    parts.push(handle);
+}
+
+fn label_handle<T: Asset>(load_context: &LoadContext, label: &str) -> Handle<T> {
+    load_context.get_handle(load_context.get_label_path(label))
+}
+
+/// The name of a scene, or its index for scenes without a name
+fn scene_name(scene: &gltf::Scene) -> String {
+    scene
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("Scene{}", scene.index()))
+}
+
+/// The name of a node, or its index for nodes without a name
+fn node_name(node: &gltf::Node) -> String {
+    node.name()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("Node{}", node.index()))
+}
+
+/// The values of the keyframes of an animation channel. Cubic splines have an in-tangent, a value
+/// and an out-tangent for each keyframe.
+fn keyframe_values<T>(values: impl Iterator<Item = T>, cubic_spline: bool) -> Vec<T> {
+    if cubic_spline {
+        values.skip(1).step_by(3).collect()
+    } else {
+        values.collect()
+    }
+}
+
+fn scene_label(scene: &gltf::Scene) -> String {
+    format!("Scene{}", scene.index())
+}
+
+fn node_label(node: &gltf::Node) -> String {
+    format!("Node{}", node.index())
+}
+
+fn mesh_label(mesh: &gltf::Mesh) -> String {
+    format!("Mesh{}", mesh.index())
+}
+
+fn animation_label(animation: &gltf::Animation) -> String {
+    format!("Animation{}", animation.index())
+}
+
 fn primitive_label(mesh: &gltf::Mesh, primitive: &Primitive) -> String {
     format!("Mesh{}/Primitive{}", mesh.index(), primitive.index())
 }
@@ -317,6 +669,17 @@ fn texture_label(texture: &gltf::Texture) -> String {
     format!("Texture{}", texture.index())
 }
 
+/// Builds the UV matrix described by a `KHR_texture_transform` extension (translation * rotation * scale)
+fn uv_transform(texture_transform: &gltf::texture::TextureTransform) -> Mat4 {
+    let offset = Vec2::from(texture_transform.offset());
+    let scale = Vec2::from(texture_transform.scale());
+    Mat4::from_scale_rotation_translation(
+        Vec3::new(scale.x(), scale.y(), 1.0),
+        Quat::from_rotation_z(-texture_transform.rotation()),
+        Vec3::new(offset.x(), offset.y(), 0.0),
+    )
+}
+
 fn texture_sampler(texture: &gltf::Texture) -> Result<SamplerDescriptor, GltfError> {
     let gltf_sampler = texture.sampler();
 
@@ -401,3 +764,54 @@ async fn load_buffers(
 
     Ok(buffer_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_required_extensions, GltfError};
+
+    // a triangle whose only data is Draco compressed
+    const DRACO_PRIMITIVE: &str = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_draco_mesh_compression"],
+        "extensionsRequired": ["KHR_draco_mesh_compression"],
+        "accessors": [
+            {
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0, 0, 0],
+                "max": [1, 1, 0]
+            }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "extensions": {
+                    "KHR_draco_mesh_compression": {
+                        "bufferView": 0,
+                        "attributes": { "POSITION": 0 }
+                    }
+                }
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn required_draco_compression_is_rejected() {
+        let gltf = gltf::Gltf::from_slice(DRACO_PRIMITIVE.as_bytes()).unwrap();
+        match check_required_extensions(&gltf) {
+            Err(GltfError::UnsupportedExtension(extension)) => {
+                assert_eq!(extension, "KHR_draco_mesh_compression")
+            }
+            result => panic!("expected an unsupported extension error, got {:?}", result),
+        }
+
+        // files that don't require the extension have uncompressed fallback data
+        let fallback = DRACO_PRIMITIVE.replace(
+            r#""extensionsRequired": ["KHR_draco_mesh_compression"],"#,
+            "",
+        );
+        let gltf = gltf::Gltf::from_slice(fallback.as_bytes()).unwrap();
+        assert!(check_required_extensions(&gltf).is_ok());
+    }
+}
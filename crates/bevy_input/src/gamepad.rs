@@ -1,12 +1,61 @@
 use crate::{Axis, Input};
 use bevy_app::{EventReader, Events};
 use bevy_ecs::{Local, Res, ResMut};
-use bevy_utils::HashMap;
+use bevy_utils::{Duration, HashMap};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gamepad(pub usize);
 
+/// The connected gamepads, in the order they were connected, so that players can be told apart
+/// by their gamepads
+#[derive(Default, Debug)]
+pub struct Gamepads {
+    gamepads: Vec<Gamepad>,
+    names: HashMap<Gamepad, String>,
+}
+
+impl Gamepads {
+    pub fn contains(&self, gamepad: Gamepad) -> bool {
+        self.gamepads.contains(&gamepad)
+    }
+
+    /// Iterates over the connected gamepads, from the first connected one
+    pub fn iter(&self) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.gamepads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gamepads.is_empty()
+    }
+
+    /// The name of a gamepad, usually the name of its model, when its input backend knows it
+    pub fn name(&self, gamepad: Gamepad) -> Option<&str> {
+        self.names.get(&gamepad).map(|name| name.as_str())
+    }
+
+    /// Sets the name of a gamepad. Input backends set it before sending the
+    /// [GamepadEventType::Connected] event of the gamepad.
+    pub fn set_name(&mut self, gamepad: Gamepad, name: impl Into<String>) {
+        self.names.insert(gamepad, name.into());
+    }
+
+    fn connect(&mut self, gamepad: Gamepad) {
+        if !self.gamepads.contains(&gamepad) {
+            self.gamepads.push(gamepad);
+        }
+    }
+
+    fn disconnect(&mut self, gamepad: Gamepad) {
+        // the name is kept, as the backend may have set it again for a new connection
+        self.gamepads.retain(|connected| *connected != gamepad);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadEventType {
@@ -69,6 +118,63 @@ pub enum GamepadAxisType {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadAxis(pub Gamepad, pub GamepadAxisType);
 
+/// The intensities of the two rumble motors of a gamepad, from 0.0 for off to 1.0 for full
+/// strength. The strong motor rumbles at a low frequency and the weak motor at a high frequency.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadRumbleIntensity {
+    pub strong_motor: f32,
+    pub weak_motor: f32,
+}
+
+impl GamepadRumbleIntensity {
+    /// Both motors at full strength
+    pub const MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 1.0,
+    };
+
+    pub fn strong_motor(intensity: f32) -> Self {
+        GamepadRumbleIntensity {
+            strong_motor: intensity,
+            weak_motor: 0.0,
+        }
+    }
+
+    pub fn weak_motor(intensity: f32) -> Self {
+        GamepadRumbleIntensity {
+            strong_motor: 0.0,
+            weak_motor: intensity,
+        }
+    }
+}
+
+/// Rumbles a gamepad when sent as an event, which the input backend handles. Nothing happens for
+/// gamepads that don't support force feedback.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadRumbleRequest {
+    /// Rumbles the gamepad at an intensity for a duration. Rumbles added while others are
+    /// playing are played together.
+    Add {
+        gamepad: Gamepad,
+        intensity: GamepadRumbleIntensity,
+        duration: Duration,
+    },
+    /// Stops all the rumbles of the gamepad
+    Stop { gamepad: Gamepad },
+}
+
+impl GamepadRumbleRequest {
+    pub fn gamepad(&self) -> Gamepad {
+        match self {
+            GamepadRumbleRequest::Add { gamepad, .. } | GamepadRumbleRequest::Stop { gamepad } => {
+                *gamepad
+            }
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct GamepadSettings {
     pub default_button_settings: ButtonSettings,
@@ -146,6 +252,16 @@ impl Default for AxisSettings {
 }
 
 impl AxisSettings {
+    /// Settings where values between `-dead_zone` and `dead_zone` are zero, like the small
+    /// values of worn sticks at rest
+    pub fn with_dead_zone(dead_zone: f32) -> Self {
+        AxisSettings {
+            positive_low: dead_zone,
+            negative_low: -dead_zone,
+            ..Default::default()
+        }
+    }
+
     fn filter(&self, new_value: f32, old_value: Option<f32>) -> Option<f32> {
         if let Some(old_value) = old_value {
             if (new_value - old_value).abs() <= self.threshold {
@@ -199,8 +315,10 @@ impl ButtonAxisSettings {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn gamepad_event_system(
     mut event_reader: Local<EventReader<GamepadEventRaw>>,
+    mut gamepads: ResMut<Gamepads>,
     mut button_input: ResMut<Input<GamepadButton>>,
     mut axis: ResMut<Axis<GamepadAxis>>,
     mut button_axis: ResMut<Axis<GamepadButton>>,
@@ -213,6 +331,7 @@ pub fn gamepad_event_system(
         let (gamepad, event) = (event.0, &event.1);
         match event {
             GamepadEventType::Connected => {
+                gamepads.connect(gamepad);
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -224,6 +343,7 @@ pub fn gamepad_event_system(
                 }
             }
             GamepadEventType::Disconnected => {
+                gamepads.disconnect(gamepad);
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -305,3 +425,34 @@ const ALL_AXIS_TYPES: [GamepadAxisType; 8] = [
     GamepadAxisType::DPadX,
     GamepadAxisType::DPadY,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::{AxisSettings, Gamepad, Gamepads};
+
+    #[test]
+    fn gamepads_keep_their_connection_order() {
+        let mut gamepads = Gamepads::default();
+        gamepads.connect(Gamepad(3));
+        gamepads.set_name(Gamepad(1), "Pad");
+        gamepads.connect(Gamepad(1));
+        gamepads.connect(Gamepad(3));
+        assert_eq!(
+            gamepads.iter().cloned().collect::<Vec<_>>(),
+            vec![Gamepad(3), Gamepad(1)]
+        );
+        assert_eq!(gamepads.name(Gamepad(1)), Some("Pad"));
+
+        gamepads.disconnect(Gamepad(1));
+        assert!(!gamepads.contains(Gamepad(1)));
+        assert_eq!(gamepads.len(), 1);
+    }
+
+    #[test]
+    fn dead_zone_values_are_zero() {
+        let settings = AxisSettings::with_dead_zone(0.2);
+        assert_eq!(settings.filter(0.15, None), Some(0.0));
+        assert_eq!(settings.filter(-0.15, None), Some(0.0));
+        assert_eq!(settings.filter(0.5, None), Some(0.5));
+    }
+}
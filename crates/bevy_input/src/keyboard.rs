@@ -1,13 +1,18 @@
 use crate::{ElementState, Input};
 use bevy_app::prelude::*;
 use bevy_ecs::{Local, Res, ResMut};
+use bevy_utils::HashMap;
+use bevy_window::WindowId;
 
 /// A key input event from a keyboard device
 #[derive(Debug, Clone)]
 pub struct KeyboardInput {
+    /// The physical key, which doesn't depend on the keyboard layout. See [ScanCode].
     pub scan_code: u32,
     pub key_code: Option<KeyCode>,
     pub state: ElementState,
+    /// The window that had focus when the key was pressed or released
+    pub window_id: WindowId,
 }
 
 /// State used by the keyboard input system
@@ -16,17 +21,29 @@ pub struct KeyboardInputState {
     keyboard_input_event_reader: EventReader<KeyboardInput>,
 }
 
-/// Updates the Input<KeyCode> resource with the latest KeyboardInput events
+/// Updates the Input<KeyCode> and Input<ScanCode> resources with the latest KeyboardInput events,
+/// and the [KeyboardLayout] with the keys that were pressed
 pub fn keyboard_input_system(
     mut state: Local<KeyboardInputState>,
     mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut scan_code_input: ResMut<Input<ScanCode>>,
+    mut keyboard_layout: ResMut<KeyboardLayout>,
     keyboard_input_events: Res<Events<KeyboardInput>>,
 ) {
     keyboard_input.update();
+    scan_code_input.update();
     for event in state
         .keyboard_input_event_reader
         .iter(&keyboard_input_events)
     {
+        let scan_code = ScanCode(event.scan_code);
+        match event.state {
+            ElementState::Pressed => scan_code_input.press(scan_code),
+            ElementState::Released => scan_code_input.release(scan_code),
+        }
+        if let Some(key_code) = event.key_code {
+            keyboard_layout.insert(scan_code, key_code);
+        }
         if let KeyboardInput {
             key_code: Some(key_code),
             state,
@@ -240,3 +257,178 @@ pub enum KeyCode {
     Paste,
     Cut,
 }
+
+/// The physical position of a key on the keyboard, which doesn't change with the keyboard layout.
+/// Controls bound to scan codes, like WASD for movement, stay in the same place on AZERTY or
+/// Dvorak keyboards, where [KeyCode]s would move them.
+///
+/// The values are platform specific: scan codes should be found with [ScanCode::from_us_layout]
+/// or from the keys players press, not written by hand.
+#[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanCode(pub u32);
+
+impl ScanCode {
+    /// The scan code of the key that has the given key code on a US QWERTY keyboard, for the
+    /// letters, the digits over the letters and a few common keys.
+    /// `ScanCode::from_us_layout(KeyCode::W)` is the key with Z on an AZERTY keyboard.
+    pub fn from_us_layout(key_code: KeyCode) -> Option<ScanCode> {
+        us_layout_scan_code(key_code).map(ScanCode)
+    }
+}
+
+/// Scan codes on macOS are the virtual key codes of Carbon
+#[cfg(target_os = "macos")]
+fn us_layout_scan_code(key_code: KeyCode) -> Option<u32> {
+    Some(match key_code {
+        KeyCode::A => 0x00,
+        KeyCode::S => 0x01,
+        KeyCode::D => 0x02,
+        KeyCode::F => 0x03,
+        KeyCode::H => 0x04,
+        KeyCode::G => 0x05,
+        KeyCode::Z => 0x06,
+        KeyCode::X => 0x07,
+        KeyCode::C => 0x08,
+        KeyCode::V => 0x09,
+        KeyCode::B => 0x0B,
+        KeyCode::Q => 0x0C,
+        KeyCode::W => 0x0D,
+        KeyCode::E => 0x0E,
+        KeyCode::R => 0x0F,
+        KeyCode::Y => 0x10,
+        KeyCode::T => 0x11,
+        KeyCode::Key1 => 0x12,
+        KeyCode::Key2 => 0x13,
+        KeyCode::Key3 => 0x14,
+        KeyCode::Key4 => 0x15,
+        KeyCode::Key6 => 0x16,
+        KeyCode::Key5 => 0x17,
+        KeyCode::Key9 => 0x19,
+        KeyCode::Key7 => 0x1A,
+        KeyCode::Key8 => 0x1C,
+        KeyCode::Key0 => 0x1D,
+        KeyCode::O => 0x1F,
+        KeyCode::U => 0x20,
+        KeyCode::I => 0x22,
+        KeyCode::P => 0x23,
+        KeyCode::Return => 0x24,
+        KeyCode::L => 0x25,
+        KeyCode::J => 0x26,
+        KeyCode::K => 0x28,
+        KeyCode::N => 0x2D,
+        KeyCode::M => 0x2E,
+        KeyCode::Tab => 0x30,
+        KeyCode::Space => 0x31,
+        KeyCode::Back => 0x33,
+        KeyCode::Escape => 0x35,
+        KeyCode::LShift => 0x38,
+        KeyCode::LControl => 0x3B,
+        _ => return None,
+    })
+}
+
+/// Scan codes on Windows and Linux are the set 1 scan codes of PC keyboards
+#[cfg(not(target_os = "macos"))]
+fn us_layout_scan_code(key_code: KeyCode) -> Option<u32> {
+    Some(match key_code {
+        KeyCode::Escape => 1,
+        KeyCode::Key1 => 2,
+        KeyCode::Key2 => 3,
+        KeyCode::Key3 => 4,
+        KeyCode::Key4 => 5,
+        KeyCode::Key5 => 6,
+        KeyCode::Key6 => 7,
+        KeyCode::Key7 => 8,
+        KeyCode::Key8 => 9,
+        KeyCode::Key9 => 10,
+        KeyCode::Key0 => 11,
+        KeyCode::Back => 14,
+        KeyCode::Tab => 15,
+        KeyCode::Q => 16,
+        KeyCode::W => 17,
+        KeyCode::E => 18,
+        KeyCode::R => 19,
+        KeyCode::T => 20,
+        KeyCode::Y => 21,
+        KeyCode::U => 22,
+        KeyCode::I => 23,
+        KeyCode::O => 24,
+        KeyCode::P => 25,
+        KeyCode::Return => 28,
+        KeyCode::LControl => 29,
+        KeyCode::A => 30,
+        KeyCode::S => 31,
+        KeyCode::D => 32,
+        KeyCode::F => 33,
+        KeyCode::G => 34,
+        KeyCode::H => 35,
+        KeyCode::J => 36,
+        KeyCode::K => 37,
+        KeyCode::L => 38,
+        KeyCode::LShift => 42,
+        KeyCode::Z => 44,
+        KeyCode::X => 45,
+        KeyCode::C => 46,
+        KeyCode::V => 47,
+        KeyCode::B => 48,
+        KeyCode::N => 49,
+        KeyCode::M => 50,
+        KeyCode::Space => 57,
+        _ => return None,
+    })
+}
+
+/// The key codes the keys of the keyboard had when they were last pressed or released, to show
+/// the key a scan code is bound to in the current layout, or to find the physical key of a key
+/// code. Keys are only known once they were used.
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardLayout {
+    key_codes: HashMap<ScanCode, KeyCode>,
+    scan_codes: HashMap<KeyCode, ScanCode>,
+}
+
+impl KeyboardLayout {
+    pub fn key_code(&self, scan_code: ScanCode) -> Option<KeyCode> {
+        self.key_codes.get(&scan_code).cloned()
+    }
+
+    pub fn scan_code(&self, key_code: KeyCode) -> Option<ScanCode> {
+        self.scan_codes.get(&key_code).cloned()
+    }
+
+    /// Records that the key at `scan_code` has `key_code`, replacing what either was mapped to
+    pub fn insert(&mut self, scan_code: ScanCode, key_code: KeyCode) {
+        if let Some(previous_key_code) = self.key_codes.insert(scan_code, key_code) {
+            if previous_key_code != key_code {
+                self.scan_codes.remove(&previous_key_code);
+            }
+        }
+        if let Some(previous_scan_code) = self.scan_codes.insert(key_code, scan_code) {
+            if previous_scan_code != scan_code {
+                self.key_codes.remove(&previous_scan_code);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyCode, KeyboardLayout, ScanCode};
+
+    #[test]
+    fn layout_maps_both_ways() {
+        let mut layout = KeyboardLayout::default();
+        layout.insert(ScanCode(17), KeyCode::W);
+        assert_eq!(layout.key_code(ScanCode(17)), Some(KeyCode::W));
+        assert_eq!(layout.scan_code(KeyCode::W), Some(ScanCode(17)));
+
+        // the layout changed to AZERTY
+        layout.insert(ScanCode(17), KeyCode::Z);
+        layout.insert(ScanCode(44), KeyCode::W);
+        assert_eq!(layout.key_code(ScanCode(17)), Some(KeyCode::Z));
+        assert_eq!(layout.scan_code(KeyCode::W), Some(ScanCode(44)));
+        assert_eq!(layout.scan_code(KeyCode::Z), Some(ScanCode(17)));
+        assert_eq!(layout.key_code(ScanCode(99)), None);
+    }
+}
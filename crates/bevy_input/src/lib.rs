@@ -5,6 +5,7 @@ pub mod keyboard;
 pub mod mouse;
 pub mod system;
 pub mod touch;
+pub mod touch_gesture;
 
 pub use axis::*;
 pub use input::*;
@@ -13,24 +14,29 @@ pub mod prelude {
     pub use crate::{
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType,
+            GamepadEventType, GamepadRumbleIntensity, GamepadRumbleRequest, Gamepads,
         },
-        keyboard::KeyCode,
-        mouse::MouseButton,
+        keyboard::{KeyCode, KeyboardLayout, ScanCode},
+        mouse::{AccumulatedMouseMotion, MouseButton, MouseMotion},
         touch::{TouchInput, Touches},
+        touch_gesture::TouchGesture,
         Axis, Input,
     };
 }
 
 use bevy_app::prelude::*;
-use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
-use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
+use keyboard::{keyboard_input_system, KeyCode, KeyboardInput, KeyboardLayout, ScanCode};
+use mouse::{
+    accumulated_mouse_motion_system, mouse_button_input_system, AccumulatedMouseMotion,
+    MouseButton, MouseButtonInput, MouseMotion, MouseWheel,
+};
 use touch::{touch_screen_input_system, TouchInput, Touches};
+use touch_gesture::{touch_gesture_system, TouchGesture, TouchGestureSettings};
 
 use bevy_app::startup_stage::STARTUP;
 use gamepad::{
     gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventRaw,
-    GamepadSettings,
+    GamepadRumbleRequest, GamepadSettings, Gamepads,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -44,12 +50,18 @@ impl Plugin for InputPlugin {
             .add_event::<MouseMotion>()
             .add_event::<MouseWheel>()
             .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<ScanCode>>()
+            .init_resource::<KeyboardLayout>()
             .add_system_to_stage(bevy_app::stage::EVENT, keyboard_input_system)
             .init_resource::<Input<MouseButton>>()
             .add_system_to_stage(bevy_app::stage::EVENT, mouse_button_input_system)
+            .init_resource::<AccumulatedMouseMotion>()
+            .add_system_to_stage(bevy_app::stage::EVENT, accumulated_mouse_motion_system)
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
+            .add_event::<GamepadRumbleRequest>()
             .init_resource::<GamepadSettings>()
+            .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
             .init_resource::<Axis<GamepadButton>>()
@@ -57,7 +69,10 @@ impl Plugin for InputPlugin {
             .add_startup_system_to_stage(STARTUP, gamepad_event_system)
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_system_to_stage(bevy_app::stage::EVENT, touch_screen_input_system);
+            .add_system_to_stage(bevy_app::stage::EVENT, touch_screen_input_system)
+            .add_event::<TouchGesture>()
+            .init_resource::<TouchGestureSettings>()
+            .add_system_to_stage(bevy_app::stage::EVENT, touch_gesture_system);
     }
 }
 
@@ -2,12 +2,15 @@ use crate::{ElementState, Input};
 use bevy_app::prelude::{EventReader, Events};
 use bevy_ecs::{Local, Res, ResMut};
 use bevy_math::Vec2;
+use bevy_window::WindowId;
 
 /// A mouse button input event
 #[derive(Debug, Clone)]
 pub struct MouseButtonInput {
     pub button: MouseButton,
     pub state: ElementState,
+    /// The window under the cursor when the button was pressed or released
+    pub window_id: WindowId,
 }
 
 /// A button on a mouse device
@@ -20,12 +23,21 @@ pub enum MouseButton {
     Other(u8),
 }
 
-/// A mouse motion event
+/// A mouse motion event, with the raw motion reported by the mouse device. It isn't accelerated
+/// by the OS and keeps being sent when the cursor is at the edge of the window or locked, so it
+/// should be used instead of the cursor position to turn cameras. The delta is in the units of the
+/// device, with y going down.
 #[derive(Debug, Clone)]
 pub struct MouseMotion {
     pub delta: Vec2,
 }
 
+/// The sum of the [MouseMotion] events of the current frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AccumulatedMouseMotion {
+    pub delta: Vec2,
+}
+
 /// Unit of scroll
 #[derive(Debug, Clone, Copy)]
 pub enum MouseScrollUnit {
@@ -39,6 +51,8 @@ pub struct MouseWheel {
     pub unit: MouseScrollUnit,
     pub x: f32,
     pub y: f32,
+    /// The window under the cursor when the wheel was scrolled
+    pub window_id: WindowId,
 }
 
 /// State used by the mouse button input system
@@ -64,3 +78,24 @@ pub fn mouse_button_input_system(
         }
     }
 }
+
+/// State used by the accumulated mouse motion system
+#[derive(Default)]
+pub struct AccumulatedMouseMotionState {
+    mouse_motion_event_reader: EventReader<MouseMotion>,
+}
+
+/// Sums the MouseMotion events of the frame into the AccumulatedMouseMotion resource
+pub fn accumulated_mouse_motion_system(
+    mut state: Local<AccumulatedMouseMotionState>,
+    mut accumulated_mouse_motion: ResMut<AccumulatedMouseMotion>,
+    mouse_motion_events: Res<Events<MouseMotion>>,
+) {
+    let delta = state
+        .mouse_motion_event_reader
+        .iter(&mouse_motion_events)
+        .fold(Vec2::zero(), |delta, event| delta + event.delta);
+    if accumulated_mouse_motion.delta != delta {
+        accumulated_mouse_motion.delta = delta;
+    }
+}
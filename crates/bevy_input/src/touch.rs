@@ -2,6 +2,7 @@ use bevy_app::{EventReader, Events};
 use bevy_ecs::{Local, Res, ResMut};
 use bevy_math::Vec2;
 use bevy_utils::HashMap;
+use bevy_window::WindowId;
 
 /// Represents a touch event
 ///
@@ -32,6 +33,8 @@ pub struct TouchInput {
     pub force: Option<ForceTouch>,
     /// Unique identifier of a finger.
     pub id: u64,
+    /// The window that was touched
+    pub window_id: WindowId,
 }
 
 /// Describes the force of a touch event
@@ -284,6 +287,7 @@ mod test {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            window_id: WindowId::primary(),
             id: 4,
         };
 
@@ -299,6 +303,7 @@ mod test {
             phase: TouchPhase::Moved,
             position: Vec2::new(5.0, 5.0),
             force: None,
+            window_id: WindowId::primary(),
             id: touch_event.id,
         };
 
@@ -320,6 +325,7 @@ mod test {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(1.0, 1.0),
             force: None,
+            window_id: WindowId::primary(),
             id: touch_event.id,
         };
 
@@ -335,6 +341,7 @@ mod test {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            window_id: WindowId::primary(),
             id: 4,
         };
 
@@ -357,6 +364,7 @@ mod test {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            window_id: WindowId::primary(),
             id: 4,
         };
 
@@ -379,6 +387,7 @@ mod test {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            window_id: WindowId::primary(),
             id: 4,
         };
 
@@ -401,6 +410,7 @@ mod test {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            window_id: WindowId::primary(),
             id: 4,
         };
 
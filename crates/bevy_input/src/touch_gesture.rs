@@ -0,0 +1,136 @@
+use crate::touch::{Touch, Touches};
+use bevy_app::Events;
+use bevy_ecs::{Local, Res, ResMut};
+use bevy_math::Vec2;
+use bevy_utils::{Duration, HashMap, Instant};
+
+/// A gesture recognized from the [Touches]. Pinches and rotations are sent every frame two
+/// fingers move on the screen, with the change since the last frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    /// A finger that touched the screen briefly and didn't move further than
+    /// [TouchGestureSettings::tap_max_distance]
+    Tap { position: Vec2 },
+    /// Two fingers moving apart or together, where `scale` is the ratio of their distances in
+    /// this frame and in the last frame
+    Pinch { center: Vec2, scale: f32 },
+    /// Two fingers turning around each other, where `angle` is in radians, counterclockwise when
+    /// y goes up
+    Rotate { center: Vec2, angle: f32 },
+}
+
+#[derive(Debug, Clone)]
+pub struct TouchGestureSettings {
+    /// The longest a finger can touch the screen for a tap
+    pub tap_max_duration: Duration,
+    /// The furthest a finger can move in logical pixels for a tap
+    pub tap_max_distance: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        TouchGestureSettings {
+            tap_max_duration: Duration::from_millis(300),
+            tap_max_distance: 10.0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TouchGestureState {
+    /// When and where the pressed touches started
+    starts: HashMap<u64, (Instant, Vec2)>,
+    /// The positions of the pressed touches in the last frame
+    positions: HashMap<u64, Vec2>,
+}
+
+/// Sends [TouchGesture] events from the changes of the [Touches]
+pub fn touch_gesture_system(
+    mut state: Local<TouchGestureState>,
+    settings: Res<TouchGestureSettings>,
+    touches: Res<Touches>,
+    mut gestures: ResMut<Events<TouchGesture>>,
+) {
+    let now = Instant::now();
+    for touch in touches.iter_just_pressed() {
+        state
+            .starts
+            .insert(touch.id(), (now, touch.start_position()));
+    }
+    for touch in touches.iter_just_released() {
+        if let Some((start_time, start_position)) = state.starts.remove(&touch.id()) {
+            if now - start_time <= settings.tap_max_duration
+                && (touch.position() - start_position).length() <= settings.tap_max_distance
+            {
+                gestures.send(TouchGesture::Tap {
+                    position: touch.position(),
+                });
+            }
+        }
+    }
+    for touch in touches.iter_just_cancelled() {
+        state.starts.remove(&touch.id());
+    }
+
+    let mut pressed = touches.iter().collect::<Vec<&Touch>>();
+    if pressed.len() == 2 {
+        pressed.sort_by_key(|touch| touch.id());
+        let (first, second) = (pressed[0], pressed[1]);
+        if let (Some(previous_first), Some(previous_second)) = (
+            state.positions.get(&first.id()),
+            state.positions.get(&second.id()),
+        ) {
+            if let Some((center, scale, angle)) = two_touch_motion(
+                (*previous_first, *previous_second),
+                (first.position(), second.position()),
+            ) {
+                if scale != 1.0 {
+                    gestures.send(TouchGesture::Pinch { center, scale });
+                }
+                if angle != 0.0 {
+                    gestures.send(TouchGesture::Rotate { center, angle });
+                }
+            }
+        }
+    }
+    state.positions = touches
+        .iter()
+        .map(|touch| (touch.id(), touch.position()))
+        .collect();
+}
+
+/// The center of two touches, and how much they scaled and turned from their previous positions
+fn two_touch_motion(previous: (Vec2, Vec2), current: (Vec2, Vec2)) -> Option<(Vec2, f32, f32)> {
+    let previous_span = previous.1 - previous.0;
+    let span = current.1 - current.0;
+    // touches at the same position have no direction
+    if previous_span.length_squared() <= f32::EPSILON || span.length_squared() <= f32::EPSILON {
+        return None;
+    }
+    let scale = span.length() / previous_span.length();
+    let angle = (previous_span.x() * span.y() - previous_span.y() * span.x())
+        .atan2(previous_span.dot(span));
+    Some(((current.0 + current.1) / 2.0, scale, angle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::two_touch_motion;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn two_touches_pinch_and_rotate() {
+        let (center, scale, angle) = two_touch_motion(
+            (Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0)),
+            (Vec2::new(0.0, -2.0), Vec2::new(0.0, 2.0)),
+        )
+        .unwrap();
+        assert_eq!(center, Vec2::zero());
+        assert!((scale - 2.0).abs() < 1e-5);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+
+        assert!(
+            two_touch_motion((Vec2::zero(), Vec2::zero()), (Vec2::zero(), Vec2::one())).is_none()
+        );
+    }
+}
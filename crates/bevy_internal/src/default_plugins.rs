@@ -13,10 +13,14 @@ impl PluginGroup for DefaultPlugins {
         group.add(bevy_window::WindowPlugin::default());
         group.add(bevy_asset::AssetPlugin::default());
         group.add(bevy_scene::ScenePlugin::default());
+        group.add(bevy_animation::AnimationPlugin::default());
 
         #[cfg(feature = "bevy_render")]
         group.add(bevy_render::RenderPlugin::default());
 
+        #[cfg(feature = "bevy_picking")]
+        group.add(bevy_picking::PickingPlugin::default());
+
         #[cfg(feature = "bevy_sprite")]
         group.add(bevy_sprite::SpritePlugin::default());
 
@@ -38,11 +42,17 @@ impl PluginGroup for DefaultPlugins {
         #[cfg(feature = "bevy_gltf")]
         group.add(bevy_gltf::GltfPlugin::default());
 
+        #[cfg(feature = "bevy_mesh_loaders")]
+        group.add(bevy_mesh_loaders::MeshLoadersPlugin::default());
+
         #[cfg(feature = "bevy_winit")]
         group.add(bevy_winit::WinitPlugin::default());
 
         #[cfg(feature = "bevy_wgpu")]
         group.add(bevy_wgpu::WgpuPlugin::default());
+
+        #[cfg(all(feature = "bevy_webgl2", target_arch = "wasm32"))]
+        group.add(bevy_webgl2::WebGL2Plugin::default());
     }
 }
 
@@ -4,6 +4,11 @@ pub mod prelude;
 mod default_plugins;
 pub use default_plugins::*;
 
+pub mod animation {
+    //! Play keyframe animations on hierarchies of entities.
+    pub use bevy_animation::*;
+}
+
 pub mod app {
     //! Build bevy apps, create plugins, and read events.
     pub use bevy_app::*;
@@ -88,6 +93,12 @@ pub mod gltf {
     pub use bevy_gltf::*;
 }
 
+#[cfg(feature = "bevy_mesh_loaders")]
+pub mod mesh_loaders {
+    //! Support for OBJ, STL and PLY file loading.
+    pub use bevy_mesh_loaders::*;
+}
+
 #[cfg(feature = "bevy_pbr")]
 pub mod pbr {
     //! Physically based rendering.
@@ -95,6 +106,12 @@ pub mod pbr {
     pub use bevy_pbr::*;
 }
 
+#[cfg(feature = "bevy_picking")]
+pub mod picking {
+    //! Pointer events for the meshes under the cursor.
+    pub use bevy_picking::*;
+}
+
 #[cfg(feature = "bevy_render")]
 pub mod render {
     //! Cameras, meshes, textures, shaders, and pipelines.
@@ -130,6 +147,12 @@ pub mod wgpu {
     pub use bevy_wgpu::*;
 }
 
+#[cfg(all(feature = "bevy_webgl2", target_arch = "wasm32"))]
+pub mod webgl2 {
+    //! A render backend utilizing WebGL2, used in browsers.
+    pub use bevy_webgl2::*;
+}
+
 #[cfg(feature = "bevy_dynamic_plugin")]
 pub mod dynamic_plugin {
     pub use bevy_dynamic_plugin::*;
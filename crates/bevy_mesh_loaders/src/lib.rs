@@ -0,0 +1,34 @@
+#[cfg(feature = "obj")]
+mod obj;
+#[cfg(feature = "ply")]
+mod ply_mesh_loader;
+#[cfg(feature = "stl")]
+mod stl_mesh_loader;
+
+#[cfg(feature = "obj")]
+pub use obj::*;
+#[cfg(feature = "ply")]
+pub use ply_mesh_loader::*;
+#[cfg(feature = "stl")]
+pub use stl_mesh_loader::*;
+
+use bevy_app::prelude::*;
+#[cfg(any(feature = "obj", feature = "ply", feature = "stl"))]
+use bevy_asset::AddAsset;
+
+/// Adds loaders for the mesh formats other than glTF that are enabled with cargo features: OBJ
+/// (with MTL materials), STL and PLY
+#[derive(Default)]
+pub struct MeshLoadersPlugin;
+
+impl Plugin for MeshLoadersPlugin {
+    #[allow(unused_variables)]
+    fn build(&self, app: &mut AppBuilder) {
+        #[cfg(feature = "obj")]
+        app.init_asset_loader::<ObjLoader>();
+        #[cfg(feature = "stl")]
+        app.init_asset_loader::<StlMeshLoader>();
+        #[cfg(feature = "ply")]
+        app.init_asset_loader::<PlyMeshLoader>();
+    }
+}
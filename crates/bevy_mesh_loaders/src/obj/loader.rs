@@ -0,0 +1,245 @@
+use super::parser::{parse_mtl, parse_obj, FaceGroup, FaceVertex, MtlMaterial, ObjData};
+use anyhow::Result;
+use bevy_asset::{AssetIoError, AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy_ecs::World;
+use bevy_math::Vec3;
+use bevy_pbr::prelude::{PbrBundle, StandardMaterial};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::{Indices, Mesh},
+    pipeline::PrimitiveTopology,
+    prelude::Color,
+};
+use bevy_scene::Scene;
+use bevy_utils::{tracing::warn, BoxedFuture, HashMap, Uuid};
+use thiserror::Error;
+
+/// An error that occurs when loading an OBJ file
+#[derive(Error, Debug)]
+pub enum ObjError {
+    #[error("Invalid OBJ or MTL file. Line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("OBJ and MTL files must be valid UTF-8.")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    #[error("Failed to load an asset path.")]
+    AssetIoError(#[from] AssetIoError),
+}
+
+/// Loads Wavefront OBJ files (and the MTL files they reference) into Mesh and StandardMaterial
+/// assets.
+///
+/// The default asset is a [Mesh] containing every face in the file. Faces that share a `usemtl`
+/// material are also available individually under the `Mesh{index}` labels, and each material is
+/// available as a [StandardMaterial] under the `Material/{name}` label. The `Scene` label is a
+/// [Scene] with an entity drawing each of these meshes with its material. Faces without a material,
+/// or whose material library is missing, use a default [StandardMaterial].
+#[derive(Default)]
+pub struct ObjLoader;
+
+impl AssetLoader for ObjLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move { Ok(load_obj(bytes, load_context).await?) })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["obj"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Mesh::TYPE_UUID)
+    }
+}
+
+async fn load_obj<'a, 'b>(
+    bytes: &'a [u8],
+    load_context: &'a mut LoadContext<'b>,
+) -> Result<(), ObjError> {
+    let obj = parse_obj(std::str::from_utf8(bytes)?)?;
+
+    for material_library in obj.material_libraries.iter() {
+        let parent = load_context.path().parent().unwrap();
+        let mtl_path = parent.join(material_library);
+        let mtl_bytes = match load_context.read_asset_bytes(&mtl_path).await {
+            Ok(mtl_bytes) => mtl_bytes,
+            Err(AssetIoError::NotFound(_)) => {
+                warn!(
+                    "Material library {} of {} not found, using the default material",
+                    mtl_path.display(),
+                    load_context.path().display()
+                );
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        for material in parse_mtl(std::str::from_utf8(&mtl_bytes)?)? {
+            load_material(&material, load_context);
+        }
+    }
+
+    let mut world = World::default();
+    for (index, group) in obj.groups.iter().enumerate() {
+        let mut mesh_builder = MeshBuilder::default();
+        mesh_builder.add_group(&obj, group);
+        load_context.set_labeled_asset(&mesh_label(index), LoadedAsset::new(mesh_builder.build()));
+
+        let group_material_label = match &group.material {
+            Some(name) if load_context.has_labeled_asset(&material_label(name)) => {
+                material_label(name)
+            }
+            _ => {
+                if !load_context.has_labeled_asset(DEFAULT_MATERIAL_LABEL) {
+                    load_context.set_labeled_asset(
+                        DEFAULT_MATERIAL_LABEL,
+                        LoadedAsset::new(StandardMaterial::default()),
+                    );
+                }
+                DEFAULT_MATERIAL_LABEL.to_string()
+            }
+        };
+        world.spawn(PbrBundle {
+            mesh: load_context.get_handle(load_context.get_label_path(&mesh_label(index))),
+            material: load_context.get_handle(load_context.get_label_path(&group_material_label)),
+            ..Default::default()
+        });
+    }
+    load_context.set_labeled_asset(SCENE_LABEL, LoadedAsset::new(Scene::new(world)));
+
+    let mut mesh_builder = MeshBuilder::default();
+    for group in obj.groups.iter() {
+        mesh_builder.add_group(&obj, group);
+    }
+    load_context.set_default_asset(LoadedAsset::new(mesh_builder.build()));
+
+    Ok(())
+}
+
+fn load_material(material: &MtlMaterial, load_context: &mut LoadContext) {
+    let mut dependencies = Vec::new();
+    let albedo_texture = material.diffuse_texture.as_ref().map(|texture| {
+        let parent = load_context.path().parent().unwrap();
+        let asset_path = AssetPath::new(parent.join(texture), None);
+        let handle = load_context.get_handle(asset_path.clone());
+        dependencies.push(asset_path);
+        handle
+    });
+    let [r, g, b] = material.diffuse;
+    load_context.set_labeled_asset(
+        &material_label(&material.name),
+        LoadedAsset::new(StandardMaterial {
+            albedo: Color::rgba(r, g, b, material.dissolve),
+            albedo_texture,
+            ..Default::default()
+        })
+        .with_dependencies(dependencies),
+    );
+}
+
+const SCENE_LABEL: &str = "Scene";
+const DEFAULT_MATERIAL_LABEL: &str = "DefaultMaterial";
+
+fn mesh_label(index: usize) -> String {
+    format!("Mesh{}", index)
+}
+
+fn material_label(name: &str) -> String {
+    format!("Material/{}", name)
+}
+
+/// Identifies a unique output vertex. Faces without normals get a flat normal, so the same OBJ
+/// corner can produce a different vertex for each face it belongs to.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct VertexKey {
+    vertex: FaceVertex,
+    flat_normal: Option<[u32; 3]>,
+}
+
+#[derive(Default)]
+struct MeshBuilder {
+    vertices: HashMap<VertexKey, u32>,
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl MeshBuilder {
+    fn add_group(&mut self, obj: &ObjData, group: &FaceGroup) {
+        for face in group.faces.iter() {
+            let flat_normal = if face.iter().any(|vertex| vertex.normal.is_none()) {
+                Some(face_normal(obj, face))
+            } else {
+                None
+            };
+
+            // OBJ faces are convex polygons, so a triangle fan is enough to triangulate them
+            for i in 1..face.len() - 1 {
+                for vertex in [face[0], face[i], face[i + 1]].iter() {
+                    let index = self.add_vertex(obj, *vertex, flat_normal);
+                    self.indices.push(index);
+                }
+            }
+        }
+    }
+
+    fn add_vertex(
+        &mut self,
+        obj: &ObjData,
+        vertex: FaceVertex,
+        flat_normal: Option<[f32; 3]>,
+    ) -> u32 {
+        let key = VertexKey {
+            vertex,
+            flat_normal: flat_normal.map(|[x, y, z]| [x.to_bits(), y.to_bits(), z.to_bits()]),
+        };
+        if let Some(index) = self.vertices.get(&key) {
+            return *index;
+        }
+
+        let index = self.positions.len() as u32;
+        self.positions.push(obj.positions[vertex.position]);
+        self.normals.push(
+            vertex
+                .normal
+                .map(|normal| obj.normals[normal])
+                .or(flat_normal)
+                .unwrap(),
+        );
+        // OBJ texture coordinates start at the bottom left, bevy textures start at the top left
+        self.uvs.push(
+            vertex
+                .tex_coord
+                .map(|tex_coord| {
+                    let [u, v] = obj.tex_coords[tex_coord];
+                    [u, 1.0 - v]
+                })
+                .unwrap_or([0.0, 0.0]),
+        );
+        self.vertices.insert(key, index);
+        index
+    }
+
+    fn build(self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.set_indices(Some(Indices::U32(self.indices)));
+        mesh
+    }
+}
+
+fn face_normal(obj: &ObjData, face: &[FaceVertex]) -> [f32; 3] {
+    let a = Vec3::from(obj.positions[face[0].position]);
+    let b = Vec3::from(obj.positions[face[1].position]);
+    let c = Vec3::from(obj.positions[face[2].position]);
+    let normal = (b - a).cross(c - a);
+    if normal.length_squared() > 0.0 {
+        normal.normalize().into()
+    } else {
+        [0.0, 0.0, 0.0]
+    }
+}
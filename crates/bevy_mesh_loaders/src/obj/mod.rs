@@ -0,0 +1,3 @@
+mod loader;
+mod parser;
+pub use loader::*;
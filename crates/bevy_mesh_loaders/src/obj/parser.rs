@@ -0,0 +1,256 @@
+use super::ObjError;
+
+/// A corner of an OBJ face. Indices are zero-based and already resolved against the vertex lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FaceVertex {
+    pub position: usize,
+    pub tex_coord: Option<usize>,
+    pub normal: Option<usize>,
+}
+
+/// A run of faces that share the same `usemtl` material
+#[derive(Debug, Default)]
+pub(crate) struct FaceGroup {
+    pub material: Option<String>,
+    pub faces: Vec<Vec<FaceVertex>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ObjData {
+    pub positions: Vec<[f32; 3]>,
+    pub tex_coords: Vec<[f32; 2]>,
+    pub normals: Vec<[f32; 3]>,
+    pub groups: Vec<FaceGroup>,
+    pub material_libraries: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MtlMaterial {
+    pub name: String,
+    pub diffuse: [f32; 3],
+    pub dissolve: f32,
+    pub diffuse_texture: Option<String>,
+}
+
+impl MtlMaterial {
+    fn new(name: String) -> Self {
+        MtlMaterial {
+            name,
+            diffuse: [1.0, 1.0, 1.0],
+            dissolve: 1.0,
+            diffuse_texture: None,
+        }
+    }
+}
+
+pub(crate) fn parse_obj(source: &str) -> Result<ObjData, ObjError> {
+    let mut data = ObjData::default();
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("v") => {
+                let position = parse_floats(&mut words, 3, line_number)?;
+                data.positions.push([position[0], position[1], position[2]]);
+            }
+            Some("vt") => {
+                // the third (w) component is optional and unused
+                let tex_coord = parse_floats(&mut words, 2, line_number)?;
+                data.tex_coords.push([tex_coord[0], tex_coord[1]]);
+            }
+            Some("vn") => {
+                let normal = parse_floats(&mut words, 3, line_number)?;
+                data.normals.push([normal[0], normal[1], normal[2]]);
+            }
+            Some("f") => {
+                let mut face = Vec::new();
+                for word in words {
+                    face.push(parse_face_vertex(word, &data, line_number)?);
+                }
+                if face.len() < 3 {
+                    return Err(ObjError::Parse {
+                        line: line_number,
+                        message: "faces need at least three vertices".to_string(),
+                    });
+                }
+                if data.groups.is_empty() {
+                    data.groups.push(FaceGroup::default());
+                }
+                data.groups.last_mut().unwrap().faces.push(face);
+            }
+            Some("usemtl") => {
+                let material = rest_of_line(line, "usemtl");
+                data.groups.push(FaceGroup {
+                    material: Some(material),
+                    faces: Vec::new(),
+                });
+            }
+            // a library statement can name several files
+            Some("mtllib") => {
+                data.material_libraries
+                    .extend(words.map(|library| library.to_string()));
+            }
+            // object names, groups and smoothing groups don't affect the generated meshes
+            _ => {}
+        }
+    }
+
+    data.groups.retain(|group| !group.faces.is_empty());
+    Ok(data)
+}
+
+pub(crate) fn parse_mtl(source: &str) -> Result<Vec<MtlMaterial>, ObjError> {
+    let mut materials: Vec<MtlMaterial> = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut words = line.split_whitespace();
+        let keyword = words.next();
+        if keyword == Some("newmtl") {
+            materials.push(MtlMaterial::new(rest_of_line(line, "newmtl")));
+            continue;
+        }
+
+        let material = match (keyword, materials.last_mut()) {
+            (None, _) | (Some(_), None) => continue,
+            (Some(_), Some(material)) => material,
+        };
+        match keyword {
+            Some("Kd") => {
+                let diffuse = parse_floats(&mut words, 3, line_number)?;
+                material.diffuse = [diffuse[0], diffuse[1], diffuse[2]];
+            }
+            Some("d") => material.dissolve = parse_floats(&mut words, 1, line_number)?[0],
+            Some("Tr") => material.dissolve = 1.0 - parse_floats(&mut words, 1, line_number)?[0],
+            // texture options (-s, -o, ...) aren't supported, so the file name is the last word
+            Some("map_Kd") => {
+                material.diffuse_texture = line.split_whitespace().last().map(String::from)
+            }
+            _ => {}
+        }
+    }
+
+    Ok(materials)
+}
+
+fn rest_of_line(line: &str, keyword: &str) -> String {
+    line.trim_start()[keyword.len()..].trim().to_string()
+}
+
+fn parse_floats<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    count: usize,
+    line: usize,
+) -> Result<Vec<f32>, ObjError> {
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        let word = words.next().ok_or_else(|| ObjError::Parse {
+            line,
+            message: format!("expected {} numbers", count),
+        })?;
+        values.push(word.parse().map_err(|_| ObjError::Parse {
+            line,
+            message: format!("invalid number '{}'", word),
+        })?);
+    }
+    Ok(values)
+}
+
+fn parse_face_vertex(word: &str, data: &ObjData, line: usize) -> Result<FaceVertex, ObjError> {
+    let mut indices = word.split('/');
+    let position = indices.next().unwrap_or_default();
+    let tex_coord = indices.next().filter(|index| !index.is_empty());
+    let normal = indices.next().filter(|index| !index.is_empty());
+    Ok(FaceVertex {
+        position: resolve_index(position, data.positions.len(), line)?,
+        tex_coord: tex_coord
+            .map(|index| resolve_index(index, data.tex_coords.len(), line))
+            .transpose()?,
+        normal: normal
+            .map(|index| resolve_index(index, data.normals.len(), line))
+            .transpose()?,
+    })
+}
+
+/// Converts a one-based (or negative, relative to the end) OBJ index into a zero-based index
+fn resolve_index(index: &str, len: usize, line: usize) -> Result<usize, ObjError> {
+    let index: isize = index.parse().map_err(|_| ObjError::Parse {
+        line,
+        message: format!("invalid index '{}'", index),
+    })?;
+    let resolved = if index < 0 {
+        len as isize + index
+    } else {
+        index - 1
+    };
+    if resolved < 0 || resolved as usize >= len {
+        return Err(ObjError::Parse {
+            line,
+            message: format!("index {} is out of range", index),
+        });
+    }
+    Ok(resolved as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quad_with_negative_indices() {
+        let data = parse_obj(
+            "mtllib quad.mtl shared.mtl
+             v 0 0 0
+             v 1 0 0
+             v 1 1 0
+             v 0 1 0
+             vt 0 0
+             vn 0 0 1
+             usemtl red
+             f -4/1/1 -3/1/1 -2/1/1 -1/1/1",
+        )
+        .unwrap();
+
+        assert_eq!(
+            data.material_libraries,
+            vec!["quad.mtl".to_string(), "shared.mtl".to_string()]
+        );
+        assert_eq!(data.groups.len(), 1);
+        assert_eq!(data.groups[0].material.as_deref(), Some("red"));
+        let face = &data.groups[0].faces[0];
+        assert_eq!(
+            face.iter()
+                .map(|vertex| vertex.position)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(face[0].tex_coord, Some(0));
+        assert_eq!(face[0].normal, Some(0));
+    }
+
+    #[test]
+    fn out_of_range_index_is_an_error() {
+        let result = parse_obj("v 0 0 0\nf 1 2 3");
+        assert!(matches!(result, Err(ObjError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn parse_materials() {
+        let materials = parse_mtl(
+            "# comment
+             newmtl red
+             Kd 1 0 0
+             d 0.5
+             newmtl textured
+             map_Kd -s 2 2 textures/brick.png",
+        )
+        .unwrap();
+
+        assert_eq!(materials.len(), 2);
+        assert_eq!(materials[0].diffuse, [1.0, 0.0, 0.0]);
+        assert_eq!(materials[0].dissolve, 0.5);
+        assert_eq!(
+            materials[1].diffuse_texture.as_deref(),
+            Some("textures/brick.png")
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_math::Vec3;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::{Indices, Mesh},
+    pipeline::PrimitiveTopology,
+};
+use bevy_utils::{BoxedFuture, Uuid};
+use std::convert::TryInto;
+use thiserror::Error;
+
+/// An error that occurs when loading a PLY file
+#[derive(Error, Debug)]
+pub enum PlyError {
+    #[error("Invalid PLY header: {0}")]
+    InvalidHeader(String),
+    #[error("PLY file ended before all elements were read.")]
+    UnexpectedEnd,
+    #[error("Invalid value in ASCII PLY file: {0}")]
+    InvalidValue(String),
+    #[error("PLY file has no 'x', 'y' and 'z' vertex properties.")]
+    MissingPositions,
+    #[error("PLY face references vertex {0}, which does not exist.")]
+    InvalidVertexIndex(usize),
+}
+
+/// Loads ASCII and binary PLY files as Mesh assets.
+///
+/// Files with faces are loaded as triangle lists (computing smooth normals if the file has none),
+/// files without faces are loaded as point clouds.
+#[derive(Clone, Default)]
+pub struct PlyMeshLoader;
+
+impl AssetLoader for PlyMeshLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mesh = load_ply(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(mesh));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ply"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Mesh::TYPE_UUID)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn from_name(name: &str) -> Result<Self, PlyError> {
+        Ok(match name {
+            "char" | "int8" => ScalarType::I8,
+            "uchar" | "uint8" => ScalarType::U8,
+            "short" | "int16" => ScalarType::I16,
+            "ushort" | "uint16" => ScalarType::U16,
+            "int" | "int32" => ScalarType::I32,
+            "uint" | "uint32" => ScalarType::U32,
+            "float" | "float32" => ScalarType::F32,
+            "double" | "float64" => ScalarType::F64,
+            _ => return Err(PlyError::InvalidHeader(format!("unknown type '{}'", name))),
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+            ScalarType::F64 => 8,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum PropertyType {
+    Scalar(ScalarType),
+    List { count: ScalarType, item: ScalarType },
+}
+
+#[derive(Debug)]
+struct Property {
+    name: String,
+    property_type: PropertyType,
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+struct PlyHeader {
+    format: PlyFormat,
+    elements: Vec<Element>,
+    body_offset: usize,
+}
+
+fn parse_header(bytes: &[u8]) -> Result<PlyHeader, PlyError> {
+    const END_HEADER: &[u8] = b"end_header";
+    let header_end = bytes
+        .windows(END_HEADER.len())
+        .position(|window| window == END_HEADER)
+        .ok_or_else(|| PlyError::InvalidHeader("missing 'end_header'".to_string()))?;
+    let body_offset = bytes[header_end..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|newline| header_end + newline + 1)
+        .unwrap_or_else(|| bytes.len());
+    let header = String::from_utf8_lossy(&bytes[..header_end]);
+
+    let mut lines = header.lines();
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err(PlyError::InvalidHeader(
+            "missing 'ply' magic number".to_string(),
+        ));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for line in lines {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => {
+                        return Err(PlyError::InvalidHeader(format!(
+                            "unknown format '{}'",
+                            name
+                        )))
+                    }
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| {
+                    PlyError::InvalidHeader(format!("invalid element count '{}'", count))
+                })?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, item, name] => {
+                let element = elements.last_mut().ok_or_else(|| {
+                    PlyError::InvalidHeader("property declared before element".to_string())
+                })?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    property_type: PropertyType::List {
+                        count: ScalarType::from_name(count)?,
+                        item: ScalarType::from_name(item)?,
+                    },
+                });
+            }
+            ["property", scalar, name] => {
+                let element = elements.last_mut().ok_or_else(|| {
+                    PlyError::InvalidHeader("property declared before element".to_string())
+                })?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    property_type: PropertyType::Scalar(ScalarType::from_name(scalar)?),
+                });
+            }
+            // comments, obj_info and blank lines
+            _ => {}
+        }
+    }
+
+    Ok(PlyHeader {
+        format: format.ok_or_else(|| PlyError::InvalidHeader("missing format".to_string()))?,
+        elements,
+        body_offset,
+    })
+}
+
+/// Reads values from the body of a PLY file, regardless of its format
+struct PlyReader<'a> {
+    format: PlyFormat,
+    bytes: &'a [u8],
+    offset: usize,
+    words: std::str::SplitWhitespace<'a>,
+}
+
+impl<'a> PlyReader<'a> {
+    fn new(format: PlyFormat, bytes: &'a [u8]) -> Self {
+        let words = if format == PlyFormat::Ascii {
+            std::str::from_utf8(bytes).unwrap_or_default()
+        } else {
+            ""
+        }
+        .split_whitespace();
+        PlyReader {
+            format,
+            bytes,
+            offset: 0,
+            words,
+        }
+    }
+
+    fn read(&mut self, scalar_type: ScalarType) -> Result<f64, PlyError> {
+        if self.format == PlyFormat::Ascii {
+            let word = self.words.next().ok_or(PlyError::UnexpectedEnd)?;
+            return word
+                .parse()
+                .map_err(|_| PlyError::InvalidValue(word.to_string()));
+        }
+
+        let size = scalar_type.size();
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + size)
+            .ok_or(PlyError::UnexpectedEnd)?;
+        self.offset += size;
+        let mut buffer = [0u8; 8];
+        buffer[..size].copy_from_slice(bytes);
+        if self.format == PlyFormat::BinaryBigEndian {
+            buffer[..size].reverse();
+        }
+
+        let two = |buffer: [u8; 8]| -> [u8; 2] { buffer[..2].try_into().unwrap() };
+        let four = |buffer: [u8; 8]| -> [u8; 4] { buffer[..4].try_into().unwrap() };
+        Ok(match scalar_type {
+            ScalarType::I8 => buffer[0] as i8 as f64,
+            ScalarType::U8 => buffer[0] as f64,
+            ScalarType::I16 => i16::from_le_bytes(two(buffer)) as f64,
+            ScalarType::U16 => u16::from_le_bytes(two(buffer)) as f64,
+            ScalarType::I32 => i32::from_le_bytes(four(buffer)) as f64,
+            ScalarType::U32 => u32::from_le_bytes(four(buffer)) as f64,
+            ScalarType::F32 => f32::from_le_bytes(four(buffer)) as f64,
+            ScalarType::F64 => f64::from_le_bytes(buffer),
+        })
+    }
+}
+
+fn load_ply(bytes: &[u8]) -> Result<Mesh, PlyError> {
+    let header = parse_header(bytes)?;
+    let mut reader = PlyReader::new(header.format, &bytes[header.body_offset..]);
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    for element in header.elements.iter() {
+        let property_index = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|property| names.contains(&property.name.as_str()))
+        };
+        let position_properties = (
+            property_index(&["x"]),
+            property_index(&["y"]),
+            property_index(&["z"]),
+        );
+        let normal_properties = (
+            property_index(&["nx"]),
+            property_index(&["ny"]),
+            property_index(&["nz"]),
+        );
+        let uv_properties = (
+            property_index(&["u", "s", "texture_u", "texture_s"]),
+            property_index(&["v", "t", "texture_v", "texture_t"]),
+        );
+        let face_property = property_index(&["vertex_indices", "vertex_index"]);
+
+        let mut scalars = vec![0.0; element.properties.len()];
+        let mut list = Vec::new();
+        for _ in 0..element.count {
+            for (index, property) in element.properties.iter().enumerate() {
+                match property.property_type {
+                    PropertyType::Scalar(scalar_type) => {
+                        scalars[index] = reader.read(scalar_type)?
+                    }
+                    PropertyType::List { count, item } => {
+                        let len = reader.read(count)? as usize;
+                        let is_face = element.name == "face" && face_property == Some(index);
+                        if is_face {
+                            list.clear();
+                        }
+                        for _ in 0..len {
+                            let value = reader.read(item)?;
+                            if is_face {
+                                list.push(value as u32);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if element.name == "vertex" {
+                if let (Some(x), Some(y), Some(z)) = position_properties {
+                    positions.push([scalars[x] as f32, scalars[y] as f32, scalars[z] as f32]);
+                } else {
+                    return Err(PlyError::MissingPositions);
+                }
+                if let (Some(x), Some(y), Some(z)) = normal_properties {
+                    normals.push([scalars[x] as f32, scalars[y] as f32, scalars[z] as f32]);
+                }
+                if let (Some(u), Some(v)) = uv_properties {
+                    uvs.push([scalars[u] as f32, 1.0 - scalars[v] as f32]);
+                }
+            } else if element.name == "face" && list.len() >= 3 {
+                // triangulate polygons as a fan
+                for i in 1..list.len() - 1 {
+                    indices.extend_from_slice(&[list[0], list[i], list[i + 1]]);
+                }
+            }
+        }
+    }
+
+    if let Some(index) = indices
+        .iter()
+        .find(|index| **index as usize >= positions.len())
+    {
+        return Err(PlyError::InvalidVertexIndex(*index as usize));
+    }
+
+    let primitive_topology = if indices.is_empty() {
+        indices = (0..positions.len() as u32).collect();
+        PrimitiveTopology::PointList
+    } else {
+        PrimitiveTopology::TriangleList
+    };
+    if normals.is_empty() {
+        normals = smooth_normals(&positions, &indices, primitive_topology);
+    }
+    if uvs.is_empty() {
+        uvs = vec![[0.0, 0.0]; positions.len()];
+    }
+
+    let mut mesh = Mesh::new(primitive_topology);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    Ok(mesh)
+}
+
+/// Computes area weighted vertex normals. Point clouds have no surface to derive normals from, so
+/// their normals are left zeroed.
+fn smooth_normals(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    primitive_topology: PrimitiveTopology,
+) -> Vec<[f32; 3]> {
+    let mut normals = vec![Vec3::zero(); positions.len()];
+    if primitive_topology == PrimitiveTopology::TriangleList {
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [
+                triangle[0] as usize,
+                triangle[1] as usize,
+                triangle[2] as usize,
+            ];
+            let position = |index: usize| Vec3::from(positions[index]);
+            // the cross product's length is twice the triangle's area
+            let normal = (position(b) - position(a)).cross(position(c) - position(a));
+            normals[a] += normal;
+            normals[b] += normal;
+            normals[c] += normal;
+        }
+    }
+
+    normals
+        .into_iter()
+        .map(|normal| {
+            if normal.length_squared() > 0.0 {
+                normal.normalize().into()
+            } else {
+                normal.into()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn load_ascii_quad() {
+        let mesh = load_ply(
+            b"ply
+format ascii 1.0
+comment a unit quad
+element vertex 4
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+1 1 0
+0 1 0
+4 0 1 2 3
+",
+        )
+        .unwrap();
+
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::TriangleList);
+        match mesh.indices() {
+            Some(Indices::U32(indices)) => assert_eq!(indices, &vec![0, 1, 2, 0, 2, 3]),
+            _ => panic!("expected u32 indices"),
+        }
+        match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float3(normals)) => {
+                assert!(normals.iter().all(|normal| *normal == [0.0, 0.0, 1.0]))
+            }
+            _ => panic!("expected computed normals"),
+        }
+    }
+
+    #[test]
+    fn load_binary_point_cloud() {
+        let mut bytes = b"ply
+format binary_big_endian 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property uchar red
+end_header
+"
+        .to_vec();
+        for (position, red) in [([1.0f32, 2.0, 3.0], 255u8), ([4.0, 5.0, 6.0], 0)].iter() {
+            for coordinate in position.iter() {
+                bytes.extend_from_slice(&coordinate.to_be_bytes());
+            }
+            bytes.push(*red);
+        }
+
+        let mesh = load_ply(&bytes).unwrap();
+        assert_eq!(mesh.primitive_topology(), PrimitiveTopology::PointList);
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float3(positions)) => {
+                assert_eq!(positions, &vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            }
+            _ => panic!("expected positions"),
+        }
+    }
+}
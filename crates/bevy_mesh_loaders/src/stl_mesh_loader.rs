@@ -0,0 +1,273 @@
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_math::Vec3;
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::{Indices, Mesh},
+    pipeline::PrimitiveTopology,
+};
+use bevy_utils::{BoxedFuture, Uuid};
+use std::convert::TryInto;
+use thiserror::Error;
+
+/// An error that occurs when loading an STL file
+#[derive(Error, Debug)]
+pub enum StlError {
+    #[error("Binary STL file is truncated.")]
+    Truncated,
+    #[error("Invalid ASCII STL file. Line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// Loads binary and ASCII STL files as Mesh assets. Facets use the normal stored in the file, or
+/// the normal of their winding when the stored one is zeroed, as many exporters leave it.
+/// Degenerate facets without either normal face +Z.
+#[derive(Clone, Default)]
+pub struct StlMeshLoader;
+
+impl AssetLoader for StlMeshLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let facets = if is_binary_stl(bytes) {
+                read_binary_stl(bytes)?
+            } else {
+                read_ascii_stl(&String::from_utf8_lossy(bytes))?
+            };
+            load_context.set_default_asset(LoadedAsset::new(facets_to_mesh(&facets)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["stl"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Mesh::TYPE_UUID)
+    }
+}
+
+/// A triangle and the normal stored for it in the file
+struct Facet {
+    normal: Vec3,
+    vertices: [Vec3; 3],
+}
+
+const BINARY_HEADER_SIZE: usize = 80;
+const BINARY_TRIANGLE_SIZE: usize = 50;
+
+/// ASCII files start with "solid", but so do plenty of binary files, so the binary size is checked
+/// first
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    if bytes.len() < BINARY_HEADER_SIZE + 4 {
+        return false;
+    }
+    let body_size = binary_body_size(&bytes[BINARY_HEADER_SIZE..]);
+    body_size == Some(bytes.len() - BINARY_HEADER_SIZE - 4) || !bytes.starts_with(b"solid")
+}
+
+/// The size of the triangles following the triangle count, or `None` if the count in the file is
+/// too large to be addressed
+fn binary_body_size(bytes: &[u8]) -> Option<usize> {
+    (read_u32(bytes) as usize).checked_mul(BINARY_TRIANGLE_SIZE)
+}
+
+fn read_binary_stl(bytes: &[u8]) -> Result<Vec<Facet>, StlError> {
+    let triangle_count = read_u32(&bytes[BINARY_HEADER_SIZE..]) as usize;
+    let body = &bytes[BINARY_HEADER_SIZE + 4..];
+    match binary_body_size(&bytes[BINARY_HEADER_SIZE..]) {
+        Some(body_size) if body.len() >= body_size => {}
+        _ => return Err(StlError::Truncated),
+    }
+
+    Ok(body
+        .chunks_exact(BINARY_TRIANGLE_SIZE)
+        .take(triangle_count)
+        .map(|triangle| {
+            // the trailing 2 bytes are an unused "attribute byte count"
+            let read_vec3 = |offset: usize| {
+                Vec3::new(
+                    read_f32(&triangle[offset..]),
+                    read_f32(&triangle[offset + 4..]),
+                    read_f32(&triangle[offset + 8..]),
+                )
+            };
+            Facet {
+                normal: read_vec3(0),
+                vertices: [read_vec3(12), read_vec3(24), read_vec3(36)],
+            }
+        })
+        .collect())
+}
+
+fn read_ascii_stl(source: &str) -> Result<Vec<Facet>, StlError> {
+    let mut facets = Vec::new();
+    let mut normal = Vec3::zero();
+    let mut vertices = Vec::with_capacity(3);
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("facet") => {
+                if words.next() == Some("normal") {
+                    normal = read_ascii_vec3(&mut words, line_number, "normal")?;
+                } else {
+                    normal = Vec3::zero();
+                }
+            }
+            Some("vertex") => {
+                vertices.push(read_ascii_vec3(&mut words, line_number, "vertex")?);
+            }
+            Some("endloop") => {
+                if vertices.len() != 3 {
+                    return Err(StlError::Parse {
+                        line: line_number,
+                        message: format!("facets need 3 vertices, found {}", vertices.len()),
+                    });
+                }
+                facets.push(Facet {
+                    normal,
+                    vertices: [vertices[0], vertices[1], vertices[2]],
+                });
+                vertices.clear();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(facets)
+}
+
+fn read_ascii_vec3<'a>(
+    words: &mut impl Iterator<Item = &'a str>,
+    line_number: usize,
+    keyword: &str,
+) -> Result<Vec3, StlError> {
+    let mut coordinates = [0.0; 3];
+    for coordinate in coordinates.iter_mut() {
+        *coordinate = words
+            .next()
+            .and_then(|word| word.parse().ok())
+            .ok_or_else(|| StlError::Parse {
+                line: line_number,
+                message: format!("expected three numbers after '{}'", keyword),
+            })?;
+    }
+    Ok(Vec3::from(coordinates))
+}
+
+/// The normal stored for the facet if it has one, otherwise the normal of its winding
+fn facet_normal(facet: &Facet) -> Vec3 {
+    const MIN_LENGTH: f32 = 1e-12;
+    let [a, b, c] = facet.vertices;
+    let winding_normal = (b - a).cross(c - a);
+    // comparisons with NaN lengths are false, so non-finite normals are skipped
+    if facet.normal.length() > MIN_LENGTH {
+        facet.normal.normalize()
+    } else if winding_normal.length() > MIN_LENGTH {
+        winding_normal.normalize()
+    } else {
+        Vec3::unit_z()
+    }
+}
+
+fn facets_to_mesh(facets: &[Facet]) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(facets.len() * 3);
+    let mut normals: Vec<[f32; 3]> = Vec::with_capacity(facets.len() * 3);
+    for facet in facets.iter() {
+        let normal = facet_normal(facet);
+        for vertex in facet.vertices.iter() {
+            positions.push((*vertex).into());
+            normals.push(normal.into());
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32((0..positions.len() as u32).collect())));
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0f32, 0.0]; positions.len()]);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::mesh::VertexAttributeValues;
+
+    fn normals(mesh: &Mesh) -> Vec<[f32; 3]> {
+        match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float3(normals)) => normals.clone(),
+            _ => panic!("mesh has no normals"),
+        }
+    }
+
+    #[test]
+    fn ascii_stl_is_loaded() {
+        let source = "solid test
+            facet normal 0 0 2
+                outer loop
+                    vertex 0 0 0
+                    vertex 1 0 0
+                    vertex 0 1 0
+                endloop
+            endfacet
+            facet normal 0 0 0
+                outer loop
+                    vertex 0 0 0
+                    vertex 0 1 0
+                    vertex 1 0 0
+                endloop
+            endfacet
+        endsolid test";
+        assert!(!is_binary_stl(source.as_bytes()));
+        let mesh = facets_to_mesh(&read_ascii_stl(source).unwrap());
+        let mut expected = vec![[0.0, 0.0, 1.0]; 3];
+        expected.extend_from_slice(&[[0.0, 0.0, -1.0]; 3]);
+        assert_eq!(normals(&mesh), expected);
+
+        let source = "solid test\nfacet normal 0 0 1\nouter loop\nvertex 0 0\n";
+        assert!(matches!(
+            read_ascii_stl(source),
+            Err(StlError::Parse { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn binary_stl_is_loaded() {
+        let facets: &[[[f32; 3]; 4]] = &[
+            [[0.0; 3], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            // a degenerate facet, with all its vertices on a line and no normal
+            [[0.0; 3], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]],
+        ];
+        let mut bytes = vec![0; BINARY_HEADER_SIZE];
+        bytes.extend_from_slice(&(facets.len() as u32).to_le_bytes());
+        for facet in facets.iter() {
+            for component in facet.iter().flatten() {
+                bytes.extend_from_slice(&component.to_le_bytes());
+            }
+            bytes.extend_from_slice(&[0; 2]);
+        }
+        assert!(is_binary_stl(&bytes));
+
+        let mesh = facets_to_mesh(&read_binary_stl(&bytes).unwrap());
+        let mut expected = vec![[1.0, 0.0, 0.0]; 3];
+        expected.extend_from_slice(&[[0.0, 0.0, 1.0]; 3]);
+        assert_eq!(normals(&mesh), expected);
+
+        bytes.truncate(bytes.len() - 1);
+        assert!(matches!(read_binary_stl(&bytes), Err(StlError::Truncated)));
+    }
+}
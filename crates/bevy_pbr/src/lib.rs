@@ -3,13 +3,19 @@ pub mod render_graph;
 mod entity;
 mod light;
 mod material;
+mod morph;
+mod skinning;
 
 pub use entity::*;
 pub use light::*;
 pub use material::*;
+pub use morph::*;
+pub use skinning::*;
 
 pub mod prelude {
-    pub use crate::{entity::*, light::Light, material::StandardMaterial};
+    pub use crate::{
+        entity::*, light::Light, material::StandardMaterial, morph::MorphWeights, skinning::Skin,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -26,11 +32,25 @@ pub struct PbrPlugin;
 impl Plugin for PbrPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<StandardMaterial>()
+            .add_asset::<MorphTargets>()
             .register_type::<Light>()
+            .register_type::<Skin>()
+            .register_type::<MorphWeights>()
             .add_system_to_stage(
                 stage::POST_UPDATE,
                 shader::asset_shader_defs_system::<StandardMaterial>,
             )
+            // joints are followed once their global transforms were updated
+            .add_system_to_stage(stage::POST_UPDATE, skinning_system)
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                shader::shader_defs_system::<JointMatrices>,
+            )
+            .add_system_to_stage(stage::POST_UPDATE, morph_targets_system)
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                shader::asset_shader_defs_system::<MorphTargets>,
+            )
             .init_resource::<AmbientLight>();
         let resources = app.resources();
         let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
@@ -46,7 +66,7 @@ impl Plugin for PbrPlugin {
             StandardMaterial {
                 albedo: Color::PINK,
                 shaded: false,
-                albedo_texture: None,
+                ..Default::default()
             },
         );
     }
@@ -1,4 +1,5 @@
 use bevy_asset::{self, Handle};
+use bevy_math::Mat4;
 use bevy_reflect::TypeUuid;
 use bevy_render::{color::Color, renderer::RenderResources, shader::ShaderDefs, texture::Texture};
 
@@ -9,6 +10,8 @@ pub struct StandardMaterial {
     pub albedo: Color,
     #[shader_def]
     pub albedo_texture: Option<Handle<Texture>>,
+    /// Transform applied to the mesh's UV coordinates before sampling `albedo_texture`
+    pub uv_transform: Mat4,
     #[render_resources(ignore)]
     #[shader_def]
     pub shaded: bool,
@@ -19,6 +22,7 @@ impl Default for StandardMaterial {
         StandardMaterial {
             albedo: Color::rgb(1.0, 1.0, 1.0),
             albedo_texture: None,
+            uv_transform: Mat4::identity(),
             shaded: true,
         }
     }
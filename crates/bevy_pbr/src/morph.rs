@@ -0,0 +1,140 @@
+use bevy_app::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Commands, Entity, Local, Query, Res, ResMut};
+use bevy_reflect::{Reflect, ReflectComponent, TypeUuid};
+use bevy_render::{
+    camera::NoFrustumCulling,
+    mesh::Mesh,
+    renderer::{RenderResource, RenderResources},
+    shader::{ShaderDefIterator, ShaderDefs},
+};
+use bevy_utils::HashMap;
+
+/// How much each of the [MorphTarget]s of the mesh of an entity is applied, usually from 0 to 1
+///
+/// [MorphTarget]: bevy_render::mesh::MorphTarget
+#[derive(Debug, Default, Clone, Reflect, RenderResources)]
+#[reflect(Component)]
+pub struct MorphWeights {
+    #[render_resources(buffer)]
+    pub weights: Vec<f32>,
+}
+
+impl MorphWeights {
+    pub fn new(weights: Vec<f32>) -> Self {
+        MorphWeights { weights }
+    }
+}
+
+/// The offsets of the [MorphTarget]s of a mesh, in the layout the vertex shader reads them: for
+/// each target and then each vertex, the offset of the position and then of the normal.
+///
+/// [MorphTarget]: bevy_render::mesh::MorphTarget
+#[derive(Debug, Default, Clone, RenderResources, TypeUuid)]
+#[uuid = "60f9aec6-d8b9-4699-a53e-065158ba6533"]
+pub struct MorphTargets {
+    pub vertex_count: u32,
+    #[render_resources(buffer)]
+    pub offsets: Vec<[f32; 4]>,
+}
+
+impl MorphTargets {
+    /// The shader def of entities with [MorphTargets]
+    pub const SHADER_DEF: &'static str = "MORPHED";
+}
+
+impl From<&Mesh> for MorphTargets {
+    fn from(mesh: &Mesh) -> Self {
+        let vertex_count = mesh.count_vertices();
+        let mut offsets = Vec::with_capacity(mesh.morph_targets().len() * vertex_count * 2);
+        for morph_target in mesh.morph_targets() {
+            for vertex in 0..vertex_count {
+                let position = morph_target.positions.get(vertex);
+                let normal = morph_target
+                    .normals
+                    .as_ref()
+                    .and_then(|normals| normals.get(vertex));
+                for offset in [position, normal].iter() {
+                    let [x, y, z] = offset.cloned().unwrap_or([0.0; 3]);
+                    offsets.push([x, y, z, 0.0]);
+                }
+            }
+        }
+        MorphTargets {
+            vertex_count: vertex_count as u32,
+            offsets,
+        }
+    }
+}
+
+impl ShaderDefs for MorphTargets {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some(MorphTargets::SHADER_DEF),
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
+}
+
+#[derive(Default)]
+pub struct MorphTargetsState {
+    mesh_event_reader: EventReader<AssetEvent<Mesh>>,
+    morph_targets: HashMap<Handle<Mesh>, Handle<MorphTargets>>,
+}
+
+/// Adds the [MorphTargets] of their mesh to entities with [MorphWeights]. The targets are shared
+/// by all entities with the same mesh, and built again when the mesh changes. Morphed meshes move
+/// out of their bounds, so they aren't culled.
+pub fn morph_targets_system(
+    commands: &mut Commands,
+    mut state: Local<MorphTargetsState>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_events: Res<Events<AssetEvent<Mesh>>>,
+    mut morph_targets: ResMut<Assets<MorphTargets>>,
+    query: Query<(
+        Entity,
+        &Handle<Mesh>,
+        &MorphWeights,
+        Option<&Handle<MorphTargets>>,
+    )>,
+) {
+    let state = &mut *state;
+    for event in state.mesh_event_reader.iter(&mesh_events) {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                if let Some(morph_targets_handle) = state.morph_targets.remove(handle) {
+                    morph_targets.remove(&morph_targets_handle);
+                }
+            }
+            AssetEvent::Created { .. }
+            | AssetEvent::Failed { .. }
+            | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    for (entity, mesh_handle, morph_weights, current_handle) in query.iter() {
+        let mesh = match meshes.get(mesh_handle) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        // without targets or weights there is nothing to blend
+        if mesh.morph_targets().is_empty() || morph_weights.weights.is_empty() {
+            continue;
+        }
+        let handle = state
+            .morph_targets
+            .entry(mesh_handle.clone_weak())
+            .or_insert_with(|| morph_targets.add(MorphTargets::from(mesh)));
+        if current_handle != Some(&*handle) {
+            commands.insert(entity, (handle.clone(), NoFrustumCulling));
+        }
+    }
+}
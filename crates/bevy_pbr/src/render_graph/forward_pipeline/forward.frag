@@ -11,6 +11,9 @@ struct Light {
 layout(location = 0) in vec3 v_Position;
 layout(location = 1) in vec3 v_Normal;
 layout(location = 2) in vec2 v_Uv;
+# ifdef MESH_VERTEX_COLOR
+layout(location = 3) in vec4 v_Color;
+# endif
 
 layout(location = 0) out vec4 o_Target;
 
@@ -28,6 +31,10 @@ layout(set = 3, binding = 0) uniform StandardMaterial_albedo {
     vec4 Albedo;
 };
 
+layout(set = 3, binding = 3) uniform StandardMaterial_uv_transform {
+    mat4 UvTransform;
+};
+
 # ifdef STANDARDMATERIAL_ALBEDO_TEXTURE
 layout(set = 3, binding = 1) uniform texture2D StandardMaterial_albedo_texture;
 layout(set = 3, binding = 2) uniform sampler StandardMaterial_albedo_texture_sampler;
@@ -38,7 +45,10 @@ void main() {
 # ifdef STANDARDMATERIAL_ALBEDO_TEXTURE
     output_color *= texture(
         sampler2D(StandardMaterial_albedo_texture, StandardMaterial_albedo_texture_sampler),
-        v_Uv);
+        (UvTransform * vec4(v_Uv, 0.0, 1.0)).xy);
+# endif
+# ifdef MESH_VERTEX_COLOR
+    output_color *= v_Color;
 # endif
 
 # ifdef STANDARDMATERIAL_SHADED
@@ -8,6 +8,16 @@ layout(location = 0) out vec3 v_Position;
 layout(location = 1) out vec3 v_Normal;
 layout(location = 2) out vec2 v_Uv;
 
+# ifdef MESH_VERTEX_COLOR
+layout(location = 3) in vec4 Vertex_Color;
+layout(location = 3) out vec4 v_Color;
+# endif
+
+# ifdef SKINNED
+layout(location = 4) in uvec4 Vertex_JointIndex;
+layout(location = 5) in vec4 Vertex_JointWeight;
+# endif
+
 layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
 };
@@ -16,9 +26,53 @@ layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
 };
 
+# ifdef SKINNED
+layout(set = 2, binding = 1) readonly buffer JointMatrices_matrices {
+    mat4[] Joints;
+};
+# endif
+
+# ifdef MORPHED
+layout(set = 2, binding = 2) readonly buffer MorphWeights_weights {
+    float[] Weights;
+};
+layout(set = 2, binding = 3) uniform MorphTargets_vertex_count {
+    uint MorphVertexCount;
+};
+layout(set = 2, binding = 4) readonly buffer MorphTargets_offsets {
+    vec4[] MorphOffsets;
+};
+# endif
+
 void main() {
-    v_Normal = mat3(Model) * Vertex_Normal;
-    v_Position = (Model * vec4(Vertex_Position, 1.0)).xyz;
+    vec4 position = vec4(Vertex_Position, 1.0);
+    vec3 normal = Vertex_Normal;
+# ifdef MORPHED
+    // the targets are blended before the skin moves the vertex
+    // weights without a target are ignored
+    int morph_target_count = min(
+        Weights.length(),
+        int(uint(MorphOffsets.length()) / (MorphVertexCount * 2))
+    );
+    for (int morph_target = 0; morph_target < morph_target_count; morph_target++) {
+        uint offset = (uint(morph_target) * MorphVertexCount + uint(gl_VertexIndex)) * 2;
+        position.xyz += Weights[morph_target] * MorphOffsets[offset].xyz;
+        normal += Weights[morph_target] * MorphOffsets[offset + 1].xyz;
+    }
+# endif
+# ifdef SKINNED
+    mat4 skin = Vertex_JointWeight.x * Joints[Vertex_JointIndex.x]
+        + Vertex_JointWeight.y * Joints[Vertex_JointIndex.y]
+        + Vertex_JointWeight.z * Joints[Vertex_JointIndex.z]
+        + Vertex_JointWeight.w * Joints[Vertex_JointIndex.w];
+    position = skin * position;
+    normal = mat3(skin) * normal;
+# endif
+    v_Normal = mat3(Model) * normal;
+    v_Position = (Model * position).xyz;
     v_Uv = Vertex_Uv;
+# ifdef MESH_VERTEX_COLOR
+    v_Color = Vertex_Color;
+# endif
     gl_Position = ViewProj * vec4(v_Position, 1.0);
 }
@@ -9,6 +9,9 @@ pub mod node {
     pub const TRANSFORM: &str = "transform";
     pub const STANDARD_MATERIAL: &str = "standard_material";
     pub const LIGHTS: &str = "lights";
+    pub const JOINT_MATRICES: &str = "joint_matrices";
+    pub const MORPH_WEIGHTS: &str = "morph_weights";
+    pub const MORPH_TARGETS: &str = "morph_targets";
 }
 
 /// the names of pbr uniforms
@@ -16,7 +19,7 @@ pub mod uniform {
     pub const LIGHTS: &str = "Lights";
 }
 
-use crate::prelude::StandardMaterial;
+use crate::{prelude::StandardMaterial, JointMatrices, MorphTargets, MorphWeights};
 use bevy_asset::Assets;
 use bevy_ecs::Resources;
 use bevy_render::{
@@ -36,6 +39,18 @@ pub(crate) fn add_pbr_graph(graph: &mut RenderGraph, resources: &Resources) {
         AssetRenderResourcesNode::<StandardMaterial>::new(true),
     );
     graph.add_system_node(node::LIGHTS, LightsNode::new(10));
+    graph.add_system_node(
+        node::JOINT_MATRICES,
+        RenderResourcesNode::<JointMatrices>::new(false),
+    );
+    graph.add_system_node(
+        node::MORPH_WEIGHTS,
+        RenderResourcesNode::<MorphWeights>::new(false),
+    );
+    graph.add_system_node(
+        node::MORPH_TARGETS,
+        AssetRenderResourcesNode::<MorphTargets>::new(false),
+    );
     let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
     let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
     pipelines.set_untracked(
@@ -53,4 +68,13 @@ pub(crate) fn add_pbr_graph(graph: &mut RenderGraph, resources: &Resources) {
     graph
         .add_node_edge(node::LIGHTS, base::node::MAIN_PASS)
         .unwrap();
+    graph
+        .add_node_edge(node::JOINT_MATRICES, base::node::MAIN_PASS)
+        .unwrap();
+    graph
+        .add_node_edge(node::MORPH_WEIGHTS, base::node::MAIN_PASS)
+        .unwrap();
+    graph
+        .add_node_edge(node::MORPH_TARGETS, base::node::MAIN_PASS)
+        .unwrap();
 }
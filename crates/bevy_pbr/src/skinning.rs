@@ -0,0 +1,112 @@
+use bevy_ecs::{Commands, Entity, EntityMap, MapEntities, MapEntitiesError, Query, Without};
+use bevy_math::Mat4;
+use bevy_reflect::{Reflect, ReflectComponent, ReflectMapEntities};
+use bevy_render::{
+    camera::NoFrustumCulling,
+    renderer::{RenderResource, RenderResources},
+    shader::{ShaderDefIterator, ShaderDefs},
+};
+use bevy_transform::components::GlobalTransform;
+
+/// Deforms the mesh of an entity with the transforms of its joints. Each vertex of the mesh is
+/// moved by up to four joints, listed by [Mesh::ATTRIBUTE_JOINT_INDEX] and weighted by
+/// [Mesh::ATTRIBUTE_JOINT_WEIGHT].
+///
+/// [Mesh::ATTRIBUTE_JOINT_INDEX]: bevy_render::mesh::Mesh::ATTRIBUTE_JOINT_INDEX
+/// [Mesh::ATTRIBUTE_JOINT_WEIGHT]: bevy_render::mesh::Mesh::ATTRIBUTE_JOINT_WEIGHT
+#[derive(Debug, Default, Clone, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct Skin {
+    /// The entities the joints follow
+    pub joints: Vec<Entity>,
+    /// The inverse of the global transform of each joint in the pose the mesh was modeled in
+    pub inverse_bind_matrices: Vec<Mat4>,
+}
+
+impl MapEntities for Skin {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        for joint in self.joints.iter_mut() {
+            *joint = entity_map.get(*joint)?;
+        }
+        Ok(())
+    }
+}
+
+/// The matrices that move the vertices of a [Skin] from the bind pose to the current pose of the
+/// joints, relative to the entity of the mesh
+#[derive(Debug, Default, Clone, RenderResources)]
+pub struct JointMatrices {
+    #[render_resources(buffer)]
+    pub matrices: Vec<[f32; 16]>,
+}
+
+impl JointMatrices {
+    /// The shader def of entities with [JointMatrices]
+    pub const SHADER_DEF: &'static str = "SKINNED";
+}
+
+impl ShaderDefs for JointMatrices {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some(JointMatrices::SHADER_DEF),
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
+}
+
+/// Adds [JointMatrices] to entities with a [Skin], and updates them from the global transforms of
+/// the joints. Skinned meshes move out of their bounds, so they aren't culled.
+pub fn skinning_system(
+    commands: &mut Commands,
+    new_skin_query: Query<(Entity, &Skin, &GlobalTransform), Without<JointMatrices>>,
+    mut skin_query: Query<(&Skin, &GlobalTransform, &mut JointMatrices)>,
+    joint_query: Query<&GlobalTransform>,
+) {
+    for (entity, skin, global_transform) in new_skin_query.iter() {
+        // skins without joints leave their meshes as they are
+        if !skin.joints.is_empty() {
+            let matrices = compute_joint_matrices(skin, global_transform, &joint_query);
+            commands.insert(entity, (JointMatrices { matrices }, NoFrustumCulling));
+        }
+    }
+
+    for (skin, global_transform, mut joint_matrices) in skin_query.iter_mut() {
+        let matrices = compute_joint_matrices(skin, global_transform, &joint_query);
+        // the matrices are only uploaded again when they changed
+        if joint_matrices.matrices != matrices && !matrices.is_empty() {
+            joint_matrices.matrices = matrices;
+        }
+    }
+}
+
+fn compute_joint_matrices(
+    skin: &Skin,
+    global_transform: &GlobalTransform,
+    joint_query: &Query<&GlobalTransform>,
+) -> Vec<[f32; 16]> {
+    let inverse_mesh_matrix = global_transform.compute_matrix().inverse();
+    skin.joints
+        .iter()
+        .enumerate()
+        .map(|(index, joint)| {
+            let joint_matrix = joint_query
+                .get(*joint)
+                .map(|joint_transform| joint_transform.compute_matrix())
+                .unwrap_or_else(|_| global_transform.compute_matrix());
+            let inverse_bind_matrix = skin
+                .inverse_bind_matrices
+                .get(index)
+                .cloned()
+                .unwrap_or_else(Mat4::identity);
+            (inverse_mesh_matrix * joint_matrix * inverse_bind_matrix).to_cols_array()
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Entity, Local, Query, Res, ResMut, With};
+use bevy_input::{mouse::MouseButton, Input};
+use bevy_math::Vec2;
+use bevy_render::{
+    camera::Camera,
+    mesh::{Aabb, Mesh},
+    picking::{intersect_mesh_with_bounds, Intersection, Ray},
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{CursorMoved, WindowId, Windows};
+
+pub mod prelude {
+    pub use crate::{PickableMesh, PickingCamera, PointerClick, PointerOut, PointerOver};
+}
+
+/// Marks a mesh entity that can be picked with the cursor
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PickableMesh;
+
+/// Marks the camera that picking rays are cast from
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PickingCamera;
+
+/// Sent when the cursor starts hovering a [PickableMesh]
+#[derive(Debug, Clone)]
+pub struct PointerOver {
+    pub entity: Entity,
+    pub intersection: Intersection,
+}
+
+/// Sent when the cursor stops hovering a [PickableMesh]
+#[derive(Debug, Clone)]
+pub struct PointerOut {
+    pub entity: Entity,
+}
+
+/// Sent when the left mouse button is pressed on a [PickableMesh]
+#[derive(Debug, Clone)]
+pub struct PointerClick {
+    pub entity: Entity,
+    pub intersection: Intersection,
+}
+
+/// The [PickableMesh] under the cursor, updated every frame
+#[derive(Debug, Default, Clone)]
+pub struct PickingState {
+    /// The closest entity under the cursor, if any
+    pub hovered: Option<(Entity, Intersection)>,
+}
+
+#[derive(Default)]
+pub struct PickingSystemState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor: Option<(WindowId, Vec2)>,
+}
+
+/// Casts a ray from the [PickingCamera] through the cursor, updates [PickingState] and sends
+/// pointer events for the closest [PickableMesh] it hits
+#[allow(clippy::too_many_arguments)]
+pub fn picking_system(
+    mut state: Local<PickingSystemState>,
+    mut picking_state: ResMut<PickingState>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    meshes: Res<Assets<Mesh>>,
+    mut pointer_over_events: ResMut<Events<PointerOver>>,
+    mut pointer_out_events: ResMut<Events<PointerOut>>,
+    mut pointer_click_events: ResMut<Events<PointerClick>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PickingCamera>>,
+    mesh_query: Query<(Entity, &Handle<Mesh>, &GlobalTransform, &Aabb), With<PickableMesh>>,
+) {
+    let state = &mut *state;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor = Some((cursor_moved.id, cursor_moved.position));
+    }
+
+    let ray = state.cursor.and_then(|(window_id, cursor_position)| {
+        let window = windows.get(window_id)?;
+        camera_query
+            .iter()
+            .find(|(camera, _)| camera.window == window_id)
+            .map(|(camera, camera_transform)| {
+                Ray::from_screenspace(cursor_position, window, camera, camera_transform)
+            })
+    });
+
+    let mut hovered: Option<(Entity, Intersection)> = None;
+    if let Some(ray) = ray {
+        // meshes are hit once they are loaded and have an [Aabb]
+        for (entity, mesh_handle, transform, aabb) in mesh_query.iter() {
+            let mesh = match meshes.get(mesh_handle) {
+                Some(mesh) => mesh,
+                None => continue,
+            };
+            let intersection =
+                intersect_mesh_with_bounds(&ray, mesh, transform, aabb.min, aabb.max);
+            if let Some(intersection) = intersection {
+                if hovered.map_or(true, |(_, closest)| {
+                    intersection.distance < closest.distance
+                }) {
+                    hovered = Some((entity, intersection));
+                }
+            }
+        }
+    }
+
+    let previous = picking_state.hovered.map(|(entity, _)| entity);
+    let current = hovered.map(|(entity, _)| entity);
+    if previous != current {
+        if let Some(entity) = previous {
+            pointer_out_events.send(PointerOut { entity });
+        }
+        if let Some((entity, intersection)) = hovered {
+            pointer_over_events.send(PointerOver {
+                entity,
+                intersection,
+            });
+        }
+    }
+    if let Some((entity, intersection)) = hovered {
+        if mouse_button_input.just_pressed(MouseButton::Left) {
+            pointer_click_events.send(PointerClick {
+                entity,
+                intersection,
+            });
+        }
+    }
+    picking_state.hovered = hovered;
+}
+
+/// Sends pointer events for [PickableMesh] entities under the cursor of a [PickingCamera]
+#[derive(Default)]
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<PickingState>()
+            .add_event::<PointerOver>()
+            .add_event::<PointerOut>()
+            .add_event::<PointerClick>()
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, picking_system);
+    }
+}
@@ -9,6 +9,7 @@ use std::marker::PhantomData;
 pub struct ReflectComponent {
     add_component: fn(&mut World, resources: &Resources, Entity, &dyn Reflect),
     apply_component: fn(&mut World, Entity, &dyn Reflect),
+    remove_component: fn(&mut World, Entity),
     reflect_component: unsafe fn(&Archetype, usize) -> &dyn Reflect,
     copy_component: fn(&World, &mut World, &Resources, Entity, Entity),
 }
@@ -28,6 +29,11 @@ impl ReflectComponent {
         (self.apply_component)(world, entity, component);
     }
 
+    /// Removes the component from the entity, if it has it
+    pub fn remove_component(&self, world: &mut World, entity: Entity) {
+        (self.remove_component)(world, entity);
+    }
+
     /// # Safety
     /// This does not do bound checks on entity_index. You must make sure entity_index is within bounds before calling.
     pub unsafe fn reflect_component<'a>(
@@ -68,6 +74,9 @@ impl<C: Component + Reflect + FromResources> FromType<C> for ReflectComponent {
                 let mut component = world.get_mut::<C>(entity).unwrap();
                 component.apply(reflected_component);
             },
+            remove_component: |world, entity| {
+                let _ = world.remove_one::<C>(entity);
+            },
             copy_component: |source_world,
                              destination_world,
                              resources,
@@ -186,6 +195,7 @@ impl<Runtime: Component + IntoComponent<Scene>, Scene: Component> FromType<Runti
 #[derive(Clone)]
 pub struct ReflectMapEntities {
     map_entities: fn(&mut World, &EntityMap) -> Result<(), MapEntitiesError>,
+    map_specific_entities: fn(&mut World, &EntityMap, &[Entity]) -> Result<(), MapEntitiesError>,
 }
 
 impl ReflectMapEntities {
@@ -196,6 +206,17 @@ impl ReflectMapEntities {
     ) -> Result<(), MapEntitiesError> {
         (self.map_entities)(world, entity_map)
     }
+
+    /// Maps the entities referred to by the components of `entities` only, leaving the other
+    /// entities of the world untouched
+    pub fn map_specific_entities(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+        entities: &[Entity],
+    ) -> Result<(), MapEntitiesError> {
+        (self.map_specific_entities)(world, entity_map, entities)
+    }
 }
 
 impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
@@ -206,6 +227,15 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
                     component.map_entities(entity_map)?;
                 }
 
+                Ok(())
+            },
+            map_specific_entities: |world, entity_map, entities| {
+                for entity in entities.iter() {
+                    if let Ok(mut component) = world.get_mut::<C>(*entity) {
+                        component.map_entities(entity_map)?;
+                    }
+                }
+
                 Ok(())
             },
         }
@@ -0,0 +1,80 @@
+use crate::mesh::Aabb;
+use bevy_ecs::{Added, Commands, Component, Entity, Query};
+use bevy_math::{Mat4, Vec3, Vec4};
+
+/// Keeps an entity with an [Aabb] from being culled when it is outside of the view of cameras.
+/// Sprites and ui nodes have it, as their shaders scale their meshes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoFrustumCulling;
+
+/// Adds [NoFrustumCulling] to entities that get a `T`
+pub fn no_frustum_culling_system<T: Component>(
+    commands: &mut Commands,
+    query: Query<Entity, Added<T>>,
+) {
+    for entity in query.iter() {
+        commands.insert_one(entity, NoFrustumCulling);
+    }
+}
+
+/// The space a camera sees, bounded by six planes facing inwards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// The normal and distance from the origin of the left, right, bottom, top, near and far
+    /// planes
+    pub planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from the product of the projection and view matrices of a camera,
+    /// which maps depths from 0 to 1
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let rows = view_projection.transpose();
+        let (x, y, z, w) = (rows.x_axis, rows.y_axis, rows.z_axis, rows.w_axis);
+        let normalize = |plane: Vec4| plane / plane.truncate().length();
+        Frustum {
+            planes: [
+                normalize(w + x),
+                normalize(w - x),
+                normalize(w + y),
+                normalize(w - y),
+                normalize(z),
+                normalize(w - z),
+            ],
+        }
+    }
+
+    /// Returns false when the box is entirely outside of one of the planes
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        self.planes.iter().all(|plane| {
+            let normal = plane.truncate();
+            let radius = (normal.abs() * half_extents).dot(Vec3::one());
+            normal.dot(center) + plane.w() + radius >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frustum;
+    use crate::{camera::CameraProjection, camera::PerspectiveProjection, mesh::Aabb};
+    use bevy_math::{Mat4, Vec3};
+
+    #[test]
+    fn boxes_outside_of_the_view_are_culled() {
+        let projection = PerspectiveProjection::default().get_projection_matrix();
+        let view = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0)).inverse();
+        let frustum = Frustum::from_view_projection(&(projection * view));
+
+        let aabb = |center: Vec3| Aabb::from_min_max(center - Vec3::one(), center + Vec3::one());
+        assert!(frustum.intersects_aabb(&aabb(Vec3::zero())));
+        // behind the camera, far to the side and beyond the far plane
+        assert!(!frustum.intersects_aabb(&aabb(Vec3::new(0.0, 0.0, 20.0))));
+        assert!(!frustum.intersects_aabb(&aabb(Vec3::new(100.0, 0.0, 0.0))));
+        assert!(!frustum.intersects_aabb(&aabb(Vec3::new(0.0, 0.0, -2000.0))));
+        // partly in view
+        assert!(frustum.intersects_aabb(&aabb(Vec3::new(0.0, 0.0, 9.0))));
+    }
+}
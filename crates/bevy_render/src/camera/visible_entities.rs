@@ -1,7 +1,7 @@
-use super::{Camera, DepthCalculation};
-use crate::Draw;
+use super::{Camera, DepthCalculation, Frustum, NoFrustumCulling};
+use crate::{mesh::Aabb, Draw};
 use bevy_core::FloatOrd;
-use bevy_ecs::{Entity, Query, With};
+use bevy_ecs::{Entity, Query, With, Without};
 use bevy_reflect::Reflect;
 use bevy_transform::prelude::GlobalTransform;
 
@@ -23,14 +23,20 @@ impl VisibleEntities {
     }
 }
 
+/// Collects the visible entities of each camera in drawing order. Entities with an [Aabb] that
+/// is outside of the [Frustum] of the camera are left out, unless they have [NoFrustumCulling].
 pub fn visible_entities_system(
     mut camera_query: Query<(&Camera, &GlobalTransform, &mut VisibleEntities)>,
     draw_query: Query<(Entity, &Draw)>,
     draw_transform_query: Query<&GlobalTransform, With<Draw>>,
+    culling_query: Query<(&Aabb, &GlobalTransform), (With<Draw>, Without<NoFrustumCulling>)>,
 ) {
     for (camera, camera_global_transform, mut visible_entities) in camera_query.iter_mut() {
         visible_entities.value.clear();
         let camera_position = camera_global_transform.translation;
+        let frustum = Frustum::from_view_projection(
+            &(camera.projection_matrix * camera_global_transform.compute_matrix().inverse()),
+        );
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
@@ -38,6 +44,12 @@ pub fn visible_entities_system(
             if !draw.is_visible {
                 continue;
             }
+            if let Ok((aabb, global_transform)) = culling_query.get(entity) {
+                let world_aabb = aabb.transformed(&global_transform.compute_matrix());
+                if !frustum.intersects_aabb(&world_aabb) {
+                    continue;
+                }
+            }
 
             let order = if let Ok(global_transform) = draw_transform_query.get(entity) {
                 let position = global_transform.translation;
@@ -5,6 +5,7 @@ pub mod draw;
 pub mod entity;
 pub mod mesh;
 pub mod pass;
+pub mod picking;
 pub mod pipeline;
 pub mod render_graph;
 pub mod renderer;
@@ -20,11 +21,11 @@ pub mod prelude {
         color::Color,
         draw::Draw,
         entity::*,
-        mesh::{shape, Mesh},
+        mesh::{shape, Aabb, Mesh},
         pass::ClearColor,
         pipeline::RenderPipelines,
         shader::Shader,
-        texture::Texture,
+        texture::{Texture, WindowIcons},
     };
 }
 
@@ -44,11 +45,17 @@ use render_graph::{
     RenderGraph,
 };
 use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
+#[cfg(feature = "dds")]
+use texture::DdsTextureLoader;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
 #[cfg(feature = "png")]
 use texture::ImageTextureLoader;
-use texture::TextureResourceSystemState;
+#[cfg(feature = "ktx2")]
+use texture::Ktx2TextureLoader;
+#[cfg(feature = "svg")]
+use texture::SvgLoader;
+use texture::{TextureResourceSystemState, WindowIcons};
 
 /// The names of "render" App stages
 pub mod stage {
@@ -86,6 +93,18 @@ impl Plugin for RenderPlugin {
         {
             app.init_asset_loader::<HdrTextureLoader>();
         }
+        #[cfg(feature = "dds")]
+        {
+            app.init_asset_loader::<DdsTextureLoader>();
+        }
+        #[cfg(feature = "ktx2")]
+        {
+            app.init_asset_loader::<Ktx2TextureLoader>();
+        }
+        #[cfg(feature = "svg")]
+        {
+            app.init_asset_loader::<SvgLoader>();
+        }
 
         if app.resources().get::<ClearColor>().is_none() {
             app.resources_mut().insert(ClearColor::default());
@@ -118,7 +137,9 @@ impl Plugin for RenderPlugin {
             .init_resource::<TextureResourceSystemState>()
             .init_resource::<AssetRenderResourceBindings>()
             .init_resource::<ActiveCameras>()
+            .init_resource::<WindowIcons>()
             .add_system_to_stage(bevy_app::stage::PRE_UPDATE, draw::clear_draw_system)
+            .add_system_to_stage(bevy_app::stage::POST_UPDATE, texture::window_icon_system)
             .add_system_to_stage(bevy_app::stage::POST_UPDATE, camera::active_cameras_system)
             .add_system_to_stage(
                 bevy_app::stage::POST_UPDATE,
@@ -128,6 +149,11 @@ impl Plugin for RenderPlugin {
                 bevy_app::stage::POST_UPDATE,
                 camera::camera_system::<PerspectiveProjection>,
             )
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                shader::asset_shader_defs_system::<Mesh>,
+            )
+            .add_system_to_stage(bevy_app::stage::POST_UPDATE, mesh::mesh_aabb_system)
             // registration order matters here. this must come after all camera_system::<T> systems
             .add_system_to_stage(
                 bevy_app::stage::POST_UPDATE,
@@ -0,0 +1,132 @@
+use super::Mesh;
+use bevy_app::prelude::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Changed, Commands, Entity, Local, Query, QuerySet, Res, Without};
+use bevy_math::{Mat4, Vec3};
+use bevy_utils::HashSet;
+
+/// An axis-aligned bounding box around the positions of a mesh, in the local space of its entity.
+/// It is kept up to date for entities with a [Handle<Mesh>] once their mesh is loaded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn from_min_max(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// Returns the box around this box after `transform` is applied to it
+    pub fn transformed(&self, transform: &Mat4) -> Aabb {
+        let center = (*transform * self.center().extend(1.0)).truncate();
+        let half_extents = self.half_extents();
+        // each transformed axis of the box adds to the extents along the axes of the world
+        let half_extents = transform.x_axis.truncate().abs() * half_extents.x()
+            + transform.y_axis.truncate().abs() * half_extents.y()
+            + transform.z_axis.truncate().abs() * half_extents.z();
+        Aabb {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MeshAabbState {
+    mesh_event_reader: EventReader<AssetEvent<Mesh>>,
+}
+
+/// Adds an [Aabb] to entities with a [Handle<Mesh>], and updates it when their mesh changes
+pub fn mesh_aabb_system(
+    commands: &mut Commands,
+    mut state: Local<MeshAabbState>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_events: Res<Events<AssetEvent<Mesh>>>,
+    new_query: Query<(Entity, &Handle<Mesh>), Without<Aabb>>,
+    mut queries: QuerySet<(
+        Query<(Entity, &Handle<Mesh>, &mut Aabb)>,
+        Query<(Entity, &Handle<Mesh>, &mut Aabb), Changed<Handle<Mesh>>>,
+    )>,
+) {
+    let mut changed_meshes = HashSet::default();
+    for event in state.mesh_event_reader.iter(&mesh_events) {
+        match event {
+            AssetEvent::Created { handle }
+            | AssetEvent::Modified { handle }
+            | AssetEvent::Removed { handle } => {
+                changed_meshes.insert(handle.clone_weak());
+            }
+            AssetEvent::Failed { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    // entities wait for their mesh to be loaded
+    for (entity, handle) in new_query.iter() {
+        if let Some(aabb) = meshes.get(handle).and_then(Mesh::compute_aabb) {
+            commands.insert_one(entity, aabb);
+        }
+    }
+
+    let mut update = |entity, handle: &Handle<Mesh>, aabb: &mut Aabb| match meshes
+        .get(handle)
+        .and_then(Mesh::compute_aabb)
+    {
+        Some(new_aabb) => {
+            if *aabb != new_aabb {
+                *aabb = new_aabb;
+            }
+        }
+        None => {
+            commands.remove_one::<Aabb>(entity);
+        }
+    };
+    if !changed_meshes.is_empty() {
+        for (entity, handle, mut aabb) in queries.q0_mut().iter_mut() {
+            if changed_meshes.contains(handle) {
+                update(entity, handle, &mut aabb);
+            }
+        }
+    }
+    for (entity, handle, mut aabb) in queries.q1_mut().iter_mut() {
+        update(entity, handle, &mut aabb);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aabb;
+    use bevy_math::{Mat4, Quat, Vec3};
+
+    #[test]
+    fn transformed_boxes_contain_the_corners() {
+        let aabb = Aabb::from_min_max(Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0));
+        let transform = Mat4::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        );
+        let transformed = aabb.transformed(&transform);
+        assert!(
+            (transformed.min - Vec3::new(4.0, -4.0, -2.0))
+                .abs()
+                .max_element()
+                < 1e-5
+        );
+        assert!(
+            (transformed.max - Vec3::new(16.0, 4.0, 2.0))
+                .abs()
+                .max_element()
+                < 1e-5
+        );
+    }
+}
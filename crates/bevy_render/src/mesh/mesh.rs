@@ -1,6 +1,7 @@
 use crate::{
     pipeline::{IndexFormat, PrimitiveTopology, RenderPipelines, VertexFormat},
     renderer::{BufferInfo, BufferUsage, RenderResourceContext, RenderResourceId},
+    shader::{ShaderDefIterator, ShaderDefs},
 };
 use bevy_app::prelude::{EventReader, Events};
 use bevy_asset::{AssetEvent, Assets, Handle};
@@ -9,7 +10,9 @@ use bevy_ecs::{Changed, Entity, Local, Mut, Query, QuerySet, Res, With};
 use bevy_math::*;
 use bevy_reflect::TypeUuid;
 use std::borrow::Cow;
+use thiserror::Error;
 
+use super::Aabb;
 use crate::pipeline::{InputStepMode, VertexAttributeDescriptor, VertexBufferDescriptor};
 use bevy_utils::{HashMap, HashSet};
 
@@ -54,6 +57,51 @@ impl VertexAttributeValues {
         self.len() == 0
     }
 
+    /// Appends the values of `other`, and returns false without changing anything when they are
+    /// in a different format
+    fn extend(&mut self, other: &VertexAttributeValues) -> bool {
+        match (self, other) {
+            (VertexAttributeValues::Float(values), VertexAttributeValues::Float(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int(values), VertexAttributeValues::Int(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint(values), VertexAttributeValues::Uint(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float2(values), VertexAttributeValues::Float2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int2(values), VertexAttributeValues::Int2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint2(values), VertexAttributeValues::Uint2(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float3(values), VertexAttributeValues::Float3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int3(values), VertexAttributeValues::Int3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint3(values), VertexAttributeValues::Uint3(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Float4(values), VertexAttributeValues::Float4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Int4(values), VertexAttributeValues::Int4(other)) => {
+                values.extend_from_slice(other)
+            }
+            (VertexAttributeValues::Uint4(values), VertexAttributeValues::Uint4(other)) => {
+                values.extend_from_slice(other)
+            }
+            _ => return false,
+        }
+        true
+    }
+
     // TODO: add vertex format as parameter here and perform type conversions
     pub fn get_bytes(&self) -> &[u8] {
         match self {
@@ -164,7 +212,7 @@ impl From<Vec<[u32; 4]>> for VertexAttributeValues {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Indices {
     U16(Vec<u16>),
     U32(Vec<u32>),
@@ -179,6 +227,29 @@ impl From<&Indices> for IndexFormat {
     }
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MeshMergeError {
+    #[error("Cannot merge a mesh of {1:?} into a mesh of {0:?}")]
+    IncompatibleTopology(PrimitiveTopology, PrimitiveTopology),
+    #[error("Cannot merge meshes of {0:?}, as strips can't be split")]
+    StripTopology(PrimitiveTopology),
+    #[error("Only one of the merged meshes has the attribute {0}")]
+    MissingAttribute(Cow<'static, str>),
+    #[error("The attribute {0} has different formats in the merged meshes")]
+    IncompatibleAttribute(Cow<'static, str>),
+    #[error("Cannot merge a mesh with {1} morph targets into a mesh with {0} morph targets")]
+    IncompatibleMorphTargets(usize, usize),
+}
+
+/// A shape the vertices of a [Mesh] are blended towards, as offsets from the position and normal
+/// of each vertex. How much of each target is applied is set per entity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MorphTarget {
+    pub positions: Vec<[f32; 3]>,
+    /// The offsets of the normals, or nothing when the target doesn't change them
+    pub normals: Option<Vec<[f32; 3]>>,
+}
+
 // TODO: allow values to be unloaded after been submitting to the GPU to conserve memory
 #[derive(Debug, TypeUuid)]
 #[uuid = "8ecbac0f-f545-4473-ad43-e1f4243af51e"]
@@ -187,18 +258,32 @@ pub struct Mesh {
     /// `bevy_utils::HashMap` with all defined vertex attributes (Positions, Normals, ...) for this mesh. Attribute name maps to attribute values.
     attributes: HashMap<Cow<'static, str>, VertexAttributeValues>,
     indices: Option<Indices>,
+    morph_targets: Vec<MorphTarget>,
 }
 
 impl Mesh {
     pub const ATTRIBUTE_NORMAL: &'static str = "Vertex_Normal";
     pub const ATTRIBUTE_POSITION: &'static str = "Vertex_Position";
     pub const ATTRIBUTE_UV_0: &'static str = "Vertex_Uv";
+    /// Linear RGBA colors as [VertexAttributeValues::Float4], multiplied into the color of the
+    /// material by the built-in pipelines
+    pub const ATTRIBUTE_COLOR: &'static str = "Vertex_Color";
+    /// The indices of the joints of a skin that move each vertex, as
+    /// [VertexAttributeValues::Uint4]
+    pub const ATTRIBUTE_JOINT_INDEX: &'static str = "Vertex_JointIndex";
+    /// How much each of the joints moves each vertex, as [VertexAttributeValues::Float4] adding
+    /// up to 1
+    pub const ATTRIBUTE_JOINT_WEIGHT: &'static str = "Vertex_JointWeight";
+
+    /// The shader def of meshes with an [Mesh::ATTRIBUTE_COLOR]
+    pub const VERTEX_COLOR_SHADER_DEF: &'static str = "MESH_VERTEX_COLOR";
 
     pub fn new(primitive_topology: PrimitiveTopology) -> Self {
         Mesh {
             primitive_topology,
             attributes: Default::default(),
             indices: None,
+            morph_targets: Vec::new(),
         }
     }
 
@@ -227,6 +312,15 @@ impl Mesh {
         self.indices.as_ref()
     }
 
+    /// Sets the morph targets of the mesh. Each target has an offset for every vertex.
+    pub fn set_morph_targets(&mut self, morph_targets: Vec<MorphTarget>) {
+        self.morph_targets = morph_targets;
+    }
+
+    pub fn morph_targets(&self) -> &[MorphTarget] {
+        &self.morph_targets
+    }
+
     pub fn get_index_buffer_bytes(&self) -> Option<Vec<u8>> {
         self.indices.as_ref().map(|indices| match &indices {
             Indices::U16(indices) => indices.as_slice().as_bytes().to_vec(),
@@ -256,7 +350,8 @@ impl Mesh {
         }
     }
 
-    fn count_vertices(&self) -> usize {
+    /// Returns the number of vertices of the mesh, which all of its attributes have values for
+    pub fn count_vertices(&self) -> usize {
         let mut vertex_count: Option<usize> = None;
         for (attribute_name, attribute_data) in self.attributes.iter() {
             let attribute_len = attribute_data.len();
@@ -298,6 +393,190 @@ impl Mesh {
 
         attributes_interleaved_buffer
     }
+
+    /// Returns the box around the positions of the mesh, or nothing if it has no positions
+    pub fn compute_aabb(&self) -> Option<Aabb> {
+        match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float3(positions)) if !positions.is_empty() => {
+                let (min, max) = positions.iter().map(|position| Vec3::from(*position)).fold(
+                    (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+                    |(min, max), position| (min.min(position), max.max(position)),
+                );
+                Some(Aabb::from_min_max(min, max))
+            }
+            _ => None,
+        }
+    }
+
+    /// Applies `transform` to the positions and normals of the mesh, and to the offsets of its
+    /// morph targets. Triangles are turned around
+    /// when the transform mirrors them, so that they keep facing the same side of the mesh.
+    pub fn transform(&mut self, transform: Mat4) {
+        if let Some(VertexAttributeValues::Float3(positions)) =
+            self.attributes.get_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for position in positions.iter_mut() {
+                *position = (transform * Vec3::from(*position).extend(1.0))
+                    .truncate()
+                    .into();
+            }
+        }
+        if let Some(VertexAttributeValues::Float3(normals)) =
+            self.attributes.get_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            // normals stay perpendicular to the surface when it is scaled unevenly
+            let normal_transform = transform.inverse().transpose();
+            for normal in normals.iter_mut() {
+                let transformed = (normal_transform * Vec3::from(*normal).extend(0.0)).truncate();
+                if transformed != Vec3::zero() {
+                    *normal = transformed.normalize().into();
+                }
+            }
+        }
+        // offsets are moved like directions, without the translation
+        let normal_transform = transform.inverse().transpose();
+        let transform_offset = |matrix: Mat4, offset: &mut [f32; 3]| {
+            *offset = (matrix * Vec3::from(*offset).extend(0.0)).truncate().into();
+        };
+        for morph_target in self.morph_targets.iter_mut() {
+            for position in morph_target.positions.iter_mut() {
+                transform_offset(transform, position);
+            }
+            for normal in morph_target.normals.iter_mut().flatten() {
+                transform_offset(normal_transform, normal);
+            }
+        }
+
+        if transform.determinant() < 0.0
+            && self.primitive_topology == PrimitiveTopology::TriangleList
+        {
+            let vertex_count = self.count_vertices();
+            match self
+                .indices
+                .get_or_insert_with(|| Indices::U32((0..vertex_count as u32).collect()))
+            {
+                Indices::U16(indices) => indices.chunks_exact_mut(3).for_each(|t| t.swap(1, 2)),
+                Indices::U32(indices) => indices.chunks_exact_mut(3).for_each(|t| t.swap(1, 2)),
+            }
+        }
+    }
+
+    /// Appends the vertices and indices of `other` to the mesh, to draw both meshes at once.
+    /// Both meshes need the same attributes in the same formats, the same list topology and the
+    /// same number of morph targets. A mesh without attributes takes the attributes of `other`.
+    pub fn merge(&mut self, other: &Mesh) -> Result<(), MeshMergeError> {
+        if self.primitive_topology != other.primitive_topology {
+            return Err(MeshMergeError::IncompatibleTopology(
+                self.primitive_topology,
+                other.primitive_topology,
+            ));
+        }
+        if let PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip =
+            self.primitive_topology
+        {
+            return Err(MeshMergeError::StripTopology(self.primitive_topology));
+        }
+        if self.attributes.is_empty() && self.indices.is_none() {
+            self.attributes = other.attributes.clone();
+            self.indices = other.indices.clone();
+            self.morph_targets = other.morph_targets.clone();
+            return Ok(());
+        }
+        if self.morph_targets.len() != other.morph_targets.len() {
+            return Err(MeshMergeError::IncompatibleMorphTargets(
+                self.morph_targets.len(),
+                other.morph_targets.len(),
+            ));
+        }
+        for (name, values) in self.attributes.iter() {
+            match other.attributes.get(name) {
+                Some(other_values) => {
+                    if VertexFormat::from(values) != VertexFormat::from(other_values) {
+                        return Err(MeshMergeError::IncompatibleAttribute(name.clone()));
+                    }
+                }
+                None => return Err(MeshMergeError::MissingAttribute(name.clone())),
+            }
+        }
+        if let Some(name) = other
+            .attributes
+            .keys()
+            .find(|name| !self.attributes.contains_key(*name))
+        {
+            return Err(MeshMergeError::MissingAttribute(name.clone()));
+        }
+
+        let offset = self.count_vertices();
+        let other_count = other.count_vertices();
+        for (name, values) in self.attributes.iter_mut() {
+            values.extend(&other.attributes[name]);
+        }
+        for (morph_target, other_target) in self
+            .morph_targets
+            .iter_mut()
+            .zip(other.morph_targets.iter())
+        {
+            morph_target.positions.resize(offset, [0.0; 3]);
+            morph_target.positions.extend(&other_target.positions);
+            morph_target
+                .positions
+                .resize(offset + other_count, [0.0; 3]);
+            // targets that only change normals in one of the meshes don't change the others
+            if morph_target.normals.is_some() || other_target.normals.is_some() {
+                let normals = morph_target.normals.get_or_insert_with(Vec::new);
+                normals.resize(offset, [0.0; 3]);
+                normals.extend(other_target.normals.iter().flatten());
+                normals.resize(offset + other_count, [0.0; 3]);
+            }
+        }
+
+        // a mesh without indices draws its vertices in order
+        if self.indices.is_some() || other.indices.is_some() {
+            let use_u32 = offset + other_count > u16::MAX as usize + 1
+                || matches!(self.indices, Some(Indices::U32(_)))
+                || matches!(other.indices, Some(Indices::U32(_)));
+            let mut indices: Vec<u32> = match self.indices.take() {
+                Some(Indices::U16(indices)) => indices.into_iter().map(u32::from).collect(),
+                Some(Indices::U32(indices)) => indices,
+                None => (0..offset as u32).collect(),
+            };
+            let offset = offset as u32;
+            match &other.indices {
+                Some(Indices::U16(other)) => {
+                    indices.extend(other.iter().map(|index| *index as u32 + offset))
+                }
+                Some(Indices::U32(other)) => {
+                    indices.extend(other.iter().map(|index| index + offset))
+                }
+                None => indices.extend(offset..offset + other_count as u32),
+            }
+            self.indices = Some(if use_u32 {
+                Indices::U32(indices)
+            } else {
+                Indices::U16(indices.into_iter().map(|index| index as u16).collect())
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ShaderDefs for Mesh {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match index {
+            0 if self.attributes.contains_key(Mesh::ATTRIBUTE_COLOR) => {
+                Some(Mesh::VERTEX_COLOR_SHADER_DEF)
+            }
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
 }
 
 fn remove_resource_save(
@@ -359,6 +638,7 @@ pub fn mesh_resource_provider_system(
                 // events are ordered so future modification events are ok
                 changed_meshes.remove(handle);
             }
+            AssetEvent::Failed { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
         }
     }
 
@@ -459,3 +739,85 @@ fn update_entity_mesh(
         render_pipelines.bindings.vertex_attribute_buffer = Some(vertex_attribute_buffer_resource);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Indices, Mesh, MeshMergeError, MorphTarget, VertexAttributeValues};
+    use crate::pipeline::PrimitiveTopology;
+    use bevy_math::{Mat4, Vec3};
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        mesh
+    }
+
+    #[test]
+    fn merged_meshes_rebase_indices() {
+        let mut mesh = triangle();
+        let mut other = triangle();
+        other.transform(Mat4::from_translation(Vec3::new(0.0, 0.0, 1.0)));
+        mesh.merge(&other).unwrap();
+
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float3(positions)) => {
+                assert_eq!(positions.len(), 6);
+                assert_eq!(positions[5], [0.0, 1.0, 1.0]);
+            }
+            _ => panic!("the mesh has no positions"),
+        }
+        match mesh.indices() {
+            Some(Indices::U16(indices)) => assert_eq!(indices, &vec![0, 1, 2, 3, 4, 5]),
+            _ => panic!("the mesh has no 16 bit indices"),
+        }
+
+        other.set_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        assert_eq!(
+            mesh.merge(&other),
+            Err(MeshMergeError::MissingAttribute(
+                Mesh::ATTRIBUTE_NORMAL.into()
+            ))
+        );
+    }
+
+    #[test]
+    fn mirroring_turns_triangles_around() {
+        let mut mesh = triangle();
+        mesh.transform(Mat4::from_scale(Vec3::new(-1.0, 1.0, 1.0)));
+        match mesh.indices() {
+            Some(Indices::U16(indices)) => assert_eq!(indices, &vec![0, 2, 1]),
+            _ => panic!("the mesh has no 16 bit indices"),
+        }
+    }
+
+    #[test]
+    fn merged_morph_targets_keep_their_vertices() {
+        let mut mesh = triangle();
+        mesh.set_morph_targets(vec![MorphTarget {
+            positions: vec![[0.0, 1.0, 0.0]; 3],
+            normals: None,
+        }]);
+        let mut other = triangle();
+        other.set_morph_targets(vec![MorphTarget {
+            positions: vec![[1.0, 0.0, 0.0]; 3],
+            normals: Some(vec![[0.0, 0.0, 1.0]; 3]),
+        }]);
+        mesh.merge(&other).unwrap();
+
+        let morph_target = &mesh.morph_targets()[0];
+        assert_eq!(morph_target.positions[2], [0.0, 1.0, 0.0]);
+        assert_eq!(morph_target.positions[3], [1.0, 0.0, 0.0]);
+        let normals = morph_target.normals.as_ref().unwrap();
+        assert_eq!(normals[2], [0.0, 0.0, 0.0]);
+        assert_eq!(normals[3], [0.0, 0.0, 1.0]);
+
+        assert_eq!(
+            mesh.merge(&triangle()),
+            Err(MeshMergeError::IncompatibleMorphTargets(1, 0))
+        );
+    }
+}
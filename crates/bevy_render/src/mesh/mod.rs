@@ -1,6 +1,8 @@
+mod aabb;
 #[allow(clippy::module_inception)]
 mod mesh;
 /// Generation for some primitive shape meshes.
 pub mod shape;
 
+pub use aabb::*;
 pub use mesh::*;
@@ -0,0 +1,83 @@
+use super::{revolve, ProfilePoint};
+use crate::mesh::Mesh;
+use bevy_math::Vec2;
+use std::f32::consts::FRAC_PI_2;
+
+/// A cylinder with hemispheres at the top and bottom, standing on the y axis
+#[derive(Debug, Clone, Copy)]
+pub struct Capsule {
+    /// The radius of the cylinder and of the hemispheres
+    pub radius: f32,
+    /// The height of the cylinder between the hemispheres
+    pub depth: f32,
+    /// The number of rings of the cylinder between the hemispheres
+    pub rings: usize,
+    /// The number of latitudes of both hemispheres together, from pole to pole
+    pub latitudes: usize,
+    /// The number of longitudes around the y axis
+    pub longitudes: usize,
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Capsule {
+            radius: 0.5,
+            depth: 1.0,
+            rings: 0,
+            latitudes: 16,
+            longitudes: 32,
+        }
+    }
+}
+
+impl From<Capsule> for Mesh {
+    fn from(capsule: Capsule) -> Self {
+        let Capsule {
+            radius,
+            depth,
+            rings,
+            latitudes,
+            longitudes,
+        } = capsule;
+        let half_latitudes = (latitudes / 2).max(1);
+        let half_depth = depth / 2.0;
+
+        // the points go from the bottom pole to the top pole
+        let mut profile = Vec::new();
+        for latitude in 0..=half_latitudes {
+            let angle = -FRAC_PI_2 + FRAC_PI_2 * latitude as f32 / half_latitudes as f32;
+            let normal = Vec2::new(angle.cos(), angle.sin());
+            profile.push((normal * radius - Vec2::new(0.0, half_depth), normal));
+        }
+        for ring in 1..=rings {
+            let y = -half_depth + depth * ring as f32 / (rings + 1) as f32;
+            profile.push((Vec2::new(radius, y), Vec2::new(1.0, 0.0)));
+        }
+        for latitude in 0..=half_latitudes {
+            let angle = FRAC_PI_2 * latitude as f32 / half_latitudes as f32;
+            let normal = Vec2::new(angle.cos(), angle.sin());
+            profile.push((normal * radius + Vec2::new(0.0, half_depth), normal));
+        }
+
+        // textures are stretched along the profile, from the top down
+        let mut distances = vec![0.0];
+        for points in profile.windows(2) {
+            let distance = distances.last().unwrap() + (points[1].0 - points[0].0).length();
+            distances.push(distance);
+        }
+        let length = distances.last().cloned().unwrap_or(0.0).max(f32::EPSILON);
+        let profile = profile
+            .iter()
+            .zip(distances.iter())
+            .map(|((position, normal), distance)| ProfilePoint {
+                radius: position.x().max(0.0),
+                y: position.y(),
+                normal: *normal,
+                v: 1.0 - distance / length,
+                cap_radius: None,
+            })
+            .collect::<Vec<_>>();
+
+        revolve(&[profile], longitudes)
+    }
+}
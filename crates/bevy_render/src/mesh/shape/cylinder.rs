@@ -0,0 +1,130 @@
+use super::{revolve, ProfilePoint};
+use crate::mesh::Mesh;
+use bevy_math::Vec2;
+
+/// A capped cylinder standing on the y axis
+#[derive(Debug, Clone, Copy)]
+pub struct Cylinder {
+    pub radius: f32,
+    pub height: f32,
+    /// The number of vertices around each ring
+    pub resolution: usize,
+    /// The number of segments along the height
+    pub segments: usize,
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 32,
+            segments: 1,
+        }
+    }
+}
+
+impl From<Cylinder> for Mesh {
+    fn from(cylinder: Cylinder) -> Self {
+        let Cylinder {
+            radius,
+            height,
+            resolution,
+            segments,
+        } = cylinder;
+        let segments = segments.max(1);
+        let half_height = height / 2.0;
+
+        let side = (0..=segments)
+            .map(|segment| {
+                let fraction = segment as f32 / segments as f32;
+                ProfilePoint {
+                    radius,
+                    y: -half_height + height * fraction,
+                    normal: Vec2::new(1.0, 0.0),
+                    v: 1.0 - fraction,
+                    cap_radius: None,
+                }
+            })
+            .collect();
+
+        revolve(
+            &[
+                cap(radius, -half_height, -1.0),
+                side,
+                cap(radius, half_height, 1.0),
+            ],
+            resolution,
+        )
+    }
+}
+
+/// A capped cone standing on the y axis, with its apex at the top
+#[derive(Debug, Clone, Copy)]
+pub struct Cone {
+    /// The radius of the base
+    pub radius: f32,
+    pub height: f32,
+    /// The number of vertices around the base
+    pub resolution: usize,
+    /// The number of segments from the base to the apex
+    pub segments: usize,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 32,
+            segments: 1,
+        }
+    }
+}
+
+impl From<Cone> for Mesh {
+    fn from(cone: Cone) -> Self {
+        let Cone {
+            radius,
+            height,
+            resolution,
+            segments,
+        } = cone;
+        let segments = segments.max(1);
+        let half_height = height / 2.0;
+        // the normal is perpendicular to the slope of the side, up to the apex
+        let normal = Vec2::new(height, radius).normalize();
+
+        let side = (0..=segments)
+            .map(|segment| {
+                let fraction = segment as f32 / segments as f32;
+                ProfilePoint {
+                    radius: radius * (1.0 - fraction),
+                    y: -half_height + height * fraction,
+                    normal,
+                    v: 1.0 - fraction,
+                    cap_radius: None,
+                }
+            })
+            .collect();
+
+        revolve(&[cap(radius, -half_height, -1.0), side], resolution)
+    }
+}
+
+/// A flat disc at `y`, facing up or down
+fn cap(radius: f32, y: f32, facing: f32) -> Vec<ProfilePoint> {
+    let point = |point_radius| ProfilePoint {
+        radius: point_radius,
+        y,
+        normal: Vec2::new(0.0, facing),
+        v: 0.0,
+        cap_radius: Some(radius),
+    };
+    // the points go around the normal the same way as the points of the sides
+    if facing < 0.0 {
+        vec![point(0.0), point(radius)]
+    } else {
+        vec![point(radius), point(0.0)]
+    }
+}
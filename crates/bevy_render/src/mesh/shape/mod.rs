@@ -0,0 +1,471 @@
+mod capsule;
+mod cylinder;
+mod torus;
+mod uvsphere;
+
+pub use capsule::Capsule;
+pub use cylinder::{Cone, Cylinder};
+pub use torus::Torus;
+pub use uvsphere::UVSphere;
+
+use super::{Indices, Mesh};
+use crate::pipeline::PrimitiveTopology;
+use bevy_math::*;
+use hexasphere::shapes::IcoSphere;
+use thiserror::Error;
+
+pub struct Cube {
+    pub size: f32,
+}
+
+impl Cube {
+    pub fn new(size: f32) -> Cube {
+        Cube { size }
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Cube { size: 1.0 }
+    }
+}
+
+impl From<Cube> for Mesh {
+    fn from(cube: Cube) -> Self {
+        Box::new(cube.size, cube.size, cube.size).into()
+    }
+}
+
+pub struct Box {
+    pub min_x: f32,
+    pub max_x: f32,
+
+    pub min_y: f32,
+    pub max_y: f32,
+
+    pub min_z: f32,
+    pub max_z: f32,
+}
+
+impl Box {
+    pub fn new(x_length: f32, y_length: f32, z_length: f32) -> Box {
+        Box {
+            max_x: x_length / 2.0,
+            min_x: -x_length / 2.0,
+            max_y: y_length / 2.0,
+            min_y: -y_length / 2.0,
+            max_z: z_length / 2.0,
+            min_z: -z_length / 2.0,
+        }
+    }
+}
+
+impl Default for Box {
+    fn default() -> Self {
+        Box::new(2.0, 1.0, 1.0)
+    }
+}
+
+impl From<Box> for Mesh {
+    fn from(sp: Box) -> Self {
+        let vertices = &[
+            // Top
+            ([sp.min_x, sp.min_y, sp.max_z], [0., 0., 1.0], [0., 0.]),
+            ([sp.max_x, sp.min_y, sp.max_z], [0., 0., 1.0], [1.0, 0.]),
+            ([sp.max_x, sp.max_y, sp.max_z], [0., 0., 1.0], [1.0, 1.0]),
+            ([sp.min_x, sp.max_y, sp.max_z], [0., 0., 1.0], [0., 1.0]),
+            // Bottom
+            ([sp.min_x, sp.max_y, sp.min_z], [0., 0., -1.0], [1.0, 0.]),
+            ([sp.max_x, sp.max_y, sp.min_z], [0., 0., -1.0], [0., 0.]),
+            ([sp.max_x, sp.min_y, sp.min_z], [0., 0., -1.0], [0., 1.0]),
+            ([sp.min_x, sp.min_y, sp.min_z], [0., 0., -1.0], [1.0, 1.0]),
+            // Right
+            ([sp.max_x, sp.min_y, sp.min_z], [1.0, 0., 0.], [0., 0.]),
+            ([sp.max_x, sp.max_y, sp.min_z], [1.0, 0., 0.], [1.0, 0.]),
+            ([sp.max_x, sp.max_y, sp.max_z], [1.0, 0., 0.], [1.0, 1.0]),
+            ([sp.max_x, sp.min_y, sp.max_z], [1.0, 0., 0.], [0., 1.0]),
+            // Left
+            ([sp.min_x, sp.min_y, sp.max_z], [-1.0, 0., 0.], [1.0, 0.]),
+            ([sp.min_x, sp.max_y, sp.max_z], [-1.0, 0., 0.], [0., 0.]),
+            ([sp.min_x, sp.max_y, sp.min_z], [-1.0, 0., 0.], [0., 1.0]),
+            ([sp.min_x, sp.min_y, sp.min_z], [-1.0, 0., 0.], [1.0, 1.0]),
+            // Front
+            ([sp.max_x, sp.max_y, sp.min_z], [0., 1.0, 0.], [1.0, 0.]),
+            ([sp.min_x, sp.max_y, sp.min_z], [0., 1.0, 0.], [0., 0.]),
+            ([sp.min_x, sp.max_y, sp.max_z], [0., 1.0, 0.], [0., 1.0]),
+            ([sp.max_x, sp.max_y, sp.max_z], [0., 1.0, 0.], [1.0, 1.0]),
+            // Back
+            ([sp.max_x, sp.min_y, sp.max_z], [0., -1.0, 0.], [0., 0.]),
+            ([sp.min_x, sp.min_y, sp.max_z], [0., -1.0, 0.], [1.0, 0.]),
+            ([sp.min_x, sp.min_y, sp.min_z], [0., -1.0, 0.], [1.0, 1.0]),
+            ([sp.max_x, sp.min_y, sp.min_z], [0., -1.0, 0.], [0., 1.0]),
+        ];
+
+        let mut positions = Vec::with_capacity(24);
+        let mut normals = Vec::with_capacity(24);
+        let mut uvs = Vec::with_capacity(24);
+
+        for (position, normal, uv) in vertices.iter() {
+            positions.push(*position);
+            normals.push(*normal);
+            uvs.push(*uv);
+        }
+
+        let indices = Indices::U32(vec![
+            0, 1, 2, 2, 3, 0, // top
+            4, 5, 6, 6, 7, 4, // bottom
+            8, 9, 10, 10, 11, 8, // right
+            12, 13, 14, 14, 15, 12, // left
+            16, 17, 18, 18, 19, 16, // front
+            20, 21, 22, 22, 23, 20, // back
+        ]);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.set_indices(Some(indices));
+        mesh
+    }
+}
+
+/// A rectangle on the XY plane.
+#[derive(Debug)]
+pub struct Quad {
+    /// Full width and height of the rectangle.
+    pub size: Vec2,
+    /// Flips the texture coords of the resulting vertices.
+    pub flip: bool,
+    /// The number of vertices added along each side, splitting the rectangle into a grid of
+    /// `subdivisions + 1` by `subdivisions + 1` rectangles.
+    pub subdivisions: usize,
+}
+
+impl Quad {
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            flip: false,
+            subdivisions: 0,
+        }
+    }
+
+    pub fn flipped(size: Vec2) -> Self {
+        Self {
+            size,
+            flip: true,
+            subdivisions: 0,
+        }
+    }
+
+    pub fn with_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+}
+
+impl From<Quad> for Mesh {
+    fn from(quad: Quad) -> Self {
+        let extent_x = quad.size.x / 2.0;
+        let extent_y = quad.size.y / 2.0;
+
+        grid(
+            quad.subdivisions,
+            |u, v| [-extent_x + u * quad.size.x, extent_y - v * quad.size.y, 0.0],
+            [0.0, 0.0, 1.0],
+            quad.flip,
+        )
+    }
+}
+
+/// A square on the XZ plane.
+#[derive(Debug)]
+pub struct Plane {
+    /// The total side length of the square.
+    pub size: f32,
+    /// The number of vertices added along each side, splitting the square into a grid of
+    /// `subdivisions + 1` by `subdivisions + 1` squares.
+    pub subdivisions: usize,
+}
+
+impl Plane {
+    pub fn new(size: f32) -> Self {
+        Self {
+            size,
+            subdivisions: 0,
+        }
+    }
+
+    pub fn with_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+}
+
+impl From<Plane> for Mesh {
+    fn from(plane: Plane) -> Self {
+        let extent = plane.size / 2.0;
+
+        grid(
+            plane.subdivisions,
+            |u, v| [-extent + u * plane.size, 0.0, extent - v * plane.size],
+            [0.0, 1.0, 0.0],
+            true,
+        )
+    }
+}
+
+/// Creates a flat grid of vertices shared by the neighboring cells. The texture coordinates go
+/// from the top left corner to the bottom right corner, and `position` places the vertex at
+/// these coordinates. The triangles face the side the texture is seen from, or the other side
+/// with `reverse_winding`.
+fn grid(
+    subdivisions: usize,
+    position: impl Fn(f32, f32) -> [f32; 3],
+    normal: [f32; 3],
+    reverse_winding: bool,
+) -> Mesh {
+    let cells = subdivisions + 1;
+    let row_size = cells as u32 + 1;
+    let vertex_count = (cells + 1) * (cells + 1);
+    let mut positions = Vec::with_capacity(vertex_count);
+    let mut uvs = Vec::with_capacity(vertex_count);
+    for row in 0..=cells {
+        for column in 0..=cells {
+            let uv = [column as f32 / cells as f32, row as f32 / cells as f32];
+            positions.push(position(uv[0], uv[1]));
+            uvs.push(uv);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(cells * cells * 6);
+    for row in 0..cells as u32 {
+        for column in 0..cells as u32 {
+            let top_left = row * row_size + column;
+            let (top_right, bottom_left) = (top_left + 1, top_left + row_size);
+            let bottom_right = bottom_left + 1;
+            if reverse_winding {
+                indices.extend_from_slice(&[top_left, bottom_right, bottom_left]);
+                indices.extend_from_slice(&[top_left, top_right, bottom_right]);
+            } else {
+                indices.extend_from_slice(&[top_left, bottom_left, bottom_right]);
+                indices.extend_from_slice(&[top_left, bottom_right, top_right]);
+            }
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, vec![normal; vertex_count]);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh
+}
+
+/// A sphere made from a subdivided Icosahedron.
+#[derive(Debug)]
+pub struct Icosphere {
+    /// The radius of the sphere.
+    pub radius: f32,
+    /// The number of subdivisions applied.
+    pub subdivisions: usize,
+}
+
+impl Default for Icosphere {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            subdivisions: 5,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IcosphereError {
+    #[error("Cannot create an icosphere of {subdivisions} subdivisions due to there being too many vertices being generated: {number_of_resulting_points} (Limited to {max_vertices} vertices)")]
+    TooManyVertices {
+        subdivisions: usize,
+        number_of_resulting_points: u128,
+        max_vertices: u32,
+    },
+}
+
+impl Icosphere {
+    /// Creates the mesh of the sphere, or fails when it has more vertices than can be indexed
+    /// with [Indices::U32]
+    pub fn try_into_mesh(self) -> Result<Mesh, IcosphereError> {
+        // https://oeis.org/A005901
+        let subdivisions = self.subdivisions as u128 + 1;
+        let number_of_resulting_points = (subdivisions * subdivisions * 10) + 2;
+        if number_of_resulting_points > u32::MAX as u128 {
+            return Err(IcosphereError::TooManyVertices {
+                subdivisions: self.subdivisions,
+                number_of_resulting_points,
+                max_vertices: u32::MAX,
+            });
+        }
+
+        let sphere = self;
+        let generated = IcoSphere::new(sphere.subdivisions, |point| {
+            let inclination = point.z.acos();
+            let azumith = point.y.atan2(point.x);
+
+            let norm_inclination = 1.0 - (inclination / std::f32::consts::PI);
+            let norm_azumith = (azumith / std::f32::consts::PI) * 0.5;
+
+            [norm_inclination, norm_azumith]
+        });
+
+        let raw_points = generated.raw_points();
+
+        let points = raw_points
+            .iter()
+            .map(|&p| (p * sphere.radius).into())
+            .collect::<Vec<[f32; 3]>>();
+
+        let normals = raw_points
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect::<Vec<[f32; 3]>>();
+
+        let uvs = generated.raw_data().to_owned();
+
+        let mut indices = Vec::with_capacity(generated.indices_per_main_triangle() * 20);
+
+        for i in 0..20 {
+            generated.get_indices(i, &mut indices);
+        }
+
+        let indices = Indices::U32(indices);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(indices));
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, points);
+        mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        Ok(mesh)
+    }
+}
+
+impl From<Icosphere> for Mesh {
+    /// Panics when the sphere has too many vertices, see [Icosphere::try_into_mesh]
+    fn from(sphere: Icosphere) -> Self {
+        match sphere.try_into_mesh() {
+            Ok(mesh) => mesh,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+/// A point of the profile of a shape that is revolved around the y axis
+struct ProfilePoint {
+    /// The distance from the y axis
+    radius: f32,
+    y: f32,
+    /// The normal in the plane of the profile, away from the y axis and up
+    normal: Vec2,
+    /// The vertical texture coordinate
+    v: f32,
+    /// Points of flat caps are textured with a disc of this radius instead, centered on the y
+    /// axis and seen from the side of the normal
+    cap_radius: Option<f32>,
+}
+
+/// Builds a mesh by revolving profiles around the y axis with `longitudes` segments. The points
+/// of each profile are connected from bottom to top, and the profiles aren't connected to each
+/// other, so that edges with different normals on each side are separate profiles.
+fn revolve(profiles: &[Vec<ProfilePoint>], longitudes: usize) -> Mesh {
+    let longitudes = longitudes.max(3);
+    let vertex_count = profiles.iter().map(Vec::len).sum::<usize>() * (longitudes + 1);
+    let mut positions = Vec::with_capacity(vertex_count);
+    let mut normals = Vec::with_capacity(vertex_count);
+    let mut uvs = Vec::with_capacity(vertex_count);
+    let mut indices = Vec::new();
+
+    for profile in profiles.iter() {
+        let first_vertex = positions.len() as u32;
+        for point in profile.iter() {
+            // the seam has vertices on both sides, with different texture coordinates
+            for longitude in 0..=longitudes {
+                let u = longitude as f32 / longitudes as f32;
+                let (sin, cos) = (u * 2.0 * std::f32::consts::PI).sin_cos();
+                positions.push([point.radius * cos, point.y, -point.radius * sin]);
+                normals.push([
+                    point.normal.x() * cos,
+                    point.normal.y(),
+                    -point.normal.x() * sin,
+                ]);
+                uvs.push(match point.cap_radius {
+                    Some(cap_radius) => {
+                        let scale = point.radius / (2.0 * cap_radius);
+                        [
+                            0.5 + cos * scale,
+                            0.5 - sin * scale * point.normal.y().signum(),
+                        ]
+                    }
+                    None => [u, point.v],
+                });
+            }
+        }
+
+        let ring_size = longitudes as u32 + 1;
+        for (index, band) in profile.windows(2).enumerate() {
+            let bottom = first_vertex + index as u32 * ring_size;
+            let top = bottom + ring_size;
+            for longitude in 0..longitudes as u32 {
+                let (a, b) = (bottom + longitude, bottom + longitude + 1);
+                let (c, d) = (top + longitude + 1, top + longitude);
+                // triangles with two vertices on a pole have no area
+                if band[0].radius > 0.0 {
+                    indices.extend_from_slice(&[a, b, c]);
+                }
+                if band[1].radius > 0.0 {
+                    indices.extend_from_slice(&[a, c, d]);
+                }
+            }
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Icosphere, IcosphereError, Plane};
+    use crate::mesh::{Indices, Mesh, VertexAttributeValues};
+
+    #[test]
+    fn icospheres_with_too_many_vertices_are_refused() {
+        let sphere = Icosphere {
+            radius: 1.0,
+            subdivisions: 30_000,
+        };
+        assert_eq!(
+            sphere.try_into_mesh().err(),
+            Some(IcosphereError::TooManyVertices {
+                subdivisions: 30_000,
+                number_of_resulting_points: 9_000_600_012,
+                max_vertices: u32::MAX,
+            })
+        );
+    }
+
+    #[test]
+    fn subdivided_planes_share_vertices() {
+        let mesh = Mesh::from(Plane::new(2.0).with_subdivisions(2));
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float3(positions)) => assert_eq!(positions.len(), 16),
+            _ => panic!("the plane has no positions"),
+        }
+        match mesh.indices() {
+            Some(Indices::U32(indices)) => assert_eq!(indices.len(), 9 * 6),
+            _ => panic!("the plane has no indices"),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::{revolve, ProfilePoint};
+use crate::mesh::Mesh;
+use bevy_math::Vec2;
+use std::f32::consts::PI;
+
+/// A torus around the y axis
+#[derive(Debug, Clone, Copy)]
+pub struct Torus {
+    /// The distance from the center of the torus to the center of its ring
+    pub radius: f32,
+    /// The radius of the ring
+    pub ring_radius: f32,
+    /// The number of segments around the y axis
+    pub subdivisions_segments: usize,
+    /// The number of sides around the ring
+    pub subdivisions_sides: usize,
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Torus {
+            radius: 1.0,
+            ring_radius: 0.5,
+            subdivisions_segments: 32,
+            subdivisions_sides: 24,
+        }
+    }
+}
+
+impl From<Torus> for Mesh {
+    fn from(torus: Torus) -> Self {
+        let sides = torus.subdivisions_sides.max(3);
+        // the ring starts and ends on the outside of the torus, where the texture wraps around
+        let profile = (0..=sides)
+            .map(|side| {
+                let angle = 2.0 * PI * side as f32 / sides as f32;
+                let normal = Vec2::new(angle.cos(), angle.sin());
+                ProfilePoint {
+                    radius: torus.radius + normal.x() * torus.ring_radius,
+                    y: normal.y() * torus.ring_radius,
+                    normal,
+                    v: 1.0 - side as f32 / sides as f32,
+                    cap_radius: None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        revolve(&[profile], torus.subdivisions_segments)
+    }
+}
@@ -0,0 +1,51 @@
+use super::{revolve, ProfilePoint};
+use crate::mesh::Mesh;
+use bevy_math::Vec2;
+use std::f32::consts::PI;
+
+/// A sphere made of sectors and stacks, like the lines of longitude and latitude of a globe. Its
+/// texture coordinates map equirectangular textures onto it, with the seam facing +x.
+#[derive(Debug, Clone, Copy)]
+pub struct UVSphere {
+    pub radius: f32,
+    /// The number of vertices around each stack
+    pub sectors: usize,
+    /// The number of stacks from the south pole to the north pole
+    pub stacks: usize,
+}
+
+impl Default for UVSphere {
+    fn default() -> Self {
+        UVSphere {
+            radius: 1.0,
+            sectors: 36,
+            stacks: 18,
+        }
+    }
+}
+
+impl From<UVSphere> for Mesh {
+    fn from(sphere: UVSphere) -> Self {
+        let stacks = sphere.stacks.max(2);
+        let profile = (0..=stacks)
+            .map(|stack| {
+                let fraction = stack as f32 / stacks as f32;
+                let (sin, cos) = (-PI / 2.0 + PI * fraction).sin_cos();
+                ProfilePoint {
+                    // the poles are exactly on the axis
+                    radius: if stack == 0 || stack == stacks {
+                        0.0
+                    } else {
+                        sphere.radius * cos
+                    },
+                    y: sphere.radius * sin,
+                    normal: Vec2::new(cos, sin),
+                    v: 1.0 - fraction,
+                    cap_radius: None,
+                }
+            })
+            .collect();
+
+        revolve(&[profile], sphere.sectors)
+    }
+}
@@ -0,0 +1,108 @@
+mod ray;
+
+pub use ray::*;
+
+use crate::{
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    pipeline::PrimitiveTopology,
+};
+use bevy_math::Vec3;
+use bevy_transform::components::GlobalTransform;
+
+/// Where a [Ray] hits a mesh, in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    pub position: Vec3,
+    /// The normal of the hit triangle, facing the ray
+    pub normal: Vec3,
+    /// The distance from the camera
+    pub distance: f32,
+}
+
+/// Returns where `ray` first hits `mesh`, which is placed in the world by `transform`
+pub fn intersect_mesh(ray: &Ray, mesh: &Mesh, transform: &GlobalTransform) -> Option<Intersection> {
+    let (min, max) = mesh_bounds(mesh)?;
+    intersect_mesh_with_bounds(ray, mesh, transform, min, max)
+}
+
+/// Like [intersect_mesh], with the box around the positions of `mesh` already computed, e.g. from
+/// its [Aabb](crate::mesh::Aabb)
+pub fn intersect_mesh_with_bounds(
+    ray: &Ray,
+    mesh: &Mesh,
+    transform: &GlobalTransform,
+    min: Vec3,
+    max: Vec3,
+) -> Option<Intersection> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float3(positions)) => positions,
+        _ => return None,
+    };
+    let matrix = transform.compute_matrix();
+    let local_ray = ray.to_local(&matrix);
+    // broad phase: skip the triangles of meshes the ray doesn't come close to
+    local_ray.intersect_aabb(min, max)?;
+
+    let closest = match mesh.indices() {
+        Some(Indices::U16(indices)) => closest_triangle(
+            &local_ray,
+            positions,
+            indices.iter().map(|index| *index as usize),
+        ),
+        Some(Indices::U32(indices)) => closest_triangle(
+            &local_ray,
+            positions,
+            indices.iter().map(|index| *index as usize),
+        ),
+        None => closest_triangle(&local_ray, positions, 0..positions.len()),
+    };
+
+    let (local_distance, [a, b, c]) = closest?;
+    let position = project(&matrix, local_ray.point_at(local_distance));
+    let mut normal = (project(&matrix, b) - project(&matrix, a))
+        .cross(project(&matrix, c) - project(&matrix, a))
+        .normalize();
+    if normal.dot(ray.direction) > 0.0 {
+        normal = -normal;
+    }
+    Some(Intersection {
+        position,
+        normal,
+        distance: (position - ray.origin).length(),
+    })
+}
+
+/// Returns the distance to and the corners of the closest triangle hit by `ray`, taking the
+/// corners of each triangle from the next three `indices`
+fn closest_triangle(
+    ray: &Ray,
+    positions: &[[f32; 3]],
+    mut indices: impl Iterator<Item = usize>,
+) -> Option<(f32, [Vec3; 3])> {
+    let vertex = |index: usize| positions.get(index).map(|position| Vec3::from(*position));
+    let mut closest: Option<(f32, [Vec3; 3])> = None;
+    while let (Some(a), Some(b), Some(c)) = (indices.next(), indices.next(), indices.next()) {
+        let (a, b, c) = match (vertex(a), vertex(b), vertex(c)) {
+            (Some(a), Some(b), Some(c)) => (a, b, c),
+            _ => continue,
+        };
+        if let Some(distance) = ray.intersect_triangle(a, b, c) {
+            if closest.map_or(true, |(closest, _)| distance < closest) {
+                closest = Some((distance, [a, b, c]));
+            }
+        }
+    }
+    closest
+}
+
+fn project(matrix: &bevy_math::Mat4, point: Vec3) -> Vec3 {
+    (*matrix * point.extend(1.0)).truncate()
+}
+
+/// Returns the corners of the box around the positions of `mesh`
+pub fn mesh_bounds(mesh: &Mesh) -> Option<(Vec3, Vec3)> {
+    mesh.compute_aabb().map(|aabb| (aabb.min, aabb.max))
+}
@@ -0,0 +1,129 @@
+use crate::camera::Camera;
+use bevy_math::{Mat4, Vec2, Vec3, Vec4};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::Window;
+
+/// A half-line starting at `origin`, used to find what is under the cursor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// Normalized direction of the ray
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Creates the ray going from `camera` through the point `cursor_position` of `window`.
+    /// `cursor_position` is in logical pixels from the bottom left corner, like
+    /// [CursorMoved](bevy_window::CursorMoved) positions.
+    pub fn from_screenspace(
+        cursor_position: Vec2,
+        window: &Window,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Self {
+        let window_size = Vec2::new(window.width() as f32, window.height() as f32);
+        let ndc = cursor_position / window_size * 2.0 - Vec2::one();
+        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix.inverse();
+        let near = project_point(&ndc_to_world, ndc.extend(0.0));
+        let far = project_point(&ndc_to_world, ndc.extend(1.0));
+        Self::new(near, far - near)
+    }
+
+    /// Returns this ray in the space that `transform` maps to world space. Distances along the
+    /// returned ray are not preserved if `transform` scales.
+    pub fn to_local(&self, transform: &Mat4) -> Self {
+        let world_to_local = transform.inverse();
+        let origin = project_point(&world_to_local, self.origin);
+        let direction = (world_to_local * self.direction.extend(0.0)).truncate();
+        Self::new(origin, direction)
+    }
+
+    /// Returns the point at `distance` along the ray
+    pub fn point_at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the distance to where the ray enters the box with corners `min` and `max`, or 0.0
+    /// if the ray starts inside of it
+    pub fn intersect_aabb(&self, min: Vec3, max: Vec3) -> Option<f32> {
+        let inverse_direction = self.direction.recip();
+        let t1 = (min - self.origin) * inverse_direction;
+        let t2 = (max - self.origin) * inverse_direction;
+        let t_min = t1.min(t2).max_element();
+        let t_max = t1.max(t2).min_element();
+        if t_max >= t_min.max(0.0) {
+            Some(t_min.max(0.0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the distance to where the ray hits the triangle `a`, `b`, `c`, from either side
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        // Möller–Trumbore intersection
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let determinant = edge1.dot(p);
+        if determinant.abs() < f32::EPSILON {
+            // the ray is parallel to the triangle
+            return None;
+        }
+        let inverse_determinant = 1.0 / determinant;
+        let s = self.origin - a;
+        let u = s.dot(p) * inverse_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inverse_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let distance = edge2.dot(q) * inverse_determinant;
+        if distance >= 0.0 {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+}
+
+fn project_point(matrix: &Mat4, point: Vec3) -> Vec3 {
+    let projected: Vec4 = *matrix * point.extend(1.0);
+    projected.truncate() / projected.w()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_intersections() {
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+
+        assert_eq!(
+            ray.intersect_aabb(Vec3::splat(-1.0), Vec3::splat(1.0)),
+            Some(4.0)
+        );
+        assert_eq!(
+            ray.intersect_aabb(Vec3::new(2.0, -1.0, -1.0), Vec3::new(3.0, 1.0, 1.0)),
+            None
+        );
+
+        let a = Vec3::new(-1.0, -1.0, 0.0);
+        let b = Vec3::new(1.0, -1.0, 0.0);
+        let c = Vec3::new(0.0, 1.0, 0.0);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(5.0));
+        assert_eq!(ray.intersect_triangle(a, c, b), Some(5.0));
+        let behind = Ray::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(behind.intersect_triangle(a, b, c), None);
+    }
+}
@@ -14,7 +14,7 @@ use bevy_reflect::Reflect;
 use bevy_window::WindowId;
 
 /// A component that indicates that an entity should be drawn in the "main pass"
-#[derive(Default, Reflect)]
+#[derive(Debug, Default, Clone, Copy, Reflect)]
 pub struct MainPass;
 
 #[derive(Debug)]
@@ -94,6 +94,19 @@ impl Default for BaseRenderGraphConfig {
 /// set of nodes. It can be customized using `BaseRenderGraphConfig`.
 pub trait BaseRenderGraphBuilder {
     fn add_base_graph(&mut self, config: &BaseRenderGraphConfig, msaa: &Msaa) -> &mut Self;
+
+    /// Adds a pass drawing the [MainPass] entities seen by the camera named `camera` to the swap
+    /// chain of a window, with its own depth texture. The nodes are named after the camera, and
+    /// the camera should be added to the [ActiveCameras](crate::camera::ActiveCameras). Windows
+    /// created with [CreateWindow](bevy_window::CreateWindow) events can be drawn to as soon as
+    /// the pass is added. The window is cleared to `clear_color` every frame.
+    fn add_window_pass(
+        &mut self,
+        window_id: WindowId,
+        camera: &str,
+        clear_color: Color,
+        msaa: &Msaa,
+    ) -> &mut Self;
 }
 
 impl BaseRenderGraphBuilder for RenderGraph {
@@ -236,4 +249,108 @@ impl BaseRenderGraphBuilder for RenderGraph {
 
         self
     }
+
+    fn add_window_pass(
+        &mut self,
+        window_id: WindowId,
+        camera: &str,
+        clear_color: Color,
+        msaa: &Msaa,
+    ) -> &mut Self {
+        let camera_node = format!("{}_camera", camera);
+        let swap_chain_node = format!("{}_swap_chain", camera);
+        let depth_texture_node = format!("{}_depth_texture", camera);
+        let sampled_color_attachment_node = format!("{}_sampled_color_attachment", camera);
+        let pass_node = format!("{}_pass", camera);
+        let window_texture = |format, sample_count| {
+            WindowTextureNode::new(
+                window_id,
+                TextureDescriptor {
+                    size: Extent3d {
+                        depth: 1,
+                        width: 1,
+                        height: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                },
+            )
+        };
+
+        self.add_system_node(camera_node.clone(), CameraNode::new(camera.to_string()));
+        self.add_node(swap_chain_node.clone(), WindowSwapChainNode::new(window_id));
+        self.add_node(
+            depth_texture_node.clone(),
+            window_texture(TextureFormat::Depth32Float, msaa.samples),
+        );
+
+        let mut pass = PassNode::<&MainPass>::new(PassDescriptor {
+            color_attachments: vec![msaa.color_attachment_descriptor(
+                TextureAttachment::Input("color_attachment".to_string()),
+                TextureAttachment::Input("color_resolve_target".to_string()),
+                Operations {
+                    load: LoadOp::Clear(clear_color),
+                    store: true,
+                },
+            )],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                attachment: TextureAttachment::Input("depth".to_string()),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            sample_count: msaa.samples,
+        });
+        pass.add_camera(camera);
+        self.add_node(pass_node.clone(), pass);
+
+        self.add_node_edge(camera_node, pass_node.clone()).unwrap();
+        if self.get_node_state(node::TEXTURE_COPY).is_ok() {
+            self.add_node_edge(node::TEXTURE_COPY, pass_node.clone())
+                .unwrap();
+        }
+        if self.get_node_state(node::SHARED_BUFFERS).is_ok() {
+            self.add_node_edge(node::SHARED_BUFFERS, pass_node.clone())
+                .unwrap();
+        }
+        self.add_slot_edge(
+            swap_chain_node,
+            WindowSwapChainNode::OUT_TEXTURE,
+            pass_node.clone(),
+            if msaa.samples > 1 {
+                "color_resolve_target"
+            } else {
+                "color_attachment"
+            },
+        )
+        .unwrap();
+        self.add_slot_edge(
+            depth_texture_node,
+            WindowTextureNode::OUT_TEXTURE,
+            pass_node.clone(),
+            "depth",
+        )
+        .unwrap();
+
+        if msaa.samples > 1 {
+            self.add_node(
+                sampled_color_attachment_node.clone(),
+                window_texture(TextureFormat::default(), msaa.samples),
+            );
+            self.add_slot_edge(
+                sampled_color_attachment_node,
+                WindowTextureNode::OUT_TEXTURE,
+                pass_node,
+                "color_attachment",
+            )
+            .unwrap();
+        }
+
+        self
+    }
 }
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::{ReadOnlyFetch, Resources, World, WorldQuery};
+use bevy_ecs::{QueryFilter, ReadOnlyFetch, Resources, World, WorldQuery};
 use bevy_utils::tracing::debug;
 use std::{fmt, marker::PhantomData, ops::Deref};
 
@@ -22,7 +22,8 @@ struct CameraInfo {
     bind_group_id: Option<BindGroupId>,
 }
 
-pub struct PassNode<Q: WorldQuery> {
+/// Draws the entities visible to its cameras that match the query `Q` and the filter `F`
+pub struct PassNode<Q: WorldQuery, F: QueryFilter = ()> {
     descriptor: PassDescriptor,
     inputs: Vec<ResourceSlotInfo>,
     cameras: Vec<CameraInfo>,
@@ -31,10 +32,10 @@ pub struct PassNode<Q: WorldQuery> {
     depth_stencil_attachment_input_index: Option<usize>,
     default_clear_color_inputs: Vec<usize>,
     camera_bind_group_descriptor: BindGroupDescriptor,
-    _marker: PhantomData<Q>,
+    _marker: PhantomData<(Q, F)>,
 }
 
-impl<Q: WorldQuery> fmt::Debug for PassNode<Q> {
+impl<Q: WorldQuery, F: QueryFilter> fmt::Debug for PassNode<Q, F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("PassNose")
             .field("descriptor", &self.descriptor)
@@ -64,7 +65,7 @@ impl<Q: WorldQuery> fmt::Debug for PassNode<Q> {
     }
 }
 
-impl<Q: WorldQuery> PassNode<Q> {
+impl<Q: WorldQuery, F: QueryFilter> PassNode<Q, F> {
     pub fn new(descriptor: PassDescriptor) -> Self {
         let mut inputs = Vec::new();
         let mut color_attachment_input_indices = Vec::new();
@@ -140,7 +141,8 @@ impl<Q: WorldQuery> PassNode<Q> {
     }
 }
 
-impl<Q: WorldQuery + Send + Sync + 'static> Node for PassNode<Q>
+impl<Q: WorldQuery + Send + Sync + 'static, F: QueryFilter + Send + Sync + 'static> Node
+    for PassNode<Q, F>
 where
     Q::Fetch: ReadOnlyFetch,
 {
@@ -156,6 +158,11 @@ where
         input: &ResourceSlots,
         _output: &mut ResourceSlots,
     ) {
+        // passes drawing to windows that were closed are skipped
+        if input.iter().any(|slot| slot.resource.is_none()) {
+            return;
+        }
+
         let render_resource_bindings = resources.get::<RenderResourceBindings>().unwrap();
         let pipelines = resources.get::<Assets<PipelineDescriptor>>().unwrap();
         let active_cameras = resources.get::<ActiveCameras>().unwrap();
@@ -225,7 +232,10 @@ where
                     // attempt to draw each visible entity
                     let mut draw_state = DrawState::default();
                     for visible_entity in visible_entities.iter() {
-                        if world.query_one::<Q>(visible_entity.entity).is_err() {
+                        if world
+                            .query_one_filtered::<Q, F>(visible_entity.entity)
+                            .is_err()
+                        {
                             // visible entity does not match the Pass query
                             continue;
                         }
@@ -32,19 +32,26 @@ impl Node for TextureCopyNode {
                         if copied_textures.contains(&handle.id) {
                             continue;
                         }
+                        // textures in formats the device doesn't support aren't created
+                        let texture_resource = match render_context
+                            .resources()
+                            .get_asset_resource(handle, TEXTURE_ASSET_INDEX)
+                        {
+                            Some(texture_resource) => texture_resource,
+                            None => continue,
+                        };
 
                         let texture_descriptor: TextureDescriptor = texture.into();
-                        let width = texture.size.width as usize;
+                        // rows are made of blocks, which are single pixels for uncompressed formats
+                        let (width, height) = texture.format.block_count(texture.size);
                         let aligned_width =
                             render_context.resources().get_aligned_texture_size(width);
-                        let format_size = texture.format.pixel_size();
-                        let mut aligned_data = vec![
-                            0;
-                            format_size
-                                * aligned_width
-                                * texture.size.height as usize
-                                * texture.size.depth as usize
-                        ];
+                        let format_size = texture.format.block_size();
+                        let mut aligned_data =
+                            vec![
+                                0;
+                                format_size * aligned_width * height * texture.size.depth as usize
+                            ];
                         texture
                             .data
                             .chunks_exact(format_size * width)
@@ -62,11 +69,6 @@ impl Node for TextureCopyNode {
                             &aligned_data,
                         );
 
-                        let texture_resource = render_context
-                            .resources()
-                            .get_asset_resource(handle, TEXTURE_ASSET_INDEX)
-                            .unwrap();
-
                         render_context.copy_buffer_to_texture(
                             texture_buffer,
                             0,
@@ -81,7 +83,9 @@ impl Node for TextureCopyNode {
                         copied_textures.insert(&handle.id);
                     }
                 }
-                AssetEvent::Removed { .. } => {}
+                AssetEvent::Removed { .. }
+                | AssetEvent::Failed { .. }
+                | AssetEvent::LoadedWithDependencies { .. } => {}
             }
         }
     }
@@ -4,13 +4,15 @@ use crate::{
 };
 use bevy_app::prelude::{EventReader, Events};
 use bevy_ecs::{Resources, World};
-use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
+use bevy_window::{PresentMode, WindowCreated, WindowId, WindowResized, Windows};
 use std::borrow::Cow;
 
 pub struct WindowSwapChainNode {
     window_id: WindowId,
     window_created_event_reader: EventReader<WindowCreated>,
     window_resized_event_reader: EventReader<WindowResized>,
+    /// The present mode the swap chain was created with
+    present_mode: Option<PresentMode>,
 }
 
 impl WindowSwapChainNode {
@@ -21,6 +23,7 @@ impl WindowSwapChainNode {
             window_id,
             window_created_event_reader: Default::default(),
             window_resized_event_reader: Default::default(),
+            present_mode: None,
         }
     }
 }
@@ -47,17 +50,25 @@ impl Node for WindowSwapChainNode {
         let window_resized_events = resources.get::<Events<WindowResized>>().unwrap();
         let windows = resources.get::<Windows>().unwrap();
 
-        let window = windows
-            .get(self.window_id)
-            .expect("Received window resized event for non-existent window");
+        // nothing is drawn to windows that were closed
+        let window = match windows.get(self.window_id) {
+            Some(window) => window,
+            None => {
+                output.get_slot_mut(WINDOW_TEXTURE).unwrap().resource = None;
+                return;
+            }
+        };
 
         let render_resource_context = render_context.resources_mut();
 
-        // create window swapchain when window is resized or created
-        if self
-            .window_created_event_reader
-            .find_latest(&window_created_events, |e| e.id == window.id())
-            .is_some()
+        // create window swapchain when window is resized or created, or its present mode changed
+        let present_mode_changed = self.present_mode != Some(window.present_mode());
+        self.present_mode = Some(window.present_mode());
+        if present_mode_changed
+            || self
+                .window_created_event_reader
+                .find_latest(&window_created_events, |e| e.id == window.id())
+                .is_some()
             || self
                 .window_resized_event_reader
                 .find_latest(&window_resized_events, |e| e.id == window.id())
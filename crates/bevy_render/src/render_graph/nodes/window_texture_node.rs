@@ -50,9 +50,17 @@ impl Node for WindowTextureNode {
         let window_resized_events = resources.get::<Events<WindowResized>>().unwrap();
         let windows = resources.get::<Windows>().unwrap();
 
-        let window = windows
-            .get(self.window_id)
-            .expect("Received window resized event for non-existent window");
+        // the texture is removed once its window was closed
+        let window = match windows.get(self.window_id) {
+            Some(window) => window,
+            None => {
+                if let Some(RenderResourceId::Texture(old_texture)) = output.get(WINDOW_TEXTURE) {
+                    render_context.resources_mut().remove_texture(old_texture);
+                    output.get_slot_mut(WINDOW_TEXTURE).unwrap().resource = None;
+                }
+                return;
+            }
+        };
 
         if self
             .window_created_event_reader
@@ -3,7 +3,7 @@ use crate::{
     pipeline::{BindGroupDescriptorId, PipelineDescriptor},
     renderer::{BindGroup, BufferId, BufferInfo, RenderResourceId, SamplerId, TextureId},
     shader::Shader,
-    texture::{SamplerDescriptor, TextureDescriptor},
+    texture::{SamplerDescriptor, TextureDescriptor, TextureFormat},
 };
 use bevy_asset::{Assets, Handle, HandleUntyped};
 use bevy_utils::HashMap;
@@ -149,6 +149,10 @@ impl RenderResourceContext for HeadlessRenderResourceContext {
         size
     }
 
+    fn supports_texture_format(&self, _format: TextureFormat) -> bool {
+        true
+    }
+
     fn get_specialized_shader(&self, shader: &Shader, _macros: Option<&[String]>) -> Shader {
         shader.clone()
     }
@@ -2,7 +2,7 @@ use crate::{
     pipeline::{BindGroupDescriptorId, PipelineDescriptor, PipelineLayout},
     renderer::{BindGroup, BufferId, BufferInfo, RenderResourceId, SamplerId, TextureId},
     shader::{Shader, ShaderLayout, ShaderStages},
-    texture::{SamplerDescriptor, TextureDescriptor},
+    texture::{SamplerDescriptor, TextureDescriptor, TextureFormat},
 };
 use bevy_asset::{Asset, Assets, Handle, HandleUntyped};
 use bevy_window::Window;
@@ -36,6 +36,9 @@ pub trait RenderResourceContext: Downcast + Send + Sync + 'static {
     fn get_buffer_info(&self, buffer: BufferId) -> Option<BufferInfo>;
     fn get_aligned_uniform_size(&self, size: usize, dynamic: bool) -> usize;
     fn get_aligned_texture_size(&self, data_size: usize) -> usize;
+    /// Whether textures of the given format can be created. Block compressed formats depend on
+    /// the features of the graphics device.
+    fn supports_texture_format(&self, format: TextureFormat) -> bool;
     fn set_asset_resource_untyped(
         &self,
         handle: HandleUntyped,
@@ -98,7 +98,11 @@ pub fn asset_shader_defs_system<T: Asset>(
     T: ShaderDefs + Send + Sync + 'static,
 {
     for (asset_handle, mut render_pipelines) in query.iter_mut() {
-        let shader_defs = assets.get(asset_handle).unwrap();
+        // assets that are still loading have no shader defs yet
+        let shader_defs = match assets.get(asset_handle) {
+            Some(shader_defs) => shader_defs,
+            None => continue,
+        };
         for shader_def in shader_defs.iter_shader_defs() {
             for render_pipeline in render_pipelines.pipelines.iter_mut() {
                 render_pipeline
@@ -0,0 +1,193 @@
+use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
+use std::convert::TryInto;
+use thiserror::Error;
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_SIZE: usize = 128;
+const DX10_HEADER_SIZE: usize = 20;
+// pixel format flags
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+
+/// An error that occurs when loading a DDS file
+#[derive(Error, Debug)]
+pub enum DdsError {
+    #[error("File is not a DDS file.")]
+    InvalidMagic,
+    #[error("DDS file ended before the texture data.")]
+    UnexpectedEnd,
+    #[error("DDS pixel format with the four character code {0:?} is not supported.")]
+    UnsupportedFourCc(String),
+    #[error("DDS pixel format with the DXGI format {0} is not supported.")]
+    UnsupportedDxgiFormat(u32),
+    #[error("DDS pixel format with {0} bits per pixel is not supported.")]
+    UnsupportedPixelFormat(u32),
+}
+
+/// Loads DDS files as Texture assets. Block compressed formats (BC1 to BC7) and RGBA8 and BGRA8
+/// pixels are supported. Only the first mip level of the first layer or face is loaded.
+///
+/// Files without a DX10 header don't say whether their colors are sRGB, their color formats are
+/// loaded as sRGB like other image files.
+#[derive(Clone, Default)]
+pub struct DdsTextureLoader;
+
+impl AssetLoader for DdsTextureLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let texture = load_dds(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dds"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Texture::TYPE_UUID)
+    }
+}
+
+fn load_dds(bytes: &[u8]) -> Result<Texture, DdsError> {
+    if bytes.len() < 4 || &bytes[0..4] != DDS_MAGIC {
+        return Err(DdsError::InvalidMagic);
+    }
+    let height = read_u32(bytes, 12)?;
+    let width = read_u32(bytes, 16)?;
+    let pixel_format_flags = read_u32(bytes, 80)?;
+    let four_cc = bytes.get(84..88).ok_or(DdsError::UnexpectedEnd)?;
+
+    let mut data_offset = HEADER_SIZE;
+    let format = if pixel_format_flags & DDPF_FOURCC != 0 {
+        match four_cc {
+            b"DX10" => {
+                data_offset += DX10_HEADER_SIZE;
+                dxgi_texture_format(read_u32(bytes, HEADER_SIZE)?)?
+            }
+            b"DXT1" => TextureFormat::Bc1RgbaUnormSrgb,
+            b"DXT2" | b"DXT3" => TextureFormat::Bc2RgbaUnormSrgb,
+            b"DXT4" | b"DXT5" => TextureFormat::Bc3RgbaUnormSrgb,
+            b"ATI1" | b"BC4U" => TextureFormat::Bc4RUnorm,
+            b"BC4S" => TextureFormat::Bc4RSnorm,
+            b"ATI2" | b"BC5U" => TextureFormat::Bc5RgUnorm,
+            b"BC5S" => TextureFormat::Bc5RgSnorm,
+            _ => {
+                return Err(DdsError::UnsupportedFourCc(
+                    String::from_utf8_lossy(four_cc).into_owned(),
+                ))
+            }
+        }
+    } else if pixel_format_flags & DDPF_RGB != 0 {
+        let bit_count = read_u32(bytes, 88)?;
+        let red_mask = read_u32(bytes, 92)?;
+        match (bit_count, red_mask) {
+            (32, 0x0000_00ff) => TextureFormat::Rgba8UnormSrgb,
+            (32, 0x00ff_0000) => TextureFormat::Bgra8UnormSrgb,
+            _ => return Err(DdsError::UnsupportedPixelFormat(bit_count)),
+        }
+    } else {
+        return Err(DdsError::UnsupportedPixelFormat(read_u32(bytes, 88)?));
+    };
+
+    let size = Extent3d::new(width, height, 1);
+    let data_end = format
+        .data_size(size)
+        .and_then(|data_size| data_size.checked_add(data_offset))
+        .ok_or(DdsError::UnexpectedEnd)?;
+    let data = bytes
+        .get(data_offset..data_end)
+        .ok_or(DdsError::UnexpectedEnd)?;
+    Ok(Texture::new(
+        size,
+        TextureDimension::D2,
+        data.to_vec(),
+        format,
+    ))
+}
+
+fn dxgi_texture_format(dxgi_format: u32) -> Result<TextureFormat, DdsError> {
+    Ok(match dxgi_format {
+        28 => TextureFormat::Rgba8Unorm,
+        29 => TextureFormat::Rgba8UnormSrgb,
+        71 => TextureFormat::Bc1RgbaUnorm,
+        72 => TextureFormat::Bc1RgbaUnormSrgb,
+        74 => TextureFormat::Bc2RgbaUnorm,
+        75 => TextureFormat::Bc2RgbaUnormSrgb,
+        77 => TextureFormat::Bc3RgbaUnorm,
+        78 => TextureFormat::Bc3RgbaUnormSrgb,
+        80 => TextureFormat::Bc4RUnorm,
+        81 => TextureFormat::Bc4RSnorm,
+        83 => TextureFormat::Bc5RgUnorm,
+        84 => TextureFormat::Bc5RgSnorm,
+        87 => TextureFormat::Bgra8Unorm,
+        91 => TextureFormat::Bgra8UnormSrgb,
+        95 => TextureFormat::Bc6hRgbUfloat,
+        96 => TextureFormat::Bc6hRgbSfloat,
+        98 => TextureFormat::Bc7RgbaUnorm,
+        99 => TextureFormat::Bc7RgbaUnormSrgb,
+        _ => return Err(DdsError::UnsupportedDxgiFormat(dxgi_format)),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, DdsError> {
+    let bytes = bytes
+        .get(offset..offset + 4)
+        .ok_or(DdsError::UnexpectedEnd)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dds_header(width: u32, height: u32, four_cc: &[u8; 4]) -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(DDS_MAGIC);
+        bytes[12..16].copy_from_slice(&height.to_le_bytes());
+        bytes[16..20].copy_from_slice(&width.to_le_bytes());
+        bytes[80..84].copy_from_slice(&DDPF_FOURCC.to_le_bytes());
+        bytes[84..88].copy_from_slice(four_cc);
+        bytes
+    }
+
+    #[test]
+    fn compressed_textures_are_loaded() {
+        // two BC1 blocks, followed by a smaller mip level which isn't loaded
+        let mut bytes = dds_header(8, 4, b"DXT1");
+        bytes.extend_from_slice(&[1; 24]);
+        let texture = load_dds(&bytes).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bc1RgbaUnormSrgb);
+        assert_eq!(texture.size, Extent3d::new(8, 4, 1));
+        assert_eq!(texture.data.len(), 16);
+
+        let mut bytes = dds_header(4, 4, b"DX10");
+        bytes.extend_from_slice(&98u32.to_le_bytes());
+        bytes.extend_from_slice(&[0; DX10_HEADER_SIZE - 4]);
+        bytes.extend_from_slice(&[2; 16]);
+        let texture = load_dds(&bytes).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bc7RgbaUnorm);
+        assert_eq!(texture.data, vec![2; 16]);
+
+        let bytes = dds_header(8, 8, b"DXT5");
+        assert!(matches!(load_dds(&bytes), Err(DdsError::UnexpectedEnd)));
+    }
+
+    #[test]
+    fn huge_dimensions_are_rejected() {
+        let bytes = dds_header(u32::MAX, u32::MAX, b"DXT5");
+        assert!(matches!(load_dds(&bytes), Err(DdsError::UnexpectedEnd)));
+
+        let bytes = dds_header(u32::MAX, 1, b"DXT1");
+        assert!(matches!(load_dds(&bytes), Err(DdsError::UnexpectedEnd)));
+    }
+}
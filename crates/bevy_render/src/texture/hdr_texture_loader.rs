@@ -1,7 +1,8 @@
 use super::{Extent3d, Texture, TextureDimension, TextureFormat};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
-use bevy_utils::BoxedFuture;
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
 
 /// Loads HDR textures as Texture assets
 #[derive(Clone, Default)]
@@ -50,4 +51,8 @@ impl AssetLoader for HdrTextureLoader {
     fn extensions(&self) -> &[&str] {
         &["hdr"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Texture::TYPE_UUID)
+    }
 }
@@ -1,7 +1,30 @@
 use super::{Extent3d, Texture, TextureDimension, TextureFormat};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
-use bevy_utils::BoxedFuture;
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
+
+/// Settings for [ImageTextureLoader], passed with
+/// [AssetServer::load_with_settings](bevy_asset::AssetServer::load_with_settings)
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ImageSettings {
+    /// Treat 8 bit color data as sRGB encoded. Disable this for data textures such as normal maps.
+    pub srgb: bool,
+    /// Compress 8 bit RGBA images to BC1/BC3 and cache the result next to the source. Only
+    /// has an effect with the `texture_compression` feature. Disabled by default, as compressed
+    /// textures are not drawn on devices without BC support, which includes most mobile devices
+    /// and browsers.
+    pub compress: bool,
+}
+
+impl Default for ImageSettings {
+    fn default() -> Self {
+        ImageSettings {
+            srgb: true,
+            compress: false,
+        }
+    }
+}
 
 /// Loader for images that can be read by the `image` crate.
 ///
@@ -18,6 +41,29 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             use bevy_core::AsBytes;
 
+            let settings = load_context
+                .settings::<ImageSettings>()
+                .cloned()
+                .unwrap_or_default();
+
+            #[cfg(feature = "texture_compression")]
+            let cache_path = {
+                let mut cache_path = load_context.path().as_os_str().to_owned();
+                cache_path.push(if settings.srgb { ".bcn" } else { ".linear.bcn" });
+                std::path::PathBuf::from(cache_path)
+            };
+            #[cfg(feature = "texture_compression")]
+            let source_hash = super::source_hash(bytes);
+            #[cfg(feature = "texture_compression")]
+            if settings.compress {
+                if let Ok(cache) = load_context.read_asset_bytes(&cache_path).await {
+                    if let Some(texture) = super::read_compressed_cache(&cache, source_hash) {
+                        load_context.set_default_asset(LoadedAsset::new(texture));
+                        return Ok(());
+                    }
+                }
+            }
+
             // Find the image type we expect. A file with the extension "png" should
             // probably load as a PNG.
 
@@ -147,6 +193,32 @@ impl AssetLoader for ImageTextureLoader {
                 }
             }
 
+            let format = if settings.srgb {
+                format
+            } else {
+                match format {
+                    TextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8Unorm,
+                    TextureFormat::Bgra8UnormSrgb => TextureFormat::Bgra8Unorm,
+                    format => format,
+                }
+            };
+
+            #[cfg(feature = "texture_compression")]
+            if settings.compress
+                && super::can_compress(width, height)
+                && matches!(
+                    format,
+                    TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb
+                )
+            {
+                let texture = super::compress_rgba8(&data, width, height, settings.srgb);
+                let cache = super::write_compressed_cache(&texture, source_hash);
+                // the cache only speeds up later loads, so failing to write it isn't an error
+                let _ = load_context.write_asset_bytes(&cache_path, &cache);
+                load_context.set_default_asset(LoadedAsset::new(texture));
+                return Ok(());
+            }
+
             let texture = Texture::new(
                 Extent3d::new(width, height, 1),
                 TextureDimension::D2,
@@ -161,4 +233,8 @@ impl AssetLoader for ImageTextureLoader {
     fn extensions(&self) -> &[&str] {
         &["png"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Texture::TYPE_UUID)
+    }
 }
@@ -0,0 +1,190 @@
+use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
+use std::convert::TryInto;
+use thiserror::Error;
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const LEVEL_INDEX_OFFSET: usize = 80;
+
+/// An error that occurs when loading a KTX2 file
+#[derive(Error, Debug)]
+pub enum Ktx2Error {
+    #[error("File is not a KTX2 file.")]
+    InvalidIdentifier,
+    #[error("KTX2 file ended before the texture data.")]
+    UnexpectedEnd,
+    #[error("KTX2 files with the Vulkan format {0} are not supported.")]
+    UnsupportedFormat(u32),
+    #[error("KTX2 files with supercompression are not supported.")]
+    UnsupportedSupercompression,
+}
+
+/// Loads KTX2 files as Texture assets. Block compressed formats (BC1 to BC7) and RGBA8, BGRA8,
+/// RGBA16 float and RGBA32 float pixels are supported, without supercompression. Only the first
+/// mip level of the first layer or face is loaded.
+#[derive(Clone, Default)]
+pub struct Ktx2TextureLoader;
+
+impl AssetLoader for Ktx2TextureLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let texture = load_ktx2(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ktx2"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Texture::TYPE_UUID)
+    }
+}
+
+fn load_ktx2(bytes: &[u8]) -> Result<Texture, Ktx2Error> {
+    if bytes.get(0..12) != Some(&KTX2_IDENTIFIER[..]) {
+        return Err(Ktx2Error::InvalidIdentifier);
+    }
+    // textures in the Basis Universal format have an undefined Vulkan format and are always
+    // supercompressed
+    if read_u32(bytes, 44)? != 0 {
+        return Err(Ktx2Error::UnsupportedSupercompression);
+    }
+    let format = vulkan_texture_format(read_u32(bytes, 12)?)?;
+    let width = read_u32(bytes, 20)?;
+    // one dimensional textures have a height of 0
+    let height = read_u32(bytes, 24)?.max(1);
+
+    // the data of the first level starts with its first layer and face
+    let size = Extent3d::new(width, height, 1);
+    // the offset comes from the file, and may be past the end of the address space
+    let level_start: usize = read_u64(bytes, LEVEL_INDEX_OFFSET)?
+        .try_into()
+        .map_err(|_| Ktx2Error::UnexpectedEnd)?;
+    let level_end = format
+        .data_size(size)
+        .and_then(|data_size| data_size.checked_add(level_start))
+        .ok_or(Ktx2Error::UnexpectedEnd)?;
+    let data = bytes
+        .get(level_start..level_end)
+        .ok_or(Ktx2Error::UnexpectedEnd)?;
+    Ok(Texture::new(
+        size,
+        TextureDimension::D2,
+        data.to_vec(),
+        format,
+    ))
+}
+
+fn vulkan_texture_format(vk_format: u32) -> Result<TextureFormat, Ktx2Error> {
+    Ok(match vk_format {
+        37 => TextureFormat::Rgba8Unorm,
+        43 => TextureFormat::Rgba8UnormSrgb,
+        44 => TextureFormat::Bgra8Unorm,
+        50 => TextureFormat::Bgra8UnormSrgb,
+        97 => TextureFormat::Rgba16Float,
+        109 => TextureFormat::Rgba32Float,
+        // BC1 textures without alpha are read as opaque RGBA
+        131 | 133 => TextureFormat::Bc1RgbaUnorm,
+        132 | 134 => TextureFormat::Bc1RgbaUnormSrgb,
+        135 => TextureFormat::Bc2RgbaUnorm,
+        136 => TextureFormat::Bc2RgbaUnormSrgb,
+        137 => TextureFormat::Bc3RgbaUnorm,
+        138 => TextureFormat::Bc3RgbaUnormSrgb,
+        139 => TextureFormat::Bc4RUnorm,
+        140 => TextureFormat::Bc4RSnorm,
+        141 => TextureFormat::Bc5RgUnorm,
+        142 => TextureFormat::Bc5RgSnorm,
+        143 => TextureFormat::Bc6hRgbUfloat,
+        144 => TextureFormat::Bc6hRgbSfloat,
+        145 => TextureFormat::Bc7RgbaUnorm,
+        146 => TextureFormat::Bc7RgbaUnormSrgb,
+        _ => return Err(Ktx2Error::UnsupportedFormat(vk_format)),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, Ktx2Error> {
+    let bytes = bytes
+        .get(offset..offset + 4)
+        .ok_or(Ktx2Error::UnexpectedEnd)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, Ktx2Error> {
+    let bytes = bytes
+        .get(offset..offset + 8)
+        .ok_or(Ktx2Error::UnexpectedEnd)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ktx2_header(vk_format: u32, width: u32, height: u32, level_offset: u64) -> Vec<u8> {
+        let mut bytes = vec![0; LEVEL_INDEX_OFFSET + 8];
+        bytes[0..12].copy_from_slice(&KTX2_IDENTIFIER);
+        bytes[12..16].copy_from_slice(&vk_format.to_le_bytes());
+        bytes[20..24].copy_from_slice(&width.to_le_bytes());
+        bytes[24..28].copy_from_slice(&height.to_le_bytes());
+        bytes[LEVEL_INDEX_OFFSET..].copy_from_slice(&level_offset.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn textures_are_loaded() {
+        // a BC7 block after the header
+        let mut bytes = ktx2_header(145, 4, 4, (LEVEL_INDEX_OFFSET + 8) as u64);
+        bytes.extend_from_slice(&[3; 16]);
+        let texture = load_ktx2(&bytes).unwrap();
+        assert_eq!(texture.format, TextureFormat::Bc7RgbaUnorm);
+        assert_eq!(texture.size, Extent3d::new(4, 4, 1));
+        assert_eq!(texture.data, vec![3; 16]);
+
+        // one dimensional textures are one pixel high
+        let mut bytes = ktx2_header(37, 2, 0, (LEVEL_INDEX_OFFSET + 8) as u64);
+        bytes.extend_from_slice(&[4; 8]);
+        let texture = load_ktx2(&bytes).unwrap();
+        assert_eq!(texture.size, Extent3d::new(2, 1, 1));
+
+        let mut bytes = ktx2_header(37, 1, 1, 0);
+        bytes[44..48].copy_from_slice(&1u32.to_le_bytes());
+        assert!(matches!(
+            load_ktx2(&bytes),
+            Err(Ktx2Error::UnsupportedSupercompression)
+        ));
+        assert!(matches!(
+            load_ktx2(&ktx2_header(1, 1, 1, 0)),
+            Err(Ktx2Error::UnsupportedFormat(1))
+        ));
+        assert!(matches!(
+            load_ktx2(b"not a ktx2 file"),
+            Err(Ktx2Error::InvalidIdentifier)
+        ));
+    }
+
+    #[test]
+    fn truncated_textures_are_rejected() {
+        let bytes = ktx2_header(145, 4, 4, (LEVEL_INDEX_OFFSET + 8) as u64);
+        assert!(matches!(load_ktx2(&bytes), Err(Ktx2Error::UnexpectedEnd)));
+        assert!(matches!(
+            load_ktx2(&bytes[..LEVEL_INDEX_OFFSET]),
+            Err(Ktx2Error::UnexpectedEnd)
+        ));
+
+        // level offsets that overflow are an error rather than a panic
+        let bytes = ktx2_header(145, 4, 4, u64::MAX);
+        assert!(matches!(load_ktx2(&bytes), Err(Ktx2Error::UnexpectedEnd)));
+    }
+}
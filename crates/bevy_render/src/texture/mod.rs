@@ -1,18 +1,36 @@
+#[cfg(feature = "dds")]
+mod dds_texture_loader;
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
 #[cfg(feature = "png")]
 mod image_texture_loader;
+#[cfg(feature = "ktx2")]
+mod ktx2_texture_loader;
 mod sampler_descriptor;
+#[cfg(feature = "svg")]
+mod svg_loader;
 #[allow(clippy::module_inception)]
 mod texture;
+#[cfg(feature = "texture_compression")]
+mod texture_compression;
 mod texture_descriptor;
 mod texture_dimension;
+mod window_icon;
 
+#[cfg(feature = "dds")]
+pub use dds_texture_loader::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
 #[cfg(feature = "png")]
 pub use image_texture_loader::*;
+#[cfg(feature = "ktx2")]
+pub use ktx2_texture_loader::*;
 pub use sampler_descriptor::*;
+#[cfg(feature = "svg")]
+pub use svg_loader::*;
 pub use texture::*;
+#[cfg(feature = "texture_compression")]
+pub use texture_compression::*;
 pub use texture_descriptor::*;
 pub use texture_dimension::*;
+pub use window_icon::*;
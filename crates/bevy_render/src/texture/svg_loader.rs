@@ -0,0 +1,268 @@
+use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use crate::{
+    mesh::{Indices, Mesh},
+    pipeline::PrimitiveTopology,
+};
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
+use lyon_tessellation::{
+    math::{point, Point},
+    path::Path,
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
+};
+use thiserror::Error;
+
+/// An error that occurs when loading an SVG file
+#[derive(Error, Debug)]
+pub enum SvgError {
+    #[error("Invalid SVG file.")]
+    Parse(#[from] usvg::Error),
+    #[error("SVG can't be rasterized at {0}x{1}.")]
+    InvalidSize(u32, u32),
+    #[error("Failed to tessellate SVG path: {0:?}")]
+    Tessellation(lyon_tessellation::TessellationError),
+}
+
+/// Settings for [SvgLoader], passed with
+/// [AssetServer::load_with_settings](bevy_asset::AssetServer::load_with_settings)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct SvgSettings {
+    /// Width of the rasterized texture in pixels. If only one of `width` and `height` is set, the
+    /// other one keeps the aspect ratio of the image. If neither is set, the size in the file is
+    /// used.
+    pub width: Option<u32>,
+    /// Height of the rasterized texture in pixels
+    pub height: Option<u32>,
+    /// Also tessellates the filled shapes to a [Mesh] labeled "mesh"
+    pub mesh: bool,
+}
+
+/// Loads SVG files.
+///
+/// The default asset is a [Texture] rasterized at the size requested in [SvgSettings]. When
+/// [SvgSettings::mesh] is set, the filled shapes are also tessellated to a [Mesh] labeled "mesh",
+/// with one unit per SVG pixel and the origin at the top left corner of the image. The mesh
+/// doesn't keep fill colors, so use the texture for images with more than one color.
+#[derive(Clone, Default)]
+pub struct SvgLoader;
+
+/// Maximum distance between a curve and its tessellation, in SVG pixels
+const TESSELLATION_TOLERANCE: f32 = 0.1;
+
+impl AssetLoader for SvgLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let settings = load_context
+                .settings::<SvgSettings>()
+                .cloned()
+                .unwrap_or_default();
+            let (texture, mesh) = load_svg(bytes, settings)?;
+            if let Some(mesh) = mesh {
+                load_context.set_labeled_asset("mesh", LoadedAsset::new(mesh));
+            }
+            load_context.set_default_asset(LoadedAsset::new(texture));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["svg"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Texture::TYPE_UUID)
+    }
+}
+
+fn load_svg(bytes: &[u8], settings: SvgSettings) -> Result<(Texture, Option<Mesh>), SvgError> {
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())?;
+    let texture = rasterize(&tree, settings)?;
+    let mesh = if settings.mesh {
+        Some(tessellate(&tree)?)
+    } else {
+        None
+    };
+    Ok((texture, mesh))
+}
+
+fn rasterize(tree: &usvg::Tree, settings: SvgSettings) -> Result<Texture, SvgError> {
+    let fit_to = match (settings.width, settings.height) {
+        (Some(width), Some(height)) => usvg::FitTo::Size(width, height),
+        (Some(width), None) => usvg::FitTo::Width(width),
+        (None, Some(height)) => usvg::FitTo::Height(height),
+        (None, None) => usvg::FitTo::Original,
+    };
+    let size = fit_to
+        .fit_to(tree.svg_node().size.to_screen_size())
+        .ok_or_else(|| {
+            SvgError::InvalidSize(settings.width.unwrap_or(0), settings.height.unwrap_or(0))
+        })?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| SvgError::InvalidSize(size.width(), size.height()))?;
+    resvg::render(tree, fit_to, pixmap.as_mut())
+        .ok_or_else(|| SvgError::InvalidSize(size.width(), size.height()))?;
+
+    // tiny-skia renders premultiplied alpha
+    let mut data = pixmap.take();
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha != 0 && alpha != 255 {
+            for channel in pixel[..3].iter_mut() {
+                *channel = ((*channel as u32 * 255 + alpha as u32 / 2) / alpha as u32) as u8;
+            }
+        }
+    }
+
+    Ok(Texture::new(
+        Extent3d::new(size.width(), size.height(), 1),
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    ))
+}
+
+fn tessellate(tree: &usvg::Tree) -> Result<Mesh, SvgError> {
+    let size = tree.svg_node().size;
+    let (width, height) = (size.width() as f32, size.height() as f32);
+    let mut buffers: VertexBuffers<Point, u32> = VertexBuffers::new();
+    let mut tessellator = FillTessellator::new();
+    for node in tree.root().descendants() {
+        if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+            let fill_rule = match path.fill {
+                Some(ref fill) if fill.rule == usvg::FillRule::NonZero => FillRule::NonZero,
+                Some(_) => FillRule::EvenOdd,
+                None => continue,
+            };
+            let path = to_lyon_path(&path.data, &node.abs_transform());
+            tessellator
+                .tessellate_path(
+                    &path,
+                    &FillOptions::tolerance(TESSELLATION_TOLERANCE).with_fill_rule(fill_rule),
+                    &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position()),
+                )
+                .map_err(SvgError::Tessellation)?;
+        }
+    }
+
+    let vertex_count = buffers.vertices.len();
+    let mut positions = Vec::with_capacity(vertex_count);
+    let mut uvs = Vec::with_capacity(vertex_count);
+    for vertex in buffers.vertices.iter() {
+        // SVG's y axis points down
+        positions.push([vertex.x, -vertex.y, 0.0]);
+        uvs.push([vertex.x / width, vertex.y / height]);
+    }
+    // flipping the y axis also flips the winding, restore counter clockwise front faces
+    let mut indices = buffers.indices;
+    for triangle in indices.chunks_exact_mut(3) {
+        triangle.swap(1, 2);
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0f32, 0.0, 1.0]; vertex_count],
+    );
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    Ok(mesh)
+}
+
+fn to_lyon_path(data: &usvg::PathData, transform: &usvg::Transform) -> Path {
+    let to_point = |x: f64, y: f64| {
+        let (x, y) = transform.apply(x, y);
+        point(x as f32, y as f32)
+    };
+
+    let mut builder = Path::builder();
+    let mut in_subpath = false;
+    for segment in data.iter() {
+        match *segment {
+            usvg::PathSegment::MoveTo { x, y } => {
+                if in_subpath {
+                    builder.end(false);
+                }
+                builder.begin(to_point(x, y));
+                in_subpath = true;
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                builder.line_to(to_point(x, y));
+            }
+            usvg::PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                builder.cubic_bezier_to(to_point(x1, y1), to_point(x2, y2), to_point(x, y));
+            }
+            usvg::PathSegment::ClosePath => {
+                if in_subpath {
+                    builder.end(true);
+                }
+                in_subpath = false;
+            }
+        }
+    }
+    if in_subpath {
+        builder.end(false);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3;
+
+    // a red square in the left half of a 20x10 image
+    const FIXTURE: &[u8] = include_bytes!("svg_loader_fixture.svg");
+
+    fn pixel(texture: &Texture, x: u32, y: u32) -> &[u8] {
+        let start = ((y * texture.size.width + x) * 4) as usize;
+        &texture.data[start..start + 4]
+    }
+
+    #[test]
+    fn svg_is_rasterized() {
+        let (texture, mesh) = load_svg(FIXTURE, SvgSettings::default()).unwrap();
+        assert_eq!(texture.size, Extent3d::new(20, 10, 1));
+        assert_eq!(pixel(&texture, 5, 5), &[255, 0, 0, 255]);
+        assert_eq!(pixel(&texture, 15, 5)[3], 0);
+        assert!(mesh.is_none());
+
+        let settings = SvgSettings {
+            width: Some(40),
+            ..Default::default()
+        };
+        let (texture, _) = load_svg(FIXTURE, settings).unwrap();
+        assert_eq!(texture.size, Extent3d::new(40, 20, 1));
+        assert_eq!(pixel(&texture, 30, 10)[3], 0);
+    }
+
+    #[test]
+    fn svg_is_tessellated_when_requested() {
+        let settings = SvgSettings {
+            mesh: true,
+            ..Default::default()
+        };
+        let (_, mesh) = load_svg(FIXTURE, settings).unwrap();
+        let mesh = mesh.unwrap();
+        let aabb = mesh.compute_aabb().unwrap();
+        assert!((aabb.min - Vec3::new(0.0, -10.0, 0.0)).length() < 1e-4);
+        assert!((aabb.max - Vec3::new(10.0, 0.0, 0.0)).length() < 1e-4);
+        match mesh.indices() {
+            Some(Indices::U32(indices)) => assert_eq!(indices.len(), 6),
+            _ => panic!("SVG meshes have u32 indices"),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use bevy_app::prelude::{EventReader, Events};
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{Res, ResMut};
 use bevy_reflect::TypeUuid;
-use bevy_utils::HashSet;
+use bevy_utils::{tracing::warn, HashSet};
 
 pub const TEXTURE_ASSET_INDEX: u64 = 0;
 pub const SAMPLER_ASSET_INDEX: u64 = 1;
@@ -45,8 +45,8 @@ impl Texture {
         format: TextureFormat,
     ) -> Self {
         debug_assert_eq!(
-            size.volume() * format.pixel_size(),
-            data.len(),
+            format.data_size(size),
+            Some(data.len()),
             "Pixel data, size and format have to match",
         );
         Self {
@@ -93,8 +93,11 @@ impl Texture {
 
     pub fn resize(&mut self, size: Extent3d) {
         self.size = size;
-        self.data
-            .resize(size.volume() * self.format.pixel_size(), 0);
+        let data_size = self
+            .format
+            .data_size(size)
+            .expect("texture size overflows usize");
+        self.data.resize(data_size, 0);
     }
 
     /// Changes the `size`, asserting that the total number of data elements (pixels) remains the same.
@@ -148,11 +151,20 @@ impl Texture {
                     // events are ordered so future modification events are ok
                     changed_textures.remove(handle);
                 }
+                AssetEvent::Failed { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
             }
         }
 
         for texture_handle in changed_textures.iter() {
             if let Some(texture) = textures.get(*texture_handle) {
+                if !render_resource_context.supports_texture_format(texture.format) {
+                    warn!(
+                        "Textures with the {:?} format are not supported by the graphics device, \
+                        {:?} won't be drawn",
+                        texture.format, texture_handle
+                    );
+                    continue;
+                }
                 let texture_descriptor: TextureDescriptor = texture.into();
                 let texture_resource = render_resource_context.create_texture(texture_descriptor);
 
@@ -0,0 +1,236 @@
+use super::{Extent3d, Texture, TextureDimension, TextureFormat};
+use std::convert::TryInto;
+
+const CACHE_MAGIC: [u8; 4] = *b"BCNC";
+const CACHE_VERSION: u8 = 1;
+const CACHE_HEADER_SIZE: usize = 26;
+
+/// Returns true if an RGBA8 image of the given size can be block compressed. Block compressed
+/// textures must be made of whole 4x4 blocks.
+pub fn can_compress(width: u32, height: u32) -> bool {
+    width > 0 && height > 0 && width % 4 == 0 && height % 4 == 0
+}
+
+/// Compresses RGBA8 pixels to BC1 if every pixel is opaque, or to BC3 otherwise
+pub fn compress_rgba8(rgba: &[u8], width: u32, height: u32, srgb: bool) -> Texture {
+    assert!(can_compress(width, height));
+    let opaque = rgba.chunks_exact(4).all(|pixel| pixel[3] == 255);
+    let format = match (opaque, srgb) {
+        (true, false) => TextureFormat::Bc1RgbaUnorm,
+        (true, true) => TextureFormat::Bc1RgbaUnormSrgb,
+        (false, false) => TextureFormat::Bc3RgbaUnorm,
+        (false, true) => TextureFormat::Bc3RgbaUnormSrgb,
+    };
+
+    let size = Extent3d::new(width, height, 1);
+    let mut data = Vec::with_capacity(format.data_size(size).unwrap_or(0));
+    let mut block = [[0u8; 4]; 16];
+    for block_y in (0..height as usize).step_by(4) {
+        for block_x in (0..width as usize).step_by(4) {
+            for (index, pixel) in block.iter_mut().enumerate() {
+                let x = block_x + index % 4;
+                let y = block_y + index / 4;
+                let offset = (y * width as usize + x) * 4;
+                pixel.copy_from_slice(&rgba[offset..offset + 4]);
+            }
+            if !opaque {
+                encode_alpha_block(&block, &mut data);
+            }
+            encode_color_block(&block, &mut data);
+        }
+    }
+
+    Texture::new(size, TextureDimension::D2, data, format)
+}
+
+/// Serializes a compressed texture together with the hash of the source it was created from
+pub fn write_compressed_cache(texture: &Texture, source_hash: u64) -> Vec<u8> {
+    let format = match texture.format {
+        TextureFormat::Bc1RgbaUnorm => 0u8,
+        TextureFormat::Bc1RgbaUnormSrgb => 1,
+        TextureFormat::Bc3RgbaUnorm => 2,
+        TextureFormat::Bc3RgbaUnormSrgb => 3,
+        format => panic!("{:?} is not a block compressed format", format),
+    };
+    let mut bytes = Vec::with_capacity(CACHE_HEADER_SIZE + texture.data.len());
+    bytes.extend_from_slice(&CACHE_MAGIC);
+    bytes.push(CACHE_VERSION);
+    bytes.push(format);
+    bytes.extend_from_slice(&texture.size.width.to_le_bytes());
+    bytes.extend_from_slice(&texture.size.height.to_le_bytes());
+    bytes.extend_from_slice(&source_hash.to_le_bytes());
+    bytes.extend_from_slice(&(texture.data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&texture.data);
+    bytes
+}
+
+/// Reads a texture written by [write_compressed_cache], returning `None` if the cache is invalid
+/// or was created from a different source
+pub fn read_compressed_cache(bytes: &[u8], source_hash: u64) -> Option<Texture> {
+    if bytes.len() < CACHE_HEADER_SIZE
+        || bytes[0..4] != CACHE_MAGIC
+        || bytes[4] != CACHE_VERSION
+        || read_u64(&bytes[14..22]) != source_hash
+    {
+        return None;
+    }
+
+    let format = match bytes[5] {
+        0 => TextureFormat::Bc1RgbaUnorm,
+        1 => TextureFormat::Bc1RgbaUnormSrgb,
+        2 => TextureFormat::Bc3RgbaUnorm,
+        3 => TextureFormat::Bc3RgbaUnormSrgb,
+        _ => return None,
+    };
+    let size = Extent3d::new(read_u32(&bytes[6..10]), read_u32(&bytes[10..14]), 1);
+    let data_len = read_u32(&bytes[22..26]) as usize;
+    let data = &bytes[CACHE_HEADER_SIZE..];
+    if data.len() != data_len || Some(data_len) != format.data_size(size) {
+        return None;
+    }
+
+    Some(Texture::new(
+        size,
+        TextureDimension::D2,
+        data.to_vec(),
+        format,
+    ))
+}
+
+/// A hash that stays the same across runs and compiler versions, used to detect stale caches
+pub fn source_hash(bytes: &[u8]) -> u64 {
+    // FNV-1a
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Encodes the color of a 4x4 block in the BC1 format, using the block's bounding box as the
+/// endpoints
+fn encode_color_block(block: &[[u8; 4]; 16], output: &mut Vec<u8>) {
+    let mut min = [255u8; 3];
+    let mut max = [0u8; 3];
+    for pixel in block.iter() {
+        for channel in 0..3 {
+            min[channel] = min[channel].min(pixel[channel]);
+            max[channel] = max[channel].max(pixel[channel]);
+        }
+    }
+
+    let mut color0 = to_rgb565(max);
+    let mut color1 = to_rgb565(min);
+    // color0 > color1 selects the four color mode, which doesn't reserve an index for transparency
+    if color0 < color1 {
+        std::mem::swap(&mut color0, &mut color1);
+    }
+
+    let mut indices = 0u32;
+    if color0 != color1 {
+        let endpoint0 = from_rgb565(color0);
+        let endpoint1 = from_rgb565(color1);
+        let mut palette = [[0i32; 3]; 4];
+        for channel in 0..3 {
+            let (a, b) = (endpoint0[channel], endpoint1[channel]);
+            palette[0][channel] = a;
+            palette[1][channel] = b;
+            palette[2][channel] = (2 * a + b) / 3;
+            palette[3][channel] = (a + 2 * b) / 3;
+        }
+
+        for (index, pixel) in block.iter().enumerate() {
+            let closest = (0..4)
+                .min_by_key(|entry| {
+                    (0..3)
+                        .map(|channel| {
+                            let difference = pixel[channel] as i32 - palette[*entry][channel];
+                            difference * difference
+                        })
+                        .sum::<i32>()
+                })
+                .unwrap();
+            indices |= (closest as u32) << (index * 2);
+        }
+    }
+
+    output.extend_from_slice(&color0.to_le_bytes());
+    output.extend_from_slice(&color1.to_le_bytes());
+    output.extend_from_slice(&indices.to_le_bytes());
+}
+
+/// Encodes the alpha of a 4x4 block in the BC3 format, using eight interpolated alpha values
+fn encode_alpha_block(block: &[[u8; 4]; 16], output: &mut Vec<u8>) {
+    let alpha0 = block.iter().map(|pixel| pixel[3]).max().unwrap();
+    let alpha1 = block.iter().map(|pixel| pixel[3]).min().unwrap();
+
+    let mut indices = 0u64;
+    if alpha0 != alpha1 {
+        let (a, b) = (alpha0 as i32, alpha1 as i32);
+        let mut palette = [a, b, 0, 0, 0, 0, 0, 0];
+        for (step, value) in palette.iter_mut().enumerate().skip(2) {
+            let weight = step as i32 - 1;
+            *value = ((7 - weight) * a + weight * b) / 7;
+        }
+
+        for (index, pixel) in block.iter().enumerate() {
+            let closest = (0..8)
+                .min_by_key(|entry| (pixel[3] as i32 - palette[*entry]).abs())
+                .unwrap();
+            indices |= (closest as u64) << (index * 3);
+        }
+    }
+
+    output.push(alpha0);
+    output.push(alpha1);
+    output.extend_from_slice(&indices.to_le_bytes()[..6]);
+}
+
+fn to_rgb565(color: [u8; 3]) -> u16 {
+    ((color[0] as u16 >> 3) << 11) | ((color[1] as u16 >> 2) << 5) | (color[2] as u16 >> 3)
+}
+
+fn from_rgb565(color: u16) -> [i32; 3] {
+    let r = ((color >> 11) & 0x1f) as i32;
+    let g = ((color >> 5) & 0x3f) as i32;
+    let b = (color & 0x1f) as i32;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_and_cache() {
+        let mut rgba = Vec::new();
+        for index in 0..8 * 4 {
+            rgba.extend_from_slice(&[(index * 8) as u8, 64, 255 - (index * 8) as u8, 255]);
+        }
+
+        let texture = compress_rgba8(&rgba, 8, 4, true);
+        assert_eq!(texture.format, TextureFormat::Bc1RgbaUnormSrgb);
+        assert_eq!(texture.data.len(), 16);
+
+        let hash = source_hash(&rgba);
+        let cache = write_compressed_cache(&texture, hash);
+        let cached = read_compressed_cache(&cache, hash).unwrap();
+        assert_eq!(cached.data, texture.data);
+        assert!(read_compressed_cache(&cache, hash + 1).is_none());
+
+        rgba[3] = 0;
+        let texture = compress_rgba8(&rgba, 8, 4, false);
+        assert_eq!(texture.format, TextureFormat::Bc3RgbaUnorm);
+        assert_eq!(texture.data.len(), 32);
+    }
+}
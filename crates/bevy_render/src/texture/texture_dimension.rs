@@ -117,6 +117,22 @@ pub enum TextureFormat {
     Depth32Float = 35,
     Depth24Plus = 36,
     Depth24PlusStencil8 = 37,
+
+    // Block compressed formats, encoding blocks of 4x4 pixels
+    Bc1RgbaUnorm = 38,
+    Bc1RgbaUnormSrgb = 39,
+    Bc3RgbaUnorm = 40,
+    Bc3RgbaUnormSrgb = 41,
+    Bc2RgbaUnorm = 42,
+    Bc2RgbaUnormSrgb = 43,
+    Bc4RUnorm = 44,
+    Bc4RSnorm = 45,
+    Bc5RgUnorm = 46,
+    Bc5RgSnorm = 47,
+    Bc6hRgbUfloat = 48,
+    Bc6hRgbSfloat = 49,
+    Bc7RgbaUnorm = 50,
+    Bc7RgbaUnormSrgb = 51,
 }
 
 impl TextureFormat {
@@ -139,6 +155,22 @@ impl TextureFormat {
             | TextureFormat::Bgra8Unorm
             | TextureFormat::Bgra8UnormSrgb => 1,
 
+            // block compressed formats decode to 8bit components, except BC6H which decodes to
+            // half floats
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => 1,
+            TextureFormat::Bc6hRgbUfloat | TextureFormat::Bc6hRgbSfloat => 2,
+
             // 16bit
             TextureFormat::R16Uint
             | TextureFormat::R16Sint
@@ -179,7 +211,9 @@ impl TextureFormat {
             | TextureFormat::R16Float
             | TextureFormat::R32Uint
             | TextureFormat::R32Sint
-            | TextureFormat::R32Float => 1,
+            | TextureFormat::R32Float
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm => 1,
 
             TextureFormat::Rg8Unorm
             | TextureFormat::Rg8Snorm
@@ -190,7 +224,11 @@ impl TextureFormat {
             | TextureFormat::Rg16Float
             | TextureFormat::Rg32Uint
             | TextureFormat::Rg32Sint
-            | TextureFormat::Rg32Float => 2,
+            | TextureFormat::Rg32Float
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm => 2,
+
+            TextureFormat::Bc6hRgbUfloat | TextureFormat::Bc6hRgbSfloat => 3,
 
             TextureFormat::Rgba8Unorm
             | TextureFormat::Rgba8UnormSrgb
@@ -204,7 +242,15 @@ impl TextureFormat {
             | TextureFormat::Rgba16Float
             | TextureFormat::Rgba32Uint
             | TextureFormat::Rgba32Sint
-            | TextureFormat::Rgba32Float => 4,
+            | TextureFormat::Rgba32Float
+            | TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => 4,
 
             // special cases
             TextureFormat::Rgb10a2Unorm
@@ -220,10 +266,79 @@ impl TextureFormat {
         }
     }
 
+    /// The size of a pixel in bytes. Block compressed formats don't store individual pixels, use
+    /// [TextureFormat::block_size] for them instead.
     pub fn pixel_size(&self) -> usize {
         let info = self.pixel_info();
         info.type_size * info.num_components
     }
+
+    pub fn is_compressed(&self) -> bool {
+        self.block_dimensions() != (1, 1)
+    }
+
+    /// The width and height in pixels of the blocks this format stores. Uncompressed formats
+    /// store blocks of a single pixel.
+    pub fn block_dimensions(&self) -> (u32, u32) {
+        match self {
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbSfloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => (4, 4),
+            _ => (1, 1),
+        }
+    }
+
+    /// The size of a block in bytes
+    pub fn block_size(&self) -> usize {
+        match self {
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm => 8,
+            TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbSfloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => 16,
+            _ => self.pixel_size(),
+        }
+    }
+
+    /// The number of blocks in each row and column of a texture of the given size
+    pub fn block_count(&self, size: Extent3d) -> (usize, usize) {
+        let (block_width, block_height) = self.block_dimensions();
+        // rounds up without overflowing for sizes close to u32::MAX
+        (
+            (size.width / block_width + (size.width % block_width != 0) as u32) as usize,
+            (size.height / block_height + (size.height % block_height != 0) as u32) as usize,
+        )
+    }
+
+    /// The number of bytes needed to store a texture of the given size, or `None` if it doesn't
+    /// fit in a `usize`
+    pub fn data_size(&self, size: Extent3d) -> Option<usize> {
+        let (columns, rows) = self.block_count(size);
+        columns
+            .checked_mul(rows)?
+            .checked_mul(size.depth as usize)?
+            .checked_mul(self.block_size())
+    }
 }
 
 impl Default for TextureFormat {
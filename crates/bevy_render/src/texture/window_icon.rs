@@ -0,0 +1,102 @@
+use super::{Texture, TextureFormat};
+use bevy_app::prelude::{EventReader, Events};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{Local, Res, ResMut};
+use bevy_utils::{tracing::warn, HashMap};
+use bevy_window::{WindowIcon, WindowId, Windows};
+
+/// The textures used as the icons of windows. Icons are set once their texture is loaded, and set
+/// again when it changes. Windows without an icon here keep the icon they were given with
+/// [Window::set_icon](bevy_window::Window::set_icon).
+#[derive(Debug, Clone, Default)]
+pub struct WindowIcons {
+    icons: HashMap<WindowId, Handle<Texture>>,
+}
+
+impl WindowIcons {
+    pub fn set(&mut self, window: WindowId, texture: Handle<Texture>) {
+        self.icons.insert(window, texture);
+    }
+
+    pub fn get(&self, window: WindowId) -> Option<&Handle<Texture>> {
+        self.icons.get(&window)
+    }
+
+    /// Removes the icon of a window, which gets the default icon of the platform back
+    pub fn remove(&mut self, window: WindowId) -> Option<Handle<Texture>> {
+        self.icons.remove(&window)
+    }
+}
+
+#[derive(Default)]
+pub struct WindowIconSystemState {
+    texture_event_reader: EventReader<AssetEvent<Texture>>,
+    /// The textures the icons of the windows were set from
+    applied: HashMap<WindowId, Handle<Texture>>,
+}
+
+/// Sets the icons of windows from their textures in the [WindowIcons]
+pub fn window_icon_system(
+    mut state: Local<WindowIconSystemState>,
+    window_icons: Res<WindowIcons>,
+    textures: Res<Assets<Texture>>,
+    texture_events: Res<Events<AssetEvent<Texture>>>,
+    mut windows: ResMut<Windows>,
+) {
+    let state = &mut *state;
+    for event in state.texture_event_reader.iter(&texture_events) {
+        if let AssetEvent::Modified { handle } = event {
+            // icons are converted again from modified textures
+            state.applied.retain(|_window, texture| texture != handle);
+        }
+    }
+
+    for (window_id, texture_handle) in window_icons.icons.iter() {
+        if state.applied.get(window_id) == Some(texture_handle) {
+            continue;
+        }
+        let (window, texture) = match (windows.get_mut(*window_id), textures.get(texture_handle)) {
+            (Some(window), Some(texture)) => (window, texture),
+            _ => continue,
+        };
+        state.applied.insert(*window_id, texture_handle.clone());
+        match window_icon(texture) {
+            Some(icon) => window.set_icon(Some(icon)),
+            None => warn!(
+                "Window icons can't be made from textures with the {:?} format",
+                texture.format
+            ),
+        }
+    }
+
+    let removed = state
+        .applied
+        .keys()
+        .filter(|window_id| window_icons.get(**window_id).is_none())
+        .cloned()
+        .collect::<Vec<_>>();
+    for window_id in removed {
+        state.applied.remove(&window_id);
+        if let Some(window) = windows.get_mut(window_id) {
+            window.set_icon(None);
+        }
+    }
+}
+
+/// Converts a texture with 8 bits per channel to the pixels of an icon
+fn window_icon(texture: &Texture) -> Option<WindowIcon> {
+    let rgba = match texture.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => texture.data.clone(),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => texture
+            .data
+            .chunks_exact(4)
+            .flat_map(|pixel| vec![pixel[2], pixel[1], pixel[0], pixel[3]])
+            .collect(),
+        _ => return None,
+    };
+    Some(WindowIcon {
+        rgba,
+        width: texture.size.width,
+        height: texture.size.height,
+    })
+}
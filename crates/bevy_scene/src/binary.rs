@@ -0,0 +1,129 @@
+use crate::{serde::SceneDeserializer, DynamicScene};
+use bevy_reflect::TypeRegistry;
+use serde::{de::DeserializeSeed, Serialize};
+use std::convert::TryInto;
+use thiserror::Error;
+
+/// The first bytes of every binary scene
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSCN";
+/// The binary scene format version written by [serialize_binary]
+pub const BINARY_SCENE_VERSION: u16 = 1;
+const HEADER_SIZE: usize = 6;
+
+/// An error that occurs when reading or writing a binary scene
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    #[error("Binary scene header is missing or invalid.")]
+    InvalidHeader,
+    #[error("Binary scene version {0} is not supported.")]
+    UnsupportedVersion(u16),
+    #[error("Failed to encode binary scene.")]
+    Encode(#[from] rmp_serde::encode::Error),
+    #[error("Failed to decode binary scene.")]
+    Decode(#[from] rmp_serde::decode::Error),
+}
+
+/// Returns true if `bytes` start with the binary scene header
+pub fn is_binary_scene(bytes: &[u8]) -> bool {
+    bytes.starts_with(&BINARY_SCENE_MAGIC)
+}
+
+/// Writes a versioned header followed by `serialize` encoded as MessagePack, which is a lot
+/// smaller and faster to parse than RON while staying self describing, as reflected components
+/// require.
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, BinarySceneError>
+where
+    S: Serialize,
+{
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&BINARY_SCENE_MAGIC);
+    buf.extend_from_slice(&BINARY_SCENE_VERSION.to_le_bytes());
+    let mut serializer = rmp_serde::Serializer::new(&mut buf).with_struct_map();
+    serialize.serialize(&mut serializer)?;
+    Ok(buf)
+}
+
+/// Reads a scene written by [DynamicScene::serialize_binary]
+pub fn deserialize_binary(
+    bytes: &[u8],
+    type_registry: &TypeRegistry,
+) -> Result<DynamicScene, BinarySceneError> {
+    if bytes.len() < HEADER_SIZE || !is_binary_scene(bytes) {
+        return Err(BinarySceneError::InvalidHeader);
+    }
+    let version = u16::from_le_bytes(bytes[4..HEADER_SIZE].try_into().unwrap());
+    if version != BINARY_SCENE_VERSION {
+        return Err(BinarySceneError::UnsupportedVersion(version));
+    }
+
+    let mut deserializer = rmp_serde::Deserializer::from_read_ref(&bytes[HEADER_SIZE..]);
+    let scene_deserializer = SceneDeserializer { type_registry };
+    Ok(scene_deserializer.deserialize(&mut deserializer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use uuid::Uuid;
+
+    #[derive(Reflect)]
+    struct Position {
+        x: f32,
+        label: String,
+    }
+
+    fn scene() -> DynamicScene {
+        DynamicScene {
+            entities: vec![Entity {
+                entity: 2,
+                uuid: Some(Uuid::new_v4()),
+                name: Some("player".to_string()),
+                components: vec![Box::new(Position {
+                    x: 1.5,
+                    label: "start".to_string(),
+                })],
+            }],
+        }
+    }
+
+    #[test]
+    fn binary_scenes_round_trip() {
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<f32>();
+        registry.write().register::<String>();
+        let scene = scene();
+
+        let bytes = scene.serialize_binary(&registry).unwrap();
+        assert!(is_binary_scene(&bytes));
+        let loaded = deserialize_binary(&bytes, &*registry.read()).unwrap();
+        assert_eq!(loaded.entities.len(), 1);
+        assert_eq!(loaded.entities[0].entity, 2);
+        assert_eq!(loaded.entities[0].uuid, scene.entities[0].uuid);
+        assert_eq!(loaded.entities[0].name.as_deref(), Some("player"));
+        let mut position = Position {
+            x: 0.0,
+            label: String::new(),
+        };
+        position.apply(&*loaded.entities[0].components[0]);
+        assert_eq!(position.x, 1.5);
+        assert_eq!(position.label, "start");
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        let registry = TypeRegistryArc::default();
+        let next_version = BINARY_SCENE_VERSION + 1;
+        let mut bytes = scene().serialize_binary(&registry).unwrap();
+        bytes[4..HEADER_SIZE].copy_from_slice(&next_version.to_le_bytes());
+        assert!(matches!(
+            deserialize_binary(&bytes, &*registry.read()),
+            Err(BinarySceneError::UnsupportedVersion(version)) if version == next_version
+        ));
+        assert!(matches!(
+            deserialize_binary(b"BSC", &*registry.read()),
+            Err(BinarySceneError::InvalidHeader)
+        ));
+    }
+}
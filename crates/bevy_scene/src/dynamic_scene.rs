@@ -1,9 +1,14 @@
-use crate::{serde::SceneSerializer, Scene};
+use crate::{serde::SceneSerializer, serialize_binary, BinarySceneError, Scene};
 use anyhow::Result;
-use bevy_ecs::{EntityMap, Resources, World};
-use bevy_reflect::{Reflect, ReflectComponent, ReflectMapEntities, TypeRegistryArc, TypeUuid};
-use serde::Serialize;
+use bevy_ecs::{Component, EntityMap, Resources, World};
+use bevy_reflect::{
+    Reflect, ReflectComponent, ReflectDeserialize, ReflectMapEntities, TypeRegistryArc, TypeUuid,
+};
+use bevy_utils::HashSet;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
 use thiserror::Error;
+use uuid::Uuid;
 
 #[derive(Error, Debug)]
 pub enum DynamicSceneToWorldError {
@@ -18,45 +23,36 @@ pub struct DynamicScene {
 }
 
 pub struct Entity {
+    /// The id other entities in the scene use to refer to this entity, for example in `Parent`
     pub entity: u32,
+    /// A stable identifier that survives merging and editing scene files. Spawned scene entities
+    /// are matched to their scene entity by this id when it is set, and scene files refer to the
+    /// entity by this id in the components of other entities.
+    pub uuid: Option<Uuid>,
+    /// A name used to target the entity with [SceneOverrides](crate::SceneOverrides)
+    pub name: Option<String>,
     pub components: Vec<Box<dyn Reflect>>,
 }
 
+/// The stable id of an entity spawned from a [DynamicScene] entity with a `uuid`.
+/// [DynamicScene::from_world] writes it back to the scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect_value(Hash, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EntityUuid(pub Uuid);
+
+/// The name of an entity spawned from a named [DynamicScene] entity.
+/// [DynamicScene::from_world] writes it back to the scene.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityName(pub String);
+
 impl DynamicScene {
     pub fn from_scene(scene: &Scene, type_registry: &TypeRegistryArc) -> Self {
         Self::from_world(&scene.world, type_registry)
     }
 
     pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
-        let mut scene = DynamicScene::default();
-        let type_registry = type_registry.read();
-        for archetype in world.archetypes() {
-            let mut entities = Vec::new();
-            for (index, entity) in archetype.iter_entities().enumerate() {
-                if index == entities.len() {
-                    entities.push(Entity {
-                        entity: entity.id(),
-                        components: Vec::new(),
-                    })
-                }
-                for type_info in archetype.types() {
-                    if let Some(registration) = type_registry.get(type_info.id()) {
-                        if let Some(reflect_component) = registration.data::<ReflectComponent>() {
-                            // SAFE: the index comes directly from a currently live component
-                            unsafe {
-                                let component =
-                                    reflect_component.reflect_component(&archetype, index);
-                                entities[index].components.push(component.clone_value());
-                            }
-                        }
-                    }
-                }
-            }
-
-            scene.entities.extend(entities.drain(..));
-        }
-
-        scene
+        DynamicSceneBuilder::new(world, type_registry).build()
     }
 
     pub fn write_to_world(
@@ -70,6 +66,14 @@ impl DynamicScene {
         for scene_entity in self.entities.iter() {
             let new_entity = world.reserve_entity();
             entity_map.insert(bevy_ecs::Entity::new(scene_entity.entity), new_entity);
+            if let Some(uuid) = scene_entity.uuid {
+                world.insert_one(new_entity, EntityUuid(uuid)).unwrap();
+            }
+            if let Some(name) = &scene_entity.name {
+                world
+                    .insert_one(new_entity, EntityName(name.clone()))
+                    .unwrap();
+            }
             for component in scene_entity.components.iter() {
                 let registration = type_registry
                     .get_with_name(component.type_name())
@@ -106,6 +110,15 @@ impl DynamicScene {
         serialize_ron(SceneSerializer::new(self, registry))
     }
 
+    /// Serializes the scene to the binary format, which is smaller and faster to load than RON.
+    /// Binary scenes are loaded by the same loader as RON scenes.
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, BinarySceneError> {
+        serialize_binary(SceneSerializer::new(self, registry))
+    }
+
     pub fn get_scene(&self, resources: &Resources) -> Result<Scene, DynamicSceneToWorldError> {
         let mut world = World::default();
         self.write_to_world(&mut world, resources)?;
@@ -113,6 +126,115 @@ impl DynamicScene {
     }
 }
 
+/// Builds a [DynamicScene] from some of the entities of a world, with some of their components.
+/// Entities referred to by the extracted components, like the `Parent` of a child, should be
+/// extracted with them for the scene to be spawned.
+///
+/// ```ignore
+/// let scene = DynamicSceneBuilder::new(&world, &type_registry)
+///     .with_entities(selected_entities)
+///     .deny::<GlobalTransform>()
+///     .build();
+/// ```
+pub struct DynamicSceneBuilder<'a> {
+    world: &'a World,
+    type_registry: &'a TypeRegistryArc,
+    entities: Option<HashSet<bevy_ecs::Entity>>,
+    allowed_components: Option<HashSet<TypeId>>,
+    denied_components: HashSet<TypeId>,
+}
+
+impl<'a> DynamicSceneBuilder<'a> {
+    /// A builder that extracts every entity of the world, with all their registered components
+    pub fn new(world: &'a World, type_registry: &'a TypeRegistryArc) -> Self {
+        DynamicSceneBuilder {
+            world,
+            type_registry,
+            entities: None,
+            allowed_components: None,
+            denied_components: HashSet::default(),
+        }
+    }
+
+    /// Only extracts the given entities. Calling it again adds to the entities.
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = bevy_ecs::Entity>) -> Self {
+        self.entities
+            .get_or_insert_with(HashSet::default)
+            .extend(entities);
+        self
+    }
+
+    /// Only extracts the allowed components, once one is allowed
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.allowed_components
+            .get_or_insert_with(HashSet::default)
+            .insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Doesn't extract the component
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.denied_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    fn extracts_component(&self, type_id: TypeId) -> bool {
+        !self.denied_components.contains(&type_id)
+            && self
+                .allowed_components
+                .as_ref()
+                .map_or(true, |allowed_components| {
+                    allowed_components.contains(&type_id)
+                })
+    }
+
+    pub fn build(self) -> DynamicScene {
+        let mut scene = DynamicScene::default();
+        let type_registry = self.type_registry.read();
+        for archetype in self.world.archetypes() {
+            for (index, entity) in archetype.iter_entities().enumerate() {
+                if let Some(entities) = &self.entities {
+                    if !entities.contains(entity) {
+                        continue;
+                    }
+                }
+                let mut scene_entity = Entity {
+                    entity: entity.id(),
+                    uuid: self
+                        .world
+                        .get::<EntityUuid>(*entity)
+                        .ok()
+                        .map(|uuid| uuid.0),
+                    name: self
+                        .world
+                        .get::<EntityName>(*entity)
+                        .ok()
+                        .map(|name| name.0.clone()),
+                    components: Vec::new(),
+                };
+                for type_info in archetype.types() {
+                    if !self.extracts_component(type_info.id()) {
+                        continue;
+                    }
+                    if let Some(registration) = type_registry.get(type_info.id()) {
+                        if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                            // SAFE: the index comes directly from a currently live component
+                            unsafe {
+                                let component =
+                                    reflect_component.reflect_component(&archetype, index);
+                                scene_entity.components.push(component.clone_value());
+                            }
+                        }
+                    }
+                }
+                scene.entities.push(scene_entity);
+            }
+        }
+
+        scene
+    }
+}
+
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
 where
     S: Serialize,
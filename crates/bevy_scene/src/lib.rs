@@ -1,22 +1,29 @@
+mod binary;
 mod command;
 mod dynamic_scene;
 mod scene;
 mod scene_loader;
+mod scene_overrides;
 mod scene_spawner;
 pub mod serde;
 
+pub use binary::*;
 pub use command::*;
 pub use dynamic_scene::*;
 pub use scene::*;
 pub use scene_loader::*;
+pub use scene_overrides::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
-    pub use crate::{DynamicScene, Scene, SceneSpawner, SpawnSceneCommands};
+    pub use crate::{
+        DynamicScene, DynamicSceneBuilder, Scene, SceneOverrides, SceneSpawner, SpawnSceneCommands,
+    };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+use bevy_reflect::RegisterTypeBuilder;
 
 #[derive(Default)]
 pub struct ScenePlugin;
@@ -28,6 +35,7 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .register_type::<EntityUuid>()
             .init_resource::<SceneSpawner>()
             .add_stage_after(stage::EVENT, SCENE_STAGE)
             .add_system_to_stage(SCENE_STAGE, scene_spawner_system);
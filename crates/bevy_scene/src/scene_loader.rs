@@ -1,9 +1,9 @@
-use crate::serde::SceneDeserializer;
+use crate::{deserialize_binary, is_binary_scene, serde::SceneDeserializer, DynamicScene};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::{FromResources, Resources};
-use bevy_reflect::TypeRegistryArc;
-use bevy_utils::BoxedFuture;
+use bevy_reflect::{TypeRegistryArc, TypeUuid};
+use bevy_utils::{BoxedFuture, Uuid};
 use serde::de::DeserializeSeed;
 
 #[derive(Debug)]
@@ -27,17 +27,26 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
-            let scene_deserializer = SceneDeserializer {
-                type_registry: &*self.type_registry.read(),
+            let type_registry = self.type_registry.read();
+            let scene = if is_binary_scene(bytes) {
+                deserialize_binary(bytes, &*type_registry)?
+            } else {
+                let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+                let scene_deserializer = SceneDeserializer {
+                    type_registry: &*type_registry,
+                };
+                scene_deserializer.deserialize(&mut deserializer)?
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
             load_context.set_default_asset(LoadedAsset::new(scene));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn"]
+        &["scn", "scnb"]
+    }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(DynamicScene::TYPE_UUID)
     }
 }
@@ -0,0 +1,40 @@
+use bevy_reflect::Reflect;
+
+/// Component values applied to named entities of a [DynamicScene](crate::DynamicScene) when it is
+/// spawned, so one scene file can be instantiated many times with different values.
+///
+/// An override replaces the matching component, or adds it if the entity doesn't have one. Pass a
+/// [DynamicStruct](bevy_reflect::DynamicStruct) named after the component type to only change
+/// some of its fields.
+#[derive(Default)]
+pub struct SceneOverrides {
+    overrides: Vec<(String, Box<dyn Reflect>)>,
+}
+
+impl SceneOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `component` to every scene entity named `entity_name`
+    pub fn with<T: Reflect>(mut self, entity_name: &str, component: T) -> Self {
+        self.add(entity_name, Box::new(component));
+        self
+    }
+
+    pub fn add(&mut self, entity_name: &str, component: Box<dyn Reflect>) {
+        self.overrides.push((entity_name.to_string(), component));
+    }
+
+    /// Returns the overrides for the scene entity named `entity_name`
+    pub fn get<'a>(&'a self, entity_name: &'a str) -> impl Iterator<Item = &'a dyn Reflect> + 'a {
+        self.overrides
+            .iter()
+            .filter(move |(name, _)| name == entity_name)
+            .map(|(_, component)| &**component)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
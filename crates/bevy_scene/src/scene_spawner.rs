@@ -1,15 +1,23 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicScene, EntityName, EntityUuid, Scene, SceneOverrides};
 use bevy_app::prelude::*;
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{EntityMap, Resources, World};
-use bevy_reflect::{ReflectComponent, ReflectMapEntities, TypeRegistryArc};
+use bevy_reflect::{Reflect, ReflectComponent, ReflectMapEntities, TypeRegistry, TypeRegistryArc};
 use bevy_utils::HashMap;
+use std::any::TypeId;
 use thiserror::Error;
 use uuid::Uuid;
 
 #[derive(Debug)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    /// Spawned entities of scene entities with a stable id. These stay matched when a hot
+    /// reloaded scene renumbers its entities.
+    uuid_map: HashMap<Uuid, bevy_ecs::Entity>,
+    /// The components the scene wrote to each spawned entity, which are removed when a hot
+    /// reloaded scene no longer has them
+    components: HashMap<bevy_ecs::Entity, Vec<TypeId>>,
+    overrides: SceneOverrides,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -27,7 +35,7 @@ pub struct SceneSpawner {
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: EventReader<AssetEvent<DynamicScene>>,
-    dynamic_scenes_to_spawn: Vec<Handle<DynamicScene>>,
+    dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, SceneOverrides)>,
     scenes_to_spawn: Vec<Handle<Scene>>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
 }
@@ -44,7 +52,16 @@ pub enum SceneSpawnError {
 
 impl SceneSpawner {
     pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
-        self.dynamic_scenes_to_spawn.push(scene_handle);
+        self.spawn_dynamic_with_overrides(scene_handle, SceneOverrides::default());
+    }
+
+    /// Spawns a new instance of the scene, applying `overrides` to its named entities
+    pub fn spawn_dynamic_with_overrides(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        overrides: SceneOverrides,
+    ) {
+        self.dynamic_scenes_to_spawn.push((scene_handle, overrides));
     }
 
     pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
@@ -79,10 +96,28 @@ impl SceneSpawner {
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
+    ) -> Result<(), SceneSpawnError> {
+        self.spawn_dynamic_with_overrides_sync(
+            world,
+            resources,
+            scene_handle,
+            SceneOverrides::default(),
+        )
+    }
+
+    pub fn spawn_dynamic_with_overrides_sync(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: &Handle<DynamicScene>,
+        overrides: SceneOverrides,
     ) -> Result<(), SceneSpawnError> {
         let instance_id = InstanceId::new();
         let mut instance_info = InstanceInfo {
             entity_map: EntityMap::default(),
+            uuid_map: HashMap::default(),
+            components: HashMap::default(),
+            overrides,
         };
         Self::spawn_dynamic_internal(world, resources, scene_handle, &mut instance_info)?;
         self.spawned_instances.insert(instance_id, instance_info);
@@ -109,36 +144,129 @@ impl SceneSpawner {
                 handle: scene_handle.clone_weak(),
             })?;
 
+        let mut components = HashMap::<bevy_ecs::Entity, Vec<TypeId>>::default();
         for scene_entity in scene.entities.iter() {
-            let entity = *instance_info
-                .entity_map
-                // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
-                .entry(bevy_ecs::Entity::new(scene_entity.entity))
-                .or_insert_with(|| world.reserve_entity());
+            // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
+            let scene_entity_id = bevy_ecs::Entity::new(scene_entity.entity);
+            let entity = if let Some(uuid) = scene_entity.uuid {
+                let entity = *instance_info
+                    .uuid_map
+                    .entry(uuid)
+                    .or_insert_with(|| world.reserve_entity());
+                instance_info.entity_map.insert(scene_entity_id, entity);
+                if world.get::<EntityUuid>(entity).is_err() {
+                    world.insert_one(entity, EntityUuid(uuid)).unwrap();
+                }
+                entity
+            } else {
+                *instance_info
+                    .entity_map
+                    .entry(scene_entity_id)
+                    .or_insert_with(|| world.reserve_entity())
+            };
+            let entity_components = components.entry(entity).or_insert_with(Vec::new);
             for component in scene_entity.components.iter() {
-                let registration = type_registry
-                    .get_with_name(component.type_name())
-                    .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
-                        type_name: component.type_name().to_string(),
-                    })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_name: component.type_name().to_string(),
+                entity_components.push(Self::write_component(
+                    world,
+                    resources,
+                    &type_registry,
+                    entity,
+                    &**component,
+                )?);
+            }
+
+            if let Some(name) = &scene_entity.name {
+                if world.get::<EntityName>(entity).is_err() {
+                    world.insert_one(entity, EntityName(name.clone())).unwrap();
+                }
+                for component in instance_info.overrides.get(name) {
+                    entity_components.push(Self::write_component(
+                        world,
+                        resources,
+                        &type_registry,
+                        entity,
+                        component,
+                    )?);
+                }
+            }
+        }
+
+        // entities and components removed from a hot reloaded scene are removed from the instance,
+        // and the other entities of the world are left as they are
+        for (entity, previous_components) in instance_info.components.drain() {
+            match components.get(&entity) {
+                Some(entity_components) => {
+                    for type_id in previous_components {
+                        if entity_components.contains(&type_id) {
+                            continue;
+                        }
+                        if let Some(reflect_component) = type_registry
+                            .get(type_id)
+                            .and_then(|registration| registration.data::<ReflectComponent>())
+                        {
+                            reflect_component.remove_component(world, entity);
                         }
-                    })?;
-                if world.has_component_type(entity, registration.type_id()) {
-                    if registration.short_name() != "Camera" {
-                        reflect_component.apply_component(world, entity, &**component);
                     }
-                } else {
-                    reflect_component.add_component(world, resources, entity, &**component);
                 }
+                None => {
+                    let _ = world.despawn(entity);
+                }
+            }
+        }
+        let removed_scene_entities = instance_info
+            .entity_map
+            .keys()
+            .filter(|scene_entity| {
+                let entity = instance_info.entity_map.get(*scene_entity).unwrap();
+                !components.contains_key(&entity)
+            })
+            .collect::<Vec<_>>();
+        for scene_entity in removed_scene_entities {
+            instance_info.entity_map.remove(scene_entity);
+        }
+        instance_info
+            .uuid_map
+            .retain(|_uuid, entity| components.contains_key(entity));
+
+        let entities = components.keys().cloned().collect::<Vec<_>>();
+        for registration in type_registry.iter() {
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect
+                    .map_specific_entities(world, &instance_info.entity_map, &entities)
+                    .unwrap();
             }
         }
+        instance_info.components = components;
         Ok(())
     }
 
+    fn write_component(
+        world: &mut World,
+        resources: &Resources,
+        type_registry: &TypeRegistry,
+        entity: bevy_ecs::Entity,
+        component: &dyn Reflect,
+    ) -> Result<TypeId, SceneSpawnError> {
+        let registration = type_registry
+            .get_with_name(component.type_name())
+            .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                type_name: component.type_name().to_string(),
+            })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            SceneSpawnError::UnregisteredComponent {
+                type_name: component.type_name().to_string(),
+            }
+        })?;
+        if world.has_component_type(entity, registration.type_id()) {
+            if registration.short_name() != "Camera" {
+                reflect_component.apply_component(world, entity, component);
+            }
+        } else {
+            reflect_component.add_component(world, resources, entity, component);
+        }
+        Ok(registration.type_id())
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
@@ -148,6 +276,9 @@ impl SceneSpawner {
         let instance_id = InstanceId::new();
         let mut instance_info = InstanceInfo {
             entity_map: EntityMap::default(),
+            uuid_map: HashMap::default(),
+            components: HashMap::default(),
+            overrides: SceneOverrides::default(),
         };
         let type_registry = resources.get::<TypeRegistryArc>().unwrap();
         let type_registry = type_registry.read();
@@ -235,13 +366,16 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_dynamic_sync(world, resources, &scene_handle) {
-                Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn.push(scene_handle)
-                }
-                Err(err) => return Err(err),
+        for (scene_handle, overrides) in scenes_to_spawn {
+            let scene_loaded = resources
+                .get::<Assets<DynamicScene>>()
+                .unwrap()
+                .get(&scene_handle)
+                .is_some();
+            if scene_loaded {
+                self.spawn_dynamic_with_overrides_sync(world, resources, &scene_handle, overrides)?;
+            } else {
+                self.dynamic_scenes_to_spawn.push((scene_handle, overrides));
             }
         }
 
@@ -283,3 +417,66 @@ pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
         .update_spawned_scenes(world, resources, &updated_spawned_scenes)
         .unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Entity, ScenePlugin};
+    use bevy_asset::AssetPlugin;
+    use bevy_reflect::{Reflect, RegisterTypeBuilder};
+    use bevy_tasks::{IoTaskPool, TaskPool};
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    fn scene_entity(entity: u32, name: &str) -> Entity {
+        Entity {
+            entity,
+            uuid: None,
+            name: Some(name.to_string()),
+            components: vec![Box::new(Health { value: 10.0 })],
+        }
+    }
+
+    #[test]
+    fn overrides_are_applied_to_named_entities() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(IoTaskPool(TaskPool::new()))
+            .add_plugin(AssetPlugin)
+            .add_plugin(ScenePlugin)
+            .register_type::<Health>();
+        let App {
+            world, resources, ..
+        } = &mut app_builder.app;
+
+        let scene_handle = resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(DynamicScene {
+                entities: vec![scene_entity(0, "enemy"), scene_entity(1, "ally")],
+            });
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_dynamic_with_overrides_sync(
+                world,
+                resources,
+                &scene_handle,
+                SceneOverrides::new().with("enemy", Health { value: 50.0 }),
+            )
+            .unwrap();
+
+        let mut health = world
+            .query::<(&EntityName, &Health)>()
+            .map(|(name, health)| (name.0.clone(), health.value))
+            .collect::<Vec<_>>();
+        health.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            health,
+            vec![("ally".to_string(), 10.0), ("enemy".to_string(), 50.0)]
+        );
+    }
+}
@@ -1,14 +1,17 @@
-use crate::{DynamicScene, Entity};
+use crate::{DynamicScene, Entity, EntityUuid};
 use anyhow::Result;
 use bevy_reflect::{
     serde::{ReflectDeserializer, ReflectSerializer},
-    Reflect, TypeRegistry, TypeRegistryArc,
+    DynamicList, DynamicMap, DynamicStruct, DynamicTupleStruct, Reflect, ReflectRef, TypeRegistry,
+    TypeRegistryArc,
 };
+use bevy_utils::HashMap;
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::{SerializeSeq, SerializeStruct},
     Deserialize, Serialize,
 };
+use uuid::Uuid;
 
 pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
@@ -26,10 +29,17 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let entity_uuids = self
+            .scene
+            .entities
+            .iter()
+            .filter_map(|entity| entity.uuid.map(|uuid| (entity.entity, uuid)))
+            .collect::<HashMap<_, _>>();
         let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
         for entity in self.scene.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
+                entity_uuids: &entity_uuids,
                 registry: self.registry,
             })?;
         }
@@ -39,6 +49,8 @@ impl<'a> Serialize for SceneSerializer<'a> {
 
 pub struct EntitySerializer<'a> {
     pub entity: &'a Entity,
+    /// The uuids of the scene entities that have one, by scene entity id
+    pub entity_uuids: &'a HashMap<u32, Uuid>,
     pub registry: &'a TypeRegistryArc,
 }
 
@@ -47,12 +59,21 @@ impl<'a> Serialize for EntitySerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 2)?;
+        let field_count =
+            2 + self.entity.uuid.is_some() as usize + self.entity.name.is_some() as usize;
+        let mut state = serializer.serialize_struct(ENTITY_STRUCT, field_count)?;
         state.serialize_field(ENTITY_FIELD_ENTITY, &self.entity.entity)?;
+        if let Some(uuid) = &self.entity.uuid {
+            state.serialize_field(ENTITY_FIELD_UUID, uuid)?;
+        }
+        if let Some(name) = &self.entity.name {
+            state.serialize_field(ENTITY_FIELD_NAME, name)?;
+        }
         state.serialize_field(
             ENTITY_FIELD_COMPONENTS,
             &ComponentsSerializer {
                 components: &self.entity.components,
+                entity_uuids: self.entity_uuids,
                 registry: self.registry,
             },
         )?;
@@ -60,8 +81,10 @@ impl<'a> Serialize for EntitySerializer<'a> {
     }
 }
 
+/// Serializes components, writing references to scene entities with a uuid as the uuid
 pub struct ComponentsSerializer<'a> {
     pub components: &'a [Box<dyn Reflect>],
+    pub entity_uuids: &'a HashMap<u32, Uuid>,
     pub registry: &'a TypeRegistryArc,
 }
 
@@ -72,10 +95,16 @@ impl<'a> Serialize for ComponentsSerializer<'a> {
     {
         let mut state = serializer.serialize_seq(Some(self.components.len()))?;
         for component in self.components.iter() {
-            state.serialize_element(&ReflectSerializer::new(
-                &**component,
-                &*self.registry.read(),
-            ))?;
+            let component = map_values(&**component, &mut |value: &dyn Reflect| {
+                Ok::<_, S::Error>(
+                    value
+                        .downcast_ref::<bevy_ecs::Entity>()
+                        .and_then(|entity| self.entity_uuids.get(&entity.id()))
+                        .map(|uuid| Box::new(EntityUuid(*uuid)) as Box<dyn Reflect>),
+                )
+            })?;
+            state
+                .serialize_element(&ReflectSerializer::new(&*component, &*self.registry.read()))?;
         }
         state.end()
     }
@@ -92,11 +121,36 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
-                type_registry: self.type_registry,
-            })?,
-        })
+        let mut entities = deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
+        })?;
+
+        // entity references written as uuids are mapped back to the scene entity with the uuid
+        let scene_entities = entities
+            .iter()
+            .filter_map(|entity| entity.uuid.map(|uuid| (uuid, entity.entity)))
+            .collect::<HashMap<_, _>>();
+        let mut map_entity_uuid =
+            |value: &dyn Reflect| -> Result<Option<Box<dyn Reflect>>, D::Error> {
+                let uuid = match value.downcast_ref::<EntityUuid>() {
+                    Some(uuid) => uuid.0,
+                    None => return Ok(None),
+                };
+                match scene_entities.get(&uuid) {
+                    Some(entity) => Ok(Some(Box::new(bevy_ecs::Entity::new(*entity)))),
+                    None => Err(Error::custom(format!(
+                        "Scene has no entity with the uuid {}.",
+                        uuid
+                    ))),
+                }
+            };
+        for entity in entities.iter_mut() {
+            for component in entity.components.iter_mut() {
+                *component = map_values(&**component, &mut map_entity_uuid)?;
+            }
+        }
+
+        Ok(DynamicScene { entities })
     }
 }
 
@@ -139,7 +193,12 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             ENTITY_STRUCT,
-            &[ENTITY_FIELD_ENTITY, ENTITY_FIELD_COMPONENTS],
+            &[
+                ENTITY_FIELD_ENTITY,
+                ENTITY_FIELD_UUID,
+                ENTITY_FIELD_NAME,
+                ENTITY_FIELD_COMPONENTS,
+            ],
             SceneEntityVisitor {
                 registry: self.type_registry,
             },
@@ -151,11 +210,15 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneEntityDeserializer<'a> {
 #[serde(field_identifier, rename_all = "lowercase")]
 enum EntityField {
     Entity,
+    Uuid,
+    Name,
     Components,
 }
 
 pub const ENTITY_STRUCT: &str = "Entity";
 pub const ENTITY_FIELD_ENTITY: &str = "entity";
+pub const ENTITY_FIELD_UUID: &str = "uuid";
+pub const ENTITY_FIELD_NAME: &str = "name";
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
 struct SceneEntityVisitor<'a> {
@@ -174,6 +237,8 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
         A: MapAccess<'de>,
     {
         let mut id = None;
+        let mut uuid = None;
+        let mut name = None;
         let mut components = None;
         while let Some(key) = map.next_key()? {
             match key {
@@ -183,6 +248,18 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
                     }
                     id = Some(map.next_value::<u32>()?);
                 }
+                EntityField::Uuid => {
+                    if uuid.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_UUID));
+                    }
+                    uuid = Some(map.next_value::<Uuid>()?);
+                }
+                EntityField::Name => {
+                    if name.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_NAME));
+                    }
+                    name = Some(map.next_value::<String>()?);
+                }
                 EntityField::Components => {
                     if components.is_some() {
                         return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
@@ -204,6 +281,8 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
         Ok(Entity {
             entity: *entity,
+            uuid,
+            name,
             components,
         })
     }
@@ -249,3 +328,117 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+/// Clones `value`, replacing the values for which `map_value` returns a new value
+fn map_values<E>(
+    value: &dyn Reflect,
+    map_value: &mut impl FnMut(&dyn Reflect) -> Result<Option<Box<dyn Reflect>>, E>,
+) -> Result<Box<dyn Reflect>, E> {
+    Ok(match value.reflect_ref() {
+        ReflectRef::Struct(struct_value) => {
+            let mut dynamic_struct = DynamicStruct::default();
+            dynamic_struct.set_name(struct_value.type_name().to_string());
+            for (index, field) in struct_value.iter_fields().enumerate() {
+                let name = struct_value.name_at(index).unwrap();
+                dynamic_struct.insert_boxed(name, map_values(field, map_value)?);
+            }
+            Box::new(dynamic_struct)
+        }
+        ReflectRef::TupleStruct(tuple_struct) => {
+            let mut dynamic_tuple_struct = DynamicTupleStruct::default();
+            dynamic_tuple_struct.set_name(tuple_struct.type_name().to_string());
+            for field in tuple_struct.iter_fields() {
+                dynamic_tuple_struct.insert_boxed(map_values(field, map_value)?);
+            }
+            Box::new(dynamic_tuple_struct)
+        }
+        ReflectRef::List(list) => {
+            let mut dynamic_list = DynamicList::default();
+            for item in list.iter() {
+                dynamic_list.push_box(map_values(item, map_value)?);
+            }
+            Box::new(dynamic_list)
+        }
+        ReflectRef::Map(map) => {
+            let mut dynamic_map = DynamicMap::default();
+            for (key, item) in map.iter() {
+                dynamic_map.insert_boxed(key.clone_value(), map_values(item, map_value)?);
+            }
+            Box::new(dynamic_map)
+        }
+        ReflectRef::Value(_) => match map_value(value)? {
+            Some(value) => value,
+            None => value.clone_value(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize_ron;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect)]
+    struct Reference {
+        entity: bevy_ecs::Entity,
+    }
+
+    #[test]
+    fn entity_references_are_written_as_uuids() {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<bevy_ecs::Entity>();
+            registry.register::<EntityUuid>();
+        }
+        let uuid = Uuid::new_v4();
+        let scene = DynamicScene {
+            entities: vec![
+                Entity {
+                    entity: 7,
+                    uuid: Some(uuid),
+                    name: None,
+                    components: Vec::new(),
+                },
+                Entity {
+                    entity: 8,
+                    uuid: None,
+                    name: None,
+                    components: vec![Box::new(Reference {
+                        entity: bevy_ecs::Entity::new(7),
+                    })],
+                },
+            ],
+        };
+
+        let ron = serialize_ron(SceneSerializer::new(&scene, &registry)).unwrap();
+        assert_eq!(ron.matches(&uuid.to_string()).count(), 2);
+
+        // the uuid is mapped back to the scene entity, which is renumbered in the file
+        let ron = ron.replace("entity: 7", "entity: 3");
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &*registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        let mut reference = Reference {
+            entity: bevy_ecs::Entity::new(0),
+        };
+        reference.apply(&*scene.entities[1].components[0]);
+        assert_eq!(reference.entity, bevy_ecs::Entity::new(3));
+
+        // references to uuids that aren't in the scene are rejected
+        let ron = ron.replace(
+            &format!("uuid: \"{}\"", uuid),
+            &format!("uuid: \"{}\"", Uuid::new_v4()),
+        );
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        assert!(SceneDeserializer {
+            type_registry: &*registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .is_err());
+    }
+}
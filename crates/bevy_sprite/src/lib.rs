@@ -29,6 +29,7 @@ use bevy_asset::{AddAsset, Assets, Handle};
 use bevy_math::Vec2;
 use bevy_reflect::{RegisterTypeBuilder, TypeUuid};
 use bevy_render::{
+    camera::no_frustum_culling_system,
     mesh::{shape, Mesh},
     render_graph::RenderGraph,
     shader::asset_shader_defs_system,
@@ -46,6 +47,12 @@ impl Plugin for SpritePlugin {
             .add_asset::<TextureAtlas>()
             .register_type::<Sprite>()
             .add_system_to_stage(stage::POST_UPDATE, sprite_system)
+            // sprites scale the quad mesh in their shaders
+            .add_system_to_stage(stage::POST_UPDATE, no_frustum_culling_system::<Sprite>)
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                no_frustum_culling_system::<TextureAtlasSprite>,
+            )
             .add_system_to_stage(
                 stage::POST_UPDATE,
                 asset_shader_defs_system::<ColorMaterial>,
@@ -1,6 +1,9 @@
 #version 450
 
 layout(location = 0) in vec2 v_Uv;
+# ifdef MESH_VERTEX_COLOR
+layout(location = 1) in vec4 v_Color;
+# endif
 
 layout(location = 0) out vec4 o_Target;
 
@@ -19,6 +22,9 @@ void main() {
     color *= texture(
         sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler),
         v_Uv);
+# endif
+# ifdef MESH_VERTEX_COLOR
+    color *= v_Color;
 # endif
     o_Target = color;
 }
@@ -6,6 +6,11 @@ layout(location = 2) in vec2 Vertex_Uv;
 
 layout(location = 0) out vec2 v_Uv;
 
+# ifdef MESH_VERTEX_COLOR
+layout(location = 3) in vec4 Vertex_Color;
+layout(location = 1) out vec4 v_Color;
+# endif
+
 layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
 };
@@ -19,6 +24,9 @@ layout(set = 2, binding = 1) uniform Sprite_size {
 
 void main() {
     v_Uv = Vertex_Uv;
+# ifdef MESH_VERTEX_COLOR
+    v_Color = Vertex_Color;
+# endif
     vec3 position = Vertex_Position * vec3(size, 1.0);
     gl_Position = ViewProj * Model * vec4(position, 1.0);
 }
\ No newline at end of file
@@ -0,0 +1,228 @@
+use ab_glyph::{point, Font as _, FontArc, Glyph, GlyphId, PxScale, Rect, ScaleFont as _};
+use bevy_render::texture::{Extent3d, Texture, TextureDimension, TextureFormat};
+use image::{imageops::FilterType, ImageFormat};
+
+/// Draws a glyph that has colors in the font, from the layers of its `COLR` table or from the
+/// bitmaps of its `CBDT` or `sbix` table. Returns the texture of the glyph and its bounds in
+/// pixels relative to the glyph origin, or nothing if the glyph has no colors.
+///
+/// Layers drawn in the color of the text use white, as color glyphs are not tinted.
+pub(crate) fn color_glyph_texture(
+    font: &FontArc,
+    font_data: &[u8],
+    glyph_id: GlyphId,
+    scale: PxScale,
+) -> Option<(Texture, Rect)> {
+    colr_glyph_texture(font, font_data, glyph_id, scale)
+        .or_else(|| bitmap_glyph_texture(font, font_data, glyph_id, scale))
+}
+
+fn colr_glyph_texture(
+    font: &FontArc,
+    font_data: &[u8],
+    glyph_id: GlyphId,
+    scale: PxScale,
+) -> Option<(Texture, Rect)> {
+    let colr = find_table(font_data, b"COLR")?;
+    let cpal = find_table(font_data, b"CPAL")?;
+
+    // the base glyph records are sorted by glyph id
+    let base_glyph_count = read_u16(colr, 2)? as usize;
+    let base_glyphs_offset = read_u32(colr, 4)? as usize;
+    let layers_offset = read_u32(colr, 8)? as usize;
+    let mut low = 0;
+    let mut high = base_glyph_count;
+    let (first_layer, layer_count) = loop {
+        if low >= high {
+            return None;
+        }
+        let middle = (low + high) / 2;
+        let record = base_glyphs_offset + middle * 6;
+        let id = read_u16(colr, record)?;
+        if id < glyph_id.0 {
+            low = middle + 1;
+        } else if id > glyph_id.0 {
+            high = middle;
+        } else {
+            break (
+                read_u16(colr, record + 2)? as usize,
+                read_u16(colr, record + 4)? as usize,
+            );
+        }
+    };
+
+    // the colors of the first palette
+    let palette_size = read_u16(cpal, 2)?;
+    let colors_offset = read_u32(cpal, 8)? as usize;
+    let first_color = read_u16(cpal, 12)? as usize;
+    let layer_color = |palette_index: u16| -> Option<[f32; 4]> {
+        if palette_index == 0xFFFF || palette_index >= palette_size {
+            return Some([1.0, 1.0, 1.0, 1.0]);
+        }
+        // colors are stored as blue, green, red and alpha
+        let color = colors_offset + (first_color + palette_index as usize) * 4;
+        let channel = |index| cpal.get(color + index).map(|value| *value as f32 / 255.0);
+        Some([channel(2)?, channel(1)?, channel(0)?, channel(3)?])
+    };
+
+    let mut layers = Vec::with_capacity(layer_count);
+    for layer in first_layer..first_layer + layer_count {
+        let record = layers_offset + layer * 4;
+        let layer_glyph = Glyph {
+            id: GlyphId(read_u16(colr, record)?),
+            scale,
+            position: point(0.0, 0.0),
+        };
+        if let Some(outlined_glyph) = font.outline_glyph(layer_glyph) {
+            layers.push((outlined_glyph, layer_color(read_u16(colr, record + 2)?)?));
+        }
+    }
+    let first = layers.first()?.0.px_bounds();
+    let bounds = layers
+        .iter()
+        .fold(first, |bounds, (outlined_glyph, _color)| {
+            let layer_bounds = outlined_glyph.px_bounds();
+            Rect {
+                min: point(
+                    bounds.min.x.min(layer_bounds.min.x),
+                    bounds.min.y.min(layer_bounds.min.y),
+                ),
+                max: point(
+                    bounds.max.x.max(layer_bounds.max.x),
+                    bounds.max.y.max(layer_bounds.max.y),
+                ),
+            }
+        });
+
+    // the layers are drawn from the bottom up, with premultiplied alpha
+    let width = bounds.width() as usize;
+    let height = bounds.height() as usize;
+    let mut pixels = vec![[0.0f32; 4]; width * height];
+    for (outlined_glyph, color) in layers.iter() {
+        let layer_bounds = outlined_glyph.px_bounds();
+        let offset_x = (layer_bounds.min.x - bounds.min.x) as usize;
+        let offset_y = (layer_bounds.min.y - bounds.min.y) as usize;
+        outlined_glyph.draw(|x, y, coverage| {
+            let index = (offset_y + y as usize) * width + offset_x + x as usize;
+            if let Some(pixel) = pixels.get_mut(index) {
+                let alpha = color[3] * coverage;
+                for channel in 0..3 {
+                    pixel[channel] = color[channel] * alpha + pixel[channel] * (1.0 - alpha);
+                }
+                pixel[3] = alpha + pixel[3] * (1.0 - alpha);
+            }
+        });
+    }
+    let data = pixels
+        .iter()
+        .flat_map(|pixel| {
+            let alpha = pixel[3];
+            let straight = |value: f32| {
+                if alpha > 0.0 {
+                    (value / alpha * 255.0) as u8
+                } else {
+                    0
+                }
+            };
+            vec![
+                straight(pixel[0]),
+                straight(pixel[1]),
+                straight(pixel[2]),
+                (alpha * 255.0) as u8,
+            ]
+        })
+        .collect();
+    let texture = Texture::new(
+        Extent3d::new(width as u32, height as u32, 1),
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    Some((texture, bounds))
+}
+
+fn bitmap_glyph_texture(
+    font: &FontArc,
+    font_data: &[u8],
+    glyph_id: GlyphId,
+    scale: PxScale,
+) -> Option<(Texture, Rect)> {
+    let face = ttf_parser::Face::from_slice(font_data, 0).ok()?;
+    // the bitmaps are drawn at a number of pixels per em, while ab_glyph scales fonts by their
+    // height
+    let pixels_per_em =
+        font.as_scaled(scale).h_scale_factor() * font.units_per_em().unwrap_or(1000.0);
+    let image = face.glyph_raster_image(
+        ttf_parser::GlyphId(glyph_id.0),
+        pixels_per_em.ceil().min(u16::MAX as f32) as u16,
+    )?;
+    if image.format != ttf_parser::RasterImageFormat::PNG || image.pixels_per_em == 0 {
+        return None;
+    }
+    let bitmap = image::load_from_memory_with_format(image.data, ImageFormat::Png)
+        .ok()?
+        .into_rgba8();
+
+    let bitmap_scale = pixels_per_em / image.pixels_per_em as f32;
+    let width = ((image.width as f32 * bitmap_scale).round() as u32).max(1);
+    let height = ((image.height as f32 * bitmap_scale).round() as u32).max(1);
+    let bitmap = image::imageops::resize(&bitmap, width, height, FilterType::Triangle);
+    // the offset is from the glyph origin to the bottom left of the bitmap, with y up
+    let left = image.x as f32 * bitmap_scale;
+    let bottom = -image.y as f32 * bitmap_scale;
+    let bounds = Rect {
+        min: point(left, bottom - height as f32),
+        max: point(left + width as f32, bottom),
+    };
+    let texture = Texture::new(
+        Extent3d::new(width, height, 1),
+        TextureDimension::D2,
+        bitmap.into_raw(),
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    Some((texture, bounds))
+}
+
+/// Returns the data of the table with the given tag in an OpenType font
+fn find_table<'a>(font_data: &'a [u8], tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let table_count = read_u16(font_data, 4)? as usize;
+    (0..table_count).find_map(|index| {
+        let record = 12 + index * 16;
+        if font_data.get(record..record + 4)? != tag {
+            return None;
+        }
+        let offset = read_u32(font_data, record + 8)? as usize;
+        let length = read_u32(font_data, record + 12)? as usize;
+        font_data.get(offset..offset + length)
+    })
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_table;
+
+    #[test]
+    fn tables_are_found_by_tag() {
+        let mut font_data = vec![0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0];
+        // table records: tag, checksum, offset and length
+        font_data.extend_from_slice(b"CPAL");
+        font_data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 44, 0, 0, 0, 2]);
+        font_data.extend_from_slice(b"COLR");
+        font_data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 46, 0, 0, 0, 3]);
+        font_data.extend_from_slice(&[1, 2, 3, 4, 5]);
+
+        assert_eq!(find_table(&font_data, b"CPAL"), Some(&[1, 2][..]));
+        assert_eq!(find_table(&font_data, b"COLR"), Some(&[3, 4, 5][..]));
+        assert_eq!(find_table(&font_data, b"CBDT"), None);
+    }
+}
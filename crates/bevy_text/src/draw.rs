@@ -1,4 +1,5 @@
-use bevy_math::{Mat4, Vec3};
+use bevy_asset::Handle;
+use bevy_math::{Mat4, Quat, Vec3, Vec4};
 use bevy_render::{
     color::Color,
     draw::{Draw, DrawContext, DrawError, Drawable},
@@ -13,7 +14,7 @@ use bevy_render::{
 use bevy_sprite::TextureAtlasSprite;
 use glyph_brush_layout::{HorizontalAlign, VerticalAlign};
 
-use crate::PositionedGlyph;
+use crate::{Font, PositionedGlyph, TEXT_PIPELINE_HANDLE, TEXT_SDF_PIPELINE_HANDLE};
 
 #[derive(Debug, Clone, Copy)]
 pub struct TextAlignment {
@@ -30,11 +31,68 @@ impl Default for TextAlignment {
     }
 }
 
+/// How text that is wider than its bounds is broken into lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextWrap {
+    /// Breaks lines between words. Words longer than a line overflow it.
+    Word,
+    /// Breaks lines between any two characters
+    Character,
+    /// Only breaks lines at line breaks in the text
+    NoWrap,
+}
+
+impl Default for TextWrap {
+    fn default() -> Self {
+        TextWrap::Word
+    }
+}
+
+/// What happens to text that doesn't fit in its bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Draws the text outside of the bounds
+    Visible,
+    /// Hides the glyphs that are not entirely inside of the bounds
+    Clip,
+    /// Hides the glyphs that don't fit, and ends the last visible line with an ellipsis
+    Ellipsis,
+}
+
+impl Default for TextOverflow {
+    fn default() -> Self {
+        TextOverflow::Visible
+    }
+}
+
+/// How the glyphs of a text are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextRendering {
+    /// Glyphs are rasterized at the size of the text. They are the sharpest at that size, but
+    /// blur when they are scaled.
+    Raster,
+    /// Glyphs are drawn from signed distance fields, which stay crisp at any scale, like in world
+    /// space or under a zooming camera. Small text is a little softer than raster text.
+    Sdf,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        TextRendering::Raster
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TextStyle {
     pub font_size: f32,
     pub color: Color,
     pub alignment: TextAlignment,
+    pub wrap: TextWrap,
+    pub overflow: TextOverflow,
+    /// The fonts tried in order for characters that the font of a section doesn't have, before
+    /// the [FontFallbacks](crate::FontFallbacks)
+    pub fallback_fonts: Vec<Handle<Font>>,
+    pub rendering: TextRendering,
 }
 
 impl Default for TextStyle {
@@ -43,6 +101,31 @@ impl Default for TextStyle {
             color: Color::WHITE,
             font_size: 12.0,
             alignment: TextAlignment::default(),
+            wrap: TextWrap::default(),
+            overflow: TextOverflow::default(),
+            fallback_fonts: Vec::new(),
+            rendering: TextRendering::default(),
+        }
+    }
+}
+
+/// A run of text with its own font, size and color. The sections of a text are laid out one
+/// after the other, continuing each other's lines.
+#[derive(Debug, Clone)]
+pub struct TextSection {
+    pub value: String,
+    pub font: Handle<Font>,
+    pub font_size: f32,
+    pub color: Color,
+}
+
+impl Default for TextSection {
+    fn default() -> Self {
+        Self {
+            value: String::new(),
+            font: Default::default(),
+            font_size: 12.0,
+            color: Color::WHITE,
         }
     }
 }
@@ -50,18 +133,35 @@ impl Default for TextStyle {
 pub struct DrawableText<'a> {
     pub render_resource_bindings: &'a mut RenderResourceBindings,
     pub asset_render_resource_bindings: &'a mut AssetRenderResourceBindings,
-    pub position: Vec3,
-    pub style: &'a TextStyle,
+    /// The transform of the origin the glyphs are positioned from
+    pub transform: Mat4,
+    /// The (min x, min y, max x, max y) rect outside of which glyphs are discarded, in the space
+    /// `transform` transforms to
+    pub clip: Vec4,
+    /// The color of each section of the text
+    pub colors: &'a [Color],
+    pub rendering: TextRendering,
     pub text_glyphs: &'a Vec<PositionedGlyph>,
     pub msaa: &'a Msaa,
     pub font_quad_vertex_descriptor: &'a VertexBufferDescriptor,
 }
 
+impl<'a> DrawableText<'a> {
+    /// The clip of text that is never clipped
+    pub fn unclipped() -> Vec4 {
+        Vec4::new(f32::MIN, f32::MIN, f32::MAX, f32::MAX)
+    }
+}
+
 impl<'a> Drawable for DrawableText<'a> {
     fn draw(&mut self, draw: &mut Draw, context: &mut DrawContext) -> Result<(), DrawError> {
+        let pipeline = match self.rendering {
+            TextRendering::Raster => &TEXT_PIPELINE_HANDLE,
+            TextRendering::Sdf => &TEXT_SDF_PIPELINE_HANDLE,
+        };
         context.set_pipeline(
             draw,
-            &bevy_sprite::SPRITE_SHEET_PIPELINE_HANDLE,
+            pipeline,
             &PipelineSpecialization {
                 sample_count: self.msaa.samples,
                 vertex_buffer_descriptor: self.font_quad_vertex_descriptor.clone(),
@@ -94,6 +194,11 @@ impl<'a> Drawable for DrawableText<'a> {
         // set global bindings
         context.set_bind_groups_from_bindings(draw, &mut [self.render_resource_bindings])?;
 
+        let clip_buffer = context
+            .shared_buffers
+            .get_buffer(&self.clip, BufferUsage::UNIFORM)
+            .unwrap();
+
         for tv in self.text_glyphs {
             let atlas_render_resource_bindings = self
                 .asset_render_resource_bindings
@@ -101,12 +206,22 @@ impl<'a> Drawable for DrawableText<'a> {
                 .unwrap();
             context.set_bind_groups_from_bindings(draw, &mut [atlas_render_resource_bindings])?;
 
+            let mut color = self.colors[tv.section_index];
+            // glyphs with their own colors only take the opacity of the text
+            if tv.is_color {
+                color = Color::rgba(1.0, 1.0, 1.0, color.a());
+            }
             let sprite = TextureAtlasSprite {
                 index: tv.atlas_info.glyph_index,
-                color: self.style.color,
+                color,
             };
 
-            let transform = Mat4::from_translation(self.position + tv.position.extend(0.));
+            let transform = self.transform
+                * Mat4::from_scale_rotation_translation(
+                    Vec3::new(tv.scale, tv.scale, 1.0),
+                    Quat::identity(),
+                    tv.position.extend(0.),
+                );
 
             let transform_buffer = context
                 .shared_buffers
@@ -119,6 +234,7 @@ impl<'a> Drawable for DrawableText<'a> {
             let sprite_bind_group = BindGroup::build()
                 .add_binding(0, transform_buffer)
                 .add_binding(1, sprite_buffer)
+                .add_binding(2, clip_buffer.clone())
                 .finish();
             context.create_bind_group_resource(2, &sprite_bind_group)?;
             draw.set_bind_group(2, &sprite_bind_group);
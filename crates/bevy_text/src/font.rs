@@ -1,21 +1,33 @@
 use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use bevy_asset::Handle;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     color::Color,
     texture::{Extent3d, Texture, TextureDimension, TextureFormat},
 };
+use std::sync::Arc;
 
 #[derive(Debug, TypeUuid)]
 #[uuid = "97059ac6-c9ba-4da9-95b6-bed82c3ce198"]
 pub struct Font {
     pub font: FontArc,
+    /// The data the font was loaded from, which text shaping reads the font tables from
+    pub data: Arc<Vec<u8>>,
+}
+
+/// The fonts tried in order for characters that none of the fonts of a text have, so that text
+/// in other scripts or with symbols doesn't show missing glyphs
+#[derive(Debug, Default, Clone)]
+pub struct FontFallbacks {
+    pub fonts: Vec<Handle<Font>>,
 }
 
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
+        let data = Arc::new(font_data.clone());
         let font = FontVec::try_from_vec(font_data)?;
         let font = FontArc::new(font);
-        Ok(Font { font })
+        Ok(Font { font, data })
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Texture {
@@ -1,5 +1,8 @@
-use crate::{error::TextError, Font, FontAtlas};
-use ab_glyph::{GlyphId, OutlinedGlyph};
+use crate::{
+    color_glyph::color_glyph_texture, error::TextError, get_outlined_glyph_sdf_texture, Font,
+    FontAtlas,
+};
+use ab_glyph::{GlyphId, OutlinedGlyph, PxScale, Rect};
 use bevy_asset::{Assets, Handle};
 use bevy_core::FloatOrd;
 use bevy_math::Vec2;
@@ -14,6 +17,11 @@ type FontSizeKey = FloatOrd;
 #[uuid = "73ba778b-b6b5-4f45-982d-d21b6b86ace2"]
 pub struct FontAtlasSet {
     font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    /// The atlases of the distance fields of glyphs, which are shared by all font sizes
+    sdf_font_atlases: Vec<FontAtlas>,
+    /// The bounds of the glyphs drawn with colors, by font size. Glyphs without colors have no
+    /// bounds.
+    color_glyphs: HashMap<(FontSizeKey, GlyphId), Option<Rect>>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +34,8 @@ impl Default for FontAtlasSet {
     fn default() -> Self {
         FontAtlasSet {
             font_atlases: HashMap::with_capacity(1),
+            sdf_font_atlases: Vec::new(),
+            color_glyphs: HashMap::default(),
         }
     }
 }
@@ -55,32 +65,15 @@ impl FontAtlasSet {
         let font_atlases = self
             .font_atlases
             .entry(FloatOrd(font_size))
-            .or_insert_with(|| {
-                vec![FontAtlas::new(
-                    textures,
-                    texture_atlases,
-                    Vec2::new(512.0, 512.0),
-                )]
-            });
+            .or_insert_with(Vec::new);
         let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
-        let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
-            atlas.add_glyph(textures, texture_atlases, glyph_id, &glyph_texture)
-        };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                Vec2::new(512.0, 512.0),
-            ));
-            if !font_atlases.last_mut().unwrap().add_glyph(
-                textures,
-                texture_atlases,
-                glyph_id,
-                &glyph_texture,
-            ) {
-                return Err(TextError::FailedToAddGlyph(glyph_id));
-            }
-        }
+        add_glyph_texture(
+            font_atlases,
+            texture_atlases,
+            textures,
+            glyph_id,
+            &glyph_texture,
+        )?;
 
         Ok(self.get_glyph_atlas_info(font_size, glyph_id).unwrap())
     }
@@ -92,18 +85,117 @@ impl FontAtlasSet {
     ) -> Option<GlyphAtlasInfo> {
         self.font_atlases
             .get(&FloatOrd(font_size))
-            .and_then(|font_atlases| {
-                font_atlases
-                    .iter()
-                    .find_map(|atlas| {
-                        atlas
-                            .get_glyph_index(glyph_id)
-                            .map(|glyph_index| (glyph_index, atlas.texture_atlas.clone_weak()))
-                    })
-                    .map(|(glyph_index, texture_atlas)| GlyphAtlasInfo {
-                        texture_atlas,
-                        glyph_index,
-                    })
-            })
+            .and_then(|font_atlases| find_glyph(font_atlases, glyph_id))
+    }
+
+    /// Adds the distance field of a glyph, which should be outlined at
+    /// [SDF_FONT_SIZE](crate::SDF_FONT_SIZE)
+    pub fn add_sdf_glyph_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: &mut Assets<Texture>,
+        outlined_glyph: OutlinedGlyph,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        let glyph_id = outlined_glyph.glyph().id;
+        let glyph_texture = get_outlined_glyph_sdf_texture(outlined_glyph);
+        add_glyph_texture(
+            &mut self.sdf_font_atlases,
+            texture_atlases,
+            textures,
+            glyph_id,
+            &glyph_texture,
+        )?;
+
+        Ok(self.get_sdf_glyph_atlas_info(glyph_id).unwrap())
+    }
+
+    pub fn get_sdf_glyph_atlas_info(&self, glyph_id: GlyphId) -> Option<GlyphAtlasInfo> {
+        find_glyph(&self.sdf_font_atlases, glyph_id)
     }
+
+    /// Returns where a glyph with colors is in the atlases, and its bounds in pixels relative to
+    /// the glyph origin. The glyph is drawn and added to the atlas of its font size the first time.
+    /// Returns nothing for glyphs without colors.
+    pub fn get_or_add_color_glyph(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        textures: &mut Assets<Texture>,
+        font: &Font,
+        glyph_id: GlyphId,
+        scale: PxScale,
+    ) -> Result<Option<(GlyphAtlasInfo, Rect)>, TextError> {
+        let font_size = scale.y;
+        let key = (FloatOrd(font_size), glyph_id);
+        let bounds = match self.color_glyphs.get(&key) {
+            Some(bounds) => *bounds,
+            None => {
+                let color_glyph = color_glyph_texture(&font.font, &font.data, glyph_id, scale);
+                let bounds = match color_glyph {
+                    Some((glyph_texture, bounds)) => {
+                        let font_atlases = self
+                            .font_atlases
+                            .entry(FloatOrd(font_size))
+                            .or_insert_with(Vec::new);
+                        add_glyph_texture(
+                            font_atlases,
+                            texture_atlases,
+                            textures,
+                            glyph_id,
+                            &glyph_texture,
+                        )?;
+                        Some(bounds)
+                    }
+                    None => None,
+                };
+                self.color_glyphs.insert(key, bounds);
+                bounds
+            }
+        };
+        Ok(bounds.and_then(|bounds| {
+            self.get_glyph_atlas_info(font_size, glyph_id)
+                .map(|atlas_info| (atlas_info, bounds))
+        }))
+    }
+}
+
+fn add_glyph_texture(
+    font_atlases: &mut Vec<FontAtlas>,
+    texture_atlases: &mut Assets<TextureAtlas>,
+    textures: &mut Assets<Texture>,
+    glyph_id: GlyphId,
+    glyph_texture: &Texture,
+) -> Result<(), TextError> {
+    let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
+        atlas.add_glyph(textures, texture_atlases, glyph_id, glyph_texture)
+    };
+    if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
+        font_atlases.push(FontAtlas::new(
+            textures,
+            texture_atlases,
+            Vec2::new(512.0, 512.0),
+        ));
+        if !font_atlases.last_mut().unwrap().add_glyph(
+            textures,
+            texture_atlases,
+            glyph_id,
+            glyph_texture,
+        ) {
+            return Err(TextError::FailedToAddGlyph(glyph_id));
+        }
+    }
+    Ok(())
+}
+
+fn find_glyph(font_atlases: &[FontAtlas], glyph_id: GlyphId) -> Option<GlyphAtlasInfo> {
+    font_atlases
+        .iter()
+        .find_map(|atlas| {
+            atlas
+                .get_glyph_index(glyph_id)
+                .map(|glyph_index| (glyph_index, atlas.texture_atlas.clone_weak()))
+        })
+        .map(|(glyph_index, texture_atlas)| GlyphAtlasInfo {
+            texture_atlas,
+            glyph_index,
+        })
 }
@@ -1,7 +1,8 @@
 use crate::Font;
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
-use bevy_utils::BoxedFuture;
+use bevy_reflect::TypeUuid;
+use bevy_utils::{BoxedFuture, Uuid};
 
 #[derive(Default)]
 pub struct FontLoader;
@@ -22,4 +23,8 @@ impl AssetLoader for FontLoader {
     fn extensions(&self) -> &[&str] {
         &["ttf"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(Font::TYPE_UUID)
+    }
 }
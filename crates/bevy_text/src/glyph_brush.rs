@@ -1,16 +1,22 @@
-use ab_glyph::{Font as _, FontArc, ScaleFont as _};
+use ab_glyph::{point, Font as _, FontArc, Glyph, GlyphId, PxScale, ScaleFont as _};
 use bevy_asset::{Assets, Handle};
 use bevy_math::{Size, Vec2};
 use bevy_render::prelude::Texture;
 use bevy_sprite::TextureAtlas;
 use glyph_brush_layout::{
-    FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph, ToSectionText,
+    BuiltInLineBreaker, FontId, GlyphPositioner, HorizontalAlign, Layout, SectionGeometry,
+    SectionGlyph, SectionText, VerticalAlign,
 };
+use std::sync::Arc;
 
-use crate::{error::TextError, Font, FontAtlasSet, GlyphAtlasInfo, TextAlignment};
+use crate::{
+    error::TextError, shaping, Font, FontAtlasSet, GlyphAtlasInfo, TextAlignment, TextOverflow,
+    TextRendering, TextWrap, SDF_FONT_SIZE,
+};
 
 pub struct GlyphBrush {
     fonts: Vec<FontArc>,
+    font_data: Vec<Arc<Vec<u8>>>,
     handles: Vec<Handle<Font>>,
     latest_font_id: FontId,
 }
@@ -19,6 +25,7 @@ impl Default for GlyphBrush {
     fn default() -> Self {
         GlyphBrush {
             fonts: Vec::new(),
+            font_data: Vec::new(),
             handles: Vec::new(),
             latest_font_id: FontId(0),
         }
@@ -26,21 +33,144 @@ impl Default for GlyphBrush {
 }
 
 impl GlyphBrush {
-    pub fn compute_glyphs<S: ToSectionText>(
+    /// Lays out `sections` within `bounds`, then shapes the laid out lines so that ligatures,
+    /// contextual forms and right-to-left text are drawn correctly
+    pub fn compute_glyphs(
         &self,
-        sections: &[S],
+        sections: &[SectionText],
         bounds: Size,
         text_alignment: TextAlignment,
+        text_wrap: TextWrap,
     ) -> Result<Vec<SectionGlyph>, TextError> {
+        let (line_breaker, wrap_width) = match text_wrap {
+            TextWrap::Word => (BuiltInLineBreaker::UnicodeLineBreaker, bounds.width),
+            TextWrap::Character => (BuiltInLineBreaker::AnyCharLineBreaker, bounds.width),
+            TextWrap::NoWrap => (BuiltInLineBreaker::UnicodeLineBreaker, f32::MAX),
+        };
         let geom = SectionGeometry {
-            bounds: (bounds.width, bounds.height),
+            bounds: (wrap_width, bounds.height),
             ..Default::default()
         };
         let section_glyphs = Layout::default()
+            .line_breaker(line_breaker)
             .h_align(text_alignment.horizontal)
             .v_align(text_alignment.vertical)
             .calculate_glyphs(&self.fonts, &geom, sections);
-        Ok(section_glyphs)
+        Ok(shaping::shape_lines(
+            &self.fonts,
+            &self.font_data,
+            sections,
+            section_glyphs,
+            text_alignment.horizontal,
+        ))
+    }
+
+    /// Removes the glyphs of laid out text that are not entirely inside of `bounds`. With
+    /// [TextOverflow::Ellipsis], lines that were cut end with an ellipsis.
+    pub fn apply_overflow(
+        &self,
+        mut glyphs: Vec<SectionGlyph>,
+        bounds: Size,
+        text_alignment: TextAlignment,
+        text_overflow: TextOverflow,
+    ) -> Vec<SectionGlyph> {
+        if text_overflow == TextOverflow::Visible {
+            return glyphs;
+        }
+
+        // glyphs are positioned around the origin depending on the alignment
+        let min_x = match text_alignment.horizontal {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Center => -bounds.width / 2.0,
+            HorizontalAlign::Right => -bounds.width,
+        };
+        let min_y = match text_alignment.vertical {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => -bounds.height / 2.0,
+            VerticalAlign::Bottom => -bounds.height,
+        };
+        let max_x = min_x + bounds.width;
+        let max_y = min_y + bounds.height;
+        let fits_vertically = |glyph: &SectionGlyph| {
+            let font = self.fonts[glyph.font_id.0].as_scaled(glyph.glyph.scale);
+            glyph.glyph.position.y - font.ascent() >= min_y
+                && glyph.glyph.position.y - font.descent() <= max_y
+        };
+        let fits_horizontally = |glyph: &SectionGlyph, max_x: f32| {
+            let font = self.fonts[glyph.font_id.0].as_scaled(glyph.glyph.scale);
+            glyph.glyph.position.x >= min_x
+                && glyph.glyph.position.x + font.h_advance(glyph.glyph.id) <= max_x
+        };
+
+        let fits = |glyph: &SectionGlyph| fits_vertically(glyph) && fits_horizontally(glyph, max_x);
+        if glyphs.iter().all(|glyph| fits(glyph)) {
+            return glyphs;
+        }
+        if text_overflow == TextOverflow::Clip {
+            glyphs.retain(|glyph| fits(glyph));
+            return glyphs;
+        }
+
+        // lines that overflow end with an ellipsis, and so does the last visible line if lines
+        // after it are hidden
+        let lines_hidden = glyphs.iter().any(|glyph| !fits_vertically(glyph));
+        let mut lines: Vec<Vec<SectionGlyph>> = Vec::new();
+        for glyph in glyphs.into_iter().filter(|glyph| fits_vertically(glyph)) {
+            match lines.last_mut() {
+                Some(line) if line[0].glyph.position.y == glyph.glyph.position.y => {
+                    line.push(glyph)
+                }
+                _ => lines.push(vec![glyph]),
+            }
+        }
+
+        let line_count = lines.len();
+        let mut visible_glyphs = Vec::new();
+        for (index, mut line) in lines.into_iter().enumerate() {
+            let is_last_line = index + 1 == line_count;
+            if !(lines_hidden && is_last_line)
+                && line.iter().all(|glyph| fits_horizontally(glyph, max_x))
+            {
+                visible_glyphs.extend(line);
+                continue;
+            }
+
+            let template = line[0].clone();
+            let (ellipsis_id, ellipsis_count) = self.ellipsis_glyph(template.font_id);
+            let font = self.fonts[template.font_id.0].as_scaled(template.glyph.scale);
+            let ellipsis_advance = font.h_advance(ellipsis_id);
+            let ellipsis_width = ellipsis_advance * ellipsis_count as f32;
+            line.retain(|glyph| fits_horizontally(glyph, max_x - ellipsis_width));
+            let mut caret_x = line
+                .iter()
+                .map(|glyph| glyph.glyph.position.x + font.h_advance(glyph.glyph.id))
+                .fold(min_x.max(template.glyph.position.x), f32::max);
+            visible_glyphs.extend(line);
+            for _ in 0..ellipsis_count {
+                visible_glyphs.push(SectionGlyph {
+                    glyph: Glyph {
+                        id: ellipsis_id,
+                        scale: template.glyph.scale,
+                        position: point(caret_x, template.glyph.position.y),
+                    },
+                    ..template.clone()
+                });
+                caret_x += ellipsis_advance;
+            }
+        }
+        visible_glyphs
+    }
+
+    /// Returns the glyph to draw an ellipsis with, and how many times to draw it. Fonts without an
+    /// ellipsis glyph use three periods.
+    fn ellipsis_glyph(&self, font_id: FontId) -> (GlyphId, usize) {
+        let font = &self.fonts[font_id.0];
+        let ellipsis = font.glyph_id('\u{2026}');
+        if ellipsis.0 != 0 {
+            (ellipsis, 1)
+        } else {
+            (font.glyph_id('.'), 3)
+        }
     }
 
     pub fn process_glyphs(
@@ -50,21 +180,18 @@ impl GlyphBrush {
         fonts: &Assets<Font>,
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Texture>,
+        text_rendering: TextRendering,
     ) -> Result<Vec<PositionedGlyph>, TextError> {
         if glyphs.is_empty() {
             return Ok(Vec::new());
         }
 
-        let first_glyph = glyphs.first().expect("Must have at least one glyph");
-        let font_id = first_glyph.font_id.0;
-        let handle = &self.handles[font_id];
-        let font = fonts.get(handle).ok_or(TextError::NoSuchFont)?;
-        let font_size = first_glyph.glyph.scale.y;
-        let scaled_font = ab_glyph::Font::as_scaled(&font.font, font_size);
+        // the glyphs of all sections are positioned from the bottom left of the text block
         let mut max_y = std::f32::MIN;
         let mut min_x = std::f32::MAX;
         for section_glyph in glyphs.iter() {
             let glyph = &section_glyph.glyph;
+            let scaled_font = self.fonts[section_glyph.font_id.0].as_scaled(glyph.scale);
             max_y = max_y.max(glyph.position.y - scaled_font.descent());
             min_x = min_x.min(glyph.position.x);
         }
@@ -73,42 +200,108 @@ impl GlyphBrush {
 
         let mut positioned_glyphs = Vec::new();
         for sg in glyphs {
+            let handle = &self.handles[sg.font_id.0];
+            let font = fonts.get(handle).ok_or(TextError::NoSuchFont)?;
+            let font_size = sg.glyph.scale.y;
             let glyph_id = sg.glyph.id;
-            if let Some(outlined_glyph) = font.font.outline_glyph(sg.glyph) {
+
+            // glyphs with colors, like emoji, are drawn from their colored layers or bitmaps
+            if text_rendering == TextRendering::Raster {
+                let handle_font_atlas: Handle<FontAtlasSet> = handle.as_weak();
+                let font_atlas_set = font_atlas_set_storage
+                    .get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
+                if let Some((atlas_info, bounds)) = font_atlas_set.get_or_add_color_glyph(
+                    texture_atlases,
+                    textures,
+                    font,
+                    glyph_id,
+                    sg.glyph.scale,
+                )? {
+                    let position = sg.glyph.position;
+                    let x = position.x + (bounds.min.x + bounds.max.x) / 2.0 - min_x;
+                    let y = max_y - position.y - (bounds.min.y + bounds.max.y) / 2.0;
+                    positioned_glyphs.push(PositionedGlyph {
+                        position: Vec2::new(x, y),
+                        atlas_info,
+                        section_index: sg.section_index,
+                        scale: 1.0,
+                        is_color: true,
+                    });
+                    continue;
+                }
+            }
+            if let Some(outlined_glyph) = font.font.outline_glyph(sg.glyph.clone()) {
                 let bounds = outlined_glyph.px_bounds();
                 let handle_font_atlas: Handle<FontAtlasSet> = handle.as_weak();
                 let font_atlas_set = font_atlas_set_storage
                     .get_or_insert_with(handle_font_atlas, FontAtlasSet::default);
 
-                let atlas_info = font_atlas_set
-                    .get_glyph_atlas_info(font_size, glyph_id)
-                    .map(Ok)
-                    .unwrap_or_else(|| {
-                        font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
-                    })?;
+                let (position, atlas_info, scale) = match text_rendering {
+                    TextRendering::Raster => {
+                        let atlas_info = font_atlas_set
+                            .get_glyph_atlas_info(font_size, glyph_id)
+                            .map(Ok)
+                            .unwrap_or_else(|| {
+                                font_atlas_set.add_glyph_to_atlas(
+                                    texture_atlases,
+                                    textures,
+                                    outlined_glyph,
+                                )
+                            })?;
+
+                        let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+                        let glyph_rect = texture_atlas.textures[atlas_info.glyph_index as usize];
+                        let glyph_width = glyph_rect.width();
+                        let glyph_height = glyph_rect.height();
 
-                let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
-                let glyph_rect = texture_atlas.textures[atlas_info.glyph_index as usize];
-                let glyph_width = glyph_rect.width();
-                let glyph_height = glyph_rect.height();
+                        let x = bounds.min.x + glyph_width / 2.0 - min_x;
+                        // the 0.5 accounts for odd-numbered heights (bump up by 1 pixel)
+                        // max_y = text block height, and up is negative (whereas for transform, up is positive)
+                        let y = max_y - bounds.max.y + glyph_height / 2.0 + 0.5;
+                        (Vec2::new(x, y), atlas_info, 1.0)
+                    }
+                    TextRendering::Sdf => {
+                        let atlas_info = match font_atlas_set.get_sdf_glyph_atlas_info(glyph_id) {
+                            Some(atlas_info) => atlas_info,
+                            None => {
+                                let sdf_glyph = Glyph {
+                                    id: glyph_id,
+                                    scale: PxScale::from(SDF_FONT_SIZE),
+                                    position: point(0.0, 0.0),
+                                };
+                                match font.font.outline_glyph(sdf_glyph) {
+                                    Some(outlined_glyph) => font_atlas_set.add_sdf_glyph_to_atlas(
+                                        texture_atlases,
+                                        textures,
+                                        outlined_glyph,
+                                    )?,
+                                    None => continue,
+                                }
+                            }
+                        };
 
-                let x = bounds.min.x + glyph_width / 2.0 - min_x;
-                // the 0.5 accounts for odd-numbered heights (bump up by 1 pixel)
-                // max_y = text block height, and up is negative (whereas for transform, up is positive)
-                let y = max_y - bounds.max.y + glyph_height / 2.0 + 0.5;
-                let position = Vec2::new(x, y);
+                        // the distance field is centered on the glyph and scaled to its size
+                        let x = (bounds.min.x + bounds.max.x) / 2.0 - min_x;
+                        let y = max_y - (bounds.min.y + bounds.max.y) / 2.0;
+                        (Vec2::new(x, y), atlas_info, font_size / SDF_FONT_SIZE)
+                    }
+                };
 
                 positioned_glyphs.push(PositionedGlyph {
                     position,
                     atlas_info,
+                    section_index: sg.section_index,
+                    scale,
+                    is_color: false,
                 });
             }
         }
         Ok(positioned_glyphs)
     }
 
-    pub fn add_font(&mut self, handle: Handle<Font>, font: FontArc) -> FontId {
-        self.fonts.push(font);
+    pub fn add_font(&mut self, handle: Handle<Font>, font: &Font) -> FontId {
+        self.fonts.push(font.font.clone());
+        self.font_data.push(font.data.clone());
         self.handles.push(handle);
         let font_id = self.latest_font_id;
         self.latest_font_id = FontId(font_id.0 + 1);
@@ -120,4 +313,10 @@ impl GlyphBrush {
 pub struct PositionedGlyph {
     pub position: Vec2,
     pub atlas_info: GlyphAtlasInfo,
+    /// The index of the section of the text the glyph is in
+    pub section_index: usize,
+    /// The scale the image of the glyph in the atlas is drawn at
+    pub scale: f32,
+    /// Whether the glyph is drawn with its own colors instead of the color of the text
+    pub is_color: bool,
 }
@@ -1,3 +1,4 @@
+mod color_glyph;
 mod draw;
 mod error;
 mod font;
@@ -6,6 +7,10 @@ mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
 mod pipeline;
+mod render;
+mod shaping;
+mod text;
+mod text2d;
 
 pub use draw::*;
 pub use error::*;
@@ -15,15 +20,22 @@ pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
 pub use pipeline::*;
+pub use render::*;
+pub use text::*;
+pub use text2d::*;
 
 pub mod prelude {
-    pub use crate::{Font, TextAlignment, TextError, TextStyle};
+    pub use crate::{
+        DefaultTextPipeline, Font, FontFallbacks, Text2dBounds, Text2dBundle, TextAlignment,
+        TextError, TextOverflow, TextRendering, TextSection, TextStyle, TextWrap,
+    };
     pub use glyph_brush_layout::{HorizontalAlign, VerticalAlign};
 }
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Assets};
 use bevy_ecs::Entity;
+use bevy_render::{pipeline::PipelineDescriptor, shader::Shader};
 
 pub type DefaultTextPipeline = TextPipeline<Entity>;
 
@@ -35,6 +47,18 @@ impl Plugin for TextPlugin {
         app.add_asset::<Font>()
             .add_asset::<FontAtlasSet>()
             .init_asset_loader::<FontLoader>()
-            .add_resource(DefaultTextPipeline::default());
+            .init_resource::<FontFallbacks>()
+            .add_resource(DefaultTextPipeline::default())
+            .add_system_to_stage(stage::POST_UPDATE, text2d_system)
+            .add_system_to_stage(bevy_render::stage::DRAW, draw_text2d_system);
+
+        let resources = app.resources();
+        let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+        let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+        pipelines.set_untracked(TEXT_PIPELINE_HANDLE, build_text_pipeline(&mut shaders));
+        pipelines.set_untracked(
+            TEXT_SDF_PIPELINE_HANDLE,
+            build_text_sdf_pipeline(&mut shaders),
+        );
     }
 }
@@ -1,16 +1,17 @@
 use std::hash::Hash;
 
-use ab_glyph::{PxScale, ScaleFont};
+use ab_glyph::{Font as _, FontArc, PxScale, PxScaleFont, ScaleFont};
 use bevy_asset::{Assets, Handle, HandleId};
 use bevy_math::Size;
 use bevy_render::prelude::Texture;
 use bevy_sprite::TextureAtlas;
 use bevy_utils::HashMap;
 
-use glyph_brush_layout::{FontId, SectionText};
+use glyph_brush_layout::{FontId, SectionGlyph, SectionText};
 
 use crate::{
     error::TextError, glyph_brush::GlyphBrush, Font, FontAtlasSet, PositionedGlyph, TextAlignment,
+    TextOverflow, TextRendering, TextSection, TextWrap,
 };
 
 pub struct TextPipeline<ID> {
@@ -31,87 +32,238 @@ impl<ID> Default for TextPipeline<ID> {
 
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
+    /// The laid out characters, including whitespace, in the order of the text
+    pub characters: Vec<PositionedCharacter>,
     pub size: Size,
 }
 
+/// Where a character of a text was laid out, used to place carets and selections
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedCharacter {
+    /// The index of the character's first byte in the text, with the sections of the text joined
+    pub byte_index: usize,
+    /// The distance from the left of the text block to the left of the character
+    pub x: f32,
+    pub advance: f32,
+}
+
+/// Characters of a section that are drawn with the same font
+struct TextRun<'a> {
+    section_index: usize,
+    /// The index of the first byte of the run in the text, with the sections of the text joined
+    byte_offset: usize,
+    font: &'a Font,
+}
+
 impl<ID: Hash + Eq> TextPipeline<ID> {
     pub fn get_or_insert_font_id(&mut self, handle: Handle<Font>, font: &Font) -> FontId {
         let brush = &mut self.brush;
         *self
             .map_font_id
             .entry(handle.id)
-            .or_insert_with(|| brush.add_font(handle.clone(), font.font.clone()))
+            .or_insert_with(|| brush.add_font(handle.clone(), font))
     }
 
     pub fn get_glyphs(&self, id: &ID) -> Option<&TextLayoutInfo> {
         self.glyph_map.get(id)
     }
 
+    /// Lays out `sections` one after the other within `bounds`. Characters that the font of their
+    /// section doesn't have are drawn with the first of `fallback_fonts` that has them.
     #[allow(clippy::too_many_arguments)]
     pub fn queue_text(
         &mut self,
         id: ID,
-        font_handle: Handle<Font>,
+        sections: &[TextSection],
+        fallback_fonts: &[Handle<Font>],
         fonts: &Assets<Font>,
-        text: &str,
-        font_size: f32,
         text_alignment: TextAlignment,
+        text_wrap: TextWrap,
+        text_overflow: TextOverflow,
+        text_rendering: TextRendering,
         bounds: Size,
         font_atlas_set_storage: &mut Assets<FontAtlasSet>,
         texture_atlases: &mut Assets<TextureAtlas>,
         textures: &mut Assets<Texture>,
     ) -> Result<(), TextError> {
-        let font = fonts.get(font_handle.id).ok_or(TextError::NoSuchFont)?;
-        let font_id = self.get_or_insert_font_id(font_handle, font);
-
-        let section = SectionText {
-            font_id,
-            scale: PxScale::from(font_size),
-            text,
-        };
-
-        let scaled_font = ab_glyph::Font::as_scaled(&font.font, font_size);
-
-        let section_glyphs = self
-            .brush
-            .compute_glyphs(&[section], bounds, text_alignment)?;
+        let (runs, run_texts) = self.split_runs(sections, fallback_fonts, fonts)?;
+        let section_glyphs =
+            self.brush
+                .compute_glyphs(&run_texts, bounds, text_alignment, text_wrap)?;
+        let section_glyphs =
+            self.brush
+                .apply_overflow(section_glyphs, bounds, text_alignment, text_overflow);
 
         if section_glyphs.is_empty() {
             self.glyph_map.insert(
                 id,
                 TextLayoutInfo {
                     glyphs: Vec::new(),
+                    characters: Vec::new(),
                     size: Size::new(0., 0.),
                 },
             );
             return Ok(());
         }
 
-        let mut min_x: f32 = std::f32::MAX;
-        let mut min_y: f32 = std::f32::MAX;
-        let mut max_x: f32 = std::f32::MIN;
-        let mut max_y: f32 = std::f32::MIN;
-
-        for section_glyph in section_glyphs.iter() {
-            let glyph = &section_glyph.glyph;
-            min_x = min_x.min(glyph.position.x);
-            min_y = min_y.min(glyph.position.y - scaled_font.ascent());
-            max_x = max_x.max(glyph.position.x + scaled_font.h_advance(glyph.id));
-            max_y = max_y.max(glyph.position.y - scaled_font.descent());
+        let (min_x, mut size) = measure_glyphs(&runs, &section_glyphs);
+        if text_overflow != TextOverflow::Visible {
+            size.width = size.width.min(bounds.width);
+            size.height = size.height.min(bounds.height);
         }
 
-        let size = Size::new(max_x - min_x, max_y - min_y);
+        // glyphs are drawn relative to the left of the leftmost glyph
+        let mut characters = section_glyphs
+            .iter()
+            .map(|section_glyph| {
+                let glyph = &section_glyph.glyph;
+                PositionedCharacter {
+                    byte_index: runs[section_glyph.section_index].byte_offset
+                        + section_glyph.byte_index,
+                    x: glyph.position.x - min_x.floor(),
+                    advance: scaled_font(&runs, section_glyph).h_advance(glyph.id),
+                }
+            })
+            .collect::<Vec<_>>();
+        // shaped glyphs are in display order, which differs from the order of the text in
+        // right-to-left runs
+        characters.sort_by_key(|character| character.byte_index);
 
-        let glyphs = self.brush.process_glyphs(
+        let mut glyphs = self.brush.process_glyphs(
             section_glyphs,
             font_atlas_set_storage,
             fonts,
             texture_atlases,
             textures,
+            text_rendering,
         )?;
+        for glyph in glyphs.iter_mut() {
+            glyph.section_index = runs[glyph.section_index].section_index;
+        }
 
-        self.glyph_map.insert(id, TextLayoutInfo { glyphs, size });
+        self.glyph_map.insert(
+            id,
+            TextLayoutInfo {
+                glyphs,
+                characters,
+                size,
+            },
+        );
 
         Ok(())
     }
+
+    /// Returns the size `sections` take up when laid out within `bounds`, without adding their
+    /// glyphs to the font atlases. Text that doesn't fit in `bounds` is measured as if it could
+    /// overflow, so that its container can grow to fit it.
+    pub fn measure_text(
+        &mut self,
+        sections: &[TextSection],
+        fallback_fonts: &[Handle<Font>],
+        fonts: &Assets<Font>,
+        text_wrap: TextWrap,
+        bounds: Size,
+    ) -> Result<Size, TextError> {
+        let (runs, run_texts) = self.split_runs(sections, fallback_fonts, fonts)?;
+        let section_glyphs =
+            self.brush
+                .compute_glyphs(&run_texts, bounds, TextAlignment::default(), text_wrap)?;
+        if section_glyphs.is_empty() {
+            return Ok(Size::new(0., 0.));
+        }
+        let (_min_x, size) = measure_glyphs(&runs, &section_glyphs);
+        Ok(size)
+    }
+
+    /// Splits sections into runs of characters drawn with the same font, the font of their section
+    /// or the first of `fallback_fonts` that has them
+    fn split_runs<'a>(
+        &mut self,
+        sections: &'a [TextSection],
+        fallback_fonts: &[Handle<Font>],
+        fonts: &'a Assets<Font>,
+    ) -> Result<(Vec<TextRun<'a>>, Vec<SectionText<'a>>), TextError> {
+        let mut fallbacks = Vec::with_capacity(fallback_fonts.len());
+        for handle in fallback_fonts {
+            let font = fonts.get(handle.id).ok_or(TextError::NoSuchFont)?;
+            fallbacks.push((self.get_or_insert_font_id(handle.clone(), font), font));
+        }
+
+        let mut runs = Vec::new();
+        let mut run_texts = Vec::new();
+        let mut section_offset = 0;
+        for (section_index, section) in sections.iter().enumerate() {
+            let font = fonts.get(section.font.id).ok_or(TextError::NoSuchFont)?;
+            let section_font = (self.get_or_insert_font_id(section.font.clone(), font), font);
+            let scale = PxScale::from(section.font_size);
+            let mut run_start = 0;
+            let mut run_font = None;
+            for (index, character) in section.value.char_indices() {
+                let character_font = if character.is_whitespace() || character.is_control() {
+                    // whitespace doesn't need to be drawn, so it doesn't start new runs
+                    run_font.unwrap_or(section_font)
+                } else {
+                    std::iter::once(section_font)
+                        .chain(fallbacks.iter().copied())
+                        .find(|(_font_id, font)| font.font.glyph_id(character).0 != 0)
+                        .unwrap_or(section_font)
+                };
+                if let Some((font_id, font)) = run_font {
+                    if font_id != character_font.0 {
+                        runs.push(TextRun {
+                            section_index,
+                            byte_offset: section_offset + run_start,
+                            font,
+                        });
+                        run_texts.push(SectionText {
+                            font_id,
+                            scale,
+                            text: &section.value[run_start..index],
+                        });
+                        run_start = index;
+                    }
+                }
+                run_font = Some(character_font);
+            }
+            let (font_id, font) = run_font.unwrap_or(section_font);
+            runs.push(TextRun {
+                section_index,
+                byte_offset: section_offset + run_start,
+                font,
+            });
+            run_texts.push(SectionText {
+                font_id,
+                scale,
+                text: &section.value[run_start..],
+            });
+            section_offset += section.value.len();
+        }
+        Ok((runs, run_texts))
+    }
+}
+
+fn scaled_font<'a>(runs: &[TextRun<'a>], section_glyph: &SectionGlyph) -> PxScaleFont<&'a FontArc> {
+    ab_glyph::Font::as_scaled(
+        &runs[section_glyph.section_index].font.font,
+        section_glyph.glyph.scale,
+    )
+}
+
+/// Returns the left of the leftmost glyph and the size of the laid out glyphs
+fn measure_glyphs(runs: &[TextRun], section_glyphs: &[SectionGlyph]) -> (f32, Size) {
+    let mut min_x: f32 = std::f32::MAX;
+    let mut min_y: f32 = std::f32::MAX;
+    let mut max_x: f32 = std::f32::MIN;
+    let mut max_y: f32 = std::f32::MIN;
+
+    for section_glyph in section_glyphs.iter() {
+        let glyph = &section_glyph.glyph;
+        let scaled_font = scaled_font(runs, section_glyph);
+        min_x = min_x.min(glyph.position.x);
+        min_y = min_y.min(glyph.position.y - scaled_font.ascent());
+        max_x = max_x.max(glyph.position.x + scaled_font.h_advance(glyph.id));
+        max_y = max_y.max(glyph.position.y - scaled_font.descent());
+    }
+
+    (min_x, Size::new(max_x - min_x, max_y - min_y))
 }
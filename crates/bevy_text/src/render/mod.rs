@@ -0,0 +1,90 @@
+use ab_glyph::OutlinedGlyph;
+use bevy_asset::{Assets, Handle};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    pipeline::PipelineDescriptor,
+    shader::{Shader, ShaderStage},
+    texture::{Extent3d, Texture, TextureDimension, TextureFormat},
+};
+
+/// The font size glyphs of signed distance field text are rasterized at. They are scaled to the
+/// size of the text when drawn.
+pub const SDF_FONT_SIZE: f32 = 48.0;
+
+/// How far in pixels at [SDF_FONT_SIZE] the distance field of a glyph reaches outside of and
+/// into the glyph
+pub const SDF_SPREAD: usize = 6;
+
+pub const TEXT_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 10384219871643257133);
+
+pub const TEXT_SDF_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 3857120539182364701);
+
+/// The sprite sheet pipeline, with shaders that discard the glyphs outside of the clip rect of
+/// the text
+pub fn build_text_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    let mut pipeline = bevy_sprite::build_sprite_sheet_pipeline(shaders);
+    pipeline.shader_stages.vertex = shaders.add(Shader::from_glsl(
+        ShaderStage::Vertex,
+        include_str!("text.vert"),
+    ));
+    pipeline.shader_stages.fragment = Some(shaders.add(Shader::from_glsl(
+        ShaderStage::Fragment,
+        include_str!("text.frag"),
+    )));
+    pipeline
+}
+
+/// The text pipeline, with a fragment shader that draws glyphs from their distance fields
+pub fn build_text_sdf_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    let mut pipeline = build_text_pipeline(shaders);
+    pipeline.shader_stages.fragment = Some(shaders.add(Shader::from_glsl(
+        ShaderStage::Fragment,
+        include_str!("text_sdf.frag"),
+    )));
+    pipeline
+}
+
+/// Rasterizes a glyph as a signed distance field, stored in the alpha channel. The outline is at
+/// 0.5, with greater values inside of the glyph.
+pub fn get_outlined_glyph_sdf_texture(outlined_glyph: OutlinedGlyph) -> Texture {
+    let bounds = outlined_glyph.px_bounds();
+    let width = bounds.width() as usize + 2 * SDF_SPREAD;
+    let height = bounds.height() as usize + 2 * SDF_SPREAD;
+    let mut inside = vec![false; width * height];
+    outlined_glyph.draw(|x, y, v| {
+        inside[(y as usize + SDF_SPREAD) * width + x as usize + SDF_SPREAD] = v >= 0.5;
+    });
+
+    let spread = SDF_SPREAD as f32;
+    let mut data = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let is_inside = inside[y * width + x];
+            // the squared distance to the nearest pixel on the other side of the outline
+            let mut nearest = spread * spread;
+            for other_y in y.saturating_sub(SDF_SPREAD)..(y + SDF_SPREAD + 1).min(height) {
+                for other_x in x.saturating_sub(SDF_SPREAD)..(x + SDF_SPREAD + 1).min(width) {
+                    if inside[other_y * width + other_x] != is_inside {
+                        let dx = other_x as f32 - x as f32;
+                        let dy = other_y as f32 - y as f32;
+                        nearest = nearest.min(dx * dx + dy * dy);
+                    }
+                }
+            }
+            // the outline is halfway between the centers of the pixels on either side of it
+            let distance = nearest.sqrt() - 0.5;
+            let signed_distance = if is_inside { distance } else { -distance };
+            let alpha = (0.5 + signed_distance / (2.0 * spread)).max(0.0).min(1.0);
+            data.extend_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+        }
+    }
+
+    Texture::new(
+        Extent3d::new(width as u32, height as u32, 1),
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
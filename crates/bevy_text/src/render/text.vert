@@ -0,0 +1,58 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+layout(location = 1) in vec3 Vertex_Normal;
+layout(location = 2) in vec2 Vertex_Uv;
+
+layout(location = 0) out vec2 v_Uv;
+layout(location = 1) out vec4 v_Color;
+layout(location = 2) out vec2 v_Position;
+layout(location = 3) out vec4 v_Clip;
+
+layout(set = 0, binding = 0) uniform Camera {
+    mat4 ViewProj;
+};
+
+layout(set = 1, binding = 0) uniform TextureAtlas_size {
+    vec2 AtlasSize;
+};
+
+struct Rect {
+    vec2 begin;
+    vec2 end;
+};
+
+layout(set = 1, binding = 1) buffer TextureAtlas_textures {
+    Rect[] Textures;
+};
+
+layout(set = 2, binding = 0) uniform Transform {
+    mat4 GlyphTransform;
+};
+
+layout(set = 2, binding = 1) uniform TextureAtlasSprite {
+    vec4 TextureAtlasSprite_color;
+    uint TextureAtlasSprite_index;
+};
+
+layout(set = 2, binding = 2) uniform TextClip {
+    vec4 Clip;
+};
+
+void main() {
+    Rect glyph_rect = Textures[TextureAtlasSprite_index];
+    vec2 glyph_dimensions = glyph_rect.end - glyph_rect.begin;
+    vec3 vertex_position = vec3(Vertex_Position.xy * glyph_dimensions, 0.0);
+    vec2 atlas_positions[4] = vec2[](
+        vec2(glyph_rect.begin.x, glyph_rect.end.y),
+        glyph_rect.begin,
+        vec2(glyph_rect.end.x, glyph_rect.begin.y),
+        glyph_rect.end
+    );
+    v_Uv = (atlas_positions[gl_VertexIndex] + vec2(0.01, 0.01)) / AtlasSize;
+    v_Color = TextureAtlasSprite_color;
+    vec4 world_position = GlyphTransform * vec4(ceil(vertex_position), 1.0);
+    v_Position = world_position.xy;
+    v_Clip = Clip;
+    gl_Position = ViewProj * world_position;
+}
@@ -0,0 +1,214 @@
+use ab_glyph::{point, Font as _, FontArc, Glyph, GlyphId, PxScale, ScaleFont as _};
+use glyph_brush_layout::{FontId, HorizontalAlign, SectionGlyph, SectionText};
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use std::sync::Arc;
+use unicode_bidi::{BidiInfo, Level};
+
+/// Characters of a line that are shaped together: they are in the same section and have the same
+/// bidi level
+struct ShapingRun {
+    section_index: usize,
+    font_id: FontId,
+    scale: PxScale,
+    /// The byte range of the run in the text of its section
+    start: usize,
+    end: usize,
+    level: Level,
+}
+
+/// Shapes laid out text line by line. The characters of each line are split into runs with the
+/// same font and direction, the runs are put in display order with the unicode bidi algorithm,
+/// and the glyphs of each run are replaced by the glyphs the font substitutes and positions for
+/// them, with ligatures, contextual forms and marks. Lines keep their alignment within the space
+/// they were laid out in.
+pub(crate) fn shape_lines(
+    fonts: &[FontArc],
+    font_data: &[Arc<Vec<u8>>],
+    sections: &[SectionText],
+    glyphs: Vec<SectionGlyph>,
+    horizontal_align: HorizontalAlign,
+) -> Vec<SectionGlyph> {
+    if glyphs.is_empty() {
+        return glyphs;
+    }
+
+    // bidi levels are resolved for whole paragraphs, across sections
+    let mut text = String::new();
+    let mut section_offsets = Vec::with_capacity(sections.len());
+    for section in sections.iter() {
+        section_offsets.push(text.len());
+        text.push_str(section.text);
+    }
+    let bidi_info = BidiInfo::new(&text, None);
+
+    let mut lines: Vec<Vec<SectionGlyph>> = Vec::new();
+    for glyph in glyphs.into_iter() {
+        match lines.last_mut() {
+            Some(line) if line[0].glyph.position.y == glyph.glyph.position.y => line.push(glyph),
+            _ => lines.push(vec![glyph]),
+        }
+    }
+
+    let mut shaped_glyphs = Vec::new();
+    for line in lines.iter() {
+        let y = line[0].glyph.position.y;
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut runs: Vec<ShapingRun> = Vec::new();
+        for section_glyph in line.iter() {
+            let glyph = &section_glyph.glyph;
+            let scaled_font = fonts[section_glyph.font_id.0].as_scaled(glyph.scale);
+            min_x = min_x.min(glyph.position.x);
+            max_x = max_x.max(glyph.position.x + scaled_font.h_advance(glyph.id));
+
+            let section_text = sections[section_glyph.section_index].text;
+            let character = match section_text[section_glyph.byte_index..].chars().next() {
+                // line breaks aren't drawn
+                Some(character) if !character.is_control() => character,
+                _ => continue,
+            };
+            let start = section_glyph.byte_index;
+            let end = start + character.len_utf8();
+            let level = bidi_info.levels[section_offsets[section_glyph.section_index] + start];
+            match runs.last_mut() {
+                Some(run)
+                    if run.section_index == section_glyph.section_index
+                        && run.level == level
+                        && run.end == start =>
+                {
+                    run.end = end
+                }
+                _ => runs.push(ShapingRun {
+                    section_index: section_glyph.section_index,
+                    font_id: section_glyph.font_id,
+                    scale: glyph.scale,
+                    start,
+                    end,
+                    level,
+                }),
+            }
+        }
+        reorder_by_level(&mut runs, |run| run.level.number());
+
+        let mut line_glyphs = Vec::new();
+        let mut x = 0.0;
+        for run in runs.iter() {
+            let section_text = &sections[run.section_index].text[run.start..run.end];
+            let font = &fonts[run.font_id.0];
+            let scaled_font = font.as_scaled(run.scale);
+            let mut place = |id: GlyphId, byte_index: usize, glyph_x: f32, offset_y: f32| {
+                line_glyphs.push(SectionGlyph {
+                    section_index: run.section_index,
+                    byte_index: run.start + byte_index,
+                    font_id: run.font_id,
+                    glyph: Glyph {
+                        id,
+                        scale: run.scale,
+                        position: point(glyph_x, y - offset_y),
+                    },
+                });
+            };
+
+            match Face::from_slice(&font_data[run.font_id.0], 0) {
+                Some(face) => {
+                    let mut buffer = UnicodeBuffer::new();
+                    buffer.push_str(section_text);
+                    buffer.set_direction(if run.level.is_rtl() {
+                        Direction::RightToLeft
+                    } else {
+                        Direction::LeftToRight
+                    });
+                    buffer.guess_segment_properties();
+                    let shaped = rustybuzz::shape(&face, &[], buffer);
+                    // positions are in font units, which ab_glyph scales by the height of the font
+                    let h_scale = scaled_font.h_scale_factor();
+                    let v_scale = scaled_font.v_scale_factor();
+                    for (info, position) in shaped
+                        .glyph_infos()
+                        .iter()
+                        .zip(shaped.glyph_positions().iter())
+                    {
+                        place(
+                            GlyphId(info.glyph_id as u16),
+                            info.cluster as usize,
+                            x + position.x_offset as f32 * h_scale,
+                            position.y_offset as f32 * v_scale,
+                        );
+                        x += position.x_advance as f32 * h_scale;
+                    }
+                }
+                // fonts the shaper can't read keep one glyph per character
+                None => {
+                    let mut characters = section_text.char_indices().collect::<Vec<_>>();
+                    if run.level.is_rtl() {
+                        characters.reverse();
+                    }
+                    for (byte_index, character) in characters {
+                        let id = font.glyph_id(character);
+                        place(id, byte_index, x, 0.0);
+                        x += scaled_font.h_advance(id);
+                    }
+                }
+            }
+        }
+
+        let start_x = match horizontal_align {
+            HorizontalAlign::Left => min_x,
+            HorizontalAlign::Center => min_x + (max_x - min_x - x) / 2.0,
+            HorizontalAlign::Right => max_x - x,
+        };
+        for glyph in line_glyphs.iter_mut() {
+            glyph.glyph.position.x += start_x;
+        }
+        shaped_glyphs.extend(line_glyphs);
+    }
+    shaped_glyphs
+}
+
+/// Puts items in display order from their bidi levels: from the highest level down to the lowest
+/// odd level, every sequence of items at that level or higher is reversed
+fn reorder_by_level<T>(items: &mut [T], level: impl Fn(&T) -> u8) {
+    let highest = items.iter().map(&level).max().unwrap_or(0);
+    let lowest_odd = match items
+        .iter()
+        .map(&level)
+        .filter(|level| level % 2 == 1)
+        .min()
+    {
+        Some(lowest_odd) => lowest_odd,
+        None => return,
+    };
+    for current in (lowest_odd..=highest).rev() {
+        let mut start = 0;
+        while start < items.len() {
+            if level(&items[start]) < current {
+                start += 1;
+                continue;
+            }
+            let mut end = start;
+            while end < items.len() && level(&items[end]) >= current {
+                end += 1;
+            }
+            items[start..end].reverse();
+            start = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reorder_by_level;
+
+    #[test]
+    fn runs_are_reordered_by_level() {
+        // left-to-right text with an embedded right-to-left phrase containing a number
+        let mut runs = [(0, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        reorder_by_level(&mut runs, |(level, _name)| *level);
+        let names = runs.iter().map(|(_level, name)| *name).collect::<String>();
+        assert_eq!(names, "adcbe");
+
+        let mut runs = [(0, 'a'), (0, 'b')];
+        reorder_by_level(&mut runs, |(level, _name)| *level);
+        assert_eq!(runs, [(0, 'a'), (0, 'b')]);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{Font, TextSection, TextStyle};
+use bevy_asset::Handle;
+
+/// A block of text. `value` is drawn with `font` and the size and color of `style`, and is
+/// followed by `sections`, which have their own font, size and color. The alignment, wrapping
+/// and overflow of `style` apply to the whole text.
+#[derive(Debug, Default, Clone)]
+pub struct Text {
+    pub value: String,
+    pub font: Handle<Font>,
+    pub style: TextStyle,
+    pub sections: Vec<TextSection>,
+}
+
+impl Text {
+    /// All sections of the text, starting with `value`
+    pub fn all_sections(&self) -> Vec<TextSection> {
+        let mut sections = Vec::with_capacity(self.sections.len() + 1);
+        sections.push(TextSection {
+            value: self.value.clone(),
+            font: self.font.clone(),
+            font_size: self.style.font_size,
+            color: self.style.color,
+        });
+        sections.extend(self.sections.iter().cloned());
+        sections
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    DefaultTextPipeline, DrawableText, Font, FontAtlasSet, FontFallbacks, Text, TextError,
+};
+use bevy_asset::Assets;
+use bevy_ecs::{Bundle, Changed, Entity, Local, Or, Query, QuerySet, Res, ResMut, With};
+use bevy_math::{Mat4, Size, Vec3};
+use bevy_render::{
+    draw::{Draw, DrawContext, Drawable},
+    mesh::Mesh,
+    prelude::Msaa,
+    render_graph::base::MainPass,
+    renderer::{AssetRenderResourceBindings, RenderResourceBindings},
+    texture::Texture,
+};
+use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use glyph_brush_layout::{HorizontalAlign, VerticalAlign};
+
+/// The box that text in the 2d world is wrapped in, and clipped to when its style doesn't let it
+/// overflow. Unbounded text is only broken at line breaks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Text2dBounds {
+    pub size: Size,
+}
+
+impl Default for Text2dBounds {
+    fn default() -> Self {
+        Text2dBounds {
+            size: Size::new(f32::MAX, f32::MAX),
+        }
+    }
+}
+
+/// Text drawn in the 2d world. The alignment of its style sets which point of the text block is
+/// at the translation of the entity: the left, center or right horizontally, and the top, center
+/// or bottom vertically.
+#[derive(Bundle, Clone, Debug)]
+pub struct Text2dBundle {
+    pub text: Text,
+    pub bounds: Text2dBounds,
+    pub draw: Draw,
+    pub main_pass: MainPass,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl Default for Text2dBundle {
+    fn default() -> Self {
+        Text2dBundle {
+            text: Default::default(),
+            bounds: Default::default(),
+            draw: Draw {
+                is_transparent: true,
+                ..Default::default()
+            },
+            main_pass: MainPass,
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QueuedText2d {
+    entities: Vec<Entity>,
+}
+
+/// Lays out 2d text that changed, and text whose fonts were not loaded yet
+#[allow(clippy::too_many_arguments)]
+pub fn text2d_system(
+    mut queued_text: Local<QueuedText2d>,
+    mut textures: ResMut<Assets<Texture>>,
+    fonts: Res<Assets<Font>>,
+    font_fallbacks: Res<FontFallbacks>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
+    mut text_pipeline: ResMut<DefaultTextPipeline>,
+    mut text_queries: QuerySet<(
+        Query<
+            Entity,
+            (
+                With<Text2dBounds>,
+                Or<(Changed<Text>, Changed<Text2dBounds>)>,
+            ),
+        >,
+        Query<(&Text, &Text2dBounds)>,
+    )>,
+) {
+    for entity in text_queries.q0_mut().iter_mut() {
+        if !queued_text.entities.contains(&entity) {
+            queued_text.entities.push(entity);
+        }
+    }
+    if queued_text.entities.is_empty() {
+        return;
+    }
+
+    let mut new_queue = Vec::new();
+    let query = text_queries.q1_mut();
+    for entity in queued_text.entities.drain(..) {
+        if let Ok((text, bounds)) = query.get_mut(entity) {
+            let fallback_fonts = text
+                .style
+                .fallback_fonts
+                .iter()
+                .chain(font_fallbacks.fonts.iter())
+                .cloned()
+                .collect::<Vec<_>>();
+            match text_pipeline.queue_text(
+                entity,
+                &text.all_sections(),
+                &fallback_fonts,
+                &fonts,
+                text.style.alignment,
+                text.style.wrap,
+                text.style.overflow,
+                text.style.rendering,
+                bounds.size,
+                &mut font_atlas_set_storage,
+                &mut texture_atlases,
+                &mut textures,
+            ) {
+                // the fonts are still loading
+                Err(TextError::NoSuchFont) => new_queue.push(entity),
+                Err(e @ TextError::FailedToAddGlyph(_)) => {
+                    panic!("Fatal error when processing text: {}", e);
+                }
+                Ok(()) => {}
+            }
+        }
+    }
+    queued_text.entities = new_queue;
+}
+
+pub fn draw_text2d_system(
+    mut context: DrawContext,
+    msaa: Res<Msaa>,
+    meshes: Res<Assets<Mesh>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    mut query: Query<(Entity, &mut Draw, &Text, &GlobalTransform), With<Text2dBounds>>,
+) {
+    let font_quad = meshes.get(&QUAD_HANDLE).unwrap();
+    let vertex_buffer_descriptor = font_quad.get_vertex_buffer_descriptor();
+
+    for (entity, mut draw, text, global_transform) in query.iter_mut() {
+        if !draw.is_visible {
+            continue;
+        }
+
+        if let Some(text_glyphs) = text_pipeline.get_glyphs(&entity) {
+            // glyphs are positioned from the bottom left of the text block
+            let size = text_glyphs.size;
+            let x = match text.style.alignment.horizontal {
+                HorizontalAlign::Left => 0.0,
+                HorizontalAlign::Center => -size.width / 2.0,
+                HorizontalAlign::Right => -size.width,
+            };
+            let y = match text.style.alignment.vertical {
+                VerticalAlign::Top => -size.height,
+                VerticalAlign::Center => -size.height / 2.0,
+                VerticalAlign::Bottom => 0.0,
+            };
+            let transform =
+                global_transform.compute_matrix() * Mat4::from_translation(Vec3::new(x, y, 0.0));
+            let colors = std::iter::once(text.style.color)
+                .chain(text.sections.iter().map(|section| section.color))
+                .collect::<Vec<_>>();
+
+            let mut drawable_text = DrawableText {
+                render_resource_bindings: &mut render_resource_bindings,
+                asset_render_resource_bindings: &mut asset_render_resource_bindings,
+                transform,
+                clip: DrawableText::unclipped(),
+                msaa: &msaa,
+                text_glyphs: &text_glyphs.glyphs,
+                font_quad_vertex_descriptor: &vertex_buffer_descriptor,
+                colors: &colors,
+                rendering: text.style.rendering,
+            };
+
+            drawable_text.draw(&mut draw, &mut context).unwrap();
+        }
+    }
+}
@@ -99,6 +99,17 @@ impl Transform {
         value
     }
 
+    /// Interpolates between this transform at 0 and `other` at 1, with the rotation taking the
+    /// shortest path
+    #[inline]
+    pub fn lerp(&self, other: &Transform, t: f32) -> Self {
+        Transform {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.lerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+
     #[inline]
     pub fn apply_non_uniform_scale(&mut self, scale: Vec3) {
         self.scale *= scale;
@@ -0,0 +1,61 @@
+use super::Transform;
+
+/// Draws an entity that moves in fixed steps smoothly between its last two steps. Systems running
+/// in [FixedUpdate](bevy_core::FixedUpdate) move the entity with [TransformInterpolation::set]
+/// instead of changing its [Transform], which is interpolated every frame by the
+/// [overstep fraction](bevy_core::FixedTime::overstep_fraction) of the fixed time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformInterpolation {
+    pub previous: Transform,
+    pub current: Transform,
+}
+
+impl TransformInterpolation {
+    pub fn new(transform: Transform) -> Self {
+        TransformInterpolation {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Moves to the transform of a new step, and keeps the current one as the previous one
+    pub fn set(&mut self, transform: Transform) {
+        self.previous = self.current;
+        self.current = transform;
+    }
+
+    /// Sets the transform without interpolating from the previous one, like when teleporting
+    pub fn reset(&mut self, transform: Transform) {
+        self.previous = transform;
+        self.current = transform;
+    }
+
+    /// The transform between the previous step at 0 and the current one at 1
+    pub fn interpolate(&self, fraction: f32) -> Transform {
+        self.previous.lerp(&self.current, fraction)
+    }
+}
+
+impl Default for TransformInterpolation {
+    fn default() -> Self {
+        TransformInterpolation::new(Transform::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransformInterpolation;
+    use crate::components::Transform;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn interpolates_between_the_last_two_steps() {
+        let mut interpolation = TransformInterpolation::new(Transform::identity());
+        interpolation.set(Transform::from_translation(Vec3::new(2.0, 0.0, 0.0)));
+        interpolation.set(Transform::from_translation(Vec3::new(4.0, 0.0, 0.0)));
+        assert_eq!(
+            interpolation.interpolate(0.25).translation,
+            Vec3::new(2.5, 0.0, 0.0)
+        );
+    }
+}
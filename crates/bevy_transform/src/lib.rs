@@ -1,5 +1,6 @@
 pub mod components;
 pub mod hierarchy;
+pub mod transform_interpolation_system;
 pub mod transform_propagate_system;
 
 pub mod prelude {
@@ -7,6 +8,7 @@ pub mod prelude {
 }
 
 use bevy_app::{prelude::*, startup_stage};
+use bevy_core::FixedTime;
 use bevy_reflect::RegisterTypeBuilder;
 use prelude::{parent_update_system, Children, GlobalTransform, Parent, PreviousParent, Transform};
 
@@ -20,6 +22,7 @@ impl Plugin for TransformPlugin {
             .register_type::<PreviousParent>()
             .register_type::<Transform>()
             .register_type::<GlobalTransform>()
+            .init_resource::<FixedTime>()
             // add transform systems to startup so the first update is "correct"
             .add_startup_system_to_stage(startup_stage::POST_STARTUP, parent_update_system)
             .add_startup_system_to_stage(
@@ -27,6 +30,10 @@ impl Plugin for TransformPlugin {
                 transform_propagate_system::transform_propagate_system,
             )
             .add_system_to_stage(stage::POST_UPDATE, parent_update_system)
+            .add_system_to_stage(
+                stage::POST_UPDATE,
+                transform_interpolation_system::transform_interpolation_system,
+            )
             .add_system_to_stage(
                 stage::POST_UPDATE,
                 transform_propagate_system::transform_propagate_system,
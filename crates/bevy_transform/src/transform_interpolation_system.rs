@@ -0,0 +1,18 @@
+use crate::components::{Transform, TransformInterpolation};
+use bevy_core::FixedTime;
+use bevy_ecs::{Query, Res};
+
+/// Sets the [Transform] of entities moving in fixed steps between their last two steps, so that
+/// they move smoothly at any frame rate
+pub fn transform_interpolation_system(
+    fixed_time: Res<FixedTime>,
+    mut query: Query<(&TransformInterpolation, &mut Transform)>,
+) {
+    let fraction = fixed_time.overstep_fraction();
+    for (interpolation, mut transform) in query.iter_mut() {
+        let interpolated = interpolation.interpolate(fraction);
+        if *transform != interpolated {
+            *transform = interpolated;
+        }
+    }
+}
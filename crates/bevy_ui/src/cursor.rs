@@ -0,0 +1,127 @@
+use crate::{entity::ImageBundle, Display, FocusPolicy, PositionType, Style, UiScale, Val, ZIndex};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Commands, Entity, Local, Query, Res, ResMut};
+use bevy_math::{Rect, Vec2};
+use bevy_render::texture::Texture;
+use bevy_sprite::ColorMaterial;
+use bevy_window::{CursorMoved, Windows};
+
+/// An image drawn in place of the cursor of the system over the primary window, which is hidden
+/// while the image is set. The image is drawn by the ui at the size of its texture, above all the
+/// other nodes.
+///
+/// Standard shapes of the cursor of the system are set with
+/// [Window::set_cursor_icon](bevy_window::Window::set_cursor_icon).
+#[derive(Debug, Clone, Default)]
+pub struct CustomCursor {
+    pub image: Option<Handle<Texture>>,
+    /// The point of the image at the position of the cursor, in pixels from the top left corner
+    /// of the image
+    pub hotspot: Vec2,
+}
+
+#[derive(Default)]
+pub struct CustomCursorState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+    entity: Option<Entity>,
+    /// The texture the material of the node was made from
+    material: Option<(Handle<Texture>, Handle<ColorMaterial>)>,
+    shown: bool,
+}
+
+/// Moves the node of the [CustomCursor] with the cursor, and hides the cursor of the system while
+/// the image is shown
+#[allow(clippy::too_many_arguments)]
+pub fn custom_cursor_system(
+    commands: &mut Commands,
+    mut state: Local<CustomCursorState>,
+    custom_cursor: Res<CustomCursor>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    textures: Res<Assets<Texture>>,
+    mut windows: ResMut<Windows>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut node_query: Query<(&mut Style, &mut Handle<ColorMaterial>)>,
+) {
+    let state = &mut *state;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+
+    let image = custom_cursor
+        .image
+        .as_ref()
+        .and_then(|image| textures.get(image).map(|texture| (image, texture)));
+    let shown = image.is_some();
+    if shown != state.shown {
+        state.shown = shown;
+        if let Some(window) = windows.get_primary_mut() {
+            window.set_cursor_visibility(!shown);
+        }
+    }
+    let (image, texture) = match image {
+        Some(image) => image,
+        None => {
+            if let Some((mut style, _material)) = state
+                .entity
+                .and_then(|entity| node_query.get_mut(entity).ok())
+            {
+                if style.display != Display::None {
+                    style.display = Display::None;
+                }
+            }
+            return;
+        }
+    };
+
+    let material = match &state.material {
+        Some((texture, material)) if texture == image => material.clone(),
+        _ => {
+            let material = materials.add(image.clone().into());
+            state.material = Some((image.clone(), material.clone()));
+            material
+        }
+    };
+    // the hotspot is from the top left corner, while the ui goes up from the bottom left corner
+    let size = Vec2::new(texture.size.width as f32, texture.size.height as f32);
+    let left = state.cursor_position.x() - custom_cursor.hotspot.x();
+    let bottom = state.cursor_position.y() - (size.y() - custom_cursor.hotspot.y());
+
+    let entity = match state.entity {
+        Some(entity) => entity,
+        None => {
+            // the node is moved once it was spawned
+            let entity = commands
+                .spawn(ImageBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    material,
+                    ..Default::default()
+                })
+                .with(FocusPolicy::Pass)
+                .with(ZIndex::Global(i32::MAX))
+                .current_entity()
+                .unwrap();
+            state.entity = Some(entity);
+            return;
+        }
+    };
+    if let Ok((mut style, mut node_material)) = node_query.get_mut(entity) {
+        if *node_material != material {
+            *node_material = material;
+        }
+        let position = Rect {
+            left: Val::Px(left),
+            bottom: Val::Px(bottom),
+            ..Default::default()
+        };
+        if style.display != Display::Flex || style.position != position {
+            style.display = Display::Flex;
+            style.position = position;
+        }
+    }
+}
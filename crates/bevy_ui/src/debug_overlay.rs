@@ -0,0 +1,394 @@
+use crate::{
+    entity::{NodeBundle, TextBundle},
+    widget::Text,
+    AlignItems, Display, FlexDirection, FocusPolicy, PositionType, Style, Val, ZIndex,
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{Commands, Entity, FromResources, Local, Query, Res, ResMut, Resources};
+use bevy_math::{Rect, Size};
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use bevy_text::{Font, FontFallbacks, TextStyle};
+use std::fmt;
+
+/// An immediate mode overlay for debugging, drawn above the rest of the ui in the top left corner
+/// of the window. Systems add lines of text, panels of values and plots to it every frame, and
+/// they are shown until the next frame, without spawning nodes for them.
+#[derive(Debug, Clone)]
+pub struct DebugOverlay {
+    /// The font of the overlay. The first of the [FontFallbacks] is used when it isn't set, and
+    /// nothing is shown without either.
+    pub font: Option<Handle<Font>>,
+    pub font_size: f32,
+    pub text_color: Color,
+    items: Vec<DebugOverlayItem>,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        DebugOverlay {
+            font: None,
+            font_size: 16.0,
+            text_color: Color::WHITE,
+            items: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DebugOverlayItem {
+    Text(String),
+    Panel(DebugPanel),
+    Plot { label: String, values: Vec<f32> },
+}
+
+/// A panel of named values in the [DebugOverlay], under a title
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DebugPanel {
+    title: String,
+    entries: Vec<(String, String)>,
+}
+
+impl DebugPanel {
+    /// Adds a line with a name and its value
+    pub fn value(&mut self, name: impl Into<String>, value: impl fmt::Display) -> &mut Self {
+        self.entries.push((name.into(), value.to_string()));
+        self
+    }
+}
+
+impl DebugOverlay {
+    /// Adds a line of text
+    pub fn text(&mut self, text: impl Into<String>) {
+        self.items.push(DebugOverlayItem::Text(text.into()));
+    }
+
+    /// Adds a panel, which values are added to with [DebugPanel::value]
+    pub fn panel(&mut self, title: impl Into<String>) -> &mut DebugPanel {
+        self.items.push(DebugOverlayItem::Panel(DebugPanel {
+            title: title.into(),
+            entries: Vec::new(),
+        }));
+        match self.items.last_mut() {
+            Some(DebugOverlayItem::Panel(panel)) => panel,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Adds a bar plot of `values`, scaled from zero to the largest value, under its label and the
+    /// last value
+    pub fn plot(&mut self, label: impl Into<String>, values: impl IntoIterator<Item = f32>) {
+        self.items.push(DebugOverlayItem::Plot {
+            label: label.into(),
+            values: values.into_iter().collect(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+pub struct DebugOverlayMaterials {
+    pub background: Handle<ColorMaterial>,
+    pub plot: Handle<ColorMaterial>,
+    pub transparent: Handle<ColorMaterial>,
+}
+
+impl FromResources for DebugOverlayMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        DebugOverlayMaterials {
+            background: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.6).into()),
+            plot: materials.add(Color::rgb(0.3, 0.8, 0.4).into()),
+            transparent: materials.add(Color::NONE.into()),
+        }
+    }
+}
+
+/// The nodes showing one item of the overlay
+#[derive(Debug)]
+struct DebugOverlaySlot {
+    entity: Entity,
+    title: Entity,
+    names: Entity,
+    values: Entity,
+    plot: Entity,
+    bars: Vec<Entity>,
+}
+
+#[derive(Debug, Default)]
+pub struct DebugOverlayState {
+    root: Option<Entity>,
+    slots: Vec<DebugOverlaySlot>,
+}
+
+/// The height of plots in logical pixels
+const PLOT_HEIGHT: f32 = 40.0;
+/// The width of the bars of plots in logical pixels
+const PLOT_BAR_WIDTH: f32 = 2.0;
+
+/// Shows the items added to the [DebugOverlay] since the last frame, and clears it. The nodes of
+/// the overlay are reused from frame to frame, and only changed when the items changed.
+pub fn debug_overlay_system(
+    commands: &mut Commands,
+    mut state: Local<DebugOverlayState>,
+    mut overlay: ResMut<DebugOverlay>,
+    materials: Res<DebugOverlayMaterials>,
+    font_fallbacks: Res<FontFallbacks>,
+    mut style_query: Query<&mut Style>,
+    mut text_query: Query<&mut Text>,
+) {
+    let items = std::mem::take(&mut overlay.items);
+    if items.is_empty() && state.slots.is_empty() {
+        return;
+    }
+    let font = match overlay
+        .font
+        .clone()
+        .or_else(|| font_fallbacks.fonts.first().cloned())
+    {
+        Some(font) => font,
+        None => return,
+    };
+    let state = &mut *state;
+
+    let root = *state.root.get_or_insert_with(|| {
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(8.0),
+                        top: Val::Px(8.0),
+                        ..Default::default()
+                    },
+                    flex_direction: FlexDirection::ColumnReverse,
+                    align_items: AlignItems::FlexStart,
+                    ..Default::default()
+                },
+                material: materials.transparent.clone(),
+                ..Default::default()
+            })
+            .with(FocusPolicy::Pass)
+            .with(ZIndex::Global(i32::MAX))
+            .current_entity()
+            .unwrap()
+    });
+    // new slots are filled in the next frame, once they were spawned
+    while state.slots.len() < items.len() {
+        let slot = spawn_slot(commands, &materials, root);
+        state.slots.push(slot);
+    }
+
+    let text_style = TextStyle {
+        font_size: overlay.font_size,
+        color: overlay.text_color,
+        ..Default::default()
+    };
+    for (index, slot) in state.slots.iter_mut().enumerate() {
+        let item = items.get(index);
+        set_display(&mut style_query, slot.entity, item.is_some());
+        let (title, names, values, plot) = match item {
+            Some(DebugOverlayItem::Text(text)) => (text.clone(), None, None, None),
+            Some(DebugOverlayItem::Panel(panel)) => {
+                let (names, values): (Vec<_>, Vec<_>) = panel.entries.iter().cloned().unzip();
+                (
+                    panel.title.clone(),
+                    Some(names.join("\n")),
+                    Some(values.join("\n")),
+                    None,
+                )
+            }
+            Some(DebugOverlayItem::Plot { label, values }) => (
+                format!("{}: {:.2}", label, values.last().cloned().unwrap_or(0.0)),
+                None,
+                None,
+                Some(&values[..]),
+            ),
+            None => (String::new(), None, None, None),
+        };
+
+        let mut set_text = |entity, value: Option<String>| {
+            set_display(&mut style_query, entity, value.is_some());
+            if let Ok(mut text) = text_query.get_mut(entity) {
+                let value = value.unwrap_or_default();
+                // only changed texts are laid out again
+                if text.value != value
+                    || text.font != font
+                    || text.style.font_size != text_style.font_size
+                    || text.style.color != text_style.color
+                {
+                    text.value = value;
+                    text.font = font.clone();
+                    text.style = text_style.clone();
+                }
+            }
+        };
+        set_text(slot.title, Some(title));
+        set_text(slot.names, names);
+        set_text(slot.values, values);
+
+        set_display(&mut style_query, slot.plot, plot.is_some());
+        let heights = bar_heights(plot.unwrap_or(&[]));
+        while slot.bars.len() < heights.len() {
+            let bar = commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..Default::default()
+                    },
+                    material: materials.plot.clone(),
+                    ..Default::default()
+                })
+                .with(FocusPolicy::Pass)
+                .current_entity()
+                .unwrap();
+            commands.push_children(slot.plot, &[bar]);
+            slot.bars.push(bar);
+        }
+        for (index, bar) in slot.bars.iter().enumerate() {
+            if let Ok(mut style) = style_query.get_mut(*bar) {
+                let (display, size) = match heights.get(index) {
+                    Some(height) => (
+                        Display::Flex,
+                        Size::new(Val::Px(PLOT_BAR_WIDTH), Val::Percent(*height * 100.0)),
+                    ),
+                    None => (Display::None, style.size),
+                };
+                if style.display != display || style.size != size {
+                    style.display = display;
+                    style.size = size;
+                }
+            }
+        }
+    }
+}
+
+fn spawn_slot(
+    commands: &mut Commands,
+    materials: &DebugOverlayMaterials,
+    root: Entity,
+) -> DebugOverlaySlot {
+    let text = || TextBundle {
+        style: Style {
+            display: Display::None,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let title = commands.spawn(text()).current_entity().unwrap();
+    let names = commands.spawn(text()).current_entity().unwrap();
+    let values = commands
+        .spawn(TextBundle {
+            style: Style {
+                display: Display::None,
+                margin: Rect {
+                    left: Val::Px(12.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .current_entity()
+        .unwrap();
+    let entries = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                ..Default::default()
+            },
+            material: materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with(FocusPolicy::Pass)
+        .current_entity()
+        .unwrap();
+    commands.push_children(entries, &[names, values]);
+    let plot = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                size: Size::new(Val::Auto, Val::Px(PLOT_HEIGHT)),
+                margin: Rect {
+                    top: Val::Px(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: materials.transparent.clone(),
+            ..Default::default()
+        })
+        .with(FocusPolicy::Pass)
+        .current_entity()
+        .unwrap();
+    let entity = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::FlexStart,
+                padding: Rect::all(Val::Px(4.0)),
+                margin: Rect {
+                    bottom: Val::Px(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: materials.background.clone(),
+            ..Default::default()
+        })
+        .with(FocusPolicy::Pass)
+        .current_entity()
+        .unwrap();
+    commands
+        .push_children(entity, &[title, entries, plot])
+        .push_children(root, &[entity]);
+    DebugOverlaySlot {
+        entity,
+        title,
+        names,
+        values,
+        plot,
+        bars: Vec::new(),
+    }
+}
+
+/// Shows or hides a node. Hidden nodes and their descendants take no space and aren't drawn.
+fn set_display(style_query: &mut Query<&mut Style>, entity: Entity, shown: bool) {
+    if let Ok(mut style) = style_query.get_mut(entity) {
+        let display = if shown { Display::Flex } else { Display::None };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+/// The heights of the bars of a plot, as fractions of the height of the plot
+fn bar_heights(values: &[f32]) -> Vec<f32> {
+    let max = values.iter().cloned().fold(0.0, f32::max);
+    values
+        .iter()
+        .map(|value| if max > 0.0 { value.max(0.0) / max } else { 0.0 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bar_heights;
+
+    #[test]
+    fn plots_scale_to_the_largest_value() {
+        assert_eq!(
+            bar_heights(&[1.0, 4.0, -2.0, 2.0]),
+            vec![0.25, 1.0, 0.0, 0.5]
+        );
+        assert_eq!(bar_heights(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
+}
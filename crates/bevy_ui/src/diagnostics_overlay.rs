@@ -0,0 +1,102 @@
+use crate::DebugOverlay;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticId, Diagnostics, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::{Res, ResMut};
+
+/// An App Plugin that shows the fps, the frame time, the entity count and other diagnostics in
+/// the [DebugOverlay]. Only the diagnostics added to the app are shown, like the ones of the
+/// [FrameTimeDiagnosticsPlugin] and of the [EntityCountDiagnosticsPlugin].
+pub struct DiagnosticsOverlayPlugin {
+    /// The diagnostics shown after the fps, the frame time and the entity count
+    pub diagnostics: Vec<DiagnosticId>,
+    /// Whether the frame times are plotted under the values
+    pub plot_frame_time: bool,
+}
+
+impl Default for DiagnosticsOverlayPlugin {
+    fn default() -> Self {
+        DiagnosticsOverlayPlugin {
+            diagnostics: Vec::new(),
+            plot_frame_time: true,
+        }
+    }
+}
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_resource(DiagnosticsOverlay {
+            shown: true,
+            diagnostics: self.diagnostics.clone(),
+            plot_frame_time: self.plot_frame_time,
+        })
+        .add_system(diagnostics_overlay_system);
+    }
+}
+
+/// The configuration of the [DiagnosticsOverlayPlugin], which can be changed while the app runs
+#[derive(Debug, Clone)]
+pub struct DiagnosticsOverlay {
+    pub shown: bool,
+    pub diagnostics: Vec<DiagnosticId>,
+    pub plot_frame_time: bool,
+}
+
+fn latest_value(diagnostic: &Diagnostic) -> Option<f64> {
+    diagnostic
+        .measurements()
+        .last()
+        .map(|measurement| measurement.value)
+}
+
+/// Adds a panel of diagnostics to the [DebugOverlay] every frame
+pub fn diagnostics_overlay_system(
+    config: Res<DiagnosticsOverlay>,
+    diagnostics: Res<Diagnostics>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if !config.shown {
+        return;
+    }
+
+    let panel = overlay.panel("Diagnostics");
+    if let Some(fps) = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|diagnostic| diagnostic.average())
+    {
+        panel.value("fps", format!("{:.0}", fps));
+    }
+    if let Some(frame_time) = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(latest_value)
+    {
+        panel.value("frame time", format!("{:.2} ms", frame_time * 1000.0));
+    }
+    if let Some(entity_count) = diagnostics
+        .get(EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+        .and_then(latest_value)
+    {
+        panel.value("entities", entity_count as u64);
+    }
+    for diagnostic in config
+        .diagnostics
+        .iter()
+        .filter_map(|id| diagnostics.get(*id))
+    {
+        if let Some(value) = latest_value(diagnostic) {
+            panel.value(diagnostic.name.clone(), format!("{:.3}", value));
+        }
+    }
+
+    if config.plot_frame_time {
+        if let Some(frame_time) = diagnostics.get(FrameTimeDiagnosticsPlugin::FRAME_TIME) {
+            overlay.plot(
+                "frame time (ms)",
+                frame_time
+                    .measurements()
+                    .map(|measurement| (measurement.value * 1000.0) as f32),
+            );
+        }
+    }
+}
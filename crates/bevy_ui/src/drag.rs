@@ -0,0 +1,320 @@
+use crate::{Disabled, FocusPolicy, Interaction, Node, Style, UiScale, UiTexturePass, Val};
+use bevy_app::{EventReader, Events};
+use bevy_core::FloatOrd;
+use bevy_ecs::prelude::*;
+use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+use bevy_math::{Rect, Vec2};
+use bevy_render::render_graph::base::MainPass;
+use bevy_transform::prelude::{GlobalTransform, Parent};
+use bevy_window::CursorMoved;
+
+/// A node that can be dragged with the mouse or by touch. Pressing a node starts dragging the
+/// closest draggable node among it and its ancestors, unless a node with an [Interaction], like a
+/// button, is in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Draggable {
+    /// Moves the node with the cursor, by offsetting the left and bottom of its [Style::position]
+    /// in pixels, or its right and top when only those are set. Without it, dragging only sends
+    /// events.
+    pub moves_node: bool,
+    /// Keeps the node within the bounds of its parent while it is moved
+    pub constrain_to_parent: bool,
+}
+
+impl Default for Draggable {
+    fn default() -> Self {
+        Draggable {
+            moves_node: true,
+            constrain_to_parent: false,
+        }
+    }
+}
+
+/// A node that [Draggable] nodes can be dropped on
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DropTarget;
+
+/// Sent when the cursor first moves after pressing a [Draggable] node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragStart {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+/// Sent when the cursor moves while a node is dragged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag {
+    pub entity: Entity,
+    pub position: Vec2,
+    /// The distance the node moved by. It is smaller than the distance the cursor moved by when
+    /// the node is constrained to its parent.
+    pub delta: Vec2,
+}
+
+/// Sent when a dragged node is released
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragEnd {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+/// Sent when a dragged node is released over a [DropTarget], after its [DragEnd]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drop {
+    pub entity: Entity,
+    pub target: Entity,
+    pub position: Vec2,
+}
+
+#[derive(Default)]
+pub struct DragState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+    pressed: Option<Entity>,
+    dragging: bool,
+}
+
+/// Drags [Draggable] nodes with the cursor, and sends the drag events
+#[allow(clippy::too_many_arguments)]
+pub fn ui_drag_system(
+    mut state: Local<DragState>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    touches_input: Res<Touches>,
+    mut events: (
+        ResMut<Events<DragStart>>,
+        ResMut<Events<Drag>>,
+        ResMut<Events<DragEnd>>,
+        ResMut<Events<Drop>>,
+    ),
+    // world space and texture nodes are not on the screen
+    node_query: Query<
+        (Entity, &Node, &GlobalTransform, Option<&FocusPolicy>),
+        (Without<MainPass>, Without<UiTexturePass>),
+    >,
+    draggable_query: Query<(
+        Option<&Draggable>,
+        Option<&Interaction>,
+        Option<&Disabled>,
+        Option<&Parent>,
+    )>,
+    drop_target_query: Query<(Entity, &Node, &GlobalTransform), With<DropTarget>>,
+    mut style_query: Query<&mut Style>,
+) {
+    let (drag_start_events, drag_events, drag_end_events, drop_events) = &mut events;
+    let previous_position = state.cursor_position;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+    if let Some(touch) = touches_input.get_pressed(0) {
+        state.cursor_position = ui_scale.to_ui(touch.position());
+    }
+    let position = state.cursor_position;
+
+    if mouse_button_input.just_pressed(MouseButton::Left) || touches_input.just_pressed(0) {
+        let mut hovered_nodes = node_query
+            .iter()
+            .filter(|(_entity, node, global_transform, focus_policy)| {
+                *focus_policy != Some(&FocusPolicy::Pass)
+                    && contains(node, global_transform, position)
+            })
+            .collect::<Vec<_>>();
+        hovered_nodes.sort_by_key(|(_entity, _node, global_transform, _focus_policy)| {
+            -FloatOrd(global_transform.translation.z())
+        });
+        state.pressed =
+            hovered_nodes
+                .first()
+                .and_then(|(entity, _node, _global_transform, _focus_policy)| {
+                    pressed_draggable(&draggable_query, *entity)
+                });
+        // a touch starts where the finger is put down
+        if touches_input.just_pressed(0) {
+            return;
+        }
+    }
+
+    let entity = match state.pressed {
+        Some(entity) => entity,
+        None => return,
+    };
+    let (draggable, parent) = match draggable_query.get(entity) {
+        Ok((draggable, _interaction, _disabled, parent)) => (
+            draggable.cloned().unwrap_or_default(),
+            parent.map(|parent| parent.0),
+        ),
+        // the node was despawned
+        Err(_) => {
+            state.pressed = None;
+            state.dragging = false;
+            return;
+        }
+    };
+
+    if position != previous_position {
+        if !state.dragging {
+            drag_start_events.send(DragStart {
+                entity,
+                position: previous_position,
+            });
+            state.dragging = true;
+        }
+
+        let mut delta = position - previous_position;
+        if draggable.moves_node {
+            if draggable.constrain_to_parent {
+                let bounds = |entity| {
+                    node_query.get(entity).ok().map(
+                        |(_entity, node, global_transform, _focus_policy)| {
+                            let center = global_transform.translation.truncate();
+                            (center - node.size / 2.0, center + node.size / 2.0)
+                        },
+                    )
+                };
+                if let (Some(node_bounds), Some(parent_bounds)) =
+                    (bounds(entity), parent.and_then(bounds))
+                {
+                    delta = constrain(delta, node_bounds, parent_bounds);
+                }
+            }
+            if let Ok(mut style) = style_query.get_mut(entity) {
+                offset_position(&mut style.position, delta);
+            }
+        }
+        drag_events.send(Drag {
+            entity,
+            position,
+            delta,
+        });
+    }
+
+    if mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0) {
+        if state.dragging {
+            drag_end_events.send(DragEnd { entity, position });
+            let target = drop_target_query
+                .iter()
+                .filter(|(target, node, global_transform)| {
+                    *target != entity && contains(node, global_transform, position)
+                })
+                .max_by_key(|(_target, _node, global_transform)| {
+                    FloatOrd(global_transform.translation.z())
+                });
+            if let Some((target, _node, _global_transform)) = target {
+                drop_events.send(Drop {
+                    entity,
+                    target,
+                    position,
+                });
+            }
+        }
+        state.pressed = None;
+        state.dragging = false;
+    }
+}
+
+fn contains(node: &Node, global_transform: &GlobalTransform, point: Vec2) -> bool {
+    let center = global_transform.translation.truncate();
+    let min = center - node.size / 2.0;
+    let max = center + node.size / 2.0;
+    point.x() >= min.x()
+        && point.x() < max.x()
+        && point.y() >= min.y()
+        && point.y() < max.y()
+        && node.is_visible_at(point)
+}
+
+/// Returns the closest [Draggable] node among `entity` and its ancestors, up to the first node
+/// that reacts to the cursor itself
+fn pressed_draggable(
+    draggable_query: &Query<(
+        Option<&Draggable>,
+        Option<&Interaction>,
+        Option<&Disabled>,
+        Option<&Parent>,
+    )>,
+    mut entity: Entity,
+) -> Option<Entity> {
+    loop {
+        let (draggable, interaction, disabled, parent) = draggable_query.get(entity).ok()?;
+        if draggable.is_some() {
+            return if disabled.is_none() {
+                Some(entity)
+            } else {
+                None
+            };
+        }
+        if interaction.is_some() {
+            return None;
+        }
+        entity = parent?.0;
+    }
+}
+
+/// Limits `delta` so that a node with the given `(min, max)` bounds stays within its parent
+fn constrain(
+    delta: Vec2,
+    (min, max): (Vec2, Vec2),
+    (parent_min, parent_max): (Vec2, Vec2),
+) -> Vec2 {
+    let low = parent_min - min;
+    let high = parent_max - max;
+    Vec2::new(
+        delta.x().min(high.x()).max(low.x().min(0.0)),
+        delta.y().min(high.y()).max(low.y().min(0.0)),
+    )
+}
+
+fn offset_position(position: &mut Rect<Val>, delta: Vec2) {
+    fn offset(val: &mut Val, by: f32) {
+        let value = match *val {
+            Val::Px(value) => value,
+            _ => 0.0,
+        };
+        *val = Val::Px(value + by);
+    }
+    let is_set = |val: Val| matches!(val, Val::Px(_) | Val::Percent(_));
+    if !is_set(position.left) && is_set(position.right) {
+        offset(&mut position.right, -delta.x());
+    } else {
+        offset(&mut position.left, delta.x());
+    }
+    if !is_set(position.bottom) && is_set(position.top) {
+        offset(&mut position.top, -delta.y());
+    } else {
+        offset(&mut position.bottom, delta.y());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{constrain, offset_position};
+    use crate::Val;
+    use bevy_math::{Rect, Vec2};
+
+    #[test]
+    fn constrained_nodes_stay_in_their_parent() {
+        let node = (Vec2::new(10.0, 10.0), Vec2::new(30.0, 30.0));
+        let parent = (Vec2::new(0.0, 0.0), Vec2::new(100.0, 40.0));
+        assert_eq!(
+            constrain(Vec2::new(-20.0, 20.0), node, parent),
+            Vec2::new(-10.0, 10.0)
+        );
+        assert_eq!(
+            constrain(Vec2::new(5.0, -5.0), node, parent),
+            Vec2::new(5.0, -5.0)
+        );
+    }
+
+    #[test]
+    fn dragging_offsets_the_set_sides() {
+        let mut position = Rect {
+            right: Val::Px(20.0),
+            ..Default::default()
+        };
+        offset_position(&mut position, Vec2::new(5.0, 8.0));
+        assert_eq!(position.right, Val::Px(15.0));
+        assert_eq!(position.left, Val::Undefined);
+        assert_eq!(position.bottom, Val::Px(8.0));
+    }
+}
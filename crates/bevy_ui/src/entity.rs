@@ -1,8 +1,8 @@
 use super::Node;
 use crate::{
-    render::UI_PIPELINE_HANDLE,
-    widget::{Button, Image, Text},
-    CalculatedSize, FocusPolicy, Interaction, Style,
+    render::{UI_IMAGE_SLICES_PIPELINE_HANDLE, UI_PIPELINE_HANDLE},
+    widget::{Button, ButtonStyle, Image, ImageSlices, Text, TextInput},
+    CalculatedSize, FocusPolicy, Focusable, Interaction, Overflow, ScrollPosition, Style,
 };
 use bevy_asset::Handle;
 use bevy_ecs::Bundle;
@@ -78,6 +78,38 @@ impl Default for ImageBundle {
     }
 }
 
+/// A node drawing the texture of its material in nine slices, see [ImageSlices]
+#[derive(Bundle, Clone, Debug)]
+pub struct ImageSlicesBundle {
+    pub node: Node,
+    pub style: Style,
+    pub slices: ImageSlices,
+    pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
+    pub material: Handle<ColorMaterial>,
+    pub draw: Draw,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl Default for ImageSlicesBundle {
+    fn default() -> Self {
+        ImageSlicesBundle {
+            mesh: QUAD_HANDLE,
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                UI_IMAGE_SLICES_PIPELINE_HANDLE,
+            )]),
+            node: Default::default(),
+            slices: Default::default(),
+            style: Default::default(),
+            material: Default::default(),
+            draw: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
 #[derive(Bundle, Clone, Debug)]
 pub struct TextBundle {
     pub node: Node,
@@ -112,9 +144,11 @@ impl Default for TextBundle {
 pub struct ButtonBundle {
     pub node: Node,
     pub button: Button,
+    pub button_style: ButtonStyle,
     pub style: Style,
     pub interaction: Interaction,
     pub focus_policy: FocusPolicy,
+    pub focusable: Focusable,
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
@@ -127,12 +161,14 @@ impl Default for ButtonBundle {
     fn default() -> Self {
         ButtonBundle {
             button: Button,
+            button_style: Default::default(),
             mesh: QUAD_HANDLE,
             render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
                 UI_PIPELINE_HANDLE,
             )]),
             interaction: Default::default(),
             focus_policy: Default::default(),
+            focusable: Default::default(),
             node: Default::default(),
             style: Default::default(),
             material: Default::default(),
@@ -143,6 +179,47 @@ impl Default for ButtonBundle {
     }
 }
 
+/// A [TextInput] on a background. The default style clips text that is longer than the node.
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    pub node: Node,
+    pub text_input: TextInput,
+    pub style: Style,
+    pub scroll_position: ScrollPosition,
+    pub interaction: Interaction,
+    pub focus_policy: FocusPolicy,
+    pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
+    pub material: Handle<ColorMaterial>,
+    pub draw: Draw,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        TextInputBundle {
+            mesh: QUAD_HANDLE,
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                UI_PIPELINE_HANDLE,
+            )]),
+            style: Style {
+                overflow: Overflow::Scroll,
+                ..Default::default()
+            },
+            text_input: Default::default(),
+            scroll_position: Default::default(),
+            interaction: Default::default(),
+            focus_policy: Default::default(),
+            node: Default::default(),
+            material: Default::default(),
+            draw: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
 #[derive(Bundle, Debug)]
 pub struct UiCameraBundle {
     pub camera: Camera,
@@ -1,6 +1,6 @@
 use crate::{
     AlignContent, AlignItems, AlignSelf, Direction, Display, FlexDirection, FlexWrap,
-    JustifyContent, PositionType, Style, Val,
+    JustifyContent, Overflow, PositionType, Style, Val,
 };
 use bevy_math::{Rect, Size};
 use bevy_reflect::Reflect;
@@ -32,7 +32,7 @@ where
 impl From<&Style> for stretch::style::Style {
     fn from(value: &Style) -> Self {
         Self {
-            overflow: stretch::style::Overflow::Visible,
+            overflow: value.overflow.into(),
             display: value.display.into(),
             position_type: value.position_type.into(),
             direction: value.direction.into(),
@@ -71,6 +71,16 @@ impl From<Val> for stretch::style::Dimension {
     }
 }
 
+impl From<Overflow> for stretch::style::Overflow {
+    fn from(value: Overflow) -> Self {
+        match value {
+            Overflow::Visible => stretch::style::Overflow::Visible,
+            Overflow::Hidden => stretch::style::Overflow::Hidden,
+            Overflow::Scroll => stretch::style::Overflow::Scroll,
+        }
+    }
+}
+
 impl From<AlignItems> for stretch::style::AlignItems {
     fn from(value: AlignItems) -> Self {
         match value {
@@ -122,7 +132,8 @@ impl From<Direction> for stretch::style::Direction {
 impl From<Display> for stretch::style::Display {
     fn from(value: Display) -> Self {
         match value {
-            Display::Flex => stretch::style::Display::Flex,
+            // the children of grids are positioned by the grid pass of the layout
+            Display::Flex | Display::Grid => stretch::style::Display::Flex,
             Display::None => stretch::style::Display::None,
         }
     }
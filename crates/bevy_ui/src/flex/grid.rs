@@ -0,0 +1,198 @@
+use crate::{GridPlacement, GridTrack, Style, Val};
+use bevy_math::Size;
+
+/// The cells a child of a grid is placed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GridArea {
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+}
+
+/// Returns the offset from the start of the grid and the size of each track, along one axis
+pub(crate) fn resolve_tracks(tracks: &[GridTrack], available: f32, gap: f32) -> Vec<(f32, f32)> {
+    let gaps = gap * tracks.len().saturating_sub(1) as f32;
+    let fixed: f32 = tracks
+        .iter()
+        .map(|track| match *track {
+            GridTrack::Px(size) => size,
+            GridTrack::Percent(percent) => available * percent / 100.0,
+            GridTrack::Fr(_) => 0.0,
+        })
+        .sum();
+    let fractions: f32 = tracks
+        .iter()
+        .map(|track| match *track {
+            GridTrack::Fr(fraction) => fraction.max(0.0),
+            _ => 0.0,
+        })
+        .sum();
+    let fraction_size = if fractions > 0.0 {
+        (available - gaps - fixed).max(0.0) / fractions
+    } else {
+        0.0
+    };
+
+    let mut offset = 0.0;
+    tracks
+        .iter()
+        .map(|track| {
+            let size = match *track {
+                GridTrack::Px(size) => size,
+                GridTrack::Percent(percent) => available * percent / 100.0,
+                GridTrack::Fr(fraction) => fraction.max(0.0) * fraction_size,
+            };
+            let resolved = (offset, size);
+            offset += size + gap;
+            resolved
+        })
+        .collect()
+}
+
+/// Places the children of a grid with `columns` columns, given their placement along the columns
+/// and the rows. Children with both a column and a row are placed first, then the others fill the
+/// free cells in order, row after row. Rows are added as needed.
+pub(crate) fn place_items(
+    columns: usize,
+    items: &[(GridPlacement, GridPlacement)],
+) -> Vec<GridArea> {
+    let columns = columns.max(1);
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+    let is_free = |occupied: &[Vec<bool>], area: &GridArea| {
+        (area.row..area.row + area.row_span).all(|row| {
+            (area.column..area.column + area.column_span)
+                .all(|column| occupied.get(row).map_or(true, |cells| !cells[column]))
+        })
+    };
+    let occupy = |occupied: &mut Vec<Vec<bool>>, area: &GridArea| {
+        while occupied.len() < area.row + area.row_span {
+            occupied.push(vec![false; columns]);
+        }
+        for row in area.row..area.row + area.row_span {
+            for column in area.column..area.column + area.column_span {
+                occupied[row][column] = true;
+            }
+        }
+    };
+    let area = |column: GridPlacement, row: GridPlacement| {
+        let column_start = column.start.unwrap_or(0).min(columns - 1);
+        GridArea {
+            column: column_start,
+            row: row.start.unwrap_or(0),
+            column_span: column.span.max(1).min(columns - column_start),
+            row_span: row.span.max(1),
+        }
+    };
+
+    let mut areas = vec![None; items.len()];
+    for (index, (column, row)) in items.iter().enumerate() {
+        if column.start.is_some() && row.start.is_some() {
+            let area = area(*column, *row);
+            occupy(&mut occupied, &area);
+            areas[index] = Some(area);
+        }
+    }
+
+    // the cursor only moves forward, so that children stay in order
+    let mut cursor = (0, 0);
+    for (index, (column, row)) in items.iter().enumerate() {
+        if areas[index].is_some() {
+            continue;
+        }
+        let mut area = area(*column, *row);
+        if column.start.is_some() {
+            // children with a column go down that column until they fit
+            while !is_free(&occupied, &area) {
+                area.row += 1;
+            }
+        } else if row.start.is_some() {
+            // children with a row go along that row, and past the end of the grid if it is full
+            area.column = 0;
+            while area.column + area.column_span <= columns && !is_free(&occupied, &area) {
+                area.column += 1;
+            }
+            if area.column + area.column_span > columns {
+                area.column = 0;
+                area.column_span = area.column_span.min(columns);
+            }
+        } else {
+            area.row = cursor.0;
+            area.column = cursor.1;
+            area.column_span = area.column_span.min(columns);
+            loop {
+                if area.column + area.column_span > columns {
+                    area.row += 1;
+                    area.column = 0;
+                } else if is_free(&occupied, &area) {
+                    break;
+                } else {
+                    area.column += 1;
+                }
+            }
+            cursor = (area.row, area.column + area.column_span);
+        }
+        occupy(&mut occupied, &area);
+        areas[index] = Some(area);
+    }
+
+    areas.into_iter().map(|area| area.unwrap()).collect()
+}
+
+/// Resolves a length in logical pixels, with percentages of `reference`
+pub(crate) fn resolve_val(val: Val, reference: f32) -> f32 {
+    match val {
+        Val::Px(value) => value,
+        Val::Percent(percent) => reference * percent / 100.0,
+        Val::Auto | Val::Undefined => 0.0,
+    }
+}
+
+/// The gap between the columns and rows of a grid node of the given width and height
+pub(crate) fn resolve_gap(style: &Style, size: Size) -> Size {
+    Size::new(
+        resolve_val(style.gap.width, size.width),
+        resolve_val(style.gap.height, size.height),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{place_items, resolve_tracks, GridArea};
+    use crate::{GridPlacement, GridTrack};
+
+    #[test]
+    fn grid_tracks_share_remaining_space() {
+        let tracks = [GridTrack::Px(100.0), GridTrack::Fr(1.0), GridTrack::Fr(3.0)];
+        assert_eq!(
+            resolve_tracks(&tracks, 520.0, 10.0),
+            vec![(0.0, 100.0), (110.0, 100.0), (220.0, 300.0)]
+        );
+    }
+
+    #[test]
+    fn grid_items_fill_free_cells() {
+        let auto = GridPlacement::default();
+        let items = [
+            (GridPlacement::start(1), GridPlacement::start(0)),
+            (auto, auto),
+            (GridPlacement::span(2), auto),
+            (auto, GridPlacement::span(2)),
+        ];
+        let area = |column, row, column_span, row_span| GridArea {
+            column,
+            row,
+            column_span,
+            row_span,
+        };
+        assert_eq!(
+            place_items(2, &items),
+            vec![
+                area(1, 0, 1, 1),
+                area(0, 0, 1, 1),
+                area(0, 1, 2, 1),
+                area(0, 2, 1, 2),
+            ]
+        );
+    }
+}
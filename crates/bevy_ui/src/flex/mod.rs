@@ -1,17 +1,27 @@
 mod convert;
+mod grid;
 
-use crate::{CalculatedSize, Node, Style};
+use crate::{
+    CalculatedSize, Display, FlexDirection, FlexWrap, Node, PositionType, ScrollPosition, Style,
+    UiScale, UiTexture, UiTextureCamera, Val,
+};
 use bevy_ecs::{Changed, Entity, Query, Res, ResMut, With, Without};
-use bevy_math::Vec2;
+use bevy_math::{Size, Vec2};
+use bevy_render::camera::Camera;
 use bevy_transform::prelude::{Children, Parent, Transform};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use bevy_window::{Window, WindowId, Windows};
-use std::fmt;
+use std::{collections::hash_map::Entry, fmt};
 use stretch::{number::Number, Stretch};
 
 pub struct FlexSurface {
     entity_to_stretch: HashMap<Entity, stretch::node::Node>,
     window_nodes: HashMap<WindowId, stretch::node::Node>,
+    /// The root nodes of ui texture cameras, by camera name
+    texture_nodes: HashMap<String, stretch::node::Node>,
+    /// The nodes whose stretch styles were adjusted after they were laid out, to place them in
+    /// grids, add gaps or keep their aspect ratio
+    adjusted_nodes: HashSet<Entity>,
     stretch: Stretch,
 }
 
@@ -20,6 +30,8 @@ impl fmt::Debug for FlexSurface {
         f.debug_struct("FlexSurface")
             .field("entity_to_stretch", &self.entity_to_stretch)
             .field("window_nodes", &self.window_nodes)
+            .field("texture_nodes", &self.texture_nodes)
+            .field("adjusted_nodes", &self.adjusted_nodes)
             .finish()
     }
 }
@@ -29,6 +41,8 @@ impl Default for FlexSurface {
         Self {
             entity_to_stretch: Default::default(),
             window_nodes: Default::default(),
+            texture_nodes: Default::default(),
+            adjusted_nodes: Default::default(),
             stretch: Stretch::new(),
         }
     }
@@ -38,7 +52,7 @@ impl FlexSurface {
     pub fn upsert_node(&mut self, entity: Entity, style: &Style) {
         let mut added = false;
         let stretch = &mut self.stretch;
-        let stretch_style = style.into();
+        let stretch_style = stretch_style(style, None);
         let stretch_node = self.entity_to_stretch.entry(entity).or_insert_with(|| {
             added = true;
             stretch.new_node(stretch_style, Vec::new()).unwrap()
@@ -53,7 +67,7 @@ impl FlexSurface {
 
     pub fn upsert_leaf(&mut self, entity: Entity, style: &Style, calculated_size: CalculatedSize) {
         let stretch = &mut self.stretch;
-        let stretch_style = style.into();
+        let stretch_style = stretch_style(style, Some(&calculated_size));
         let measure = Box::new(move |constraints: stretch::geometry::Size<Number>| {
             let mut size = stretch::geometry::Size {
                 width: calculated_size.size.width,
@@ -103,26 +117,11 @@ impl FlexSurface {
             .unwrap();
     }
 
-    pub fn update_window(&mut self, window: &Window) {
-        let stretch = &mut self.stretch;
-        let node = self.window_nodes.entry(window.id()).or_insert_with(|| {
-            stretch
-                .new_node(stretch::style::Style::default(), Vec::new())
-                .unwrap()
-        });
-
-        stretch
-            .set_style(
-                *node,
-                stretch::style::Style {
-                    size: stretch::geometry::Size {
-                        width: stretch::style::Dimension::Points(window.width() as f32),
-                        height: stretch::style::Dimension::Points(window.height() as f32),
-                    },
-                    ..Default::default()
-                },
-            )
-            .unwrap();
+    /// Sizes the root node of a window, in ui coordinates scaled by `ui_scale`
+    pub fn update_window(&mut self, window: &Window, ui_scale: &UiScale) {
+        let node = self.window_nodes.entry(window.id());
+        let size = ui_scale.to_ui(Vec2::new(window.width() as f32, window.height() as f32));
+        update_root_node(&mut self.stretch, node, Size::new(size.x(), size.y()));
     }
 
     pub fn set_window_children(
@@ -130,48 +129,401 @@ impl FlexSurface {
         window_id: WindowId,
         children: impl Iterator<Item = Entity>,
     ) {
-        let stretch_node = self.window_nodes.get(&window_id).unwrap();
+        let stretch_node = *self.window_nodes.get(&window_id).unwrap();
+        self.set_root_children(stretch_node, children);
+    }
+
+    /// Updates the root node of the nodes drawn by the ui texture camera named `camera_name`,
+    /// which has the size of its texture
+    pub fn update_texture(&mut self, camera_name: &str, size: Size) {
+        let node = self.texture_nodes.entry(camera_name.to_string());
+        update_root_node(&mut self.stretch, node, size);
+    }
+
+    pub fn set_texture_children(
+        &mut self,
+        camera_name: &str,
+        children: impl Iterator<Item = Entity>,
+    ) {
+        let stretch_node = *self.texture_nodes.get(camera_name).unwrap();
+        self.set_root_children(stretch_node, children);
+    }
+
+    fn set_root_children(
+        &mut self,
+        stretch_node: stretch::node::Node,
+        children: impl Iterator<Item = Entity>,
+    ) {
         let child_nodes = children
             .map(|e| *self.entity_to_stretch.get(&e).unwrap())
             .collect::<Vec<stretch::node::Node>>();
         self.stretch
-            .set_children(*stretch_node, child_nodes)
+            .set_children(stretch_node, child_nodes)
             .unwrap();
     }
 
-    pub fn compute_window_layouts(&mut self) {
-        for window_node in self.window_nodes.values() {
+    /// Computes the layouts of the nodes in windows and in ui textures
+    pub fn compute_layouts(&mut self) {
+        for root_node in self
+            .window_nodes
+            .values()
+            .chain(self.texture_nodes.values())
+        {
             self.stretch
-                .compute_layout(*window_node, stretch::geometry::Size::undefined())
+                .compute_layout(*root_node, stretch::geometry::Size::undefined())
                 .unwrap();
         }
     }
 
+    /// Places the children of a grid node in the cells of its tracks, as absolutely positioned
+    /// nodes. Children that are not sized fill their cells.
+    pub fn place_grid_items(
+        &self,
+        entity: Entity,
+        style: &Style,
+        children: &[ChildStyle],
+        adjusted: &mut HashMap<Entity, stretch::style::Style>,
+    ) {
+        let layout = match self.get_layout(entity) {
+            Ok(layout) => layout,
+            Err(_) => return,
+        };
+        let size = Size::new(layout.size.width, layout.size.height);
+        // percentages of padding are relative to the width, as in css
+        let left = grid::resolve_val(style.padding.left, size.width);
+        let right = grid::resolve_val(style.padding.right, size.width);
+        let top = grid::resolve_val(style.padding.top, size.width);
+        let bottom = grid::resolve_val(style.padding.bottom, size.width);
+        let content_size = Size::new(size.width - left - right, size.height - top - bottom);
+        let gap = grid::resolve_gap(style, content_size);
+
+        let items = children
+            .iter()
+            .filter(|(_entity, child_style, _calculated_size)| is_in_flow(child_style))
+            .collect::<Vec<_>>();
+        let mut columns = style.grid_template_columns.clone();
+        if columns.is_empty() {
+            columns.push(Default::default());
+        }
+        let areas = grid::place_items(
+            columns.len(),
+            &items
+                .iter()
+                .map(|(_entity, child_style, _calculated_size)| {
+                    (child_style.grid_column, child_style.grid_row)
+                })
+                .collect::<Vec<_>>(),
+        );
+        let mut rows = style.grid_template_rows.clone();
+        let row_count = areas
+            .iter()
+            .map(|area| area.row + area.row_span)
+            .max()
+            .unwrap_or(0);
+        while rows.len() < row_count {
+            rows.push(style.grid_auto_rows);
+        }
+        let columns = grid::resolve_tracks(&columns, content_size.width, gap.width);
+        let rows = grid::resolve_tracks(&rows, content_size.height, gap.height);
+        let span = |tracks: &[(f32, f32)], start: usize, span: usize| {
+            let (offset, _size) = tracks[start];
+            let (last_offset, last_size) = tracks[start + span - 1];
+            (offset, last_offset + last_size - offset)
+        };
+
+        for ((child, child_style, calculated_size), area) in items.into_iter().zip(areas) {
+            let (x, width) = span(&columns, area.column, area.column_span);
+            let (y, height) = span(&rows, area.row, area.row_span);
+            let fill = |val: Val, cell_size: f32, margin_start: Val, margin_end: Val| match val {
+                Val::Auto | Val::Undefined => stretch::style::Dimension::Points(
+                    cell_size
+                        - grid::resolve_val(margin_start, size.width)
+                        - grid::resolve_val(margin_end, size.width),
+                ),
+                val => val.into(),
+            };
+
+            // stretch has a flipped y-axis, so rows are placed from its bottom
+            let stretch_style = adjusted
+                .entry(*child)
+                .or_insert_with(|| stretch_style(child_style, *calculated_size));
+            stretch_style.position_type = stretch::style::PositionType::Absolute;
+            stretch_style.position = stretch::geometry::Rect {
+                start: stretch::style::Dimension::Points(left + x),
+                end: stretch::style::Dimension::Undefined,
+                top: stretch::style::Dimension::Undefined,
+                bottom: stretch::style::Dimension::Points(top + y),
+            };
+            stretch_style.size = stretch::geometry::Size {
+                width: fill(
+                    child_style.size.width,
+                    width,
+                    child_style.margin.left,
+                    child_style.margin.right,
+                ),
+                height: fill(
+                    child_style.size.height,
+                    height,
+                    child_style.margin.top,
+                    child_style.margin.bottom,
+                ),
+            };
+        }
+    }
+
+    /// Adds the gap of a flex node to the margins of its children: before each child that is not
+    /// the first of its line, and before each line but the first.
+    pub fn apply_flex_gap(
+        &self,
+        entity: Entity,
+        style: &Style,
+        children: &[ChildStyle],
+        adjusted: &mut HashMap<Entity, stretch::style::Style>,
+    ) {
+        let layout = match self.get_layout(entity) {
+            Ok(layout) => layout,
+            Err(_) => return,
+        };
+        let size = Size::new(layout.size.width, layout.size.height);
+        let gap = grid::resolve_gap(style, size);
+        let is_row = matches!(
+            style.flex_direction,
+            FlexDirection::Row | FlexDirection::RowReverse
+        );
+        let (main_gap, cross_gap) = if is_row {
+            (gap.width, gap.height)
+        } else {
+            (gap.height, gap.width)
+        };
+        if main_gap <= 0.0 && (cross_gap <= 0.0 || style.flex_wrap == FlexWrap::NoWrap) {
+            return;
+        }
+
+        let mut previous_main_offset = None;
+        let mut first_line = true;
+        for (child, child_style, calculated_size) in children.iter() {
+            if !is_in_flow(child_style) {
+                continue;
+            }
+            let child_layout = match self.get_layout(*child) {
+                Ok(child_layout) => child_layout,
+                Err(_) => continue,
+            };
+            // lines start where children go back to the start of the main axis
+            let location = child_layout.location;
+            let child_size = child_layout.size;
+            let main_offset = match style.flex_direction {
+                FlexDirection::Row => location.x,
+                FlexDirection::RowReverse => size.width - location.x - child_size.width,
+                FlexDirection::Column => location.y,
+                FlexDirection::ColumnReverse => size.height - location.y - child_size.height,
+            };
+            let starts_line = match previous_main_offset {
+                Some(previous_main_offset) => main_offset <= previous_main_offset,
+                None => false,
+            };
+            if starts_line {
+                first_line = false;
+            }
+            let first_in_line = previous_main_offset.is_none() || starts_line;
+            previous_main_offset = Some(main_offset);
+
+            let stretch_style = adjusted
+                .entry(*child)
+                .or_insert_with(|| stretch_style(child_style, *calculated_size));
+            let margin = &mut stretch_style.margin;
+            if !first_in_line {
+                let main_margin = match style.flex_direction {
+                    FlexDirection::Row => &mut margin.start,
+                    FlexDirection::RowReverse => &mut margin.end,
+                    // stretch has a flipped y-axis
+                    FlexDirection::Column => &mut margin.top,
+                    FlexDirection::ColumnReverse => &mut margin.bottom,
+                };
+                *main_margin = add_points(*main_margin, main_gap, size.width);
+            }
+            if !first_line && style.flex_wrap != FlexWrap::NoWrap {
+                let reverse = style.flex_wrap == FlexWrap::WrapReverse;
+                let cross_margin = match (is_row, reverse) {
+                    (true, false) => &mut margin.top,
+                    (true, true) => &mut margin.bottom,
+                    (false, false) => &mut margin.start,
+                    (false, true) => &mut margin.end,
+                };
+                *cross_margin = add_points(*cross_margin, cross_gap, size.width);
+            }
+        }
+    }
+
+    /// Sets the size of a node with an aspect ratio along the axis where it is auto, from the size
+    /// it was laid out with along the other axis
+    pub fn apply_aspect_ratio(
+        &self,
+        entity: Entity,
+        style: &Style,
+        calculated_size: Option<&CalculatedSize>,
+        adjusted: &mut HashMap<Entity, stretch::style::Style>,
+    ) {
+        let aspect_ratio = match style.aspect_ratio {
+            Some(aspect_ratio) if aspect_ratio > 0.0 => aspect_ratio,
+            _ => return,
+        };
+        let layout = match self.get_layout(entity) {
+            Ok(layout) => layout,
+            Err(_) => return,
+        };
+        let is_auto = |val: Val| matches!(val, Val::Auto | Val::Undefined);
+        let size = match (is_auto(style.size.width), is_auto(style.size.height)) {
+            (false, true) => stretch::geometry::Size {
+                width: style.size.width.into(),
+                height: stretch::style::Dimension::Points(layout.size.width / aspect_ratio),
+            },
+            (true, false) => stretch::geometry::Size {
+                width: stretch::style::Dimension::Points(layout.size.height * aspect_ratio),
+                height: style.size.height.into(),
+            },
+            _ => return,
+        };
+        adjusted
+            .entry(entity)
+            .or_insert_with(|| stretch_style(style, calculated_size))
+            .size = size;
+    }
+
+    /// Sets the styles of nodes that were adjusted after they were laid out, and gives the nodes
+    /// that are no longer adjusted their own style back. Returns true if a style changed, which
+    /// means the layout has to be computed again.
+    pub fn set_adjusted_styles<'a>(
+        &mut self,
+        adjusted: HashMap<Entity, stretch::style::Style>,
+        style: impl Fn(Entity) -> Option<(&'a Style, Option<&'a CalculatedSize>)>,
+    ) -> bool {
+        let mut changed = false;
+        for (entity, stretch_style) in adjusted.iter() {
+            if let Some(stretch_node) = self.entity_to_stretch.get(entity) {
+                if *self.stretch.style(*stretch_node).unwrap() != *stretch_style {
+                    self.stretch
+                        .set_style(*stretch_node, *stretch_style)
+                        .unwrap();
+                    changed = true;
+                }
+            }
+        }
+        for entity in self.adjusted_nodes.iter() {
+            if adjusted.contains_key(entity) {
+                continue;
+            }
+            if let (Some(stretch_node), Some((style, calculated_size))) =
+                (self.entity_to_stretch.get(entity), style(*entity))
+            {
+                self.stretch
+                    .set_style(*stretch_node, stretch_style(style, calculated_size))
+                    .unwrap();
+                changed = true;
+            }
+        }
+        self.adjusted_nodes = adjusted
+            .into_iter()
+            .map(|(entity, _style)| entity)
+            .collect();
+        changed
+    }
+
     pub fn get_layout(&self, entity: Entity) -> Result<&stretch::result::Layout, stretch::Error> {
         let stretch_node = self.entity_to_stretch.get(&entity).unwrap();
         self.stretch.layout(*stretch_node)
     }
 }
 
+/// The number of times the layout is computed again for the nodes adjusted after they were laid
+/// out, like the children of nested grids
+const MAX_ADJUSTMENT_PASSES: usize = 4;
+
+/// A child node, with its style and the size of its content if it is a leaf
+pub type ChildStyle<'a> = (Entity, &'a Style, Option<&'a CalculatedSize>);
+
+/// Converts the style of a node to stretch. The automatic min width of a leaf is the min-content
+/// width of its content, as in css.
+fn update_root_node<K>(stretch: &mut Stretch, node: Entry<'_, K, stretch::node::Node>, size: Size) {
+    let node = node.or_insert_with(|| {
+        stretch
+            .new_node(stretch::style::Style::default(), Vec::new())
+            .unwrap()
+    });
+    stretch
+        .set_style(
+            *node,
+            stretch::style::Style {
+                size: stretch::geometry::Size {
+                    width: stretch::style::Dimension::Points(size.width),
+                    height: stretch::style::Dimension::Points(size.height),
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+}
+
+fn stretch_style(style: &Style, calculated_size: Option<&CalculatedSize>) -> stretch::style::Style {
+    let mut stretch_style: stretch::style::Style = style.into();
+    if let Some(calculated_size) = calculated_size {
+        if style.min_size.width == Val::Auto && calculated_size.min_size.width > 0.0 {
+            stretch_style.min_size.width =
+                stretch::style::Dimension::Points(calculated_size.min_size.width);
+        }
+    }
+    stretch_style
+}
+
+/// Returns true if the node is laid out by its parent
+fn is_in_flow(style: &Style) -> bool {
+    style.display != Display::None && style.position_type != PositionType::Absolute
+}
+
+/// Adds logical pixels to a margin, with percentages of `reference`. Auto margins already take up
+/// the free space.
+fn add_points(
+    dimension: stretch::style::Dimension,
+    points: f32,
+    reference: f32,
+) -> stretch::style::Dimension {
+    match dimension {
+        stretch::style::Dimension::Points(value) => {
+            stretch::style::Dimension::Points(value + points)
+        }
+        stretch::style::Dimension::Percent(value) => {
+            stretch::style::Dimension::Points(value * reference + points)
+        }
+        stretch::style::Dimension::Undefined => stretch::style::Dimension::Points(points),
+        stretch::style::Dimension::Auto => stretch::style::Dimension::Auto,
+    }
+}
+
 // SAFE: as long as MeasureFunc is Send + Sync. https://github.com/vislyhq/stretch/issues/69
 unsafe impl Send for FlexSurface {}
 unsafe impl Sync for FlexSurface {}
 
+#[allow(clippy::too_many_arguments)]
 pub fn flex_node_system(
     windows: Res<Windows>,
+    ui_scale: Res<UiScale>,
     mut flex_surface: ResMut<FlexSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    texture_camera_query: Query<(&Camera, &UiTextureCamera)>,
+    root_node_query: Query<(Entity, Option<&UiTexture>), (With<Node>, Without<Parent>)>,
     node_query: Query<(Entity, &Style, Option<&CalculatedSize>), (With<Node>, Changed<Style>)>,
     changed_size_query: Query<
         (Entity, &Style, &CalculatedSize),
         (With<Node>, Changed<CalculatedSize>),
     >,
     children_query: Query<(Entity, &Children), (With<Node>, Changed<Children>)>,
+    mut scroll_query: Query<(Entity, &mut ScrollPosition, Option<&Children>), With<Node>>,
+    container_query: Query<(Entity, &Style, &Children), With<Node>>,
+    style_query: Query<(Entity, &Style, Option<&CalculatedSize>), With<Node>>,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
 ) {
     // update window root nodes
     for window in windows.iter() {
-        flex_surface.update_window(window);
+        flex_surface.update_window(window, &ui_scale);
     }
 
     // update changed nodes
@@ -192,7 +544,29 @@ pub fn flex_node_system(
 
     // update window children (for now assuming all Nodes live in the primary window)
     if let Some(primary_window) = windows.get_primary() {
-        flex_surface.set_window_children(primary_window.id(), root_node_query.iter());
+        flex_surface.set_window_children(
+            primary_window.id(),
+            root_node_query
+                .iter()
+                .filter(|(_entity, ui_texture)| ui_texture.is_none())
+                .map(|(entity, _ui_texture)| entity),
+        );
+    }
+
+    // update ui texture children, which are laid out within their textures
+    for (camera, ui_texture_camera) in texture_camera_query.iter() {
+        if let Some(ref camera_name) = camera.name {
+            flex_surface.update_texture(camera_name, ui_texture_camera.size);
+            flex_surface.set_texture_children(
+                camera_name,
+                root_node_query
+                    .iter()
+                    .filter(|(_entity, ui_texture)| {
+                        ui_texture.map_or(false, |ui_texture| &ui_texture.camera == camera_name)
+                    })
+                    .map(|(entity, _ui_texture)| entity),
+            );
+        }
     }
 
     // update children
@@ -201,7 +575,66 @@ pub fn flex_node_system(
     }
 
     // compute layouts
-    flex_surface.compute_window_layouts();
+    flex_surface.compute_layouts();
+
+    // grids place their children and flex nodes add their gaps once the nodes were laid out.
+    // nested nodes are adjusted after the layout of their parents was adjusted, so the layout is
+    // repeated until it settles.
+    for _ in 0..MAX_ADJUSTMENT_PASSES {
+        let mut adjusted = HashMap::default();
+        for (entity, style, children) in container_query.iter() {
+            let children = children
+                .iter()
+                .filter_map(|child| style_query.get(*child).ok())
+                .collect::<Vec<_>>();
+            match style.display {
+                Display::Grid => {
+                    flex_surface.place_grid_items(entity, style, &children, &mut adjusted)
+                }
+                Display::Flex => {
+                    flex_surface.apply_flex_gap(entity, style, &children, &mut adjusted)
+                }
+                Display::None => {}
+            }
+        }
+        for (entity, style, calculated_size) in style_query.iter() {
+            flex_surface.apply_aspect_ratio(entity, style, calculated_size, &mut adjusted);
+        }
+        let style = |entity| {
+            style_query
+                .get(entity)
+                .ok()
+                .map(|(_entity, style, calculated_size)| (style, calculated_size))
+        };
+        if !flex_surface.set_adjusted_styles(adjusted, style) {
+            break;
+        }
+        flex_surface.compute_layouts();
+    }
+
+    // update the scrollable range of scroll containers from the bounds of their children
+    let mut scroll_offsets = HashMap::default();
+    for (entity, mut scroll_position, children) in scroll_query.iter_mut() {
+        let layout = flex_surface.get_layout(entity).unwrap();
+        let size = Vec2::new(layout.size.width, layout.size.height);
+        let mut min = Vec2::zero();
+        let mut max = size;
+        for child in children.iter().flat_map(|children| children.iter()) {
+            if let Ok(child_layout) = flex_surface.get_layout(*child) {
+                let location = Vec2::new(child_layout.location.x, child_layout.location.y);
+                min = min.min(location);
+                max = max
+                    .max(location + Vec2::new(child_layout.size.width, child_layout.size.height));
+            }
+        }
+
+        let mut updated_scroll_position = scroll_position.clone();
+        updated_scroll_position.set_content_bounds(min, max, size);
+        if *scroll_position != updated_scroll_position {
+            *scroll_position = updated_scroll_position;
+        }
+        scroll_offsets.insert(entity, scroll_position.offset);
+    }
 
     for (entity, mut node, mut transform, parent) in node_transform_query.iter_mut() {
         let layout = flex_surface.get_layout(entity).unwrap();
@@ -214,6 +647,72 @@ pub fn flex_node_system(
                 position.x -= parent_layout.size.width / 2.0;
                 position.y -= parent_layout.size.height / 2.0;
             }
+            if let Some(scroll_offset) = scroll_offsets.get(&parent.0) {
+                position.x -= scroll_offset.x();
+                position.y += scroll_offset.y();
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FlexSurface;
+    use crate::{AlignContent, FlexWrap, Style, Val};
+    use bevy_ecs::Entity;
+    use bevy_math::Size;
+    use bevy_transform::prelude::Children;
+    use bevy_utils::HashMap;
+
+    #[test]
+    fn flex_gap_separates_children_and_lines() {
+        let mut flex_surface = FlexSurface::default();
+        let container = Entity::new(0);
+        let children = [Entity::new(1), Entity::new(2), Entity::new(3)];
+        let container_style = Style {
+            size: Size::new(Val::Px(120.0), Val::Px(200.0)),
+            flex_wrap: FlexWrap::Wrap,
+            align_content: AlignContent::FlexStart,
+            gap: Size::new(Val::Px(10.0), Val::Px(20.0)),
+            ..Default::default()
+        };
+        let child_style = Style {
+            size: Size::new(Val::Px(50.0), Val::Px(30.0)),
+            flex_shrink: 0.0,
+            ..Default::default()
+        };
+        flex_surface.upsert_node(container, &container_style);
+        for child in children.iter() {
+            flex_surface.upsert_node(*child, &child_style);
+        }
+        flex_surface.update_children(container, &Children::with(&children));
+
+        let container_node = flex_surface.entity_to_stretch[&container];
+        let child_styles = children
+            .iter()
+            .map(|child| (*child, &child_style, None))
+            .collect::<Vec<_>>();
+        for _ in 0..2 {
+            flex_surface
+                .stretch
+                .compute_layout(container_node, stretch::geometry::Size::undefined())
+                .unwrap();
+            let mut adjusted = HashMap::default();
+            flex_surface.apply_flex_gap(container, &container_style, &child_styles, &mut adjusted);
+            flex_surface.set_adjusted_styles(adjusted, |_entity| None);
+        }
+        flex_surface
+            .stretch
+            .compute_layout(container_node, stretch::geometry::Size::undefined())
+            .unwrap();
+
+        let location = |entity| {
+            let layout = flex_surface.get_layout(entity).unwrap();
+            (layout.location.x, layout.location.y)
+        };
+        assert_eq!(location(children[0]), (0.0, 0.0));
+        assert_eq!(location(children[1]), (60.0, 0.0));
+        // the third child doesn't fit next to the others with the gap, and starts a new line
+        assert_eq!(location(children[2]), (0.0, 50.0));
+    }
+}
@@ -1,9 +1,16 @@
-use crate::Node;
+use crate::{Node, UiScale, UiTexturePass};
 use bevy_app::{EventReader, Events};
 use bevy_core::FloatOrd;
 use bevy_ecs::prelude::*;
-use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType},
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    touch::Touches,
+    ElementState, Input,
+};
 use bevy_math::Vec2;
+use bevy_render::{draw::Draw, render_graph::base::MainPass};
 use bevy_transform::components::GlobalTransform;
 use bevy_window::CursorMoved;
 
@@ -32,6 +39,35 @@ impl Default for FocusPolicy {
     }
 }
 
+/// The node that receives keyboard input, like a focused [TextInput](crate::widget::TextInput)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Focus {
+    pub entity: Option<Entity>,
+}
+
+/// A node that can be focused, by clicking it or by navigating to it with the keyboard or a
+/// gamepad. Tab and shift tab focus the next and previous nodes, from the top left to the bottom
+/// right, and the arrow keys and the directional pad of gamepads focus the closest node in their
+/// direction. Space, enter and the south button of gamepads activate the focused node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Focusable {
+    /// The node keeps the arrow keys while it is focused, instead of them moving the focus
+    pub captures_arrow_keys: bool,
+    /// The node keeps space and enter while it is focused, instead of them activating it
+    pub captures_activation_keys: bool,
+}
+
+/// A node that doesn't react to the cursor and can't be focused. It still blocks the nodes below
+/// it according to its [FocusPolicy].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Disabled;
+
+/// Sent when a [Focusable] node is clicked, or activated while it is focused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Activated {
+    pub entity: Entity,
+}
+
 #[derive(Default)]
 pub struct State {
     cursor_moved_event_reader: EventReader<CursorMoved>,
@@ -39,28 +75,48 @@ pub struct State {
     hovered_entity: Option<Entity>,
 }
 
+/// Updates the interaction of the nodes under the cursor. Clicking a [Focusable] node focuses and
+/// activates it, and clicking anywhere else removes the focus.
+#[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
+    mut focus: ResMut<Focus>,
+    mut activated_events: ResMut<Events<Activated>>,
     mouse_button_input: Res<Input<MouseButton>>,
     cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
     touches_input: Res<Touches>,
-    mut node_query: Query<(
-        Entity,
-        &Node,
-        &GlobalTransform,
-        Option<&mut Interaction>,
-        Option<&FocusPolicy>,
-    )>,
+    // world space and texture nodes are not on the screen
+    mut node_query: Query<
+        (
+            Entity,
+            &Node,
+            &GlobalTransform,
+            Option<&mut Interaction>,
+            Option<&FocusPolicy>,
+            Option<&Focusable>,
+            Option<&Disabled>,
+        ),
+        (Without<MainPass>, Without<UiTexturePass>),
+    >,
 ) {
     if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
-        state.cursor_position = cursor_moved.position;
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
     }
     if let Some(touch) = touches_input.get_pressed(0) {
-        state.cursor_position = touch.position();
+        state.cursor_position = ui_scale.to_ui(touch.position());
     }
 
     if mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0) {
-        for (_entity, _node, _global_transform, interaction, _focus_policy) in node_query.iter_mut()
+        for (
+            _entity,
+            _node,
+            _global_transform,
+            interaction,
+            _focus_policy,
+            _focusable,
+            _disabled,
+        ) in node_query.iter_mut()
         {
             if let Some(mut interaction) = interaction {
                 if *interaction == Interaction::Clicked {
@@ -73,22 +129,46 @@ pub fn ui_focus_system(
     let mouse_clicked =
         mouse_button_input.just_pressed(MouseButton::Left) || touches_input.just_released(0);
     let mut hovered_entity = None;
+    let mut clicked_focusable = None;
 
     {
         let mut moused_over_z_sorted_nodes = node_query
             .iter_mut()
             .filter_map(
-                |(entity, node, global_transform, interaction, focus_policy)| {
+                |(
+                    entity,
+                    node,
+                    global_transform,
+                    mut interaction,
+                    focus_policy,
+                    focusable,
+                    disabled,
+                )| {
+                    if disabled.is_some() {
+                        if let Some(interaction) = &mut interaction {
+                            if **interaction != Interaction::None {
+                                **interaction = Interaction::None;
+                            }
+                        }
+                        interaction = None;
+                    }
                     let position = global_transform.translation;
                     let ui_position = position.truncate();
                     let extents = node.size / 2.0;
                     let min = ui_position - extents;
                     let max = ui_position + extents;
-                    // if the current cursor position is within the bounds of the node, consider it for clicking
+                    // if the current cursor position is within the visible bounds of the node, consider it for clicking
                     if (min.x..max.x).contains(&state.cursor_position.x)
                         && (min.y..max.y).contains(&state.cursor_position.y)
+                        && node.is_visible_at(state.cursor_position)
                     {
-                        Some((entity, focus_policy, interaction, FloatOrd(position.z)))
+                        Some((
+                            entity,
+                            focus_policy,
+                            interaction,
+                            focusable.is_some() && disabled.is_none(),
+                            FloatOrd(position.z),
+                        ))
                     } else {
                         if let Some(mut interaction) = interaction {
                             if *interaction == Interaction::Hovered {
@@ -101,8 +181,11 @@ pub fn ui_focus_system(
             )
             .collect::<Vec<_>>();
 
-        moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, z)| -*z);
-        for (entity, focus_policy, interaction, _) in moused_over_z_sorted_nodes {
+        moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, _, z)| -*z);
+        for (entity, focus_policy, interaction, focusable, _) in moused_over_z_sorted_nodes {
+            if mouse_clicked && focusable && clicked_focusable.is_none() {
+                clicked_focusable = Some(entity);
+            }
             if let Some(mut interaction) = interaction {
                 if mouse_clicked {
                     // only consider nodes with ClickState "clickable"
@@ -141,4 +224,193 @@ pub fn ui_focus_system(
         }
         state.hovered_entity = hovered_entity;
     }
+
+    if mouse_clicked {
+        if focus.entity != clicked_focusable {
+            focus.entity = clicked_focusable;
+        }
+        if let Some(entity) = clicked_focusable {
+            activated_events.send(Activated { entity });
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct NavigationState {
+    keyboard_input_event_reader: EventReader<KeyboardInput>,
+}
+
+/// Moves the focus between [Focusable] nodes with the keyboard and gamepads, and activates the
+/// focused node
+#[allow(clippy::too_many_arguments)]
+pub fn ui_navigation_system(
+    mut state: Local<NavigationState>,
+    mut focus: ResMut<Focus>,
+    mut activated_events: ResMut<Events<Activated>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    keyboard_input_events: Res<Events<KeyboardInput>>,
+    gamepad_button_input: Res<Input<GamepadButton>>,
+    // world space and texture nodes are not on the screen, and disabled nodes can't be focused
+    focusable_query: Query<
+        (Entity, &Focusable, &Node, &GlobalTransform, Option<&Draw>),
+        (Without<MainPass>, Without<UiTexturePass>, Without<Disabled>),
+    >,
+) {
+    let focused = focus
+        .entity
+        .and_then(|entity| focusable_query.get(entity).ok());
+    let mut navigation = Vec::new();
+
+    // keyboard input events repeat while keys are held down
+    for event in state
+        .keyboard_input_event_reader
+        .iter(&keyboard_input_events)
+    {
+        if event.state != ElementState::Pressed {
+            continue;
+        }
+        let captures_arrow_keys = focused.map_or(false, |(_, focusable, _, _, _)| {
+            focusable.captures_arrow_keys
+        });
+        let captures_activation_keys = focused.map_or(false, |(_, focusable, _, _, _)| {
+            focusable.captures_activation_keys
+        });
+        let shift =
+            keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+        match event.key_code {
+            Some(KeyCode::Tab) if shift => navigation.push(Navigation::Previous),
+            Some(KeyCode::Tab) => navigation.push(Navigation::Next),
+            Some(KeyCode::Up) if !captures_arrow_keys => navigation.push(Navigation::Up),
+            Some(KeyCode::Down) if !captures_arrow_keys => navigation.push(Navigation::Down),
+            Some(KeyCode::Left) if !captures_arrow_keys => navigation.push(Navigation::Left),
+            Some(KeyCode::Right) if !captures_arrow_keys => navigation.push(Navigation::Right),
+            Some(KeyCode::Space) | Some(KeyCode::Return) | Some(KeyCode::NumpadEnter)
+                if !captures_activation_keys =>
+            {
+                navigation.push(Navigation::Activate)
+            }
+            _ => {}
+        }
+    }
+    for GamepadButton(_gamepad, button_type) in gamepad_button_input.get_just_pressed() {
+        match button_type {
+            GamepadButtonType::DPadUp => navigation.push(Navigation::Up),
+            GamepadButtonType::DPadDown => navigation.push(Navigation::Down),
+            GamepadButtonType::DPadLeft => navigation.push(Navigation::Left),
+            GamepadButtonType::DPadRight => navigation.push(Navigation::Right),
+            GamepadButtonType::South => navigation.push(Navigation::Activate),
+            _ => {}
+        }
+    }
+    if navigation.is_empty() {
+        return;
+    }
+
+    let mut nodes = focusable_query
+        .iter()
+        .filter(|(_entity, _focusable, node, _global_transform, draw)| {
+            node.size.x() > 0.0 && node.size.y() > 0.0 && draw.map_or(true, |draw| draw.is_visible)
+        })
+        .map(|(entity, _focusable, _node, global_transform, _draw)| {
+            (entity, global_transform.translation.truncate())
+        })
+        .collect::<Vec<_>>();
+    // tab goes through the nodes from the top left to the bottom right, and y is up
+    nodes.sort_by_key(|(_entity, position)| (FloatOrd(-position.y()), FloatOrd(position.x())));
+
+    let mut focused = focus.entity;
+    for navigation in navigation {
+        let position = focused.and_then(|focused| {
+            nodes
+                .iter()
+                .position(|(entity, _position)| *entity == focused)
+        });
+        let next = match (navigation, position) {
+            (Navigation::Activate, _) => {
+                if let Some(entity) = focused {
+                    activated_events.send(Activated { entity });
+                }
+                continue;
+            }
+            (Navigation::Next, Some(position)) => nodes.get((position + 1) % nodes.len()),
+            (Navigation::Previous, Some(position)) => {
+                nodes.get((position + nodes.len() - 1) % nodes.len())
+            }
+            (Navigation::Previous, None) => nodes.last(),
+            // nothing is focused, so any navigation focuses the first node
+            (_, None) => nodes.first(),
+            (direction, Some(position)) => {
+                let from = nodes[position].1;
+                let direction = match direction {
+                    Navigation::Up => Vec2::new(0.0, 1.0),
+                    Navigation::Down => Vec2::new(0.0, -1.0),
+                    Navigation::Left => Vec2::new(-1.0, 0.0),
+                    _ => Vec2::new(1.0, 0.0),
+                };
+                closest_in_direction(from, direction, &nodes)
+            }
+        };
+        if let Some((entity, _position)) = next {
+            focused = Some(*entity);
+        }
+    }
+    if focus.entity != focused {
+        focus.entity = focused;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Navigation {
+    Up,
+    Down,
+    Left,
+    Right,
+    Next,
+    Previous,
+    Activate,
+}
+
+/// Returns the node closest to `from` in `direction`. Nodes that are off to the side count as
+/// further away than nodes straight ahead.
+fn closest_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    nodes: &[(Entity, Vec2)],
+) -> Option<&(Entity, Vec2)> {
+    nodes
+        .iter()
+        .filter_map(|node| {
+            let offset = node.1 - from;
+            let distance = offset.dot(direction);
+            if distance <= 0.0 {
+                return None;
+            }
+            let side_distance = (offset - direction * distance).length();
+            Some((node, FloatOrd(distance + 2.0 * side_distance)))
+        })
+        .min_by_key(|(_node, score)| *score)
+        .map(|(node, _score)| node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::closest_in_direction;
+    use bevy_ecs::Entity;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn navigation_prefers_nodes_straight_ahead() {
+        let nodes = [
+            (Entity::new(0), Vec2::new(0.0, 0.0)),
+            (Entity::new(1), Vec2::new(100.0, 0.0)),
+            (Entity::new(2), Vec2::new(60.0, 50.0)),
+            (Entity::new(3), Vec2::new(0.0, 100.0)),
+        ];
+        let closest = |direction| {
+            closest_in_direction(Vec2::zero(), direction, &nodes).map(|(entity, _)| *entity)
+        };
+        assert_eq!(closest(Vec2::new(1.0, 0.0)), Some(Entity::new(1)));
+        assert_eq!(closest(Vec2::new(0.0, 1.0)), Some(Entity::new(3)));
+        assert_eq!(closest(Vec2::new(-1.0, 0.0)), None);
+    }
 }
@@ -1,32 +1,62 @@
 mod anchors;
+mod cursor;
+mod debug_overlay;
+mod diagnostics_overlay;
+mod drag;
 pub mod entity;
 mod flex;
 mod focus;
 mod margins;
 mod node;
 mod render;
+mod scroll;
+mod tooltip;
+mod transition;
+mod ui_scale;
+mod ui_texture;
 pub mod update;
 pub mod widget;
+mod world_space;
 
 pub use anchors::*;
+pub use cursor::*;
+pub use debug_overlay::*;
+pub use diagnostics_overlay::*;
+pub use drag::*;
 pub use flex::*;
 pub use focus::*;
 pub use margins::*;
 pub use node::*;
 pub use render::*;
+pub use scroll::*;
+pub use tooltip::*;
+pub use transition::*;
+pub use ui_scale::*;
+pub use ui_texture::*;
+pub use world_space::*;
 
 pub mod prelude {
     pub use crate::{
         entity::*,
         node::*,
-        widget::{Button, Text},
-        Anchors, Interaction, Margins,
+        widget::{
+            Button, ButtonStateStyle, ButtonStyle, Checkbox, CheckboxChanged, ImageSlices, Slider,
+            SliderChanged, Text, TextInput, TextInputChanged, TextInputSubmitted, Toggle,
+            ToggleChanged,
+        },
+        Activated, Anchors, CustomCursor, DebugOverlay, DiagnosticsOverlay,
+        DiagnosticsOverlayPlugin, Disabled, Drag, DragEnd, DragStart, Draggable, DropTarget,
+        Easing, Focus, Focusable, Interaction, Margins, ScrollAxis, ScrollPosition, ScrollbarThumb,
+        Tooltip, Transition, TransitionFinished, TransitionTarget, Transitions, UiScale, UiTexture,
+        UiTextureCamera, WorldSpaceUi,
     };
 }
 
 use bevy_app::prelude::*;
-use bevy_render::render_graph::RenderGraph;
-use update::ui_z_system;
+use bevy_render::{
+    camera::no_frustum_culling_system, render_graph::RenderGraph, shader::shader_defs_system,
+};
+use update::{ui_border_system, ui_clip_system, ui_opacity_system, ui_z_system};
 
 #[derive(Default)]
 pub struct UiPlugin;
@@ -38,13 +68,75 @@ pub mod stage {
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<FlexSurface>()
+            .init_resource::<UiScale>()
+            .init_resource::<CustomCursor>()
+            .init_resource::<DebugOverlay>()
+            .init_resource::<DebugOverlayMaterials>()
+            .init_resource::<Focus>()
+            .init_resource::<widget::Clipboard>()
+            .init_resource::<widget::TextInputMaterials>()
+            .init_resource::<widget::SliderMaterials>()
+            .init_resource::<widget::CheckboxMaterials>()
+            .add_event::<Activated>()
+            .add_event::<DragStart>()
+            .add_event::<Drag>()
+            .add_event::<DragEnd>()
+            .add_event::<Drop>()
+            .add_event::<TransitionFinished>()
+            .add_event::<widget::TextInputChanged>()
+            .add_event::<widget::TextInputSubmitted>()
+            .add_event::<widget::SliderChanged>()
+            .add_event::<widget::CheckboxChanged>()
+            .add_event::<widget::ToggleChanged>()
             .add_stage_before(bevy_app::stage::POST_UPDATE, stage::UI)
             .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_focus_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_navigation_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_drag_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, ui_scroll_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::text_input_setup_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::text_input_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::slider_setup_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::slider_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::checkbox_setup_system)
+            .add_system_to_stage(bevy_app::stage::PRE_UPDATE, widget::checkbox_system)
             // add these stages to front because these must run before transform update systems
+            .add_system_to_stage(stage::UI, ui_transition_system)
+            .add_system_to_stage(stage::UI, widget::text_input_text_system)
             .add_system_to_stage(stage::UI, widget::text_system)
+            .add_system_to_stage(stage::UI, widget::text_input_caret_system)
             .add_system_to_stage(stage::UI, widget::image_node_system)
+            .add_system_to_stage(stage::UI, widget::image_slices_system)
             .add_system_to_stage(stage::UI, ui_z_system)
+            .add_system_to_stage(stage::UI, ui_opacity_system)
+            .add_system_to_stage(stage::UI, scrollbar_system)
+            .add_system_to_stage(stage::UI, widget::slider_thumb_system)
+            .add_system_to_stage(stage::UI, widget::checkbox_indicator_system)
+            .add_system_to_stage(stage::UI, widget::button_style_system)
+            .add_system_to_stage(stage::UI, tooltip_system)
+            .add_system_to_stage(stage::UI, debug_overlay_system)
+            .add_system_to_stage(stage::UI, custom_cursor_system)
+            .add_system_to_stage(stage::UI, ui_scale_system)
             .add_system_to_stage(stage::UI, flex_node_system)
+            .add_system_to_stage(stage::UI, ui_border_system)
+            .add_system_to_stage(stage::UI, world_space_ui_system)
+            .add_system_to_stage(stage::UI, ui_texture_system)
+            // clipping needs the global transforms of this frame
+            .add_system_to_stage(bevy_app::stage::POST_UPDATE, ui_clip_system)
+            // nodes scale the quad mesh in their shaders
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                no_frustum_culling_system::<Node>,
+            )
+            .add_system_to_stage(
+                bevy_app::stage::POST_UPDATE,
+                shader_defs_system::<widget::ImageSlices>,
+            )
+            // ui texture cameras are sized and their visible entities are filtered after the
+            // cameras were updated
+            .add_system_to_stage(
+                bevy_render::stage::RENDER_RESOURCE,
+                ui_texture_camera_system,
+            )
             .add_system_to_stage(bevy_render::stage::DRAW, widget::draw_text_system);
 
         let resources = app.resources();
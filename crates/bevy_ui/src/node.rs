@@ -1,13 +1,54 @@
-use bevy_math::{Rect, Size, Vec2};
+use bevy_math::{Rect, Size, Vec2, Vec4};
 use bevy_reflect::{Reflect, ReflectComponent, ReflectDeserialize};
-use bevy_render::renderer::RenderResources;
+use bevy_render::{color::Color, renderer::RenderResources};
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign};
 
-#[derive(Debug, Clone, Default, RenderResources, Reflect)]
+#[derive(Debug, Clone, RenderResources, Reflect)]
 #[reflect(Component)]
 pub struct Node {
     pub size: Vec2,
+    /// The area the node is visible in, as `(min x, min y, max x, max y)` in logical pixels. It
+    /// is the intersection of the bounds of the ancestors that don't let their content overflow.
+    pub clip: Vec4,
+    /// The product of the [Opacity] of the node and of its ancestors. It multiplies the alpha of
+    /// the color, image and text of the node.
+    pub opacity: f32,
+    /// The radii of the corners of the node in logical pixels, from its [CornerRadius], as
+    /// `(top left, top right, bottom right, bottom left)`
+    pub corner_radius: Vec4,
+    /// The widths of the border of the node in logical pixels, as `(left, right, top, bottom)`.
+    /// They are the `border` of the [Style] of nodes with a [BorderColor], and zero otherwise.
+    pub border: Vec4,
+    pub border_color: Color,
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self {
+            size: Default::default(),
+            clip: Node::unclipped(),
+            opacity: 1.0,
+            corner_radius: Vec4::zero(),
+            border: Vec4::zero(),
+            border_color: Color::NONE,
+        }
+    }
+}
+
+impl Node {
+    /// The clip of nodes without clipping ancestors
+    pub fn unclipped() -> Vec4 {
+        Vec4::new(f32::MIN, f32::MIN, f32::MAX, f32::MAX)
+    }
+
+    /// Returns true if `point` is in the visible area of the node
+    pub fn is_visible_at(&self, point: Vec2) -> bool {
+        point.x() >= self.clip.x()
+            && point.y() >= self.clip.y()
+            && point.x() <= self.clip.z()
+            && point.y() <= self.clip.w()
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
@@ -48,9 +89,68 @@ impl AddAssign<f32> for Val {
     }
 }
 
+/// Overrides the order nodes are drawn in. By default children are drawn above their parent, and
+/// later siblings above earlier ones.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ZIndex {
+    /// Orders the node among its siblings. Higher values are drawn above lower ones.
+    Local(i32),
+    /// Orders the node and its descendants among the root nodes, which are at 0. Higher values
+    /// are drawn above lower ones, regardless of where the node is in the hierarchy.
+    Global(i32),
+}
+
+impl Default for ZIndex {
+    fn default() -> Self {
+        ZIndex::Local(0)
+    }
+}
+
+/// Fades a node and all of its descendants, from 0.0 for invisible to 1.0 for opaque. The
+/// opacities of nested nodes multiply.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Opacity(pub f32);
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Opacity(1.0)
+    }
+}
+
+/// Rounds the corners of a node and of its border. The radii are in logical pixels, and are
+/// limited to half the size of the node.
+#[derive(Default, Copy, Clone, PartialEq, Debug)]
+pub struct CornerRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadius {
+    pub fn all(radius: f32) -> Self {
+        CornerRadius {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+/// Draws the border of a node in a color, over the color or image of the node. The widths of the
+/// border are the `border` of the [Style] of the node, which leaves room for them in the layout.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BorderColor(pub Color);
+
+/// The size of the content of a leaf node, like a text or an image
 #[derive(Default, Copy, Clone, Debug)]
 pub struct CalculatedSize {
+    /// The max-content size, which the node has when it is not constrained
     pub size: Size,
+    /// The min-content size, which the node can't shrink below when its min size is auto, like
+    /// the width of the longest word of a text. Zero when the content can shrink to nothing.
+    pub min_size: Size,
 }
 
 #[derive(Clone, PartialEq, Debug, Reflect)]
@@ -74,7 +174,23 @@ pub struct Style {
     pub size: Size<Val>,
     pub min_size: Size<Val>,
     pub max_size: Size<Val>,
+    /// The width divided by the height of the node. It sets the size of the node along an axis
+    /// where it is auto, from its size along the other axis.
     pub aspect_ratio: Option<f32>,
+    pub overflow: Overflow,
+    /// The columns of a [Display::Grid] node. A grid without columns has a single column.
+    pub grid_template_columns: Vec<GridTrack>,
+    /// The rows of a [Display::Grid] node. Rows are added with the size of `grid_auto_rows` when
+    /// its children don't fit in them.
+    pub grid_template_rows: Vec<GridTrack>,
+    pub grid_auto_rows: GridTrack,
+    /// The columns a child of a [Display::Grid] node is placed in
+    pub grid_column: GridPlacement,
+    /// The rows a child of a [Display::Grid] node is placed in
+    pub grid_row: GridPlacement,
+    /// The space between the columns (width) and rows (height) of a [Display::Grid] node, or
+    /// between the children of a [Display::Flex] node and between their lines when they wrap
+    pub gap: Size<Val>,
 }
 
 impl Default for Style {
@@ -100,6 +216,13 @@ impl Default for Style {
             min_size: Size::new(Val::Auto, Val::Auto),
             max_size: Size::new(Val::Auto, Val::Auto),
             aspect_ratio: Default::default(),
+            overflow: Default::default(),
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_auto_rows: GridTrack::Fr(1.0),
+            grid_column: Default::default(),
+            grid_row: Default::default(),
+            gap: Size::new(Val::Px(0.0), Val::Px(0.0)),
         }
     }
 }
@@ -172,6 +295,9 @@ impl Default for Direction {
 #[reflect_value(PartialEq, Serialize, Deserialize)]
 pub enum Display {
     Flex,
+    /// Places the children of the node in the cells of a grid of rows and columns. Grid nodes
+    /// are not sized by their children, so they are usually given a size or a flex grow.
+    Grid,
     None,
 }
 
@@ -181,6 +307,64 @@ impl Default for Display {
     }
 }
 
+/// The size of a row or column of a [Display::Grid] node
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum GridTrack {
+    Px(f32),
+    /// A percentage of the size of the node inside of its padding
+    Percent(f32),
+    /// A fraction of the space left by the other tracks and the gaps. The space is shared by the
+    /// fractional tracks in proportion to their values.
+    Fr(f32),
+}
+
+impl Default for GridTrack {
+    fn default() -> GridTrack {
+        GridTrack::Fr(1.0)
+    }
+}
+
+/// The tracks a child of a [Display::Grid] node is placed in, along one axis. Children without a
+/// start are placed in the first free cells, in the order of the children, row after row.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    /// The index of the first track, from 0
+    pub start: Option<usize>,
+    /// The number of tracks
+    pub span: usize,
+}
+
+impl Default for GridPlacement {
+    fn default() -> GridPlacement {
+        GridPlacement {
+            start: None,
+            span: 1,
+        }
+    }
+}
+
+impl GridPlacement {
+    pub fn start(start: usize) -> GridPlacement {
+        GridPlacement {
+            start: Some(start),
+            span: 1,
+        }
+    }
+
+    pub fn span(span: usize) -> GridPlacement {
+        GridPlacement { start: None, span }
+    }
+
+    pub fn start_span(start: usize, span: usize) -> GridPlacement {
+        GridPlacement {
+            start: Some(start),
+            span,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect_value(PartialEq, Serialize, Deserialize)]
 pub enum FlexDirection {
@@ -213,19 +397,24 @@ impl Default for JustifyContent {
     }
 }
 
-// TODO: add support for overflow settings
-// #[derive(Copy, Clone, PartialEq, Debug)]
-// pub enum Overflow {
-//     Visible,
-//     Hidden,
-//     Scroll,
-// }
+/// What happens to the children of a node that don't fit in it
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum Overflow {
+    /// Children are drawn outside of the node
+    Visible,
+    /// Children are clipped to the node
+    Hidden,
+    /// Children are clipped to the node, and can be scrolled with a
+    /// [ScrollPosition](crate::ScrollPosition)
+    Scroll,
+}
 
-// impl Default for Overflow {
-//     fn default() -> Overflow {
-//         Overflow::Visible
-//     }
-// }
+impl Default for Overflow {
+    fn default() -> Overflow {
+        Overflow::Visible
+    }
+}
 
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect_value(PartialEq, Serialize, Deserialize)]
@@ -1,9 +1,14 @@
-use crate::Node;
-use bevy_asset::{Assets, Handle};
-use bevy_ecs::Resources;
+mod texture_node;
+
+pub use texture_node::*;
+
+use crate::{widget::ImageSlices, Node, UiTexturePass};
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::{Resources, With, Without};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     camera::ActiveCameras,
+    color::Color,
     pass::{
         LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
         TextureAttachment,
@@ -11,9 +16,11 @@ use bevy_render::{
     pipeline::*,
     prelude::Msaa,
     render_graph::{
-        base, CameraNode, PassNode, RenderGraph, RenderResourcesNode, WindowSwapChainNode,
-        WindowTextureNode,
+        base::{self, MainPass},
+        AssetRenderResourcesNode, CameraNode, PassNode, RenderGraph, RenderResourcesNode,
+        WindowSwapChainNode, WindowTextureNode,
     },
+    renderer::RenderResources,
     shader::{Shader, ShaderStage, ShaderStages},
     texture::TextureFormat,
 };
@@ -69,9 +76,52 @@ pub fn build_ui_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
     }
 }
 
+pub const UI_IMAGE_SLICES_PIPELINE_HANDLE: Handle<PipelineDescriptor> =
+    Handle::weak_from_u64(PipelineDescriptor::TYPE_UUID, 9017521484310927458);
+
+/// The ui pipeline, with a fragment shader that draws the texture of the node in [ImageSlices]
+pub fn build_ui_image_slices_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    let mut pipeline = build_ui_pipeline(shaders);
+    pipeline.shader_stages.fragment = Some(shaders.add(Shader::from_glsl(
+        ShaderStage::Fragment,
+        include_str!("ui_image_slices.frag"),
+    )));
+    pipeline
+}
+
+/// The ui pipeline, with a custom fragment shader that draws nodes with a material of its own,
+/// like a gradient or a radial cooldown indicator. The material is a [RenderResources] asset,
+/// whose render resources node is added with [UiRenderGraphBuilder::add_ui_material], and its
+/// handle is added to the nodes drawn with the pipeline.
+///
+/// The fragment shader gets the inputs of the vertex shader of the ui: the uvs of the node going
+/// from `(0, 0)` in the top left corner to `(1, 1)` in the bottom right corner, its position in
+/// logical pixels, the area it is visible in and its opacity:
+///
+/// ```glsl
+/// layout(location = 0) in vec2 v_Uv;
+/// layout(location = 1) in vec2 v_Position;
+/// layout(location = 2) in vec4 v_Clip;
+/// layout(location = 3) in float v_Opacity;
+/// ```
+///
+/// The uniforms of the [Node] are in set 1, like `Node_size` at binding 1. The uniforms of the
+/// material go in set 2 when the shader doesn't use the [ColorMaterial](bevy_sprite::ColorMaterial)
+/// of the node, like `layout(set = 2, binding = 0) uniform MyMaterial_color`, and in set 3
+/// otherwise.
+pub fn build_ui_material_pipeline(
+    shaders: &mut Assets<Shader>,
+    fragment: Handle<Shader>,
+) -> PipelineDescriptor {
+    let mut pipeline = build_ui_pipeline(shaders);
+    pipeline.shader_stages.fragment = Some(fragment);
+    pipeline
+}
+
 pub mod node {
     pub const UI_CAMERA: &str = "ui_camera";
     pub const NODE: &str = "node";
+    pub const IMAGE_SLICES: &str = "image_slices";
     pub const UI_PASS: &str = "ui_pass";
 }
 
@@ -81,6 +131,14 @@ pub mod camera {
 
 pub trait UiRenderGraphBuilder {
     fn add_ui_graph(&mut self, resources: &Resources) -> &mut Self;
+    /// Adds a pass that draws the nodes of the ui texture camera named `camera_name` into its
+    /// texture. The nodes are drawn on a transparent background, before the main pass.
+    fn add_ui_texture_graph(&mut self, camera_name: &str, msaa: &Msaa) -> &mut Self;
+    /// Adds a node that binds the render resources of the material assets `T` of ui nodes drawn
+    /// with a [build_ui_material_pipeline], before every pass that draws ui nodes
+    fn add_ui_material<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: RenderResources + Asset;
 }
 
 impl UiRenderGraphBuilder for RenderGraph {
@@ -89,26 +147,32 @@ impl UiRenderGraphBuilder for RenderGraph {
         let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
         let msaa = resources.get::<Msaa>().unwrap();
         pipelines.set_untracked(UI_PIPELINE_HANDLE, build_ui_pipeline(&mut shaders));
+        pipelines.set_untracked(
+            UI_IMAGE_SLICES_PIPELINE_HANDLE,
+            build_ui_image_slices_pipeline(&mut shaders),
+        );
 
-        let mut ui_pass_node = PassNode::<&Node>::new(PassDescriptor {
-            color_attachments: vec![msaa.color_attachment_descriptor(
-                TextureAttachment::Input("color_attachment".to_string()),
-                TextureAttachment::Input("color_resolve_target".to_string()),
-                Operations {
-                    load: LoadOp::Load,
-                    store: true,
-                },
-            )],
-            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
-                attachment: TextureAttachment::Input("depth".to_string()),
-                depth_ops: Some(Operations {
-                    load: LoadOp::Clear(1.0),
-                    store: true,
+        // world space nodes are drawn by the main pass, and texture nodes by their own passes
+        let mut ui_pass_node =
+            PassNode::<&Node, (Without<MainPass>, Without<UiTexturePass>)>::new(PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input("color_attachment".to_string()),
+                    TextureAttachment::Input("color_resolve_target".to_string()),
+                    Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: TextureAttachment::Input("depth".to_string()),
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
                 }),
-                stencil_ops: None,
-            }),
-            sample_count: msaa.samples,
-        });
+                sample_count: msaa.samples,
+            });
 
         ui_pass_node.add_camera(camera::UI_CAMERA);
         self.add_node(node::UI_PASS, ui_pass_node);
@@ -152,8 +216,99 @@ impl UiRenderGraphBuilder for RenderGraph {
         self.add_node_edge(node::UI_CAMERA, node::UI_PASS).unwrap();
         self.add_system_node(node::NODE, RenderResourcesNode::<Node>::new(true));
         self.add_node_edge(node::NODE, node::UI_PASS).unwrap();
+        self.add_system_node(
+            node::IMAGE_SLICES,
+            RenderResourcesNode::<ImageSlices>::new(true),
+        );
+        self.add_node_edge(node::IMAGE_SLICES, node::UI_PASS)
+            .unwrap();
         let mut active_cameras = resources.get_mut::<ActiveCameras>().unwrap();
         active_cameras.add(camera::UI_CAMERA);
         self
     }
+    fn add_ui_texture_graph(&mut self, camera_name: &str, msaa: &Msaa) -> &mut Self {
+        let texture_node = format!("{}_texture", camera_name);
+        let pass_node = format!("{}_pass", camera_name);
+        let camera_node = format!("{}_camera", camera_name);
+
+        let mut ui_texture_pass_node =
+            PassNode::<&Node, With<UiTexturePass>>::new(PassDescriptor {
+                color_attachments: vec![msaa.color_attachment_descriptor(
+                    TextureAttachment::Input("color_attachment".to_string()),
+                    TextureAttachment::Input("color_resolve_target".to_string()),
+                    Operations {
+                        load: LoadOp::Clear(Color::NONE),
+                        store: true,
+                    },
+                )],
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: TextureAttachment::Input("depth".to_string()),
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+                sample_count: msaa.samples,
+            });
+        ui_texture_pass_node.add_camera(camera_name);
+        self.add_node(pass_node.clone(), ui_texture_pass_node);
+        self.add_node(
+            texture_node.clone(),
+            UiTextureNode::new(camera_name, msaa.samples),
+        );
+
+        self.add_slot_edge(
+            texture_node.clone(),
+            UiTextureNode::OUT_TEXTURE,
+            pass_node.clone(),
+            if msaa.samples > 1 {
+                "color_resolve_target"
+            } else {
+                "color_attachment"
+            },
+        )
+        .unwrap();
+
+        self.add_slot_edge(
+            texture_node.clone(),
+            UiTextureNode::OUT_DEPTH,
+            pass_node.clone(),
+            "depth",
+        )
+        .unwrap();
+
+        if msaa.samples > 1 {
+            self.add_slot_edge(
+                texture_node,
+                UiTextureNode::OUT_SAMPLED_COLOR_ATTACHMENT,
+                pass_node.clone(),
+                "color_attachment",
+            )
+            .unwrap();
+        }
+
+        // the texture is drawn before the passes that use it
+        self.add_node_edge(pass_node.clone(), base::node::MAIN_PASS)
+            .unwrap();
+
+        self.add_system_node(
+            camera_node.clone(),
+            CameraNode::new(camera_name.to_string()),
+        );
+        self.add_node_edge(camera_node, pass_node.clone()).unwrap();
+        self.add_node_edge(node::NODE, pass_node.clone()).unwrap();
+        self.add_node_edge(node::IMAGE_SLICES, pass_node).unwrap();
+        self
+    }
+
+    fn add_ui_material<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: RenderResources + Asset,
+    {
+        self.add_system_node(name.to_string(), AssetRenderResourcesNode::<T>::new(true));
+        // the node resources run before all the ui passes, including the ones added later
+        self.add_node_edge(name.to_string(), node::NODE).unwrap();
+        self
+    }
 }
@@ -0,0 +1,172 @@
+use crate::UiTextureCamera;
+use bevy_asset::Handle;
+use bevy_ecs::{Resources, World};
+use bevy_render::{
+    camera::ActiveCameras,
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{RenderContext, RenderResourceContext, RenderResourceId, RenderResourceType},
+    texture::{
+        Extent3d, SamplerDescriptor, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+        SAMPLER_ASSET_INDEX, TEXTURE_ASSET_INDEX,
+    },
+};
+use std::borrow::Cow;
+
+/// Creates the textures a ui texture camera draws into: the texture of its [UiTextureCamera], a
+/// multi-sampled color attachment when msaa is enabled, and a depth texture. They are created
+/// again when the size or the texture of the camera changes.
+pub struct UiTextureNode {
+    camera_name: String,
+    samples: u32,
+    texture: Option<(Handle<Texture>, Extent3d)>,
+}
+
+impl UiTextureNode {
+    pub const OUT_TEXTURE: &'static str = "texture";
+    pub const OUT_SAMPLED_COLOR_ATTACHMENT: &'static str = "sampled_color_attachment";
+    pub const OUT_DEPTH: &'static str = "depth";
+
+    pub fn new(camera_name: &str, samples: u32) -> Self {
+        UiTextureNode {
+            camera_name: camera_name.to_string(),
+            samples,
+            texture: None,
+        }
+    }
+}
+
+impl Node for UiTextureNode {
+    fn output(&self) -> &[ResourceSlotInfo] {
+        static OUTPUT: &[ResourceSlotInfo] = &[
+            ResourceSlotInfo {
+                name: Cow::Borrowed(UiTextureNode::OUT_TEXTURE),
+                resource_type: RenderResourceType::Texture,
+            },
+            ResourceSlotInfo {
+                name: Cow::Borrowed(UiTextureNode::OUT_SAMPLED_COLOR_ATTACHMENT),
+                resource_type: RenderResourceType::Texture,
+            },
+            ResourceSlotInfo {
+                name: Cow::Borrowed(UiTextureNode::OUT_DEPTH),
+                resource_type: RenderResourceType::Texture,
+            },
+        ];
+        OUTPUT
+    }
+
+    fn update(
+        &mut self,
+        world: &World,
+        resources: &Resources,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        output: &mut ResourceSlots,
+    ) {
+        const TEXTURE: usize = 0;
+        const SAMPLED_COLOR_ATTACHMENT: usize = 1;
+        const DEPTH: usize = 2;
+        let active_cameras = resources.get::<ActiveCameras>().unwrap();
+        let camera_texture = active_cameras
+            .get(&self.camera_name)
+            .and_then(|camera| world.get::<UiTextureCamera>(camera).ok())
+            .map(|ui_texture_camera| {
+                let size = Extent3d::new(
+                    (ui_texture_camera.size.width as u32).max(1),
+                    (ui_texture_camera.size.height as u32).max(1),
+                    1,
+                );
+                (ui_texture_camera.texture.clone(), size)
+            });
+        match camera_texture {
+            // the pass always needs textures, so they are kept until the camera is spawned again
+            None if output.get(TEXTURE).is_some() => return,
+            Some(ref camera_texture) if self.texture.as_ref() == Some(camera_texture) => return,
+            _ => {}
+        }
+
+        let render_resource_context = render_context.resources_mut();
+        for index in &[SAMPLED_COLOR_ATTACHMENT, DEPTH] {
+            if let Some(RenderResourceId::Texture(old_texture)) = output.get(*index) {
+                render_resource_context.remove_texture(old_texture);
+            }
+        }
+        match self.texture.take() {
+            Some((old_texture, _)) => {
+                remove_texture_asset_resources(render_resource_context, &old_texture)
+            }
+            None => {
+                if let Some(RenderResourceId::Texture(old_texture)) = output.get(TEXTURE) {
+                    render_resource_context.remove_texture(old_texture);
+                }
+            }
+        }
+
+        // until the camera is spawned, the pass draws into a placeholder
+        let size = camera_texture
+            .as_ref()
+            .map_or(Extent3d::new(1, 1, 1), |(_, size)| *size);
+        let descriptor = TextureDescriptor {
+            size,
+            format: TextureFormat::default(),
+            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+            ..Default::default()
+        };
+        let texture_resource = render_resource_context.create_texture(descriptor);
+        if let Some((ref texture, _)) = camera_texture {
+            let sampler_resource =
+                render_resource_context.create_sampler(&SamplerDescriptor::default());
+            render_resource_context.set_asset_resource(
+                texture,
+                RenderResourceId::Texture(texture_resource),
+                TEXTURE_ASSET_INDEX,
+            );
+            render_resource_context.set_asset_resource(
+                texture,
+                RenderResourceId::Sampler(sampler_resource),
+                SAMPLER_ASSET_INDEX,
+            );
+        }
+        output.set(TEXTURE, RenderResourceId::Texture(texture_resource));
+
+        if self.samples > 1 {
+            let sampled_color_attachment =
+                render_resource_context.create_texture(TextureDescriptor {
+                    sample_count: self.samples,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    ..descriptor
+                });
+            output.set(
+                SAMPLED_COLOR_ATTACHMENT,
+                RenderResourceId::Texture(sampled_color_attachment),
+            );
+        }
+
+        let depth_texture = render_resource_context.create_texture(TextureDescriptor {
+            sample_count: self.samples,
+            format: TextureFormat::Depth32Float,
+            usage: TextureUsage::OUTPUT_ATTACHMENT,
+            ..descriptor
+        });
+        output.set(DEPTH, RenderResourceId::Texture(depth_texture));
+
+        self.texture = camera_texture;
+    }
+}
+
+fn remove_texture_asset_resources(
+    render_resource_context: &dyn RenderResourceContext,
+    handle: &Handle<Texture>,
+) {
+    if let Some(RenderResourceId::Texture(resource)) =
+        render_resource_context.get_asset_resource(handle, TEXTURE_ASSET_INDEX)
+    {
+        render_resource_context.remove_texture(resource);
+        render_resource_context.remove_asset_resource(handle, TEXTURE_ASSET_INDEX);
+    }
+    if let Some(RenderResourceId::Sampler(resource)) =
+        render_resource_context.get_asset_resource(handle, SAMPLER_ASSET_INDEX)
+    {
+        render_resource_context.remove_sampler(resource);
+        render_resource_context.remove_asset_resource(handle, SAMPLER_ASSET_INDEX);
+    }
+}
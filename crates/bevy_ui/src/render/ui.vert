@@ -5,6 +5,9 @@ layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
 
 layout(location = 0) out vec2 v_Uv;
+layout(location = 1) out vec2 v_Position;
+layout(location = 2) out vec4 v_Clip;
+layout(location = 3) out float v_Opacity;
 
 layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
@@ -16,9 +19,19 @@ layout(set = 1, binding = 0) uniform Transform {
 layout(set = 1, binding = 1) uniform Node_size {
     vec2 NodeSize;
 };
+layout(set = 1, binding = 2) uniform Node_clip {
+    vec4 NodeClip;
+};
+layout(set = 1, binding = 3) uniform Node_opacity {
+    float NodeOpacity;
+};
 
 void main() {
     v_Uv = Vertex_Uv;
     vec3 position = Vertex_Position * vec3(NodeSize, 0.0);
-    gl_Position = ViewProj * Object * vec4(position, 1.0);
+    vec4 world_position = Object * vec4(position, 1.0);
+    v_Position = world_position.xy;
+    v_Clip = NodeClip;
+    v_Opacity = NodeOpacity;
+    gl_Position = ViewProj * world_position;
 }
\ No newline at end of file
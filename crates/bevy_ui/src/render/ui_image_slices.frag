@@ -0,0 +1,119 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec2 v_Position;
+layout(location = 2) in vec4 v_Clip;
+layout(location = 3) in float v_Opacity;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 1) uniform Node_size {
+    vec2 NodeSize;
+};
+
+layout(set = 1, binding = 4) uniform Node_corner_radius {
+    vec4 CornerRadius;
+};
+layout(set = 1, binding = 5) uniform Node_border {
+    vec4 NodeBorder;
+};
+layout(set = 1, binding = 6) uniform Node_border_color {
+    vec4 BorderColor;
+};
+
+layout(set = 2, binding = 0) uniform ColorMaterial_color {
+    vec4 Color;
+};
+
+# ifdef COLORMATERIAL_TEXTURE 
+layout(set = 2, binding = 1) uniform texture2D ColorMaterial_texture;
+layout(set = 2, binding = 2) uniform sampler ColorMaterial_texture_sampler;
+# endif
+
+layout(set = 3, binding = 0) uniform ImageSlices_border {
+    vec4 Border;
+};
+layout(set = 3, binding = 1) uniform ImageSlices_texture_size {
+    vec2 TextureSize;
+};
+
+// maps a distance along one axis of the node to a distance along the same axis of the texture,
+// where start and end are the insets of the slices on that axis
+float slice(float position, float size, float texture_size, float start, float end) {
+    if (position < start) {
+        return position;
+    }
+    if (position > size - end) {
+        return texture_size - (size - position);
+    }
+    float center = texture_size - start - end;
+    float node_center = size - start - end;
+    if (center <= 0.0 || node_center <= 0.0) {
+        return start;
+    }
+# ifdef IMAGESLICES_TILE
+    return start + mod(position - start, center);
+# else
+    return start + (position - start) / node_center * center;
+# endif
+}
+
+// the signed distance from a point to a rectangle with rounded corners, negative inside. The point
+// is relative to the center of the rectangle with y down, and the radii are
+// (top left, top right, bottom right, bottom left).
+float rounded_rect_distance(vec2 point, vec2 half_size, vec4 radii) {
+    float radius = point.x < 0.0
+        ? (point.y < 0.0 ? radii.x : radii.w)
+        : (point.y < 0.0 ? radii.y : radii.z);
+    radius = min(radius, min(half_size.x, half_size.y));
+    vec2 corner = abs(point) - half_size + radius;
+    return min(max(corner.x, corner.y), 0.0) + length(max(corner, 0.0)) - radius;
+}
+
+// draws the border of the node over its color, and fades its rounded corners over a pixel
+vec4 border_and_corners(vec4 color, vec2 position) {
+    float outer = rounded_rect_distance(position - NodeSize / 2.0, NodeSize / 2.0, CornerRadius);
+    if (outer >= 0.5) {
+        discard;
+    }
+    // NodeBorder is (left, right, top, bottom)
+    if (any(greaterThan(NodeBorder, vec4(0.0)))) {
+        vec2 inner_min = NodeBorder.xz;
+        vec2 inner_max = max(NodeSize - NodeBorder.yw, inner_min);
+        vec4 inner_radii = max(CornerRadius - vec4(
+            max(NodeBorder.x, NodeBorder.z),
+            max(NodeBorder.y, NodeBorder.z),
+            max(NodeBorder.y, NodeBorder.w),
+            max(NodeBorder.x, NodeBorder.w)
+        ), 0.0);
+        float inner = rounded_rect_distance(
+            position - (inner_min + inner_max) / 2.0,
+            (inner_max - inner_min) / 2.0,
+            inner_radii);
+        color = mix(BorderColor, color, clamp(0.5 - inner, 0.0, 1.0));
+    }
+    color.a *= clamp(0.5 - outer, 0.0, 1.0);
+    return color;
+}
+
+void main() {
+    // v_Clip is (min x, min y, max x, max y) of the area the node is visible in
+    if (any(lessThan(v_Position, v_Clip.xy)) || any(greaterThan(v_Position, v_Clip.zw))) {
+        discard;
+    }
+    // the uvs of the quad go from the top left to the bottom right, like the texture
+    vec2 position = v_Uv * NodeSize;
+    vec2 uv = vec2(
+        slice(position.x, NodeSize.x, TextureSize.x, Border.x, Border.y),
+        slice(position.y, NodeSize.y, TextureSize.y, Border.z, Border.w)
+    ) / TextureSize;
+    vec4 color = Color;
+# ifdef COLORMATERIAL_TEXTURE
+    color *= texture(
+        sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler),
+        uv);
+# endif
+    color = border_and_corners(color, position);
+    color.a *= v_Opacity;
+    o_Target = color;
+}
@@ -0,0 +1,287 @@
+use crate::{Interaction, Node, Style, UiScale, Val};
+use bevy_app::{EventReader, Events};
+use bevy_core::FloatOrd;
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{Reflect, ReflectComponent};
+use bevy_transform::prelude::{GlobalTransform, Parent};
+use bevy_utils::HashMap;
+use bevy_window::CursorMoved;
+
+/// The distance in logical pixels scrolled for each line of mouse wheel movement
+pub const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+/// How far the children of a node with [Overflow::Scroll](crate::Overflow::Scroll) are
+/// scrolled, in logical pixels. Positive offsets scroll the content right and down.
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ScrollPosition {
+    pub offset: Vec2,
+    /// The smallest offset the content can be scrolled to. It is computed from the bounds of the
+    /// children during layout.
+    pub min_offset: Vec2,
+    /// The largest offset the content can be scrolled to. It is computed from the bounds of the
+    /// children during layout.
+    pub max_offset: Vec2,
+}
+
+impl ScrollPosition {
+    /// Scrolls by `delta`, staying within the bounds of the content
+    pub fn scroll_by(&mut self, delta: Vec2) {
+        self.offset = (self.offset + delta)
+            .max(self.min_offset)
+            .min(self.max_offset);
+    }
+
+    /// Updates the scrollable range from the bounds of the content, `min` and `max`, relative to
+    /// the bottom left corner of a node of the given `size`
+    pub fn set_content_bounds(&mut self, min: Vec2, max: Vec2, size: Vec2) {
+        // content below the node is revealed by scrolling down, which moves the content up
+        self.min_offset = Vec2::new(min.x().min(0.0), -(max.y() - size.y()).max(0.0));
+        self.max_offset = Vec2::new((max.x() - size.x()).max(0.0), -min.y().min(0.0));
+        self.scroll_by(Vec2::zero());
+    }
+
+    /// The size of the scrolled content, given the size of the node
+    pub fn content_size(&self, size: Vec2) -> Vec2 {
+        size + self.max_offset - self.min_offset
+    }
+
+    /// How far the offset is into the scrollable range, from 0.0 at the left and top to 1.0 at
+    /// the right and bottom
+    pub fn fraction(&self) -> Vec2 {
+        let range = self.max_offset - self.min_offset;
+        let fraction = |offset: f32, min: f32, range: f32| {
+            if range > 0.0 {
+                (offset - min) / range
+            } else {
+                0.0
+            }
+        };
+        Vec2::new(
+            fraction(self.offset.x(), self.min_offset.x(), range.x()),
+            fraction(self.offset.y(), self.min_offset.y(), range.y()),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ScrollAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// Makes a node the thumb of a scrollbar. Its size and position along `axis` follow the scroll
+/// position of `container`, and dragging it scrolls the container. The thumb should be an
+/// absolutely positioned child of a node acting as the track, with an [Interaction] to be
+/// draggable.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollbarThumb {
+    pub container: Entity,
+    pub axis: ScrollAxis,
+}
+
+#[derive(Default)]
+pub struct ScrollState {
+    mouse_wheel_event_reader: EventReader<MouseWheel>,
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+    /// The containers scrolled by the touches that started on them
+    touch_containers: HashMap<u64, Entity>,
+}
+
+/// Scrolls the containers with the mouse wheel, touch drags and their scrollbar thumbs
+#[allow(clippy::too_many_arguments)]
+pub fn ui_scroll_system(
+    mut state: Local<ScrollState>,
+    mouse_wheel_events: Res<Events<MouseWheel>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    touches: Res<Touches>,
+    mut scroll_query: Query<(Entity, &Node, &GlobalTransform, &mut ScrollPosition)>,
+    thumb_query: Query<(&ScrollbarThumb, &Interaction, Option<&Parent>)>,
+    node_query: Query<&Node>,
+) {
+    let state = &mut *state;
+    let previous_cursor_position = state.cursor_position;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+    let cursor_delta = state.cursor_position - previous_cursor_position;
+
+    let mut wheel_delta = Vec2::zero();
+    for event in state.mouse_wheel_event_reader.iter(&mouse_wheel_events) {
+        let scale = match event.unit {
+            MouseScrollUnit::Line => SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => 1.0 / ui_scale.scale as f32,
+        };
+        wheel_delta -= Vec2::new(event.x, event.y) * scale;
+    }
+    if wheel_delta != Vec2::zero() {
+        if let Some(container) = container_at(&mut scroll_query, state.cursor_position) {
+            if let Ok(mut scroll_position) =
+                scroll_query.get_component_mut::<ScrollPosition>(container)
+            {
+                scroll_position.scroll_by(wheel_delta);
+            }
+        }
+    }
+
+    // the content follows the touches that started on it
+    for touch in touches.iter_just_pressed() {
+        if let Some(container) = container_at(&mut scroll_query, ui_scale.to_ui(touch.position())) {
+            state.touch_containers.insert(touch.id(), container);
+        }
+    }
+    for touch in touches.iter() {
+        if let Some(container) = state.touch_containers.get(&touch.id()) {
+            if let Ok(mut scroll_position) =
+                scroll_query.get_component_mut::<ScrollPosition>(*container)
+            {
+                let delta = ui_scale.to_ui(touch.delta());
+                scroll_position.scroll_by(Vec2::new(-delta.x(), delta.y()));
+            }
+        }
+    }
+    for touch in touches
+        .iter_just_released()
+        .chain(touches.iter_just_cancelled())
+    {
+        state.touch_containers.remove(&touch.id());
+    }
+
+    if cursor_delta == Vec2::zero() {
+        return;
+    }
+    for (thumb, interaction, parent) in thumb_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let track_size = match parent.and_then(|parent| node_query.get(parent.0).ok()) {
+            Some(track) => track.size,
+            None => continue,
+        };
+        if let Ok((_entity, node, _global_transform, mut scroll_position)) =
+            scroll_query.get_mut(thumb.container)
+        {
+            // the thumb moves across the track as the view moves across the content
+            let content_size = scroll_position.content_size(node.size);
+            let delta = match thumb.axis {
+                ScrollAxis::Horizontal if track_size.x() > 0.0 => {
+                    Vec2::new(cursor_delta.x() * content_size.x() / track_size.x(), 0.0)
+                }
+                ScrollAxis::Vertical if track_size.y() > 0.0 => {
+                    Vec2::new(0.0, -cursor_delta.y() * content_size.y() / track_size.y())
+                }
+                _ => continue,
+            };
+            scroll_position.scroll_by(delta);
+        }
+    }
+}
+
+/// Returns the topmost scroll container visible at `position`
+fn container_at(
+    scroll_query: &mut Query<(Entity, &Node, &GlobalTransform, &mut ScrollPosition)>,
+    position: Vec2,
+) -> Option<Entity> {
+    scroll_query
+        .iter_mut()
+        .filter(|(_entity, node, global_transform, _scroll_position)| {
+            let center = global_transform.translation.truncate();
+            let extents = node.size / 2.0;
+            let min = center - extents;
+            let max = center + extents;
+            (min.x()..max.x()).contains(&position.x())
+                && (min.y()..max.y()).contains(&position.y())
+                && node.is_visible_at(position)
+        })
+        .max_by_key(|(_entity, _node, global_transform, _scroll_position)| {
+            FloatOrd(global_transform.translation.z())
+        })
+        .map(|(entity, _node, _global_transform, _scroll_position)| entity)
+}
+
+/// Sizes and positions the scrollbar thumbs from the scroll position of their containers
+pub fn scrollbar_system(
+    scroll_query: Query<(&Node, &ScrollPosition)>,
+    mut thumb_query: Query<(&ScrollbarThumb, &mut Style)>,
+) {
+    for (thumb, mut style) in thumb_query.iter_mut() {
+        let (node, scroll_position) = match scroll_query.get(thumb.container) {
+            Ok(container) => container,
+            Err(_) => continue,
+        };
+        let content_size = scroll_position.content_size(node.size);
+        let fraction = scroll_position.fraction();
+        let visible_percent = |size: f32, content_size: f32| {
+            if content_size > 0.0 {
+                (size / content_size).min(1.0) * 100.0
+            } else {
+                100.0
+            }
+        };
+
+        let mut size = style.size;
+        let mut position = style.position;
+        match thumb.axis {
+            ScrollAxis::Horizontal => {
+                let width = visible_percent(node.size.x(), content_size.x());
+                size.width = Val::Percent(width);
+                position.left = Val::Percent(fraction.x() * (100.0 - width));
+            }
+            ScrollAxis::Vertical => {
+                let height = visible_percent(node.size.y(), content_size.y());
+                size.height = Val::Percent(height);
+                position.top = Val::Percent(fraction.y() * (100.0 - height));
+            }
+        }
+        // only changed styles are laid out again
+        if style.size != size || style.position != position {
+            style.size = size;
+            style.position = position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollPosition;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn scroll_position_is_clamped_to_content() {
+        let mut scroll_position = ScrollPosition::default();
+        // 300 pixels of content below a 100 pixel tall node
+        scroll_position.set_content_bounds(
+            Vec2::new(0.0, -200.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(100.0, 100.0),
+        );
+        assert_eq!(scroll_position.min_offset, Vec2::zero());
+        assert_eq!(scroll_position.max_offset, Vec2::new(0.0, 200.0));
+        assert_eq!(
+            scroll_position.content_size(Vec2::new(100.0, 100.0)),
+            Vec2::new(100.0, 300.0)
+        );
+
+        scroll_position.scroll_by(Vec2::new(50.0, 50.0));
+        assert_eq!(scroll_position.offset, Vec2::new(0.0, 50.0));
+        assert_eq!(scroll_position.fraction(), Vec2::new(0.0, 0.25));
+
+        scroll_position.scroll_by(Vec2::new(0.0, 1000.0));
+        assert_eq!(scroll_position.offset, Vec2::new(0.0, 200.0));
+
+        // shrinking the content pulls the offset back into range
+        scroll_position.set_content_bounds(
+            Vec2::new(0.0, -50.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(100.0, 100.0),
+        );
+        assert_eq!(scroll_position.offset, Vec2::new(0.0, 50.0));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{Display, Interaction, Node, PositionType, Style, UiScale, Val};
+use bevy_app::{EventReader, Events};
+use bevy_core::Time;
+use bevy_ecs::{Entity, Local, Query, Res};
+use bevy_math::Vec2;
+use bevy_render::draw::Draw;
+use bevy_transform::prelude::Children;
+use bevy_window::{CursorMoved, Windows};
+
+/// Shows the `content` root node and its descendants after the cursor hovered the node for
+/// `delay` seconds, and hides them when the cursor leaves the node. The node needs an
+/// [Interaction]. The content is placed with its top left corner at `offset` from the cursor, and
+/// kept within the window, so it should let the cursor pass with
+/// [FocusPolicy::Pass](crate::FocusPolicy::Pass) to not take the hover from the node.
+#[derive(Debug, Clone)]
+pub struct Tooltip {
+    pub content: Entity,
+    /// The time in seconds the node is hovered before the content is shown
+    pub delay: f32,
+    pub offset: Vec2,
+    hovered_time: f32,
+    /// Whether the content is shown, once it was first shown or hidden
+    shown: Option<bool>,
+}
+
+impl Tooltip {
+    pub fn new(content: Entity) -> Self {
+        Tooltip {
+            content,
+            delay: 0.5,
+            offset: Vec2::new(12.0, -12.0),
+            hovered_time: 0.0,
+            shown: None,
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.shown == Some(true)
+    }
+}
+
+#[derive(Default)]
+pub struct TooltipState {
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+}
+
+/// Shows and hides the content of [Tooltip]s, and moves the shown content with the cursor
+#[allow(clippy::too_many_arguments)]
+pub fn tooltip_system(
+    mut state: Local<TooltipState>,
+    time: Res<Time>,
+    windows: Res<Windows>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    mut tooltip_query: Query<(&mut Tooltip, &Interaction)>,
+    mut content_query: Query<(&mut Style, &Node)>,
+    mut draw_query: Query<&mut Draw>,
+    children_query: Query<&Children>,
+) {
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+    let window_size = windows
+        .get_primary()
+        .map(|window| ui_scale.to_ui(Vec2::new(window.width() as f32, window.height() as f32)));
+
+    for (mut tooltip, interaction) in tooltip_query.iter_mut() {
+        let hovered = *interaction != Interaction::None;
+        if hovered {
+            tooltip.hovered_time += time.raw_delta_seconds();
+        } else if tooltip.hovered_time > 0.0 {
+            tooltip.hovered_time = 0.0;
+        }
+        let shown = hovered && tooltip.hovered_time >= tooltip.delay;
+
+        let (mut style, node) = match content_query.get_mut(tooltip.content) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        if tooltip.shown != Some(shown) {
+            tooltip.shown = Some(shown);
+            // hidden content takes no space, so that it doesn't block the cursor
+            style.display = if shown { Display::Flex } else { Display::None };
+            set_visible(&mut draw_query, &children_query, tooltip.content, shown);
+        }
+        if !shown {
+            continue;
+        }
+
+        // the size of the content is known once it was laid out after being shown
+        let mut left = state.cursor_position.x() + tooltip.offset.x();
+        let mut bottom = state.cursor_position.y() + tooltip.offset.y() - node.size.y();
+        if let Some(window_size) = window_size {
+            left = left.min(window_size.x() - node.size.x()).max(0.0);
+            bottom = bottom.min(window_size.y() - node.size.y()).max(0.0);
+        }
+        // the style is only changed when the content moved, to not lay it out again every frame
+        if style.position_type != PositionType::Absolute {
+            style.position_type = PositionType::Absolute;
+        }
+        if style.position.left != Val::Px(left) || style.position.bottom != Val::Px(bottom) {
+            style.position.left = Val::Px(left);
+            style.position.bottom = Val::Px(bottom);
+        }
+    }
+}
+
+fn set_visible(
+    draw_query: &mut Query<&mut Draw>,
+    children_query: &Query<&Children>,
+    entity: Entity,
+    is_visible: bool,
+) {
+    if let Ok(mut draw) = draw_query.get_mut(entity) {
+        draw.is_visible = is_visible;
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            set_visible(draw_query, children_query, *child, is_visible);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{Node, Opacity, Style, Val};
+use bevy_app::prelude::Events;
+use bevy_asset::{Assets, Handle};
+use bevy_core::Time;
+use bevy_ecs::{Commands, Entity, Query, Res, ResMut, With};
+use bevy_math::{Rect, Size, Vec4};
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use std::f32::consts::PI;
+
+/// How the progress of a [Transition] speeds up and slows down over its duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut,
+    /// Overshoots the target a little before settling on it
+    BackOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::CubicOut
+    }
+}
+
+impl Easing {
+    /// Maps the elapsed fraction `t` of a transition, from 0.0 to 1.0, to its progress
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadraticIn => t * t,
+            Easing::QuadraticOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Easing::SineInOut => (1.0 - (PI * t).cos()) / 2.0,
+            Easing::BackOut => {
+                const OVERSHOOT: f32 = 1.70158;
+                let t = t - 1.0;
+                1.0 + t * t * ((OVERSHOOT + 1.0) * t + OVERSHOOT)
+            }
+        }
+    }
+}
+
+/// A property of a node animated by a [Transition], with the value it is animated to
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionTarget {
+    /// The [Style::position] of the node
+    Position(Rect<Val>),
+    /// The [Style::size] of the node
+    Size(Size<Val>),
+    /// The color of the [ColorMaterial] of the node. The material is changed in place, so all the
+    /// nodes that share it change color together.
+    Color(Color),
+    /// The [Opacity] of the node
+    Opacity(f32),
+}
+
+impl TransitionTarget {
+    fn is_same_property(&self, other: &TransitionTarget) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Interpolates from `self` to `target`. Lengths in different units can't be interpolated,
+    /// and jump to the target at the end.
+    fn lerp(&self, target: &TransitionTarget, progress: f32) -> TransitionTarget {
+        match (self, target) {
+            (TransitionTarget::Position(start), TransitionTarget::Position(end)) => {
+                TransitionTarget::Position(Rect {
+                    left: lerp_val(start.left, end.left, progress),
+                    right: lerp_val(start.right, end.right, progress),
+                    top: lerp_val(start.top, end.top, progress),
+                    bottom: lerp_val(start.bottom, end.bottom, progress),
+                })
+            }
+            (TransitionTarget::Size(start), TransitionTarget::Size(end)) => {
+                TransitionTarget::Size(Size::new(
+                    lerp_val(start.width, end.width, progress),
+                    lerp_val(start.height, end.height, progress),
+                ))
+            }
+            (TransitionTarget::Color(start), TransitionTarget::Color(end)) => {
+                TransitionTarget::Color(Vec4::from(*start).lerp(Vec4::from(*end), progress).into())
+            }
+            (TransitionTarget::Opacity(start), TransitionTarget::Opacity(end)) => {
+                TransitionTarget::Opacity(start + (end - start) * progress)
+            }
+            _ => target.clone(),
+        }
+    }
+}
+
+fn lerp_val(start: Val, end: Val, progress: f32) -> Val {
+    match (start, end) {
+        (Val::Px(start), Val::Px(end)) => Val::Px(start + (end - start) * progress),
+        (Val::Percent(start), Val::Percent(end)) => Val::Percent(start + (end - start) * progress),
+        _ if progress >= 1.0 => end,
+        _ => start,
+    }
+}
+
+/// Animates a property of a node from its current value to `target`
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub target: TransitionTarget,
+    /// The duration of the transition in seconds
+    pub duration: f32,
+    pub easing: Easing,
+    /// The time in seconds before the transition starts
+    pub delay: f32,
+    elapsed: f32,
+    start: Option<TransitionTarget>,
+}
+
+impl Transition {
+    pub fn new(target: TransitionTarget, duration: f32, easing: Easing) -> Self {
+        Transition {
+            target,
+            duration,
+            easing,
+            delay: 0.0,
+            elapsed: 0.0,
+            start: None,
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// The progress of the transition, from 0.0 when it starts to 1.0 when it is finished
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            self.easing
+                .ease((self.elapsed - self.delay) / self.duration)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+}
+
+/// The transitions running on a node. Starting a transition of a property that is already
+/// animated replaces the running transition, which continues from the current value.
+#[derive(Debug, Clone, Default)]
+pub struct Transitions {
+    transitions: Vec<Transition>,
+}
+
+impl Transitions {
+    pub fn new(transition: Transition) -> Self {
+        Transitions {
+            transitions: vec![transition],
+        }
+    }
+
+    pub fn with(mut self, transition: Transition) -> Self {
+        self.start(transition);
+        self
+    }
+
+    pub fn start(&mut self, transition: Transition) {
+        self.transitions
+            .retain(|running| !running.target.is_same_property(&transition.target));
+        self.transitions.push(transition);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Transition> {
+        self.transitions.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+}
+
+/// Sent when a transition of a node reached its target
+#[derive(Debug, Clone)]
+pub struct TransitionFinished {
+    pub entity: Entity,
+    pub target: TransitionTarget,
+}
+
+/// Advances the [Transitions] of nodes and sets the animated properties
+pub fn ui_transition_system(
+    commands: &mut Commands,
+    time: Res<Time>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut finished_events: ResMut<Events<TransitionFinished>>,
+    mut node_query: Query<
+        (
+            Entity,
+            &mut Transitions,
+            &mut Style,
+            Option<&mut Opacity>,
+            Option<&Handle<ColorMaterial>>,
+        ),
+        With<Node>,
+    >,
+) {
+    let delta = time.raw_delta_seconds();
+    for (entity, mut transitions, mut style, mut opacity, material) in node_query.iter_mut() {
+        if transitions.is_empty() {
+            continue;
+        }
+        for transition in transitions.transitions.iter_mut() {
+            transition.elapsed += delta;
+            if transition.elapsed < transition.delay {
+                continue;
+            }
+
+            let current = match transition.target {
+                TransitionTarget::Position(_) => TransitionTarget::Position(style.position),
+                TransitionTarget::Size(_) => TransitionTarget::Size(style.size),
+                TransitionTarget::Color(_) => {
+                    match material.and_then(|material| color_materials.get(material)) {
+                        Some(material) => TransitionTarget::Color(material.color),
+                        None => continue,
+                    }
+                }
+                TransitionTarget::Opacity(_) => {
+                    TransitionTarget::Opacity(opacity.as_ref().map_or(1.0, |opacity| opacity.0))
+                }
+            };
+            let progress = transition.progress();
+            let start = transition.start.get_or_insert(current);
+            let value = start.lerp(&transition.target, progress);
+
+            match value {
+                TransitionTarget::Position(position) => style.position = position,
+                TransitionTarget::Size(size) => style.size = size,
+                TransitionTarget::Color(color) => {
+                    if let Some(material) =
+                        material.and_then(|material| color_materials.get_mut(material))
+                    {
+                        material.color = color;
+                    }
+                }
+                TransitionTarget::Opacity(value) => match opacity {
+                    Some(ref mut opacity) => opacity.0 = value,
+                    None => commands.insert_one(entity, Opacity(value)),
+                },
+            }
+        }
+
+        transitions.transitions.retain(|transition| {
+            if transition.finished() {
+                finished_events.send(TransitionFinished {
+                    entity,
+                    target: transition.target.clone(),
+                });
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, TransitionTarget};
+    use crate::Val;
+    use bevy_math::Size;
+
+    #[test]
+    fn easings_start_and_end_at_their_targets() {
+        for easing in &[
+            Easing::Linear,
+            Easing::QuadraticIn,
+            Easing::QuadraticOut,
+            Easing::QuadraticInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SineInOut,
+            Easing::BackOut,
+        ] {
+            assert!(easing.ease(0.0).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.ease(1.0) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+        assert_eq!(Easing::QuadraticIn.ease(0.5), 0.25);
+    }
+
+    #[test]
+    fn lengths_in_different_units_jump_at_the_end() {
+        let start = TransitionTarget::Size(Size::new(Val::Px(100.0), Val::Px(10.0)));
+        let end = TransitionTarget::Size(Size::new(Val::Px(200.0), Val::Percent(50.0)));
+        assert_eq!(
+            start.lerp(&end, 0.5),
+            TransitionTarget::Size(Size::new(Val::Px(150.0), Val::Px(10.0)))
+        );
+        assert_eq!(start.lerp(&end, 1.0), end);
+    }
+}
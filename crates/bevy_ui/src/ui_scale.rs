@@ -0,0 +1,59 @@
+use crate::camera::UI_CAMERA;
+use bevy_ecs::{Query, Res};
+use bevy_math::{Vec2, Vec3};
+use bevy_render::camera::Camera;
+use bevy_transform::prelude::Transform;
+
+/// Scales the ui drawn in windows, on top of the scale factor of the window. At a scale of 2, a
+/// node that is 100 pixels wide takes up as much of the window as a node 200 pixels wide at a
+/// scale of 1, and the nodes are laid out within half the size of the window.
+///
+/// Text is drawn at its font size and scaled with the rest of the ui. World space ui and ui
+/// drawn into textures are not scaled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiScale {
+    pub scale: f64,
+}
+
+impl Default for UiScale {
+    fn default() -> Self {
+        UiScale { scale: 1.0 }
+    }
+}
+
+impl UiScale {
+    /// Converts a position in the window, like the position of the cursor, to ui coordinates
+    pub fn to_ui(&self, position: Vec2) -> Vec2 {
+        position / self.scale as f32
+    }
+
+    /// Converts a size or position in ui coordinates to the window
+    pub fn to_window(&self, position: Vec2) -> Vec2 {
+        position * self.scale as f32
+    }
+}
+
+/// Scales the window ui cameras by the [UiScale]
+pub fn ui_scale_system(ui_scale: Res<UiScale>, mut camera_query: Query<(&Camera, &mut Transform)>) {
+    let inverse_scale = 1.0 / ui_scale.scale as f32;
+    let scale = Vec3::new(inverse_scale, inverse_scale, 1.0);
+    for (camera, mut transform) in camera_query.iter_mut() {
+        if camera.name.as_deref() == Some(UI_CAMERA) && transform.scale != scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UiScale;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn window_positions_convert_to_ui_coordinates() {
+        let ui_scale = UiScale { scale: 2.0 };
+        let position = Vec2::new(300.0, 120.0);
+        assert_eq!(ui_scale.to_ui(position), Vec2::new(150.0, 60.0));
+        assert_eq!(ui_scale.to_window(ui_scale.to_ui(position)), position);
+    }
+}
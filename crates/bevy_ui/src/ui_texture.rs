@@ -0,0 +1,113 @@
+use crate::Node;
+use bevy_asset::Handle;
+use bevy_ecs::{Commands, Entity, Query, ResMut, With, Without};
+use bevy_math::Size;
+use bevy_render::{
+    camera::{ActiveCameras, Camera, CameraProjection, OrthographicProjection, VisibleEntities},
+    texture::Texture,
+};
+use bevy_transform::prelude::{Children, Parent};
+
+/// Draws a root node and its descendants into the texture of the ui texture camera named
+/// `camera`, instead of on the screen. The node is laid out within the size of the texture.
+///
+/// Nodes drawn into a texture don't interact with the cursor and can't be focused.
+#[derive(Debug, Clone)]
+pub struct UiTexture {
+    pub camera: String,
+}
+
+/// A ui camera that draws into `texture` instead of the window. The texture is created by the
+/// render graph nodes added with
+/// [add_ui_texture_graph](crate::UiRenderGraphBuilder::add_ui_texture_graph) and can be used by
+/// materials like any other texture, for example to show the ui on a screen in the 3d world. The
+/// handle should not belong to a texture in `Assets<Texture>`.
+#[derive(Debug, Clone)]
+pub struct UiTextureCamera {
+    pub texture: Handle<Texture>,
+    /// The size of the texture in pixels
+    pub size: Size,
+}
+
+/// Marks the nodes drawn by the ui texture camera named `camera`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiTexturePass {
+    pub camera: String,
+}
+
+/// Marks the subtrees of [UiTexture] root nodes, so that they are drawn by their camera instead of
+/// the window ui camera
+pub fn ui_texture_system(
+    commands: &mut Commands,
+    root_query: Query<(Entity, &UiTexture), Without<Parent>>,
+    children_query: Query<&Children>,
+    pass_query: Query<Option<&UiTexturePass>, With<Node>>,
+) {
+    for (entity, ui_texture) in root_query.iter() {
+        let pass = UiTexturePass {
+            camera: ui_texture.camera.clone(),
+        };
+        move_to_texture_pass(commands, &children_query, &pass_query, &pass, entity);
+    }
+}
+
+fn move_to_texture_pass(
+    commands: &mut Commands,
+    children_query: &Query<&Children>,
+    pass_query: &Query<Option<&UiTexturePass>, With<Node>>,
+    pass: &UiTexturePass,
+    entity: Entity,
+) {
+    if let Ok(current_pass) = pass_query.get(entity) {
+        if current_pass != Some(pass) {
+            commands.insert_one(entity, pass.clone());
+        }
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            move_to_texture_pass(commands, children_query, pass_query, pass, child);
+        }
+    }
+}
+
+/// Sizes the projections of ui texture cameras to their textures, and keeps only their own nodes
+/// visible to them. It runs after the projections were sized to the window and the visible
+/// entities were collected.
+pub fn ui_texture_camera_system(
+    mut active_cameras: ResMut<ActiveCameras>,
+    mut camera_query: Query<(
+        Entity,
+        &mut Camera,
+        &mut OrthographicProjection,
+        &mut VisibleEntities,
+        &UiTextureCamera,
+    )>,
+    pass_query: Query<&UiTexturePass>,
+) {
+    for (entity, mut camera, mut projection, mut visible_entities, ui_texture_camera) in
+        camera_query.iter_mut()
+    {
+        let name = match camera.name {
+            Some(ref name) => name.clone(),
+            None => continue,
+        };
+        if active_cameras.get(&name).is_none() {
+            active_cameras.set(&name, entity);
+        }
+
+        projection.update(
+            ui_texture_camera.size.width as usize,
+            ui_texture_camera.size.height as usize,
+        );
+        let projection_matrix = projection.get_projection_matrix();
+        if camera.projection_matrix != projection_matrix {
+            camera.projection_matrix = projection_matrix;
+        }
+
+        visible_entities.value.retain(|visible_entity| {
+            pass_query
+                .get(visible_entity.entity)
+                .map_or(false, |pass| pass.camera == name)
+        });
+    }
+}
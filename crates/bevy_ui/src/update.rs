@@ -1,59 +1,245 @@
-use super::Node;
+use super::{BorderColor, CornerRadius, Node, Opacity, Overflow, Style, Val, WorldSpaceUi, ZIndex};
 use bevy_ecs::{Entity, Query, With, Without};
-use bevy_transform::prelude::{Children, Parent, Transform};
+use bevy_math::Vec4;
+use bevy_render::color::Color;
+use bevy_transform::prelude::{Children, GlobalTransform, Parent, Transform};
+use bevy_utils::HashMap;
 
 pub const UI_Z_STEP: f32 = 0.001;
 
+/// Orders the nodes along z. Children are above their parent and later siblings above earlier
+/// ones, unless a [ZIndex] says otherwise.
 pub fn ui_z_system(
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    mut node_query: Query<&mut Transform, With<Node>>,
+    mut node_query: Query<(&mut Transform, Option<&Parent>), With<Node>>,
     children_query: Query<&Children>,
+    z_index_query: Query<&ZIndex>,
 ) {
-    let mut current_global_z = 0.0;
+    // nodes with a global z-index are ordered with their descendants as if they were root nodes
+    let mut stacking_roots = Vec::new();
+    for entity in root_node_query.iter() {
+        let z_index = match z_index_query.get(entity) {
+            Ok(ZIndex::Global(z_index)) => *z_index,
+            _ => 0,
+        };
+        stacking_roots.push((z_index, entity));
+    }
     for entity in root_node_query.iter() {
+        collect_global_z_indices(&children_query, &z_index_query, entity, &mut stacking_roots);
+    }
+    stacking_roots.sort_by_key(|(z_index, _entity)| *z_index);
+
+    let mut global_zs = HashMap::default();
+    let mut current_global_z = 0.0;
+    for (_z_index, entity) in stacking_roots {
         current_global_z = update_hierarchy(
             &children_query,
-            &mut node_query,
+            &z_index_query,
+            &mut global_zs,
             entity,
             current_global_z,
-            current_global_z,
         );
     }
+
+    // transforms are relative to the parent
+    for (entity, global_z) in global_zs.iter() {
+        if let Ok((mut transform, parent)) = node_query.get_mut(*entity) {
+            let parent_global_z = parent
+                .and_then(|parent| global_zs.get(&parent.0))
+                .cloned()
+                .unwrap_or(0.0);
+            transform.translation.z = global_z - parent_global_z;
+        }
+    }
+}
+
+fn collect_global_z_indices(
+    children_query: &Query<&Children>,
+    z_index_query: &Query<&ZIndex>,
+    entity: Entity,
+    stacking_roots: &mut Vec<(i32, Entity)>,
+) {
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            if let Ok(ZIndex::Global(z_index)) = z_index_query.get(child) {
+                stacking_roots.push((*z_index, child));
+            }
+            collect_global_z_indices(children_query, z_index_query, child, stacking_roots);
+        }
+    }
 }
 
 fn update_hierarchy(
     children_query: &Query<&Children>,
-    node_query: &mut Query<&mut Transform, With<Node>>,
+    z_index_query: &Query<&ZIndex>,
+    global_zs: &mut HashMap<Entity, f32>,
     entity: Entity,
-    parent_global_z: f32,
     mut current_global_z: f32,
 ) -> f32 {
     current_global_z += UI_Z_STEP;
-    if let Ok(mut transform) = node_query.get_mut(entity) {
-        transform.translation.z = current_global_z - parent_global_z;
-    }
+    global_zs.insert(entity, current_global_z);
     if let Ok(children) = children_query.get(entity) {
-        let current_parent_global_z = current_global_z;
-        for child in children.iter().cloned() {
+        let mut ordered_children = children
+            .iter()
+            .filter_map(|child| match z_index_query.get(*child) {
+                Ok(ZIndex::Global(_)) => None,
+                Ok(ZIndex::Local(z_index)) => Some((*z_index, *child)),
+                Err(_) => Some((0, *child)),
+            })
+            .collect::<Vec<_>>();
+        ordered_children.sort_by_key(|(z_index, _child)| *z_index);
+        for (_z_index, child) in ordered_children {
             current_global_z = update_hierarchy(
                 children_query,
-                node_query,
+                z_index_query,
+                global_zs,
                 child,
-                current_parent_global_z,
                 current_global_z,
             );
         }
     }
     current_global_z
 }
+
+/// Clips the nodes to the bounds of their ancestors that don't have [Overflow::Visible]
+pub fn ui_clip_system(
+    root_node_query: Query<Entity, (With<Node>, Without<Parent>, Without<WorldSpaceUi>)>,
+    mut node_query: Query<(&mut Node, &GlobalTransform, Option<&Style>)>,
+    children_query: Query<&Children>,
+) {
+    for entity in root_node_query.iter() {
+        update_clip(&children_query, &mut node_query, entity, Node::unclipped());
+    }
+}
+
+fn update_clip(
+    children_query: &Query<&Children>,
+    node_query: &mut Query<(&mut Node, &GlobalTransform, Option<&Style>)>,
+    entity: Entity,
+    clip: Vec4,
+) {
+    let mut children_clip = clip;
+    if let Ok((mut node, global_transform, style)) = node_query.get_mut(entity) {
+        if node.clip != clip {
+            node.clip = clip;
+        }
+        if style.map_or(false, |style| style.overflow != Overflow::Visible) {
+            let center = global_transform.translation.truncate();
+            let extents = node.size / 2.0;
+            let min = center - extents;
+            let max = center + extents;
+            children_clip = Vec4::new(
+                clip.x().max(min.x()),
+                clip.y().max(min.y()),
+                clip.z().min(max.x()),
+                clip.w().min(max.y()),
+            );
+        }
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            update_clip(children_query, node_query, child, children_clip);
+        }
+    }
+}
+
+/// Multiplies the [Opacity] of nodes down the hierarchy into [Node::opacity]
+pub fn ui_opacity_system(
+    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    mut node_query: Query<(&mut Node, Option<&Opacity>)>,
+    children_query: Query<&Children>,
+) {
+    for entity in root_node_query.iter() {
+        update_opacity(&children_query, &mut node_query, entity, 1.0);
+    }
+}
+
+fn update_opacity(
+    children_query: &Query<&Children>,
+    node_query: &mut Query<(&mut Node, Option<&Opacity>)>,
+    entity: Entity,
+    parent_opacity: f32,
+) {
+    let mut opacity = parent_opacity;
+    if let Ok((mut node, node_opacity)) = node_query.get_mut(entity) {
+        if let Some(node_opacity) = node_opacity {
+            opacity *= node_opacity.0.max(0.0).min(1.0);
+        }
+        if node.opacity != opacity {
+            node.opacity = opacity;
+        }
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            update_opacity(children_query, node_query, child, opacity);
+        }
+    }
+}
+
+/// Copies the [CornerRadius] and the border of nodes with a [BorderColor] into [Node], once they
+/// were laid out
+pub fn ui_border_system(
+    mut node_query: Query<(
+        &mut Node,
+        Option<&Style>,
+        Option<&CornerRadius>,
+        Option<&BorderColor>,
+    )>,
+) {
+    for (mut node, style, corner_radius, border_color) in node_query.iter_mut() {
+        let corner_radius = corner_radius.map_or(Vec4::zero(), |radius| {
+            Vec4::new(
+                radius.top_left,
+                radius.top_right,
+                radius.bottom_right,
+                radius.bottom_left,
+            )
+        });
+        let (border, border_color) = match (style, border_color) {
+            (Some(style), Some(border_color)) => {
+                (border_widths(style, node.size.x()), border_color.0)
+            }
+            _ => (Vec4::zero(), Color::NONE),
+        };
+        if node.corner_radius != corner_radius
+            || node.border != border
+            || node.border_color != border_color
+        {
+            node.corner_radius = corner_radius;
+            node.border = border;
+            node.border_color = border_color;
+        }
+    }
+}
+
+/// The widths of the border of a node as `(left, right, top, bottom)`. Percentages are of the
+/// width of the node.
+fn border_widths(style: &Style, width: f32) -> Vec4 {
+    let resolve = |value: Val| match value {
+        Val::Px(value) => value.max(0.0),
+        Val::Percent(percent) => (width * percent / 100.0).max(0.0),
+        Val::Undefined | Val::Auto => 0.0,
+    };
+    Vec4::new(
+        resolve(style.border.left),
+        resolve(style.border.right),
+        resolve(style.border.top),
+        resolve(style.border.bottom),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{Commands, IntoSystem, Resources, Schedule, World};
-    use bevy_transform::{components::Transform, hierarchy::BuildChildren};
+    use bevy_math::{Vec2, Vec3, Vec4};
+    use bevy_transform::{
+        components::{GlobalTransform, Transform},
+        hierarchy::BuildChildren,
+    };
 
-    use crate::Node;
+    use crate::{Node, Opacity, Overflow, Style, Val, ZIndex};
 
-    use super::{ui_z_system, UI_Z_STEP};
+    use super::{border_widths, ui_clip_system, ui_opacity_system, ui_z_system, UI_Z_STEP};
 
     fn node_with_transform(name: &str) -> (String, Node, Transform) {
         (name.to_owned(), Node::default(), Transform::default())
@@ -127,7 +313,8 @@ mod tests {
         actual_result.sort_unstable_by_key(|(name, _)| name.clone());
         let expected_result = vec![
             ("0".to_owned(), 1),
-            ("1".to_owned(), 1),
+            // root nodes are ordered after the previous root's descendants
+            ("1".to_owned(), 2),
             ("1-0".to_owned(), 1),
             ("1-0-0".to_owned(), 1),
             // 1-0-1 has no transform
@@ -146,4 +333,156 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn test_ui_clip_system() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+
+        let node = |name: &str, size: f32, center: f32, overflow: Overflow| {
+            (
+                name.to_owned(),
+                Node {
+                    size: Vec2::new(size, size),
+                    ..Default::default()
+                },
+                Style {
+                    overflow,
+                    ..Default::default()
+                },
+                GlobalTransform::from_translation(Vec3::new(center, center, 0.0)),
+            )
+        };
+        commands
+            .spawn(node("0", 100.0, 50.0, Overflow::Hidden))
+            .with_children(|parent| {
+                parent
+                    .spawn(node("0-0", 100.0, 75.0, Overflow::Scroll))
+                    .with_children(|parent| {
+                        parent.spawn(node("0-0-0", 10.0, 75.0, Overflow::Visible));
+                    });
+            });
+        commands.apply(&mut world, &mut resources);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", ui_clip_system.system());
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        let mut actual_result = world
+            .query::<(&String, &Node)>()
+            .map(|(name, node)| (name.clone(), node.clip))
+            .collect::<Vec<(String, Vec4)>>();
+        actual_result.sort_unstable_by_key(|(name, _)| name.clone());
+        let expected_result = vec![
+            ("0".to_owned(), Node::unclipped()),
+            ("0-0".to_owned(), Vec4::new(0.0, 0.0, 100.0, 100.0)),
+            // the intersection of the bounds of both ancestors
+            ("0-0-0".to_owned(), Vec4::new(25.0, 25.0, 100.0, 100.0)),
+        ];
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn test_ui_opacity_system() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+
+        commands
+            .spawn(node_without_transform("0"))
+            .with(Opacity(0.5))
+            .with_children(|parent| {
+                parent
+                    .spawn(node_without_transform("0-0"))
+                    .with_children(|parent| {
+                        parent
+                            .spawn(node_without_transform("0-0-0"))
+                            .with(Opacity(0.5));
+                    });
+            });
+        commands.spawn(node_without_transform("1"));
+        commands.apply(&mut world, &mut resources);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", ui_opacity_system.system());
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        let mut actual_result = world
+            .query::<(&String, &Node)>()
+            .map(|(name, node)| (name.clone(), node.opacity))
+            .collect::<Vec<(String, f32)>>();
+        actual_result.sort_unstable_by_key(|(name, _)| name.clone());
+        let expected_result = vec![
+            ("0".to_owned(), 0.5),
+            ("0-0".to_owned(), 0.5),
+            ("0-0-0".to_owned(), 0.25),
+            ("1".to_owned(), 1.0),
+        ];
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn test_ui_z_system_with_z_index() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let mut commands = Commands::default();
+        commands.set_entity_reserver(world.get_entity_reserver());
+
+        commands.spawn(node_with_transform("b"));
+        commands
+            .spawn(node_with_transform("a"))
+            .with_children(|parent| {
+                parent
+                    .spawn(node_with_transform("a-0"))
+                    .with(ZIndex::Local(1));
+                parent.spawn(node_with_transform("a-1"));
+                parent
+                    .spawn(node_with_transform("a-2"))
+                    .with(ZIndex::Global(1));
+            });
+        commands.apply(&mut world, &mut resources);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        schedule.add_system_to_stage("update", ui_z_system.system());
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+
+        let mut actual_result = world
+            .query::<(&String, &Transform)>()
+            .map(|(name, transform)| (name.clone(), get_steps(transform)))
+            .collect::<Vec<(String, u32)>>();
+        actual_result.sort_unstable_by_key(|(name, _)| name.clone());
+        let expected_result = vec![
+            ("a".to_owned(), 2),
+            // a-0 is drawn above its later sibling
+            ("a-0".to_owned(), 2),
+            ("a-1".to_owned(), 1),
+            // a-2 is drawn above all other nodes, a-0 being globally at 4
+            ("a-2".to_owned(), 3),
+            ("b".to_owned(), 1),
+        ];
+        assert_eq!(actual_result, expected_result);
+    }
+
+    #[test]
+    fn border_widths_resolve_from_style() {
+        let style = Style {
+            border: bevy_math::Rect {
+                left: Val::Px(2.0),
+                right: Val::Percent(10.0),
+                top: Val::Auto,
+                bottom: Val::Px(-1.0),
+            },
+            ..Default::default()
+        };
+        assert_eq!(border_widths(&style, 50.0), Vec4::new(2.0, 5.0, 0.0, 0.0));
+    }
 }
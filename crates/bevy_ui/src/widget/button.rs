@@ -1,2 +1,135 @@
+use super::Text;
+use crate::{Disabled, Focus, Interaction};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::prelude::Children;
+
 #[derive(Debug, Clone)]
 pub struct Button;
+
+/// The look of a button in one of its states. Properties that are not set are taken from the
+/// normal state.
+#[derive(Debug, Clone, Default)]
+pub struct ButtonStateStyle {
+    /// The material of the button, with its background color and image
+    pub material: Option<Handle<ColorMaterial>>,
+    /// The color of the texts that are children of the button
+    pub text_color: Option<Color>,
+}
+
+/// The look of a button in each of its states, applied by [button_style_system]. A [Disabled]
+/// button is drawn as disabled, then a pressed button as pressed, a hovered button as hovered and
+/// a focused button as focused.
+#[derive(Debug, Clone, Default)]
+pub struct ButtonStyle {
+    pub normal: ButtonStateStyle,
+    pub hovered: ButtonStateStyle,
+    pub pressed: ButtonStateStyle,
+    pub focused: ButtonStateStyle,
+    pub disabled: ButtonStateStyle,
+}
+
+impl ButtonStyle {
+    /// Returns the material and the text color of the button in the given state
+    pub fn resolve(
+        &self,
+        interaction: Interaction,
+        focused: bool,
+        disabled: bool,
+    ) -> (Option<&Handle<ColorMaterial>>, Option<Color>) {
+        let state = if disabled {
+            &self.disabled
+        } else {
+            match interaction {
+                Interaction::Clicked => &self.pressed,
+                Interaction::Hovered => &self.hovered,
+                Interaction::None if focused => &self.focused,
+                Interaction::None => &self.normal,
+            }
+        };
+        (
+            state
+                .material
+                .as_ref()
+                .or_else(|| self.normal.material.as_ref()),
+            state.text_color.or(self.normal.text_color),
+        )
+    }
+}
+
+/// Sets the material and the text color of buttons from their [ButtonStyle]
+pub fn button_style_system(
+    focus: Res<Focus>,
+    mut button_query: Query<(
+        Entity,
+        &ButtonStyle,
+        &Interaction,
+        Option<&Disabled>,
+        &mut Handle<ColorMaterial>,
+        Option<&Children>,
+    )>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, button_style, interaction, disabled, mut material, children) in
+        button_query.iter_mut()
+    {
+        let (state_material, text_color) = button_style.resolve(
+            *interaction,
+            focus.entity == Some(entity),
+            disabled.is_some(),
+        );
+        // only changed materials and texts are drawn and laid out again
+        if let Some(state_material) = state_material {
+            if *material != *state_material {
+                *material = state_material.clone();
+            }
+        }
+        if let (Some(text_color), Some(children)) = (text_color, children) {
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    if text.style.color != text_color {
+                        text.style.color = text_color;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ButtonStateStyle, ButtonStyle};
+    use crate::Interaction;
+    use bevy_render::color::Color;
+
+    #[test]
+    fn button_states_fall_back_to_normal() {
+        let button_style = ButtonStyle {
+            normal: ButtonStateStyle {
+                text_color: Some(Color::WHITE),
+                ..Default::default()
+            },
+            hovered: ButtonStateStyle {
+                text_color: Some(Color::RED),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let text_color =
+            |interaction, focused, disabled| button_style.resolve(interaction, focused, disabled).1;
+        assert_eq!(
+            text_color(Interaction::Hovered, false, false),
+            Some(Color::RED)
+        );
+        assert_eq!(
+            text_color(Interaction::Hovered, false, true),
+            Some(Color::WHITE)
+        );
+        assert_eq!(
+            text_color(Interaction::None, true, false),
+            Some(Color::WHITE)
+        );
+    }
+}
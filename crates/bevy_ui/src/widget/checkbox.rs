@@ -0,0 +1,194 @@
+use crate::{entity::NodeBundle, Activated, FocusPolicy, Focusable, PositionType, Style, Val};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Rect, Size};
+use bevy_render::{color::Color, draw::Draw};
+use bevy_sprite::ColorMaterial;
+use bevy_transform::prelude::BuildChildren;
+
+/// A box that is checked and unchecked by clicking it, or with space and enter once it is
+/// focused. The check mark is drawn by a child node spawned by [checkbox_setup_system].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Checkbox {
+    pub checked: bool,
+}
+
+/// A switch that is turned on and off by clicking it, or with space and enter once it is
+/// focused. The knob is drawn by a child node spawned by [checkbox_setup_system], on the left
+/// when the toggle is off and on the right when it is on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Toggle {
+    pub on: bool,
+}
+
+/// The child node drawing the check mark of a [Checkbox]
+#[derive(Debug, Clone, Copy)]
+pub struct CheckboxParts {
+    pub check_mark: Entity,
+}
+
+/// The child node drawing the knob of a [Toggle]
+#[derive(Debug, Clone, Copy)]
+pub struct ToggleParts {
+    pub knob: Entity,
+}
+
+/// The materials of the check marks of all checkboxes and the knobs of all toggles
+#[derive(Debug, Clone)]
+pub struct CheckboxMaterials {
+    pub check_mark: Handle<ColorMaterial>,
+    pub knob_off: Handle<ColorMaterial>,
+    pub knob_on: Handle<ColorMaterial>,
+}
+
+impl FromResources for CheckboxMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        CheckboxMaterials {
+            check_mark: materials.add(Color::rgb(0.9, 0.9, 0.9).into()),
+            knob_off: materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
+            knob_on: materials.add(Color::rgb(0.35, 0.75, 0.35).into()),
+        }
+    }
+}
+
+/// Sent when a [Checkbox] is checked or unchecked by the user
+#[derive(Debug, Clone)]
+pub struct CheckboxChanged {
+    pub entity: Entity,
+    pub checked: bool,
+}
+
+/// Sent when a [Toggle] is turned on or off by the user
+#[derive(Debug, Clone)]
+pub struct ToggleChanged {
+    pub entity: Entity,
+    pub on: bool,
+}
+
+/// Spawns the check marks of new checkboxes and the knobs of new toggles, and makes them
+/// focusable
+pub fn checkbox_setup_system(
+    commands: &mut Commands,
+    materials: Res<CheckboxMaterials>,
+    checkbox_query: Query<Entity, (With<Checkbox>, Without<CheckboxParts>)>,
+    toggle_query: Query<Entity, (With<Toggle>, Without<ToggleParts>)>,
+) {
+    for entity in checkbox_query.iter() {
+        let check_mark = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect::all(Val::Percent(20.0)),
+                    ..Default::default()
+                },
+                material: materials.check_mark.clone(),
+                draw: Draw {
+                    is_visible: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .with(FocusPolicy::Pass)
+            .current_entity()
+            .unwrap();
+        commands
+            .push_children(entity, &[check_mark])
+            .insert(entity, (CheckboxParts { check_mark }, Focusable::default()));
+    }
+    for entity in toggle_query.iter() {
+        let knob = commands
+            .spawn(NodeBundle {
+                style: knob_style(false),
+                material: materials.knob_off.clone(),
+                ..Default::default()
+            })
+            .with(FocusPolicy::Pass)
+            .current_entity()
+            .unwrap();
+        commands
+            .push_children(entity, &[knob])
+            .insert(entity, (ToggleParts { knob }, Focusable::default()));
+    }
+}
+
+/// The knob covers the half of the toggle on the side of its state
+fn knob_style(on: bool) -> Style {
+    let mut position = Rect {
+        top: Val::Px(0.0),
+        bottom: Val::Px(0.0),
+        ..Default::default()
+    };
+    if on {
+        position.right = Val::Px(0.0);
+    } else {
+        position.left = Val::Px(0.0);
+    }
+    Style {
+        position_type: PositionType::Absolute,
+        position,
+        size: Size::new(Val::Percent(50.0), Val::Auto),
+        ..Default::default()
+    }
+}
+
+#[derive(Default)]
+pub struct CheckboxState {
+    activated_event_reader: EventReader<Activated>,
+}
+
+/// Flips checkboxes and toggles when they are activated, by clicking them or with the keyboard
+pub fn checkbox_system(
+    mut state: Local<CheckboxState>,
+    activated_events: Res<Events<Activated>>,
+    mut checkbox_events: ResMut<Events<CheckboxChanged>>,
+    mut toggle_events: ResMut<Events<ToggleChanged>>,
+    mut checkbox_query: Query<&mut Checkbox>,
+    mut toggle_query: Query<&mut Toggle>,
+) {
+    for Activated { entity } in state.activated_event_reader.iter(&activated_events) {
+        let entity = *entity;
+        if let Ok(mut checkbox) = checkbox_query.get_mut(entity) {
+            checkbox.checked = !checkbox.checked;
+            checkbox_events.send(CheckboxChanged {
+                entity,
+                checked: checkbox.checked,
+            });
+        } else if let Ok(mut toggle) = toggle_query.get_mut(entity) {
+            toggle.on = !toggle.on;
+            toggle_events.send(ToggleChanged {
+                entity,
+                on: toggle.on,
+            });
+        }
+    }
+}
+
+/// Shows the check marks of checked checkboxes and moves the knobs of toggles to their state
+pub fn checkbox_indicator_system(
+    materials: Res<CheckboxMaterials>,
+    checkbox_query: Query<
+        (&Checkbox, &CheckboxParts),
+        Or<(Changed<Checkbox>, Added<CheckboxParts>)>,
+    >,
+    toggle_query: Query<(&Toggle, &ToggleParts), Or<(Changed<Toggle>, Added<ToggleParts>)>>,
+    mut draw_query: Query<&mut Draw>,
+    mut knob_query: Query<(&mut Style, &mut Handle<ColorMaterial>)>,
+) {
+    for (checkbox, parts) in checkbox_query.iter() {
+        if let Ok(mut draw) = draw_query.get_mut(parts.check_mark) {
+            draw.is_visible = checkbox.checked;
+        }
+    }
+    for (toggle, parts) in toggle_query.iter() {
+        if let Ok((mut style, mut material)) = knob_query.get_mut(parts.knob) {
+            *style = knob_style(toggle.on);
+            *material = if toggle.on {
+                materials.knob_on.clone()
+            } else {
+                materials.knob_off.clone()
+            };
+        }
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+use std::cell::RefCell;
+
+/// Text copied and cut from text inputs. On Windows, macOS and Linux it is the system clipboard,
+/// shared with other applications. On other platforms, and when the system clipboard can't be
+/// opened (on a headless machine for example), the text is only shared by the text inputs of the
+/// app.
+#[derive(Debug, Default, Clone)]
+pub struct Clipboard {
+    contents: String,
+}
+
+impl Clipboard {
+    /// Returns the text in the clipboard
+    pub fn get(&self) -> String {
+        system_clipboard_text().unwrap_or_else(|| self.contents.clone())
+    }
+
+    /// Replaces the text in the clipboard
+    pub fn set(&mut self, contents: String) {
+        set_system_clipboard_text(&contents);
+        self.contents = contents;
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+thread_local! {
+    // the system clipboard can't be sent between threads on every platform, so each thread opens
+    // its own
+    static SYSTEM_CLIPBOARD: RefCell<Option<arboard::Clipboard>> =
+        RefCell::new(arboard::Clipboard::new().ok());
+}
+
+/// Returns the text in the system clipboard, or `None` if there is no system clipboard. Clipboards
+/// without text are empty.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+fn system_clipboard_text() -> Option<String> {
+    SYSTEM_CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        Some(clipboard.as_mut()?.get_text().unwrap_or_default())
+    })
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+fn set_system_clipboard_text(contents: &str) {
+    SYSTEM_CLIPBOARD.with(|clipboard| {
+        if let Some(clipboard) = clipboard.borrow_mut().as_mut() {
+            let _ = clipboard.set_text(contents.to_string());
+        }
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn system_clipboard_text() -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn set_system_clipboard_text(_contents: &str) {}
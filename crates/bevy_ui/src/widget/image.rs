@@ -1,8 +1,8 @@
 use crate::CalculatedSize;
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{Query, Res, With};
-use bevy_math::Size;
-use bevy_render::texture::Texture;
+use bevy_math::{Size, Vec2, Vec4};
+use bevy_render::{renderer::RenderResources, shader::ShaderDefs, texture::Texture};
 use bevy_sprite::ColorMaterial;
 
 #[derive(Debug, Clone)]
@@ -34,3 +34,60 @@ pub fn image_node_system(
         }
     }
 }
+
+/// Draws the texture of a node's [ColorMaterial] in nine slices, so frames and panels can be
+/// resized without distorting them. The corners keep their size in texture pixels, the edges
+/// stretch along one axis and the center stretches along both, or they repeat when `tile` is set.
+///
+/// Only nodes drawn with [UI_IMAGE_SLICES_PIPELINE_HANDLE](crate::UI_IMAGE_SLICES_PIPELINE_HANDLE)
+/// are sliced, see [ImageSlicesBundle](crate::entity::ImageSlicesBundle).
+#[derive(Debug, Clone, RenderResources, ShaderDefs)]
+pub struct ImageSlices {
+    /// The insets of the slices from the left, right, top and bottom of the texture, in pixels
+    pub border: Vec4,
+    /// The size of the sliced texture, kept up to date by [image_slices_system]
+    pub texture_size: Vec2,
+    #[render_resources(ignore)]
+    #[shader_def]
+    pub tile: bool,
+}
+
+impl ImageSlices {
+    /// Slices with the same inset on every side
+    pub fn all(border: f32) -> Self {
+        ImageSlices {
+            border: Vec4::splat(border),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for ImageSlices {
+    fn default() -> Self {
+        ImageSlices {
+            border: Vec4::zero(),
+            texture_size: Vec2::one(),
+            tile: false,
+        }
+    }
+}
+
+pub fn image_slices_system(
+    materials: Res<Assets<ColorMaterial>>,
+    textures: Res<Assets<Texture>>,
+    mut query: Query<(&mut ImageSlices, &Handle<ColorMaterial>)>,
+) {
+    for (mut slices, material_handle) in query.iter_mut() {
+        if let Some(texture) = materials
+            .get(material_handle)
+            .and_then(|material| material.texture.as_ref())
+            .and_then(|texture_handle| textures.get(texture_handle))
+        {
+            let texture_size = Vec2::new(texture.size.width as f32, texture.size.height as f32);
+            // only changed slices are uploaded again
+            if slices.texture_size != texture_size {
+                slices.texture_size = texture_size;
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
 mod button;
+mod checkbox;
+mod clipboard;
 mod image;
+mod slider;
 mod text;
+mod text_input;
 
 pub use button::*;
+pub use checkbox::*;
+pub use clipboard::*;
 pub use image::*;
+pub use slider::*;
 pub use text::*;
+pub use text_input::*;
@@ -0,0 +1,266 @@
+use crate::{
+    entity::NodeBundle, Focus, FocusPolicy, Focusable, Interaction, Node, PositionType, Style,
+    UiScale, Val,
+};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    touch::Touches,
+    ElementState,
+};
+use bevy_math::{Rect, Size, Vec2};
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::prelude::{BuildChildren, GlobalTransform};
+use bevy_window::CursorMoved;
+
+/// The width in logical pixels of the thumb of a slider
+pub const SLIDER_THUMB_WIDTH: f32 = 12.0;
+
+/// A horizontal slider selecting a value between `min` and `max`. The value is set by dragging
+/// the node, or with the arrow keys, home and end once it is focused.
+///
+/// The thumb is drawn by a child node spawned by [slider_setup_system].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slider {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// The value snaps to multiples of `step` from `min`. With no step the value is continuous.
+    pub step: Option<f32>,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Slider {
+            value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            step: None,
+        }
+    }
+}
+
+impl Slider {
+    /// Sets the value, snapped to the step and clamped to the range of the slider
+    pub fn set_value(&mut self, value: f32) {
+        let value = match self.step {
+            Some(step) if step > 0.0 => self.min + ((value - self.min) / step).round() * step,
+            _ => value,
+        };
+        self.value = value.max(self.min).min(self.max);
+    }
+
+    /// Sets the value at `fraction` of the range, from 0.0 at `min` to 1.0 at `max`
+    pub fn set_fraction(&mut self, fraction: f32) {
+        self.set_value(self.min + fraction * (self.max - self.min));
+    }
+
+    /// How far the value is into the range, from 0.0 at `min` to 1.0 at `max`
+    pub fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min))
+                .max(0.0)
+                .min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The amount the arrow keys change the value by: the step, or a hundredth of the range
+    pub fn key_increment(&self) -> f32 {
+        match self.step {
+            Some(step) if step > 0.0 => step,
+            _ => (self.max - self.min) / 100.0,
+        }
+    }
+}
+
+/// The child node drawing the thumb of a [Slider]
+#[derive(Debug, Clone, Copy)]
+pub struct SliderParts {
+    pub thumb: Entity,
+}
+
+/// The material of the thumb of all sliders
+#[derive(Debug, Clone)]
+pub struct SliderMaterials {
+    pub thumb: Handle<ColorMaterial>,
+}
+
+impl FromResources for SliderMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        SliderMaterials {
+            thumb: materials.add(Color::rgb(0.8, 0.8, 0.8).into()),
+        }
+    }
+}
+
+/// Sent when the value of a [Slider] is changed by the user
+#[derive(Debug, Clone)]
+pub struct SliderChanged {
+    pub entity: Entity,
+    pub value: f32,
+}
+
+/// Spawns the thumbs of new sliders, and makes them focusable
+pub fn slider_setup_system(
+    commands: &mut Commands,
+    materials: Res<SliderMaterials>,
+    query: Query<Entity, (With<Slider>, Without<SliderParts>)>,
+) {
+    for entity in query.iter() {
+        let thumb = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Px(SLIDER_THUMB_WIDTH), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+                material: materials.thumb.clone(),
+                ..Default::default()
+            })
+            .with(FocusPolicy::Pass)
+            .current_entity()
+            .unwrap();
+        commands.push_children(entity, &[thumb]).insert(
+            entity,
+            (
+                SliderParts { thumb },
+                Focusable {
+                    captures_arrow_keys: true,
+                    ..Default::default()
+                },
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct SliderState {
+    keyboard_input_event_reader: EventReader<KeyboardInput>,
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+}
+
+/// Sets the value of sliders that are dragged, or of the focused slider when keys are pressed
+#[allow(clippy::too_many_arguments)]
+pub fn slider_system(
+    mut state: Local<SliderState>,
+    focus: Res<Focus>,
+    keyboard_input_events: Res<Events<KeyboardInput>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    touches_input: Res<Touches>,
+    mut changed_events: ResMut<Events<SliderChanged>>,
+    mut slider_query: Query<(Entity, &mut Slider, &Interaction, &Node, &GlobalTransform)>,
+) {
+    let state = &mut *state;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+    if let Some(touch) = touches_input.get_pressed(0) {
+        state.cursor_position = ui_scale.to_ui(touch.position());
+    }
+
+    for (entity, mut slider, interaction, node, global_transform) in slider_query.iter_mut() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        // the center of the thumb follows the cursor
+        let track_width = node.size.x() - SLIDER_THUMB_WIDTH;
+        if track_width <= 0.0 {
+            continue;
+        }
+        let left = global_transform.translation.x() - node.size.x() / 2.0;
+        let fraction = (state.cursor_position.x() - left - SLIDER_THUMB_WIDTH / 2.0) / track_width;
+        let mut dragged_slider = slider.clone();
+        dragged_slider.set_fraction(fraction);
+        if *slider != dragged_slider {
+            *slider = dragged_slider;
+            changed_events.send(SliderChanged {
+                entity,
+                value: slider.value,
+            });
+        }
+    }
+    // keyboard input events repeat while keys are held down
+    for event in state
+        .keyboard_input_event_reader
+        .iter(&keyboard_input_events)
+    {
+        if event.state != ElementState::Pressed {
+            continue;
+        }
+        let (entity, mut slider) = match focus
+            .entity
+            .and_then(|entity| slider_query.get_mut(entity).ok())
+        {
+            Some((entity, slider, _interaction, _node, _global_transform)) => (entity, slider),
+            None => continue,
+        };
+        let increment = slider.key_increment();
+        let value = match event.key_code {
+            Some(KeyCode::Left) | Some(KeyCode::Down) => slider.value - increment,
+            Some(KeyCode::Right) | Some(KeyCode::Up) => slider.value + increment,
+            Some(KeyCode::Home) => slider.min,
+            Some(KeyCode::End) => slider.max,
+            _ => continue,
+        };
+        let previous_value = slider.value;
+        slider.set_value(value);
+        if slider.value != previous_value {
+            changed_events.send(SliderChanged {
+                entity,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+/// Moves the thumbs of sliders to their value
+pub fn slider_thumb_system(
+    slider_query: Query<(&Slider, &Node, &SliderParts)>,
+    mut thumb_query: Query<&mut Style>,
+) {
+    for (slider, node, parts) in slider_query.iter() {
+        if let Ok(mut style) = thumb_query.get_mut(parts.thumb) {
+            let track_width = (node.size.x() - SLIDER_THUMB_WIDTH).max(0.0);
+            let position = Rect {
+                left: Val::Px(slider.fraction() * track_width),
+                ..Default::default()
+            };
+            // only changed styles are laid out again
+            if style.position != position {
+                style.position = position;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slider;
+
+    #[test]
+    fn slider_value_snaps_to_step() {
+        let mut slider = Slider {
+            min: 10.0,
+            max: 20.0,
+            step: Some(2.5),
+            ..Default::default()
+        };
+        slider.set_value(13.0);
+        assert_eq!(slider.value, 12.5);
+        slider.set_fraction(0.9);
+        assert_eq!(slider.value, 20.0);
+        assert_eq!(slider.fraction(), 1.0);
+        slider.set_value(-5.0);
+        assert_eq!(slider.value, 10.0);
+        assert_eq!(slider.key_increment(), 2.5);
+    }
+}
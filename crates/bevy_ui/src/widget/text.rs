@@ -1,7 +1,7 @@
 use crate::{CalculatedSize, Node, Style, Val};
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{Changed, Entity, Local, Or, Query, QuerySet, Res, ResMut};
-use bevy_math::Size;
+use bevy_math::{Mat4, Size};
 use bevy_render::{
     draw::{Draw, DrawContext, Drawable},
     mesh::Mesh,
@@ -10,19 +10,17 @@ use bevy_render::{
     texture::Texture,
 };
 use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
-use bevy_text::{DefaultTextPipeline, DrawableText, Font, FontAtlasSet, TextError, TextStyle};
+use bevy_text::{DefaultTextPipeline, DrawableText, Font, FontAtlasSet, FontFallbacks, TextError};
 use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::HashMap;
+
+pub use bevy_text::Text;
 
 #[derive(Debug, Default)]
 pub struct QueuedText {
     entities: Vec<Entity>,
-}
-
-#[derive(Debug, Default, Clone)]
-pub struct Text {
-    pub value: String,
-    pub font: Handle<Font>,
-    pub style: TextStyle,
+    /// The node width each text was wrapped at
+    wrap_widths: HashMap<Entity, f32>,
 }
 
 /// Defines how min_size, size, and max_size affects the bounds of a text
@@ -39,23 +37,39 @@ pub fn text_constraint(min_size: Val, size: Val, max_size: Val) -> f32 {
 }
 
 /// Computes the size of a text block and updates the TextGlyphs with the
-/// new computed glyphs from the layout
+/// new computed glyphs from the layout.
+///
+/// Text is wrapped at the width its node was given by the layout. The calculated width stays the
+/// width of the unwrapped text, so that the node grows back when it has more space.
+#[allow(clippy::too_many_arguments)]
 pub fn text_system(
     mut queued_text: Local<QueuedText>,
     mut textures: ResMut<Assets<Texture>>,
     fonts: Res<Assets<Font>>,
+    font_fallbacks: Res<FontFallbacks>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
     mut text_pipeline: ResMut<DefaultTextPipeline>,
     mut text_queries: QuerySet<(
         Query<Entity, Or<(Changed<Text>, Changed<Style>)>>,
-        Query<(&Text, &Style, &mut CalculatedSize)>,
+        Query<(Entity, &Text, &Style, &Node, &mut CalculatedSize)>,
     )>,
 ) {
+    let queued_text = &mut *queued_text;
     // Adds all entities where the text or the style has changed to the local queue
     for entity in text_queries.q0_mut().iter_mut() {
         queued_text.entities.push(entity);
     }
+    // Adds the entities whose node width changed since they were wrapped
+    for (entity, _text, _style, node, _calculated_size) in text_queries.q1_mut().iter_mut() {
+        let wrapped_at_node_width = queued_text
+            .wrap_widths
+            .get(&entity)
+            .map_or(false, |width| (width - node.size.x()).abs() < 0.5);
+        if !wrapped_at_node_width && !queued_text.entities.contains(&entity) {
+            queued_text.entities.push(entity);
+        }
+    }
 
     if queued_text.entities.is_empty() {
         return;
@@ -65,28 +79,67 @@ pub fn text_system(
     let mut new_queue = Vec::new();
     let query = text_queries.q1_mut();
     for entity in queued_text.entities.drain(..) {
-        if let Ok((text, style, mut calculated_size)) = query.get_mut(entity) {
-            match add_text_to_pipeline(
-                entity,
-                &*text,
-                &*style,
-                &mut *textures,
-                &*fonts,
-                &mut *texture_atlases,
-                &mut *font_atlas_set_storage,
-                &mut *text_pipeline,
-            ) {
-                TextPipelineResult::Ok => {
-                    let text_layout_info = text_pipeline.get_glyphs(&entity).expect(
-                        "Failed to get glyphs from the pipeline that have just been computed",
-                    );
-                    calculated_size.size = text_layout_info.size;
+        if let Ok((_entity, text, style, node, mut calculated_size)) = query.get_mut(entity) {
+            let mut bounds = Size::new(
+                text_constraint(style.min_size.width, style.size.width, style.max_size.width),
+                text_constraint(
+                    style.min_size.height,
+                    style.size.height,
+                    style.max_size.height,
+                ),
+            );
+            let mut layout = |bounds: Size| {
+                add_text_to_pipeline(
+                    entity,
+                    &*text,
+                    &font_fallbacks,
+                    bounds,
+                    &mut *textures,
+                    &*fonts,
+                    &mut *texture_atlases,
+                    &mut *font_atlas_set_storage,
+                    &mut *text_pipeline,
+                )
+            };
+
+            let mut result = layout(bounds);
+            let mut size = Size::new(0.0, 0.0);
+            if let TextPipelineResult::Ok(unwrapped_size) = result {
+                size = unwrapped_size;
+                // the node has no width until it was laid out once. layouts are rounded to whole
+                // pixels, the extra pixel keeps text that was sized to fit from wrapping.
+                let node_width = node.size.x() + 1.0;
+                if node.size.x() > 0.0 && node_width < size.width {
+                    bounds.width = node_width;
+                    result = layout(bounds);
+                    if let TextPipelineResult::Ok(wrapped_size) = result {
+                        size.height = wrapped_size.height;
+                    }
+                }
+            }
+
+            match result {
+                TextPipelineResult::Ok(_) => {
+                    calculated_size.size = size;
+                    // the text can be wrapped down to the width of its longest word
+                    calculated_size.min_size = text_pipeline
+                        .measure_text(
+                            &text.all_sections(),
+                            &fallback_fonts(&text, &font_fallbacks),
+                            &fonts,
+                            text.style.wrap,
+                            Size::new(0.0, f32::MAX),
+                        )
+                        .unwrap_or(size);
+                    queued_text.wrap_widths.insert(entity, node.size.x());
                 }
                 TextPipelineResult::Reschedule => {
                     // There was an error processing the text layout, let's add this entity to the queue for further processing
                     new_queue.push(entity);
                 }
             }
+        } else {
+            queued_text.wrap_widths.remove(&entity);
         }
     }
 
@@ -94,39 +147,43 @@ pub fn text_system(
 }
 
 enum TextPipelineResult {
-    Ok,
+    Ok(Size),
     Reschedule,
 }
 
-/// Computes the text layout and stores it in the TextPipeline resource.
+/// The fallback fonts of the text are tried before the default ones
+fn fallback_fonts(text: &Text, font_fallbacks: &FontFallbacks) -> Vec<Handle<Font>> {
+    text.style
+        .fallback_fonts
+        .iter()
+        .chain(font_fallbacks.fonts.iter())
+        .cloned()
+        .collect()
+}
+
+/// Computes the text layout within `bounds` and stores it in the TextPipeline resource.
 #[allow(clippy::too_many_arguments)]
 fn add_text_to_pipeline(
     entity: Entity,
     text: &Text,
-    style: &Style,
+    font_fallbacks: &FontFallbacks,
+    bounds: Size,
     textures: &mut Assets<Texture>,
     fonts: &Assets<Font>,
     texture_atlases: &mut Assets<TextureAtlas>,
     font_atlas_set_storage: &mut Assets<FontAtlasSet>,
     text_pipeline: &mut DefaultTextPipeline,
 ) -> TextPipelineResult {
-    let node_size = Size::new(
-        text_constraint(style.min_size.width, style.size.width, style.max_size.width),
-        text_constraint(
-            style.min_size.height,
-            style.size.height,
-            style.max_size.height,
-        ),
-    );
-
     match text_pipeline.queue_text(
         entity,
-        text.font.clone(),
+        &text.all_sections(),
+        &fallback_fonts(text, font_fallbacks),
         &fonts,
-        &text.value,
-        text.style.font_size,
         text.style.alignment,
-        node_size,
+        text.style.wrap,
+        text.style.overflow,
+        text.style.rendering,
+        bounds,
         font_atlas_set_storage,
         texture_atlases,
         textures,
@@ -135,7 +192,12 @@ fn add_text_to_pipeline(
         Err(e @ TextError::FailedToAddGlyph(_)) => {
             panic!("Fatal error when processing text: {}", e);
         }
-        Ok(()) => TextPipelineResult::Ok,
+        Ok(()) => {
+            let text_layout_info = text_pipeline
+                .get_glyphs(&entity)
+                .expect("Failed to get glyphs from the pipeline that have just been computed");
+            TextPipelineResult::Ok(text_layout_info.size)
+        }
     }
 }
 
@@ -153,21 +215,35 @@ pub fn draw_text_system(
     let vertex_buffer_descriptor = font_quad.get_vertex_buffer_descriptor();
 
     for (entity, mut draw, text, node, global_transform) in query.iter_mut() {
-        if !draw.is_visible {
+        if !draw.is_visible || node.opacity <= 0.0 {
             continue;
         }
 
         if let Some(text_glyphs) = text_pipeline.get_glyphs(&entity) {
-            let position = global_transform.translation - (node.size / 2.0).extend(0.0);
+            // glyphs are positioned from the bottom left corner, which is only rotated and scaled
+            // along with world space nodes
+            let transform = global_transform.compute_matrix()
+                * Mat4::from_translation(-(node.size / 2.0).extend(0.0));
+
+            let colors = std::iter::once(text.style.color)
+                .chain(text.sections.iter().map(|section| section.color))
+                .map(|mut color| {
+                    color.set_a(color.a() * node.opacity);
+                    color
+                })
+                .collect::<Vec<_>>();
 
             let mut drawable_text = DrawableText {
                 render_resource_bindings: &mut render_resource_bindings,
                 asset_render_resource_bindings: &mut asset_render_resource_bindings,
-                position,
+                transform,
+                // glyphs outside of the visible area of the node are discarded, like its quad
+                clip: node.clip,
                 msaa: &msaa,
                 text_glyphs: &text_glyphs.glyphs,
                 font_quad_vertex_descriptor: &vertex_buffer_descriptor,
-                style: &text.style,
+                colors: &colors,
+                rendering: text.style.rendering,
             };
 
             drawable_text.draw(&mut draw, &mut context).unwrap();
@@ -0,0 +1,699 @@
+use super::{Clipboard, Text};
+use crate::{
+    camera::UI_CAMERA,
+    entity::{NodeBundle, TextBundle},
+    CalculatedSize, Focus, FocusPolicy, Focusable, Interaction, Node, PositionType, ScrollPosition,
+    Style, UiScale, Val,
+};
+use bevy_app::{EventReader, Events};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{Time, Timer};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    touch::Touches,
+    ElementState, Input,
+};
+use bevy_math::{Rect, Size, Vec2};
+use bevy_render::{
+    camera::{ActiveCameras, Camera},
+    color::Color,
+    draw::Draw,
+};
+use bevy_sprite::ColorMaterial;
+use bevy_text::{
+    DefaultTextPipeline, Font, PositionedCharacter, TextOverflow, TextStyle, TextWrap,
+};
+use bevy_transform::prelude::{BuildChildren, GlobalTransform};
+use bevy_window::{CursorMoved, ReceivedCharacter, Windows};
+use std::ops::Range;
+
+/// The distance in logical pixels between the left edge of a text input and its text
+pub const TEXT_INPUT_PADDING: f32 = 4.0;
+
+/// A single line of editable text. Once the node is focused, by clicking it or navigating to it,
+/// it receives typed characters, including the text committed by input methods. The candidate
+/// window of input methods is placed under the caret, in the window of the ui camera. Text that
+/// is still being composed isn't shown in the input, as winit doesn't report it.
+///
+/// The text, the selection and the caret are drawn by child nodes spawned by
+/// [text_input_setup_system]. Give the node [Overflow::Scroll](crate::Overflow::Scroll) and a
+/// [ScrollPosition] to clip text that is longer than the node and keep the caret in view.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    pub value: String,
+    /// The byte index in `value` where text is inserted
+    pub cursor: usize,
+    /// The byte index of the other end of the selection, which extends to the cursor
+    pub selection_anchor: Option<usize>,
+    pub font: Handle<Font>,
+    pub style: TextStyle,
+}
+
+impl TextInput {
+    /// The byte range of the selected text, if any is selected
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let cursor = self.clamp(self.cursor);
+        let anchor = self.clamp(self.selection_anchor?);
+        match anchor.cmp(&cursor) {
+            std::cmp::Ordering::Less => Some(anchor..cursor),
+            std::cmp::Ordering::Greater => Some(cursor..anchor),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection()
+            .map_or("", |selection| &self.value[selection])
+    }
+
+    pub fn select_all(&mut self) {
+        self.selection_anchor = Some(0);
+        self.cursor = self.value.len();
+    }
+
+    /// Replaces the selection, or inserts at the cursor if nothing is selected
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let cursor = self.clamp(self.cursor);
+        self.value.insert_str(cursor, text);
+        self.cursor = cursor + text.len();
+    }
+
+    /// Removes the selected text. Returns false if nothing was selected.
+    pub fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        match selection {
+            Some(selection) => {
+                self.cursor = selection.start;
+                self.value.replace_range(selection, "");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the selection or the character before the cursor. Returns false if nothing was
+    /// removed.
+    pub fn backspace(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let cursor = self.clamp(self.cursor);
+        let previous = self.previous_boundary(cursor);
+        self.value.replace_range(previous..cursor, "");
+        self.cursor = previous;
+        previous != cursor
+    }
+
+    /// Removes the selection or the character after the cursor. Returns false if nothing was
+    /// removed.
+    pub fn delete(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let cursor = self.clamp(self.cursor);
+        let next = self.next_boundary(cursor);
+        self.value.replace_range(cursor..next, "");
+        self.cursor = cursor;
+        next != cursor
+    }
+
+    /// Moves the cursor to `cursor`. With `select` the selection is extended to the new position,
+    /// otherwise it is cleared.
+    pub fn move_cursor(&mut self, cursor: usize, select: bool) {
+        if select {
+            let anchor = self.selection_anchor.unwrap_or(self.cursor);
+            self.selection_anchor = Some(anchor);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = self.clamp(cursor);
+        if self.selection_anchor == Some(self.cursor) {
+            self.selection_anchor = None;
+        }
+    }
+
+    pub fn move_left(&mut self, select: bool) {
+        match self.selection() {
+            Some(selection) if !select => self.move_cursor(selection.start, false),
+            _ => self.move_cursor(self.previous_boundary(self.clamp(self.cursor)), select),
+        }
+    }
+
+    pub fn move_right(&mut self, select: bool) {
+        match self.selection() {
+            Some(selection) if !select => self.move_cursor(selection.end, false),
+            _ => self.move_cursor(self.next_boundary(self.clamp(self.cursor)), select),
+        }
+    }
+
+    /// Moves `index` back to a character boundary within the value
+    fn clamp(&self, index: usize) -> usize {
+        let mut index = index.min(self.value.len());
+        while !self.value.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    fn previous_boundary(&self, index: usize) -> usize {
+        self.value[..index]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self, index: usize) -> usize {
+        self.value[index..]
+            .chars()
+            .next()
+            .map_or(index, |character| index + character.len_utf8())
+    }
+
+    /// The text drawing the value
+    fn text(&self) -> Text {
+        Text {
+            value: self.value.clone(),
+            font: self.font.clone(),
+            style: TextStyle {
+                wrap: TextWrap::NoWrap,
+                overflow: TextOverflow::Visible,
+                ..self.style.clone()
+            },
+            sections: Vec::new(),
+        }
+    }
+}
+
+/// The child nodes drawing a [TextInput]
+#[derive(Debug, Clone, Copy)]
+pub struct TextInputParts {
+    pub text: Entity,
+    pub selection: Entity,
+    pub caret: Entity,
+}
+
+/// The materials of the caret and the selection of all text inputs
+#[derive(Debug, Clone)]
+pub struct TextInputMaterials {
+    pub caret: Handle<ColorMaterial>,
+    pub selection: Handle<ColorMaterial>,
+}
+
+impl FromResources for TextInputMaterials {
+    fn from_resources(resources: &Resources) -> Self {
+        let mut materials = resources.get_mut::<Assets<ColorMaterial>>().unwrap();
+        TextInputMaterials {
+            caret: materials.add(Color::WHITE.into()),
+            selection: materials.add(Color::rgba(0.3, 0.5, 0.9, 0.5).into()),
+        }
+    }
+}
+
+/// Sent when the value of a [TextInput] was edited
+#[derive(Debug, Clone)]
+pub struct TextInputChanged {
+    pub entity: Entity,
+    pub value: String,
+}
+
+/// Sent when enter is pressed in a focused [TextInput]
+#[derive(Debug, Clone)]
+pub struct TextInputSubmitted {
+    pub entity: Entity,
+    pub value: String,
+}
+
+fn part_style() -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        ..Default::default()
+    }
+}
+
+/// Spawns the nodes drawing the text, selection and caret of new text inputs, and makes them
+/// focusable. Focused text inputs keep the arrow keys, space and enter.
+pub fn text_input_setup_system(
+    commands: &mut Commands,
+    materials: Res<TextInputMaterials>,
+    query: Query<(Entity, &TextInput), Without<TextInputParts>>,
+) {
+    for (entity, text_input) in query.iter() {
+        let mut spawn_highlight = |material: &Handle<ColorMaterial>| {
+            commands
+                .spawn(NodeBundle {
+                    style: part_style(),
+                    material: material.clone(),
+                    draw: Draw {
+                        is_visible: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with(FocusPolicy::Pass)
+                .current_entity()
+                .unwrap()
+        };
+        let selection = spawn_highlight(&materials.selection);
+        let caret = spawn_highlight(&materials.caret);
+        let text = commands
+            .spawn(TextBundle {
+                style: part_style(),
+                text: text_input.text(),
+                ..Default::default()
+            })
+            .current_entity()
+            .unwrap();
+
+        commands
+            .push_children(entity, &[selection, text, caret])
+            .insert(
+                entity,
+                (
+                    TextInputParts {
+                        text,
+                        selection,
+                        caret,
+                    },
+                    Focusable {
+                        captures_arrow_keys: true,
+                        captures_activation_keys: true,
+                    },
+                ),
+            );
+    }
+}
+
+#[derive(Default)]
+pub struct TextInputState {
+    keyboard_input_event_reader: EventReader<KeyboardInput>,
+    received_character_event_reader: EventReader<ReceivedCharacter>,
+    cursor_moved_event_reader: EventReader<CursorMoved>,
+    cursor_position: Vec2,
+    /// The text input being dragged over with the mouse and where the drag started
+    drag: Option<(Entity, usize)>,
+}
+
+/// Edits the focused text input with the keyboard and the mouse
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_system(
+    mut state: Local<TextInputState>,
+    focus: Res<Focus>,
+    mut clipboard: ResMut<Clipboard>,
+    keyboard_input: Res<Input<KeyCode>>,
+    keyboard_input_events: Res<Events<KeyboardInput>>,
+    received_character_events: Res<Events<ReceivedCharacter>>,
+    cursor_moved_events: Res<Events<CursorMoved>>,
+    ui_scale: Res<UiScale>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    mut changed_events: ResMut<Events<TextInputChanged>>,
+    mut submitted_events: ResMut<Events<TextInputSubmitted>>,
+    mut input_query: Query<(Entity, &mut TextInput, &Interaction, &TextInputParts)>,
+    text_node_query: Query<(&Node, &GlobalTransform)>,
+) {
+    let state = &mut *state;
+    if let Some(cursor_moved) = state.cursor_moved_event_reader.latest(&cursor_moved_events) {
+        state.cursor_position = ui_scale.to_ui(cursor_moved.position);
+    }
+    // touches that were lifted this frame still place the caret
+    if let Some(touch) = touches_input
+        .iter()
+        .chain(touches_input.iter_just_released())
+        .next()
+    {
+        state.cursor_position = ui_scale.to_ui(touch.position());
+    }
+
+    // the byte index in the value of a text input closest to the cursor
+    let cursor_position = state.cursor_position;
+    let index_at_cursor = |text_input: &TextInput, parts: &TextInputParts| {
+        let (node, global_transform) = text_node_query.get(parts.text).ok()?;
+        let characters = &text_pipeline.get_glyphs(&parts.text)?.characters;
+        let x = cursor_position.x() - (global_transform.translation.x() - node.size.x() / 2.0);
+        Some(
+            characters
+                .iter()
+                .find(|character| x < character.x + character.advance / 2.0)
+                .map_or(text_input.value.len(), |character| character.byte_index),
+        )
+    };
+
+    // clicking a text input places the caret
+    let mouse_clicked = mouse_button_input.just_pressed(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some();
+    if mouse_clicked {
+        let clicked = input_query
+            .iter_mut()
+            .find(|(_entity, _text_input, interaction, _parts)| {
+                **interaction == Interaction::Clicked
+            });
+        if let Some((entity, mut text_input, _interaction, parts)) = clicked {
+            if let Some(index) = index_at_cursor(&text_input, parts) {
+                text_input.move_cursor(index, false);
+                state.drag = Some((entity, index));
+            }
+        }
+    } else if mouse_button_input.pressed(MouseButton::Left) {
+        // dragging selects the text between the cursor and where the drag started
+        if let Some((entity, anchor)) = state.drag {
+            if let Ok((_entity, mut text_input, _interaction, parts)) = input_query.get_mut(entity)
+            {
+                if let Some(index) = index_at_cursor(&text_input, parts) {
+                    let selection_anchor = if index != anchor { Some(anchor) } else { None };
+                    if text_input.cursor != index || text_input.selection_anchor != selection_anchor
+                    {
+                        text_input.cursor = index;
+                        text_input.selection_anchor = selection_anchor;
+                    }
+                }
+            }
+        }
+    } else {
+        state.drag = None;
+    }
+
+    // events are read every frame, so that they don't reach inputs focused later
+    let mut focused_input = focus
+        .entity
+        .and_then(|entity| input_query.get_mut(entity).ok())
+        .map(|(entity, text_input, _interaction, _parts)| (entity, text_input));
+    let shift = keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+    let control = keyboard_input.pressed(KeyCode::LControl)
+        || keyboard_input.pressed(KeyCode::RControl)
+        || keyboard_input.pressed(KeyCode::LWin)
+        || keyboard_input.pressed(KeyCode::RWin);
+    let mut changed = false;
+    let mut submitted = false;
+    // keyboard input events repeat while keys are held down
+    for event in state
+        .keyboard_input_event_reader
+        .iter(&keyboard_input_events)
+    {
+        let text_input = match &mut focused_input {
+            Some((_entity, text_input)) if event.state == ElementState::Pressed => text_input,
+            _ => continue,
+        };
+        match event.key_code {
+            Some(KeyCode::Left) => text_input.move_left(shift),
+            Some(KeyCode::Right) => text_input.move_right(shift),
+            Some(KeyCode::Home) => text_input.move_cursor(0, shift),
+            Some(KeyCode::End) => {
+                let end = text_input.value.len();
+                text_input.move_cursor(end, shift);
+            }
+            Some(KeyCode::Back) => changed |= text_input.backspace(),
+            Some(KeyCode::Delete) => changed |= text_input.delete(),
+            Some(KeyCode::Return) | Some(KeyCode::NumpadEnter) => submitted = true,
+            Some(KeyCode::A) if control => text_input.select_all(),
+            // an empty selection leaves the clipboard alone
+            Some(KeyCode::C) if control && !text_input.selected_text().is_empty() => {
+                clipboard.set(text_input.selected_text().to_string());
+            }
+            Some(KeyCode::X) if control && !text_input.selected_text().is_empty() => {
+                clipboard.set(text_input.selected_text().to_string());
+                changed |= text_input.delete_selection();
+            }
+            Some(KeyCode::V) if control => {
+                // the input is a single line
+                let pasted = clipboard.get().replace(|c: char| c.is_control(), "");
+                if !pasted.is_empty() {
+                    text_input.insert(&pasted);
+                    changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    for event in state
+        .received_character_event_reader
+        .iter(&received_character_events)
+    {
+        let text_input = match &mut focused_input {
+            Some((_entity, text_input)) => text_input,
+            None => continue,
+        };
+        // control characters are handled with their key codes
+        if event.char.is_control() || control {
+            continue;
+        }
+        text_input.insert(event.char.encode_utf8(&mut [0; 4]));
+        changed = true;
+    }
+
+    if let Some((entity, text_input)) = focused_input {
+        if changed {
+            changed_events.send(TextInputChanged {
+                entity,
+                value: text_input.value.clone(),
+            });
+        }
+        if submitted {
+            submitted_events.send(TextInputSubmitted {
+                entity,
+                value: text_input.value.clone(),
+            });
+        }
+    }
+}
+
+/// Updates the text drawn by text inputs whose value, font or font size changed
+pub fn text_input_text_system(
+    input_query: Query<(&TextInput, &TextInputParts), Changed<TextInput>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (text_input, parts) in input_query.iter() {
+        if let Ok(mut text) = text_query.get_mut(parts.text) {
+            if text.value != text_input.value
+                || text.font != text_input.font
+                || text.style.font_size != text_input.style.font_size
+            {
+                *text = text_input.text();
+            }
+        }
+    }
+}
+
+pub struct CaretBlink {
+    timer: Timer,
+    visible: bool,
+    focused: Option<Entity>,
+}
+
+impl Default for CaretBlink {
+    fn default() -> Self {
+        CaretBlink {
+            timer: Timer::from_seconds(0.5, true),
+            visible: true,
+            focused: None,
+        }
+    }
+}
+
+/// The distance from the left of the text to the character boundary at `index`
+fn caret_x(characters: &[PositionedCharacter], index: usize) -> f32 {
+    match characters
+        .iter()
+        .find(|character| character.byte_index >= index)
+    {
+        Some(character) => character.x,
+        None => characters
+            .last()
+            .map_or(0.0, |character| character.x + character.advance),
+    }
+}
+
+/// Positions the text, selection and caret of text inputs, blinks the caret of the focused text
+/// input, scrolls edited text inputs to their caret and places the candidate window of input
+/// methods under the caret of the focused text input
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_caret_system(
+    mut blink: Local<CaretBlink>,
+    time: Res<Time>,
+    focus: Res<Focus>,
+    ui_scale: Res<UiScale>,
+    mut windows: ResMut<Windows>,
+    active_cameras: Res<ActiveCameras>,
+    camera_query: Query<&Camera>,
+    text_pipeline: Res<DefaultTextPipeline>,
+    changed_query: Query<Entity, Changed<TextInput>>,
+    mut input_query: Query<(
+        Entity,
+        &TextInput,
+        &Node,
+        &TextInputParts,
+        Option<&mut ScrollPosition>,
+    )>,
+    text_size_query: Query<&CalculatedSize>,
+    caret_transform_query: Query<&GlobalTransform>,
+    mut part_query: Query<(&mut Style, &mut Draw)>,
+) {
+    // the caret stays visible while the focused text input is edited
+    let edited = focus
+        .entity
+        .map_or(false, |entity| changed_query.get(entity).is_ok());
+    if edited || blink.focused != focus.entity {
+        blink.timer.reset();
+        blink.visible = true;
+        blink.focused = focus.entity;
+    }
+    if blink.timer.tick(time.raw_delta_seconds()).just_finished() {
+        blink.visible = !blink.visible;
+    }
+    // the candidate window is placed in the window the ui is drawn to
+    let ui_window = active_cameras
+        .get(UI_CAMERA)
+        .and_then(|camera| camera_query.get(camera).ok())
+        .map(|camera| camera.window);
+
+    for (entity, text_input, node, parts, scroll_position) in input_query.iter_mut() {
+        let characters = text_pipeline
+            .get_glyphs(&parts.text)
+            .map_or(&[][..], |text_layout_info| &text_layout_info.characters[..]);
+        let text_height = text_size_query
+            .get(parts.text)
+            .map(|calculated_size| calculated_size.size.height)
+            .ok()
+            .filter(|height| *height > 0.0)
+            .unwrap_or(text_input.style.font_size);
+        let top = ((node.size.y() - text_height) / 2.0).max(0.0);
+        let focused = focus.entity == Some(entity);
+        let caret = TEXT_INPUT_PADDING + caret_x(characters, text_input.cursor);
+
+        update_part(
+            &mut part_query,
+            parts.text,
+            TEXT_INPUT_PADDING,
+            top,
+            None,
+            true,
+        );
+        update_part(
+            &mut part_query,
+            parts.caret,
+            caret,
+            top,
+            Some(Size::new(1.0, text_height)),
+            focused && blink.visible,
+        );
+        match text_input.selection() {
+            Some(selection) => {
+                let start = TEXT_INPUT_PADDING + caret_x(characters, selection.start);
+                let end = TEXT_INPUT_PADDING + caret_x(characters, selection.end);
+                update_part(
+                    &mut part_query,
+                    parts.selection,
+                    start,
+                    top,
+                    Some(Size::new(end - start, text_height)),
+                    focused,
+                );
+            }
+            None => update_part(&mut part_query, parts.selection, 0.0, top, None, false),
+        }
+
+        // the caret was moved by the layout of the last frame
+        if focused {
+            let window = match ui_window {
+                Some(window_id) => windows.get_mut(window_id),
+                None => windows.get_primary_mut(),
+            };
+            if let (Ok(caret_transform), Some(window)) =
+                (caret_transform_query.get(parts.caret), window)
+            {
+                let caret_bottom = Vec2::new(
+                    caret_transform.translation.x(),
+                    caret_transform.translation.y() - text_height / 2.0,
+                );
+                window.set_ime_position(ui_scale.to_window(caret_bottom));
+            }
+        }
+
+        // keep the caret of edited text inputs in view
+        if let Some(mut scroll_position) = scroll_position {
+            if changed_query.get(entity).is_ok() {
+                let offset = scroll_position.offset.x();
+                let view_width = node.size.x() - TEXT_INPUT_PADDING;
+                let scrolled_offset = if caret < offset + TEXT_INPUT_PADDING {
+                    caret - TEXT_INPUT_PADDING
+                } else if caret > offset + view_width {
+                    caret - view_width
+                } else {
+                    offset
+                };
+                if scrolled_offset != offset {
+                    scroll_position.offset = Vec2::new(scrolled_offset, scroll_position.offset.y());
+                }
+            }
+        }
+    }
+}
+
+/// Moves a part of a text input, only changing its style when it moved so that the layout isn't
+/// recomputed every frame
+fn update_part(
+    part_query: &mut Query<(&mut Style, &mut Draw)>,
+    entity: Entity,
+    left: f32,
+    top: f32,
+    size: Option<Size>,
+    is_visible: bool,
+) {
+    if let Ok((mut style, mut draw)) = part_query.get_mut(entity) {
+        let position = Rect {
+            left: Val::Px(left),
+            top: Val::Px(top),
+            ..Default::default()
+        };
+        let size = size.map_or(style.size, |size| {
+            Size::new(Val::Px(size.width), Val::Px(size.height))
+        });
+        if style.position != position || style.size != size {
+            style.position = position;
+            style.size = size;
+        }
+        if draw.is_visible != is_visible {
+            draw.is_visible = is_visible;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    #[test]
+    fn edit_text_input() {
+        let mut text_input = TextInput {
+            value: "héllo".to_string(),
+            cursor: 3,
+            ..Default::default()
+        };
+
+        text_input.backspace();
+        assert_eq!(text_input.value, "hllo");
+        assert_eq!(text_input.cursor, 1);
+
+        text_input.insert("e");
+        assert_eq!(text_input.value, "hello");
+        text_input.move_right(true);
+        text_input.move_right(true);
+        assert_eq!(text_input.selected_text(), "ll");
+
+        text_input.insert("LL");
+        assert_eq!(text_input.value, "heLLo");
+        assert_eq!(text_input.selection(), None);
+
+        text_input.move_cursor(0, true);
+        assert_eq!(text_input.selected_text(), "heLL");
+        text_input.move_right(false);
+        assert_eq!(text_input.cursor, 4);
+        assert!(text_input.delete());
+        assert!(!text_input.delete());
+        assert_eq!(text_input.value, "heLL");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::Node;
+use bevy_ecs::{Commands, Entity, Query, Res, With, Without};
+use bevy_math::{Quat, Vec3};
+use bevy_render::{
+    camera::ActiveCameras,
+    render_graph::base::{self, MainPass},
+};
+use bevy_transform::prelude::{Children, GlobalTransform, Transform};
+
+/// Draws a root node and its descendants in the 3d world, with the 3d camera, instead of on the
+/// screen. The node is laid out as usual and then placed with its center at `offset` from the
+/// `target` entity, or at `offset` in the world without a target.
+///
+/// Sizes in percent are still relative to the window, so world space nodes are usually sized in
+/// pixels. World space nodes are not clipped by their ancestors and don't interact with the
+/// cursor.
+#[derive(Debug, Clone)]
+pub struct WorldSpaceUi {
+    pub target: Option<Entity>,
+    pub offset: Vec3,
+    /// The size of a logical pixel in world units
+    pub scale: f32,
+    /// The rotation of the node. It is ignored when the node is billboarded.
+    pub rotation: Quat,
+    /// Turns the node to always face the 3d camera
+    pub billboard: bool,
+}
+
+impl Default for WorldSpaceUi {
+    fn default() -> Self {
+        WorldSpaceUi {
+            target: None,
+            offset: Vec3::zero(),
+            scale: 0.01,
+            rotation: Quat::identity(),
+            billboard: false,
+        }
+    }
+}
+
+/// Places world space nodes in the world, and moves their subtrees from the ui pass to the main
+/// pass
+pub fn world_space_ui_system(
+    commands: &mut Commands,
+    active_cameras: Res<ActiveCameras>,
+    mut root_query: Query<(Entity, &WorldSpaceUi, &mut Transform)>,
+    target_query: Query<&GlobalTransform>,
+    children_query: Query<&Children>,
+    moved_query: Query<Entity, (With<Node>, Without<MainPass>)>,
+) {
+    let camera_rotation = active_cameras
+        .get(base::camera::CAMERA3D)
+        .and_then(|camera| target_query.get(camera).ok())
+        .map(|camera_transform| camera_transform.rotation);
+
+    for (entity, world_space_ui, mut transform) in root_query.iter_mut() {
+        let mut translation = world_space_ui.offset;
+        if let Some(target) = world_space_ui.target {
+            match target_query.get(target) {
+                Ok(target_transform) => translation += target_transform.translation,
+                // the target was despawned
+                Err(_) => continue,
+            }
+        }
+        let rotation = match camera_rotation {
+            Some(camera_rotation) if world_space_ui.billboard => camera_rotation,
+            _ => world_space_ui.rotation,
+        };
+        // z isn't scaled, so children are drawn far enough in front of their parent to not
+        // fight over depth
+        *transform = Transform {
+            translation,
+            rotation,
+            scale: Vec3::new(world_space_ui.scale, world_space_ui.scale, 1.0),
+        };
+
+        move_to_main_pass(commands, &children_query, &moved_query, entity);
+    }
+}
+
+fn move_to_main_pass(
+    commands: &mut Commands,
+    children_query: &Query<&Children>,
+    moved_query: &Query<Entity, (With<Node>, Without<MainPass>)>,
+    entity: Entity,
+) {
+    if moved_query.get(entity).is_ok() {
+        commands.insert_one(entity, MainPass);
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter().cloned() {
+            move_to_main_pass(commands, children_query, moved_query, child);
+        }
+    }
+}
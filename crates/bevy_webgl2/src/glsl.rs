@@ -0,0 +1,810 @@
+//! WebGL2 can't consume SPIR-V, so shaders are kept as GLSL. This module resolves shader defs,
+//! reflects [ShaderLayout]s from the GLSL source and translates the Vulkan flavored GLSL that bevy
+//! shaders are written in to GLSL ES 3.00.
+
+use bevy_render::{
+    pipeline::{
+        BindGroupDescriptor, BindType, BindingDescriptor, BindingShaderStage, InputStepMode,
+        UniformProperty, VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
+    },
+    shader::{ShaderLayout, ShaderStage, GL_VERTEX_INDEX},
+    texture::{TextureComponentType, TextureViewDimension},
+};
+use bevy_utils::{HashMap, HashSet};
+
+/// GLSL ES has no storage buffers, so their runtime sized arrays become uniform arrays of this
+/// length
+pub const STORAGE_ARRAY_LENGTH: usize = 256;
+
+const ES_HEADER: &str = "#version 300 es
+precision highp float;
+precision highp int;
+precision highp sampler2DArray;
+precision highp sampler3D;
+precision highp sampler2DShadow;
+";
+
+const COMBINED_SAMPLER_TYPES: &[&str] = &[
+    "sampler2D",
+    "sampler2DArray",
+    "sampler3D",
+    "samplerCube",
+    "sampler2DShadow",
+];
+
+/// Resolves the `#ifdef`, `#ifndef`, `#else` and `#endif` blocks of `source` for the given shader
+/// defs. Other directives are kept for the GLSL compiler.
+pub fn preprocess(source: &str, shader_defs: &[String]) -> String {
+    let mut defined = shader_defs.iter().cloned().collect::<HashSet<String>>();
+    // for each open conditional: whether its parent is active, and whether its current branch is
+    let mut scopes: Vec<(bool, bool)> = Vec::new();
+    let mut output = String::with_capacity(source.len());
+    for line in source.lines() {
+        let active = scopes
+            .last()
+            .map_or(true, |(parent, branch)| *parent && *branch);
+        if let Some(directive) = line.trim_start().strip_prefix('#') {
+            let mut words = directive.split_whitespace();
+            match words.next() {
+                Some(directive @ "ifdef") | Some(directive @ "ifndef") => {
+                    let is_defined = words.next().map_or(false, |name| defined.contains(name));
+                    scopes.push((active, is_defined == (directive == "ifdef")));
+                    continue;
+                }
+                Some("else") => {
+                    if let Some((_, branch)) = scopes.last_mut() {
+                        *branch = !*branch;
+                    }
+                    continue;
+                }
+                Some("endif") => {
+                    scopes.pop();
+                    continue;
+                }
+                Some("define") if active => {
+                    if let Some(name) = words.next() {
+                        defined.insert(name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if active {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// Reflects the layout of a preprocessed GLSL shader, following the same conventions as
+/// SPIR-V reflection
+pub fn reflect_layout(source: &str, stage: ShaderStage, bevy_conventions: bool) -> ShaderLayout {
+    let source = strip_comments(source);
+    let mut parser = Parser::new(&source);
+    let mut structs = HashMap::<String, Vec<Member>>::default();
+    let mut constants = HashMap::<String, usize>::default();
+    let mut bind_groups = HashMap::<u32, Vec<BindingDescriptor>>::default();
+    let mut vertex_attribute_descriptors = Vec::new();
+
+    let shader_stage = match stage {
+        ShaderStage::Vertex => BindingShaderStage::VERTEX,
+        ShaderStage::Fragment => BindingShaderStage::FRAGMENT,
+        ShaderStage::Compute => BindingShaderStage::COMPUTE,
+    };
+
+    while let Some(token) = parser.next() {
+        match token {
+            "struct" => {
+                let name = parser.next().unwrap_or_default().to_string();
+                parser.expect("{");
+                let members = parser.members(&constants);
+                parser.skip_past(";");
+                structs.insert(name, members);
+            }
+            "const" => {
+                let _type = parser.next();
+                let name = parser.next().unwrap_or_default().to_string();
+                parser.expect("=");
+                if let Some(value) = parser.next().and_then(|value| value.parse().ok()) {
+                    constants.insert(name, value);
+                }
+                parser.skip_past(";");
+            }
+            "layout" => {
+                let qualifiers = parser.layout_qualifiers();
+                let storage = parser.storage_qualifier();
+                match storage {
+                    Some("in") if stage == ShaderStage::Vertex => {
+                        let format = vertex_format(parser.next().unwrap_or_default());
+                        let name = parser.next().unwrap_or_default().to_string();
+                        if name != GL_VERTEX_INDEX {
+                            vertex_attribute_descriptors.push(VertexAttributeDescriptor {
+                                name: name.into(),
+                                format,
+                                offset: 0,
+                                shader_location: qualifiers.get("location").copied().unwrap_or(0),
+                            });
+                        }
+                    }
+                    Some(storage @ "uniform") | Some(storage @ "buffer") => {
+                        let type_name = parser.next().unwrap_or_default().to_string();
+                        let (name, bind_type) = if parser.peek() == Some("{") {
+                            parser.expect("{");
+                            let members = parser.members(&constants);
+                            let bind_type = if storage == "buffer" {
+                                BindType::StorageBuffer {
+                                    dynamic: false,
+                                    readonly: true,
+                                }
+                            } else {
+                                BindType::Uniform {
+                                    dynamic: false,
+                                    property: UniformProperty::Struct(
+                                        members
+                                            .iter()
+                                            .map(|member| member.property(&structs))
+                                            .collect(),
+                                    ),
+                                }
+                            };
+                            (type_name, bind_type)
+                        } else {
+                            let name = parser.next().unwrap_or_default().to_string();
+                            (name, resource_bind_type(&type_name))
+                        };
+
+                        let set = qualifiers.get("set").copied().unwrap_or(0);
+                        let binding_shader_stage = if name == "Camera" {
+                            BindingShaderStage::VERTEX | BindingShaderStage::FRAGMENT
+                        } else {
+                            shader_stage
+                        };
+                        bind_groups
+                            .entry(set)
+                            .or_insert_with(Vec::new)
+                            .push(BindingDescriptor {
+                                name,
+                                index: qualifiers.get("binding").copied().unwrap_or(0),
+                                bind_type,
+                                shader_stage: binding_shader_stage,
+                            });
+                    }
+                    _ => {}
+                }
+                parser.skip_past(";");
+            }
+            // function bodies don't declare anything that is reflected
+            "{" => parser.skip_block(),
+            _ => {}
+        }
+    }
+
+    let mut bind_groups = bind_groups
+        .drain()
+        .map(|(index, mut bindings)| {
+            bindings.sort_by_key(|binding| binding.index);
+            BindGroupDescriptor::new(index, bindings)
+        })
+        .collect::<Vec<_>>();
+    bind_groups.sort_by_key(|bind_group| bind_group.index);
+
+    vertex_attribute_descriptors.sort_by_key(|attribute| attribute.shader_location);
+    let vertex_buffer_descriptors = vertex_attribute_descriptors
+        .drain(..)
+        .map(|attribute| {
+            let (name, step_mode) = if bevy_conventions {
+                let step_mode = if attribute.name.starts_with("I_") {
+                    InputStepMode::Instance
+                } else {
+                    InputStepMode::Vertex
+                };
+                (attribute.name.to_string(), step_mode)
+            } else {
+                ("DefaultVertex".to_string(), InputStepMode::Vertex)
+            };
+            VertexBufferDescriptor {
+                name: name.into(),
+                stride: 0,
+                step_mode,
+                attributes: vec![attribute],
+            }
+        })
+        .collect();
+
+    ShaderLayout {
+        bind_groups,
+        vertex_buffer_descriptors,
+        entry_point: "main".to_string(),
+    }
+}
+
+/// Translates a preprocessed shader to GLSL ES 3.00:
+/// * descriptor sets and bindings are removed, programs are bound by name instead
+/// * uniform blocks use the std140 layout, and storage buffers become uniform blocks with
+///   [STORAGE_ARRAY_LENGTH] long arrays
+/// * separate textures and samplers are merged into combined samplers
+/// * `gl_VertexIndex` and `gl_InstanceIndex` become `gl_VertexID` and `gl_InstanceID`
+pub fn to_glsl_es(source: &str, stage: ShaderStage) -> String {
+    let source = strip_comments(source);
+    let body = match source.find("#version") {
+        Some(start) => {
+            let end = source[start..]
+                .find('\n')
+                .map_or(source.len(), |end| start + end);
+            format!("{}{}", &source[..start], &source[end..])
+        }
+        None => source,
+    };
+
+    let mut output = String::from(ES_HEADER);
+    let mut rest = body.as_str();
+    while let Some(start) = find_identifier(rest, "layout") {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = declaration_end(rest);
+        output.push_str(&convert_declaration(&rest[..end], stage));
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+
+    let output = replace_combined_samplers(&output);
+    let output = replace_identifier(&output, "gl_VertexIndex", "gl_VertexID");
+    replace_identifier(&output, "gl_InstanceIndex", "gl_InstanceID")
+}
+
+fn convert_declaration(declaration: &str, stage: ShaderStage) -> String {
+    let arguments_end = declaration
+        .find(')')
+        .map_or(declaration.len(), |end| end + 1);
+    let mut parser = Parser::new(&declaration[..arguments_end]);
+    parser.expect("layout");
+    let qualifiers = parser.layout_qualifiers();
+    let rest = declaration[arguments_end..].trim_start();
+
+    let mut words = Parser::new(rest);
+    let storage = words.storage_qualifier();
+    let type_name = words.next().unwrap_or_default();
+    match storage {
+        Some("in") if stage == ShaderStage::Vertex => keep_location(&qualifiers, rest),
+        Some("out") if stage == ShaderStage::Fragment => keep_location(&qualifiers, rest),
+        Some("uniform") if words.peek() != Some("{") => match type_name {
+            // samplers are merged into the textures they are used with
+            "sampler" | "samplerShadow" => String::new(),
+            _ => {
+                let sampler_type = type_name.replacen("texture", "sampler", 1);
+                replace_identifier(rest, type_name, &sampler_type)
+            }
+        },
+        Some("uniform") => format!("layout(std140) {}", rest),
+        Some("buffer") => {
+            let block = replace_identifier(rest, "buffer", "uniform");
+            let block = ["readonly", "writeonly", "coherent", "restrict"]
+                .iter()
+                .fold(block, |block, qualifier| {
+                    replace_identifier(&block, qualifier, "")
+                });
+            format!("layout(std140) {}", fix_runtime_arrays(block.trim_start()))
+        }
+        _ => rest.to_string(),
+    }
+}
+
+fn keep_location(qualifiers: &HashMap<&str, u32>, rest: &str) -> String {
+    match qualifiers.get("location") {
+        Some(location) => format!("layout(location = {}) {}", location, rest),
+        None => rest.to_string(),
+    }
+}
+
+/// Gives the runtime sized arrays of a block a fixed length
+fn fix_runtime_arrays(block: &str) -> String {
+    let (start, end) = match (block.find('{'), block.rfind('}')) {
+        (Some(start), Some(end)) if start < end => (start + 1, end),
+        _ => return block.to_string(),
+    };
+    let members = block[start..end]
+        .split(';')
+        .map(|member| {
+            if member.contains("[]") {
+                format!(
+                    "{}[{}]",
+                    member.replace("[]", "").trim_end(),
+                    STORAGE_ARRAY_LENGTH
+                )
+            } else {
+                member.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    format!("{}{}{}", &block[..start], members, &block[end..])
+}
+
+/// Replaces `sampler2D(texture, sampler)` constructors by the texture, which is itself a
+/// combined sampler in GLSL ES
+fn replace_combined_samplers(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    loop {
+        let next = COMBINED_SAMPLER_TYPES
+            .iter()
+            .filter_map(|sampler_type| {
+                find_identifier(rest, sampler_type).map(|start| (start, sampler_type.len()))
+            })
+            .min();
+        let (start, length) = match next {
+            Some(next) => next,
+            None => break,
+        };
+        let after = &rest[start + length..];
+        let arguments_start = after.len() - after.trim_start().len();
+        if !after[arguments_start..].starts_with('(') {
+            // a declaration, not a constructor
+            output.push_str(&rest[..start + length]);
+            rest = after;
+            continue;
+        }
+
+        let arguments = &after[arguments_start + 1..];
+        let mut depth = 0;
+        let mut first_argument_end = None;
+        let mut end = arguments.len();
+        for (index, character) in arguments.char_indices() {
+            match character {
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    end = index;
+                    break;
+                }
+                ')' => depth -= 1,
+                ',' if depth == 0 && first_argument_end.is_none() => {
+                    first_argument_end = Some(index)
+                }
+                _ => {}
+            }
+        }
+        output.push_str(&rest[..start]);
+        output.push_str(arguments[..first_argument_end.unwrap_or(end)].trim());
+        rest = &arguments[(end + 1).min(arguments.len())..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Returns the end of the declaration starting at the beginning of `source`, including the
+/// members of blocks
+fn declaration_end(source: &str) -> usize {
+    let semicolon = source.find(';').map_or(source.len(), |end| end + 1);
+    match source.find('{') {
+        Some(block_start) if block_start < semicolon => source[block_start..]
+            .find('}')
+            .and_then(|block_end| {
+                let block_end = block_start + block_end;
+                source[block_end..].find(';').map(|end| block_end + end + 1)
+            })
+            .unwrap_or_else(|| source.len()),
+        _ => semicolon,
+    }
+}
+
+fn is_identifier_character(character: u8) -> bool {
+    character.is_ascii_alphanumeric() || character == b'_'
+}
+
+fn find_identifier(source: &str, identifier: &str) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut offset = 0;
+    while let Some(index) = source[offset..].find(identifier) {
+        let start = offset + index;
+        let end = start + identifier.len();
+        let starts_word = start == 0 || !is_identifier_character(bytes[start - 1]);
+        let ends_word = end == bytes.len() || !is_identifier_character(bytes[end]);
+        if starts_word && ends_word {
+            return Some(start);
+        }
+        offset = end;
+    }
+    None
+}
+
+fn replace_identifier(source: &str, from: &str, to: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = find_identifier(rest, from) {
+        output.push_str(&rest[..start]);
+        output.push_str(to);
+        rest = &rest[start + from.len()..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    loop {
+        let line_comment = rest.find("//");
+        let block_comment = rest.find("/*");
+        match (line_comment, block_comment) {
+            (Some(line), block) if block.map_or(true, |block| line < block) => {
+                output.push_str(&rest[..line]);
+                rest = rest[line..]
+                    .find('\n')
+                    .map_or("", |end| &rest[line + end..]);
+            }
+            (_, Some(block)) => {
+                output.push_str(&rest[..block]);
+                output.push(' ');
+                rest = rest[block + 2..]
+                    .find("*/")
+                    .map_or("", |end| &rest[block + 2 + end + 2..]);
+            }
+            _ => break,
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn resource_bind_type(type_name: &str) -> BindType {
+    let (component_type, texture_type) = match type_name.as_bytes().first() {
+        Some(b'i') => (TextureComponentType::Sint, &type_name[1..]),
+        Some(b'u') => (TextureComponentType::Uint, &type_name[1..]),
+        _ => (TextureComponentType::Float, type_name),
+    };
+    let dimension = match texture_type {
+        "sampler" => return BindType::Sampler { comparison: false },
+        "samplerShadow" => return BindType::Sampler { comparison: true },
+        "texture1D" => TextureViewDimension::D1,
+        "texture2D" => TextureViewDimension::D2,
+        "texture2DArray" => TextureViewDimension::D2Array,
+        "texture3D" => TextureViewDimension::D3,
+        "textureCube" => TextureViewDimension::Cube,
+        "textureCubeArray" => TextureViewDimension::CubeArray,
+        _ => panic!("unsupported uniform type {}", type_name),
+    };
+    BindType::SampledTexture {
+        multisampled: false,
+        dimension,
+        component_type,
+    }
+}
+
+fn vertex_format(type_name: &str) -> VertexFormat {
+    match type_name {
+        "float" => VertexFormat::Float,
+        "vec2" => VertexFormat::Float2,
+        "vec3" => VertexFormat::Float3,
+        "vec4" => VertexFormat::Float4,
+        "int" => VertexFormat::Int,
+        "ivec2" => VertexFormat::Int2,
+        "ivec3" => VertexFormat::Int3,
+        "ivec4" => VertexFormat::Int4,
+        "uint" => VertexFormat::Uint,
+        "uvec2" => VertexFormat::Uint2,
+        "uvec3" => VertexFormat::Uint3,
+        "uvec4" => VertexFormat::Uint4,
+        _ => panic!("unexpected vertex attribute type {}", type_name),
+    }
+}
+
+/// A member of a struct or block
+#[derive(Debug)]
+struct Member {
+    type_name: String,
+    /// `Some(0)` for runtime sized arrays
+    array_length: Option<usize>,
+}
+
+impl Member {
+    fn property(&self, structs: &HashMap<String, Vec<Member>>) -> UniformProperty {
+        let property = match self.type_name.as_str() {
+            "uint" => UniformProperty::UInt,
+            "int" => UniformProperty::Int,
+            "ivec2" => UniformProperty::IVec2,
+            "float" => UniformProperty::Float,
+            "uvec4" => UniformProperty::UVec4,
+            "vec2" => UniformProperty::Vec2,
+            "vec3" => UniformProperty::Vec3,
+            "vec4" => UniformProperty::Vec4,
+            "mat3" => UniformProperty::Mat3,
+            "mat4" => UniformProperty::Mat4,
+            name => match structs.get(name) {
+                Some(members) => UniformProperty::Struct(
+                    members
+                        .iter()
+                        .map(|member| member.property(structs))
+                        .collect(),
+                ),
+                None => panic!("unexpected uniform property type {}", name),
+            },
+        };
+        match self.array_length {
+            Some(length) => UniformProperty::Array(Box::new(property), length),
+            None => property,
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        let bytes = source.as_bytes();
+        let mut tokens = Vec::new();
+        let mut index = 0;
+        while index < bytes.len() {
+            let character = bytes[index];
+            if character == b'#' {
+                // directives are left to the GLSL compiler
+                index = source[index..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| index + end);
+            } else if character.is_ascii_whitespace() {
+                index += 1;
+            } else if is_identifier_character(character) || character == b'.' {
+                let start = index;
+                while index < bytes.len()
+                    && (is_identifier_character(bytes[index]) || bytes[index] == b'.')
+                {
+                    index += 1;
+                }
+                tokens.push(&source[start..index]);
+            } else {
+                let length = source[index..].chars().next().map_or(1, char::len_utf8);
+                tokens.push(&source[index..index + length]);
+                index += length;
+            }
+        }
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn expect(&mut self, token: &str) {
+        if self.peek() == Some(token) {
+            self.position += 1;
+        }
+    }
+
+    fn skip_past(&mut self, token: &str) {
+        while let Some(next) = self.next() {
+            if next == token {
+                break;
+            }
+        }
+    }
+
+    /// Skips to the end of a block whose opening brace was just read
+    fn skip_block(&mut self) {
+        let mut depth = 1;
+        while let Some(token) = self.next() {
+            match token {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads `(name = value, name)` after `layout`
+    fn layout_qualifiers(&mut self) -> HashMap<&'a str, u32> {
+        let mut qualifiers = HashMap::default();
+        self.expect("(");
+        while let Some(name) = self.next() {
+            match name {
+                ")" => break,
+                "," => continue,
+                _ => {
+                    let value = if self.peek() == Some("=") {
+                        self.position += 1;
+                        self.next().and_then(|value| value.parse().ok())
+                    } else {
+                        None
+                    };
+                    qualifiers.insert(name, value.unwrap_or(0));
+                }
+            }
+        }
+        qualifiers
+    }
+
+    /// Reads the qualifiers of a declaration, returning its storage qualifier
+    fn storage_qualifier(&mut self) -> Option<&'a str> {
+        let mut storage = None;
+        while let Some(token) = self.peek() {
+            match token {
+                "in" | "out" | "uniform" | "buffer" => storage = Some(token),
+                "readonly" | "writeonly" | "coherent" | "restrict" | "flat" | "smooth"
+                | "centroid" | "highp" | "mediump" | "lowp" => {}
+                _ => break,
+            }
+            self.position += 1;
+        }
+        storage
+    }
+
+    /// Reads the members of a struct or block whose opening brace was just read
+    fn members(&mut self, constants: &HashMap<String, usize>) -> Vec<Member> {
+        let mut members = Vec::new();
+        while let Some(token) = self.next() {
+            if token == "}" {
+                break;
+            }
+            if matches!(token, "highp" | "mediump" | "lowp") {
+                continue;
+            }
+            let type_name = token.to_string();
+            let type_array_length = self.array_length(constants);
+            loop {
+                let _name = self.next();
+                let array_length = self.array_length(constants).or(type_array_length);
+                members.push(Member {
+                    type_name: type_name.clone(),
+                    array_length,
+                });
+                if self.next() != Some(",") {
+                    break;
+                }
+            }
+        }
+        members
+    }
+
+    fn array_length(&mut self, constants: &HashMap<String, usize>) -> Option<usize> {
+        if self.peek() != Some("[") {
+            return None;
+        }
+        self.position += 1;
+        let mut length = 0;
+        while let Some(token) = self.next() {
+            if token == "]" {
+                break;
+            }
+            length = token
+                .parse()
+                .ok()
+                .or_else(|| constants.get(token).copied())
+                .unwrap_or(length);
+        }
+        Some(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERTEX_SHADER: &str = r#"
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+layout(location = 1) in vec2 Vertex_Uv;
+layout(location = 0) out vec2 v_Uv;
+
+layout(set = 0, binding = 0) uniform Camera {
+    mat4 ViewProj;
+};
+
+struct Rect {
+    vec2 begin;
+    vec2 end;
+};
+
+layout(set = 1, binding = 0) buffer TextureAtlas_textures {
+    Rect[] Textures;
+};
+
+# ifdef SPRITE_TEXTURE
+layout(set = 1, binding = 1) uniform texture2D Sprite_texture;
+layout(set = 1, binding = 2) uniform sampler Sprite_texture_sampler;
+# endif
+
+void main() {
+    v_Uv = Textures[gl_VertexIndex].begin;
+# ifdef SPRITE_TEXTURE
+    v_Uv = texture(sampler2D(Sprite_texture, Sprite_texture_sampler), v_Uv).xy;
+# endif
+    gl_Position = ViewProj * vec4(Vertex_Position, 1.0);
+}
+"#;
+
+    #[test]
+    fn preprocess_shader_defs() {
+        let source = "#version 450\n#ifdef A\na\n# ifndef B\nnot b\n#else\nb\n#endif\n#endif\nend";
+        assert_eq!(preprocess(source, &[]), "#version 450\nend\n");
+        assert_eq!(
+            preprocess(source, &["A".to_string()]),
+            "#version 450\na\nnot b\nend\n"
+        );
+        assert_eq!(
+            preprocess(source, &["A".to_string(), "B".to_string()]),
+            "#version 450\na\nb\nend\n"
+        );
+    }
+
+    #[test]
+    fn reflect_glsl_layout() {
+        let source = preprocess(VERTEX_SHADER, &["SPRITE_TEXTURE".to_string()]);
+        let layout = reflect_layout(&source, ShaderStage::Vertex, true);
+
+        assert_eq!(layout.vertex_buffer_descriptors.len(), 2);
+        let uv = &layout.vertex_buffer_descriptors[1];
+        assert_eq!(uv.name, "Vertex_Uv");
+        assert_eq!(uv.attributes[0].format, VertexFormat::Float2);
+        assert_eq!(uv.attributes[0].shader_location, 1);
+
+        assert_eq!(layout.bind_groups.len(), 2);
+        let camera = &layout.bind_groups[0].bindings[0];
+        assert_eq!(camera.name, "Camera");
+        assert_eq!(
+            camera.shader_stage,
+            BindingShaderStage::VERTEX | BindingShaderStage::FRAGMENT
+        );
+        assert_eq!(
+            camera.bind_type,
+            BindType::Uniform {
+                dynamic: false,
+                property: UniformProperty::Struct(vec![UniformProperty::Mat4]),
+            }
+        );
+        let names = layout.bind_groups[1]
+            .bindings
+            .iter()
+            .map(|binding| binding.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "TextureAtlas_textures",
+                "Sprite_texture",
+                "Sprite_texture_sampler"
+            ]
+        );
+
+        let source = preprocess(VERTEX_SHADER, &[]);
+        let layout = reflect_layout(&source, ShaderStage::Vertex, true);
+        assert_eq!(layout.bind_groups[1].bindings.len(), 1);
+    }
+
+    #[test]
+    fn translate_to_glsl_es() {
+        let source = preprocess(VERTEX_SHADER, &["SPRITE_TEXTURE".to_string()]);
+        let translated = to_glsl_es(&source, ShaderStage::Vertex);
+
+        assert!(translated.starts_with("#version 300 es\n"));
+        assert!(!translated.contains("#version 450"));
+        assert!(translated.contains("layout(location = 1) in vec2 Vertex_Uv;"));
+        assert!(translated.contains("\nout vec2 v_Uv;"));
+        assert!(translated.contains("layout(std140) uniform Camera {"));
+        assert!(translated.contains("layout(std140) uniform TextureAtlas_textures {"));
+        assert!(translated.contains(&format!("Rect Textures[{}];", STORAGE_ARRAY_LENGTH)));
+        assert!(translated.contains("uniform sampler2D Sprite_texture;"));
+        assert!(!translated.contains("Sprite_texture_sampler"));
+        assert!(translated.contains("texture(Sprite_texture, v_Uv)"));
+        assert!(translated.contains("Textures[gl_VertexID]"));
+        assert!(!translated.contains("set ="));
+    }
+}
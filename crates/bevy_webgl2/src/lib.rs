@@ -0,0 +1,64 @@
+//! A WebGL2 render backend, used to run Bevy apps in the browser.
+//!
+//! Shaders are written in the same GLSL as for the wgpu backend and translated to GLSL ES 3.0 at
+//! runtime, see [glsl]. Some features don't map to WebGL2:
+//! * storage buffers become uniform blocks, and their runtime-sized arrays are limited to
+//!   [glsl::STORAGE_ARRAY_LENGTH] elements
+//! * multisampling is ignored
+//! * textures that are rendered to are vertically flipped compared to the wgpu backend
+//! * storage textures and compute shaders aren't supported
+
+pub mod glsl;
+#[cfg(target_arch = "wasm32")]
+pub mod renderer;
+#[cfg(target_arch = "wasm32")]
+mod webgl2_render_pass;
+#[cfg(target_arch = "wasm32")]
+mod webgl2_renderer;
+#[cfg(target_arch = "wasm32")]
+mod webgl2_resources;
+#[cfg(target_arch = "wasm32")]
+mod webgl2_type_converter;
+
+#[cfg(target_arch = "wasm32")]
+pub use webgl2_render_pass::*;
+#[cfg(target_arch = "wasm32")]
+pub use webgl2_renderer::*;
+#[cfg(target_arch = "wasm32")]
+pub use webgl2_resources::*;
+
+#[cfg(target_arch = "wasm32")]
+use bevy_app::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use bevy_ecs::{Resources, World};
+#[cfg(target_arch = "wasm32")]
+use bevy_render::renderer::{free_shared_buffers_system, RenderResourceContext, SharedBuffers};
+#[cfg(target_arch = "wasm32")]
+use renderer::WebGL2RenderResourceContext;
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub struct WebGL2Plugin;
+
+#[cfg(target_arch = "wasm32")]
+impl Plugin for WebGL2Plugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let render_system = get_webgl2_render_system(app.resources_mut());
+        app.add_system_to_stage(bevy_app::stage::FIRST, webgl2_window_system)
+            .add_system_to_stage(bevy_render::stage::RENDER, render_system)
+            .add_system_to_stage(bevy_render::stage::POST_RENDER, free_shared_buffers_system);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn get_webgl2_render_system(
+    resources: &mut Resources,
+) -> impl FnMut(&mut World, &mut Resources) {
+    let mut webgl2_renderer = WebGL2Renderer::default();
+    let resource_context = WebGL2RenderResourceContext::default();
+    resources.insert::<Box<dyn RenderResourceContext>>(Box::new(resource_context.clone()));
+    resources.insert(SharedBuffers::new(Box::new(resource_context)));
+    move |world, resources| {
+        webgl2_renderer.update(world, resources);
+    }
+}
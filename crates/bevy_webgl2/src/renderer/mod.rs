@@ -0,0 +1,7 @@
+mod webgl2_render_context;
+mod webgl2_render_graph_executor;
+mod webgl2_render_resource_context;
+
+pub use webgl2_render_context::*;
+pub use webgl2_render_graph_executor::*;
+pub use webgl2_render_resource_context::*;
@@ -0,0 +1,221 @@
+use super::WebGL2RenderResourceContext;
+use crate::{Gl, WebGL2RenderPass};
+use bevy_render::{
+    pass::{LoadOp, PassDescriptor, RenderPass, TextureAttachment},
+    renderer::{
+        BufferId, RenderContext, RenderResourceBinding, RenderResourceBindings,
+        RenderResourceContext, TextureId,
+    },
+    texture::{Extent3d, TextureFormat},
+};
+use wasm_bindgen::JsValue;
+
+/// WebGL2 executes commands immediately, so this context only forwards them to the
+/// [WebGL2RenderResourceContext]
+#[derive(Debug)]
+pub struct WebGL2RenderContext {
+    pub render_resource_context: WebGL2RenderResourceContext,
+}
+
+impl WebGL2RenderContext {
+    pub fn new(resources: WebGL2RenderResourceContext) -> Self {
+        WebGL2RenderContext {
+            render_resource_context: resources,
+        }
+    }
+
+    /// Binds the framebuffer of the pass' attachments and clears them. Returns the size of the
+    /// attachments.
+    fn bind_framebuffer(
+        &self,
+        pass_descriptor: &PassDescriptor,
+        render_resource_bindings: &RenderResourceBindings,
+    ) -> Extent3d {
+        let resources = &self.render_resource_context.resources;
+        let gl = resources.gl();
+        // multisampling isn't supported, passes render to their resolve targets directly
+        let color_attachments = pass_descriptor
+            .color_attachments
+            .iter()
+            .map(|color_attachment| {
+                get_texture(
+                    render_resource_bindings,
+                    color_attachment
+                        .resolve_target
+                        .as_ref()
+                        .unwrap_or(&color_attachment.attachment),
+                )
+            })
+            .collect::<Vec<_>>();
+        let depth_attachment =
+            pass_descriptor
+                .depth_stencil_attachment
+                .as_ref()
+                .map(|depth_attachment| {
+                    get_texture(render_resource_bindings, &depth_attachment.attachment)
+                });
+
+        let mut attachments = color_attachments.clone();
+        attachments.extend(depth_attachment);
+        let textures = resources.textures.read();
+        let mut framebuffers = resources.framebuffers.write();
+        let framebuffer = framebuffers.entry(attachments).or_insert_with(|| {
+            let framebuffer = gl
+                .create_framebuffer()
+                .expect("Failed to create framebuffer");
+            gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+            let draw_buffers = js_sys::Array::new();
+            for (i, texture_id) in color_attachments.iter().enumerate() {
+                let texture = textures.get(texture_id).unwrap();
+                let attachment = Gl::COLOR_ATTACHMENT0 + i as u32;
+                gl.framebuffer_texture_2d(
+                    Gl::FRAMEBUFFER,
+                    attachment,
+                    Gl::TEXTURE_2D,
+                    Some(&texture.texture),
+                    0,
+                );
+                draw_buffers.push(&JsValue::from(attachment));
+            }
+            if let Some(texture_id) = depth_attachment {
+                let texture = textures.get(&texture_id).unwrap();
+                let attachment = match texture.descriptor.format {
+                    TextureFormat::Depth24PlusStencil8 => Gl::DEPTH_STENCIL_ATTACHMENT,
+                    _ => Gl::DEPTH_ATTACHMENT,
+                };
+                gl.framebuffer_texture_2d(
+                    Gl::FRAMEBUFFER,
+                    attachment,
+                    Gl::TEXTURE_2D,
+                    Some(&texture.texture),
+                    0,
+                );
+            }
+            gl.draw_buffers(&draw_buffers);
+            framebuffer
+        });
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(framebuffer));
+
+        // clears are affected by the write masks and scissor test of the previous pipeline
+        gl.disable(Gl::SCISSOR_TEST);
+        gl.color_mask(true, true, true, true);
+        gl.depth_mask(true);
+        gl.stencil_mask(!0);
+        for (i, color_attachment) in pass_descriptor.color_attachments.iter().enumerate() {
+            if let LoadOp::Clear(color) = color_attachment.ops.load {
+                gl.clear_bufferfv_with_f32_array(
+                    Gl::COLOR,
+                    i as i32,
+                    &[
+                        color.r_linear(),
+                        color.g_linear(),
+                        color.b_linear(),
+                        color.a(),
+                    ],
+                );
+            }
+        }
+        if let Some(depth_stencil_attachment) = &pass_descriptor.depth_stencil_attachment {
+            if let Some(LoadOp::Clear(depth)) = depth_stencil_attachment
+                .depth_ops
+                .as_ref()
+                .map(|ops| &ops.load)
+            {
+                gl.clear_bufferfv_with_f32_array(Gl::DEPTH, 0, &[*depth]);
+            }
+            if let Some(LoadOp::Clear(stencil)) = depth_stencil_attachment
+                .stencil_ops
+                .as_ref()
+                .map(|ops| &ops.load)
+            {
+                gl.clear_bufferiv_with_i32_array(Gl::STENCIL, 0, &[*stencil as i32]);
+            }
+        }
+
+        color_attachments
+            .first()
+            .or_else(|| depth_attachment.as_ref())
+            .map(|texture_id| textures.get(texture_id).unwrap().descriptor.size)
+            .unwrap_or_else(|| Extent3d::new(1, 1, 1))
+    }
+}
+
+fn get_texture(
+    global_render_resource_bindings: &RenderResourceBindings,
+    attachment: &TextureAttachment,
+) -> TextureId {
+    match attachment {
+        TextureAttachment::Name(name) => match global_render_resource_bindings.get(&name) {
+            Some(RenderResourceBinding::Texture(resource)) => *resource,
+            _ => {
+                panic!("Color attachment {} does not exist", name);
+            }
+        },
+        TextureAttachment::Id(render_resource) => *render_resource,
+        TextureAttachment::Input(_) => panic!("Encountered unset TextureAttachment::Input. The RenderGraph executor should always set TextureAttachment::Inputs to TextureAttachment::RenderResource before running. This is a bug"),
+    }
+}
+
+impl RenderContext for WebGL2RenderContext {
+    fn copy_buffer_to_buffer(
+        &mut self,
+        source_buffer: BufferId,
+        source_offset: u64,
+        destination_buffer: BufferId,
+        destination_offset: u64,
+        size: u64,
+    ) {
+        self.render_resource_context.copy_buffer_to_buffer(
+            source_buffer,
+            source_offset,
+            destination_buffer,
+            destination_offset,
+            size,
+        );
+    }
+
+    fn copy_buffer_to_texture(
+        &mut self,
+        source_buffer: BufferId,
+        source_offset: u64,
+        source_bytes_per_row: u32,
+        destination_texture: TextureId,
+        destination_origin: [u32; 3],
+        destination_mip_level: u32,
+        size: Extent3d,
+    ) {
+        self.render_resource_context.copy_buffer_to_texture(
+            source_buffer,
+            source_offset,
+            source_bytes_per_row,
+            destination_texture,
+            destination_origin,
+            destination_mip_level,
+            size,
+        )
+    }
+
+    fn resources(&self) -> &dyn RenderResourceContext {
+        &self.render_resource_context
+    }
+
+    fn resources_mut(&mut self) -> &mut dyn RenderResourceContext {
+        &mut self.render_resource_context
+    }
+
+    fn begin_pass(
+        &mut self,
+        pass_descriptor: &PassDescriptor,
+        render_resource_bindings: &RenderResourceBindings,
+        run_pass: &mut dyn Fn(&mut dyn RenderPass),
+    ) {
+        let size = self.bind_framebuffer(pass_descriptor, render_resource_bindings);
+        let gl = self.render_resource_context.resources.gl();
+        gl.viewport(0, 0, size.width as i32, size.height as i32);
+        gl.depth_range(0.0, 1.0);
+
+        let mut render_pass = WebGL2RenderPass::new(self, gl, size.height);
+        run_pass(&mut render_pass);
+        render_pass.finish();
+    }
+}
@@ -0,0 +1,61 @@
+use super::{WebGL2RenderContext, WebGL2RenderResourceContext};
+use bevy_ecs::{Resources, World};
+use bevy_render::{
+    render_graph::{Edge, NodeId, ResourceSlots, StageBorrow},
+    renderer::RenderResourceContext,
+};
+use bevy_utils::HashMap;
+
+/// Runs the nodes of the render graph one after the other, as the WebGL2 context can only be used
+/// from the main thread
+#[derive(Debug, Default)]
+pub struct WebGL2RenderGraphExecutor;
+
+impl WebGL2RenderGraphExecutor {
+    pub fn execute(&self, world: &World, resources: &Resources, stages: &mut [StageBorrow]) {
+        let render_resource_context = resources
+            .get::<Box<dyn RenderResourceContext>>()
+            .unwrap()
+            .downcast_ref::<WebGL2RenderResourceContext>()
+            .unwrap()
+            .clone();
+        let mut render_context = WebGL2RenderContext::new(render_resource_context);
+        let mut node_outputs: HashMap<NodeId, ResourceSlots> = Default::default();
+        for stage in stages.iter_mut() {
+            for job in stage.jobs.iter_mut() {
+                for node_state in job.node_states.iter_mut() {
+                    // bind inputs from connected node outputs
+                    for (i, mut input_slot) in node_state.input_slots.iter_mut().enumerate() {
+                        if let Edge::SlotEdge {
+                            output_node,
+                            output_index,
+                            ..
+                        } = node_state.edges.get_input_slot_edge(i).unwrap()
+                        {
+                            let outputs = if let Some(outputs) = node_outputs.get(output_node) {
+                                outputs
+                            } else {
+                                panic!("node inputs not set")
+                            };
+
+                            let output_resource =
+                                outputs.get(*output_index).expect("output should be set");
+                            input_slot.resource = Some(output_resource);
+                        } else {
+                            panic!("no edge connected to input")
+                        }
+                    }
+                    node_state.node.update(
+                        world,
+                        resources,
+                        &mut render_context,
+                        &node_state.input_slots,
+                        &mut node_state.output_slots,
+                    );
+
+                    node_outputs.insert(node_state.id, node_state.output_slots.clone());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,830 @@
+use crate::{
+    glsl,
+    webgl2_type_converter::{
+        address_mode, compare_function, compression_extension, mag_filter, min_filter,
+        texture_format,
+    },
+    Gl, ProgramBinding, WebGL2Buffer, WebGL2Pipeline, WebGL2Resources, WebGL2SwapChain,
+    WebGL2Texture,
+};
+use bevy_asset::{Assets, Handle, HandleUntyped};
+use bevy_render::{
+    pipeline::{BindGroupDescriptorId, BindType, PipelineDescriptor, PipelineLayout},
+    renderer::{
+        BindGroup, BufferId, BufferInfo, BufferUsage, RenderResourceContext, RenderResourceId,
+        SamplerId, TextureId,
+    },
+    shader::{Shader, ShaderLayout, ShaderSource, ShaderStage, ShaderStages},
+    texture::{
+        Extent3d, SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureUsage, TextureViewDimension,
+    },
+};
+use bevy_utils::{tracing::warn, HashMap};
+use bevy_window::{Window, WindowId};
+use std::ops::Range;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, WebGlContextAttributes, WebGlProgram, WebGlShader};
+
+/// The alignment of dynamic uniform offsets. WebGL2 implementations require 256 at most.
+pub const UNIFORM_BUFFER_OFFSET_ALIGNMENT: usize = 256;
+/// The smallest maximum size of a uniform block. Storage buffers are allocated with at least this
+/// size, as the uniform blocks replacing them can be larger than their contents.
+pub const MIN_MAX_UNIFORM_BLOCK_SIZE: usize = 16384;
+
+const COMPRESSION_EXTENSIONS: [&str; 4] = [
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_compression_bptc",
+];
+
+const PRESENT_VERTEX_SHADER: &str = "#version 300 es
+out vec2 v_Uv;
+void main() {
+    v_Uv = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    gl_Position = vec4(v_Uv * 2.0 - 1.0, 0.0, 1.0);
+}
+";
+
+const PRESENT_FRAGMENT_SHADER: &str = "#version 300 es
+precision highp float;
+uniform sampler2D Frame;
+in vec2 v_Uv;
+out vec4 o_Target;
+void main() {
+    vec3 color = texture(Frame, v_Uv).rgb;
+    vec3 srgb = mix(color * 12.92, 1.055 * pow(color, vec3(1.0 / 2.4)) - 0.055, step(0.0031308, color));
+    o_Target = vec4(srgb, 1.0);
+}
+";
+
+#[derive(Clone, Debug, Default)]
+pub struct WebGL2RenderResourceContext {
+    pub resources: WebGL2Resources,
+}
+
+impl WebGL2RenderResourceContext {
+    /// Creates the WebGL2 context of a window's canvas. Only the first window can be rendered to.
+    pub fn set_window_canvas(&self, window_id: WindowId, canvas: &HtmlCanvasElement) {
+        let mut gl = self.resources.gl.write();
+        if gl.is_some() {
+            warn!(
+                "WebGL2 can only render to the first window, {:?} won't be drawn",
+                window_id
+            );
+            return;
+        }
+
+        let mut attributes = WebGlContextAttributes::new();
+        attributes.alpha(false).depth(false).antialias(false);
+        let context = canvas
+            .get_context_with_context_options("webgl2", &attributes)
+            .ok()
+            .flatten()
+            .expect("WebGL2 is not supported by this browser")
+            .dyn_into::<Gl>()
+            .unwrap();
+        // block compressed textures are used when the browser supports them
+        let mut extensions = self.resources.enabled_extensions.write();
+        for extension in COMPRESSION_EXTENSIONS.iter() {
+            if let Ok(Some(_)) = context.get_extension(extension) {
+                extensions.insert(*extension);
+            }
+        }
+        context.pixel_storei(Gl::UNPACK_ALIGNMENT, 1);
+
+        *gl = Some(context);
+        *self.resources.canvas_window.write() = Some(window_id);
+    }
+
+    pub fn copy_buffer_to_buffer(
+        &self,
+        source_buffer: BufferId,
+        source_offset: u64,
+        destination_buffer: BufferId,
+        destination_offset: u64,
+        size: u64,
+    ) {
+        let mut buffers = self.resources.buffers.write();
+        let source_range = source_offset as usize..(source_offset + size) as usize;
+        let data = buffers.get(&source_buffer).unwrap().data[source_range].to_vec();
+        let destination = buffers.get_mut(&destination_buffer).unwrap();
+        let destination_range = destination_offset as usize..(destination_offset + size) as usize;
+        destination.data[destination_range].copy_from_slice(&data);
+        if let Some(buffer) = &destination.buffer {
+            let gl = self.resources.gl();
+            gl.bind_buffer(Gl::COPY_WRITE_BUFFER, Some(buffer));
+            gl.buffer_sub_data_with_i32_and_u8_array(
+                Gl::COPY_WRITE_BUFFER,
+                destination_offset as i32,
+                &data,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_buffer_to_texture(
+        &self,
+        source_buffer: BufferId,
+        source_offset: u64,
+        source_bytes_per_row: u32,
+        destination_texture: TextureId,
+        destination_origin: [u32; 3],
+        destination_mip_level: u32,
+        size: Extent3d,
+    ) {
+        let gl = self.resources.gl();
+        let buffers = self.resources.buffers.read();
+        let textures = self.resources.textures.read();
+        let data = &buffers.get(&source_buffer).unwrap().data[source_offset as usize..];
+        let texture = textures.get(&destination_texture).unwrap();
+        let format = texture.descriptor.format;
+        let (internal_format, pixel_format, pixel_type) = texture_format(format);
+        let [x, y, z] = destination_origin;
+        gl.bind_texture(texture.target, Some(&texture.texture));
+
+        if format.is_compressed() {
+            let data = &data[..format
+                .data_size(size)
+                .map_or(data.len(), |len| len.min(data.len()))];
+            gl.compressed_tex_sub_image_2d_with_u8_array(
+                texture.target,
+                destination_mip_level as i32,
+                x as i32,
+                y as i32,
+                size.width as i32,
+                size.height as i32,
+                internal_format,
+                data,
+            );
+            return;
+        }
+
+        gl.pixel_storei(
+            Gl::UNPACK_ROW_LENGTH,
+            (source_bytes_per_row as usize / format.pixel_size()) as i32,
+        );
+        let result = if texture.target == Gl::TEXTURE_2D {
+            gl.tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_opt_u8_array(
+                texture.target,
+                destination_mip_level as i32,
+                x as i32,
+                y as i32,
+                size.width as i32,
+                size.height as i32,
+                pixel_format,
+                pixel_type,
+                Some(data),
+            )
+        } else {
+            gl.tex_sub_image_3d_with_opt_u8_array(
+                texture.target,
+                destination_mip_level as i32,
+                x as i32,
+                y as i32,
+                z as i32,
+                size.width as i32,
+                size.height as i32,
+                size.depth as i32,
+                pixel_format,
+                pixel_type,
+                Some(data),
+            )
+        };
+        gl.pixel_storei(Gl::UNPACK_ROW_LENGTH, 0);
+        result.expect("Failed to copy buffer to texture");
+    }
+
+    /// Copies the swap chain of the canvas' window to the canvas, encoding its colors to sRGB as
+    /// the canvas isn't an sRGB surface
+    pub fn present(&self) {
+        let window_id = match *self.resources.canvas_window.read() {
+            Some(window_id) => window_id,
+            None => return,
+        };
+        let swap_chain = match self.resources.swap_chains.read().get(&window_id) {
+            Some(swap_chain) => *swap_chain,
+            None => return,
+        };
+        let gl = self.resources.gl();
+        let mut present_program = self.resources.present_program.write();
+        let program = present_program.get_or_insert_with(|| {
+            let program = link_program(
+                &gl,
+                &compile_shader(&gl, Gl::VERTEX_SHADER, PRESENT_VERTEX_SHADER),
+                Some(&compile_shader(
+                    &gl,
+                    Gl::FRAGMENT_SHADER,
+                    PRESENT_FRAGMENT_SHADER,
+                )),
+            );
+            gl.use_program(Some(&program));
+            gl.uniform1i(gl.get_uniform_location(&program, "Frame").as_ref(), 0);
+            program
+        });
+
+        let textures = self.resources.textures.read();
+        let texture = match textures.get(&swap_chain.texture) {
+            Some(texture) => texture,
+            None => return,
+        };
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        gl.viewport(0, 0, gl.drawing_buffer_width(), gl.drawing_buffer_height());
+        for capability in &[
+            Gl::BLEND,
+            Gl::CULL_FACE,
+            Gl::DEPTH_TEST,
+            Gl::POLYGON_OFFSET_FILL,
+            Gl::SCISSOR_TEST,
+            Gl::STENCIL_TEST,
+        ] {
+            gl.disable(*capability);
+        }
+        gl.color_mask(true, true, true, true);
+        gl.use_program(Some(program));
+        gl.active_texture(Gl::TEXTURE0);
+        gl.bind_texture(Gl::TEXTURE_2D, Some(&texture.texture));
+        gl.bind_sampler(0, None);
+        gl.draw_arrays(Gl::TRIANGLES, 0, 3);
+    }
+
+    fn create_gl_buffer(&self, buffer_info: &BufferInfo, data: &[u8]) -> WebGL2Buffer {
+        let usage = buffer_info.buffer_usage;
+        let mut data = data.to_vec();
+        // WebGL2 checks that the bound range of a buffer covers the whole uniform block, which
+        // can be larger than the data bevy writes because of std140 padding
+        if usage.contains(BufferUsage::STORAGE) {
+            data.resize(data.len().max(MIN_MAX_UNIFORM_BLOCK_SIZE), 0);
+        }
+        if usage.intersects(BufferUsage::UNIFORM | BufferUsage::STORAGE) {
+            data.resize(align(data.len(), UNIFORM_BUFFER_OFFSET_ALIGNMENT), 0);
+        }
+
+        let target = if usage.contains(BufferUsage::INDEX) {
+            Gl::ELEMENT_ARRAY_BUFFER
+        } else {
+            Gl::ARRAY_BUFFER
+        };
+        let buffer = if usage.intersects(
+            BufferUsage::INDEX | BufferUsage::VERTEX | BufferUsage::UNIFORM | BufferUsage::STORAGE,
+        ) {
+            let gl = self.resources.gl();
+            let buffer = gl.create_buffer().expect("Failed to create buffer");
+            gl.bind_buffer(target, Some(&buffer));
+            let draw_usage = if usage.contains(BufferUsage::COPY_DST) {
+                Gl::DYNAMIC_DRAW
+            } else {
+                Gl::STATIC_DRAW
+            };
+            gl.buffer_data_with_u8_array(target, &data, draw_usage);
+            Some(buffer)
+        } else {
+            None
+        };
+
+        WebGL2Buffer {
+            buffer,
+            target,
+            data,
+        }
+    }
+}
+
+fn align(size: usize, alignment: usize) -> usize {
+    (size + alignment - 1) & !(alignment - 1)
+}
+
+fn compile_shader(gl: &Gl, shader_type: u32, source: &str) -> WebGlShader {
+    let shader = gl
+        .create_shader(shader_type)
+        .expect("Failed to create shader");
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+    if !gl
+        .get_shader_parameter(&shader, Gl::COMPILE_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        panic!(
+            "Failed to compile shader: {}\n{}",
+            gl.get_shader_info_log(&shader).unwrap_or_default(),
+            source
+        );
+    }
+    shader
+}
+
+fn link_program(gl: &Gl, vertex: &WebGlShader, fragment: Option<&WebGlShader>) -> WebGlProgram {
+    let program = gl.create_program().expect("Failed to create program");
+    gl.attach_shader(&program, vertex);
+    if let Some(fragment) = fragment {
+        gl.attach_shader(&program, fragment);
+    }
+    gl.link_program(&program);
+    if !gl
+        .get_program_parameter(&program, Gl::LINK_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        panic!(
+            "Failed to link program: {}",
+            gl.get_program_info_log(&program).unwrap_or_default()
+        );
+    }
+    program
+}
+
+fn texture_target(dimension: TextureViewDimension) -> u32 {
+    match dimension {
+        TextureViewDimension::D1 | TextureViewDimension::D2 => Gl::TEXTURE_2D,
+        TextureViewDimension::D2Array | TextureViewDimension::CubeArray => Gl::TEXTURE_2D_ARRAY,
+        TextureViewDimension::Cube => Gl::TEXTURE_CUBE_MAP,
+        TextureViewDimension::D3 => Gl::TEXTURE_3D,
+    }
+}
+
+fn glsl_source(shader: &Shader) -> &str {
+    match &shader.source {
+        ShaderSource::Glsl(source) => source,
+        ShaderSource::Spirv(_) => panic!("WebGL2 can only use GLSL shaders"),
+    }
+}
+
+impl RenderResourceContext for WebGL2RenderResourceContext {
+    fn create_swap_chain(&self, window: &Window) {
+        if let Some(swap_chain) = self.resources.swap_chains.write().remove(&window.id()) {
+            self.remove_texture(swap_chain.texture);
+        }
+
+        let width = window.scaled_width().max(1);
+        let height = window.scaled_height().max(1);
+        let texture = self.create_texture(TextureDescriptor {
+            size: Extent3d::new(width, height, 1),
+            format: TextureFormat::Rgba8UnormSrgb,
+            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+            ..Default::default()
+        });
+        {
+            let gl = self.resources.gl();
+            let textures = self.resources.textures.read();
+            gl.bind_texture(
+                Gl::TEXTURE_2D,
+                Some(&textures.get(&texture).unwrap().texture),
+            );
+            gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::NEAREST as i32);
+            gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::NEAREST as i32);
+        }
+        self.resources.swap_chains.write().insert(
+            window.id(),
+            WebGL2SwapChain {
+                texture,
+                width,
+                height,
+            },
+        );
+    }
+
+    fn next_swap_chain_texture(&self, window: &Window) -> TextureId {
+        self.resources
+            .swap_chains
+            .read()
+            .get(&window.id())
+            .expect("No swap chain found for window")
+            .texture
+    }
+
+    fn drop_swap_chain_texture(&self, _resource: TextureId) {
+        // the swap chain texture is reused every frame
+    }
+
+    fn drop_all_swap_chain_textures(&self) {}
+
+    fn create_sampler(&self, sampler_descriptor: &SamplerDescriptor) -> SamplerId {
+        let gl = self.resources.gl();
+        let sampler = gl.create_sampler().expect("Failed to create sampler");
+        let parameters = [
+            (
+                Gl::TEXTURE_WRAP_S,
+                address_mode(sampler_descriptor.address_mode_u),
+            ),
+            (
+                Gl::TEXTURE_WRAP_T,
+                address_mode(sampler_descriptor.address_mode_v),
+            ),
+            (
+                Gl::TEXTURE_WRAP_R,
+                address_mode(sampler_descriptor.address_mode_w),
+            ),
+            (
+                Gl::TEXTURE_MIN_FILTER,
+                min_filter(
+                    sampler_descriptor.min_filter,
+                    sampler_descriptor.mipmap_filter,
+                ),
+            ),
+            (
+                Gl::TEXTURE_MAG_FILTER,
+                mag_filter(sampler_descriptor.mag_filter),
+            ),
+        ];
+        for (parameter, value) in parameters.iter() {
+            gl.sampler_parameteri(&sampler, *parameter, *value as i32);
+        }
+        gl.sampler_parameterf(
+            &sampler,
+            Gl::TEXTURE_MIN_LOD,
+            sampler_descriptor.lod_min_clamp,
+        );
+        gl.sampler_parameterf(
+            &sampler,
+            Gl::TEXTURE_MAX_LOD,
+            sampler_descriptor.lod_max_clamp,
+        );
+        if let Some(compare) = sampler_descriptor.compare_function {
+            gl.sampler_parameteri(
+                &sampler,
+                Gl::TEXTURE_COMPARE_MODE,
+                Gl::COMPARE_REF_TO_TEXTURE as i32,
+            );
+            gl.sampler_parameteri(
+                &sampler,
+                Gl::TEXTURE_COMPARE_FUNC,
+                compare_function(compare) as i32,
+            );
+        }
+
+        let id = SamplerId::new();
+        self.resources.samplers.write().insert(id, sampler);
+        id
+    }
+
+    fn create_texture(&self, texture_descriptor: TextureDescriptor) -> TextureId {
+        let gl = self.resources.gl();
+        let texture = gl.create_texture().expect("Failed to create texture");
+        let size = texture_descriptor.size;
+        let target = match texture_descriptor.dimension {
+            TextureDimension::D1 | TextureDimension::D2 if size.depth <= 1 => Gl::TEXTURE_2D,
+            TextureDimension::D1 | TextureDimension::D2 => Gl::TEXTURE_2D_ARRAY,
+            TextureDimension::D3 => Gl::TEXTURE_3D,
+        };
+        let (internal_format, _, _) = texture_format(texture_descriptor.format);
+        gl.bind_texture(target, Some(&texture));
+        // multisampled textures are rendered to directly, passes don't resolve them
+        if target == Gl::TEXTURE_2D {
+            gl.tex_storage_2d(
+                target,
+                texture_descriptor.mip_level_count as i32,
+                internal_format,
+                size.width as i32,
+                size.height as i32,
+            );
+        } else {
+            gl.tex_storage_3d(
+                target,
+                texture_descriptor.mip_level_count as i32,
+                internal_format,
+                size.width as i32,
+                size.height as i32,
+                size.depth as i32,
+            );
+        }
+
+        let id = TextureId::new();
+        self.resources.textures.write().insert(
+            id,
+            WebGL2Texture {
+                texture,
+                target,
+                descriptor: texture_descriptor,
+            },
+        );
+        id
+    }
+
+    fn create_buffer(&self, buffer_info: BufferInfo) -> BufferId {
+        let data = vec![0; buffer_info.size];
+        self.create_buffer_with_data(buffer_info, &data)
+    }
+
+    fn write_mapped_buffer(
+        &self,
+        id: BufferId,
+        range: Range<u64>,
+        write: &mut dyn FnMut(&mut [u8], &dyn RenderResourceContext),
+    ) {
+        // the data is taken out of the buffer so that `write` can use this context
+        let mut data =
+            std::mem::take(&mut self.resources.buffers.write().get_mut(&id).unwrap().data);
+        write(&mut data[range.start as usize..range.end as usize], self);
+        self.resources.buffers.write().get_mut(&id).unwrap().data = data;
+    }
+
+    fn map_buffer(&self, _id: BufferId) {}
+
+    fn unmap_buffer(&self, id: BufferId) {
+        let buffers = self.resources.buffers.read();
+        let buffer = buffers.get(&id).unwrap();
+        if let Some(gl_buffer) = &buffer.buffer {
+            let gl = self.resources.gl();
+            gl.bind_buffer(Gl::COPY_WRITE_BUFFER, Some(gl_buffer));
+            gl.buffer_sub_data_with_i32_and_u8_array(Gl::COPY_WRITE_BUFFER, 0, &buffer.data);
+        }
+    }
+
+    fn create_buffer_with_data(&self, buffer_info: BufferInfo, data: &[u8]) -> BufferId {
+        let buffer = self.create_gl_buffer(&buffer_info, data);
+        let id = BufferId::new();
+        self.resources.buffer_infos.write().insert(id, buffer_info);
+        self.resources.buffers.write().insert(id, buffer);
+        id
+    }
+
+    fn create_shader_module(&self, shader_handle: &Handle<Shader>, shaders: &Assets<Shader>) {
+        if self.resources.shaders.read().contains_key(shader_handle) {
+            return;
+        }
+        let shader = shaders.get(shader_handle).unwrap();
+        self.create_shader_module_from_source(shader_handle, shader);
+    }
+
+    fn create_shader_module_from_source(&self, shader_handle: &Handle<Shader>, shader: &Shader) {
+        let gl = self.resources.gl();
+        let shader_type = match shader.stage {
+            ShaderStage::Vertex => Gl::VERTEX_SHADER,
+            ShaderStage::Fragment => Gl::FRAGMENT_SHADER,
+            ShaderStage::Compute => panic!("WebGL2 doesn't support compute shaders"),
+        };
+        let source = glsl::to_glsl_es(glsl_source(shader), shader.stage);
+        let gl_shader = compile_shader(&gl, shader_type, &source);
+        self.resources
+            .shaders
+            .write()
+            .insert(shader_handle.clone_weak(), gl_shader);
+    }
+
+    fn get_specialized_shader(&self, shader: &Shader, macros: Option<&[String]>) -> Shader {
+        Shader {
+            source: ShaderSource::Glsl(glsl::preprocess(
+                glsl_source(shader),
+                macros.unwrap_or(&[]),
+            )),
+            ..*shader
+        }
+    }
+
+    fn remove_buffer(&self, buffer: BufferId) {
+        if let Some(buffer) = self.resources.buffers.write().remove(&buffer) {
+            if let Some(gl_buffer) = buffer.buffer {
+                self.resources.gl().delete_buffer(Some(&gl_buffer));
+            }
+        }
+        self.resources.buffer_infos.write().remove(&buffer);
+    }
+
+    fn remove_texture(&self, texture: TextureId) {
+        let gl = self.resources.gl();
+        if let Some(texture) = self.resources.textures.write().remove(&texture) {
+            gl.delete_texture(Some(&texture.texture));
+        }
+        self.resources
+            .framebuffers
+            .write()
+            .retain(|attachments, framebuffer| {
+                let retain = !attachments.contains(&texture);
+                if !retain {
+                    gl.delete_framebuffer(Some(framebuffer));
+                }
+                retain
+            });
+    }
+
+    fn remove_sampler(&self, sampler: SamplerId) {
+        if let Some(sampler) = self.resources.samplers.write().remove(&sampler) {
+            self.resources.gl().delete_sampler(Some(&sampler));
+        }
+    }
+
+    fn get_buffer_info(&self, buffer: BufferId) -> Option<BufferInfo> {
+        self.resources.buffer_infos.read().get(&buffer).cloned()
+    }
+
+    fn get_aligned_uniform_size(&self, size: usize, dynamic: bool) -> usize {
+        if dynamic {
+            align(size, UNIFORM_BUFFER_OFFSET_ALIGNMENT)
+        } else {
+            size
+        }
+    }
+
+    fn get_aligned_texture_size(&self, data_size: usize) -> usize {
+        data_size
+    }
+
+    fn supports_texture_format(&self, format: TextureFormat) -> bool {
+        match compression_extension(format) {
+            Some(extension) => self.resources.enabled_extensions.read().contains(extension),
+            None => true,
+        }
+    }
+
+    fn set_asset_resource_untyped(
+        &self,
+        handle: HandleUntyped,
+        render_resource: RenderResourceId,
+        index: u64,
+    ) {
+        let mut asset_resources = self.resources.asset_resources.write();
+        asset_resources.insert((handle, index), render_resource);
+    }
+
+    fn get_asset_resource_untyped(
+        &self,
+        handle: HandleUntyped,
+        index: u64,
+    ) -> Option<RenderResourceId> {
+        let asset_resources = self.resources.asset_resources.read();
+        asset_resources.get(&(handle, index)).cloned()
+    }
+
+    fn remove_asset_resource_untyped(&self, handle: HandleUntyped, index: u64) {
+        let mut asset_resources = self.resources.asset_resources.write();
+        asset_resources.remove(&(handle, index));
+    }
+
+    fn create_render_pipeline(
+        &self,
+        pipeline_handle: Handle<PipelineDescriptor>,
+        pipeline_descriptor: &PipelineDescriptor,
+        shaders: &Assets<Shader>,
+    ) {
+        if self
+            .resources
+            .pipelines
+            .read()
+            .contains_key(&pipeline_handle)
+        {
+            return;
+        }
+
+        let shader_stages = &pipeline_descriptor.shader_stages;
+        self.create_shader_module(&shader_stages.vertex, shaders);
+        if let Some(fragment_handle) = &shader_stages.fragment {
+            self.create_shader_module(fragment_handle, shaders);
+        }
+
+        let gl = self.resources.gl();
+        let program = {
+            let gl_shaders = self.resources.shaders.read();
+            link_program(
+                &gl,
+                gl_shaders.get(&shader_stages.vertex).unwrap(),
+                shader_stages
+                    .fragment
+                    .as_ref()
+                    .map(|fragment_handle| gl_shaders.get(fragment_handle).unwrap()),
+            )
+        };
+
+        // WebGL2 has no bind groups, so every binding of the layout gets its own uniform buffer
+        // binding point or texture unit in the program
+        let layout = pipeline_descriptor.get_layout().unwrap();
+        let mut bindings = HashMap::default();
+        let mut texture_units = HashMap::default();
+        let mut next_binding_point = 0;
+        let mut next_unit = 0;
+        gl.use_program(Some(&program));
+        for bind_group in layout.bind_groups.iter() {
+            self.resources
+                .bind_group_descriptors
+                .write()
+                .insert(bind_group.id);
+            for binding in bind_group.bindings.iter() {
+                match &binding.bind_type {
+                    BindType::Uniform { .. } | BindType::StorageBuffer { .. } => {
+                        let index = gl.get_uniform_block_index(&program, &binding.name);
+                        if index == Gl::INVALID_INDEX {
+                            continue;
+                        }
+                        gl.uniform_block_binding(&program, index, next_binding_point);
+                        let size = gl
+                            .get_active_uniform_block_parameter(
+                                &program,
+                                index,
+                                Gl::UNIFORM_BLOCK_DATA_SIZE,
+                            )
+                            .ok()
+                            .and_then(|size| size.as_f64())
+                            .unwrap_or(0.0);
+                        bindings.insert(
+                            (bind_group.index, binding.index),
+                            ProgramBinding::UniformBlock {
+                                binding_point: next_binding_point,
+                                size: size as i32,
+                            },
+                        );
+                        next_binding_point += 1;
+                    }
+                    BindType::SampledTexture { dimension, .. } => {
+                        let location = match gl.get_uniform_location(&program, &binding.name) {
+                            Some(location) => location,
+                            None => continue,
+                        };
+                        gl.uniform1i(Some(&location), next_unit as i32);
+                        bindings.insert(
+                            (bind_group.index, binding.index),
+                            ProgramBinding::Texture {
+                                unit: next_unit,
+                                target: texture_target(*dimension),
+                            },
+                        );
+                        texture_units.insert(binding.name.as_str(), next_unit);
+                        next_unit += 1;
+                    }
+                    BindType::Sampler { .. } => {}
+                    BindType::StorageTexture { .. } => {
+                        warn!(
+                            "WebGL2 doesn't support storage textures, {} is ignored",
+                            binding.name
+                        );
+                    }
+                }
+            }
+        }
+
+        // samplers are merged into their textures, they follow the "{texture}_sampler" convention
+        for bind_group in layout.bind_groups.iter() {
+            for binding in bind_group.bindings.iter() {
+                if let BindType::Sampler { .. } = binding.bind_type {
+                    if let Some(unit) = binding
+                        .name
+                        .strip_suffix("_sampler")
+                        .and_then(|texture| texture_units.get(texture))
+                    {
+                        bindings.insert(
+                            (bind_group.index, binding.index),
+                            ProgramBinding::Sampler { unit: *unit },
+                        );
+                    }
+                }
+            }
+        }
+
+        self.resources.pipelines.write().insert(
+            pipeline_handle,
+            WebGL2Pipeline {
+                program,
+                descriptor: pipeline_descriptor.clone(),
+                bindings,
+            },
+        );
+    }
+
+    fn bind_group_descriptor_exists(
+        &self,
+        bind_group_descriptor_id: BindGroupDescriptorId,
+    ) -> bool {
+        self.resources
+            .bind_group_descriptors
+            .read()
+            .contains(&bind_group_descriptor_id)
+    }
+
+    fn create_bind_group(
+        &self,
+        _bind_group_descriptor_id: BindGroupDescriptorId,
+        bind_group: &BindGroup,
+    ) {
+        if !self.resources.has_bind_group(bind_group.id) {
+            self.resources
+                .bind_groups
+                .write()
+                .insert(bind_group.id, bind_group.clone());
+        }
+        self.resources.mark_bind_group_used(bind_group.id);
+    }
+
+    fn clear_bind_groups(&self) {
+        self.resources.bind_groups.write().clear();
+    }
+
+    fn remove_stale_bind_groups(&self) {
+        self.resources.remove_stale_bind_groups();
+    }
+
+    fn reflect_pipeline_layout(
+        &self,
+        shaders: &Assets<Shader>,
+        shader_stages: &ShaderStages,
+        enforce_bevy_conventions: bool,
+    ) -> PipelineLayout {
+        let mut shader_layouts: Vec<ShaderLayout> = shader_stages
+            .iter()
+            .map(|handle| {
+                let shader = shaders.get(&handle).unwrap();
+                glsl::reflect_layout(glsl_source(shader), shader.stage, enforce_bevy_conventions)
+            })
+            .collect();
+        PipelineLayout::from_shader_layouts(&mut shader_layouts)
+    }
+}
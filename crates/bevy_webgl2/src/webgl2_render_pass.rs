@@ -0,0 +1,421 @@
+use crate::{
+    renderer::WebGL2RenderContext,
+    webgl2_type_converter::{
+        blend_factor, blend_operation, compare_function, index_format, primitive_mode,
+        stencil_operation, vertex_format,
+    },
+    Gl, ProgramBinding, WebGL2Resources,
+};
+use bevy_asset::Handle;
+use bevy_render::{
+    pass::RenderPass,
+    pipeline::{
+        BindGroupDescriptorId, BindType, BlendDescriptor, ColorWrite, CullMode, FrontFace,
+        InputStepMode, PipelineDescriptor, StencilStateFaceDescriptor,
+    },
+    renderer::{BindGroupId, BufferId, RenderContext, RenderResourceBinding},
+};
+use bevy_utils::{tracing::trace, HashMap};
+use std::ops::Range;
+
+/// Records the state that WebGL2 only applies when drawing, like vertex buffers, and applies
+/// the rest immediately
+#[derive(Debug)]
+pub struct WebGL2RenderPass<'a> {
+    pub render_context: &'a WebGL2RenderContext,
+    pub gl: Gl,
+    /// The height of the attachments, used to flip viewports and scissor rects as WebGL2's origin
+    /// is the bottom left corner
+    pub target_height: u32,
+    pub pipeline: Option<Handle<PipelineDescriptor>>,
+    pub vertex_buffers: HashMap<u32, (BufferId, u64)>,
+    pub index_buffer: Option<(BufferId, u64)>,
+    pub enabled_attributes: Vec<u32>,
+    pub stencil_reference: u32,
+}
+
+impl<'a> WebGL2RenderPass<'a> {
+    pub fn new(render_context: &'a WebGL2RenderContext, gl: Gl, target_height: u32) -> Self {
+        WebGL2RenderPass {
+            render_context,
+            gl,
+            target_height,
+            pipeline: None,
+            vertex_buffers: Default::default(),
+            index_buffer: None,
+            enabled_attributes: Vec::new(),
+            stencil_reference: 0,
+        }
+    }
+
+    fn resources(&self) -> &WebGL2Resources {
+        &self.render_context.render_resource_context.resources
+    }
+
+    /// Disables the vertex attributes so that the next pass starts from a clean state
+    pub fn finish(&mut self) {
+        for location in self.enabled_attributes.drain(..) {
+            self.gl.disable_vertex_attrib_array(location);
+        }
+    }
+
+    fn set_stencil_state(&self, pipeline_descriptor: &PipelineDescriptor) {
+        let gl = &self.gl;
+        let stencil = match &pipeline_descriptor.depth_stencil_state {
+            Some(depth_stencil_state) => &depth_stencil_state.stencil,
+            None => {
+                gl.disable(Gl::STENCIL_TEST);
+                return;
+            }
+        };
+        if stencil.front == StencilStateFaceDescriptor::IGNORE
+            && stencil.back == StencilStateFaceDescriptor::IGNORE
+        {
+            gl.disable(Gl::STENCIL_TEST);
+            return;
+        }
+
+        gl.enable(Gl::STENCIL_TEST);
+        for (face, state) in &[(Gl::FRONT, &stencil.front), (Gl::BACK, &stencil.back)] {
+            gl.stencil_func_separate(
+                *face,
+                compare_function(state.compare),
+                self.stencil_reference as i32,
+                stencil.read_mask,
+            );
+            gl.stencil_op_separate(
+                *face,
+                stencil_operation(state.fail_op),
+                stencil_operation(state.depth_fail_op),
+                stencil_operation(state.pass_op),
+            );
+        }
+        gl.stencil_mask(stencil.write_mask);
+    }
+
+    fn set_pipeline_state(&self, pipeline_descriptor: &PipelineDescriptor) {
+        let gl = &self.gl;
+        let rasterization_state = pipeline_descriptor
+            .rasterization_state
+            .clone()
+            .unwrap_or_default();
+        match rasterization_state.cull_mode {
+            CullMode::None => gl.disable(Gl::CULL_FACE),
+            CullMode::Front => {
+                gl.enable(Gl::CULL_FACE);
+                gl.cull_face(Gl::FRONT);
+            }
+            CullMode::Back => {
+                gl.enable(Gl::CULL_FACE);
+                gl.cull_face(Gl::BACK);
+            }
+        }
+        gl.front_face(match rasterization_state.front_face {
+            FrontFace::Ccw => Gl::CCW,
+            FrontFace::Cw => Gl::CW,
+        });
+        if rasterization_state.depth_bias != 0 || rasterization_state.depth_bias_slope_scale != 0.0
+        {
+            gl.enable(Gl::POLYGON_OFFSET_FILL);
+            gl.polygon_offset(
+                rasterization_state.depth_bias_slope_scale,
+                rasterization_state.depth_bias as f32,
+            );
+        } else {
+            gl.disable(Gl::POLYGON_OFFSET_FILL);
+        }
+
+        if let Some(depth_stencil_state) = &pipeline_descriptor.depth_stencil_state {
+            gl.enable(Gl::DEPTH_TEST);
+            gl.depth_func(compare_function(depth_stencil_state.depth_compare));
+            gl.depth_mask(depth_stencil_state.depth_write_enabled);
+        } else {
+            gl.disable(Gl::DEPTH_TEST);
+        }
+        self.set_stencil_state(pipeline_descriptor);
+
+        // WebGL2 can't blend draw buffers differently, the first color state is used for all of them
+        if let Some(color_state) = pipeline_descriptor.color_states.first() {
+            if color_state.color_blend == BlendDescriptor::REPLACE
+                && color_state.alpha_blend == BlendDescriptor::REPLACE
+            {
+                gl.disable(Gl::BLEND);
+            } else {
+                gl.enable(Gl::BLEND);
+                gl.blend_equation_separate(
+                    blend_operation(color_state.color_blend.operation),
+                    blend_operation(color_state.alpha_blend.operation),
+                );
+                gl.blend_func_separate(
+                    blend_factor(color_state.color_blend.src_factor),
+                    blend_factor(color_state.color_blend.dst_factor),
+                    blend_factor(color_state.alpha_blend.src_factor),
+                    blend_factor(color_state.alpha_blend.dst_factor),
+                );
+            }
+            let write_mask = color_state.write_mask;
+            gl.color_mask(
+                write_mask.contains(ColorWrite::RED),
+                write_mask.contains(ColorWrite::GREEN),
+                write_mask.contains(ColorWrite::BLUE),
+                write_mask.contains(ColorWrite::ALPHA),
+            );
+        }
+    }
+
+    /// Points the vertex attributes of the current pipeline to the vertex buffers. WebGL2 has no
+    /// base vertex or first instance, so the attributes are offset instead.
+    fn set_vertex_attributes(&mut self, first_vertex: i64, first_instance: i64) -> Option<u32> {
+        let resources = self.resources().clone();
+        let pipelines = resources.pipelines.read();
+        let pipeline = pipelines.get(self.pipeline.as_ref()?)?;
+        let buffers = resources.buffers.read();
+        let layout = pipeline.descriptor.get_layout().unwrap();
+        let gl = &self.gl;
+
+        let mut enabled_attributes = Vec::new();
+        for (slot, vertex_buffer_descriptor) in layout.vertex_buffer_descriptors.iter().enumerate()
+        {
+            let (buffer_id, buffer_offset) = match self.vertex_buffers.get(&(slot as u32)) {
+                Some(vertex_buffer) => *vertex_buffer,
+                None => continue,
+            };
+            let buffer = buffers.get(&buffer_id).unwrap();
+            gl.bind_buffer(Gl::ARRAY_BUFFER, buffer.buffer.as_ref());
+            let stride = vertex_buffer_descriptor.stride as i64;
+            let (first, divisor) = match vertex_buffer_descriptor.step_mode {
+                InputStepMode::Vertex => (first_vertex, 0),
+                InputStepMode::Instance => (first_instance, 1),
+            };
+            for attribute in vertex_buffer_descriptor.attributes.iter() {
+                let location = attribute.shader_location;
+                let offset = (buffer_offset + attribute.offset) as i64 + first * stride;
+                let (size, data_type, normalized, integer) = vertex_format(attribute.format);
+                gl.enable_vertex_attrib_array(location);
+                if integer {
+                    gl.vertex_attrib_i_pointer_with_i32(
+                        location,
+                        size,
+                        data_type,
+                        stride as i32,
+                        offset as i32,
+                    );
+                } else {
+                    gl.vertex_attrib_pointer_with_i32(
+                        location,
+                        size,
+                        data_type,
+                        normalized,
+                        stride as i32,
+                        offset as i32,
+                    );
+                }
+                gl.vertex_attrib_divisor(location, divisor);
+                enabled_attributes.push(location);
+            }
+        }
+
+        for location in self.enabled_attributes.iter() {
+            if !enabled_attributes.contains(location) {
+                gl.disable_vertex_attrib_array(*location);
+            }
+        }
+        self.enabled_attributes = enabled_attributes;
+        Some(primitive_mode(pipeline.descriptor.primitive_topology))
+    }
+}
+
+impl<'a> RenderPass for WebGL2RenderPass<'a> {
+    fn get_render_context(&self) -> &dyn RenderContext {
+        self.render_context
+    }
+
+    fn set_vertex_buffer(&mut self, start_slot: u32, buffer_id: BufferId, offset: u64) {
+        self.vertex_buffers.insert(start_slot, (buffer_id, offset));
+    }
+
+    fn set_viewport(&mut self, x: f32, y: f32, w: f32, h: f32, min_depth: f32, max_depth: f32) {
+        let y = self.target_height as f32 - y - h;
+        self.gl.viewport(x as i32, y as i32, w as i32, h as i32);
+        self.gl.depth_range(min_depth, max_depth);
+    }
+
+    fn set_scissor_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        let y = self.target_height as i32 - y as i32 - h as i32;
+        self.gl.enable(Gl::SCISSOR_TEST);
+        self.gl.scissor(x as i32, y, w as i32, h as i32);
+    }
+
+    fn set_stencil_reference(&mut self, reference: u32) {
+        self.stencil_reference = reference;
+        let resources = self.resources().clone();
+        let pipelines = resources.pipelines.read();
+        if let Some(pipeline) = self
+            .pipeline
+            .as_ref()
+            .and_then(|handle| pipelines.get(handle))
+        {
+            self.set_stencil_state(&pipeline.descriptor);
+        }
+    }
+
+    fn set_index_buffer(&mut self, buffer_id: BufferId, offset: u64) {
+        self.index_buffer = Some((buffer_id, offset));
+    }
+
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        let mode = match self.set_vertex_attributes(base_vertex as i64, instances.start as i64) {
+            Some(mode) => mode,
+            None => return,
+        };
+        let (buffer_id, buffer_offset) = match self.index_buffer {
+            Some(index_buffer) => index_buffer,
+            None => return,
+        };
+        let resources = self.resources().clone();
+        let (index_type, index_size) = {
+            let pipelines = resources.pipelines.read();
+            let pipeline = pipelines.get(self.pipeline.as_ref().unwrap()).unwrap();
+            index_format(pipeline.descriptor.index_format)
+        };
+        let buffers = resources.buffers.read();
+        self.gl.bind_buffer(
+            Gl::ELEMENT_ARRAY_BUFFER,
+            buffers.get(&buffer_id).unwrap().buffer.as_ref(),
+        );
+        self.gl.draw_elements_instanced_with_i32(
+            mode,
+            (indices.end - indices.start) as i32,
+            index_type,
+            buffer_offset as i32 + indices.start as i32 * index_size,
+            (instances.end - instances.start) as i32,
+        );
+    }
+
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        if let Some(mode) = self.set_vertex_attributes(0, instances.start as i64) {
+            self.gl.draw_arrays_instanced(
+                mode,
+                vertices.start as i32,
+                (vertices.end - vertices.start) as i32,
+                (instances.end - instances.start) as i32,
+            );
+        }
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group_descriptor_id: BindGroupDescriptorId,
+        bind_group: BindGroupId,
+        dynamic_uniform_indices: Option<&[u32]>,
+    ) {
+        let resources = self.resources().clone();
+        let bind_groups = resources.bind_groups.read();
+        let pipelines = resources.pipelines.read();
+        let (bind_group_info, pipeline) = match (
+            bind_groups.get(&bind_group),
+            self.pipeline
+                .as_ref()
+                .and_then(|handle| pipelines.get(handle)),
+        ) {
+            (Some(bind_group_info), Some(pipeline)) => (bind_group_info, pipeline),
+            _ => return,
+        };
+        let bind_group_descriptor = match pipeline
+            .descriptor
+            .get_layout()
+            .unwrap()
+            .get_bind_group(index)
+        {
+            Some(descriptor) if descriptor.id == bind_group_descriptor_id => descriptor,
+            _ => return,
+        };
+        trace!(
+            "set bind group {:?} {:?}: {:?}",
+            bind_group_descriptor_id,
+            dynamic_uniform_indices,
+            bind_group
+        );
+
+        // dynamic offsets are given in the order of the dynamic bindings of the layout
+        let mut dynamic_offsets = dynamic_uniform_indices.unwrap_or(&[]).iter();
+        let dynamic_offsets = bind_group_descriptor
+            .bindings
+            .iter()
+            .filter(|binding| match binding.bind_type {
+                BindType::Uniform { dynamic, .. } | BindType::StorageBuffer { dynamic, .. } => {
+                    dynamic
+                }
+                _ => false,
+            })
+            .filter_map(|binding| Some((binding.index, *dynamic_offsets.next()? as u64)))
+            .collect::<HashMap<_, _>>();
+
+        let buffers = resources.buffers.read();
+        let textures = resources.textures.read();
+        let samplers = resources.samplers.read();
+        let gl = &self.gl;
+        for indexed_binding in bind_group_info.indexed_bindings.iter() {
+            let program_binding = match pipeline.bindings.get(&(index, indexed_binding.index)) {
+                Some(program_binding) => *program_binding,
+                None => continue,
+            };
+            match (program_binding, &indexed_binding.entry) {
+                (
+                    ProgramBinding::UniformBlock {
+                        binding_point,
+                        size,
+                    },
+                    RenderResourceBinding::Buffer { buffer, range, .. },
+                ) => {
+                    let buffer = buffers.get(buffer).unwrap();
+                    let offset = range.start
+                        + dynamic_offsets
+                            .get(&indexed_binding.index)
+                            .cloned()
+                            .unwrap_or(0);
+                    // the bound range has to cover the whole block, which can be padded
+                    let length = ((range.end - range.start) as i64)
+                        .max(size as i64)
+                        .min(buffer.data.len() as i64 - offset as i64);
+                    gl.bind_buffer_range_with_i32_and_i32(
+                        Gl::UNIFORM_BUFFER,
+                        binding_point,
+                        buffer.buffer.as_ref(),
+                        offset as i32,
+                        length as i32,
+                    );
+                }
+                (
+                    ProgramBinding::Texture { unit, target },
+                    RenderResourceBinding::Texture(texture),
+                ) => {
+                    gl.active_texture(Gl::TEXTURE0 + unit);
+                    gl.bind_texture(
+                        target,
+                        textures.get(texture).map(|texture| &texture.texture),
+                    );
+                }
+                (ProgramBinding::Sampler { unit }, RenderResourceBinding::Sampler(sampler)) => {
+                    gl.bind_sampler(unit, samplers.get(sampler));
+                }
+                _ => {}
+            }
+        }
+        resources.mark_bind_group_used(bind_group);
+    }
+
+    fn set_pipeline(&mut self, pipeline_handle: &Handle<PipelineDescriptor>) {
+        let resources = self.resources().clone();
+        let pipelines = resources.pipelines.read();
+        let pipeline = pipelines.get(pipeline_handle).expect(
+            "Attempted to use a pipeline that does not exist in this RenderPass's RenderContext",
+        );
+        self.gl.use_program(Some(&pipeline.program));
+        self.set_pipeline_state(&pipeline.descriptor);
+        self.pipeline = Some(pipeline_handle.clone_weak());
+        self.vertex_buffers.clear();
+        self.index_buffer = None;
+    }
+}
@@ -0,0 +1,60 @@
+use crate::renderer::{WebGL2RenderGraphExecutor, WebGL2RenderResourceContext};
+use bevy_app::prelude::*;
+use bevy_ecs::{Local, Res, Resources, World};
+use bevy_render::{
+    render_graph::{DependentNodeStager, RenderGraph, RenderGraphStager},
+    renderer::RenderResourceContext,
+};
+use bevy_window::WindowCreated;
+use bevy_winit::WinitWindows;
+
+#[derive(Debug, Default)]
+pub struct WebGL2Renderer;
+
+impl WebGL2Renderer {
+    pub fn run_graph(&mut self, world: &mut World, resources: &mut Resources) {
+        let mut render_graph = resources.get_mut::<RenderGraph>().unwrap();
+        // stage nodes
+        let mut stager = DependentNodeStager::loose_grouping();
+        let stages = stager.get_stages(&render_graph).unwrap();
+        let mut borrowed = stages.borrow(&mut render_graph);
+
+        // execute stages
+        let graph_executor = WebGL2RenderGraphExecutor;
+        graph_executor.execute(world, resources, &mut borrowed);
+    }
+
+    pub fn update(&mut self, world: &mut World, resources: &mut Resources) {
+        self.run_graph(world, resources);
+
+        let render_resource_context = resources.get::<Box<dyn RenderResourceContext>>().unwrap();
+        if let Some(render_resource_context) =
+            render_resource_context.downcast_ref::<WebGL2RenderResourceContext>()
+        {
+            render_resource_context.present();
+        }
+        render_resource_context.drop_all_swap_chain_textures();
+        render_resource_context.remove_stale_bind_groups();
+    }
+}
+
+/// Creates the WebGL2 context from the canvas of the first window. This runs before the other
+/// systems of the frame, as they can create render resources.
+pub fn webgl2_window_system(
+    mut window_created_event_reader: Local<EventReader<WindowCreated>>,
+    window_created_events: Res<Events<WindowCreated>>,
+    winit_windows: Res<WinitWindows>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+) {
+    use winit::platform::web::WindowExtWebSys;
+
+    let render_resource_context = render_resource_context
+        .downcast_ref::<WebGL2RenderResourceContext>()
+        .unwrap();
+    for window_created_event in window_created_event_reader.iter(&window_created_events) {
+        let winit_window = winit_windows
+            .get_window(window_created_event.id)
+            .expect("Received window created event for non-existent window");
+        render_resource_context.set_window_canvas(window_created_event.id, &winit_window.canvas());
+    }
+}
@@ -0,0 +1,122 @@
+use bevy_asset::{Handle, HandleUntyped};
+use bevy_render::{
+    pipeline::{BindGroupDescriptorId, PipelineDescriptor},
+    renderer::{
+        BindGroup, BindGroupId, BufferId, BufferInfo, RenderResourceId, SamplerId, TextureId,
+    },
+    shader::Shader,
+    texture::TextureDescriptor,
+};
+use bevy_utils::{HashMap, HashSet};
+use bevy_window::WindowId;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use web_sys::{
+    WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlSampler, WebGlShader,
+    WebGlTexture,
+};
+
+pub type Gl = WebGl2RenderingContext;
+
+/// A buffer together with a copy of its contents. WebGL2 can't map buffers, so writes go to the
+/// copy and are uploaded when the buffer is unmapped.
+#[derive(Debug)]
+pub struct WebGL2Buffer {
+    /// Buffers that are only used for copies don't need to exist on the GPU
+    pub buffer: Option<WebGlBuffer>,
+    /// The target the buffer is bound to. WebGL2 doesn't allow index buffers to be bound to other
+    /// targets.
+    pub target: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct WebGL2Texture {
+    pub texture: WebGlTexture,
+    pub target: u32,
+    pub descriptor: TextureDescriptor,
+}
+
+/// Where a binding of a pipeline layout is bound in a linked program
+#[derive(Debug, Clone, Copy)]
+pub enum ProgramBinding {
+    UniformBlock { binding_point: u32, size: i32 },
+    Texture { unit: u32, target: u32 },
+    Sampler { unit: u32 },
+}
+
+#[derive(Debug)]
+pub struct WebGL2Pipeline {
+    pub program: WebGlProgram,
+    pub descriptor: PipelineDescriptor,
+    /// The [ProgramBinding] of each `(bind group index, binding index)` of the pipeline layout
+    pub bindings: HashMap<(u32, u32), ProgramBinding>,
+}
+
+/// The offscreen texture a window is rendered to. It is copied to the canvas when the frame ends,
+/// encoding its colors to sRGB.
+#[derive(Debug, Clone, Copy)]
+pub struct WebGL2SwapChain {
+    pub texture: TextureId,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct WebGL2Resources {
+    pub gl: Arc<RwLock<Option<Gl>>>,
+    pub buffer_infos: Arc<RwLock<HashMap<BufferId, BufferInfo>>>,
+    pub buffers: Arc<RwLock<HashMap<BufferId, WebGL2Buffer>>>,
+    pub textures: Arc<RwLock<HashMap<TextureId, WebGL2Texture>>>,
+    pub samplers: Arc<RwLock<HashMap<SamplerId, WebGlSampler>>>,
+    pub shaders: Arc<RwLock<HashMap<Handle<Shader>, WebGlShader>>>,
+    pub pipelines: Arc<RwLock<HashMap<Handle<PipelineDescriptor>, WebGL2Pipeline>>>,
+    pub bind_group_descriptors: Arc<RwLock<HashSet<BindGroupDescriptorId>>>,
+    pub bind_groups: Arc<RwLock<HashMap<BindGroupId, BindGroup>>>,
+    pub bind_group_usage_counts: Arc<RwLock<HashMap<BindGroupId, u64>>>,
+    pub asset_resources: Arc<RwLock<HashMap<(HandleUntyped, u64), RenderResourceId>>>,
+    pub swap_chains: Arc<RwLock<HashMap<WindowId, WebGL2SwapChain>>>,
+    /// The window whose canvas the context belongs to
+    pub canvas_window: Arc<RwLock<Option<WindowId>>>,
+    /// Framebuffers for the color and depth attachments of passes
+    pub framebuffers: Arc<RwLock<HashMap<Vec<TextureId>, WebGlFramebuffer>>>,
+    pub present_program: Arc<RwLock<Option<WebGlProgram>>>,
+    /// The texture compression extensions the browser supports, enabled with the context
+    pub enabled_extensions: Arc<RwLock<HashSet<&'static str>>>,
+}
+
+impl WebGL2Resources {
+    /// Returns the context of the canvas, which is created with the first window
+    pub fn gl(&self) -> Gl {
+        self.gl
+            .read()
+            .clone()
+            .expect("The WebGL2 context is only available once a window has been created")
+    }
+
+    pub fn has_bind_group(&self, bind_group_id: BindGroupId) -> bool {
+        self.bind_groups.read().contains_key(&bind_group_id)
+    }
+
+    pub fn mark_bind_group_used(&self, bind_group_id: BindGroupId) {
+        // free every two frames
+        self.bind_group_usage_counts
+            .write()
+            .insert(bind_group_id, 2);
+    }
+
+    pub fn remove_stale_bind_groups(&self) {
+        let mut bind_group_usage_counts = self.bind_group_usage_counts.write();
+        bind_group_usage_counts.retain(|_, count| {
+            *count -= 1;
+            *count > 0
+        });
+        self.bind_groups
+            .write()
+            .retain(|id, _| bind_group_usage_counts.contains_key(id));
+    }
+}
+
+// WARNING: this only works under the assumption that wasm runtime is single threaded
+unsafe impl Send for WebGL2Resources {}
+unsafe impl Sync for WebGL2Resources {}
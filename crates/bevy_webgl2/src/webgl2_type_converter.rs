@@ -0,0 +1,252 @@
+use crate::Gl;
+use bevy_render::{
+    pipeline::{
+        BlendFactor, BlendOperation, CompareFunction, IndexFormat, PrimitiveTopology,
+        StencilOperation, VertexFormat,
+    },
+    texture::{AddressMode, FilterMode, TextureFormat},
+};
+
+// from the WEBGL_compressed_texture_s3tc and WEBGL_compressed_texture_s3tc_srgb extensions
+const COMPRESSED_RGBA_S3TC_DXT1_EXT: u32 = 0x83F1;
+const COMPRESSED_RGBA_S3TC_DXT3_EXT: u32 = 0x83F2;
+const COMPRESSED_RGBA_S3TC_DXT5_EXT: u32 = 0x83F3;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT: u32 = 0x8C4D;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT: u32 = 0x8C4E;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT: u32 = 0x8C4F;
+// from the EXT_texture_compression_rgtc extension
+const COMPRESSED_RED_RGTC1_EXT: u32 = 0x8DBB;
+const COMPRESSED_SIGNED_RED_RGTC1_EXT: u32 = 0x8DBC;
+const COMPRESSED_RED_GREEN_RGTC2_EXT: u32 = 0x8DBD;
+const COMPRESSED_SIGNED_RED_GREEN_RGTC2_EXT: u32 = 0x8DBE;
+// from the EXT_texture_compression_bptc extension
+const COMPRESSED_RGBA_BPTC_UNORM_EXT: u32 = 0x8E8C;
+const COMPRESSED_SRGB_ALPHA_BPTC_UNORM_EXT: u32 = 0x8E8D;
+const COMPRESSED_RGB_BPTC_SIGNED_FLOAT_EXT: u32 = 0x8E8E;
+const COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT_EXT: u32 = 0x8E8F;
+
+/// The internal format, format and type of a texture format. The format and type of compressed
+/// formats are 0.
+pub fn texture_format(format: TextureFormat) -> (u32, u32, u32) {
+    match format {
+        TextureFormat::R8Unorm => (Gl::R8, Gl::RED, Gl::UNSIGNED_BYTE),
+        TextureFormat::R8Snorm => (Gl::R8_SNORM, Gl::RED, Gl::BYTE),
+        TextureFormat::R8Uint => (Gl::R8UI, Gl::RED_INTEGER, Gl::UNSIGNED_BYTE),
+        TextureFormat::R8Sint => (Gl::R8I, Gl::RED_INTEGER, Gl::BYTE),
+        TextureFormat::R16Uint => (Gl::R16UI, Gl::RED_INTEGER, Gl::UNSIGNED_SHORT),
+        TextureFormat::R16Sint => (Gl::R16I, Gl::RED_INTEGER, Gl::SHORT),
+        TextureFormat::R16Float => (Gl::R16F, Gl::RED, Gl::HALF_FLOAT),
+        TextureFormat::Rg8Unorm => (Gl::RG8, Gl::RG, Gl::UNSIGNED_BYTE),
+        TextureFormat::Rg8Snorm => (Gl::RG8_SNORM, Gl::RG, Gl::BYTE),
+        TextureFormat::Rg8Uint => (Gl::RG8UI, Gl::RG_INTEGER, Gl::UNSIGNED_BYTE),
+        TextureFormat::Rg8Sint => (Gl::RG8I, Gl::RG_INTEGER, Gl::BYTE),
+        TextureFormat::R32Uint => (Gl::R32UI, Gl::RED_INTEGER, Gl::UNSIGNED_INT),
+        TextureFormat::R32Sint => (Gl::R32I, Gl::RED_INTEGER, Gl::INT),
+        TextureFormat::R32Float => (Gl::R32F, Gl::RED, Gl::FLOAT),
+        TextureFormat::Rg16Uint => (Gl::RG16UI, Gl::RG_INTEGER, Gl::UNSIGNED_SHORT),
+        TextureFormat::Rg16Sint => (Gl::RG16I, Gl::RG_INTEGER, Gl::SHORT),
+        TextureFormat::Rg16Float => (Gl::RG16F, Gl::RG, Gl::HALF_FLOAT),
+        // WebGL2 has no BGRA formats, the channels of BGRA textures are swapped
+        TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm => {
+            (Gl::RGBA8, Gl::RGBA, Gl::UNSIGNED_BYTE)
+        }
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Bgra8UnormSrgb => {
+            (Gl::SRGB8_ALPHA8, Gl::RGBA, Gl::UNSIGNED_BYTE)
+        }
+        TextureFormat::Rgba8Snorm => (Gl::RGBA8_SNORM, Gl::RGBA, Gl::BYTE),
+        TextureFormat::Rgba8Uint => (Gl::RGBA8UI, Gl::RGBA_INTEGER, Gl::UNSIGNED_BYTE),
+        TextureFormat::Rgba8Sint => (Gl::RGBA8I, Gl::RGBA_INTEGER, Gl::BYTE),
+        TextureFormat::Rgb10a2Unorm => (Gl::RGB10_A2, Gl::RGBA, Gl::UNSIGNED_INT_2_10_10_10_REV),
+        TextureFormat::Rg11b10Float => (
+            Gl::R11F_G11F_B10F,
+            Gl::RGB,
+            Gl::UNSIGNED_INT_10F_11F_11F_REV,
+        ),
+        TextureFormat::Rg32Uint => (Gl::RG32UI, Gl::RG_INTEGER, Gl::UNSIGNED_INT),
+        TextureFormat::Rg32Sint => (Gl::RG32I, Gl::RG_INTEGER, Gl::INT),
+        TextureFormat::Rg32Float => (Gl::RG32F, Gl::RG, Gl::FLOAT),
+        TextureFormat::Rgba16Uint => (Gl::RGBA16UI, Gl::RGBA_INTEGER, Gl::UNSIGNED_SHORT),
+        TextureFormat::Rgba16Sint => (Gl::RGBA16I, Gl::RGBA_INTEGER, Gl::SHORT),
+        TextureFormat::Rgba16Float => (Gl::RGBA16F, Gl::RGBA, Gl::HALF_FLOAT),
+        TextureFormat::Rgba32Uint => (Gl::RGBA32UI, Gl::RGBA_INTEGER, Gl::UNSIGNED_INT),
+        TextureFormat::Rgba32Sint => (Gl::RGBA32I, Gl::RGBA_INTEGER, Gl::INT),
+        TextureFormat::Rgba32Float => (Gl::RGBA32F, Gl::RGBA, Gl::FLOAT),
+        TextureFormat::Depth32Float => (Gl::DEPTH_COMPONENT32F, Gl::DEPTH_COMPONENT, Gl::FLOAT),
+        TextureFormat::Depth24Plus => {
+            (Gl::DEPTH_COMPONENT24, Gl::DEPTH_COMPONENT, Gl::UNSIGNED_INT)
+        }
+        TextureFormat::Depth24PlusStencil8 => (
+            Gl::DEPTH24_STENCIL8,
+            Gl::DEPTH_STENCIL,
+            Gl::UNSIGNED_INT_24_8,
+        ),
+        TextureFormat::Bc1RgbaUnorm => (COMPRESSED_RGBA_S3TC_DXT1_EXT, 0, 0),
+        TextureFormat::Bc1RgbaUnormSrgb => (COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT, 0, 0),
+        TextureFormat::Bc3RgbaUnorm => (COMPRESSED_RGBA_S3TC_DXT5_EXT, 0, 0),
+        TextureFormat::Bc3RgbaUnormSrgb => (COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT, 0, 0),
+        TextureFormat::Bc2RgbaUnorm => (COMPRESSED_RGBA_S3TC_DXT3_EXT, 0, 0),
+        TextureFormat::Bc2RgbaUnormSrgb => (COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT, 0, 0),
+        TextureFormat::Bc4RUnorm => (COMPRESSED_RED_RGTC1_EXT, 0, 0),
+        TextureFormat::Bc4RSnorm => (COMPRESSED_SIGNED_RED_RGTC1_EXT, 0, 0),
+        TextureFormat::Bc5RgUnorm => (COMPRESSED_RED_GREEN_RGTC2_EXT, 0, 0),
+        TextureFormat::Bc5RgSnorm => (COMPRESSED_SIGNED_RED_GREEN_RGTC2_EXT, 0, 0),
+        TextureFormat::Bc6hRgbUfloat => (COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT_EXT, 0, 0),
+        TextureFormat::Bc6hRgbSfloat => (COMPRESSED_RGB_BPTC_SIGNED_FLOAT_EXT, 0, 0),
+        TextureFormat::Bc7RgbaUnorm => (COMPRESSED_RGBA_BPTC_UNORM_EXT, 0, 0),
+        TextureFormat::Bc7RgbaUnormSrgb => (COMPRESSED_SRGB_ALPHA_BPTC_UNORM_EXT, 0, 0),
+    }
+}
+
+/// The extension needed to create textures of a block compressed format
+pub fn compression_extension(format: TextureFormat) -> Option<&'static str> {
+    match format {
+        TextureFormat::Bc1RgbaUnorm | TextureFormat::Bc2RgbaUnorm | TextureFormat::Bc3RgbaUnorm => {
+            Some("WEBGL_compressed_texture_s3tc")
+        }
+        TextureFormat::Bc1RgbaUnormSrgb
+        | TextureFormat::Bc2RgbaUnormSrgb
+        | TextureFormat::Bc3RgbaUnormSrgb => Some("WEBGL_compressed_texture_s3tc_srgb"),
+        TextureFormat::Bc4RUnorm
+        | TextureFormat::Bc4RSnorm
+        | TextureFormat::Bc5RgUnorm
+        | TextureFormat::Bc5RgSnorm => Some("EXT_texture_compression_rgtc"),
+        TextureFormat::Bc6hRgbUfloat
+        | TextureFormat::Bc6hRgbSfloat
+        | TextureFormat::Bc7RgbaUnorm
+        | TextureFormat::Bc7RgbaUnormSrgb => Some("EXT_texture_compression_bptc"),
+        _ => None,
+    }
+}
+
+/// The component count, component type, whether the components are normalized and whether they
+/// are read as integers by shaders
+pub fn vertex_format(format: VertexFormat) -> (i32, u32, bool, bool) {
+    match format {
+        VertexFormat::Uchar2 => (2, Gl::UNSIGNED_BYTE, false, true),
+        VertexFormat::Uchar4 => (4, Gl::UNSIGNED_BYTE, false, true),
+        VertexFormat::Char2 => (2, Gl::BYTE, false, true),
+        VertexFormat::Char4 => (4, Gl::BYTE, false, true),
+        VertexFormat::Uchar2Norm => (2, Gl::UNSIGNED_BYTE, true, false),
+        VertexFormat::Uchar4Norm => (4, Gl::UNSIGNED_BYTE, true, false),
+        VertexFormat::Char2Norm => (2, Gl::BYTE, true, false),
+        VertexFormat::Char4Norm => (4, Gl::BYTE, true, false),
+        VertexFormat::Ushort2 => (2, Gl::UNSIGNED_SHORT, false, true),
+        VertexFormat::Ushort4 => (4, Gl::UNSIGNED_SHORT, false, true),
+        VertexFormat::Short2 => (2, Gl::SHORT, false, true),
+        VertexFormat::Short4 => (4, Gl::SHORT, false, true),
+        VertexFormat::Ushort2Norm => (2, Gl::UNSIGNED_SHORT, true, false),
+        VertexFormat::Ushort4Norm => (4, Gl::UNSIGNED_SHORT, true, false),
+        VertexFormat::Short2Norm => (2, Gl::SHORT, true, false),
+        VertexFormat::Short4Norm => (4, Gl::SHORT, true, false),
+        VertexFormat::Half2 => (2, Gl::HALF_FLOAT, false, false),
+        VertexFormat::Half4 => (4, Gl::HALF_FLOAT, false, false),
+        VertexFormat::Float => (1, Gl::FLOAT, false, false),
+        VertexFormat::Float2 => (2, Gl::FLOAT, false, false),
+        VertexFormat::Float3 => (3, Gl::FLOAT, false, false),
+        VertexFormat::Float4 => (4, Gl::FLOAT, false, false),
+        VertexFormat::Uint => (1, Gl::UNSIGNED_INT, false, true),
+        VertexFormat::Uint2 => (2, Gl::UNSIGNED_INT, false, true),
+        VertexFormat::Uint3 => (3, Gl::UNSIGNED_INT, false, true),
+        VertexFormat::Uint4 => (4, Gl::UNSIGNED_INT, false, true),
+        VertexFormat::Int => (1, Gl::INT, false, true),
+        VertexFormat::Int2 => (2, Gl::INT, false, true),
+        VertexFormat::Int3 => (3, Gl::INT, false, true),
+        VertexFormat::Int4 => (4, Gl::INT, false, true),
+    }
+}
+
+pub fn primitive_mode(topology: PrimitiveTopology) -> u32 {
+    match topology {
+        PrimitiveTopology::PointList => Gl::POINTS,
+        PrimitiveTopology::LineList => Gl::LINES,
+        PrimitiveTopology::LineStrip => Gl::LINE_STRIP,
+        PrimitiveTopology::TriangleList => Gl::TRIANGLES,
+        PrimitiveTopology::TriangleStrip => Gl::TRIANGLE_STRIP,
+    }
+}
+
+/// The index type and the size of an index in bytes
+pub fn index_format(format: IndexFormat) -> (u32, i32) {
+    match format {
+        IndexFormat::Uint16 => (Gl::UNSIGNED_SHORT, 2),
+        IndexFormat::Uint32 => (Gl::UNSIGNED_INT, 4),
+    }
+}
+
+pub fn blend_factor(factor: BlendFactor) -> u32 {
+    match factor {
+        BlendFactor::Zero => Gl::ZERO,
+        BlendFactor::One => Gl::ONE,
+        BlendFactor::SrcColor => Gl::SRC_COLOR,
+        BlendFactor::OneMinusSrcColor => Gl::ONE_MINUS_SRC_COLOR,
+        BlendFactor::SrcAlpha => Gl::SRC_ALPHA,
+        BlendFactor::OneMinusSrcAlpha => Gl::ONE_MINUS_SRC_ALPHA,
+        BlendFactor::DstColor => Gl::DST_COLOR,
+        BlendFactor::OneMinusDstColor => Gl::ONE_MINUS_DST_COLOR,
+        BlendFactor::DstAlpha => Gl::DST_ALPHA,
+        BlendFactor::OneMinusDstAlpha => Gl::ONE_MINUS_DST_ALPHA,
+        BlendFactor::SrcAlphaSaturated => Gl::SRC_ALPHA_SATURATE,
+        BlendFactor::BlendColor => Gl::CONSTANT_COLOR,
+        BlendFactor::OneMinusBlendColor => Gl::ONE_MINUS_CONSTANT_COLOR,
+    }
+}
+
+pub fn blend_operation(operation: BlendOperation) -> u32 {
+    match operation {
+        BlendOperation::Add => Gl::FUNC_ADD,
+        BlendOperation::Subtract => Gl::FUNC_SUBTRACT,
+        BlendOperation::ReverseSubtract => Gl::FUNC_REVERSE_SUBTRACT,
+        BlendOperation::Min => Gl::MIN,
+        BlendOperation::Max => Gl::MAX,
+    }
+}
+
+pub fn compare_function(function: CompareFunction) -> u32 {
+    match function {
+        CompareFunction::Never => Gl::NEVER,
+        CompareFunction::Less => Gl::LESS,
+        CompareFunction::Equal => Gl::EQUAL,
+        CompareFunction::LessEqual => Gl::LEQUAL,
+        CompareFunction::Greater => Gl::GREATER,
+        CompareFunction::NotEqual => Gl::NOTEQUAL,
+        CompareFunction::GreaterEqual => Gl::GEQUAL,
+        CompareFunction::Always => Gl::ALWAYS,
+    }
+}
+
+pub fn stencil_operation(operation: StencilOperation) -> u32 {
+    match operation {
+        StencilOperation::Keep => Gl::KEEP,
+        StencilOperation::Zero => Gl::ZERO,
+        StencilOperation::Replace => Gl::REPLACE,
+        StencilOperation::Invert => Gl::INVERT,
+        StencilOperation::IncrementClamp => Gl::INCR,
+        StencilOperation::DecrementClamp => Gl::DECR,
+        StencilOperation::IncrementWrap => Gl::INCR_WRAP,
+        StencilOperation::DecrementWrap => Gl::DECR_WRAP,
+    }
+}
+
+pub fn address_mode(mode: AddressMode) -> u32 {
+    match mode {
+        AddressMode::ClampToEdge => Gl::CLAMP_TO_EDGE,
+        AddressMode::Repeat => Gl::REPEAT,
+        AddressMode::MirrorRepeat => Gl::MIRRORED_REPEAT,
+    }
+}
+
+pub fn min_filter(filter: FilterMode, mipmap_filter: FilterMode) -> u32 {
+    match (filter, mipmap_filter) {
+        (FilterMode::Nearest, FilterMode::Nearest) => Gl::NEAREST_MIPMAP_NEAREST,
+        (FilterMode::Nearest, FilterMode::Linear) => Gl::NEAREST_MIPMAP_LINEAR,
+        (FilterMode::Linear, FilterMode::Nearest) => Gl::LINEAR_MIPMAP_NEAREST,
+        (FilterMode::Linear, FilterMode::Linear) => Gl::LINEAR_MIPMAP_LINEAR,
+    }
+}
+
+pub fn mag_filter(filter: FilterMode) -> u32 {
+    match filter {
+        FilterMode::Nearest => Gl::NEAREST,
+        FilterMode::Linear => Gl::LINEAR,
+    }
+}
@@ -1,2 +1,6 @@
+mod render_graph_cpu_diagnostics_plugin;
 mod wgpu_resource_diagnostics_plugin;
+pub use render_graph_cpu_diagnostics_plugin::{
+    RenderGraphCpuDiagnosticsPlugin, RenderGraphCpuTimings,
+};
 pub use wgpu_resource_diagnostics_plugin::WgpuResourceDiagnosticsPlugin;
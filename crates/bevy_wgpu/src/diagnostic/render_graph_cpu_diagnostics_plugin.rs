@@ -0,0 +1,82 @@
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::{Res, ResMut};
+use bevy_utils::{Duration, Uuid};
+use std::borrow::Cow;
+
+/// The CPU time spent in the render graph in the last frame, written by the render graph executor
+#[derive(Debug, Default)]
+pub struct RenderGraphCpuTimings {
+    /// The time each node took to record its commands, by node name
+    pub nodes: Vec<(Cow<'static, str>, Duration)>,
+    /// The time spent submitting the commands of every stage to the queue
+    pub submit: Duration,
+    /// The time the whole render graph took
+    pub total: Duration,
+}
+
+/// Publishes the CPU time spent in each render graph node through [Diagnostics], as
+/// `render_graph_cpu_node/<name>` in milliseconds. Nodes with no name aren't measured.
+///
+/// This is the time the render graph executor takes to record and submit commands, not the time
+/// the GPU takes to execute them, which wgpu 0.6 can't measure as it has no timestamp queries.
+/// Nodes that acquire a swap chain texture also include the time spent waiting for it to be
+/// presented.
+#[derive(Default)]
+pub struct RenderGraphCpuDiagnosticsPlugin;
+
+impl Plugin for RenderGraphCpuDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<RenderGraphCpuTimings>()
+            .add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl RenderGraphCpuDiagnosticsPlugin {
+    pub const RENDER_GRAPH_CPU: DiagnosticId =
+        DiagnosticId::from_u128(187349516303186723650383548932151893741);
+    pub const RENDER_GRAPH_CPU_SUBMIT: DiagnosticId =
+        DiagnosticId::from_u128(86402731588140961240563436950293574227);
+
+    /// The id of the diagnostic of a render graph node, which is the same in every build
+    pub fn node_diagnostic_id(name: &str) -> DiagnosticId {
+        DiagnosticId(Uuid::new_v5(&Self::RENDER_GRAPH_CPU.0, name.as_bytes()))
+    }
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::RENDER_GRAPH_CPU,
+            "render_graph_cpu",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::RENDER_GRAPH_CPU_SUBMIT,
+            "render_graph_cpu_submit",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        timings: Res<RenderGraphCpuTimings>,
+    ) {
+        if timings.total == Duration::from_secs(0) {
+            return;
+        }
+        let milliseconds = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        diagnostics.add_measurement(Self::RENDER_GRAPH_CPU, milliseconds(timings.total));
+        diagnostics.add_measurement(Self::RENDER_GRAPH_CPU_SUBMIT, milliseconds(timings.submit));
+        for (name, duration) in timings.nodes.iter() {
+            let id = Self::node_diagnostic_id(name);
+            if diagnostics.get(id).is_none() {
+                diagnostics.add(Diagnostic::new(
+                    id,
+                    &format!("render_graph_cpu_node/{}", name),
+                    20,
+                ));
+            }
+            diagnostics.add_measurement(id, milliseconds(*duration));
+        }
+    }
+}
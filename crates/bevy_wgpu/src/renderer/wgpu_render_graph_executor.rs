@@ -1,10 +1,13 @@
 use super::{WgpuRenderContext, WgpuRenderResourceContext};
+use crate::diagnostic::RenderGraphCpuTimings;
 use bevy_ecs::{Resources, World};
 use bevy_render::{
     render_graph::{Edge, NodeId, ResourceSlots, StageBorrow},
     renderer::RenderResourceContext,
 };
-use bevy_utils::HashMap;
+#[cfg(feature = "trace_spans")]
+use bevy_utils::tracing::info_span;
+use bevy_utils::{HashMap, Instant};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
@@ -29,7 +32,18 @@ impl WgpuRenderGraphExecutor {
             .downcast_mut::<WgpuRenderResourceContext>()
             .unwrap();
         let node_outputs: Arc<RwLock<HashMap<NodeId, ResourceSlots>>> = Default::default();
-        for stage in stages.iter_mut() {
+        // the timings are only measured when they are used for diagnostics
+        let mut timings = resources
+            .get::<RenderGraphCpuTimings>()
+            .map(|_| RenderGraphCpuTimings::default());
+        let start = Instant::now();
+        #[cfg_attr(not(feature = "trace_spans"), allow(unused_variables))]
+        for (stage_index, stage) in stages.iter_mut().enumerate() {
+            #[cfg(feature = "trace_spans")]
+            let stage_span = info_span!("render_graph_stage", index = stage_index);
+            #[cfg(feature = "trace_spans")]
+            let _stage_guard = stage_span.enter();
+
             // TODO: sort jobs and slice by "amount of work" / weights
             // stage.jobs.sort_by_key(|j| j.node_states.len());
 
@@ -63,13 +77,21 @@ impl WgpuRenderGraphExecutor {
                                     panic!("node inputs not set")
                                 };
 
-                                let output_resource =
-                                    outputs.get(*output_index).expect("output should be set");
-                                input_slot.resource = Some(output_resource);
+                                // outputs aren't set for windows that were closed
+                                input_slot.resource = outputs.get(*output_index);
                             } else {
                                 panic!("no edge connected to input")
                             }
                         }
+                        #[cfg(feature = "trace_spans")]
+                        let node_span = info_span!(
+                            "render_graph_node",
+                            name = node_state.name.as_deref().unwrap_or("unnamed")
+                        );
+                        #[cfg(feature = "trace_spans")]
+                        let _node_guard = node_span.enter();
+
+                        let node_start = Instant::now();
                         node_state.node.update(
                             world,
                             resources,
@@ -77,6 +99,9 @@ impl WgpuRenderGraphExecutor {
                             &node_state.input_slots,
                             &mut node_state.output_slots,
                         );
+                        if let (Some(timings), Some(name)) = (&mut timings, &node_state.name) {
+                            timings.nodes.push((name.clone(), node_start.elapsed()));
+                        }
 
                         node_outputs
                             .write()
@@ -97,7 +122,20 @@ impl WgpuRenderGraphExecutor {
                 }
             }
 
+            #[cfg(feature = "trace_spans")]
+            let submit_span = info_span!("render_graph_submit");
+            #[cfg(feature = "trace_spans")]
+            let _submit_guard = submit_span.enter();
+            let submit_start = Instant::now();
             queue.submit(command_buffers.drain(..));
+            if let Some(timings) = &mut timings {
+                timings.submit += submit_start.elapsed();
+            }
+        }
+
+        if let Some(mut timings) = timings {
+            timings.total = start.elapsed();
+            *resources.get_mut::<RenderGraphCpuTimings>().unwrap() = timings;
         }
     }
 }
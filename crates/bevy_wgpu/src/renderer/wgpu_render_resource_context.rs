@@ -13,7 +13,7 @@ use bevy_render::{
         RenderResourceId, SamplerId, TextureId,
     },
     shader::{glsl_to_spirv, Shader, ShaderSource},
-    texture::{Extent3d, SamplerDescriptor, TextureDescriptor},
+    texture::{Extent3d, SamplerDescriptor, TextureDescriptor, TextureFormat},
 };
 use bevy_utils::tracing::trace;
 use bevy_window::{Window, WindowId};
@@ -566,6 +566,14 @@ impl RenderResourceContext for WgpuRenderResourceContext {
         (size + TEXTURE_ALIGNMENT - 1) & !(TEXTURE_ALIGNMENT - 1)
     }
 
+    fn supports_texture_format(&self, format: TextureFormat) -> bool {
+        !format.is_compressed()
+            || self
+                .device
+                .features()
+                .contains(wgpu::Features::TEXTURE_COMPRESSION_BC)
+    }
+
     fn get_aligned_uniform_size(&self, size: usize, dynamic: bool) -> usize {
         if dynamic {
             (size + BIND_BUFFER_ALIGNMENT - 1) & !(BIND_BUFFER_ALIGNMENT - 1)
@@ -44,7 +44,8 @@ impl WgpuRenderer {
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    features: wgpu::Features::empty(),
+                    // block compressed textures are used when the adapter supports them
+                    features: adapter.features() & wgpu::Features::TEXTURE_COMPRESSION_BC,
                     limits: wgpu::Limits::default(),
                     shader_validation: true,
                 },
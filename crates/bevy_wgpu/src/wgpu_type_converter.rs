@@ -14,7 +14,7 @@ use bevy_render::{
         TextureDescriptor, TextureDimension, TextureFormat, TextureUsage, TextureViewDimension,
     },
 };
-use bevy_window::Window;
+use bevy_window::{PresentMode, Window};
 
 pub trait WgpuFrom<T> {
     fn from(val: T) -> Self;
@@ -313,6 +313,20 @@ impl WgpuFrom<TextureFormat> for wgpu::TextureFormat {
             TextureFormat::Depth32Float => wgpu::TextureFormat::Depth32Float,
             TextureFormat::Depth24Plus => wgpu::TextureFormat::Depth24Plus,
             TextureFormat::Depth24PlusStencil8 => wgpu::TextureFormat::Depth24PlusStencil8,
+            TextureFormat::Bc1RgbaUnorm => wgpu::TextureFormat::Bc1RgbaUnorm,
+            TextureFormat::Bc1RgbaUnormSrgb => wgpu::TextureFormat::Bc1RgbaUnormSrgb,
+            TextureFormat::Bc3RgbaUnorm => wgpu::TextureFormat::Bc3RgbaUnorm,
+            TextureFormat::Bc3RgbaUnormSrgb => wgpu::TextureFormat::Bc3RgbaUnormSrgb,
+            TextureFormat::Bc2RgbaUnorm => wgpu::TextureFormat::Bc2RgbaUnorm,
+            TextureFormat::Bc2RgbaUnormSrgb => wgpu::TextureFormat::Bc2RgbaUnormSrgb,
+            TextureFormat::Bc4RUnorm => wgpu::TextureFormat::Bc4RUnorm,
+            TextureFormat::Bc4RSnorm => wgpu::TextureFormat::Bc4RSnorm,
+            TextureFormat::Bc5RgUnorm => wgpu::TextureFormat::Bc5RgUnorm,
+            TextureFormat::Bc5RgSnorm => wgpu::TextureFormat::Bc5RgSnorm,
+            TextureFormat::Bc6hRgbUfloat => wgpu::TextureFormat::Bc6hRgbUfloat,
+            TextureFormat::Bc6hRgbSfloat => wgpu::TextureFormat::Bc6hRgbSfloat,
+            TextureFormat::Bc7RgbaUnorm => wgpu::TextureFormat::Bc7RgbaUnorm,
+            TextureFormat::Bc7RgbaUnormSrgb => wgpu::TextureFormat::Bc7RgbaUnormSrgb,
         }
     }
 }
@@ -559,6 +573,16 @@ impl WgpuFrom<FilterMode> for wgpu::FilterMode {
     }
 }
 
+impl WgpuFrom<PresentMode> for wgpu::PresentMode {
+    fn from(val: PresentMode) -> Self {
+        match val {
+            PresentMode::Immediate => wgpu::PresentMode::Immediate,
+            PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+            PresentMode::Fifo => wgpu::PresentMode::Fifo,
+        }
+    }
+}
+
 impl WgpuFrom<&Window> for wgpu::SwapChainDescriptor {
     fn from(window: &Window) -> Self {
         wgpu::SwapChainDescriptor {
@@ -566,11 +590,7 @@ impl WgpuFrom<&Window> for wgpu::SwapChainDescriptor {
             format: TextureFormat::default().wgpu_into(),
             width: window.scaled_width(),
             height: window.scaled_height(),
-            present_mode: if window.vsync() {
-                wgpu::PresentMode::Mailbox
-            } else {
-                wgpu::PresentMode::Immediate
-            },
+            present_mode: window.present_mode().wgpu_into(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use super::{WindowDescriptor, WindowId};
 use bevy_math::Vec2;
+use std::path::PathBuf;
 
 /// A window event that is sent whenever a window has been resized.
 #[derive(Debug, Clone)]
@@ -16,12 +17,19 @@ pub struct CreateWindow {
     pub descriptor: WindowDescriptor,
 }
 
-/// An event that indicates a window should be closed.
+/// An event that indicates a window should be closed, like [Window::close](crate::Window::close).
 #[derive(Debug, Clone)]
 pub struct CloseWindow {
     pub id: WindowId,
 }
 
+/// An event that is sent whenever a window was closed, once it was removed from the
+/// [Windows](crate::Windows)
+#[derive(Debug, Clone)]
+pub struct WindowClosed {
+    pub id: WindowId,
+}
+
 /// An event that is sent whenever a new window is created.
 #[derive(Debug, Clone)]
 pub struct WindowCreated {
@@ -29,12 +37,34 @@ pub struct WindowCreated {
 }
 
 /// An event that is sent whenever a close was requested for a window. For example: when the "close" button
-/// is pressed on a window.
+/// is pressed on a window. The window is only closed by [Window::close](crate::Window::close), which
+/// [WindowPlugin::close_when_requested](crate::WindowPlugin::close_when_requested) does for every
+/// request, so apps that ask for confirmation first turn it off.
 #[derive(Debug, Clone)]
 pub struct WindowCloseRequested {
     pub id: WindowId,
 }
 
+/// An event that is sent whenever a window was moved on the desktop, with the position of its top
+/// left corner in physical pixels
+#[derive(Debug, Clone)]
+pub struct WindowMoved {
+    pub id: WindowId,
+    pub position: (i32, i32),
+}
+
+/// An event that is sent whenever a window gains or loses the focus
+#[derive(Debug, Clone)]
+pub struct WindowFocused {
+    pub id: WindowId,
+    pub focused: bool,
+}
+
+/// An event that asks for the app to be updated again, when it is only updated in response to
+/// events. Systems send it while something changes on its own, like an animation.
+#[derive(Debug, Clone, Default)]
+pub struct RequestRedraw;
+
 #[derive(Debug, Clone)]
 pub struct CursorMoved {
     pub id: WindowId,
@@ -47,3 +77,28 @@ pub struct ReceivedCharacter {
     pub id: WindowId,
     pub char: char,
 }
+
+/// An event that is sent whenever files are dragged from the OS over a window or dropped on it.
+/// The position is the last position of the cursor in the window, from the bottom left corner,
+/// which isn't known when the cursor didn't move in the window before the files were dragged
+/// over it.
+///
+/// Files can't be dragged onto windows on Windows, where it would conflict with the audio
+/// output.
+#[derive(Debug, Clone)]
+pub enum FileDragAndDrop {
+    /// A file is dragged over the window. An event is sent for each file that is dragged.
+    HoveredFile {
+        id: WindowId,
+        path_buf: PathBuf,
+        position: Option<Vec2>,
+    },
+    /// A file was dropped on the window. An event is sent for each file that is dropped.
+    DroppedFile {
+        id: WindowId,
+        path_buf: PathBuf,
+        position: Option<Vec2>,
+    },
+    /// The files dragged over the window left it, or the drag was cancelled
+    HoveredFileCancelled { id: WindowId },
+}
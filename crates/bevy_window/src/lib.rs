@@ -1,22 +1,31 @@
 mod event;
+mod monitor;
 mod system;
 mod window;
 mod windows;
 
 pub use event::*;
+pub use monitor::*;
 pub use system::*;
 pub use window::*;
 pub use windows::*;
 
 pub mod prelude {
-    pub use crate::{CursorMoved, ReceivedCharacter, Window, WindowDescriptor, Windows};
+    pub use crate::{
+        CursorGrabMode, CursorIcon, CursorMoved, FileDragAndDrop, MonitorSelection, Monitors,
+        PresentMode, ReceivedCharacter, RequestRedraw, VideoMode, Window, WindowDescriptor,
+        WindowMode, Windows,
+    };
 }
 
 use bevy_app::prelude::*;
 
 pub struct WindowPlugin {
     pub add_primary_window: bool,
+    /// Exits the app when the primary window is closed
     pub exit_on_close: bool,
+    /// Closes windows when their close is requested, with their "close" button for example
+    pub close_when_requested: bool,
 }
 
 impl Default for WindowPlugin {
@@ -24,6 +33,7 @@ impl Default for WindowPlugin {
         WindowPlugin {
             add_primary_window: true,
             exit_on_close: true,
+            close_when_requested: true,
         }
     }
 }
@@ -35,9 +45,16 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowCreated>()
             .add_event::<WindowCloseRequested>()
             .add_event::<CloseWindow>()
+            .add_event::<WindowClosed>()
+            .add_event::<WindowFocused>()
+            .add_event::<WindowMoved>()
             .add_event::<CursorMoved>()
             .add_event::<ReceivedCharacter>()
-            .init_resource::<Windows>();
+            .add_event::<FileDragAndDrop>()
+            .add_event::<RequestRedraw>()
+            .init_resource::<Windows>()
+            .init_resource::<Monitors>()
+            .add_system(close_window_system);
 
         if self.add_primary_window {
             let resources = app.resources();
@@ -52,6 +69,10 @@ impl Plugin for WindowPlugin {
             });
         }
 
+        if self.close_when_requested {
+            app.add_system(close_when_requested_system);
+        }
+
         if self.exit_on_close {
             app.add_system(exit_on_window_close_system);
         }
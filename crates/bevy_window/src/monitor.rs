@@ -0,0 +1,82 @@
+/// A resolution and refresh rate a monitor can be set to in exclusive fullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    /// The width in physical pixels
+    pub width: u32,
+    /// The height in physical pixels
+    pub height: u32,
+    pub bit_depth: u16,
+    /// The refresh rate in hertz
+    pub refresh_rate: u16,
+}
+
+/// A monitor connected to the computer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub name: Option<String>,
+    /// The position of the top left corner of the monitor on the desktop, in physical pixels
+    pub position: (i32, i32),
+    /// The current size of the monitor in physical pixels
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    /// The video modes of the monitor, from the highest resolution and refresh rate to the lowest
+    pub video_modes: Vec<VideoMode>,
+}
+
+/// The monitors connected to the computer, as they were when the windowing backend started, for
+/// settings menus to choose where and how windows are shown in fullscreen
+#[derive(Debug, Default)]
+pub struct Monitors {
+    monitors: Vec<Monitor>,
+    primary: Option<usize>,
+}
+
+impl Monitors {
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    /// The index of the primary monitor, which isn't known on every platform
+    pub fn primary_index(&self) -> Option<usize> {
+        self.primary
+    }
+
+    pub fn get_primary(&self) -> Option<&Monitor> {
+        self.primary.and_then(|index| self.get(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    #[doc(hidden)]
+    pub fn update_from_backend(&mut self, monitors: Vec<Monitor>, primary: Option<usize>) {
+        self.monitors = monitors;
+        self.primary = primary;
+    }
+}
+
+/// The monitor a window is shown on in fullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorSelection {
+    /// The monitor the window is on
+    Current,
+    Primary,
+    /// A monitor by its index in the [Monitors]
+    Index(usize),
+}
+
+impl Default for MonitorSelection {
+    fn default() -> Self {
+        MonitorSelection::Current
+    }
+}
@@ -1,4 +1,4 @@
-use crate::WindowCloseRequested;
+use crate::{CloseWindow, WindowCloseRequested, WindowClosed, Windows};
 use bevy_app::{
     prelude::{EventReader, Events},
     AppExit,
@@ -7,20 +7,56 @@ use bevy_ecs::{Local, Res, ResMut};
 
 #[derive(Default)]
 pub struct ExitOnWindowCloseState {
-    event_reader: EventReader<WindowCloseRequested>,
+    event_reader: EventReader<WindowClosed>,
 }
 
+/// Exits the app when the primary window was closed
 pub fn exit_on_window_close_system(
     mut state: Local<ExitOnWindowCloseState>,
     mut app_exit_events: ResMut<Events<AppExit>>,
-    window_close_requested_events: Res<Events<WindowCloseRequested>>,
+    window_closed_events: Res<Events<WindowClosed>>,
 ) {
     if state
         .event_reader
-        .iter(&window_close_requested_events)
-        .next()
-        .is_some()
+        .iter(&window_closed_events)
+        .any(|event| event.id.is_primary())
     {
         app_exit_events.send(AppExit);
     }
 }
+
+#[derive(Default)]
+pub struct CloseWhenRequestedState {
+    event_reader: EventReader<WindowCloseRequested>,
+}
+
+/// Closes the windows whose close was requested
+pub fn close_when_requested_system(
+    mut state: Local<CloseWhenRequestedState>,
+    mut windows: ResMut<Windows>,
+    window_close_requested_events: Res<Events<WindowCloseRequested>>,
+) {
+    for event in state.event_reader.iter(&window_close_requested_events) {
+        if let Some(window) = windows.get_mut(event.id) {
+            window.close();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CloseWindowState {
+    event_reader: EventReader<CloseWindow>,
+}
+
+/// Closes the windows of [CloseWindow] events
+pub fn close_window_system(
+    mut state: Local<CloseWindowState>,
+    mut windows: ResMut<Windows>,
+    close_window_events: Res<Events<CloseWindow>>,
+) {
+    for event in state.event_reader.iter(&close_window_events) {
+        if let Some(window) = windows.get_mut(event.id) {
+            window.close();
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::{MonitorSelection, VideoMode};
+use bevy_math::Vec2;
 use bevy_utils::Uuid;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -37,12 +39,22 @@ pub struct Window {
     width: u32,
     height: u32,
     title: String,
-    vsync: bool,
+    present_mode: PresentMode,
     resizable: bool,
+    resize_constraints: WindowResizeConstraints,
     decorations: bool,
+    transparent: bool,
+    always_on_top: bool,
+    position: Option<(i32, i32)>,
     cursor_visible: bool,
-    cursor_locked: bool,
+    cursor_grab_mode: CursorGrabMode,
+    cursor_icon: CursorIcon,
+    focused: bool,
+    cursor_position: Option<Vec2>,
+    ime_position: Vec2,
+    icon: Option<WindowIcon>,
     mode: WindowMode,
+    monitor: MonitorSelection,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
     command_queue: Vec<WindowCommand>,
@@ -54,6 +66,7 @@ pub enum WindowCommand {
     SetWindowMode {
         mode: WindowMode,
         resolution: (u32, u32),
+        monitor: MonitorSelection,
     },
     SetTitle {
         title: String,
@@ -62,25 +75,200 @@ pub enum WindowCommand {
         width: u32,
         height: u32,
     },
-    SetVsync {
-        vsync: bool,
+    SetPresentMode {
+        present_mode: PresentMode,
     },
     SetResizable {
         resizable: bool,
     },
+    SetResizeConstraints {
+        resize_constraints: WindowResizeConstraints,
+    },
     SetDecorations {
         decorations: bool,
     },
-    SetCursorLockMode {
-        locked: bool,
+    SetAlwaysOnTop {
+        always_on_top: bool,
+    },
+    SetPosition {
+        x: i32,
+        y: i32,
+    },
+    SetCursorGrabMode {
+        mode: CursorGrabMode,
     },
     SetCursorVisibility {
         visible: bool,
     },
+    SetCursorIcon {
+        icon: CursorIcon,
+    },
     SetCursorPosition {
         x: i32,
         y: i32,
     },
+    SetImePosition {
+        position: Vec2,
+    },
+    SetIcon {
+        icon: Option<WindowIcon>,
+    },
+    Close,
+}
+
+/// The image shown for a window in its title bar and in the taskbar, as RGBA pixels of 8 bits per
+/// channel, row by row from the top. Icons are set from textures with
+/// `bevy::render::texture::WindowIcons`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowIcon {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// How the cursor is kept in a window. The grab is released while the window isn't focused, and
+/// the cursor is shown, so that other windows can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorGrabMode {
+    /// The cursor moves freely
+    None,
+    /// The cursor can't leave the window
+    Confined,
+    /// The cursor is kept at the center of the window, for controls that only use the motion of
+    /// the mouse, like first-person cameras. The cursor is usually hidden too.
+    Locked,
+}
+
+/// The standard shapes of the cursor of the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    /// The platform-dependent default cursor, usually an arrow
+    Default,
+    /// A simple crosshair
+    Crosshair,
+    /// A hand, often used for links
+    Hand,
+    /// An arrow
+    Arrow,
+    /// Something is to be moved
+    Move,
+    /// Text can be selected, usually an I-beam
+    Text,
+    /// The program is busy, and the user should wait
+    Wait,
+    /// Help is available
+    Help,
+    /// The program is busy in the background, but can still be used
+    Progress,
+    /// The requested action is not allowed
+    NotAllowed,
+    ContextMenu,
+    Cell,
+    VerticalText,
+    Alias,
+    Copy,
+    NoDrop,
+    /// Something can be grabbed
+    Grab,
+    /// Something is being grabbed
+    Grabbing,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+    /// An edge or a corner is to be moved, for resizing
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+}
+
+impl Default for CursorIcon {
+    fn default() -> Self {
+        CursorIcon::Default
+    }
+}
+
+/// The limits of the size of a window when it is resized, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowResizeConstraints {
+    pub min_width: f32,
+    pub min_height: f32,
+    pub max_width: f32,
+    pub max_height: f32,
+    /// The width divided by the height the window keeps when it is resized
+    pub aspect_ratio: Option<f32>,
+}
+
+impl Default for WindowResizeConstraints {
+    fn default() -> Self {
+        WindowResizeConstraints {
+            min_width: 180.0,
+            min_height: 120.0,
+            max_width: f32::INFINITY,
+            max_height: f32::INFINITY,
+            aspect_ratio: None,
+        }
+    }
+}
+
+impl WindowResizeConstraints {
+    /// Returns constraints where the minimums are at least one pixel and not over the maximums,
+    /// and the aspect ratio is positive
+    pub fn check_constraints(&self) -> Self {
+        let min_width = self.min_width.max(1.0);
+        let min_height = self.min_height.max(1.0);
+        WindowResizeConstraints {
+            min_width,
+            min_height,
+            max_width: self.max_width.max(min_width),
+            max_height: self.max_height.max(min_height),
+            aspect_ratio: self
+                .aspect_ratio
+                .filter(|aspect_ratio| aspect_ratio.is_finite() && *aspect_ratio > 0.0),
+        }
+    }
+
+    /// The size closest to `width` and `height` that fits the constraints. With an aspect ratio,
+    /// the height follows the width unless that is out of bounds.
+    pub fn constrain(&self, width: f32, height: f32) -> (f32, f32) {
+        let width = width.max(self.min_width).min(self.max_width);
+        let height = height.max(self.min_height).min(self.max_height);
+        match self.aspect_ratio {
+            Some(aspect_ratio) => {
+                let height = (width / aspect_ratio)
+                    .max(self.min_height)
+                    .min(self.max_height);
+                let width = (height * aspect_ratio)
+                    .max(self.min_width)
+                    .min(self.max_width);
+                (width, height)
+            }
+            None => (width, height),
+        }
+    }
+}
+
+/// How frames are presented to a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    /// Frames are shown as soon as they are drawn, which can tear
+    Immediate,
+    /// Frames are shown when the display refreshes, and the latest drawn frame replaces the ones
+    /// waiting to be shown, so drawing doesn't wait for the display
+    Mailbox,
+    /// Frames are shown when the display refreshes, in the order they were drawn, and drawing
+    /// waits when the queue of frames is full. This is supported everywhere.
+    Fifo,
 }
 
 /// Defines the way a window is displayed
@@ -88,11 +276,14 @@ pub enum WindowCommand {
 /// defines whether a videomode is chosen that best fits the width and height
 /// in the Window structure, or if these are ignored.
 /// E.g. when use_size is set to false the best video mode possible is chosen.
-#[derive(Debug, Clone, Copy)]
+/// The ExclusiveFullscreen variant sets the monitor to one of its [VideoMode]s, as found in the
+/// [Monitors](crate::Monitors).
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WindowMode {
     Windowed,
     BorderlessFullscreen,
     Fullscreen { use_size: bool },
+    ExclusiveFullscreen { video_mode: VideoMode },
 }
 
 impl Window {
@@ -102,12 +293,28 @@ impl Window {
             height: window_descriptor.height,
             width: window_descriptor.width,
             title: window_descriptor.title.clone(),
-            vsync: window_descriptor.vsync,
+            present_mode: window_descriptor
+                .present_mode
+                .unwrap_or(if window_descriptor.vsync {
+                    PresentMode::Mailbox
+                } else {
+                    PresentMode::Immediate
+                }),
             resizable: window_descriptor.resizable,
+            resize_constraints: window_descriptor.resize_constraints.check_constraints(),
             decorations: window_descriptor.decorations,
+            transparent: window_descriptor.transparent,
+            always_on_top: window_descriptor.always_on_top,
+            position: window_descriptor.position,
             cursor_visible: window_descriptor.cursor_visible,
-            cursor_locked: window_descriptor.cursor_locked,
+            cursor_grab_mode: window_descriptor.cursor_grab_mode,
+            cursor_icon: CursorIcon::Default,
+            focused: true,
+            cursor_position: None,
+            ime_position: Vec2::zero(),
+            icon: None,
             mode: window_descriptor.mode,
+            monitor: window_descriptor.monitor,
             #[cfg(target_arch = "wasm32")]
             canvas: window_descriptor.canvas.clone(),
             command_queue: Vec::new(),
@@ -171,15 +378,34 @@ impl Window {
         self.command_queue.push(WindowCommand::SetTitle { title });
     }
 
+    /// Whether frames wait for the display, which is the case with any [PresentMode] but
+    /// `Immediate`
     #[inline]
     pub fn vsync(&self) -> bool {
-        self.vsync
+        self.present_mode != PresentMode::Immediate
     }
 
+    /// Sets the present mode to `Mailbox` with vsync, or to `Immediate` without it
     #[inline]
     pub fn set_vsync(&mut self, vsync: bool) {
-        self.vsync = vsync;
-        self.command_queue.push(WindowCommand::SetVsync { vsync });
+        self.set_present_mode(if vsync {
+            PresentMode::Mailbox
+        } else {
+            PresentMode::Immediate
+        });
+    }
+
+    #[inline]
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Sets how frames are presented to the window. The swap chain of the window is created again
+    /// with the new mode before the next frame is drawn.
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        self.present_mode = present_mode;
+        self.command_queue
+            .push(WindowCommand::SetPresentMode { present_mode });
     }
 
     #[inline]
@@ -193,6 +419,20 @@ impl Window {
             .push(WindowCommand::SetResizable { resizable });
     }
 
+    #[inline]
+    pub fn resize_constraints(&self) -> WindowResizeConstraints {
+        self.resize_constraints
+    }
+
+    /// Sets the limits of the size of the window when it is resized. The window is resized to fit
+    /// them if it doesn't already.
+    pub fn set_resize_constraints(&mut self, resize_constraints: WindowResizeConstraints) {
+        let resize_constraints = resize_constraints.check_constraints();
+        self.resize_constraints = resize_constraints;
+        self.command_queue
+            .push(WindowCommand::SetResizeConstraints { resize_constraints });
+    }
+
     #[inline]
     pub fn decorations(&self) -> bool {
         self.decorations
@@ -204,15 +444,78 @@ impl Window {
             .push(WindowCommand::SetDecorations { decorations });
     }
 
+    #[inline]
+    pub fn always_on_top(&self) -> bool {
+        self.always_on_top
+    }
+
+    /// Keeps the window above the other windows of the desktop
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        self.always_on_top = always_on_top;
+        self.command_queue
+            .push(WindowCommand::SetAlwaysOnTop { always_on_top });
+    }
+
+    /// The position of the top left corner of the window on the desktop, including its
+    /// decorations, in physical pixels. It isn't known on every platform.
+    #[inline]
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.position
+    }
+
+    /// Moves the top left corner of the window to a position on the desktop, in physical pixels
+    pub fn set_position(&mut self, x: i32, y: i32) {
+        self.position = Some((x, y));
+        self.command_queue.push(WindowCommand::SetPosition { x, y });
+    }
+
+    #[doc(hidden)]
+    pub fn update_position_from_backend(&mut self, position: Option<(i32, i32)>) {
+        self.position = position;
+    }
+
+    /// Whether the window is see-through where nothing opaque is drawn. It can only be set when
+    /// the window is created.
+    #[inline]
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Whether the cursor can't leave the window, see [Window::cursor_grab_mode]
     #[inline]
     pub fn cursor_locked(&self) -> bool {
-        self.cursor_locked
+        self.cursor_grab_mode != CursorGrabMode::None
     }
 
+    /// Confines the cursor to the window, or releases it
     pub fn set_cursor_lock_mode(&mut self, lock_mode: bool) {
-        self.cursor_locked = lock_mode;
+        self.set_cursor_grab_mode(if lock_mode {
+            CursorGrabMode::Confined
+        } else {
+            CursorGrabMode::None
+        });
+    }
+
+    #[inline]
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        self.cursor_grab_mode
+    }
+
+    pub fn set_cursor_grab_mode(&mut self, mode: CursorGrabMode) {
+        self.cursor_grab_mode = mode;
         self.command_queue
-            .push(WindowCommand::SetCursorLockMode { locked: lock_mode });
+            .push(WindowCommand::SetCursorGrabMode { mode });
+    }
+
+    /// Whether the window receives the keyboard input
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    #[doc(hidden)]
+    pub fn update_focused_from_backend(&mut self, focused: bool) {
+        self.focused = focused;
     }
 
     #[inline]
@@ -227,11 +530,63 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    /// Sets the shape of the cursor of the system over the window
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        if self.cursor_icon != icon {
+            self.cursor_icon = icon;
+            self.command_queue
+                .push(WindowCommand::SetCursorIcon { icon });
+        }
+    }
+
+    /// The last position of the cursor in the window, from the bottom left corner, or nothing
+    /// when the cursor is outside of the window
+    #[inline]
+    pub fn cursor_position(&self) -> Option<Vec2> {
+        self.cursor_position
+    }
+
+    #[doc(hidden)]
+    pub fn update_cursor_position_from_backend(&mut self, cursor_position: Option<Vec2>) {
+        self.cursor_position = cursor_position;
+    }
+
     pub fn set_cursor_position(&mut self, x: i32, y: i32) {
         self.command_queue
             .push(WindowCommand::SetCursorPosition { x, y });
     }
 
+    #[inline]
+    pub fn ime_position(&self) -> Vec2 {
+        self.ime_position
+    }
+
+    /// Sets where the candidate window of input methods is placed while text is composed, from
+    /// the bottom left corner of the window. Text inputs place it under their caret.
+    pub fn set_ime_position(&mut self, position: Vec2) {
+        if self.ime_position != position {
+            self.ime_position = position;
+            self.command_queue
+                .push(WindowCommand::SetImePosition { position });
+        }
+    }
+
+    #[inline]
+    pub fn icon(&self) -> Option<&WindowIcon> {
+        self.icon.as_ref()
+    }
+
+    /// Sets the icon of the window, or the default icon of the platform with `None`
+    pub fn set_icon(&mut self, icon: Option<WindowIcon>) {
+        self.icon = icon.clone();
+        self.command_queue.push(WindowCommand::SetIcon { icon });
+    }
+
     #[inline]
     pub fn mode(&self) -> WindowMode {
         self.mode
@@ -242,9 +597,31 @@ impl Window {
         self.command_queue.push(WindowCommand::SetWindowMode {
             mode,
             resolution: (self.width, self.height),
+            monitor: self.monitor,
         });
     }
 
+    /// The monitor the window is shown on in fullscreen
+    #[inline]
+    pub fn monitor(&self) -> MonitorSelection {
+        self.monitor
+    }
+
+    /// Sets the monitor the window is shown on in fullscreen, moving the window there if it is in
+    /// fullscreen
+    pub fn set_monitor(&mut self, monitor: MonitorSelection) {
+        self.monitor = monitor;
+        if self.mode != WindowMode::Windowed {
+            self.set_mode(self.mode);
+        }
+    }
+
+    /// Closes the window at the end of the frame. Closing the primary window exits the app when
+    /// [WindowPlugin::exit_on_close](crate::WindowPlugin::exit_on_close) is set.
+    pub fn close(&mut self) {
+        self.command_queue.push(WindowCommand::Close);
+    }
+
     #[inline]
     pub fn drain_commands(&mut self) -> impl Iterator<Item = WindowCommand> + '_ {
         self.command_queue.drain(..)
@@ -257,11 +634,24 @@ pub struct WindowDescriptor {
     pub height: u32,
     pub title: String,
     pub vsync: bool,
+    /// The present mode of the window, which is chosen from `vsync` if it isn't set
+    pub present_mode: Option<PresentMode>,
     pub resizable: bool,
+    pub resize_constraints: WindowResizeConstraints,
     pub decorations: bool,
+    /// Makes the window see-through where the clear color and what is drawn aren't opaque, on
+    /// platforms whose compositor blends windows with the desktop. Windows without decorations
+    /// can be used for overlays and widgets.
+    pub transparent: bool,
+    pub always_on_top: bool,
+    /// The position of the top left corner of the window on the desktop in physical pixels, or
+    /// where the platform places it by default
+    pub position: Option<(i32, i32)>,
     pub cursor_visible: bool,
-    pub cursor_locked: bool,
+    pub cursor_grab_mode: CursorGrabMode,
     pub mode: WindowMode,
+    /// The monitor the window is shown on in fullscreen
+    pub monitor: MonitorSelection,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
 }
@@ -273,13 +663,44 @@ impl Default for WindowDescriptor {
             width: 1280,
             height: 720,
             vsync: true,
+            present_mode: None,
             resizable: true,
+            resize_constraints: WindowResizeConstraints::default(),
             decorations: true,
-            cursor_locked: false,
+            transparent: false,
+            always_on_top: false,
+            position: None,
+            cursor_grab_mode: CursorGrabMode::None,
             cursor_visible: true,
             mode: WindowMode::Windowed,
+            monitor: MonitorSelection::Current,
             #[cfg(target_arch = "wasm32")]
             canvas: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::WindowResizeConstraints;
+
+    #[test]
+    fn resize_constraints_keep_the_aspect_ratio() {
+        let resize_constraints = WindowResizeConstraints {
+            min_width: 200.0,
+            min_height: 100.0,
+            max_width: 1000.0,
+            max_height: 800.0,
+            aspect_ratio: Some(2.0),
+        };
+        assert_eq!(resize_constraints.constrain(600.0, 600.0), (600.0, 300.0));
+        assert_eq!(resize_constraints.constrain(100.0, 50.0), (200.0, 100.0));
+        assert_eq!(resize_constraints.constrain(4000.0, 800.0), (1000.0, 500.0));
+
+        let resize_constraints = WindowResizeConstraints {
+            aspect_ratio: None,
+            ..resize_constraints
+        };
+        assert_eq!(resize_constraints.constrain(100.0, 900.0), (200.0, 800.0));
+    }
+}
@@ -11,6 +11,10 @@ impl Windows {
         self.windows.insert(window.id(), window);
     }
 
+    pub fn remove(&mut self, id: WindowId) -> Option<Window> {
+        self.windows.remove(&id)
+    }
+
     pub fn get(&self, id: WindowId) -> Option<&Window> {
         self.windows.get(&id)
     }
@@ -27,6 +31,17 @@ impl Windows {
         self.get_mut(WindowId::primary())
     }
 
+    /// Returns the window that currently has focus, if any.
+    pub fn get_focused(&self) -> Option<&Window> {
+        self.iter().find(|window| window.is_focused())
+    }
+
+    /// The window under the cursor, which input from the mouse is meant for
+    pub fn get_hovered(&self) -> Option<&Window> {
+        self.iter()
+            .find(|window| window.cursor_position().is_some())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Window> {
         self.windows.values()
     }
@@ -12,10 +12,14 @@ pub use winit_windows::*;
 use bevy_app::{prelude::*, AppExit};
 use bevy_ecs::{Resources, World};
 use bevy_math::Vec2;
-use bevy_utils::tracing::{error, trace};
+use bevy_utils::{
+    tracing::{error, trace},
+    Instant,
+};
 use bevy_window::{
-    CreateWindow, CursorMoved, ReceivedCharacter, Window, WindowCloseRequested, WindowCreated,
-    WindowResized, Windows,
+    CreateWindow, CursorGrabMode, CursorMoved, FileDragAndDrop, Monitors, ReceivedCharacter,
+    RequestRedraw, Window, WindowCloseRequested, WindowClosed, WindowCreated, WindowFocused,
+    WindowMode, WindowMoved, WindowResized, Windows,
 };
 use winit::{
     event::{self, DeviceEvent, Event, WindowEvent},
@@ -38,34 +42,29 @@ impl Plugin for WinitPlugin {
 }
 
 fn change_window(_: &mut World, resources: &mut Resources) {
-    let winit_windows = resources.get::<WinitWindows>().unwrap();
+    let mut winit_windows = resources.get_mut::<WinitWindows>().unwrap();
     let mut windows = resources.get_mut::<Windows>().unwrap();
+    let mut closed_windows = Vec::new();
 
     for bevy_window in windows.iter_mut() {
         let id = bevy_window.id();
+        let focused = bevy_window.is_focused();
+        let height = bevy_window.height();
         for command in bevy_window.drain_commands() {
             match command {
                 bevy_window::WindowCommand::SetWindowMode {
                     mode,
                     resolution: (width, height),
+                    monitor,
                 } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    match mode {
-                        bevy_window::WindowMode::BorderlessFullscreen => {
-                            window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
-                        }
-                        bevy_window::WindowMode::Fullscreen { use_size } => window.set_fullscreen(
-                            Some(winit::window::Fullscreen::Exclusive(match use_size {
-                                true => get_fitting_videomode(
-                                    &window.current_monitor().unwrap(),
-                                    width,
-                                    height,
-                                ),
-                                false => get_best_videomode(&window.current_monitor().unwrap()),
-                            })),
-                        ),
-                        bevy_window::WindowMode::Windowed => window.set_fullscreen(None),
-                    }
+                    let monitor = select_monitor(
+                        monitor,
+                        window.current_monitor(),
+                        window.primary_monitor(),
+                        window.available_monitors(),
+                    );
+                    window.set_fullscreen(get_fullscreen(mode, monitor, width, height));
                 }
                 bevy_window::WindowCommand::SetTitle { title } => {
                     let window = winit_windows.get_window(id).unwrap();
@@ -75,24 +74,49 @@ fn change_window(_: &mut World, resources: &mut Resources) {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_inner_size(winit::dpi::LogicalSize::new(width, height));
                 }
-                bevy_window::WindowCommand::SetVsync { .. } => (),
+                // the renderer creates the swap chain again with the new mode
+                bevy_window::WindowCommand::SetPresentMode { .. } => (),
                 bevy_window::WindowCommand::SetResizable { resizable } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_resizable(resizable);
                 }
+                bevy_window::WindowCommand::SetResizeConstraints { resize_constraints } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    set_resize_constraints(window, resize_constraints);
+                    let size = window.inner_size().to_logical::<f32>(window.scale_factor());
+                    let (width, height) = resize_constraints.constrain(size.width, size.height);
+                    if (width, height) != (size.width, size.height) {
+                        window.set_inner_size(winit::dpi::LogicalSize::new(width, height));
+                    }
+                }
+                bevy_window::WindowCommand::SetAlwaysOnTop { always_on_top } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_always_on_top(always_on_top);
+                }
+                bevy_window::WindowCommand::SetPosition { x, y } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+                }
                 bevy_window::WindowCommand::SetDecorations { decorations } => {
                     let window = winit_windows.get_window(id).unwrap();
                     window.set_decorations(decorations);
                 }
-                bevy_window::WindowCommand::SetCursorLockMode { locked } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window
-                        .set_cursor_grab(locked)
-                        .unwrap_or_else(|e| error!("Unable to un/grab cursor: {}", e));
+                bevy_window::WindowCommand::SetCursorGrabMode { mode } => {
+                    // unfocused windows grab the cursor again once they are focused
+                    if focused {
+                        let window = winit_windows.get_window(id).unwrap();
+                        set_cursor_grab(window, mode);
+                    }
                 }
                 bevy_window::WindowCommand::SetCursorVisibility { visible } => {
+                    if focused {
+                        let window = winit_windows.get_window(id).unwrap();
+                        window.set_cursor_visible(visible);
+                    }
+                }
+                bevy_window::WindowCommand::SetCursorIcon { icon } => {
                     let window = winit_windows.get_window(id).unwrap();
-                    window.set_cursor_visible(visible);
+                    window.set_cursor_icon(converters::convert_cursor_icon(icon));
                 }
                 bevy_window::WindowCommand::SetCursorPosition { x, y } => {
                     let window = winit_windows.get_window(id).unwrap();
@@ -100,6 +124,40 @@ fn change_window(_: &mut World, resources: &mut Resources) {
                         .set_cursor_position(winit::dpi::LogicalPosition::new(x, y))
                         .unwrap_or_else(|e| error!("Unable to set cursor position: {}", e));
                 }
+                bevy_window::WindowCommand::SetIcon { icon } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    window.set_window_icon(icon.as_ref().and_then(converters::convert_window_icon));
+                }
+                bevy_window::WindowCommand::Close => closed_windows.push(id),
+                bevy_window::WindowCommand::SetImePosition { position } => {
+                    let window = winit_windows.get_window(id).unwrap();
+                    // move origin to top left
+                    window.set_ime_position(winit::dpi::LogicalPosition::new(
+                        position.x(),
+                        height as f32 - position.y(),
+                    ));
+                }
+            }
+        }
+
+        if focused && bevy_window.cursor_grab_mode() == CursorGrabMode::Locked {
+            let window = winit_windows.get_window(id).unwrap();
+            let center = winit::dpi::LogicalPosition::new(
+                bevy_window.width() as f64 / 2.0,
+                bevy_window.height() as f64 / 2.0,
+            );
+            if let Err(err) = window.set_cursor_position(center) {
+                trace!("Unable to keep the cursor locked: {}", err);
+            }
+        }
+    }
+
+    if !closed_windows.is_empty() {
+        let mut window_closed_events = resources.get_mut::<Events<WindowClosed>>().unwrap();
+        for id in closed_windows {
+            winit_windows.remove_window(id);
+            if windows.remove(id).is_some() {
+                window_closed_events.send(WindowClosed { id });
             }
         }
     }
@@ -155,6 +213,11 @@ pub fn winit_runner(mut app: App) {
 
     app.resources.insert_thread_local(event_loop.create_proxy());
 
+    if let Some(mut monitors) = app.resources.get_mut::<Monitors>() {
+        let (available_monitors, primary) = get_monitors(&event_loop);
+        monitors.update_from_backend(available_monitors, primary);
+    }
+
     // Create Windows and WinitWindows resources, so startup systems
     // in below app.initialize() have access to them.
     handle_create_window_events(
@@ -167,15 +230,23 @@ pub fn winit_runner(mut app: App) {
 
     trace!("Entering winit event loop");
 
-    let should_return_from_run = app
+    let (should_return_from_run, update_mode) = app
         .resources
         .get::<WinitConfig>()
-        .map_or(false, |config| config.return_from_run);
+        .map_or((false, UpdateMode::default()), |config| {
+            (config.return_from_run, config.update_mode)
+        });
+    let mut redraw_event_reader = EventReader::<RequestRedraw>::default();
+    let mut redraw_requested = false;
+    let mut received_events = false;
+    let mut last_update = Instant::now();
 
     let event_handler = move |event: Event<()>,
                               event_loop: &EventLoopWindowTarget<()>,
                               control_flow: &mut ControlFlow| {
-        *control_flow = ControlFlow::Poll;
+        if let Event::WindowEvent { .. } | Event::DeviceEvent { .. } = event {
+            received_events = true;
+        }
 
         if let Some(app_exit_events) = app.resources.get_mut::<Events<AppExit>>() {
             if app_exit_event_reader.latest(&app_exit_events).is_some() {
@@ -184,6 +255,14 @@ pub fn winit_runner(mut app: App) {
         }
 
         match event {
+            // events can still arrive for windows that were closed
+            event::Event::WindowEvent { window_id, .. }
+                if app
+                    .resources
+                    .get::<WinitWindows>()
+                    .unwrap()
+                    .get_window_id(window_id)
+                    .is_none() => {}
             event::Event::WindowEvent {
                 event,
                 window_id: winit_window_id,
@@ -198,6 +277,21 @@ pub fn winit_runner(mut app: App) {
                     let size = size.to_logical(winit_window.scale_factor());
                     window.update_resolution_from_backend(size.width, size.height);
 
+                    // winit doesn't keep the aspect ratio, so the window is resized again to it
+                    let resize_constraints = window.resize_constraints();
+                    if resize_constraints.aspect_ratio.is_some()
+                        && matches!(window.mode(), WindowMode::Windowed)
+                    {
+                        let (width, height) =
+                            resize_constraints.constrain(size.width as f32, size.height as f32);
+                        if (width - size.width as f32).abs() > 1.0
+                            || (height - size.height as f32).abs() > 1.0
+                        {
+                            winit_window
+                                .set_inner_size(winit::dpi::LogicalSize::new(width, height));
+                        }
+                    }
+
                     let mut resize_events =
                         app.resources.get_mut::<Events<WindowResized>>().unwrap();
                     resize_events.send(WindowResized {
@@ -206,6 +300,21 @@ pub fn winit_runner(mut app: App) {
                         width: window.width() as usize,
                     });
                 }
+                WindowEvent::Moved(position) => {
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    let window = windows.get_mut(window_id).unwrap();
+                    let position = (position.x, position.y);
+                    window.update_position_from_backend(Some(position));
+
+                    let mut window_moved_events =
+                        app.resources.get_mut::<Events<WindowMoved>>().unwrap();
+                    window_moved_events.send(WindowMoved {
+                        id: window_id,
+                        position,
+                    });
+                }
                 WindowEvent::CloseRequested => {
                     let mut window_close_requested_events = app
                         .resources
@@ -218,51 +327,108 @@ pub fn winit_runner(mut app: App) {
                 WindowEvent::KeyboardInput { ref input, .. } => {
                     let mut keyboard_input_events =
                         app.resources.get_mut::<Events<KeyboardInput>>().unwrap();
-                    keyboard_input_events.send(converters::convert_keyboard_input(input));
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    keyboard_input_events
+                        .send(converters::convert_keyboard_input(input, window_id));
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     let mut cursor_moved_events =
                         app.resources.get_mut::<Events<CursorMoved>>().unwrap();
                     let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
                     let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
                     let window = winit_windows.get_window(window_id).unwrap();
                     let position = position.to_logical(window.scale_factor());
                     let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
                     // move origin to bottom left
                     let y_position = inner_size.height - position.y;
+                    let position = Vec2::new(position.x, y_position);
+                    windows
+                        .get_mut(window_id)
+                        .unwrap()
+                        .update_cursor_position_from_backend(Some(position));
                     cursor_moved_events.send(CursorMoved {
                         id: window_id,
-                        position: Vec2::new(position.x, y_position),
+                        position,
+                    });
+                }
+                WindowEvent::CursorLeft { .. } => {
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    windows
+                        .get_mut(window_id)
+                        .unwrap()
+                        .update_cursor_position_from_backend(None);
+                }
+                WindowEvent::HoveredFile(path_buf) => {
+                    let mut file_drag_and_drop_events =
+                        app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    file_drag_and_drop_events.send(FileDragAndDrop::HoveredFile {
+                        id: window_id,
+                        path_buf,
+                        position: windows.get(window_id).unwrap().cursor_position(),
                     });
                 }
+                WindowEvent::DroppedFile(path_buf) => {
+                    let mut file_drag_and_drop_events =
+                        app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    file_drag_and_drop_events.send(FileDragAndDrop::DroppedFile {
+                        id: window_id,
+                        path_buf,
+                        position: windows.get(window_id).unwrap().cursor_position(),
+                    });
+                }
+                WindowEvent::HoveredFileCancelled => {
+                    let mut file_drag_and_drop_events =
+                        app.resources.get_mut::<Events<FileDragAndDrop>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    file_drag_and_drop_events
+                        .send(FileDragAndDrop::HoveredFileCancelled { id: window_id });
+                }
                 WindowEvent::MouseInput { state, button, .. } => {
                     let mut mouse_button_input_events =
                         app.resources.get_mut::<Events<MouseButtonInput>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
                     mouse_button_input_events.send(MouseButtonInput {
                         button: converters::convert_mouse_button(button),
                         state: converters::convert_element_state(state),
+                        window_id,
                     });
                 }
-                WindowEvent::MouseWheel { delta, .. } => match delta {
-                    event::MouseScrollDelta::LineDelta(x, y) => {
-                        let mut mouse_wheel_input_events =
-                            app.resources.get_mut::<Events<MouseWheel>>().unwrap();
-                        mouse_wheel_input_events.send(MouseWheel {
-                            unit: MouseScrollUnit::Line,
-                            x,
-                            y,
-                        });
-                    }
-                    event::MouseScrollDelta::PixelDelta(p) => {
-                        let mut mouse_wheel_input_events =
-                            app.resources.get_mut::<Events<MouseWheel>>().unwrap();
-                        mouse_wheel_input_events.send(MouseWheel {
-                            unit: MouseScrollUnit::Pixel,
-                            x: p.x as f32,
-                            y: p.y as f32,
-                        });
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let mut mouse_wheel_input_events =
+                        app.resources.get_mut::<Events<MouseWheel>>().unwrap();
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    match delta {
+                        event::MouseScrollDelta::LineDelta(x, y) => {
+                            mouse_wheel_input_events.send(MouseWheel {
+                                unit: MouseScrollUnit::Line,
+                                x,
+                                y,
+                                window_id,
+                            });
+                        }
+                        event::MouseScrollDelta::PixelDelta(p) => {
+                            mouse_wheel_input_events.send(MouseWheel {
+                                unit: MouseScrollUnit::Pixel,
+                                x: p.x as f32,
+                                y: p.y as f32,
+                                window_id,
+                            });
+                        }
                     }
-                },
+                }
                 WindowEvent::Touch(touch) => {
                     let mut touch_input_events =
                         app.resources.get_mut::<Events<TouchInput>>().unwrap();
@@ -278,7 +444,8 @@ pub fn winit_runner(mut app: App) {
                         let window_height = windows.get_primary().unwrap().height();
                         location.y = window_height as f32 - location.y;
                     }
-                    touch_input_events.send(converters::convert_touch_input(touch, location));
+                    touch_input_events
+                        .send(converters::convert_touch_input(touch, location, window_id));
                 }
                 WindowEvent::ReceivedCharacter(c) => {
                     let mut char_input_events = app
@@ -294,6 +461,30 @@ pub fn winit_runner(mut app: App) {
                         char: c,
                     })
                 }
+                WindowEvent::Focused(focused) => {
+                    let winit_windows = app.resources.get_mut::<WinitWindows>().unwrap();
+                    let mut windows = app.resources.get_mut::<Windows>().unwrap();
+                    let window_id = winit_windows.get_window_id(winit_window_id).unwrap();
+                    let winit_window = winit_windows.get_window(window_id).unwrap();
+                    let window = windows.get_mut(window_id).unwrap();
+                    window.update_focused_from_backend(focused);
+
+                    // the cursor is released and shown while the window isn't focused
+                    if focused {
+                        set_cursor_grab(winit_window, window.cursor_grab_mode());
+                        winit_window.set_cursor_visible(window.cursor_visible());
+                    } else {
+                        set_cursor_grab(winit_window, CursorGrabMode::None);
+                        winit_window.set_cursor_visible(true);
+                    }
+
+                    let mut focused_events =
+                        app.resources.get_mut::<Events<WindowFocused>>().unwrap();
+                    focused_events.send(WindowFocused {
+                        id: window_id,
+                        focused,
+                    });
+                }
                 WindowEvent::ScaleFactorChanged {
                     scale_factor,
                     new_inner_size,
@@ -323,7 +514,36 @@ pub fn winit_runner(mut app: App) {
                     event_loop,
                     &mut create_window_event_reader,
                 );
-                app.update();
+                let should_update = match update_mode {
+                    UpdateMode::Continuous => true,
+                    UpdateMode::Reactive { max_wait } => {
+                        received_events
+                            || redraw_requested
+                            || max_wait.map_or(false, |max_wait| last_update.elapsed() >= max_wait)
+                    }
+                };
+                if should_update {
+                    received_events = false;
+                    last_update = Instant::now();
+                    app.update();
+                    redraw_requested = app
+                        .resources
+                        .get::<Events<RequestRedraw>>()
+                        .map_or(false, |events| {
+                            redraw_event_reader.latest(&events).is_some()
+                        });
+                }
+
+                if *control_flow != ControlFlow::Exit {
+                    *control_flow = match update_mode {
+                        // the event loop sleeps until the next event or the next forced update
+                        UpdateMode::Reactive { max_wait } if !redraw_requested => match max_wait {
+                            Some(max_wait) => ControlFlow::WaitUntil(last_update + max_wait),
+                            None => ControlFlow::Wait,
+                        },
+                        _ => ControlFlow::Poll,
+                    };
+                }
             }
             _ => (),
         }
@@ -1,3 +1,5 @@
+use bevy_utils::Duration;
+
 /// A resource for configuring usage of the `rust_winit` library.
 #[derive(Debug, Default)]
 pub struct WinitConfig {
@@ -12,4 +14,25 @@ pub struct WinitConfig {
     /// `openbsd`. If set to true on an unsupported platform
     /// [run](bevy_app::App::run) will panic.
     pub return_from_run: bool,
+    /// Configures when the app is updated
+    pub update_mode: UpdateMode,
+}
+
+/// When the winit runner updates the app
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateMode {
+    /// The app is updated as often as possible, or as often as the present mode of the windows
+    /// allows
+    Continuous,
+    /// The app is only updated when the windows or input devices send events, when a
+    /// [RequestRedraw](bevy_window::RequestRedraw) event was sent in the last update, or after
+    /// `max_wait` since the last update. This suits editors and other apps that are idle most of
+    /// the time.
+    Reactive { max_wait: Option<Duration> },
+}
+
+impl Default for UpdateMode {
+    fn default() -> Self {
+        UpdateMode::Continuous
+    }
 }
@@ -1,5 +1,8 @@
-use bevy_utils::HashMap;
-use bevy_window::{Window, WindowId, WindowMode};
+use bevy_utils::{tracing::error, HashMap};
+use bevy_window::{
+    CursorGrabMode, Monitor, MonitorSelection, VideoMode, Window, WindowId, WindowMode,
+    WindowResizeConstraints,
+};
 
 #[derive(Debug, Default)]
 pub struct WinitWindows {
@@ -24,30 +27,37 @@ impl WinitWindows {
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
         winit_window_builder = match window.mode() {
-            WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
-            )),
-            WindowMode::Fullscreen { use_size } => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Exclusive(match use_size {
-                    true => get_fitting_videomode(
-                        &event_loop.primary_monitor().unwrap(),
-                        window.width(),
-                        window.height(),
-                    ),
-                    false => get_best_videomode(&event_loop.primary_monitor().unwrap()),
-                }),
-            )),
-            _ => winit_window_builder
+            WindowMode::Windowed => winit_window_builder
                 .with_inner_size(winit::dpi::LogicalSize::new(
                     window.width(),
                     window.height(),
                 ))
                 .with_resizable(window.resizable())
                 .with_decorations(window.decorations()),
+            // the window isn't on a monitor yet
+            mode => winit_window_builder.with_fullscreen(get_fullscreen(
+                mode,
+                select_monitor(
+                    window.monitor(),
+                    None,
+                    event_loop.primary_monitor(),
+                    event_loop.available_monitors(),
+                ),
+                window.width(),
+                window.height(),
+            )),
         };
 
         #[allow(unused_mut)]
-        let mut winit_window_builder = winit_window_builder.with_title(window.title());
+        let mut winit_window_builder = winit_window_builder
+            .with_title(window.title())
+            .with_transparent(window.transparent())
+            .with_always_on_top(window.always_on_top())
+            .with_window_icon(
+                window
+                    .icon()
+                    .and_then(crate::converters::convert_window_icon),
+            );
 
         #[cfg(target_arch = "wasm32")]
         {
@@ -71,12 +81,17 @@ impl WinitWindows {
 
         let winit_window = winit_window_builder.build(&event_loop).unwrap();
 
-        match winit_window.set_cursor_grab(window.cursor_locked()) {
-            Ok(_) => {}
-            Err(winit::error::ExternalError::NotSupported(_)) => {}
-            Err(err) => Err(err).unwrap(),
+        set_cursor_grab(&winit_window, window.cursor_grab_mode());
+        set_resize_constraints(&winit_window, window.resize_constraints());
+        if let Some((x, y)) = window.position() {
+            winit_window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
         }
-
+        window.update_position_from_backend(
+            winit_window
+                .outer_position()
+                .ok()
+                .map(|position| (position.x, position.y)),
+        );
         winit_window.set_cursor_visible(window.cursor_visible());
 
         self.window_id_to_winit
@@ -114,7 +129,142 @@ impl WinitWindows {
     pub fn get_window_id(&self, id: winit::window::WindowId) -> Option<WindowId> {
         self.winit_to_window_id.get(&id).cloned()
     }
+
+    /// Removes a window, which closes it once it is dropped
+    pub fn remove_window(&mut self, id: WindowId) -> Option<winit::window::Window> {
+        let winit_id = self.window_id_to_winit.remove(&id)?;
+        self.winit_to_window_id.remove(&winit_id);
+        self.windows.remove(&winit_id)
+    }
+}
+/// Grabs the cursor of a winit window for a [CursorGrabMode]. Locked cursors are grabbed like
+/// confined ones, and are moved back to the center of the window every frame.
+pub fn set_cursor_grab(winit_window: &winit::window::Window, mode: CursorGrabMode) {
+    match winit_window.set_cursor_grab(mode != CursorGrabMode::None) {
+        Ok(_) => {}
+        Err(winit::error::ExternalError::NotSupported(_)) => {}
+        Err(err) => error!("Unable to un/grab cursor: {}", err),
+    }
+}
+
+/// Limits the size of a window to its resize constraints. The aspect ratio is kept when the
+/// window is resized.
+pub fn set_resize_constraints(
+    winit_window: &winit::window::Window,
+    resize_constraints: WindowResizeConstraints,
+) {
+    let size = |width: f32, height: f32| {
+        if width.is_finite() && height.is_finite() {
+            Some(winit::dpi::LogicalSize::new(width, height))
+        } else {
+            None
+        }
+    };
+    winit_window.set_min_inner_size(size(
+        resize_constraints.min_width,
+        resize_constraints.min_height,
+    ));
+    winit_window.set_max_inner_size(size(
+        resize_constraints.max_width,
+        resize_constraints.max_height,
+    ));
 }
+
+/// Finds the monitor selected for a window. The primary monitor is used when the selected monitor
+/// isn't found.
+pub fn select_monitor(
+    selection: MonitorSelection,
+    current: Option<winit::monitor::MonitorHandle>,
+    primary: Option<winit::monitor::MonitorHandle>,
+    mut available: impl Iterator<Item = winit::monitor::MonitorHandle>,
+) -> Option<winit::monitor::MonitorHandle> {
+    let selected = match selection {
+        MonitorSelection::Current => current,
+        MonitorSelection::Primary => None,
+        MonitorSelection::Index(index) => available.nth(index),
+    };
+    selected.or(primary)
+}
+
+/// The fullscreen state of a window in a fullscreen mode on a monitor
+pub fn get_fullscreen(
+    mode: WindowMode,
+    monitor: Option<winit::monitor::MonitorHandle>,
+    width: u32,
+    height: u32,
+) -> Option<winit::window::Fullscreen> {
+    match mode {
+        WindowMode::Windowed => None,
+        WindowMode::BorderlessFullscreen => Some(winit::window::Fullscreen::Borderless(monitor)),
+        WindowMode::Fullscreen { use_size } => {
+            let monitor = monitor?;
+            Some(winit::window::Fullscreen::Exclusive(match use_size {
+                true => get_fitting_videomode(&monitor, width, height),
+                false => get_best_videomode(&monitor),
+            }))
+        }
+        WindowMode::ExclusiveFullscreen { video_mode } => {
+            let monitor = monitor?;
+            let winit_video_mode = monitor
+                .video_modes()
+                .find(|winit_video_mode| convert_video_mode(winit_video_mode) == video_mode);
+            Some(winit::window::Fullscreen::Exclusive(
+                winit_video_mode.unwrap_or_else(|| {
+                    get_fitting_videomode(&monitor, video_mode.width, video_mode.height)
+                }),
+            ))
+        }
+    }
+}
+
+pub fn convert_video_mode(video_mode: &winit::monitor::VideoMode) -> VideoMode {
+    VideoMode {
+        width: video_mode.size().width,
+        height: video_mode.size().height,
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate: video_mode.refresh_rate(),
+    }
+}
+
+/// Lists the monitors and their video modes, with the index of the primary monitor
+pub fn get_monitors(
+    event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
+) -> (Vec<Monitor>, Option<usize>) {
+    let primary = event_loop.primary_monitor();
+    let mut primary_index = None;
+    let monitors = event_loop
+        .available_monitors()
+        .enumerate()
+        .map(|(index, monitor)| {
+            if Some(&monitor) == primary.as_ref() {
+                primary_index = Some(index);
+            }
+            let mut video_modes = monitor
+                .video_modes()
+                .map(|video_mode| convert_video_mode(&video_mode))
+                .collect::<Vec<_>>();
+            video_modes.sort_by(|a, b| {
+                (b.width, b.height, b.refresh_rate, b.bit_depth).cmp(&(
+                    a.width,
+                    a.height,
+                    a.refresh_rate,
+                    a.bit_depth,
+                ))
+            });
+            video_modes.dedup();
+            Monitor {
+                name: monitor.name(),
+                position: (monitor.position().x, monitor.position().y),
+                width: monitor.size().width,
+                height: monitor.size().height,
+                scale_factor: monitor.scale_factor(),
+                video_modes,
+            }
+        })
+        .collect();
+    (monitors, primary_index)
+}
+
 pub fn get_fitting_videomode(
     monitor: &winit::monitor::MonitorHandle,
     width: u32,
@@ -120,6 +120,7 @@ fn setup(
                     color: Color::WHITE,
                     ..Default::default()
                 },
+                ..Default::default()
             },
             ..Default::default()
         });
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+/// This example illustrates text in the 2d world: labels aligned to a moving sprite, a paragraph
+/// wrapped in a box, and a speech bubble sized to the text it holds.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_system)
+        .add_system(speech_bubble_system)
+        .run();
+}
+
+/// A sprite sized to fit the text of its child, with `padding` around the text
+struct SpeechBubble {
+    padding: f32,
+}
+
+struct Moving;
+
+fn move_system(time: Res<Time>, mut query: Query<&mut Transform, With<Moving>>) {
+    let x = (time.seconds_since_startup() as f32).sin() * 200.0;
+    for mut transform in query.iter_mut() {
+        transform.translation.x = x;
+    }
+}
+
+fn speech_bubble_system(
+    mut text_pipeline: ResMut<DefaultTextPipeline>,
+    fonts: Res<Assets<Font>>,
+    mut bubble_query: Query<(&SpeechBubble, &mut Sprite, &Children)>,
+    text_query: Query<(&Text, &Text2dBounds)>,
+) {
+    for (bubble, mut sprite, children) in bubble_query.iter_mut() {
+        for child in children.iter() {
+            if let Ok((text, bounds)) = text_query.get(*child) {
+                // the text can only be measured once its font is loaded
+                if let Ok(size) = text_pipeline.measure_text(
+                    &text.all_sections(),
+                    &text.style.fallback_fonts,
+                    &fonts,
+                    text.style.wrap,
+                    bounds.size,
+                ) {
+                    sprite.size = Vec2::new(
+                        size.width + 2.0 * bubble.padding,
+                        size.height + 2.0 * bubble.padding,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let label = |value: &str, horizontal: HorizontalAlign, vertical: VerticalAlign| Text {
+        value: value.to_string(),
+        font: font.clone(),
+        style: TextStyle {
+            font_size: 20.0,
+            color: Color::WHITE,
+            alignment: TextAlignment {
+                horizontal,
+                vertical,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    commands
+        .spawn(Camera2dBundle::default())
+        // the labels are anchored on the corners of the sprite
+        .spawn(SpriteBundle {
+            material: materials.add(Color::rgb(0.3, 0.3, 0.8).into()),
+            sprite: Sprite::new(Vec2::new(100.0, 100.0)),
+            transform: Transform::from_translation(Vec3::new(0.0, 150.0, 0.0)),
+            ..Default::default()
+        })
+        .with(Moving)
+        .with_children(|parent| {
+            parent
+                .spawn(Text2dBundle {
+                    text: label("top right", HorizontalAlign::Left, VerticalAlign::Bottom),
+                    transform: Transform::from_translation(Vec3::new(50.0, 50.0, 1.0)),
+                    ..Default::default()
+                })
+                .spawn(Text2dBundle {
+                    text: label("center", HorizontalAlign::Center, VerticalAlign::Center),
+                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+                    ..Default::default()
+                })
+                .spawn(Text2dBundle {
+                    text: label("bottom left", HorizontalAlign::Right, VerticalAlign::Top),
+                    transform: Transform::from_translation(Vec3::new(-50.0, -50.0, 1.0)),
+                    ..Default::default()
+                });
+        })
+        // a paragraph wrapped in a 300 pixel wide box, with its top center at the origin
+        .spawn(Text2dBundle {
+            text: Text {
+                value: "Text in the 2d world can be wrapped in a box, so that long descriptions \
+                        of the things in a game stay next to them."
+                    .to_string(),
+                font: font.clone(),
+                style: TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    alignment: TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        vertical: VerticalAlign::Top,
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            bounds: Text2dBounds {
+                size: Size::new(300.0, f32::MAX),
+            },
+            transform: Transform::from_translation(Vec3::new(0.0, -50.0, 0.0)),
+            ..Default::default()
+        })
+        // a speech bubble that grows to fit its text
+        .spawn(SpriteBundle {
+            material: materials.add(Color::rgb(0.9, 0.9, 0.9).into()),
+            sprite: Sprite::new(Vec2::zero()),
+            transform: Transform::from_translation(Vec3::new(0.0, -250.0, 0.0)),
+            ..Default::default()
+        })
+        .with(SpeechBubble { padding: 10.0 })
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text {
+                    value: "Hello there!\nThis bubble is as big as its text.".to_string(),
+                    font,
+                    style: TextStyle {
+                        font_size: 24.0,
+                        color: Color::BLACK,
+                        alignment: TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            vertical: VerticalAlign::Center,
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+                ..Default::default()
+            });
+        });
+}
@@ -0,0 +1,121 @@
+use bevy::{
+    animation::{Interpolation, Keyframes, VariableCurve},
+    prelude::*,
+};
+
+/// This example plays a keyframe animation on a cube and its child, and pauses it with space
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(pause_system)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut animation_clips: ResMut<Assets<AnimationClip>>,
+) {
+    // the cube moves around a square and turns, and the small cube on it grows and shrinks
+    let mut animation_clip = AnimationClip::default();
+    animation_clip.add_curve_to_path(
+        EntityPath::from(vec!["cube"]),
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            keyframes: Keyframes::Translation(vec![
+                Vec3::new(-1.0, 0.5, -1.0),
+                Vec3::new(1.0, 0.5, -1.0),
+                Vec3::new(1.0, 0.5, 1.0),
+                Vec3::new(-1.0, 0.5, 1.0),
+                Vec3::new(-1.0, 0.5, -1.0),
+            ]),
+            interpolation: Interpolation::Linear,
+        },
+    );
+    animation_clip.add_curve_to_path(
+        EntityPath::from(vec!["cube"]),
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 2.0, 4.0],
+            keyframes: Keyframes::Rotation(vec![
+                Quat::identity(),
+                Quat::from_rotation_y(std::f32::consts::PI),
+                Quat::from_rotation_y(2.0 * std::f32::consts::PI),
+            ]),
+            interpolation: Interpolation::Linear,
+        },
+    );
+    animation_clip.add_curve_to_path(
+        EntityPath::from(vec!["cube", "top"]),
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 2.0, 3.0, 4.0],
+            keyframes: Keyframes::Scale(vec![
+                Vec3::splat(0.25),
+                Vec3::splat(0.5),
+                Vec3::splat(0.25),
+                Vec3::splat(0.5),
+                Vec3::splat(0.25),
+            ]),
+            interpolation: Interpolation::Step,
+        },
+    );
+    let mut animation_player = AnimationPlayer::default();
+    animation_player
+        .play(animation_clips.add(animation_clip))
+        .set_looping(true);
+
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    commands
+        .spawn((
+            Transform::default(),
+            GlobalTransform::default(),
+            animation_player,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(PbrBundle {
+                    mesh: cube.clone(),
+                    material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+                    ..Default::default()
+                })
+                .with(Name::new("cube"))
+                .with_children(|parent| {
+                    parent
+                        .spawn(PbrBundle {
+                            mesh: cube.clone(),
+                            material: materials.add(Color::rgb(0.6, 0.7, 0.8).into()),
+                            transform: Transform::from_translation(Vec3::new(0.0, 0.75, 0.0)),
+                            ..Default::default()
+                        })
+                        .with(Name::new("top"));
+                });
+        })
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(5.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-3.0, 4.0, 6.0))
+                .looking_at(Vec3::zero(), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn pause_system(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut AnimationPlayer>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        for mut animation_player in query.iter_mut() {
+            if animation_player.is_paused() {
+                animation_player.resume();
+            } else {
+                animation_player.pause();
+            }
+        }
+    }
+}
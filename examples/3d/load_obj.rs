@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+/// This example loads an OBJ file whose parts use different MTL materials
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(commands: &mut Commands, asset_server: Res<AssetServer>) {
+    // the scene draws the faces of each `usemtl` with their material
+    commands
+        .spawn_scene(asset_server.load("models/obj/cube.obj#Scene"))
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-2.0, 2.0, 3.0))
+                .looking_at(Vec3::zero(), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+/// This example shows how to find the meshes under the cursor
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(highlight_system)
+        .add_system(click_system)
+        .run();
+}
+
+struct Materials {
+    normal: Handle<StandardMaterial>,
+    hovered: Handle<StandardMaterial>,
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let normal = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    let hovered = materials.add(Color::rgb(0.9, 0.3, 0.3).into());
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let sphere = meshes.add(Mesh::from(shape::Icosphere {
+        radius: 0.6,
+        subdivisions: 4,
+    }));
+
+    commands
+        .spawn(PbrBundle {
+            mesh: cube,
+            material: normal.clone(),
+            transform: Transform::from_translation(Vec3::new(-1.0, 0.5, 0.0)),
+            ..Default::default()
+        })
+        // only entities with a PickableMesh can be picked
+        .with(PickableMesh)
+        .spawn(PbrBundle {
+            mesh: sphere,
+            material: normal.clone(),
+            transform: Transform::from_translation(Vec3::new(1.0, 0.6, 0.0)),
+            ..Default::default()
+        })
+        .with(PickableMesh)
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 2.5, 5.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        })
+        // rays are cast from the camera with a PickingCamera
+        .with(PickingCamera);
+
+    commands.insert_resource(Materials { normal, hovered });
+}
+
+fn highlight_system(
+    mut pointer_over_reader: Local<EventReader<PointerOver>>,
+    mut pointer_out_reader: Local<EventReader<PointerOut>>,
+    pointer_over_events: Res<Events<PointerOver>>,
+    pointer_out_events: Res<Events<PointerOut>>,
+    materials: Res<Materials>,
+    mut query: Query<&mut Handle<StandardMaterial>>,
+) {
+    for event in pointer_out_reader.iter(&pointer_out_events) {
+        if let Ok(mut material) = query.get_mut(event.entity) {
+            *material = materials.normal.clone();
+        }
+    }
+    for event in pointer_over_reader.iter(&pointer_over_events) {
+        if let Ok(mut material) = query.get_mut(event.entity) {
+            *material = materials.hovered.clone();
+        }
+    }
+}
+
+fn click_system(
+    mut pointer_click_reader: Local<EventReader<PointerClick>>,
+    pointer_click_events: Res<Events<PointerClick>>,
+) {
+    for event in pointer_click_reader.iter(&pointer_click_events) {
+        println!(
+            "clicked {:?} at {:?}, surface normal {:?}",
+            event.entity, event.intersection.position, event.intersection.normal
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+/// This example shows the primitive shapes meshes can be made from, turning slowly
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotate)
+        .run();
+}
+
+struct Shape;
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shapes = vec![
+        meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        meshes.add(Mesh::from(shape::Icosphere {
+            radius: 0.6,
+            subdivisions: 4,
+        })),
+        meshes.add(Mesh::from(shape::UVSphere {
+            radius: 0.6,
+            ..Default::default()
+        })),
+        meshes.add(Mesh::from(shape::Capsule::default())),
+        meshes.add(Mesh::from(shape::Torus {
+            radius: 0.6,
+            ring_radius: 0.25,
+            ..Default::default()
+        })),
+        meshes.add(Mesh::from(shape::Cylinder::default())),
+        meshes.add(Mesh::from(shape::Cone::default())),
+    ];
+    let material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+
+    let spacing = 1.75;
+    let start = -spacing * (shapes.len() - 1) as f32 / 2.0;
+    for (index, mesh) in shapes.into_iter().enumerate() {
+        commands
+            .spawn(PbrBundle {
+                mesh,
+                material: material.clone(),
+                transform: Transform::from_translation(Vec3::new(
+                    start + index as f32 * spacing,
+                    1.0,
+                    0.0,
+                )),
+                ..Default::default()
+            })
+            .with(Shape);
+    }
+
+    commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(50.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 4.0, 9.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Shape>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotation = Quat::from_rotation_x(0.3)
+            * Quat::from_rotation_y(time.seconds_since_startup() as f32 * 0.5);
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+/// This example bends a cylinder with the two joints of a skin
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(bend_system)
+        .run();
+}
+
+/// The joint that bends the upper half of the cylinder
+struct Bend;
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut mesh = Mesh::from(shape::Cylinder {
+        radius: 0.25,
+        height: 2.0,
+        segments: 16,
+        ..Default::default()
+    });
+    // the vertices follow the lower joint at the bottom and the upper joint at the top
+    let weights = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float3(positions)) => positions
+            .iter()
+            .map(|position| {
+                let upper = ((position[1] + 1.0) / 2.0).max(0.0).min(1.0);
+                [1.0 - upper, upper, 0.0, 0.0]
+            })
+            .collect::<Vec<[f32; 4]>>(),
+        _ => panic!("the cylinder has no positions"),
+    };
+    mesh.set_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        vec![[0u32, 1, 0, 0]; weights.len()],
+    );
+    mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, weights);
+
+    // the joints are placed at the bottom and in the middle of the cylinder
+    let lower_joint = commands
+        .spawn((Transform::default(), GlobalTransform::default()))
+        .current_entity()
+        .unwrap();
+    let upper_joint = commands
+        .spawn((
+            Transform::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+            GlobalTransform::default(),
+            Bend,
+        ))
+        .current_entity()
+        .unwrap();
+    commands.push_children(lower_joint, &[upper_joint]);
+
+    commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(mesh),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+            ..Default::default()
+        })
+        // the inverse bind matrices undo the transforms of the joints in the modeled pose,
+        // relative to the mesh
+        .with(Skin {
+            joints: vec![lower_joint, upper_joint],
+            inverse_bind_matrices: vec![
+                Mat4::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+                Mat4::identity(),
+            ],
+        })
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(5.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-2.0, 2.5, 5.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
+
+fn bend_system(time: Res<Time>, mut query: Query<&mut Transform, With<Bend>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotation = Quat::from_rotation_z(time.seconds_since_startup().sin() as f32);
+    }
+}
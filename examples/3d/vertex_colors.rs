@@ -0,0 +1,50 @@
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+/// This example shows how to color the vertices of a mesh
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // color each vertex of a sphere by the direction it faces
+    let mut sphere = Mesh::from(shape::UVSphere::default());
+    let colors = match sphere.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float3(normals)) => normals
+            .iter()
+            .map(|[x, y, z]| [x * 0.5 + 0.5, y * 0.5 + 0.5, z * 0.5 + 0.5, 1.0])
+            .collect::<Vec<[f32; 4]>>(),
+        _ => panic!("the sphere has no normals"),
+    };
+    sphere.set_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+
+    commands
+        // the vertex colors are multiplied into the color of the material
+        .spawn(PbrBundle {
+            mesh: meshes.add(sphere),
+            material: materials.add(Color::WHITE.into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+            ..Default::default()
+        })
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(5.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-2.0, 2.5, 5.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        });
+}
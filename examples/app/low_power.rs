@@ -0,0 +1,34 @@
+use bevy::{
+    prelude::*,
+    utils::Duration,
+    winit::{UpdateMode, WinitConfig},
+};
+
+/// This example only updates the app when there is input, when the window changes, or once per
+/// second otherwise. The number of updates is shown in the title of the window. Holding space
+/// asks for updates continuously, like an animation would.
+fn main() {
+    App::build()
+        .add_resource(WinitConfig {
+            update_mode: UpdateMode::Reactive {
+                max_wait: Some(Duration::from_secs(1)),
+            },
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_system(count_updates)
+        .add_system(request_redraw)
+        .run();
+}
+
+fn count_updates(mut updates: Local<u64>, mut windows: ResMut<Windows>) {
+    *updates += 1;
+    let window = windows.get_primary_mut().unwrap();
+    window.set_title(format!("{} updates", *updates));
+}
+
+fn request_redraw(input: Res<Input<KeyCode>>, mut redraw_events: ResMut<Events<RequestRedraw>>) {
+    if input.pressed(KeyCode::Space) {
+        redraw_events.send(RequestRedraw);
+    }
+}
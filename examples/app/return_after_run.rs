@@ -5,6 +5,7 @@ fn main() {
     App::build()
         .add_resource(WinitConfig {
             return_from_run: true,
+            ..Default::default()
         })
         .add_resource(ClearColor(Color::rgb(0.2, 0.2, 0.8)))
         .add_plugins(DefaultPlugins)
@@ -13,6 +14,7 @@ fn main() {
     App::build()
         .add_resource(WinitConfig {
             return_from_run: true,
+            ..Default::default()
         })
         .add_resource(ClearColor(Color::rgb(0.2, 0.8, 0.2)))
         .add_plugins(DefaultPlugins)
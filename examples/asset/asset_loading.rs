@@ -32,6 +32,14 @@ fn setup(
     // You can load all assets in a folder like this. They will be loaded in parallel without blocking
     let _scenes: Vec<HandleUntyped> = asset_server.load_folder("models/monkey").unwrap();
 
+    // Folder loads can also be filtered by extension or glob pattern, which returns typed handles:
+    let _monkey_scenes: Vec<Handle<Scene>> = asset_server
+        .load_folder_filtered(
+            "models",
+            &FolderFilter::default().with_pattern("**/Monkey.gltf"),
+        )
+        .unwrap();
+
     // Then any asset in the folder can be accessed like this:
     let monkey_handle = asset_server.get_handle("models/monkey/Monkey.gltf#Mesh0/Primitive0");
 
@@ -2,7 +2,7 @@ use bevy::{
     asset::{AssetLoader, LoadContext, LoadedAsset},
     prelude::*,
     reflect::TypeUuid,
-    utils::BoxedFuture,
+    utils::{BoxedFuture, Uuid},
 };
 use serde::Deserialize;
 
@@ -31,6 +31,10 @@ impl AssetLoader for CustomAssetLoader {
     fn extensions(&self) -> &[&str] {
         &["custom"]
     }
+
+    fn asset_type_uuid(&self) -> Option<Uuid> {
+        Some(CustomAsset::TYPE_UUID)
+    }
 }
 
 fn main() {
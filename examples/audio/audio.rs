@@ -1,14 +1,28 @@
 use bevy::prelude::*;
 
-/// This example illustrates how to load and play an audio file
+/// This example illustrates how to load and loop an audio file, and pause it with the space key
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup)
+        .add_system(pause)
         .run();
 }
 
-fn setup(asset_server: Res<AssetServer>, audio: Res<Audio>) {
+struct Music(AudioSink);
+
+fn setup(commands: &mut Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
     let music = asset_server.load("sounds/Windless Slopes.mp3");
-    audio.play(music);
+    commands.insert_resource(Music(audio.play_looped(music)));
+}
+
+fn pause(keyboard_input: Res<Input<KeyCode>>, music: Res<Music>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if music.0.is_paused() {
+            music.0.resume();
+        } else {
+            music.0.pause();
+        }
+        info!("position: {:?}", music.0.position());
+    }
 }
@@ -0,0 +1,27 @@
+use bevy::{audio::ProceduralAudio, prelude::*};
+use std::f32::consts::PI;
+
+/// This example illustrates how to play audio that is generated while it plays
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(mut sounds: ResMut<Assets<ProceduralAudio>>, audio: Res<Audio<ProceduralAudio>>) {
+    const SAMPLE_RATE: u32 = 44100;
+    // a two second 440 Hz tone that fades out
+    let tone = ProceduralAudio::from_generator(1, SAMPLE_RATE, || {
+        let mut sample_index = 0;
+        move || {
+            let time = sample_index as f32 / SAMPLE_RATE as f32;
+            sample_index += 1;
+            if time > 2.0 {
+                return None;
+            }
+            Some((time * 440.0 * 2.0 * PI).sin() * (1.0 - time / 2.0) * 0.5)
+        }
+    });
+    audio.play(sounds.add(tone));
+}
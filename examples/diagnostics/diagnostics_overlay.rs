@@ -0,0 +1,36 @@
+use bevy::{
+    diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+/// This example illustrates showing diagnostics on screen. Press F3 to show or hide them.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        // Adds frame time and entity count diagnostics
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(EntityCountDiagnosticsPlugin::default())
+        // Shows the diagnostics in the debug overlay
+        .add_plugin(DiagnosticsOverlayPlugin::default())
+        .add_startup_system(setup)
+        .add_system(toggle_system)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    commands.spawn(UiCameraBundle::default());
+    overlay.font = Some(asset_server.load("fonts/FiraMono-Medium.ttf"));
+}
+
+fn toggle_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut diagnostics_overlay: ResMut<DiagnosticsOverlay>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        diagnostics_overlay.shown = !diagnostics_overlay.shown;
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+/// This example moves two sprites back and forth in fixed steps of 10 per second. The top one
+/// jumps from step to step, while the bottom one is interpolated between its last two steps and
+/// moves smoothly.
+fn main() {
+    App::build()
+        .add_resource(FixedTime::from_seconds(0.1))
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(FixedUpdate::new().with_system(move_sprites.system()))
+        .run();
+}
+
+struct Mover {
+    speed: f32,
+}
+
+fn setup(commands: &mut Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    let material = materials.add(Color::rgb(0.3, 0.5, 0.9).into());
+    let sprite = |y: f32| SpriteBundle {
+        material: material.clone(),
+        sprite: Sprite::new(Vec2::new(50.0, 50.0)),
+        transform: Transform::from_translation(Vec3::new(0.0, y, 0.0)),
+        ..Default::default()
+    };
+    commands
+        .spawn(Camera2dBundle::default())
+        .spawn(sprite(100.0))
+        .with(Mover { speed: 300.0 })
+        .spawn(sprite(-100.0))
+        .with(Mover { speed: 300.0 })
+        .with(TransformInterpolation::new(Transform::from_translation(
+            Vec3::new(0.0, -100.0, 0.0),
+        )));
+}
+
+fn move_sprites(
+    fixed_time: Res<FixedTime>,
+    mut query: Query<(
+        &mut Mover,
+        &mut Transform,
+        Option<&mut TransformInterpolation>,
+    )>,
+) {
+    for (mut mover, mut transform, interpolation) in query.iter_mut() {
+        // interpolated sprites are moved from their current step
+        let mut next = match &interpolation {
+            Some(interpolation) => interpolation.current,
+            None => *transform,
+        };
+        *next.translation.x_mut() += mover.speed * fixed_time.step_seconds();
+        if next.translation.x().abs() > 300.0 {
+            mover.speed = -mover.speed;
+        }
+        match interpolation {
+            Some(mut interpolation) => interpolation.set(next),
+            None => *transform = next,
+        }
+    }
+}
@@ -75,6 +75,7 @@ fn setup(
                     font_size: 40.0,
                     ..Default::default()
                 },
+                ..Default::default()
             },
             style: Style {
                 position_type: PositionType::Absolute,
@@ -1,33 +1,31 @@
 use bevy::{
-    input::gamepad::{Gamepad, GamepadButton, GamepadEvent, GamepadEventType},
+    input::gamepad::{GamepadButton, GamepadEvent, GamepadEventType},
     prelude::*,
-    utils::HashSet,
 };
 
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
-        .init_resource::<GamepadLobby>()
         .add_system_to_stage(stage::PRE_UPDATE, connection_system)
         .add_system(gamepad_system)
         .run();
 }
 
-#[derive(Default)]
-struct GamepadLobby {
-    gamepads: HashSet<Gamepad>,
-    gamepad_event_reader: EventReader<GamepadEvent>,
-}
-
-fn connection_system(mut lobby: ResMut<GamepadLobby>, gamepad_event: Res<Events<GamepadEvent>>) {
-    for event in lobby.gamepad_event_reader.iter(&gamepad_event) {
+fn connection_system(
+    mut gamepad_event_reader: Local<EventReader<GamepadEvent>>,
+    gamepad_event: Res<Events<GamepadEvent>>,
+    gamepads: Res<Gamepads>,
+) {
+    for event in gamepad_event_reader.iter(&gamepad_event) {
         match &event {
             GamepadEvent(gamepad, GamepadEventType::Connected) => {
-                lobby.gamepads.insert(*gamepad);
-                println!("{:?} Connected", gamepad);
+                println!(
+                    "{:?} Connected: {}",
+                    gamepad,
+                    gamepads.name(*gamepad).unwrap_or("unknown gamepad")
+                );
             }
             GamepadEvent(gamepad, GamepadEventType::Disconnected) => {
-                lobby.gamepads.remove(gamepad);
                 println!("{:?} Disconnected", gamepad);
             }
             _ => (),
@@ -36,16 +34,16 @@ fn connection_system(mut lobby: ResMut<GamepadLobby>, gamepad_event: Res<Events<
 }
 
 fn gamepad_system(
-    lobby: Res<GamepadLobby>,
+    gamepads: Res<Gamepads>,
     button_inputs: Res<Input<GamepadButton>>,
     button_axes: Res<Axis<GamepadButton>>,
     axes: Res<Axis<GamepadAxis>>,
 ) {
-    for gamepad in lobby.gamepads.iter().cloned() {
+    for (player, gamepad) in gamepads.iter().cloned().enumerate() {
         if button_inputs.just_pressed(GamepadButton(gamepad, GamepadButtonType::South)) {
-            println!("{:?} just pressed South", gamepad);
+            println!("Player {} ({:?}) just pressed South", player + 1, gamepad);
         } else if button_inputs.just_released(GamepadButton(gamepad, GamepadButtonType::South)) {
-            println!("{:?} just released South", gamepad);
+            println!("Player {} ({:?}) just released South", player + 1, gamepad);
         }
 
         let right_trigger = button_axes
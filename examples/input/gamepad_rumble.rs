@@ -0,0 +1,49 @@
+use bevy::{
+    input::gamepad::{GamepadButton, GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+    utils::Duration,
+};
+
+/// This example illustrates rumbling gamepads: South rumbles the strong motor, East the weak
+/// motor, North both motors for a long time and West stops the rumbles.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_system(gamepad_system)
+        .run();
+}
+
+fn gamepad_system(
+    gamepads: Res<Gamepads>,
+    button_inputs: Res<Input<GamepadButton>>,
+    mut rumble_requests: ResMut<Events<GamepadRumbleRequest>>,
+) {
+    for gamepad in gamepads.iter().cloned() {
+        let just_pressed =
+            |button_type| button_inputs.just_pressed(GamepadButton(gamepad, button_type));
+        if just_pressed(GamepadButtonType::South) {
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity: GamepadRumbleIntensity::strong_motor(0.8),
+                duration: Duration::from_millis(300),
+            });
+        }
+        if just_pressed(GamepadButtonType::East) {
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity: GamepadRumbleIntensity::weak_motor(0.8),
+                duration: Duration::from_millis(300),
+            });
+        }
+        if just_pressed(GamepadButtonType::North) {
+            rumble_requests.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity: GamepadRumbleIntensity::MAX,
+                duration: Duration::from_secs(5),
+            });
+        }
+        if just_pressed(GamepadButtonType::West) {
+            rumble_requests.send(GamepadRumbleRequest::Stop { gamepad });
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+/// This example locks the cursor in the window and prints the raw motion of the mouse, which
+/// keeps being reported while the cursor can't move
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(lock_cursor)
+        .add_system(mouse_motion_system)
+        .run();
+}
+
+fn lock_cursor(mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    window.set_cursor_grab_mode(CursorGrabMode::Locked);
+    window.set_cursor_visibility(false);
+}
+
+fn mouse_motion_system(accumulated_mouse_motion: Res<AccumulatedMouseMotion>) {
+    if accumulated_mouse_motion.delta != Vec2::zero() {
+        println!("mouse moved by {:?}", accumulated_mouse_motion.delta);
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+/// This example moves with the keys in the place of WASD on a US keyboard, whatever the layout
+/// of the keyboard is, and prints the key codes they have in the current layout
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_system(movement_system)
+        .run();
+}
+
+fn movement_system(scan_code_input: Res<Input<ScanCode>>, keyboard_layout: Res<KeyboardLayout>) {
+    let directions = [
+        (KeyCode::W, "forward"),
+        (KeyCode::A, "left"),
+        (KeyCode::S, "back"),
+        (KeyCode::D, "right"),
+    ];
+    for (us_key_code, direction) in directions.iter() {
+        let scan_code = match ScanCode::from_us_layout(*us_key_code) {
+            Some(scan_code) => scan_code,
+            None => continue,
+        };
+        if scan_code_input.just_pressed(scan_code) {
+            println!(
+                "moving {} with {:?}",
+                direction,
+                keyboard_layout.key_code(scan_code)
+            );
+        }
+    }
+}
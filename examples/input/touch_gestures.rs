@@ -0,0 +1,23 @@
+use bevy::{input::touch_gesture::TouchGesture, prelude::*};
+
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_system(touch_gesture_system)
+        .run();
+}
+
+#[derive(Default)]
+struct State {
+    event_reader: EventReader<TouchGesture>,
+}
+
+fn touch_gesture_system(mut state: Local<State>, gestures: Res<Events<TouchGesture>>) {
+    for gesture in state.event_reader.iter(&gestures) {
+        match gesture {
+            TouchGesture::Tap { position } => println!("Tap at {}", position),
+            TouchGesture::Pinch { scale, .. } => println!("Pinch by {:.3}", scale),
+            TouchGesture::Rotate { angle, .. } => println!("Rotate by {:.3} radians", angle),
+        }
+    }
+}
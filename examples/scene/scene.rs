@@ -56,7 +56,8 @@ fn load_scene_system(asset_server: Res<AssetServer>, mut scene_spawner: ResMut<S
     scene_spawner.spawn_dynamic(scene_handle);
 
     // This tells the AssetServer to watch for changes to assets.
-    // It enables our scenes to automatically reload in game when we modify their files
+    // It enables our scenes to automatically reload in game when we modify their files. Spawned scenes are updated in place:
+    // entities and components removed from the file are removed, and the other entities of the World are left as they are.
     asset_server.watch_for_changes().unwrap();
 }
 
@@ -92,6 +93,21 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
     // Scenes can be serialized like this:
     println!("{}", scene.serialize_ron(&type_registry).unwrap());
 
+    // Large scenes can use the more compact binary format instead:
+    let binary_scene = scene.serialize_binary(&type_registry).unwrap();
+    println!("binary scene size: {} bytes", binary_scene.len());
+
+    // Scenes can also be built from some of the entities of a World, with some of their components
+    let entities = world
+        .query::<(Entity, &ComponentB)>()
+        .map(|(entity, _component_b)| entity)
+        .collect::<Vec<_>>();
+    let filtered_scene = DynamicSceneBuilder::new(&world, &type_registry)
+        .with_entities(entities)
+        .deny::<Transform>()
+        .build();
+    println!("{}", filtered_scene.serialize_ron(&type_registry).unwrap());
+
     // TODO: save scene
 }
 
@@ -110,6 +126,7 @@ fn infotext_system(commands: &mut Commands, asset_server: Res<AssetServer>) {
                 color: Color::WHITE,
                 ..Default::default()
             },
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -61,6 +61,7 @@ fn setup(commands: &mut Commands, asset_server: Res<AssetServer>) {
                     font_size: 40.0,
                     ..Default::default()
                 },
+                ..Default::default()
             },
             style: Style {
                 position_type: PositionType::Absolute,
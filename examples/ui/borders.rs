@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+/// This example illustrates nodes with rounded corners and colored borders, drawn without images
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(commands: &mut Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    let panel = materials.add(Color::rgb(0.2, 0.25, 0.35).into());
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::SpaceAround,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // rounded corners without a border
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(200.0), Val::Px(150.0)),
+                        ..Default::default()
+                    },
+                    material: panel.clone(),
+                    ..Default::default()
+                })
+                .with(CornerRadius::all(24.0));
+            // a border with square corners
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(200.0), Val::Px(150.0)),
+                        border: Rect::all(Val::Px(4.0)),
+                        ..Default::default()
+                    },
+                    material: panel.clone(),
+                    ..Default::default()
+                })
+                .with(BorderColor(Color::rgb(0.9, 0.7, 0.2)));
+            // a border with rounded corners of different sizes
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(200.0), Val::Px(150.0)),
+                        border: Rect {
+                            left: Val::Px(2.0),
+                            right: Val::Px(2.0),
+                            top: Val::Px(8.0),
+                            bottom: Val::Px(2.0),
+                        },
+                        ..Default::default()
+                    },
+                    material: panel,
+                    ..Default::default()
+                })
+                .with(CornerRadius {
+                    top_left: 40.0,
+                    top_right: 8.0,
+                    bottom_right: 40.0,
+                    bottom_left: 8.0,
+                })
+                .with(BorderColor(Color::rgb(0.3, 0.8, 0.9)));
+            // a circle
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(120.0), Val::Px(120.0)),
+                        border: Rect::all(Val::Px(6.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.8, 0.3, 0.3).into()),
+                    ..Default::default()
+                })
+                .with(CornerRadius::all(60.0))
+                .with(BorderColor(Color::WHITE));
+        });
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 /// This example illustrates how to create a button that changes color and text based on its interaction state.
+/// The colors come from the `ButtonStyle` of the button, and the text is changed by a system.
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
@@ -28,29 +29,17 @@ impl FromResources for ButtonMaterials {
 }
 
 fn button_system(
-    button_materials: Res<ButtonMaterials>,
-    mut interaction_query: Query<
-        (&Interaction, &mut Handle<ColorMaterial>, &Children),
-        (Mutated<Interaction>, With<Button>),
-    >,
+    mut interaction_query: Query<(&Interaction, &Children), (Mutated<Interaction>, With<Button>)>,
     mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, mut material, children) in interaction_query.iter_mut() {
+    for (interaction, children) in interaction_query.iter_mut() {
         let mut text = text_query.get_mut(children[0]).unwrap();
-        match *interaction {
-            Interaction::Clicked => {
-                text.value = "Press".to_string();
-                *material = button_materials.pressed.clone();
-            }
-            Interaction::Hovered => {
-                text.value = "Hover".to_string();
-                *material = button_materials.hovered.clone();
-            }
-            Interaction::None => {
-                text.value = "Button".to_string();
-                *material = button_materials.normal.clone();
-            }
+        text.value = match *interaction {
+            Interaction::Clicked => "Press",
+            Interaction::Hovered => "Hover",
+            Interaction::None => "Button",
         }
+        .to_string();
     }
 }
 
@@ -74,6 +63,21 @@ fn setup(
                 ..Default::default()
             },
             material: button_materials.normal.clone(),
+            button_style: ButtonStyle {
+                normal: ButtonStateStyle {
+                    material: Some(button_materials.normal.clone()),
+                    ..Default::default()
+                },
+                hovered: ButtonStateStyle {
+                    material: Some(button_materials.hovered.clone()),
+                    ..Default::default()
+                },
+                pressed: ButtonStateStyle {
+                    material: Some(button_materials.pressed.clone()),
+                    text_color: Some(Color::rgb(0.1, 0.1, 0.1)),
+                },
+                ..Default::default()
+            },
             ..Default::default()
         })
         .with_children(|parent| {
@@ -86,6 +90,7 @@ fn setup(
                         color: Color::rgb(0.9, 0.9, 0.9),
                         ..Default::default()
                     },
+                    ..Default::default()
                 },
                 ..Default::default()
             });
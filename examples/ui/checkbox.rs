@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+/// This example illustrates a checkbox and a toggle, which are flipped by clicking them.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(print_changes_system)
+        .run();
+}
+
+#[derive(Default)]
+struct ChangeState {
+    checkbox_event_reader: EventReader<CheckboxChanged>,
+    toggle_event_reader: EventReader<ToggleChanged>,
+}
+
+fn print_changes_system(
+    mut state: Local<ChangeState>,
+    checkbox_events: Res<Events<CheckboxChanged>>,
+    toggle_events: Res<Events<ToggleChanged>>,
+) {
+    for event in state.checkbox_event_reader.iter(&checkbox_events) {
+        println!("checkbox checked: {}", event.checked);
+    }
+    for event in state.toggle_event_reader.iter(&toggle_events) {
+        println!("toggle on: {}", event.on);
+    }
+}
+
+fn setup(commands: &mut Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    let background = materials.add(Color::rgb(0.15, 0.15, 0.15).into());
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                        margin: Rect::all(Val::Px(20.0)),
+                        ..Default::default()
+                    },
+                    material: background.clone(),
+                    ..Default::default()
+                })
+                .with(Checkbox { checked: true })
+                .spawn(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(80.0), Val::Px(40.0)),
+                        margin: Rect::all(Val::Px(20.0)),
+                        ..Default::default()
+                    },
+                    material: background,
+                    ..Default::default()
+                })
+                .with(Toggle::default());
+        });
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+/// This example illustrates the debug overlay: the frame time, a panel of values and a plot of
+/// recent frame times are shown every frame without building a ui tree.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(debug_system)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    debug_overlay.font = Some(asset_server.load("fonts/FiraMono-Medium.ttf"));
+    commands.spawn(UiCameraBundle::default());
+}
+
+fn debug_system(
+    mut frame_times: Local<Vec<f32>>,
+    time: Res<Time>,
+    windows: Res<Windows>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    let frame_time = time.delta_seconds() * 1000.0;
+    frame_times.push(frame_time);
+    if frame_times.len() > 100 {
+        frame_times.remove(0);
+    }
+
+    debug_overlay.text(format!("Frame time: {:.2} ms", frame_time));
+    if let Some(window) = windows.get_primary() {
+        debug_overlay
+            .panel("Window")
+            .value("width", window.width())
+            .value("height", window.height())
+            .value("scale factor", window.scale_factor());
+    }
+    debug_overlay.plot("Frame times (ms)", frame_times.iter().cloned());
+}
@@ -0,0 +1,147 @@
+use bevy::{prelude::*, ui::Drop};
+
+/// This example illustrates dragging: an inventory panel that is dragged around and stays in the
+/// window, and an item that is dragged between its slots.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(drop_item_system)
+        .run();
+}
+
+struct Item;
+
+#[derive(Default)]
+struct DropState {
+    drag_end_event_reader: EventReader<DragEnd>,
+    drop_event_reader: EventReader<Drop>,
+}
+
+fn drop_item_system(
+    commands: &mut Commands,
+    mut state: Local<DropState>,
+    drag_end_events: Res<Events<DragEnd>>,
+    drop_events: Res<Events<Drop>>,
+    mut item_query: Query<&mut Style, With<Item>>,
+    slot_query: Query<Option<&Children>, With<DropTarget>>,
+) {
+    // released items go back to the center of their slot
+    for event in state.drag_end_event_reader.iter(&drag_end_events) {
+        if let Ok(mut style) = item_query.get_mut(event.entity) {
+            style.position = Rect::default();
+        }
+    }
+    // unless they were dropped on an empty slot
+    for event in state.drop_event_reader.iter(&drop_events) {
+        if item_query.get_mut(event.entity).is_ok() {
+            if let Ok(None) = slot_query.get(event.target) {
+                commands.push_children(event.target, &[event.entity]);
+            }
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let slot = materials.add(Color::rgb(0.25, 0.25, 0.3).into());
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                // the panel is dragged by any part that isn't an item, and stays in the window
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(272.0), Val::Px(140.0)),
+                        position_type: PositionType::Absolute,
+                        position: Rect {
+                            left: Val::Px(50.0),
+                            bottom: Val::Px(50.0),
+                            ..Default::default()
+                        },
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        padding: Rect::all(Val::Px(8.0)),
+                        gap: Size::new(Val::Px(0.0), Val::Px(8.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+                    ..Default::default()
+                })
+                .with(Draggable {
+                    constrain_to_parent: true,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(TextBundle {
+                            text: Text {
+                                value: "Inventory".to_string(),
+                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                style: TextStyle {
+                                    font_size: 30.0,
+                                    color: Color::WHITE,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .spawn(NodeBundle {
+                            style: Style {
+                                gap: Size::new(Val::Px(8.0), Val::Px(0.0)),
+                                ..Default::default()
+                            },
+                            material: materials.add(Color::NONE.into()),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            for index in 0..4 {
+                                parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Px(58.0), Val::Px(58.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..Default::default()
+                                        },
+                                        material: slot.clone(),
+                                        ..Default::default()
+                                    })
+                                    .with(DropTarget);
+                                if index == 0 {
+                                    parent.with_children(|parent| {
+                                        parent
+                                            .spawn(NodeBundle {
+                                                style: Style {
+                                                    size: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                                                    ..Default::default()
+                                                },
+                                                material: materials
+                                                    .add(Color::rgb(0.8, 0.6, 0.2).into()),
+                                                ..Default::default()
+                                            })
+                                            .with(Item)
+                                            .with(Draggable::default())
+                                            // the item is drawn above the other slots
+                                            .with(ZIndex::Global(1));
+                                    });
+                                }
+                            }
+                        });
+                });
+        });
+}
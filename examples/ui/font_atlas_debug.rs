@@ -86,6 +86,7 @@ fn setup(commands: &mut Commands, asset_server: Res<AssetServer>, mut state: Res
                 color: Color::WHITE,
                 ..Default::default()
             },
+            ..Default::default()
         },
         ..Default::default()
     });
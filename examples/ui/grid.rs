@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+/// This example illustrates a grid layout: an inventory with a header spanning all columns, a
+/// large item spanning two rows and columns, and items filling the free cells.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let slot = materials.add(Color::rgb(0.25, 0.25, 0.3).into());
+    let large_slot = materials.add(Color::rgb(0.35, 0.3, 0.2).into());
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                // the inventory has five columns of 64 pixels, a header row, and rows of 64 pixels
+                // added for the items
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        size: Size::new(Val::Px(368.0), Val::Px(272.0)),
+                        padding: Rect::all(Val::Px(8.0)),
+                        grid_template_columns: vec![GridTrack::Fr(1.0); 5],
+                        grid_template_rows: vec![GridTrack::Px(40.0)],
+                        grid_auto_rows: GridTrack::Px(64.0),
+                        gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                grid_column: GridPlacement::span(5),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            material: materials.add(Color::NONE.into()),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle {
+                                text: Text {
+                                    value: "Inventory".to_string(),
+                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    style: TextStyle {
+                                        font_size: 30.0,
+                                        color: Color::WHITE,
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            });
+                        })
+                        // the large item is in the last two columns of the first two item rows
+                        .spawn(NodeBundle {
+                            style: Style {
+                                grid_column: GridPlacement::start_span(3, 2),
+                                grid_row: GridPlacement::start_span(1, 2),
+                                ..Default::default()
+                            },
+                            material: large_slot,
+                            ..Default::default()
+                        });
+                    for _ in 0..11 {
+                        parent.spawn(NodeBundle {
+                            material: slot.clone(),
+                            ..Default::default()
+                        });
+                    }
+                });
+        });
+}
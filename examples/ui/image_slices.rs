@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+/// This example illustrates nine-slice images: the frame of the text box keeps its size while
+/// the panels stretch to very different sizes.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let text_box = materials.add(
+        asset_server
+            .load("textures/rpg/ui/generic-rpg-ui-text-box.png")
+            .into(),
+    );
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (width, height, tile) in [
+                (150.0, 100.0, false),
+                (400.0, 300.0, false),
+                (400.0, 300.0, true),
+            ]
+            .iter()
+            {
+                parent.spawn(ImageSlicesBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(*width), Val::Px(*height)),
+                        margin: Rect::all(Val::Px(20.0)),
+                        ..Default::default()
+                    },
+                    slices: ImageSlices {
+                        tile: *tile,
+                        ..ImageSlices::all(6.0)
+                    },
+                    material: text_box.clone(),
+                    ..Default::default()
+                });
+            }
+        });
+}
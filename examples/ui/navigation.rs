@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+/// This example illustrates a menu that is used without a mouse. Tab, the arrow keys and the
+/// directional pad of gamepads move the focus between the buttons, and space, enter and the south
+/// button of gamepads activate the focused button.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(activation_system)
+        .run();
+}
+
+#[derive(Default)]
+struct ActivationState {
+    activated_event_reader: EventReader<Activated>,
+}
+
+fn activation_system(
+    mut state: Local<ActivationState>,
+    activated_events: Res<Events<Activated>>,
+    button_query: Query<&Children, With<Button>>,
+    text_query: Query<&Text>,
+) {
+    for event in state.activated_event_reader.iter(&activated_events) {
+        if let Ok(children) = button_query.get(event.entity) {
+            if let Ok(text) = text_query.get(children[0]) {
+                println!("activated {}", text.value);
+            }
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let normal = materials.add(Color::rgb(0.15, 0.15, 0.15).into());
+    let focused = materials.add(Color::rgb(0.35, 0.55, 0.75).into());
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node, with the buttons from top to bottom
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(0.0), Val::Px(10.0)),
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for label in &["Play", "Options", "Quit"] {
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(200.0), Val::Px(50.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        material: normal.clone(),
+                        // the focused button is highlighted
+                        button_style: ButtonStyle {
+                            normal: ButtonStateStyle {
+                                material: Some(normal.clone()),
+                                ..Default::default()
+                            },
+                            focused: ButtonStateStyle {
+                                material: Some(focused.clone()),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            text: Text {
+                                value: label.to_string(),
+                                font: font.clone(),
+                                style: TextStyle {
+                                    font_size: 30.0,
+                                    color: Color::WHITE,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        });
+                    });
+            }
+        });
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+/// This example illustrates a list that is longer than its container. Scroll it with the mouse
+/// wheel, by dragging it on a touch screen, or by dragging the scrollbar thumb.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let item_material = materials.add(Color::rgb(0.25, 0.25, 0.25).into());
+
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // scroll container
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(400.0), Val::Px(400.0)),
+                        // list the items from top to bottom
+                        flex_direction: FlexDirection::ColumnReverse,
+                        overflow: Overflow::Scroll,
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+                    ..Default::default()
+                })
+                .with(ScrollPosition::default())
+                .with_children(|parent| {
+                    for i in 0..30 {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                                    // keep the items from shrinking to fit the container
+                                    flex_shrink: 0.0,
+                                    margin: Rect::all(Val::Px(4.0)),
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                material: item_material.clone(),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle {
+                                    style: Style {
+                                        margin: Rect::all(Val::Px(8.0)),
+                                        ..Default::default()
+                                    },
+                                    text: Text {
+                                        value: format!("Item {}", i),
+                                        font: font.clone(),
+                                        style: TextStyle {
+                                            font_size: 24.0,
+                                            color: Color::WHITE,
+                                            ..Default::default()
+                                        },
+                                        ..Default::default()
+                                    },
+                                    ..Default::default()
+                                });
+                            });
+                    }
+                });
+            let container = parent.current_entity().unwrap();
+
+            // scrollbar track
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(16.0), Val::Px(400.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.1, 0.1, 0.1).into()),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    // scrollbar thumb
+                    parent
+                        .spawn(ButtonBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                ..Default::default()
+                            },
+                            material: materials.add(Color::rgb(0.5, 0.5, 0.5).into()),
+                            ..Default::default()
+                        })
+                        .with(ScrollbarThumb {
+                            container,
+                            axis: ScrollAxis::Vertical,
+                        });
+                });
+        });
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+/// This example illustrates a slider. Drag it, or click it and use the arrow keys.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(slider_text_system)
+        .run();
+}
+
+struct SliderText;
+
+fn slider_text_system(
+    mut slider_event_reader: Local<EventReader<SliderChanged>>,
+    slider_events: Res<Events<SliderChanged>>,
+    mut text_query: Query<&mut Text, With<SliderText>>,
+) {
+    if let Some(event) = slider_event_reader.latest(&slider_events) {
+        for mut text in text_query.iter_mut() {
+            text.value = format!("Volume: {}", event.value);
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle {
+                    style: Style {
+                        margin: Rect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: "Volume: 50".to_string(),
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        style: TextStyle {
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with(SliderText);
+            // the slider track
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(300.0), Val::Px(20.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+                    ..Default::default()
+                })
+                .with(Slider {
+                    value: 50.0,
+                    min: 0.0,
+                    max: 100.0,
+                    step: Some(1.0),
+                });
+        });
+}
@@ -3,7 +3,8 @@ use bevy::{
     prelude::*,
 };
 
-/// This example illustrates how to create text and update it in a system. It displays the current FPS in the upper left hand corner.
+/// This example illustrates how to create text and update it in a system. It displays the current FPS in the upper left hand corner,
+/// with the value in its own color and font.
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
@@ -20,7 +21,7 @@ fn text_update_system(diagnostics: Res<Diagnostics>, mut query: Query<&mut Text,
     for mut text in query.iter_mut() {
         if let Some(fps) = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS) {
             if let Some(average) = fps.average() {
-                text.value = format!("FPS: {:.2}", average);
+                text.sections[0].value = format!("{:.2}", average);
             }
         }
     }
@@ -37,13 +38,19 @@ fn setup(commands: &mut Commands, asset_server: Res<AssetServer>) {
                 ..Default::default()
             },
             text: Text {
-                value: "FPS:".to_string(),
+                value: "FPS: ".to_string(),
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 style: TextStyle {
                     font_size: 60.0,
                     color: Color::WHITE,
                     ..Default::default()
                 },
+                sections: vec![TextSection {
+                    value: String::new(),
+                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                    font_size: 60.0,
+                    color: Color::GOLD,
+                }],
             },
             ..Default::default()
         })
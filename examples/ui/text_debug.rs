@@ -32,7 +32,9 @@ fn infotext_system(commands: &mut Commands, asset_server: Res<AssetServer>) {
                 font_size: 50.0,
                 color: Color::WHITE,
                 alignment: TextAlignment::default(),
+                ..Default::default()
             },
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -62,7 +64,9 @@ fn infotext_system(commands: &mut Commands, asset_server: Res<AssetServer>) {
                     horizontal: HorizontalAlign::Center,
                     vertical: VerticalAlign::Center,
                 },
+                ..Default::default()
             },
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -86,7 +90,9 @@ fn infotext_system(commands: &mut Commands, asset_server: Res<AssetServer>) {
                     font_size: 50.0,
                     color: Color::WHITE,
                     alignment: TextAlignment::default(),
+                    ..Default::default()
                 },
+                ..Default::default()
             },
             ..Default::default()
         })
@@ -114,7 +120,9 @@ fn infotext_system(commands: &mut Commands, asset_server: Res<AssetServer>) {
                 font_size: 50.0,
                 color: Color::WHITE,
                 alignment: TextAlignment::default(),
+                ..Default::default()
             },
+            ..Default::default()
         },
         ..Default::default()
     });
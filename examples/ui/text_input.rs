@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+/// This example illustrates text inputs. Click the input to focus it, then type and press enter.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(submit_system)
+        .run();
+}
+
+struct SubmittedText;
+
+fn submit_system(
+    mut submitted_event_reader: Local<EventReader<TextInputSubmitted>>,
+    submitted_events: Res<Events<TextInputSubmitted>>,
+    mut input_query: Query<&mut TextInput>,
+    mut text_query: Query<&mut Text, With<SubmittedText>>,
+) {
+    for event in submitted_event_reader.iter(&submitted_events) {
+        for mut text in text_query.iter_mut() {
+            text.value = format!("Submitted: {}", event.value);
+        }
+        // clear the input for the next line
+        if let Ok(mut text_input) = input_query.get_mut(event.entity) {
+            text_input.value.clear();
+            text_input.cursor = 0;
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextInputBundle {
+                style: Style {
+                    size: Size::new(Val::Px(400.0), Val::Px(40.0)),
+                    overflow: Overflow::Scroll,
+                    ..Default::default()
+                },
+                text_input: TextInput {
+                    value: "Type here".to_string(),
+                    font: font.clone(),
+                    style: TextStyle {
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+                ..Default::default()
+            });
+            parent
+                .spawn(TextBundle {
+                    style: Style {
+                        margin: Rect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    text: Text {
+                        value: "Submitted:".to_string(),
+                        font,
+                        style: TextStyle {
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with(SubmittedText);
+        });
+}
@@ -0,0 +1,80 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+/// This example illustrates a tooltip that appears next to the cursor after it hovered a button
+/// for a moment.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let tooltip = commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // the tooltip is a root node, drawn above the rest of the ui. it lets the cursor pass, so
+        // that the button stays hovered when the tooltip is pushed under the cursor at the edges
+        // of the window
+        .spawn(NodeBundle {
+            style: Style {
+                padding: Rect::all(Val::Px(6.0)),
+                ..Default::default()
+            },
+            material: materials.add(Color::rgb(0.95, 0.9, 0.7).into()),
+            ..Default::default()
+        })
+        .with(ZIndex::Global(1))
+        .with(FocusPolicy::Pass)
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Saves the game to the last slot".to_string(),
+                    font: font.clone(),
+                    style: TextStyle {
+                        font_size: 20.0,
+                        color: Color::rgb(0.1, 0.1, 0.1),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        })
+        .current_entity()
+        .unwrap();
+
+    commands
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(150.0), Val::Px(65.0)),
+                margin: Rect::all(Val::Auto),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+            ..Default::default()
+        })
+        .with(Tooltip::new(tooltip).with_delay(0.6))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Save".to_string(),
+                    font,
+                    style: TextStyle {
+                        font_size: 40.0,
+                        color: Color::rgb(0.9, 0.9, 0.9),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        });
+}
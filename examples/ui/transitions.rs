@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+/// This example illustrates transitions: a panel slides out and fades when space is pressed, and
+/// slides back in and flashes when space is pressed again.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(toggle_panel_system)
+        .add_system(flash_panel_system)
+        .run();
+}
+
+struct Panel {
+    open: bool,
+}
+
+fn toggle_panel_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<(&mut Panel, &mut Transitions)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for (mut panel, mut transitions) in query.iter_mut() {
+        panel.open = !panel.open;
+        let (left, opacity) = if panel.open {
+            (Val::Px(20.0), 1.0)
+        } else {
+            (Val::Px(-300.0), 0.0)
+        };
+        transitions.start(Transition::new(
+            TransitionTarget::Position(Rect {
+                left,
+                bottom: Val::Px(20.0),
+                ..Default::default()
+            }),
+            0.5,
+            Easing::BackOut,
+        ));
+        transitions.start(Transition::new(
+            TransitionTarget::Opacity(opacity),
+            0.3,
+            Easing::Linear,
+        ));
+    }
+}
+
+#[derive(Default)]
+struct FlashState {
+    finished_event_reader: EventReader<TransitionFinished>,
+}
+
+fn flash_panel_system(
+    mut state: Local<FlashState>,
+    finished_events: Res<Events<TransitionFinished>>,
+    mut query: Query<(&Panel, &mut Transitions)>,
+) {
+    for event in state.finished_event_reader.iter(&finished_events) {
+        if let TransitionTarget::Position(_) = event.target {
+            if let Ok((panel, mut transitions)) = query.get_mut(event.entity) {
+                if panel.open {
+                    // the panel lights up, then goes back to its color
+                    transitions.start(Transition::new(
+                        TransitionTarget::Color(Color::rgb(0.4, 0.5, 0.7)),
+                        0.1,
+                        Easing::QuadraticOut,
+                    ));
+                }
+            }
+        } else if let TransitionTarget::Color(color) = event.target {
+            if color != Color::rgb(0.2, 0.25, 0.35) {
+                if let Ok((_panel, mut transitions)) = query.get_mut(event.entity) {
+                    transitions.start(Transition::new(
+                        TransitionTarget::Color(Color::rgb(0.2, 0.25, 0.35)),
+                        0.4,
+                        Easing::QuadraticIn,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(260.0), Val::Px(160.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::rgb(0.2, 0.25, 0.35).into()),
+            ..Default::default()
+        })
+        .with(Panel { open: true })
+        .with(Transitions::default())
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Press space".to_string(),
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    style: TextStyle {
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        });
+}
@@ -65,6 +65,7 @@ fn setup(
                                         color: Color::WHITE,
                                         ..Default::default()
                                     },
+                                    ..Default::default()
                                 },
                                 ..Default::default()
                             });
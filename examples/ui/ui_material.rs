@@ -0,0 +1,130 @@
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        pipeline::{PipelineDescriptor, RenderPipeline},
+        render_graph::RenderGraph,
+        renderer::RenderResources,
+        shader::ShaderStage,
+    },
+    ui::{build_ui_material_pipeline, UiRenderGraphBuilder},
+};
+
+/// This example illustrates ui nodes drawn with a material and a fragment shader of their own: a
+/// radial cooldown indicator that fills up every few seconds
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_asset::<CooldownMaterial>()
+        .add_startup_system(setup)
+        .add_system(cooldown_system)
+        .run();
+}
+
+#[derive(RenderResources, Default, TypeUuid)]
+#[uuid = "5f1d5b0c-3c8e-4bb2-9d0e-6a4c2f7e8b91"]
+struct CooldownMaterial {
+    pub color: Color,
+    pub background: Color,
+    pub progress: f32,
+}
+
+/// The duration of the cooldown in seconds
+struct Cooldown(f64);
+
+const FRAGMENT_SHADER: &str = r#"
+#version 450
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec2 v_Position;
+layout(location = 2) in vec4 v_Clip;
+layout(location = 3) in float v_Opacity;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 2, binding = 0) uniform CooldownMaterial_color {
+    vec4 Color;
+};
+layout(set = 2, binding = 1) uniform CooldownMaterial_background {
+    vec4 Background;
+};
+layout(set = 2, binding = 2) uniform CooldownMaterial_progress {
+    float Progress;
+};
+
+void main() {
+    if (any(lessThan(v_Position, v_Clip.xy)) || any(greaterThan(v_Position, v_Clip.zw))) {
+        discard;
+    }
+    vec2 offset = v_Uv - vec2(0.5);
+    if (length(offset) > 0.5) {
+        discard;
+    }
+    // the fraction of a turn from the top, going clockwise
+    float turn = fract(atan(offset.x, -offset.y) / 6.2831853 + 1.0);
+    vec4 color = turn < Progress ? Color : Background;
+    color.a *= v_Opacity;
+    o_Target = color;
+}
+"#;
+
+fn cooldown_system(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<CooldownMaterial>>,
+    query: Query<(&Cooldown, &Handle<CooldownMaterial>)>,
+) {
+    for (cooldown, handle) in query.iter() {
+        if let Some(material) = materials.get_mut(handle) {
+            material.progress = (time.seconds_since_startup() / cooldown.0).fract() as f32;
+        }
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut shaders: ResMut<Assets<Shader>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut cooldown_materials: ResMut<Assets<CooldownMaterial>>,
+    mut render_graph: ResMut<RenderGraph>,
+) {
+    let fragment = shaders.add(Shader::from_glsl(ShaderStage::Fragment, FRAGMENT_SHADER));
+    let pipeline = pipelines.add(build_ui_material_pipeline(&mut shaders, fragment));
+    render_graph.add_ui_material::<CooldownMaterial>("cooldown_material");
+
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // root node
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (index, duration) in [1.0, 2.0, 3.0].iter().enumerate() {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(100.0), Val::Px(100.0)),
+                            margin: Rect::all(Val::Px(20.0)),
+                            ..Default::default()
+                        },
+                        render_pipelines: RenderPipelines::from_pipelines(vec![
+                            RenderPipeline::new(pipeline.clone()),
+                        ]),
+                        ..Default::default()
+                    })
+                    .with(cooldown_materials.add(CooldownMaterial {
+                        color: Color::rgb(0.2 + index as f32 * 0.3, 0.7, 0.4),
+                        background: Color::rgba(0.1, 0.1, 0.1, 0.8),
+                        progress: 0.0,
+                    }))
+                    .with(Cooldown(*duration));
+            }
+        });
+}
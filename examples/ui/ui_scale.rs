@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+/// This example illustrates scaling the whole ui, like a "ui size" setting. Press up and down to
+/// change the scale.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(change_scale_system)
+        .run();
+}
+
+struct ScaleText;
+
+fn change_scale_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut ui_scale: ResMut<UiScale>,
+    mut text_query: Query<&mut Text, With<ScaleText>>,
+) {
+    let scale = if keyboard_input.just_pressed(KeyCode::Up) {
+        ui_scale.scale + 0.25
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        ui_scale.scale - 0.25
+    } else {
+        return;
+    };
+    ui_scale.scale = scale.max(0.5).min(3.0);
+    for mut text in text_query.iter_mut() {
+        text.value = format!("Ui scale: {:.2}", ui_scale.scale);
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let button = materials.add(Color::rgb(0.3, 0.4, 0.6).into());
+    commands
+        // ui camera
+        .spawn(UiCameraBundle::default())
+        // a panel in the bottom left corner, which grows with the scale
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(220.0), Val::Px(120.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.0),
+                    bottom: Val::Px(10.0),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "Ui scale: 1.00".to_string(),
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        style: TextStyle {
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with(ScaleText);
+        })
+        // a button on the right, which stays clickable at any scale
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(120.0), Val::Px(50.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: button.clone(),
+            ..Default::default()
+        })
+        .with(ButtonStyle {
+            normal: ButtonStateStyle {
+                material: Some(button),
+                ..Default::default()
+            },
+            hovered: ButtonStateStyle {
+                material: Some(materials.add(Color::rgb(0.4, 0.5, 0.7).into())),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+}
@@ -0,0 +1,138 @@
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::{camera::Camera, render_graph::RenderGraph},
+    ui::UiRenderGraphBuilder,
+};
+
+/// This example illustrates a ui drawn into a texture instead of the window, and shown on the
+/// screen of a computer monitor in the 3d world.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(uptime_system)
+        .run();
+}
+
+const MONITOR_CAMERA: &str = "monitor";
+
+// the texture is created by the render graph, so it doesn't belong to the texture assets
+const MONITOR_TEXTURE: Handle<Texture> =
+    Handle::weak_from_u64(Texture::TYPE_UUID, 13378939762009864029);
+
+struct UptimeText;
+
+fn uptime_system(time: Res<Time>, mut query: Query<&mut Text, With<UptimeText>>) {
+    for mut text in query.iter_mut() {
+        text.value = format!("Uptime: {:.1}s", time.seconds_since_startup());
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    msaa: Res<Msaa>,
+    mut render_graph: ResMut<RenderGraph>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    render_graph.add_ui_texture_graph(MONITOR_CAMERA, &msaa);
+
+    commands
+        // plane
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(5.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        // light
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        // camera
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-1.5, 2.0, 4.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::unit_y()),
+            ..Default::default()
+        })
+        // monitor case
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(2.2, 1.5, 0.2))),
+            material: materials.add(Color::rgb(0.2, 0.2, 0.2).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 0.0)),
+            ..Default::default()
+        })
+        // monitor screen, showing the ui texture
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2::new(2.0, 1.25)))),
+            material: materials.add(StandardMaterial {
+                albedo_texture: Some(MONITOR_TEXTURE),
+                shaded: false,
+                ..Default::default()
+            }),
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 0.101)),
+            ..Default::default()
+        })
+        // the ui camera of the monitor draws into a texture with the aspect ratio of the screen
+        .spawn(UiCameraBundle {
+            camera: Camera {
+                name: Some(MONITOR_CAMERA.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with(UiTextureCamera {
+            texture: MONITOR_TEXTURE,
+            size: Size::new(512.0, 320.0),
+        })
+        // the ui of the monitor fills its texture
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                gap: Size::new(Val::Px(0.0), Val::Px(16.0)),
+                ..Default::default()
+            },
+            material: color_materials.add(Color::rgb(0.05, 0.1, 0.25).into()),
+            ..Default::default()
+        })
+        .with(UiTexture {
+            camera: MONITOR_CAMERA.to_string(),
+        })
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+            parent
+                .spawn(TextBundle {
+                    text: Text {
+                        value: "Bevy OS".to_string(),
+                        font: font.clone(),
+                        style: TextStyle {
+                            font_size: 60.0,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .spawn(TextBundle {
+                    text: Text {
+                        font,
+                        style: TextStyle {
+                            font_size: 30.0,
+                            color: Color::rgb(0.6, 0.8, 1.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with(UptimeText);
+        });
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+/// This example illustrates a name tag with a health bar that follows a moving cube in the 3d
+/// world and always faces the camera. Its text is drawn from distance fields to stay sharp.
+fn main() {
+    App::build()
+        .add_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(move_cube_system)
+        .run();
+}
+
+struct MovingCube;
+
+fn move_cube_system(time: Res<Time>, mut query: Query<&mut Transform, With<MovingCube>>) {
+    let angle = time.seconds_since_startup() as f32;
+    for mut transform in query.iter_mut() {
+        transform.translation = Vec3::new(angle.cos() * 1.5, 0.5, angle.sin() * 1.5);
+    }
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let cube = commands
+        // plane
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::new(5.0))),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        // light
+        .spawn(LightBundle {
+            transform: Transform::from_translation(Vec3::new(4.0, 8.0, 4.0)),
+            ..Default::default()
+        })
+        // camera
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-2.0, 2.5, 5.0))
+                .looking_at(Vec3::default(), Vec3::unit_y()),
+            ..Default::default()
+        })
+        // cube
+        .spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            ..Default::default()
+        })
+        .with(MovingCube)
+        .current_entity()
+        .unwrap();
+
+    // the name tag floats above the cube
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(160.0), Val::Px(60.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceAround,
+                ..Default::default()
+            },
+            material: color_materials.add(Color::rgba(0.1, 0.1, 0.1, 0.8).into()),
+            ..Default::default()
+        })
+        .with(WorldSpaceUi {
+            target: Some(cube),
+            offset: Vec3::new(0.0, 1.0, 0.0),
+            scale: 0.005,
+            billboard: true,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    value: "Cube".to_string(),
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    style: TextStyle {
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                        // distance field glyphs stay crisp when the name tag is scaled
+                        rendering: TextRendering::Sdf,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            // health bar
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(140.0), Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    material: color_materials.add(Color::rgb(0.4, 0.1, 0.1).into()),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(70.0), Val::Percent(100.0)),
+                            ..Default::default()
+                        },
+                        material: color_materials.add(Color::rgb(0.2, 0.8, 0.2).into()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
@@ -9,6 +9,7 @@ fn main() {
     App::build()
         .add_resource(AssetServerSettings {
             asset_folder: "/".to_string(),
+            cache_database: Some("assets_wasm".to_string()),
         })
         .add_plugins(DefaultPlugins)
         .add_asset::<RustSourceCode>()
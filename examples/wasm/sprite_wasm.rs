@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+/// Renders a sprite to a canvas with the WebGL2 backend. Build it with
+/// `cargo build --example sprite_wasm --target wasm32-unknown-unknown --no-default-features --features bevy_winit,bevy_webgl2,render,png`
+fn main() {
+    App::build()
+        .add_resource(WindowDescriptor {
+            width: 600,
+            height: 400,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(rotate)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let texture_handle = asset_server.load("branding/icon.png");
+    commands
+        .spawn(Camera2dBundle::default())
+        .spawn(SpriteBundle {
+            material: materials.add(texture_handle.into()),
+            ..Default::default()
+        });
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Sprite>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_z(time.delta_seconds()));
+    }
+}
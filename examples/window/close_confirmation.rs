@@ -0,0 +1,52 @@
+use bevy::{
+    prelude::*,
+    window::{WindowCloseRequested, WindowPlugin},
+};
+
+/// This example asks for a confirmation before the window is closed. Closing the window with its
+/// "close" button only asks to close it, and Y or N then answer.
+fn main() {
+    App::build()
+        .add_plugins_with(DefaultPlugins, |group| group.disable::<WindowPlugin>())
+        .add_plugin(WindowPlugin {
+            // the window stays open when its close is requested, until it is closed with
+            // `Window::close`
+            close_when_requested: false,
+            ..Default::default()
+        })
+        .add_system(confirm_close)
+        .run();
+}
+
+#[derive(Default)]
+struct CloseConfirmation {
+    close_requested_event_reader: EventReader<WindowCloseRequested>,
+    pending: bool,
+}
+
+fn confirm_close(
+    mut state: Local<CloseConfirmation>,
+    close_requested_events: Res<Events<WindowCloseRequested>>,
+    input: Res<Input<KeyCode>>,
+    mut windows: ResMut<Windows>,
+) {
+    if state
+        .close_requested_event_reader
+        .latest(&close_requested_events)
+        .is_some()
+        && !state.pending
+    {
+        state.pending = true;
+        println!("Close the window? Press Y to close it or N to keep it open");
+    }
+    if !state.pending {
+        return;
+    }
+    if input.just_pressed(KeyCode::Y) {
+        // closing the primary window exits the app
+        windows.get_primary_mut().unwrap().close();
+    } else if input.just_pressed(KeyCode::N) {
+        state.pending = false;
+        println!("The window stays open");
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to change the cursor of the system to one of its standard icons,
+/// and how to replace it with an image
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(change_cursor)
+        .run();
+}
+
+fn setup(commands: &mut Commands) {
+    // the custom cursor image is drawn by the ui
+    commands.spawn(CameraUiBundle::default());
+}
+
+const ICONS: [CursorIcon; 6] = [
+    CursorIcon::Default,
+    CursorIcon::Hand,
+    CursorIcon::Text,
+    CursorIcon::Crosshair,
+    CursorIcon::EwResize,
+    CursorIcon::NsResize,
+];
+
+/// Space goes through standard icons, and I toggles a custom image with its hotspot at the tip of
+/// the rod
+fn change_cursor(
+    input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut windows: ResMut<Windows>,
+    mut custom_cursor: ResMut<CustomCursor>,
+    mut icon_index: Local<usize>,
+) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::Space) {
+        *icon_index = (*icon_index + 1) % ICONS.len();
+        window.set_cursor_icon(ICONS[*icon_index]);
+        println!("cursor icon: {:?}", ICONS[*icon_index]);
+    }
+    if input.just_pressed(KeyCode::I) {
+        if custom_cursor.image.is_some() {
+            custom_cursor.image = None;
+        } else {
+            custom_cursor.image = Some(asset_server.load("textures/rpg/props/generic-rpg-rod.png"));
+            custom_cursor.hotspot = Vec2::new(7.0, 0.0);
+        }
+    }
+}
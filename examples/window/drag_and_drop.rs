@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+/// This example prints the files that are dragged over the window and dropped on it
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_system(file_drag_and_drop_system)
+        .run();
+}
+
+#[derive(Default)]
+struct State {
+    event_reader: EventReader<FileDragAndDrop>,
+}
+
+fn file_drag_and_drop_system(
+    mut state: Local<State>,
+    file_drag_and_drop_events: Res<Events<FileDragAndDrop>>,
+) {
+    for event in state.event_reader.iter(&file_drag_and_drop_events) {
+        match event {
+            FileDragAndDrop::HoveredFile {
+                path_buf, position, ..
+            } => println!("hovering {} at {:?}", path_buf.display(), position),
+            FileDragAndDrop::DroppedFile {
+                path_buf, position, ..
+            } => println!("dropped {} at {:?}", path_buf.display(), position),
+            FileDragAndDrop::HoveredFileCancelled { .. } => println!("drag cancelled"),
+        }
+    }
+}
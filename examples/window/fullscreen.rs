@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+
+/// This example lists the monitors and their video modes, and switches the window between
+/// windowed, borderless fullscreen and exclusive fullscreen like a settings menu would
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(list_monitors)
+        .add_system(change_mode)
+        .run();
+}
+
+fn list_monitors(monitors: Res<Monitors>) {
+    for (index, monitor) in monitors.iter().enumerate() {
+        println!(
+            "monitor {}: {} ({}x{}){}",
+            index,
+            monitor.name.as_deref().unwrap_or("unknown"),
+            monitor.width,
+            monitor.height,
+            if monitors.primary_index() == Some(index) {
+                " primary"
+            } else {
+                ""
+            }
+        );
+        for video_mode in monitor.video_modes.iter() {
+            println!(
+                "  {}x{} {}Hz",
+                video_mode.width, video_mode.height, video_mode.refresh_rate
+            );
+        }
+    }
+    println!(
+        "W: windowed, B: borderless, E: next video mode in exclusive fullscreen, M: next monitor"
+    );
+}
+
+#[derive(Default)]
+struct Selection {
+    monitor: usize,
+    video_mode: usize,
+}
+
+fn change_mode(
+    input: Res<Input<KeyCode>>,
+    monitors: Res<Monitors>,
+    mut windows: ResMut<Windows>,
+    mut selection: Local<Selection>,
+) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::W) {
+        window.set_mode(WindowMode::Windowed);
+    }
+    if input.just_pressed(KeyCode::B) {
+        window.set_mode(WindowMode::BorderlessFullscreen);
+    }
+    if input.just_pressed(KeyCode::M) && !monitors.is_empty() {
+        selection.monitor = (selection.monitor + 1) % monitors.len();
+        selection.video_mode = 0;
+        window.set_monitor(MonitorSelection::Index(selection.monitor));
+    }
+    if input.just_pressed(KeyCode::E) {
+        let video_modes = match monitors.get(selection.monitor) {
+            Some(monitor) if !monitor.video_modes.is_empty() => &monitor.video_modes,
+            _ => return,
+        };
+        let video_mode = video_modes[selection.video_mode % video_modes.len()];
+        selection.video_mode += 1;
+        println!(
+            "{}x{} {}Hz",
+            video_mode.width, video_mode.height, video_mode.refresh_rate
+        );
+        window.set_monitor(MonitorSelection::Index(selection.monitor));
+        window.set_mode(WindowMode::ExclusiveFullscreen { video_mode });
+    }
+}
@@ -1,13 +1,9 @@
 use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput},
     prelude::*,
     render::{
         camera::{ActiveCameras, Camera},
-        pass::*,
-        render_graph::{
-            base::MainPass, CameraNode, PassNode, RenderGraph, WindowSwapChainNode,
-            WindowTextureNode,
-        },
-        texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
+        render_graph::{base::BaseRenderGraphBuilder, RenderGraph},
     },
     window::{CreateWindow, WindowDescriptor, WindowId},
 };
@@ -18,6 +14,7 @@ fn main() {
         .add_resource(Msaa { samples: 4 })
         .add_plugins(DefaultPlugins)
         .add_startup_system(setup)
+        .add_system(focused_window_system)
         .run();
 }
 
@@ -43,113 +40,10 @@ fn setup(
         },
     });
 
-    // here we setup our render graph to draw our second camera to the new window's swap chain
-
-    // add a swapchain node for our new window
-    render_graph.add_node(
-        "second_window_swap_chain",
-        WindowSwapChainNode::new(window_id),
-    );
-
-    // add a new depth texture node for our new window
-    render_graph.add_node(
-        "second_window_depth_texture",
-        WindowTextureNode::new(
-            window_id,
-            TextureDescriptor {
-                format: TextureFormat::Depth32Float,
-                usage: TextureUsage::OUTPUT_ATTACHMENT,
-                sample_count: msaa.samples,
-                ..Default::default()
-            },
-        ),
-    );
-
-    // add a new camera node for our new window
-    render_graph.add_system_node("secondary_camera", CameraNode::new("Secondary"));
-
-    // add a new render pass for our new window / camera
-    let mut second_window_pass = PassNode::<&MainPass>::new(PassDescriptor {
-        color_attachments: vec![msaa.color_attachment_descriptor(
-            TextureAttachment::Input("color_attachment".to_string()),
-            TextureAttachment::Input("color_resolve_target".to_string()),
-            Operations {
-                load: LoadOp::Clear(Color::rgb(0.5, 0.5, 0.8)),
-                store: true,
-            },
-        )],
-        depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
-            attachment: TextureAttachment::Input("depth".to_string()),
-            depth_ops: Some(Operations {
-                load: LoadOp::Clear(1.0),
-                store: true,
-            }),
-            stencil_ops: None,
-        }),
-        sample_count: msaa.samples,
-    });
-
-    second_window_pass.add_camera("Secondary");
+    // draw the camera named "Secondary" to the new window's swap chain
+    render_graph.add_window_pass(window_id, "Secondary", Color::rgb(0.5, 0.5, 0.8), &msaa);
     active_cameras.add("Secondary");
 
-    render_graph.add_node("second_window_pass", second_window_pass);
-
-    render_graph
-        .add_slot_edge(
-            "second_window_swap_chain",
-            WindowSwapChainNode::OUT_TEXTURE,
-            "second_window_pass",
-            if msaa.samples > 1 {
-                "color_resolve_target"
-            } else {
-                "color_attachment"
-            },
-        )
-        .unwrap();
-
-    render_graph
-        .add_slot_edge(
-            "second_window_depth_texture",
-            WindowTextureNode::OUT_TEXTURE,
-            "second_window_pass",
-            "depth",
-        )
-        .unwrap();
-
-    render_graph
-        .add_node_edge("secondary_camera", "second_window_pass")
-        .unwrap();
-
-    if msaa.samples > 1 {
-        render_graph.add_node(
-            "second_multi_sampled_color_attachment",
-            WindowTextureNode::new(
-                window_id,
-                TextureDescriptor {
-                    size: Extent3d {
-                        depth: 1,
-                        width: 1,
-                        height: 1,
-                    },
-                    mip_level_count: 1,
-                    sample_count: msaa.samples,
-                    dimension: TextureDimension::D2,
-                    format: TextureFormat::default(),
-                    usage: TextureUsage::OUTPUT_ATTACHMENT,
-                },
-            ),
-        );
-
-        render_graph
-            .add_slot_edge(
-                "second_multi_sampled_color_attachment",
-                WindowSwapChainNode::OUT_TEXTURE,
-                "second_window_pass",
-                "color_attachment",
-            )
-            .unwrap();
-    }
-
     // SETUP SCENE
 
     // add entities to the world
@@ -178,3 +72,28 @@ fn setup(
             ..Default::default()
         });
 }
+
+/// Input events carry the id of the window they were received in, so each window can react to
+/// its own input
+fn focused_window_system(
+    windows: Res<Windows>,
+    mut keyboard_input_reader: Local<EventReader<KeyboardInput>>,
+    keyboard_input_events: Res<Events<KeyboardInput>>,
+    mut mouse_button_input_reader: Local<EventReader<MouseButtonInput>>,
+    mouse_button_input_events: Res<Events<MouseButtonInput>>,
+) {
+    for event in keyboard_input_reader.iter(&keyboard_input_events) {
+        if event.key_code == Some(KeyCode::Space) && event.state.is_pressed() {
+            if let Some(window) = windows.get(event.window_id) {
+                println!("space pressed in {}", window.title());
+            }
+        }
+    }
+    for event in mouse_button_input_reader.iter(&mouse_button_input_events) {
+        if event.button == MouseButton::Left && event.state.is_pressed() {
+            if let Some(window) = windows.get(event.window_id) {
+                println!("clicked in {}", window.title());
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+/// This example shows a window without decorations, that the desktop can be seen through. D
+/// toggles the decorations.
+fn main() {
+    App::build()
+        .add_resource(ClearColor(Color::NONE))
+        .add_resource(WindowDescriptor {
+            width: 400,
+            height: 400,
+            transparent: true,
+            decorations: false,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(toggle_decorations)
+        .run();
+}
+
+fn setup(
+    commands: &mut Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let texture_handle = asset_server.load("branding/icon.png");
+    commands
+        .spawn(Camera2dBundle::default())
+        .spawn(SpriteBundle {
+            material: materials.add(texture_handle.into()),
+            ..Default::default()
+        });
+}
+
+fn toggle_decorations(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::D) {
+        window.set_decorations(!window.decorations());
+    }
+}
@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::WindowId};
 
 /// This example illustrates how to customize the default window settings
 fn main() {
@@ -12,11 +12,19 @@ fn main() {
             ..Default::default()
         })
         .add_plugins(DefaultPlugins)
+        .add_startup_system(set_icon)
         .add_system(change_title)
         .add_system(toggle_cursor)
+        .add_system(cycle_present_mode)
+        .add_system(toggle_always_on_top)
         .run();
 }
 
+/// This system sets the icon of the window from an image, once it is loaded
+fn set_icon(asset_server: Res<AssetServer>, mut window_icons: ResMut<WindowIcons>) {
+    window_icons.set(WindowId::primary(), asset_server.load("branding/icon.png"));
+}
+
 /// This system will then change the title during execution
 fn change_title(time: Res<Time>, mut windows: ResMut<Windows>) {
     let window = windows.get_primary_mut().unwrap();
@@ -26,11 +34,47 @@ fn change_title(time: Res<Time>, mut windows: ResMut<Windows>) {
     ));
 }
 
-/// This system toggles the cursor's visibility when the space bar is pressed
+/// This system toggles the cursor's visibility when the space bar is pressed, and locks it in the
+/// center of the window like first-person controls when L is pressed
 fn toggle_cursor(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
     let window = windows.get_primary_mut().unwrap();
     if input.just_pressed(KeyCode::Space) {
         window.set_cursor_lock_mode(!window.cursor_locked());
         window.set_cursor_visibility(!window.cursor_visible());
     }
+    if input.just_pressed(KeyCode::L) {
+        let locked = window.cursor_grab_mode() == CursorGrabMode::Locked;
+        window.set_cursor_grab_mode(if locked {
+            CursorGrabMode::None
+        } else {
+            CursorGrabMode::Locked
+        });
+        window.set_cursor_visibility(locked);
+    }
+}
+
+/// This system changes how frames are presented when V is pressed, like a vsync option would
+fn cycle_present_mode(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::V) {
+        let present_mode = match window.present_mode() {
+            PresentMode::Immediate => PresentMode::Mailbox,
+            PresentMode::Mailbox => PresentMode::Fifo,
+            PresentMode::Fifo => PresentMode::Immediate,
+        };
+        println!("present mode: {:?}", present_mode);
+        window.set_present_mode(present_mode);
+    }
+}
+
+/// This system keeps the window above the others while T is toggled on, and moves it to the top
+/// left corner of the desktop when P is pressed
+fn toggle_always_on_top(input: Res<Input<KeyCode>>, mut windows: ResMut<Windows>) {
+    let window = windows.get_primary_mut().unwrap();
+    if input.just_pressed(KeyCode::T) {
+        window.set_always_on_top(!window.always_on_top());
+    }
+    if input.just_pressed(KeyCode::P) {
+        window.set_position(0, 0);
+    }
 }
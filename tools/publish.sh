@@ -16,13 +16,16 @@ crates=(
     bevy_core
     bevy_diagnostic
     bevy_transform
+    bevy_animation
     bevy_window
     bevy_render
     bevy_input
     bevy_gilrs
     bevy_pbr
+    bevy_picking
     bevy_gltf
     bevy_scene
+    bevy_mesh_loaders
     bevy_sprite
     bevy_text
     bevy_ui